@@ -120,6 +120,7 @@ export enum FuncBackendKind {
   Object = "Object",
   ResourcePayloadToValue = "ResourcePayloadToValue",
   String = "String",
+  StringTemplate = "StringTemplate",
   Unset = "Unset",
   Validation = "Validation",
 }
@@ -52,6 +52,11 @@ export type UpdateComponentNameArgs = {
   name: string;
 };
 
+export type UpdateComponentMetadataArgs = {
+  labels: Record<string, string>;
+  owner: string | null;
+};
+
 export type UpdateComponentManageArgs = {
   componentId: string;
 };
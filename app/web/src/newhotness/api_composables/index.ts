@@ -59,6 +59,7 @@ export enum routes {
   MgmtFuncGetLatest = "MgmtFuncGetLatest",
   UpdateComponentAttributes = "UpdateComponentAttributes",
   UpdateComponentManage = "UpdateComponentManage",
+  UpdateComponentMetadata = "UpdateComponentMetadata",
   UpdateComponentName = "UpdateComponentName",
   UpdateView = "UpdateView",
   UpgradeComponents = "UpgradeComponents",
@@ -134,6 +135,7 @@ const _routes: Record<routes, string> = {
   SetDefaultSubscriptionSource: "/components/<id>/attributes/default_source",
   UpdateComponentAttributes: "/components/<id>/attributes",
   UpdateComponentManage: "/components/<id>/manage",
+  UpdateComponentMetadata: "/components/<id>/metadata",
   UpdateComponentName: "/components/<id>/name",
   UpdateView: "/views/<viewId>",
   UpgradeComponents: "/components/upgrade",
@@ -38,6 +38,8 @@ export function useComponentDeletion(viewId?: string, skipNavigation = false) {
       toDelete: component.toDelete,
       resourceId: null,
       hasSocketConnections: false,
+      labels: component.labels,
+      owner: component.owner,
     };
   };
 
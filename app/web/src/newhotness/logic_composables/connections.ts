@@ -1,7 +1,7 @@
 import { computed, inject, ref } from "vue";
 import { useQuery } from "@tanstack/vue-query";
 import { ComponentId } from "@/api/sdf/dal/component";
-import { Connection, EntityKind, IncomingConnections } from "@/workers/types/entity_kind_types";
+import { ComponentConnection, Connection, EntityKind, IncomingConnections } from "@/workers/types/entity_kind_types";
 import { bifrost, getOutgoingConnections, useMakeArgs, useMakeKey } from "@/store/realtime/heimdall";
 import { assertIsDefined, Context } from "../types";
 import { SimpleConnection } from "../layout_components/ConnectionLayout.vue";
@@ -54,15 +54,18 @@ export const useConnections = () => {
       });
 
       // TODO(nick): decide if this needs to be an inner computed or not.
-      const outgoingConnections = computed<Connection[]>(() => {
+      const outgoingConnections = computed<ComponentConnection[]>(() => {
         if (!allOutgoingQuery.data.value) return [];
         const mine = allOutgoingQuery.data.value.get(componentId);
         if (!mine) return [];
         return Object.values(mine);
       });
 
-      const incoming: SimpleConnection[] = incomingConnections.value.map((conn) => {
-        if (conn.kind === "management") {
+      // Variable connections have no source component to link to, so they are not listed here.
+      const incoming: SimpleConnection[] = incomingConnections.value.flatMap((conn) => {
+        if (conn.kind === "variable") {
+          return [];
+        } else if (conn.kind === "management") {
           // FIXME(nick,jobelenus): we should split the connection type into two now that
           // management connections have their own MV.
           return {
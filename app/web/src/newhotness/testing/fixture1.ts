@@ -24,6 +24,8 @@ export const component: ComponentInList = {
   diffStatus: "Modified",
   toDelete: false,
   hasSocketConnections: false,
+  labels: {},
+  owner: null,
 };
 export const attributeTree: AttributeTree = {
   id: "01K3GTCTNVYW3NGHP33G5F31XD",
@@ -31,7 +31,7 @@ import {
   ConnStatusFn,
 } from "@/workers/types/dbinterface";
 import {
-  Connection,
+  ComponentConnection,
   EntityKind,
   GLOBAL_IDENTIFIER,
   GlobalEntity,
@@ -617,7 +617,7 @@ export const getOutgoingConnections = async (args: { workspaceId: WorkspacePk; c
 
   const connectionsById = await db.getOutgoingConnectionsByComponentId(args.workspaceId, args.changeSetId);
   if (connectionsById) return reactive(connectionsById);
-  return new DefaultMap<string, Record<string, Connection>>(() => ({}));
+  return new DefaultMap<string, Record<string, ComponentConnection>>(() => ({}));
 };
 
 export const getIncomingManagement = async (args: { workspaceId: WorkspacePk; changeSetId: ChangeSetId }) => {
@@ -625,7 +625,7 @@ export const getIncomingManagement = async (args: { workspaceId: WorkspacePk; ch
 
   const connectionsById = await db.getIncomingManagementByComponentId(args.workspaceId, args.changeSetId);
   if (connectionsById) return reactive(connectionsById);
-  return new DefaultMap<string, Record<string, Connection>>(() => ({}));
+  return new DefaultMap<string, Record<string, ComponentConnection>>(() => ({}));
 };
 
 const waitForInitCompletion = (): Promise<void> => {
@@ -14,7 +14,7 @@ import { DefaultMap } from "@/utils/defaultmap";
 import { ComponentId } from "@/api/sdf/dal/component";
 import { WorkspacePk } from "@/api/sdf/dal/workspace";
 import { ViewId } from "@/api/sdf/dal/views";
-import { Connection, DefaultSubscriptions, EntityKind, GlobalEntity, PossibleConnection } from "./entity_kind_types";
+import { ComponentConnection, DefaultSubscriptions, EntityKind, GlobalEntity, PossibleConnection } from "./entity_kind_types";
 
 export type Column = string;
 export type Columns = Column[];
@@ -26,9 +26,9 @@ export type BustCacheFn = (
   noBroadcast?: boolean,
 ) => void;
 
-export type OutgoingConnections = DefaultMap<ComponentId, Record<string, Connection>>;
+export type OutgoingConnections = DefaultMap<ComponentId, Record<string, ComponentConnection>>;
 
-export type IncomingManagementConnections = DefaultMap<string, Record<string, Connection>>;
+export type IncomingManagementConnections = DefaultMap<string, Record<string, ComponentConnection>>;
 
 export type ConnStatusFn = (workspaceId: string, connected: boolean, noBroadcast?: boolean) => void;
 
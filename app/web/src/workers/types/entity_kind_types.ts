@@ -307,6 +307,8 @@ export interface BifrostComponent {
     diff?: null | string;
   };
   toDelete: boolean;
+  labels: Record<string, string>;
+  owner: string | null;
 }
 
 export type ComponentDiffStatus = "Added" | "None" | "Modified" | "Removed";
@@ -497,6 +499,8 @@ export interface ComponentInList {
   toDelete: boolean;
   resourceId: string | null;
   hasSocketConnections: boolean;
+  labels: Record<string, string>;
+  owner: string | null;
 }
 
 export interface BifrostComponentList {
@@ -606,7 +610,7 @@ export interface IncomingConnectionsList {
 // NOTE: these are OUTGOING
 export interface ManagementConnections {
   id: ComponentId;
-  connections: Connection[];
+  connections: ComponentConnection[];
 }
 
 export interface IncomingConnections {
@@ -619,6 +623,8 @@ export interface BifrostIncomingConnections {
   connections: Connection[];
 }
 
+export type VariableId = string;
+
 // FIXME(nick,jobelenus): we should split the connection type into two now that management
 // connections have their own MV.
 export type Connection =
@@ -639,8 +645,21 @@ export type Connection =
       toPropPath: string;
       toAttributeValueId: AttributeValueId;
       toAttributeValuePath: string;
+    }
+  | {
+      kind: "variable";
+      variableId: VariableId;
+      variableName: string;
+      toComponentId: ComponentId;
+      toPropId: PropId;
+      toPropPath: string;
+      toAttributeValueId: AttributeValueId;
+      toAttributeValuePath: string;
     };
 
+// Component-to-component connections. Variable connections have no source component.
+export type ComponentConnection = Exclude<Connection, { kind: "variable" }>;
+
 export interface SecretFormDataView {
   name: string;
   kind: string;
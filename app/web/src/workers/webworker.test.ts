@@ -590,12 +590,12 @@ const fullDiagnosticTest = async (db: Comlink.Remote<TabDBInterface>) => {
     incoming.connections[0]?.toComponentId === thingComponent,
     `thing ID is wrong ${incoming.connections[0]?.toComponentId}`,
   );
-  assert(
-    incoming.connections[0]?.fromComponentId === regionComponent,
-    `region component ID is wrong ${incoming.connections[0]?.fromComponentId}`,
-  );
   assert(incoming.connections[0]?.kind === "prop", "not a prop");
   if (incoming.connections[0]?.kind === "prop") {
+    assert(
+      incoming.connections[0]?.fromComponentId === regionComponent,
+      `region component ID is wrong ${incoming.connections[0]?.fromComponentId}`,
+    );
     assert(
       incoming.connections[0]?.toAttributeValuePath === "/domain/extra/Region",
       `path is not extra/Region: ${incoming.connections[0]?.toAttributeValuePath}`,
@@ -71,7 +71,7 @@ import {
 } from "./types/dbinterface";
 import {
   BifrostComponent,
-  Connection,
+  ComponentConnection,
   EddaComponent,
   IncomingConnections,
   EntityKind,
@@ -2602,12 +2602,12 @@ const possibleConns = new DefaultMap<ChangeSetId, DefaultMap<ComponentId, Record
 );
 
 // the `string` is `${toAttributeValueId}-${fromAttributeValueId}`
-const allOutgoingConns = new DefaultMap<ChangeSetId, DefaultMap<ComponentId, Record<string, Connection>>>(
+const allOutgoingConns = new DefaultMap<ChangeSetId, DefaultMap<ComponentId, Record<string, ComponentConnection>>>(
   () => new DefaultMap(() => ({})),
 );
 
 // the `string` is `${toComponentId}-${fromComponentId}`
-const allIncomingMgmt = new DefaultMap<ChangeSetId, DefaultMap<ComponentId, Record<string, Connection>>>(
+const allIncomingMgmt = new DefaultMap<ChangeSetId, DefaultMap<ComponentId, Record<string, ComponentConnection>>>(
   () => new DefaultMap(() => ({})),
 );
 
@@ -2773,7 +2773,7 @@ const postProcess = async (
       }
     } else {
       data.connections.forEach((outgoing) => {
-        if (outgoing.kind !== "prop") {
+        if (outgoing.kind === "management") {
           const id = `${outgoing.toComponentId}-${outgoing.fromComponentId}`;
           const incoming = flip(outgoing);
           const conns = allIncomingMgmt.get(changeSetId).get(outgoing.toComponentId);
@@ -2803,7 +2803,8 @@ const postProcess = async (
       }
     } else {
       data.connections.forEach((incoming) => {
-        if (incoming.kind !== "management") {
+        // Variable connections have no source component, so there is nothing outgoing to track.
+        if (incoming.kind === "prop") {
           const id = `${incoming.toAttributeValueId}-${incoming.fromAttributeValueId}`;
           const outgoing = flip(incoming);
           const conns = allOutgoingConns.get(changeSetId).get(incoming.fromComponentId);
@@ -3011,8 +3012,8 @@ const getComponentsInOnlyOneView = async (
   return result;
 };
 
-const flip = (i: Connection): Connection => {
-  const o: Connection = {
+const flip = (i: ComponentConnection): ComponentConnection => {
+  const o: ComponentConnection = {
     ...i,
     fromComponentId: i.toComponentId,
     toComponentId: i.fromComponentId,
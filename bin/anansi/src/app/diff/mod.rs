@@ -0,0 +1,142 @@
+mod model;
+mod state;
+mod ui;
+
+use std::time::Duration;
+
+use color_eyre::Result;
+use crossterm::event::{
+    self,
+    Event,
+    KeyCode,
+    KeyEvent,
+    KeyModifiers,
+};
+use dal::{
+    WorkspaceSnapshotGraph,
+    workspace_snapshot::graph::RebaseBatch,
+};
+use model::{
+    apply_rebase_batch,
+    compute_snapshot_diff,
+};
+use state::{
+    DiffFocus,
+    DiffState,
+};
+
+use super::terminal::{
+    restore_terminal,
+    setup_terminal,
+};
+
+/// Side-by-side view of the differences between two snapshots
+pub struct DiffApp {
+    state: DiffState,
+}
+
+impl DiffApp {
+    /// Diffs the `base` snapshot against the `updated` snapshot
+    pub fn new(
+        base: &WorkspaceSnapshotGraph,
+        updated: &WorkspaceSnapshotGraph,
+        base_label: String,
+        updated_label: String,
+    ) -> Result<Self> {
+        let entries = compute_snapshot_diff(base, updated)?;
+        Ok(Self {
+            state: DiffState::new(base_label, updated_label, entries),
+        })
+    }
+
+    /// Diffs the `base` snapshot against the result of applying `rebase_batch` to it
+    pub fn from_rebase_batch(
+        base: &WorkspaceSnapshotGraph,
+        rebase_batch: &RebaseBatch,
+        base_label: String,
+        updated_label: String,
+    ) -> Result<Self> {
+        let updated = apply_rebase_batch(base, rebase_batch)?;
+        Self::new(base, &updated, base_label, updated_label)
+    }
+
+    pub async fn run(&mut self) -> Result<()> {
+        let mut terminal = setup_terminal()?;
+
+        while !self.state.should_quit {
+            terminal.draw(|f| {
+                self.state.frame_size = f.area();
+                ui::render(f, &self.state);
+            })?;
+
+            if event::poll(Duration::from_millis(100))? {
+                if let Event::Key(key) = event::read()? {
+                    self.handle_key_event(key);
+                }
+            }
+        }
+
+        restore_terminal(terminal)?;
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if self.state.filter_mode {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter => {
+                    self.state.filter_mode = false;
+                }
+                KeyCode::Backspace => {
+                    self.state.filter_text.pop();
+                    self.state.update_filter();
+                }
+                KeyCode::Char(c) => {
+                    self.state.filter_text.push(c);
+                    self.state.update_filter();
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        let page = self.state.frame_size.height.saturating_sub(8).max(1);
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.state.should_quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.state.should_quit = true;
+            }
+            KeyCode::Tab | KeyCode::BackTab => self.state.toggle_focus(),
+            KeyCode::Char('/') => self.state.filter_mode = true,
+            KeyCode::Char('f') => self.state.cycle_kind_filter(true),
+            KeyCode::Char('F') => self.state.cycle_kind_filter(false),
+            KeyCode::Char('c') => self.state.cycle_change_filter(),
+            KeyCode::Char('t') => self.state.cycle_target_filter(),
+            code => match self.state.focus {
+                DiffFocus::DiffList => match code {
+                    KeyCode::Down | KeyCode::Char('j') => self.state.select_next(),
+                    KeyCode::Up | KeyCode::Char('k') => self.state.select_previous(),
+                    KeyCode::PageDown => self.state.page_down(page as usize),
+                    KeyCode::PageUp => self.state.page_up(page as usize),
+                    _ => {}
+                },
+                DiffFocus::Details => match code {
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        self.state.details_scroll = self.state.details_scroll.saturating_add(1);
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        self.state.details_scroll = self.state.details_scroll.saturating_sub(1);
+                    }
+                    KeyCode::PageDown => {
+                        self.state.details_scroll = self.state.details_scroll.saturating_add(page);
+                    }
+                    KeyCode::PageUp => {
+                        self.state.details_scroll = self.state.details_scroll.saturating_sub(page);
+                    }
+                    KeyCode::Home => self.state.details_scroll = 0,
+                    _ => {}
+                },
+            },
+        }
+    }
+}
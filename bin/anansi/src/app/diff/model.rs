@@ -0,0 +1,396 @@
+use std::collections::HashSet;
+
+use color_eyre::{
+    Result,
+    eyre::eyre,
+};
+use dal::{
+    EdgeWeightKindDiscriminants,
+    Ulid,
+    WorkspaceSnapshotGraph,
+    workspace_snapshot::{
+        NodeInformation,
+        edge_weight::EdgeWeight,
+        graph::{
+            RebaseBatch,
+            detector::{
+                Detector,
+                Update,
+            },
+        },
+        node_weight::{
+            NodeWeight,
+            NodeWeightDiscriminants,
+        },
+    },
+};
+
+use crate::app::helpers::extract_node_name;
+
+/// Whether something was added, removed or changed between the base and updated snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    pub const ALL: &'static [ChangeKind] =
+        &[ChangeKind::Added, ChangeKind::Removed, ChangeKind::Changed];
+
+    /// Single character marker used in the diff list
+    pub fn marker(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "-",
+            ChangeKind::Changed => "~",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "Added",
+            ChangeKind::Removed => "Removed",
+            ChangeKind::Changed => "Changed",
+        }
+    }
+}
+
+/// A single difference between two snapshots
+#[derive(Debug, Clone)]
+pub enum DiffEntry {
+    NodeAdded {
+        updated: NodeWeight,
+    },
+    NodeRemoved {
+        base: NodeWeight,
+    },
+    NodeChanged {
+        base: NodeWeight,
+        updated: NodeWeight,
+    },
+    EdgeAdded {
+        source: NodeInformation,
+        destination: NodeInformation,
+        edge_weight: EdgeWeight,
+    },
+    EdgeRemoved {
+        source: NodeInformation,
+        destination: NodeInformation,
+        edge_kind: EdgeWeightKindDiscriminants,
+    },
+}
+
+impl DiffEntry {
+    pub fn change_kind(&self) -> ChangeKind {
+        match self {
+            DiffEntry::NodeAdded { .. } | DiffEntry::EdgeAdded { .. } => ChangeKind::Added,
+            DiffEntry::NodeRemoved { .. } | DiffEntry::EdgeRemoved { .. } => ChangeKind::Removed,
+            DiffEntry::NodeChanged { .. } => ChangeKind::Changed,
+        }
+    }
+
+    pub fn is_edge(&self) -> bool {
+        matches!(
+            self,
+            DiffEntry::EdgeAdded { .. } | DiffEntry::EdgeRemoved { .. }
+        )
+    }
+
+    /// The id of the node, or of the source node for edges
+    pub fn node_id(&self) -> Ulid {
+        match self {
+            DiffEntry::NodeAdded { updated } | DiffEntry::NodeChanged { updated, .. } => {
+                updated.id()
+            }
+            DiffEntry::NodeRemoved { base } => base.id(),
+            DiffEntry::EdgeAdded { source, .. } | DiffEntry::EdgeRemoved { source, .. } => {
+                source.id.into()
+            }
+        }
+    }
+
+    /// The kind of the node, or of the source node for edges
+    pub fn node_weight_kind(&self) -> NodeWeightDiscriminants {
+        match self {
+            DiffEntry::NodeAdded { updated } | DiffEntry::NodeChanged { updated, .. } => {
+                updated.into()
+            }
+            DiffEntry::NodeRemoved { base } => base.into(),
+            DiffEntry::EdgeAdded { source, .. } | DiffEntry::EdgeRemoved { source, .. } => {
+                source.node_weight_kind
+            }
+        }
+    }
+
+    /// A short description of the entry for the diff list: the node name, or the edge kind and
+    /// destination for edges
+    pub fn summary(&self) -> String {
+        match self {
+            DiffEntry::NodeAdded { updated } | DiffEntry::NodeChanged { updated, .. } => {
+                extract_node_name(updated).unwrap_or_default()
+            }
+            DiffEntry::NodeRemoved { base } => extract_node_name(base).unwrap_or_default(),
+            DiffEntry::EdgeAdded {
+                destination,
+                edge_weight,
+                ..
+            } => format!(
+                "{:?} → {} {}",
+                EdgeWeightKindDiscriminants::from(edge_weight.kind()),
+                destination.node_weight_kind,
+                destination.id
+            ),
+            DiffEntry::EdgeRemoved {
+                destination,
+                edge_kind,
+                ..
+            } => format!(
+                "{edge_kind:?} → {} {}",
+                destination.node_weight_kind, destination.id
+            ),
+        }
+    }
+
+    /// The node weight as it is in the base snapshot, if it exists there
+    pub fn base_weight(&self) -> Option<&NodeWeight> {
+        match self {
+            DiffEntry::NodeRemoved { base } | DiffEntry::NodeChanged { base, .. } => Some(base),
+            _ => None,
+        }
+    }
+
+    /// The node weight as it is in the updated snapshot, if it exists there
+    pub fn updated_weight(&self) -> Option<&NodeWeight> {
+        match self {
+            DiffEntry::NodeAdded { updated } | DiffEntry::NodeChanged { updated, .. } => {
+                Some(updated)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Applies a rebase batch to a copy of the base snapshot, producing the snapshot the batch would
+/// result in
+pub fn apply_rebase_batch(
+    base: &WorkspaceSnapshotGraph,
+    rebase_batch: &RebaseBatch,
+) -> Result<WorkspaceSnapshotGraph> {
+    let mut updated = base.clone();
+    updated.perform_updates(rebase_batch.updates())?;
+    updated.cleanup_and_merkle_tree_hash()?;
+    Ok(updated)
+}
+
+/// Computes the node and edge differences between two snapshots using the graph [`Detector`].
+///
+/// The detector only walks the updated graph, so nodes that only exist in the base graph are
+/// reported as removed separately.
+pub fn compute_snapshot_diff(
+    base: &WorkspaceSnapshotGraph,
+    updated: &WorkspaceSnapshotGraph,
+) -> Result<Vec<DiffEntry>> {
+    // The detector relies on up to date merkle tree hashes and no orphaned nodes
+    let mut base = base.clone();
+    base.cleanup_and_merkle_tree_hash()?;
+    let mut updated = updated.clone();
+    updated.cleanup_and_merkle_tree_hash()?;
+
+    let mut entries = Vec::new();
+    for update in Detector::new(base.inner(), updated.inner()).detect_updates() {
+        let entry = match update {
+            Update::NewNode { node_weight } => DiffEntry::NodeAdded {
+                updated: node_weight,
+            },
+            Update::ReplaceNode { node_weight } => {
+                let base_weight = base
+                    .get_node_weight_by_id_opt(node_weight.id())
+                    .ok_or_else(|| {
+                        eyre!("replaced node {} not found in base graph", node_weight.id())
+                    })?
+                    .clone();
+                DiffEntry::NodeChanged {
+                    base: base_weight,
+                    updated: node_weight,
+                }
+            }
+            Update::NewEdge {
+                source,
+                destination,
+                edge_weight,
+            } => DiffEntry::EdgeAdded {
+                source,
+                destination,
+                edge_weight,
+            },
+            Update::RemoveEdge {
+                source,
+                destination,
+                edge_kind,
+            } => DiffEntry::EdgeRemoved {
+                source,
+                destination,
+                edge_kind,
+            },
+        };
+        entries.push(entry);
+    }
+
+    let updated_ids: HashSet<Ulid> = updated.all_node_ids();
+    for (node_weight, _) in base.nodes() {
+        if !updated_ids.contains(&node_weight.id()) {
+            entries.push(DiffEntry::NodeRemoved {
+                base: node_weight.clone(),
+            });
+        }
+    }
+
+    // Group the differences by node so that a node's changes and edges are listed together
+    entries.sort_by_key(|entry| (entry.node_id(), entry.is_edge()));
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use dal::{
+        PropKind,
+        workspace_snapshot::{
+            edge_weight::EdgeWeightKind,
+            graph::WorkspaceSnapshotGraphVCurrent,
+        },
+    };
+    use si_events::ContentHash;
+
+    use super::*;
+
+    fn create_test_graph() -> WorkspaceSnapshotGraph {
+        let inner = WorkspaceSnapshotGraphVCurrent::new_with_categories_only()
+            .expect("Unable to create WorkspaceSnapshotGraph");
+        WorkspaceSnapshotGraph::V4(inner)
+    }
+
+    fn add_prop_under_root(graph: &mut WorkspaceSnapshotGraph, name: &str) -> Ulid {
+        let id = graph.generate_ulid().expect("Unable to generate Ulid");
+        let lineage_id = graph.generate_ulid().expect("Unable to generate Ulid");
+        let prop = NodeWeight::new_prop(
+            id,
+            lineage_id,
+            PropKind::String,
+            name,
+            ContentHash::new(name.as_bytes()),
+        );
+        let prop_idx = graph.add_or_replace_node(prop).expect("add node");
+        graph
+            .add_edge(
+                graph.root(),
+                EdgeWeight::new(EdgeWeightKind::new_use()),
+                prop_idx,
+            )
+            .expect("add edge");
+        id
+    }
+
+    #[test]
+    fn test_identical_snapshots_have_no_diff() {
+        let mut graph = create_test_graph();
+        add_prop_under_root(&mut graph, "unchanged");
+
+        let entries = compute_snapshot_diff(&graph, &graph).expect("compute diff");
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_added_node_and_edge() {
+        let base = create_test_graph();
+        let mut updated = base.clone();
+        let prop_id = add_prop_under_root(&mut updated, "new_prop");
+
+        let entries = compute_snapshot_diff(&base, &updated).expect("compute diff");
+
+        assert!(entries.iter().any(|entry| matches!(
+            entry,
+            DiffEntry::NodeAdded { updated } if updated.id() == prop_id
+        )));
+        assert!(entries.iter().any(|entry| matches!(
+            entry,
+            DiffEntry::EdgeAdded { destination, .. }
+                if Ulid::from(destination.id) == prop_id
+        )));
+    }
+
+    #[test]
+    fn test_removed_node_and_edge() {
+        let mut base = create_test_graph();
+        let prop_id = add_prop_under_root(&mut base, "old_prop");
+        let updated = create_test_graph();
+
+        let entries = compute_snapshot_diff(&base, &updated).expect("compute diff");
+
+        assert!(entries.iter().any(|entry| matches!(
+            entry,
+            DiffEntry::NodeRemoved { base } if base.id() == prop_id
+        )));
+        assert!(entries.iter().any(|entry| matches!(
+            entry,
+            DiffEntry::EdgeRemoved { destination, .. }
+                if Ulid::from(destination.id) == prop_id
+        )));
+    }
+
+    #[test]
+    fn test_changed_node() {
+        let mut base = create_test_graph();
+        let prop_id = add_prop_under_root(&mut base, "prop");
+        let mut updated = base.clone();
+
+        let mut prop = updated
+            .get_node_weight_by_id(prop_id)
+            .expect("find prop")
+            .get_prop_node_weight()
+            .expect("prop node weight");
+        prop.set_name("renamed_prop");
+        updated
+            .add_or_replace_node(NodeWeight::Prop(prop))
+            .expect("replace node");
+
+        let entries = compute_snapshot_diff(&base, &updated).expect("compute diff");
+
+        let changed = entries
+            .iter()
+            .find(|entry| entry.change_kind() == ChangeKind::Changed && entry.node_id() == prop_id)
+            .expect("changed prop entry");
+        assert_eq!(
+            Some("prop".to_string()),
+            changed.base_weight().and_then(extract_node_name)
+        );
+        assert_eq!(
+            Some("renamed_prop".to_string()),
+            changed.updated_weight().and_then(extract_node_name)
+        );
+    }
+
+    #[test]
+    fn test_apply_rebase_batch() {
+        let base = create_test_graph();
+        let mut updated = base.clone();
+        let prop_id = add_prop_under_root(&mut updated, "batched_prop");
+        updated.cleanup_and_merkle_tree_hash().expect("cleanup");
+
+        let mut base_for_detector = base.clone();
+        base_for_detector
+            .cleanup_and_merkle_tree_hash()
+            .expect("cleanup");
+        let rebase_batch = RebaseBatch::new(
+            Detector::new(base_for_detector.inner(), updated.inner()).detect_updates(),
+        );
+
+        let rebased = apply_rebase_batch(&base, &rebase_batch).expect("apply rebase batch");
+        assert!(rebased.get_node_weight_by_id_opt(prop_id).is_some());
+
+        let entries = compute_snapshot_diff(&rebased, &updated).expect("compute diff");
+        assert!(entries.is_empty());
+    }
+}
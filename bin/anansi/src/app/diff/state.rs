@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+
+use dal::workspace_snapshot::node_weight::NodeWeightDiscriminants;
+use ratatui::layout::Rect;
+
+use super::model::{
+    ChangeKind,
+    DiffEntry,
+};
+
+/// Which panel has keyboard focus in diff mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffFocus {
+    DiffList,
+    Details,
+}
+
+/// Whether to show node differences, edge differences or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffTargetFilter {
+    All,
+    Nodes,
+    Edges,
+}
+
+impl DiffTargetFilter {
+    pub fn next(self) -> Self {
+        match self {
+            DiffTargetFilter::All => DiffTargetFilter::Nodes,
+            DiffTargetFilter::Nodes => DiffTargetFilter::Edges,
+            DiffTargetFilter::Edges => DiffTargetFilter::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffTargetFilter::All => "Nodes & Edges",
+            DiffTargetFilter::Nodes => "Nodes",
+            DiffTargetFilter::Edges => "Edges",
+        }
+    }
+
+    fn matches(&self, entry: &DiffEntry) -> bool {
+        match self {
+            DiffTargetFilter::All => true,
+            DiffTargetFilter::Nodes => !entry.is_edge(),
+            DiffTargetFilter::Edges => entry.is_edge(),
+        }
+    }
+}
+
+pub struct DiffState {
+    /// Label for the base side, usually the base snapshot file name
+    pub base_label: String,
+    /// Label for the updated side, the other snapshot or the rebase batch file name
+    pub updated_label: String,
+    pub entries: Vec<DiffEntry>,
+    /// Indices into `entries` that pass the active filters
+    pub filtered: Vec<usize>,
+    pub selected_index: usize,
+    pub scroll_offset: usize,
+    /// Node weight kinds present in the diff, used to cycle the kind filter
+    pub available_kinds: Vec<NodeWeightDiscriminants>,
+    pub kind_filter: Option<NodeWeightDiscriminants>,
+    pub change_filter: Option<ChangeKind>,
+    pub target_filter: DiffTargetFilter,
+    pub filter_text: String,
+    pub filter_mode: bool,
+    pub focus: DiffFocus,
+    /// Shared scroll offset for the base and updated panes so they stay aligned
+    pub details_scroll: u16,
+    pub should_quit: bool,
+    pub frame_size: Rect,
+}
+
+impl DiffState {
+    pub fn new(base_label: String, updated_label: String, entries: Vec<DiffEntry>) -> Self {
+        let mut available_kinds: Vec<NodeWeightDiscriminants> = entries
+            .iter()
+            .map(|entry| entry.node_weight_kind())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        available_kinds.sort_by_key(|kind| kind.to_string());
+
+        let mut state = Self {
+            base_label,
+            updated_label,
+            entries,
+            filtered: Vec::new(),
+            selected_index: 0,
+            scroll_offset: 0,
+            available_kinds,
+            kind_filter: None,
+            change_filter: None,
+            target_filter: DiffTargetFilter::All,
+            filter_text: String::new(),
+            filter_mode: false,
+            focus: DiffFocus::DiffList,
+            details_scroll: 0,
+            should_quit: false,
+            frame_size: Rect::default(),
+        };
+        state.update_filter();
+        state
+    }
+
+    pub fn selected_entry(&self) -> Option<&DiffEntry> {
+        self.filtered
+            .get(self.selected_index)
+            .and_then(|idx| self.entries.get(*idx))
+    }
+
+    /// Recomputes the filtered entries from the kind, change, target and text filters
+    pub fn update_filter(&mut self) {
+        let filter_lower = self.filter_text.to_lowercase();
+        self.filtered = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                self.kind_filter
+                    .is_none_or(|kind| entry.node_weight_kind() == kind)
+                    && self
+                        .change_filter
+                        .is_none_or(|change| entry.change_kind() == change)
+                    && self.target_filter.matches(entry)
+                    && (filter_lower.is_empty()
+                        || entry
+                            .node_id()
+                            .to_string()
+                            .to_lowercase()
+                            .contains(&filter_lower)
+                        || entry.summary().to_lowercase().contains(&filter_lower))
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+        self.details_scroll = 0;
+    }
+
+    /// Cycles the node weight kind filter forwards (or backwards), passing through "all kinds"
+    pub fn cycle_kind_filter(&mut self, forward: bool) {
+        let kinds = &self.available_kinds;
+        self.kind_filter = match self.kind_filter {
+            None if forward => kinds.first().copied(),
+            None => kinds.last().copied(),
+            Some(current) => {
+                let pos = kinds.iter().position(|kind| *kind == current);
+                match pos {
+                    Some(pos) if forward => kinds.get(pos + 1).copied(),
+                    Some(pos) if pos > 0 => kinds.get(pos - 1).copied(),
+                    _ => None,
+                }
+            }
+        };
+        self.update_filter();
+    }
+
+    pub fn cycle_change_filter(&mut self) {
+        self.change_filter = match self.change_filter {
+            None => ChangeKind::ALL.first().copied(),
+            Some(current) => {
+                let pos = ChangeKind::ALL.iter().position(|kind| *kind == current);
+                pos.and_then(|pos| ChangeKind::ALL.get(pos + 1).copied())
+            }
+        };
+        self.update_filter();
+    }
+
+    pub fn cycle_target_filter(&mut self) {
+        self.target_filter = self.target_filter.next();
+        self.update_filter();
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected_index + 1 < self.filtered.len() {
+            self.selected_index += 1;
+            self.details_scroll = 0;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.selected_index > 0 {
+            self.selected_index -= 1;
+            self.details_scroll = 0;
+        }
+    }
+
+    pub fn page_down(&mut self, amount: usize) {
+        if !self.filtered.is_empty() {
+            self.selected_index = (self.selected_index + amount).min(self.filtered.len() - 1);
+            self.details_scroll = 0;
+        }
+    }
+
+    pub fn page_up(&mut self, amount: usize) {
+        self.selected_index = self.selected_index.saturating_sub(amount);
+        self.details_scroll = 0;
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            DiffFocus::DiffList => DiffFocus::Details,
+            DiffFocus::Details => DiffFocus::DiffList,
+        };
+    }
+}
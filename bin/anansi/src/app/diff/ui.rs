@@ -0,0 +1,347 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    Frame,
+    layout::{
+        Constraint,
+        Direction,
+        Layout,
+        Rect,
+    },
+    style::{
+        Color,
+        Modifier,
+        Style,
+    },
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Block,
+        Borders,
+        Paragraph,
+        Row,
+        Table,
+    },
+};
+
+use super::{
+    model::{
+        ChangeKind,
+        DiffEntry,
+    },
+    state::{
+        DiffFocus,
+        DiffState,
+    },
+};
+use crate::app::ui::helpers::{
+    ScrollView,
+    truncate_string,
+};
+
+pub fn render(f: &mut Frame, state: &DiffState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Length(3), // Filters
+            Constraint::Min(0),    // Main content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(f.area());
+
+    render_title(f, chunks[0], state);
+    render_filters(f, chunks[1], state);
+
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[2]);
+
+    render_diff_list(f, main_chunks[0], state);
+    render_side_by_side(f, main_chunks[1], state);
+    render_status_bar(f, chunks[3], state);
+}
+
+fn render_title(f: &mut Frame, area: Rect, state: &DiffState) {
+    let title = Paragraph::new(format!(
+        "anansi diff - {} → {}",
+        state.base_label, state.updated_label
+    ))
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, area);
+}
+
+fn render_filters(f: &mut Frame, area: Rect, state: &DiffState) {
+    let label = Style::default().fg(Color::Yellow);
+    let kind = state
+        .kind_filter
+        .map(|kind| kind.to_string())
+        .unwrap_or_else(|| "All".to_string());
+    let change = state
+        .change_filter
+        .map(|change| change.label())
+        .unwrap_or("All");
+
+    let mut spans = vec![
+        Span::styled("Kind: ", label),
+        Span::raw(kind),
+        Span::raw("  "),
+        Span::styled("Change: ", label),
+        Span::raw(change),
+        Span::raw("  "),
+        Span::styled("Show: ", label),
+        Span::raw(state.target_filter.label()),
+        Span::raw("  "),
+        Span::styled("Filter: ", label),
+        Span::raw(state.filter_text.clone()),
+    ];
+    if state.filter_mode {
+        spans.push(Span::styled("_", Style::default().fg(Color::Cyan)));
+    }
+
+    let block = if state.filter_mode {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title("Filters")
+    } else {
+        Block::default().borders(Borders::ALL).title("Filters")
+    };
+
+    f.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+}
+
+fn change_color(change: ChangeKind) -> Color {
+    match change {
+        ChangeKind::Added => Color::Green,
+        ChangeKind::Removed => Color::Red,
+        ChangeKind::Changed => Color::Yellow,
+    }
+}
+
+fn render_diff_list(f: &mut Frame, area: Rect, state: &DiffState) {
+    let block = if state.focus == DiffFocus::DiffList {
+        Block::default().borders(Borders::ALL).border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Block::default().borders(Borders::ALL)
+    };
+
+    if state.filtered.is_empty() {
+        let empty_msg = if state.entries.is_empty() {
+            "Snapshots are identical"
+        } else {
+            "No differences match the filters"
+        };
+        f.render_widget(
+            Paragraph::new(empty_msg).block(block.title("Differences")),
+            area,
+        );
+        return;
+    }
+
+    let total_items = state.filtered.len();
+    // Visible height for data rows (subtract borders + header)
+    let visible_height = area.height.saturating_sub(4).max(1) as usize;
+    let selected_index = state.selected_index.min(total_items.saturating_sub(1));
+
+    // Ensure selected item is visible by adjusting scroll offset
+    let scroll_offset = if selected_index < state.scroll_offset {
+        selected_index
+    } else if selected_index >= state.scroll_offset + visible_height {
+        selected_index.saturating_sub(visible_height - 1)
+    } else {
+        state.scroll_offset
+    };
+
+    let start = scroll_offset.min(total_items.saturating_sub(1));
+    let end = (start + visible_height).min(total_items);
+
+    let rows: Vec<Row> = state.filtered[start..end]
+        .iter()
+        .enumerate()
+        .filter_map(|(i, idx)| {
+            let entry = state.entries.get(*idx)?;
+            let change = entry.change_kind();
+            let style = if start + i == selected_index {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default().fg(change_color(change))
+            };
+
+            Some(
+                Row::new(vec![
+                    change.marker().to_string(),
+                    if entry.is_edge() { "edge" } else { "node" }.to_string(),
+                    entry.node_id().to_string(),
+                    entry.node_weight_kind().to_string(),
+                    truncate_string(&entry.summary(), 60),
+                ])
+                .style(style),
+            )
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),  // Change marker
+            Constraint::Length(4),  // Node or edge
+            Constraint::Length(26), // Node ID
+            Constraint::Length(20), // Node weight kind
+            Constraint::Min(20),    // Name or edge summary
+        ],
+    )
+    .header(
+        Row::new(vec!["", "", "ID", "Node Weight Kind", "Name / Edge"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(block.title(format!(
+        "Differences ({}/{} | {} total)",
+        selected_index + 1,
+        total_items,
+        state.entries.len()
+    )));
+
+    f.render_widget(table, area);
+}
+
+/// The text shown in the base pane for an entry, if the entry exists in the base snapshot
+fn base_text(entry: &DiffEntry) -> Option<String> {
+    match entry {
+        DiffEntry::EdgeRemoved {
+            source,
+            destination,
+            edge_kind,
+        } => Some(format!(
+            "source: {source:#?}\ndestination: {destination:#?}\nedge_kind: {edge_kind:?}"
+        )),
+        _ => entry
+            .base_weight()
+            .map(|node_weight| format!("{node_weight:#?}")),
+    }
+}
+
+/// The text shown in the updated pane for an entry, if the entry exists in the updated snapshot
+fn updated_text(entry: &DiffEntry) -> Option<String> {
+    match entry {
+        DiffEntry::EdgeAdded {
+            source,
+            destination,
+            edge_weight,
+        } => Some(format!(
+            "source: {source:#?}\ndestination: {destination:#?}\nedge_weight: {edge_weight:#?}"
+        )),
+        _ => entry
+            .updated_weight()
+            .map(|node_weight| format!("{node_weight:#?}")),
+    }
+}
+
+/// Builds the lines for one side of the diff, highlighting lines that don't appear on the other
+/// side
+fn diff_lines(text: &str, other: Option<&str>, highlight: Color) -> Vec<Line<'static>> {
+    let other_lines: HashSet<&str> = other.map(|o| o.lines().collect()).unwrap_or_default();
+    text.lines()
+        .map(|line| {
+            if other.is_some() && other_lines.contains(line) {
+                Line::from(line.to_string())
+            } else {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(highlight),
+                ))
+            }
+        })
+        .collect()
+}
+
+fn render_side_by_side(f: &mut Frame, area: Rect, state: &DiffState) {
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let border_style = if state.focus == DiffFocus::Details {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+
+    let (base, updated) = match state.selected_entry() {
+        Some(entry) => (base_text(entry), updated_text(entry)),
+        None => (None, None),
+    };
+
+    let base_lines = match &base {
+        Some(text) => diff_lines(text, updated.as_deref(), Color::Red),
+        None => vec![Line::from(Span::styled(
+            "(not present)",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+    let updated_lines = match &updated {
+        Some(text) => diff_lines(text, base.as_deref(), Color::Green),
+        None => vec![Line::from(Span::styled(
+            "(not present)",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+
+    // Both panes share one scroll offset so matching lines stay side by side
+    let content_lines = base_lines.len().max(updated_lines.len());
+    let scroll = ScrollView::new(content_lines, panes[0], state.details_scroll).scroll_tuple();
+
+    let base_pane = Paragraph::new(base_lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(format!("Base ({})", state.base_label)),
+        )
+        .scroll(scroll);
+    let updated_pane = Paragraph::new(updated_lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(format!("Updated ({})", state.updated_label)),
+        )
+        .scroll(scroll);
+
+    f.render_widget(base_pane, panes[0]);
+    f.render_widget(updated_pane, panes[1]);
+}
+
+fn render_status_bar(f: &mut Frame, area: Rect, state: &DiffState) {
+    let text = if state.filter_mode {
+        "Enter: Apply filter | Esc: Cancel | Type to filter"
+    } else {
+        match state.focus {
+            DiffFocus::DiffList => {
+                "Tab: Panels | ↑/↓: Select | f/F: Kind | c: Change | t: Nodes/Edges | /: Filter | q: Quit"
+            }
+            DiffFocus::Details => {
+                "Tab: Panels | ↑/↓: Scroll | f/F: Kind | c: Change | t: Nodes/Edges | /: Filter | q: Quit"
+            }
+        }
+    };
+
+    f.render_widget(
+        Paragraph::new(Span::styled(text, Style::default().fg(Color::Gray))),
+        area,
+    );
+}
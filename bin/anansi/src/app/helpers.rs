@@ -50,6 +50,7 @@ pub const CATEGORY_KIND_OPTIONS: &[&str] = &[
     "DiagramObject",
     "DefaultSubscriptionSources",
     "Overlays",
+    "Variable",
 ];
 
 pub fn extract_node_name(node_weight: &NodeWeight) -> Option<String> {
@@ -358,6 +359,7 @@ pub fn parse_category_kind(value: &str) -> Result<CategoryNodeKind, &'static str
         "diagramobject" => Ok(CategoryNodeKind::DiagramObject),
         "defaultsubscriptionsources" => Ok(CategoryNodeKind::DefaultSubscriptionSources),
         "overlays" => Ok(CategoryNodeKind::Overlays),
+        "variable" => Ok(CategoryNodeKind::Variable),
         _ => Err("Invalid category kind"),
     }
 }
@@ -2,6 +2,7 @@ mod add_edge;
 mod delete;
 mod edit_modal;
 mod navigation;
+mod query;
 mod save;
 mod undo;
 
@@ -35,6 +36,7 @@ use super::{
         build_node_list,
         compute_snapshot_stats,
     },
+    split::SplitSnapshotInfo,
     state::{
         ActiveModal,
         AppState,
@@ -60,6 +62,13 @@ impl App {
         Ok(Self { state })
     }
 
+    /// Creates an app for a graph flattened from a split snapshot, keeping its subgraph layout
+    pub fn new_split(graph: WorkspaceSnapshotGraph, split_info: SplitSnapshotInfo) -> Result<Self> {
+        let mut app = Self::new(graph)?;
+        app.state.split_info = Some(split_info);
+        Ok(app)
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         let mut terminal = setup_terminal()?;
 
@@ -118,6 +127,11 @@ impl App {
             return Ok(());
         }
 
+        if self.state.query.input_mode {
+            self.handle_query_input_keys(key.code);
+            return Ok(());
+        }
+
         if self.state.node_list.filter_mode {
             match key.code {
                 KeyCode::Esc => {
@@ -164,6 +178,8 @@ impl App {
                     if !self.state.node_list.filter_text.is_empty() {
                         self.state.node_list.filter_text.clear();
                         self.state.node_list.update_filter();
+                    } else if self.state.query.active_query.is_some() {
+                        self.state.query.clear();
                     } else {
                         self.state.should_quit = true;
                     }
@@ -171,6 +187,15 @@ impl App {
                 KeyCode::Char('/') => {
                     self.state.node_list.filter_mode = true;
                 }
+                KeyCode::Char(':') => {
+                    self.state.query.input_mode = true;
+                }
+                KeyCode::Char('n') => {
+                    self.jump_to_query_match(true);
+                }
+                KeyCode::Char('N') => {
+                    self.jump_to_query_match(false);
+                }
                 KeyCode::Char('e') => {
                     self.open_edit_modal();
                 }
@@ -0,0 +1,104 @@
+use crossterm::event::KeyCode;
+
+use super::App;
+use crate::app::query::{
+    Query,
+    run_query,
+};
+
+impl App {
+    pub(super) fn handle_query_input_keys(&mut self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => {
+                self.state.query.input_mode = false;
+            }
+            KeyCode::Enter => {
+                self.state.query.input_mode = false;
+                self.run_query();
+            }
+            KeyCode::Backspace => {
+                self.state.query.input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.state.query.input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Runs the query in the input buffer and jumps to the first match
+    fn run_query(&mut self) {
+        let input = self.state.query.input.trim().to_string();
+        self.state.query.clear();
+        if input.is_empty() {
+            return;
+        }
+
+        let result = Query::parse(&input).and_then(|query| {
+            run_query(
+                &query,
+                &self.state.working_graph,
+                self.state.split_info.as_ref(),
+            )
+        });
+
+        match result {
+            Ok(result) => {
+                self.state.query.matches = result.matches;
+                self.state.query.notes = result.notes;
+                self.state.query.active_query = Some(input);
+                // Start from the top so the first match in list order is selected
+                self.state.node_list.selected_index = 0;
+                if !self.selected_matches_query() {
+                    self.jump_to_query_match(true);
+                }
+                self.adjust_scroll();
+            }
+            Err(err) => {
+                self.state.query.error = Some(err.to_string());
+            }
+        }
+    }
+
+    fn selected_matches_query(&self) -> bool {
+        self.state
+            .node_list
+            .filtered_node_list
+            .get(self.state.node_list.selected_index)
+            .is_some_and(|item| self.state.query.matches.contains(&item.node_id))
+    }
+
+    /// Selects the next (or previous) node in the list that matches the active query, wrapping
+    /// around at the ends of the list
+    pub(super) fn jump_to_query_match(&mut self, forward: bool) {
+        let list = &self.state.node_list.filtered_node_list;
+        if list.is_empty() || self.state.query.matches.is_empty() {
+            return;
+        }
+
+        let len = list.len();
+        let current = self.state.node_list.selected_index;
+        let found = (1..=len)
+            .map(|offset| {
+                if forward {
+                    (current + offset) % len
+                } else {
+                    (current + len - offset % len) % len
+                }
+            })
+            .find(|index| {
+                self.state
+                    .query
+                    .matches
+                    .contains(&self.state.node_list.filtered_node_list[*index].node_id)
+            });
+
+        if let Some(index) = found {
+            self.state.node_list.selected_index = index;
+            self.adjust_scroll();
+            self.state.edge_panel.selected_edge = 0;
+            self.state.edge_panel.scroll_offset = 0;
+            self.state.details.scroll_offset = 0;
+        }
+    }
+}
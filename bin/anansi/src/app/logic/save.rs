@@ -74,7 +74,12 @@ impl App {
         self.state.pending_edits.clear();
         self.state.original_graph = self.state.working_graph.clone();
 
-        self.state.success_message = Some("Graph saved successfully!".to_string());
+        // Split snapshots are edited in flattened form, so they're written back as legacy graphs
+        self.state.success_message = Some(if self.state.split_info.is_some() {
+            "Graph saved as a legacy snapshot!".to_string()
+        } else {
+            "Graph saved successfully!".to_string()
+        });
 
         Ok(())
     }
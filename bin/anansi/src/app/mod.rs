@@ -1,9 +1,13 @@
+pub mod diff;
 pub mod graph_edit;
 pub mod helpers;
 pub mod logic;
+pub mod query;
 pub mod splash;
+pub mod split;
 pub mod state;
 pub mod terminal;
 pub mod ui;
 
+pub use diff::DiffApp;
 pub use logic::App;
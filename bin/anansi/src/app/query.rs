@@ -0,0 +1,302 @@
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
+
+use color_eyre::{
+    Result,
+    eyre::eyre,
+};
+use dal::{
+    Ulid,
+    WorkspaceSnapshotGraph,
+    workspace_snapshot::{
+        content_address::ContentAddressDiscriminants,
+        edge_weight::EdgeWeightKind,
+        graph::{
+            NodeIndex,
+            validator::{
+                ValidationIssue,
+                validate_graph_with_text,
+            },
+        },
+        node_weight::NodeWeight,
+    },
+};
+use petgraph::{
+    Direction,
+    visit::EdgeRef,
+};
+
+use super::split::SplitSnapshotInfo;
+
+/// Usage shown when a query can't be parsed
+pub const QUERY_HELP: &str = "queries: components of schema <id> | attribute values with no prototype | orphaned nodes | invalid nodes | subgraph <n>";
+
+/// A query that can be run against the graph from the query prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// Attribute values that have no prototype of their own and whose prop has none either
+    AttributeValuesWithoutPrototype,
+    /// Components using the given schema or schema variant. Schema names live in the content
+    /// store rather than the graph, so the schema is identified by id.
+    ComponentsOfSchema(Ulid),
+    /// Nodes flagged by the graph validator
+    InvalidNodes,
+    /// Nodes other than the root that have no incoming edges
+    OrphanedNodes,
+    /// Nodes that live in the given subgraph of a split snapshot
+    SubGraph(usize),
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self> {
+        let normalized = input.trim().to_lowercase();
+        let words: Vec<&str> = normalized.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["components", "of", "schema", id] | ["components", id] => {
+                let id =
+                    Ulid::from_string(id).map_err(|err| eyre!("invalid schema id {id}: {err}"))?;
+                Ok(Query::ComponentsOfSchema(id))
+            }
+            ["attribute", "values", "with", "no", "prototype"]
+            | ["attribute", "values", "without", "prototype"]
+            | ["avs", "without", "prototype"]
+            | ["no", "prototype"] => Ok(Query::AttributeValuesWithoutPrototype),
+            ["orphaned", "nodes"] | ["orphaned"] | ["orphans"] => Ok(Query::OrphanedNodes),
+            ["invalid", "nodes"] | ["invalid"] => Ok(Query::InvalidNodes),
+            ["subgraph", index] => index
+                .parse()
+                .map(Query::SubGraph)
+                .map_err(|err| eyre!("invalid subgraph index {index}: {err}")),
+            _ => Err(eyre!("unknown query \"{}\"; {QUERY_HELP}", input.trim())),
+        }
+    }
+}
+
+/// The nodes matched by a query, along with any notes to show for them (e.g. validation issues)
+#[derive(Debug, Clone, Default)]
+pub struct QueryResult {
+    pub matches: HashSet<Ulid>,
+    pub notes: BTreeMap<Ulid, Vec<String>>,
+}
+
+pub fn run_query(
+    query: &Query,
+    graph: &WorkspaceSnapshotGraph,
+    split_info: Option<&SplitSnapshotInfo>,
+) -> Result<QueryResult> {
+    let mut result = QueryResult::default();
+
+    match query {
+        Query::AttributeValuesWithoutPrototype => {
+            for (node_weight, node_index) in graph.nodes() {
+                if !matches!(node_weight, NodeWeight::AttributeValue(_))
+                    || has_prototype(graph, node_index)
+                {
+                    continue;
+                }
+                let prop_has_prototype = graph
+                    .edges_directed(node_index, Direction::Outgoing)
+                    .find(|edge| matches!(edge.weight().kind(), EdgeWeightKind::Prop))
+                    .is_some_and(|edge| has_prototype(graph, edge.target()));
+                if !prop_has_prototype {
+                    result.matches.insert(node_weight.id());
+                }
+            }
+        }
+        Query::ComponentsOfSchema(id) => {
+            let node_weight = graph
+                .get_node_weight_by_id_opt(*id)
+                .ok_or_else(|| eyre!("no node with id {id}"))?;
+            let node_index = graph.get_node_index_by_id(*id)?;
+
+            let schema_variant_indexes: HashSet<NodeIndex> = match node_weight {
+                NodeWeight::SchemaVariant(_) => HashSet::from([node_index]),
+                NodeWeight::Content(content)
+                    if content.content_address_discriminants()
+                        == ContentAddressDiscriminants::Schema =>
+                {
+                    graph
+                        .edges_directed(node_index, Direction::Outgoing)
+                        .filter(|edge| {
+                            matches!(
+                                graph.get_node_weight(edge.target()),
+                                Ok(NodeWeight::SchemaVariant(_))
+                            )
+                        })
+                        .map(|edge| edge.target())
+                        .collect()
+                }
+                _ => return Err(eyre!("{id} is not a schema or schema variant")),
+            };
+
+            for variant_index in schema_variant_indexes {
+                for edge in graph.edges_directed(variant_index, Direction::Incoming) {
+                    if let Ok(component @ NodeWeight::Component(_)) =
+                        graph.get_node_weight(edge.source())
+                    {
+                        result.matches.insert(component.id());
+                    }
+                }
+            }
+        }
+        Query::InvalidNodes => {
+            for (issue, text) in validate_graph_with_text(graph)? {
+                for node_id in issue_node_ids(&issue) {
+                    result.matches.insert(node_id);
+                    result.notes.entry(node_id).or_default().push(text.clone());
+                }
+            }
+        }
+        Query::OrphanedNodes => {
+            for (node_weight, node_index) in graph.nodes() {
+                if node_index != graph.root()
+                    && graph
+                        .edges_directed(node_index, Direction::Incoming)
+                        .next()
+                        .is_none()
+                {
+                    result.matches.insert(node_weight.id());
+                }
+            }
+        }
+        Query::SubGraph(index) => {
+            let split_info =
+                split_info.ok_or_else(|| eyre!("subgraph queries need a split snapshot"))?;
+            result.matches.extend(
+                split_info
+                    .subgraph_by_node
+                    .iter()
+                    .filter(|(_, subgraph)| *subgraph == index)
+                    .map(|(node_id, _)| *node_id),
+            );
+        }
+    }
+
+    Ok(result)
+}
+
+fn has_prototype(graph: &WorkspaceSnapshotGraph, node_index: NodeIndex) -> bool {
+    graph
+        .edges_directed(node_index, Direction::Outgoing)
+        .any(|edge| matches!(edge.weight().kind(), EdgeWeightKind::Prototype(_)))
+}
+
+/// The nodes a validation issue should be flagged on
+fn issue_node_ids(issue: &ValidationIssue) -> Vec<Ulid> {
+    match issue {
+        ValidationIssue::ChildOrderingMismatch { node, .. }
+        | ValidationIssue::MissingOrderingNode { node } => vec![*node],
+        ValidationIssue::CyclicSubscriptions => vec![],
+        ValidationIssue::DuplicateAttributeValue {
+            original,
+            duplicate,
+        }
+        | ValidationIssue::DuplicateAttributeValueWithDifferentValues {
+            original,
+            duplicate,
+        } => vec![(*original).into(), (*duplicate).into()],
+        ValidationIssue::MissingChildAttributeValues { object, .. } => vec![(*object).into()],
+        ValidationIssue::MissingValue { apa } | ValidationIssue::MultipleValues { apa } => {
+            vec![(*apa).into()]
+        }
+        ValidationIssue::OrderingDuplicateEntry { ordering, .. }
+        | ValidationIssue::OrderingNodeMismatch { ordering, .. } => vec![*ordering],
+        ValidationIssue::UnknownChildAttributeValue { child } => vec![(*child).into()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dal::{
+        PropKind,
+        workspace_snapshot::{
+            edge_weight::EdgeWeight,
+            graph::WorkspaceSnapshotGraphVCurrent,
+        },
+    };
+    use si_events::ContentHash;
+
+    use super::*;
+
+    fn create_test_graph() -> WorkspaceSnapshotGraph {
+        let inner = WorkspaceSnapshotGraphVCurrent::new_with_categories_only()
+            .expect("Unable to create WorkspaceSnapshotGraph");
+        WorkspaceSnapshotGraph::V4(inner)
+    }
+
+    fn add_prop(graph: &mut WorkspaceSnapshotGraph, name: &str) -> (Ulid, NodeIndex) {
+        let id = graph.generate_ulid().expect("Unable to generate Ulid");
+        let prop = NodeWeight::new_prop(
+            id,
+            id,
+            PropKind::String,
+            name,
+            ContentHash::new(name.as_bytes()),
+        );
+        let index = graph.add_or_replace_node(prop).expect("add prop");
+        (id, index)
+    }
+
+    #[test]
+    fn test_parse_queries() {
+        assert_eq!(
+            Query::AttributeValuesWithoutPrototype,
+            Query::parse("attribute values with no prototype").expect("parse")
+        );
+        assert_eq!(
+            Query::OrphanedNodes,
+            Query::parse("  Orphaned Nodes ").expect("parse")
+        );
+        assert_eq!(Query::InvalidNodes, Query::parse("invalid").expect("parse"));
+        assert_eq!(
+            Query::SubGraph(3),
+            Query::parse("subgraph 3").expect("parse")
+        );
+
+        let id = Ulid::new();
+        assert_eq!(
+            Query::ComponentsOfSchema(id),
+            Query::parse(&format!(
+                "components of schema {}",
+                id.to_string().to_lowercase()
+            ))
+            .expect("parse")
+        );
+
+        assert!(Query::parse("components of schema not-an-id").is_err());
+        assert!(Query::parse("everything").is_err());
+    }
+
+    #[test]
+    fn test_orphaned_nodes_query() {
+        let mut graph = create_test_graph();
+        let (orphan_id, _) = add_prop(&mut graph, "orphan");
+        let (attached_id, attached_index) = add_prop(&mut graph, "attached");
+        graph
+            .add_edge(
+                graph.root(),
+                EdgeWeight::new(EdgeWeightKind::new_use()),
+                attached_index,
+            )
+            .expect("add edge");
+
+        let result = run_query(&Query::OrphanedNodes, &graph, None).expect("run query");
+
+        assert!(result.matches.contains(&orphan_id));
+        assert!(!result.matches.contains(&attached_id));
+        assert!(
+            !result
+                .matches
+                .contains(&graph.get_node_weight(graph.root()).expect("root").id())
+        );
+    }
+
+    #[test]
+    fn test_subgraph_query_requires_split_snapshot() {
+        let graph = create_test_graph();
+        assert!(run_query(&Query::SubGraph(0), &graph, None).is_err());
+    }
+}
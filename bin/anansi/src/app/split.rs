@@ -0,0 +1,416 @@
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+        HashSet,
+    },
+    fs::File,
+    io::Read as _,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use color_eyre::{
+    Result,
+    eyre::eyre,
+};
+use dal::{
+    EdgeWeightKindDiscriminants,
+    Ulid,
+    WorkspaceSnapshotGraph,
+    WorkspaceSnapshotGraphVCurrent,
+    workspace_snapshot::{
+        content_address::ContentAddress,
+        edge_weight::{
+            EdgeWeight,
+            EdgeWeightKind,
+        },
+        node_weight::{
+            NodeWeight,
+            OrderingNodeWeight,
+        },
+        split_snapshot::{
+            SplitSnapshotGraphVCurrent,
+            SubGraphVCurrent,
+        },
+    },
+};
+use petgraph::stable_graph::StableDiGraph;
+use si_layer_cache::db::serialize;
+use si_split_graph::{
+    SplitGraphEdgeWeight,
+    SplitGraphNodeWeight,
+    SubGraphAddress,
+    SuperGraph,
+};
+
+/// Summary of a single subgraph of a split snapshot
+#[derive(Debug, Clone)]
+pub struct SubGraphSummary {
+    pub index: usize,
+    pub address: Option<SubGraphAddress>,
+    pub root_id: Option<Ulid>,
+    pub node_count: usize,
+    pub external_source_count: usize,
+}
+
+/// An edge that crosses a subgraph boundary, as recorded by the `ExternalSource` edge in the
+/// subgraph of the target node
+#[derive(Debug, Clone)]
+pub struct ExternalSourceEdge {
+    /// The subgraph the target node (and the `ExternalSource` edge) lives in
+    pub subgraph: usize,
+    pub source_id: Ulid,
+    pub target_id: Ulid,
+    pub edge_kind: EdgeWeightKindDiscriminants,
+    pub is_default: bool,
+}
+
+/// Split snapshot structure that is lost when flattening into a [`WorkspaceSnapshotGraph`]
+#[derive(Debug, Clone, Default)]
+pub struct SplitSnapshotInfo {
+    pub subgraphs: Vec<SubGraphSummary>,
+    pub subgraph_by_node: HashMap<Ulid, usize>,
+    pub external_source_edges: Vec<ExternalSourceEdge>,
+}
+
+impl SplitSnapshotInfo {
+    pub fn subgraph_for_node(&self, node_id: Ulid) -> Option<usize> {
+        self.subgraph_by_node.get(&node_id).copied()
+    }
+
+    /// Returns true if an edge between these two nodes crosses a subgraph boundary
+    pub fn crosses_boundary(&self, source_id: Ulid, target_id: Ulid) -> bool {
+        match (
+            self.subgraph_for_node(source_id),
+            self.subgraph_for_node(target_id),
+        ) {
+            (Some(source), Some(target)) => source != target,
+            _ => false,
+        }
+    }
+}
+
+/// Loads a split snapshot from a supergraph file and its subgraph files.
+///
+/// Subgraphs are taken from `subgraph_paths` in order if any are given. Otherwise each subgraph is
+/// read from a file named after its layer cache address in `subgraph_dir`, defaulting to the
+/// directory the supergraph lives in.
+pub fn load_split_snapshot(
+    supergraph_path: &Path,
+    subgraph_paths: &[PathBuf],
+    subgraph_dir: Option<&Path>,
+) -> Result<SplitSnapshotGraphVCurrent> {
+    let supergraph: SuperGraph = read_serialized(supergraph_path)?;
+
+    let paths: Vec<PathBuf> = if !subgraph_paths.is_empty() {
+        if subgraph_paths.len() != supergraph.addresses().len() {
+            return Err(eyre!(
+                "supergraph has {} subgraphs but {} subgraph files were given",
+                supergraph.addresses().len(),
+                subgraph_paths.len()
+            ));
+        }
+        subgraph_paths.to_vec()
+    } else {
+        let dir = subgraph_dir
+            .or_else(|| supergraph_path.parent())
+            .unwrap_or_else(|| Path::new("."));
+        supergraph
+            .addresses()
+            .iter()
+            .map(|address| dir.join(address.to_string()))
+            .collect()
+    };
+
+    let mut subgraphs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let subgraph: SubGraphVCurrent = read_serialized(&path)
+            .map_err(|err| eyre!("unable to load subgraph {}: {err}", path.display()))?;
+        subgraphs.push(subgraph);
+    }
+
+    Ok(SplitSnapshotGraphVCurrent::from_parts(
+        supergraph, subgraphs,
+    ))
+}
+
+fn read_serialized<T>(path: &Path) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let mut file = File::open(path)?;
+    let mut bytes = vec![];
+    file.read_to_end(&mut bytes)?;
+
+    Ok(serialize::from_bytes(&bytes)?)
+}
+
+/// Flattens a split snapshot into a single [`WorkspaceSnapshotGraph`] so it can be browsed,
+/// edited and validated like a legacy snapshot.
+///
+/// The split graph's internal nodes are resolved away: the graph root becomes a `Root` content
+/// node, `ExternalTarget` placeholders are replaced by edges to the real target, and split
+/// `Ordering` nodes become [`OrderingNodeWeight`]s with `Ordinal` edges to their children. The
+/// subgraph layout and `ExternalSource` edges are returned alongside in [`SplitSnapshotInfo`].
+pub fn flatten_split_graph(
+    split_graph: &SplitSnapshotGraphVCurrent,
+) -> Result<(WorkspaceSnapshotGraph, SplitSnapshotInfo)> {
+    let root_id = split_graph.root_id()?;
+
+    let mut petgraph = StableDiGraph::new();
+    let root_index = petgraph.add_node(NodeWeight::new_content(
+        root_id,
+        root_id,
+        ContentAddress::Root,
+    ));
+    let mut graph = WorkspaceSnapshotGraph::V4(WorkspaceSnapshotGraphVCurrent::new_from_parts(
+        petgraph,
+        HashMap::from([(root_id, root_index)]),
+        HashMap::from([(root_id, HashSet::from([root_index]))]),
+        root_index,
+    ));
+
+    let mut info = SplitSnapshotInfo::default();
+    let mut external_targets = HashMap::new();
+    let mut orderings: BTreeMap<Ulid, (Ulid, Vec<Ulid>)> = BTreeMap::new();
+    let mut custom_edges = Vec::new();
+
+    for (subgraph_index, subgraph) in split_graph.subgraphs().iter().enumerate() {
+        let inner = subgraph.graph();
+        let mut node_count = 0;
+        let mut external_source_count = 0;
+
+        for node_index in inner.node_indices() {
+            let Some(node) = inner.node_weight(node_index) else {
+                continue;
+            };
+            match node {
+                SplitGraphNodeWeight::Custom(node_weight) => {
+                    graph.add_or_replace_node(node_weight.clone())?;
+                    info.subgraph_by_node
+                        .insert(node_weight.id(), subgraph_index);
+                    node_count += 1;
+                }
+                SplitGraphNodeWeight::GraphRoot { id, .. } => {
+                    info.subgraph_by_node.insert(*id, subgraph_index);
+                }
+                SplitGraphNodeWeight::ExternalTarget { id, target, .. } => {
+                    external_targets.insert(*id, *target);
+                }
+                SplitGraphNodeWeight::Ordering { .. }
+                | SplitGraphNodeWeight::SubGraphRoot { .. } => {}
+            }
+        }
+
+        for edge_index in inner.edge_indices() {
+            let Some((source_index, target_index)) = inner.edge_endpoints(edge_index) else {
+                continue;
+            };
+            let (Some(edge), Some(source), Some(target)) = (
+                inner.edge_weight(edge_index),
+                inner.node_weight(source_index),
+                inner.node_weight(target_index),
+            ) else {
+                continue;
+            };
+
+            match edge {
+                SplitGraphEdgeWeight::Custom(edge_weight) => {
+                    custom_edges.push((source.id(), edge_weight.clone(), target.id()));
+                }
+                SplitGraphEdgeWeight::ExternalSource {
+                    source_id,
+                    is_default,
+                    edge_kind,
+                    ..
+                } => {
+                    info.external_source_edges.push(ExternalSourceEdge {
+                        subgraph: subgraph_index,
+                        source_id: *source_id,
+                        target_id: target.id(),
+                        edge_kind: *edge_kind,
+                        is_default: *is_default,
+                    });
+                    external_source_count += 1;
+                }
+                SplitGraphEdgeWeight::Ordering => {
+                    if let SplitGraphNodeWeight::Ordering { id, order, .. } = target {
+                        orderings.insert(source.id(), (*id, order.clone()));
+                    }
+                }
+                SplitGraphEdgeWeight::Ordinal => {}
+            }
+        }
+
+        info.subgraphs.push(SubGraphSummary {
+            index: subgraph_index,
+            address: split_graph
+                .supergraph()
+                .address_for_subgraph(subgraph_index),
+            root_id: subgraph.root_id(),
+            node_count,
+            external_source_count,
+        });
+    }
+
+    for (source_id, edge_weight, target_id) in custom_edges {
+        let target_id = external_targets
+            .get(&target_id)
+            .copied()
+            .unwrap_or(target_id);
+        let source_index = graph.get_node_index_by_id(source_id)?;
+        let target_index = graph.get_node_index_by_id(target_id)?;
+        graph.add_edge(source_index, edge_weight, target_index)?;
+    }
+
+    for (container_id, (ordering_id, order)) in orderings {
+        let mut ordering_node = OrderingNodeWeight::new(ordering_id, ordering_id);
+        ordering_node.set_order(order.clone());
+        let ordering_index = graph.add_or_replace_node(NodeWeight::Ordering(ordering_node))?;
+        let container_index = graph.get_node_index_by_id(container_id)?;
+        graph.add_edge(
+            container_index,
+            EdgeWeight::new(EdgeWeightKind::Ordering),
+            ordering_index,
+        )?;
+        for element_id in order {
+            let element_index = graph.get_node_index_by_id(element_id)?;
+            graph.add_edge(
+                ordering_index,
+                EdgeWeight::new(EdgeWeightKind::Ordinal),
+                element_index,
+            )?;
+        }
+        if let Some(subgraph) = info.subgraph_for_node(container_id) {
+            info.subgraph_by_node.insert(ordering_id, subgraph);
+        }
+    }
+
+    graph.cleanup_and_merkle_tree_hash()?;
+
+    Ok((graph, info))
+}
+
+#[cfg(test)]
+mod tests {
+    use dal::{
+        PropKind,
+        workspace_snapshot::split_snapshot::SplitSnapshotGraphV1,
+    };
+    use petgraph::{
+        Direction,
+        visit::EdgeRef,
+    };
+    use si_events::ContentHash;
+
+    use super::*;
+
+    fn new_prop(split_graph: &mut SplitSnapshotGraphV1, name: &str) -> Ulid {
+        let id = split_graph.make_node_id();
+        split_graph
+            .add_or_replace_node(NodeWeight::new_prop(
+                id,
+                id,
+                PropKind::String,
+                name,
+                ContentHash::new(name.as_bytes()),
+            ))
+            .expect("add prop");
+        id
+    }
+
+    #[test]
+    fn test_flatten_split_graph_across_subgraphs() {
+        // A split max of 2 forces the props into more than one subgraph
+        let mut split_graph = SplitSnapshotGraphV1::new(2);
+        let root_id = split_graph.root_id().expect("root id");
+
+        let prop_ids: Vec<Ulid> = (0..5)
+            .map(|i| new_prop(&mut split_graph, &format!("prop_{i}")))
+            .collect();
+        for prop_id in &prop_ids {
+            split_graph
+                .add_edge(
+                    root_id,
+                    EdgeWeight::new(EdgeWeightKind::new_use()),
+                    *prop_id,
+                )
+                .expect("add edge");
+        }
+        split_graph.cleanup_and_merkle_tree_hash();
+        assert!(split_graph.subgraph_count() > 1);
+
+        let (graph, info) = flatten_split_graph(&split_graph).expect("flatten split graph");
+
+        assert_eq!(split_graph.subgraph_count(), info.subgraphs.len());
+        for prop_id in &prop_ids {
+            let prop_index = graph.get_node_index_by_id(*prop_id).expect("prop in graph");
+            assert!(
+                graph
+                    .edges_directed(prop_index, Direction::Incoming)
+                    .any(|edge| edge.source() == graph.root()),
+                "every prop should hang off the root after flattening"
+            );
+            assert!(info.subgraph_for_node(*prop_id).is_some());
+        }
+        assert!(
+            prop_ids
+                .iter()
+                .any(|prop_id| info.crosses_boundary(root_id, *prop_id)),
+            "some props should live in a different subgraph than the root"
+        );
+        assert!(!info.external_source_edges.is_empty());
+    }
+
+    #[test]
+    fn test_flatten_split_graph_preserves_ordering() {
+        let mut split_graph = SplitSnapshotGraphV1::new(usize::MAX / 2);
+        let root_id = split_graph.root_id().expect("root id");
+
+        let container_id = split_graph.make_node_id();
+        split_graph
+            .add_ordered_node(NodeWeight::new_prop(
+                container_id,
+                container_id,
+                PropKind::Array,
+                "container",
+                ContentHash::new(b"container"),
+            ))
+            .expect("add container");
+        split_graph
+            .add_edge(
+                root_id,
+                EdgeWeight::new(EdgeWeightKind::new_use()),
+                container_id,
+            )
+            .expect("add edge");
+
+        let children: Vec<Ulid> = (0..3)
+            .map(|i| new_prop(&mut split_graph, &format!("child_{i}")))
+            .collect();
+        for child_id in &children {
+            split_graph
+                .add_ordered_edge(
+                    container_id,
+                    EdgeWeight::new(EdgeWeightKind::new_use()),
+                    *child_id,
+                )
+                .expect("add ordered edge");
+        }
+        split_graph.cleanup_and_merkle_tree_hash();
+
+        let (graph, _) = flatten_split_graph(&split_graph).expect("flatten split graph");
+
+        let container_index = graph
+            .get_node_index_by_id(container_id)
+            .expect("container in graph");
+        let ordering_node = graph
+            .ordering_node_for_container(container_index)
+            .expect("look up ordering node")
+            .expect("container should have an ordering node");
+        assert_eq!(&children, ordering_node.order());
+    }
+}
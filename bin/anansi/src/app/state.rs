@@ -1,3 +1,8 @@
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
+
 use dal::{
     ChangeSetId,
     PropKind,
@@ -24,7 +29,10 @@ use dal::{
 use ratatui::layout::Rect;
 use si_events::ContentHash;
 
-use super::helpers::SnapshotStats;
+use super::{
+    helpers::SnapshotStats,
+    split::SplitSnapshotInfo,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FocusPanel {
@@ -951,6 +959,32 @@ pub struct EditModalState {
     pub editing: bool,
 }
 
+/// State for the query prompt
+#[derive(Debug, Clone, Default)]
+pub struct QueryState {
+    /// Current query input buffer
+    pub input: String,
+    /// Whether we're in query input mode
+    pub input_mode: bool,
+    /// The last query that was run, if its results are still shown
+    pub active_query: Option<String>,
+    /// Nodes matched by the active query
+    pub matches: HashSet<Ulid>,
+    /// Extra information about matched nodes (e.g. validation issues)
+    pub notes: BTreeMap<Ulid, Vec<String>>,
+    /// Error from parsing or running the last query
+    pub error: Option<String>,
+}
+
+impl QueryState {
+    pub fn clear(&mut self) {
+        self.active_query = None;
+        self.matches.clear();
+        self.notes.clear();
+        self.error = None;
+    }
+}
+
 pub struct SaveModalState {
     /// The filename/path being edited
     pub filename: String,
@@ -1022,6 +1056,12 @@ pub struct AppState {
     /// Scroll offset for the stats modal
     pub stats_scroll: u16,
 
+    /// Subgraph layout of the split snapshot this graph was flattened from, if any
+    pub split_info: Option<SplitSnapshotInfo>,
+
+    /// State for the query prompt
+    pub query: QueryState,
+
     /// Current frame size (updated before each event handling)
     pub frame_size: Rect,
 }
@@ -1060,6 +1100,8 @@ impl AppState {
             success_message: None,
             stats,
             stats_scroll: 0,
+            split_info: None,
+            query: QueryState::default(),
             frame_size: Rect::default(),
         }
     }
@@ -836,6 +836,45 @@ pub fn render_stats_modal(f: &mut Frame, state: &AppState) {
 
     lines.push(Line::from(""));
 
+    // Subgraph section for split snapshots
+    if let Some(ref split_info) = state.split_info {
+        lines.push(Line::from(Span::styled(
+            "Subgraphs",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        )));
+        lines.push(Line::from(""));
+
+        for subgraph in &split_info.subgraphs {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:>4}  ", subgraph.index),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    format!("{:>8}", subgraph.node_count),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(" nodes  ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("{:>6}", subgraph.external_source_count),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(" external sources  ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    subgraph
+                        .address
+                        .map(|address| truncate_string(&address.to_string(), 16))
+                        .unwrap_or_else(|| "(unwritten)".to_string()),
+                    Style::default().fg(Color::Green),
+                ),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+    }
+
     // Node weights section
     lines.push(Line::from(Span::styled(
         "Node Weights by Kind",
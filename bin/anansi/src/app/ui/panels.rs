@@ -59,6 +59,13 @@ pub fn render_title(f: &mut Frame, area: Rect, state: &AppState) {
         title_text.push_str(" - [unsaved]");
     }
 
+    if let Some(ref split_info) = state.split_info {
+        title_text.push_str(&format!(
+            " [split snapshot: {} subgraphs]",
+            split_info.subgraphs.len()
+        ));
+    }
+
     let title = Paragraph::new(title_text).style(
         Style::default()
             .fg(Color::Cyan)
@@ -68,6 +75,11 @@ pub fn render_title(f: &mut Frame, area: Rect, state: &AppState) {
 }
 
 pub fn render_filter_input(f: &mut Frame, area: Rect, state: &AppState) {
+    if state.query.input_mode || state.query.error.is_some() || state.query.active_query.is_some() {
+        render_query_input(f, area, state);
+        return;
+    }
+
     let filter_display = if state.node_list.filter_mode {
         format!("Filter: {}_", state.node_list.filter_text)
     } else if !state.node_list.filter_text.is_empty() {
@@ -96,6 +108,37 @@ pub fn render_filter_input(f: &mut Frame, area: Rect, state: &AppState) {
     f.render_widget(filter_widget, area);
 }
 
+fn render_query_input(f: &mut Frame, area: Rect, state: &AppState) {
+    let (query_display, style) = if state.query.input_mode {
+        (
+            format!("Query: {}_", state.query.input),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else if let Some(ref error) = state.query.error {
+        (
+            format!("Query error: {error} (Press : to edit)"),
+            Style::default().fg(Color::Red),
+        )
+    } else {
+        (
+            format!(
+                "Query: {} - {} matches (n/N: next/previous, Esc: clear)",
+                state.query.active_query.as_deref().unwrap_or_default(),
+                state.query.matches.len()
+            ),
+            Style::default().fg(Color::Magenta),
+        )
+    };
+
+    let query_widget = Paragraph::new(query_display)
+        .style(style)
+        .block(Block::default().borders(Borders::ALL).title("Query"));
+
+    f.render_widget(query_widget, area);
+}
+
 pub fn render_node_list(f: &mut Frame, area: Rect, state: &AppState) {
     if state.node_list.filtered_node_list.is_empty() {
         let empty_msg = if !state.node_list.filter_text.is_empty() {
@@ -135,19 +178,36 @@ pub fn render_node_list(f: &mut Frame, area: Rect, state: &AppState) {
         .enumerate()
         .map(|(i, item)| {
             let is_selected = start + i == selected_index;
+            let is_match = state.query.matches.contains(&item.node_id);
             let style = if is_selected {
                 Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else if is_match {
+                Style::default().fg(Color::Magenta)
             } else {
                 Style::default()
             };
 
-            Row::new(vec![
-                if is_selected { "[•]" } else { "[ ]" }.to_string(),
-                item.node_id.to_string(),
-                item.node_weight_kind.clone(),
-                item.name.clone().unwrap_or_default(),
-            ])
-            .style(style)
+            let marker = if is_selected {
+                "[•]"
+            } else if is_match {
+                "[*]"
+            } else {
+                "[ ]"
+            };
+
+            let mut cells = vec![marker.to_string(), item.node_id.to_string()];
+            if let Some(ref split_info) = state.split_info {
+                cells.push(
+                    split_info
+                        .subgraph_for_node(item.node_id)
+                        .map(|subgraph| subgraph.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                );
+            }
+            cells.push(item.node_weight_kind.clone());
+            cells.push(item.name.clone().unwrap_or_default());
+
+            Row::new(cells).style(style)
         })
         .collect();
 
@@ -181,20 +241,32 @@ pub fn render_node_list(f: &mut Frame, area: Rect, state: &AppState) {
         Block::default().borders(Borders::ALL).title(title)
     };
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(3),  // Selection indicator
-            Constraint::Length(26), // Node ID
-            Constraint::Length(20), // Node weight kind
-            Constraint::Min(20),    // Name (if available)
-        ],
-    )
-    .header(
-        Row::new(vec!["", "ID", "Node Weight Kind", "Name"])
-            .style(Style::default().add_modifier(Modifier::BOLD)),
-    )
-    .block(block);
+    let (widths, header) = if state.split_info.is_some() {
+        (
+            vec![
+                Constraint::Length(3),  // Selection indicator
+                Constraint::Length(26), // Node ID
+                Constraint::Length(4),  // Subgraph index
+                Constraint::Length(20), // Node weight kind
+                Constraint::Min(20),    // Name (if available)
+            ],
+            vec!["", "ID", "Sub", "Node Weight Kind", "Name"],
+        )
+    } else {
+        (
+            vec![
+                Constraint::Length(3),  // Selection indicator
+                Constraint::Length(26), // Node ID
+                Constraint::Length(20), // Node weight kind
+                Constraint::Min(20),    // Name (if available)
+            ],
+            vec!["", "ID", "Node Weight Kind", "Name"],
+        )
+    };
+
+    let table = Table::new(rows, widths)
+        .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(block);
 
     f.render_widget(table, area);
 }
@@ -297,6 +369,28 @@ fn render_node_details(
         ]),
     ];
 
+    if let Some(ref split_info) = state.split_info {
+        details_text.push(Line::from(vec![
+            Span::styled("Subgraph: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(
+                split_info
+                    .subgraph_for_node(node_weight.id())
+                    .map(|subgraph| subgraph.to_string())
+                    .unwrap_or_else(|| "(not in split snapshot)".to_string()),
+            ),
+        ]));
+    }
+
+    if let Some(notes) = state.query.notes.get(&node_weight.id()) {
+        details_text.push(Line::from(""));
+        for note in notes {
+            details_text.push(Line::from(Span::styled(
+                format!("! {note}"),
+                Style::default().fg(Color::Red),
+            )));
+        }
+    }
+
     // Add type-specific details using Debug formatting
     details_text.push(Line::from(""));
 
@@ -358,7 +452,36 @@ fn render_node_details(
     f.render_widget(details, area);
 }
 
+/// Label for an edge that crosses a split snapshot subgraph boundary, naming the subgraph of the
+/// node on the other end
+fn subgraph_boundary_label(
+    state: &AppState,
+    source_id: Option<Ulid>,
+    target_id: Option<Ulid>,
+    direction: Direction,
+) -> String {
+    let (Some(split_info), Some(source_id), Some(target_id)) =
+        (state.split_info.as_ref(), source_id, target_id)
+    else {
+        return String::new();
+    };
+    if !split_info.crosses_boundary(source_id, target_id) {
+        return String::new();
+    }
+
+    let (label, other_id) = match direction {
+        Direction::Outgoing => ("external target", target_id),
+        Direction::Incoming => ("external source", source_id),
+    };
+    split_info
+        .subgraph_for_node(other_id)
+        .map(|subgraph| format!(" [{label}, subgraph {subgraph}]"))
+        .unwrap_or_default()
+}
+
 fn render_edges(f: &mut Frame, area: Rect, state: &AppState, node_idx: petgraph::graph::NodeIndex) {
+    let node_id = state.working_graph.node_index_to_id(node_idx);
+
     // Get outgoing edges
     let outgoing_edges: Vec<_> = state
         .working_graph
@@ -407,12 +530,18 @@ fn render_edges(f: &mut Frame, area: Rect, state: &AppState, node_idx: petgraph:
                     selected_line = Some(lines.len());
                 }
 
-                let text = format!(
+                let mut text = format!(
                     " - {:?} → {} ({})",
                     edge_kind,
                     NodeWeightDiscriminants::from(target_weight),
                     target_weight.id(),
                 );
+                text.push_str(&subgraph_boundary_label(
+                    state,
+                    node_id,
+                    Some(target_weight.id()),
+                    Direction::Outgoing,
+                ));
 
                 let line = if is_selected {
                     Line::from(vec![
@@ -460,12 +589,18 @@ fn render_edges(f: &mut Frame, area: Rect, state: &AppState, node_idx: petgraph:
                     selected_line = Some(lines.len());
                 }
 
-                let text = format!(
+                let mut text = format!(
                     " ← {:?} - {} ({})",
                     edge_kind,
                     NodeWeightDiscriminants::from(source_weight),
                     source_weight.id(),
                 );
+                text.push_str(&subgraph_boundary_label(
+                    state,
+                    Some(source_weight.id()),
+                    node_id,
+                    Direction::Incoming,
+                ));
 
                 let line = if is_selected {
                     Line::from(vec![
@@ -639,7 +774,9 @@ pub fn render_status_bar(f: &mut Frame, area: Rect, state: &AppState) {
         return;
     }
 
-    let base_text = if state.node_list.filter_mode {
+    let base_text = if state.query.input_mode {
+        "Enter: Run query | Esc: Cancel | Type a query".to_string()
+    } else if state.node_list.filter_mode {
         "Enter: Apply filter | Esc: Cancel | Type to filter".to_string()
     } else {
         let save_hint = if state.is_dirty {
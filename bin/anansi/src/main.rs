@@ -1,16 +1,31 @@
 use std::{
     fs::File,
-    io::Read as _,
-    path::PathBuf,
+    io::{
+        Read as _,
+        Write as _,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
 };
 
 use app::{
     App,
+    DiffApp,
     splash,
+    split,
 };
 use clap::Parser;
 use color_eyre::Result;
-use dal::WorkspaceSnapshotGraph;
+use dal::{
+    WorkspaceSnapshotAddress,
+    WorkspaceSnapshotGraph,
+    workspace_snapshot::graph::{
+        RebaseBatch,
+        repair::repair_graph,
+    },
+};
 use si_layer_cache::db::serialize;
 use tokio_util::sync::CancellationToken;
 
@@ -20,9 +35,41 @@ mod app;
 #[command(name = "anansi", version = "0.1.0")]
 #[command(about = "anansi k(n)ows about graphs in system initiative")]
 struct Args {
-    /// Path to the snapshot you want to debug
+    /// Path to the snapshot you want to debug (the supergraph when used with --split)
     #[arg(required = true)]
     snapshot_path: PathBuf,
+    /// Treat the snapshot as the supergraph of a split snapshot and load its subgraphs
+    #[arg(long, default_value_t = false, conflicts_with_all = ["diff", "rebase_batch"])]
+    split: bool,
+    /// Path to a subgraph of the split snapshot, in supergraph order (repeatable)
+    #[arg(long = "subgraph", value_name = "SUBGRAPH_PATH", requires = "split")]
+    subgraphs: Vec<PathBuf>,
+    /// Directory holding subgraph files named by their layer cache address (defaults to the
+    /// supergraph's directory)
+    #[arg(
+        long,
+        value_name = "DIR",
+        requires = "split",
+        conflicts_with = "subgraphs"
+    )]
+    subgraph_dir: Option<PathBuf>,
+    /// Compare the snapshot against this snapshot instead of opening it for editing
+    #[arg(long, value_name = "SNAPSHOT_PATH", conflicts_with = "rebase_batch")]
+    diff: Option<PathBuf>,
+    /// Compare the snapshot against the result of applying this rebase batch to it
+    #[arg(long, value_name = "REBASE_BATCH_PATH")]
+    rebase_batch: Option<PathBuf>,
+    /// Repair the issues the graph validator finds instead of opening the snapshot, printing what
+    /// was repaired. This is a dry run unless --apply is given.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["diff", "rebase_batch", "split"])]
+    repair: bool,
+    /// Write the repaired snapshot to --output
+    #[arg(long, default_value_t = false, requires = "repair")]
+    apply: bool,
+    /// Where --apply writes the repaired snapshot (defaults to the snapshot path with a .repaired
+    /// extension)
+    #[arg(long, value_name = "PATH", requires = "apply")]
+    output: Option<PathBuf>,
     /// Show the splash screen for longer (because pretty)
     #[arg(long, default_value_t = false)]
     long_splash: bool,
@@ -33,7 +80,13 @@ async fn main() -> Result<()> {
     color_eyre::install()?;
     let args = Args::parse();
 
-    run_app(args).await?;
+    if args.repair {
+        run_repair(args)?;
+    } else if args.diff.is_some() || args.rebase_batch.is_some() {
+        run_diff_app(args).await?;
+    } else {
+        run_app(args).await?;
+    }
 
     Ok(())
 }
@@ -49,14 +102,19 @@ async fn run_app(args: Args) -> Result<()> {
         Ok::<(), color_eyre::Report>(())
     });
 
-    let snapshot_path = args.snapshot_path.clone();
     let init_app_future = tokio::spawn(async move {
-        let mut snap_file = File::open(&snapshot_path)?;
-        let mut snap_bytes = vec![];
-        snap_file.read_to_end(&mut snap_bytes)?;
-
-        let graph: WorkspaceSnapshotGraph = serialize::from_bytes(&snap_bytes)?;
-        let app = App::new(graph)?;
+        let app = if args.split {
+            let split_graph = split::load_split_snapshot(
+                &args.snapshot_path,
+                &args.subgraphs,
+                args.subgraph_dir.as_deref(),
+            )?;
+            let (graph, split_info) = split::flatten_split_graph(&split_graph)?;
+            App::new_split(graph, split_info)?
+        } else {
+            let graph: WorkspaceSnapshotGraph = read_serialized(&args.snapshot_path)?;
+            App::new(graph)?
+        };
 
         // App has loaded, kill the splash screen
         cancel_token_clone.cancel();
@@ -71,3 +129,108 @@ async fn run_app(args: Args) -> Result<()> {
 
     Ok(())
 }
+
+async fn run_diff_app(args: Args) -> Result<()> {
+    let cancel_token = CancellationToken::new();
+    let cancel_token_clone = cancel_token.clone();
+    let long_wait = args.long_splash;
+
+    // Show splash while the diff is computed
+    let splash_future = tokio::spawn(async move {
+        splash::show_splash(cancel_token, long_wait).await?;
+        Ok::<(), color_eyre::Report>(())
+    });
+
+    let init_app_future = tokio::spawn(async move {
+        let base: WorkspaceSnapshotGraph = read_serialized(&args.snapshot_path)?;
+        let base_label = file_label(&args.snapshot_path);
+
+        let app = match (args.diff, args.rebase_batch) {
+            (Some(updated_path), _) => {
+                let updated: WorkspaceSnapshotGraph = read_serialized(&updated_path)?;
+                DiffApp::new(&base, &updated, base_label, file_label(&updated_path))?
+            }
+            (None, Some(rebase_batch_path)) => {
+                let rebase_batch: RebaseBatch = read_serialized(&rebase_batch_path)?;
+                DiffApp::from_rebase_batch(
+                    &base,
+                    &rebase_batch,
+                    base_label,
+                    file_label(&rebase_batch_path),
+                )?
+            }
+            (None, None) => unreachable!("diff mode requires --diff or --rebase-batch"),
+        };
+
+        // Diff has been computed, kill the splash screen
+        cancel_token_clone.cancel();
+
+        Ok::<_, color_eyre::Report>(app)
+    });
+
+    let mut app = init_app_future.await??;
+    splash_future.await??;
+
+    app.run().await?;
+
+    Ok(())
+}
+
+fn run_repair(args: Args) -> Result<()> {
+    let mut graph: WorkspaceSnapshotGraph = read_serialized(&args.snapshot_path)?;
+    let reports = repair_graph(&mut graph)?;
+
+    if reports.is_empty() {
+        println!("no validation issues found");
+    }
+    for report in &reports {
+        let status = if report.resolved {
+            "resolved"
+        } else {
+            "unresolved"
+        };
+        println!("[{status}] {}", report.message);
+        for repair in &report.repairs {
+            println!("    {repair:?}");
+        }
+    }
+
+    let resolved = reports.iter().filter(|report| report.resolved).count();
+    let (bytes, _) = serialize::to_vec(&graph)?;
+    let address = WorkspaceSnapshotAddress::new(&bytes);
+    println!(
+        "{resolved} of {} issues resolved, repaired snapshot address: {address}",
+        reports.len()
+    );
+
+    if args.apply {
+        let output = args
+            .output
+            .unwrap_or_else(|| args.snapshot_path.with_extension("repaired"));
+        let mut file = File::create(&output)?;
+        file.write_all(&bytes)?;
+        println!("repaired snapshot written to {}", output.display());
+    } else {
+        println!("dry run, pass --apply to write the repaired snapshot");
+    }
+
+    Ok(())
+}
+
+fn read_serialized<T>(path: &Path) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let mut file = File::open(path)?;
+    let mut bytes = vec![];
+    file.read_to_end(&mut bytes)?;
+
+    Ok(serialize::from_bytes(&bytes)?)
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("unknown")
+        .to_string()
+}
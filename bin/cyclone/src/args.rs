@@ -176,6 +176,10 @@ pub(crate) struct Args {
     /// Disables process gatherer.
     #[arg(long, group = "gatherer")]
     pub(crate) disable_process_gatherer: bool,
+
+    /// Hosts that WebAssembly functions may send HTTP requests to (comma separated).
+    #[arg(long, value_delimiter = ',')]
+    pub(crate) wasm_http_allowlist: Vec<String>,
 }
 
 impl TryFrom<Args> for Config {
@@ -241,6 +245,9 @@ impl TryFrom<Args> for Config {
         } else if args.disable_process_gatherer {
             builder.enable_forwarder(false);
         }
+
+        builder.wasm_http_allowlist(args.wasm_http_allowlist);
+
         builder.build().map_err(Into::into)
     }
 }
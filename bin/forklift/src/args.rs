@@ -114,6 +114,10 @@ pub(crate) struct Args {
     /// Enables the audit logs app
     #[arg(long)]
     pub(crate) enable_audit_logs_app: Option<bool>,
+
+    /// Enables aggregating billing events into usage rollups in the audit database
+    #[arg(long)]
+    pub(crate) enable_billing_usage_rollups: Option<bool>,
 }
 
 fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
@@ -139,6 +143,9 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     if let Some(enable_audit_logs_app) = args.enable_audit_logs_app {
         config_map.set("enable_audit_logs_app", enable_audit_logs_app);
     }
+    if let Some(enable_billing_usage_rollups) = args.enable_billing_usage_rollups {
+        config_map.set("enable_billing_usage_rollups", enable_billing_usage_rollups);
+    }
     config_map
 }
 
@@ -14,6 +14,7 @@ pub enum Commands {
     ValidateSpecs(ValidateSpecsArgs),
     WriteAllSpecs(WriteAllSpecsArgs),
     WriteExistingModulesSpec(WriteExistingModulesSpecArgs),
+    WriteModuleBundle(WriteModuleBundleArgs),
     WriteSpec(WriteSpecArgs),
 }
 
@@ -174,6 +175,39 @@ pub struct WriteExistingModulesSpecArgs {
     pub out: PathBuf,
 }
 
+#[derive(clap::Args, Debug)]
+#[command(about = "Download modules from the module index into an offline module bundle at {out}")]
+pub struct WriteModuleBundleArgs {
+    #[arg(
+        long,
+        short = 'o',
+        required = true,
+        help = "Path of the bundle to write"
+    )]
+    pub out: PathBuf,
+
+    #[arg(
+        long = "module-id",
+        short = 'm',
+        help = "Id of a module to bundle in addition to the builtins. May be repeated."
+    )]
+    pub module_ids: Vec<String>,
+
+    #[arg(
+        long = "no-builtins",
+        help = "Only bundle the modules passed with --module-id",
+        action = clap::ArgAction::SetTrue
+    )]
+    pub no_builtins: bool,
+
+    #[arg(
+        long,
+        default_value = "100",
+        help = "Maximum number of concurrent downloads."
+    )]
+    pub max_concurrent: usize,
+}
+
 #[derive(clap::Args, Debug)]
 #[command(about = "Get {spec_name} from the module index and write it to {out}")]
 pub struct WriteSpecArgs {
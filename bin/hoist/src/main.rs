@@ -22,11 +22,15 @@ use std::{
     time::Duration,
 };
 
+use chrono::Utc;
 use clap::{
     CommandFactory,
     Parser,
 };
-use color_eyre::Result;
+use color_eyre::{
+    Result,
+    eyre::eyre,
+};
 use commands::Commands;
 use diff::{
     patch_list_to_changelog,
@@ -39,13 +43,18 @@ use indicatif::{
 };
 use json_patch::diff;
 use module_index_client::{
+    MODULE_BUNDLE_MANIFEST_VERSION,
+    ModuleBundleManifest,
     ModuleDetailsResponse,
     ModuleIndexClient,
+    ModuleSignature,
 };
 use rand::random;
+use si_crypto::PackageSigningKey;
 use si_pkg::{
     PkgSpec,
     SiPkg,
+    SiPkgBundleWriter,
 };
 use tokio::sync::Mutex;
 use ulid::Ulid;
@@ -71,6 +80,13 @@ struct Args {
     endpoint: String,
     #[arg(long, short = 't', env = "SI_BEARER_TOKEN", hide_env_values(true))]
     token: String,
+    #[arg(
+        long,
+        env = "SI_MODULE_SIGNING_KEY",
+        hide_env_values(true),
+        help = "Base64 encoded ed25519 key used to sign uploaded modules"
+    )]
+    signing_key: Option<String>,
 
     #[command(subcommand)]
     command: Option<Commands>,
@@ -86,11 +102,24 @@ async fn main() -> Result<()> {
     let client = ModuleIndexClient::new(Url::parse(endpoint)?, token)?;
     println!("Working with Module Index at: {endpoint}");
 
+    let signing_key = args
+        .signing_key
+        .as_deref()
+        .map(PackageSigningKey::from_base64)
+        .transpose()?;
+    if let Some(signing_key) = &signing_key {
+        println!(
+            "Signing modules with key: {}",
+            signing_key.verifying_key().key_id()
+        );
+    }
+
     match args.command {
         Some(Commands::AnonymizeSpecs(args)) => anonymize_specs(args.target_dir, args.out).await?,
         Some(Commands::UploadAllSpecs(args)) => {
             upload_pkg_specs(
                 &client,
+                signing_key.as_ref(),
                 args.target_dir,
                 args.max_concurrent,
                 args.skip_confirmation,
@@ -101,6 +130,7 @@ async fn main() -> Result<()> {
         Some(Commands::UploadSpec(args)) => {
             upload_pkg_specs(
                 &client,
+                signing_key.as_ref(),
                 args.target,
                 args.max_concurrent,
                 args.skip_confirmation,
@@ -112,6 +142,17 @@ async fn main() -> Result<()> {
         Some(Commands::WriteExistingModulesSpec(args)) => {
             write_existing_modules_spec(client, args.out).await?
         }
+        Some(Commands::WriteModuleBundle(args)) => {
+            write_module_bundle(
+                &client,
+                endpoint,
+                args.out,
+                args.module_ids,
+                args.no_builtins,
+                args.max_concurrent,
+            )
+            .await?
+        }
         Some(Commands::WriteAllSpecs(args)) => {
             write_all_specs(client, args.out.to_path_buf()).await?
         }
@@ -315,6 +356,93 @@ async fn write_all_specs(client: ModuleIndexClient, out: PathBuf) -> Result<()>
     Ok(())
 }
 
+async fn write_module_bundle(
+    client: &ModuleIndexClient,
+    endpoint: &str,
+    out: PathBuf,
+    module_ids: Vec<String>,
+    no_builtins: bool,
+    max_concurrent: usize,
+) -> Result<()> {
+    let mut modules = if no_builtins {
+        vec![]
+    } else {
+        client.list_builtins().await?.modules
+    };
+    let builtin_ids: HashSet<String> = modules.iter().map(|module| module.id.clone()).collect();
+    for module_id in module_ids {
+        if !builtin_ids.contains(&module_id) {
+            modules.push(
+                client
+                    .module_details(Ulid::from_string(&module_id)?)
+                    .await?,
+            );
+        }
+    }
+
+    let pb = setup_progress_bar(modules.len() as u64);
+    pb.set_message("⏰ Downloading modules ...");
+
+    let mut downloads: Vec<(ModuleDetailsResponse, SiPkg)> = futures::stream::iter(modules)
+        .map(|module| {
+            let pb = pb.clone();
+            let is_builtin = builtin_ids.contains(&module.id);
+            async move {
+                let module_id = Ulid::from_string(&module.id)?;
+                let module_bytes = if is_builtin {
+                    client.get_builtin(module_id).await?
+                } else {
+                    client.download_module(module_id).await?
+                };
+                let pkg = SiPkg::load_from_bytes(&module_bytes)?;
+                let hash = pkg.hash()?.to_string();
+                if hash != module.latest_hash {
+                    return Err(eyre!(
+                        "module {} ({}) has hash {hash}, expected {}",
+                        module.name,
+                        module.id,
+                        module.latest_hash
+                    ));
+                }
+
+                pb.set_message(format!("Downloaded: {}", module.name));
+                pb.inc(1);
+                Ok::<_, color_eyre::Report>((module, pkg))
+            }
+        })
+        .buffer_unordered(max_concurrent)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<_>>()?;
+    downloads.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+
+    let mut writer = SiPkgBundleWriter::new();
+    let mut manifest_modules = Vec::with_capacity(downloads.len());
+    for (module, pkg) in downloads {
+        writer.add_pkg(&module.id, &pkg)?;
+        manifest_modules.push(module);
+    }
+    let module_count = manifest_modules.len();
+    let bundle_bytes = writer.finish(&ModuleBundleManifest {
+        version: MODULE_BUNDLE_MANIFEST_VERSION,
+        created_at: Utc::now(),
+        module_index_url: Some(endpoint.to_string()),
+        modules: manifest_modules,
+    })?;
+
+    if let Some(parent) = out.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&out, bundle_bytes)?;
+    pb.finish_with_message(format!(
+        "✨ Wrote {module_count} modules to {}",
+        out.display()
+    ));
+
+    Ok(())
+}
+
 async fn compare_specs(source: PathBuf, target: PathBuf) -> Result<()> {
     let source_spec = json_to_spec(source)?;
     let target_spec = json_to_spec(target)?;
@@ -552,9 +680,29 @@ async fn write_spec(client: ModuleIndexClient, module_id: String, out: PathBuf)
     Ok(())
 }
 
-async fn upload_pkg_spec(client: &ModuleIndexClient, pkg: &SiPkg) -> Result<()> {
+async fn upload_pkg_spec(
+    client: &ModuleIndexClient,
+    signing_key: Option<&PackageSigningKey>,
+    pkg: &SiPkg,
+) -> Result<()> {
     let schema = pkg.schemas()?[0].clone();
     let metadata = pkg.metadata()?;
+    let module_bytes = pkg.write_to_bytes()?;
+
+    // The module index verifies the signature against the hash of the uploaded bytes, which can
+    // differ from the hash of the spec the package was built from.
+    let module_signature = match signing_key {
+        Some(signing_key) => {
+            let uploaded_hash = SiPkg::load_from_bytes(&module_bytes)?.hash()?.to_string();
+            let verifying_key = signing_key.verifying_key();
+            Some(ModuleSignature {
+                key_id: verifying_key.key_id(),
+                public_key: verifying_key.to_base64(),
+                signature: signing_key.sign(uploaded_hash).to_base64(),
+            })
+        }
+        None => None,
+    };
 
     client
         .upsert_builtin(
@@ -562,9 +710,10 @@ async fn upload_pkg_spec(client: &ModuleIndexClient, pkg: &SiPkg) -> Result<()>
             metadata.version(),
             Some(metadata.hash().to_string()),
             schema.unique_id().map(String::from),
-            pkg.write_to_bytes()?,
+            module_bytes,
             schema.variants()?[0].unique_id().map(String::from),
             Some(metadata.version().to_string()),
+            module_signature,
         )
         .await?;
 
@@ -573,6 +722,7 @@ async fn upload_pkg_spec(client: &ModuleIndexClient, pkg: &SiPkg) -> Result<()>
 
 async fn upload_pkg_specs(
     client: &ModuleIndexClient,
+    signing_key: Option<&PackageSigningKey>,
     target_dir: PathBuf,
     max_concurrent: usize,
     skip_confirmation: bool,
@@ -687,7 +837,7 @@ async fn upload_pkg_specs(
                 let mut backoff_ms = 100;
 
                 loop {
-                    match upload_pkg_spec(client, &pkg).await {
+                    match upload_pkg_spec(client, signing_key, &pkg).await {
                         Ok(_) => break,
                         Err(e) => {
                             retries += 1;
@@ -20,6 +20,8 @@ export interface ManagementFunc extends Func {
     [key: string]: ComponentWithGeometry;
   };
   variantSocketMap: Record<SchemaName, number>;
+  /// The payload delivered to the webhook that triggered this run, if any.
+  webhookPayload?: unknown;
 }
 
 export type ManagementFuncResult =
@@ -115,6 +117,7 @@ async function execute(
     components,
     currentView,
     variantSocketMap,
+    webhookPayload,
     handler,
   }: ManagementFunc,
   code: string,
@@ -133,6 +136,7 @@ async function execute(
         components,
         currentView,
         variantSocketMap,
+        webhookPayload,
       },
     );
   } catch (err) {
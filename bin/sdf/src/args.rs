@@ -188,6 +188,15 @@ pub(crate) struct Args {
     #[arg(long, env = "SI_BACKFILL_FUNC_RUN_LOGS_CUTOFF_ID")]
     pub(crate) backfill_func_run_logs_cutoff_id: Option<String>,
 
+    /// Number of workspaces to migrate per batch when migrating to split snapshots
+    #[arg(long, default_value = "10", env = "SI_SPLIT_MIGRATION_BATCH_SIZE")]
+    pub(crate) split_migration_batch_size: usize,
+
+    /// Comma-separated workspace ids to migrate to (or roll back from) split snapshots. Required
+    /// when rolling back; when migrating, limits the migration to these workspaces.
+    #[arg(long, env = "SI_SPLIT_MIGRATION_WORKSPACE_IDS")]
+    pub(crate) split_migration_workspace_ids: Option<String>,
+
     /// Veritech encryption key file location [default: /run/sdf/veritech_encryption.key]
     #[arg(long)]
     pub(crate) veritech_encryption_key_path: Option<PathBuf>,
@@ -264,6 +273,11 @@ pub(crate) struct Args {
     #[arg(long, env = "SI_MODULE_INDEX_URL")]
     pub(crate) module_index_url: Option<String>,
 
+    /// Location on disk of an offline module bundle to load into the module cache instead of
+    /// using the module index
+    #[arg(long, env = "SI_MODULE_BUNDLE_PATH")]
+    pub(crate) module_bundle_path: Option<String>,
+
     /// Allow for Posthog feature flags in SDF
     #[arg(
         long,
@@ -453,6 +467,9 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
     if let Some(module_index_url) = args.module_index_url {
         config_map.set("module_index_url", module_index_url);
     }
+    if let Some(module_bundle_path) = args.module_bundle_path {
+        config_map.set("module_bundle_path", module_bundle_path);
+    }
 
     if let Some(auth_api_url) = args.auth_api_url {
         config_map.set("auth_api_url", auth_api_url);
@@ -521,6 +538,17 @@ fn build_config_map(args: Args, config_map: &mut ConfigMap) -> &ConfigMap {
         );
     }
 
+    config_map.set(
+        "split_migration_batch_size",
+        i64::try_from(args.split_migration_batch_size).unwrap_or(10),
+    );
+    if let Some(split_migration_workspace_ids) = args.split_migration_workspace_ids {
+        config_map.set(
+            "split_migration_workspace_ids",
+            split_migration_workspace_ids,
+        );
+    }
+
     config_map.set("nats.connection_name", NAME);
     config_map.set("pg.application_name", NAME);
     config_map.set("layer_db_config.pg_pool_config.application_name", NAME);
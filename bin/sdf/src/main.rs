@@ -14,6 +14,7 @@ use sdf_server::{
     Migrator,
     Server,
     SnapshotGarbageCollector,
+    SplitSnapshotMigrator,
     key_generation,
 };
 use si_service::{
@@ -170,6 +171,30 @@ async fn async_main() -> Result<()> {
                 telemetry_shutdown,
             )
             .await
+        } else if config.migration_mode().is_migrate_split_snapshots() {
+            migrate_split_snapshots(
+                config,
+                main_tracker,
+                main_token,
+                helping_tasks_tracker,
+                helping_tasks_token,
+                telemetry_tracker,
+                telemetry_token,
+                telemetry_shutdown,
+            )
+            .await
+        } else if config.migration_mode().is_rollback_split_snapshots() {
+            rollback_split_snapshots(
+                config,
+                main_tracker,
+                main_token,
+                helping_tasks_tracker,
+                helping_tasks_token,
+                telemetry_tracker,
+                telemetry_token,
+                telemetry_shutdown,
+            )
+            .await
         } else {
             run_server(
                 config,
@@ -314,6 +339,64 @@ async fn garbage_collect_snapshots(
         .map_err(Into::into)
 }
 
+#[inline]
+#[allow(clippy::too_many_arguments)]
+async fn migrate_split_snapshots(
+    config: Config,
+    main_tracker: TaskTracker,
+    main_token: CancellationToken,
+    helping_tasks_tracker: TaskTracker,
+    helping_tasks_token: CancellationToken,
+    telemetry_tracker: TaskTracker,
+    telemetry_token: CancellationToken,
+    telemetry_shutdown: TelemetryShutdownGuard,
+) -> Result<()> {
+    let migrator =
+        SplitSnapshotMigrator::new(config, &helping_tasks_tracker, helping_tasks_token.clone())
+            .await?;
+
+    let handle = main_tracker.spawn(migrator.migrate(main_token.clone()));
+
+    shutdown::graceful_with_handle(handle)
+        .group(main_tracker, main_token)
+        .group(helping_tasks_tracker, helping_tasks_token)
+        .group(telemetry_tracker, telemetry_token)
+        .telemetry_guard(telemetry_shutdown.into_future())
+        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
+        .wait()
+        .await
+        .map_err(Into::into)
+}
+
+#[inline]
+#[allow(clippy::too_many_arguments)]
+async fn rollback_split_snapshots(
+    config: Config,
+    main_tracker: TaskTracker,
+    main_token: CancellationToken,
+    helping_tasks_tracker: TaskTracker,
+    helping_tasks_token: CancellationToken,
+    telemetry_tracker: TaskTracker,
+    telemetry_token: CancellationToken,
+    telemetry_shutdown: TelemetryShutdownGuard,
+) -> Result<()> {
+    let migrator =
+        SplitSnapshotMigrator::new(config, &helping_tasks_tracker, helping_tasks_token.clone())
+            .await?;
+
+    let handle = main_tracker.spawn(migrator.rollback());
+
+    shutdown::graceful_with_handle(handle)
+        .group(main_tracker, main_token)
+        .group(helping_tasks_tracker, helping_tasks_token)
+        .group(telemetry_tracker, telemetry_token)
+        .telemetry_guard(telemetry_shutdown.into_future())
+        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
+        .wait()
+        .await
+        .map_err(Into::into)
+}
+
 #[inline]
 #[allow(clippy::too_many_arguments)]
 async fn backfill_layer_cache(
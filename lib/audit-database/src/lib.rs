@@ -58,6 +58,7 @@ use thiserror::Error;
 mod config;
 mod context;
 mod migrate;
+mod usage;
 
 pub use config::{
     AuditDatabaseConfig,
@@ -72,6 +73,12 @@ pub use migrate::{
     AuditDatabaseMigrationError,
     migrate,
 };
+pub use usage::{
+    FuncRunUsage,
+    ResourceChange,
+    UsageGranularity,
+    WorkspaceUsageRollup,
+};
 
 #[allow(missing_docs)]
 #[remain::sorted]
@@ -79,6 +86,8 @@ pub use migrate::{
 pub enum AuditDatabaseError {
     #[error("chrono parse error: {0}")]
     ChronoParse(#[from] chrono::ParseError),
+    #[error("invalid usage range: {0}")]
+    InvalidUsageRange(String),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error("pg pool error: {0}")]
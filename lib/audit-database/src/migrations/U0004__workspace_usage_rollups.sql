@@ -0,0 +1,27 @@
+CREATE TABLE workspace_usage_rollups (
+    workspace_id text NOT NULL,
+    granularity text NOT NULL,
+    bucket_start timestamp with time zone NOT NULL,
+    resource_count bigint,
+    resource_count_observed_at timestamp with time zone,
+    resources_created bigint NOT NULL DEFAULT 0,
+    resources_deleted bigint NOT NULL DEFAULT 0,
+    PRIMARY KEY (workspace_id, granularity, bucket_start)
+);
+
+CREATE TABLE workspace_func_run_usage_rollups (
+    workspace_id text NOT NULL,
+    granularity text NOT NULL,
+    bucket_start timestamp with time zone NOT NULL,
+    func_kind text NOT NULL,
+    run_count bigint NOT NULL DEFAULT 0,
+    run_milliseconds bigint NOT NULL DEFAULT 0,
+    PRIMARY KEY (workspace_id, granularity, bucket_start, func_kind)
+);
+
+CREATE TABLE workspace_usage_recorded_events (
+    func_run_id text NOT NULL,
+    kind text NOT NULL,
+    recorded_at timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (func_run_id, kind)
+);
@@ -0,0 +1,373 @@
+//! Hourly and daily rollups of the billable usage of a workspace, aggregated from billing events.
+
+use std::collections::BTreeMap;
+
+use chrono::{
+    DateTime,
+    DurationRound,
+    TimeDelta,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_data_pg::InstrumentedTransaction;
+use si_events::{
+    FuncKind,
+    FuncRunId,
+    WorkspacePk,
+};
+use telemetry::prelude::*;
+
+use crate::{
+    AuditDatabaseContext,
+    AuditDatabaseError,
+    Result,
+};
+
+/// The size of the time buckets that usage is rolled up into.
+#[remain::sorted]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum UsageGranularity {
+    /// Usage is rolled up per UTC day.
+    Day,
+    /// Usage is rolled up per hour.
+    Hour,
+}
+
+impl UsageGranularity {
+    const ALL: [Self; 2] = [Self::Hour, Self::Day];
+
+    /// Returns the string stored in the rollup tables for this granularity.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Hour => "hour",
+        }
+    }
+
+    /// Returns the longest range that can be listed at this granularity.
+    pub fn max_range(&self) -> TimeDelta {
+        match self {
+            Self::Day => TimeDelta::days(366),
+            Self::Hour => TimeDelta::days(31),
+        }
+    }
+
+    /// Returns the start of the bucket that the given point in time falls into.
+    pub fn bucket_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let bucket_size = match self {
+            Self::Day => TimeDelta::days(1),
+            Self::Hour => TimeDelta::hours(1),
+        };
+        // Truncation can only fail for durations that do not fit in nanoseconds or for times
+        // outside of the representable range, neither of which apply to buckets this small.
+        at.duration_trunc(bucket_size).unwrap_or(at)
+    }
+}
+
+/// Whether a resource was created or deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceChange {
+    /// A resource was created.
+    Created,
+    /// A resource was deleted.
+    Deleted,
+}
+
+impl ResourceChange {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "resource_created",
+            Self::Deleted => "resource_deleted",
+        }
+    }
+}
+
+/// The kind of event recorded for a func run that has finished, used to deduplicate redeliveries.
+const FUNC_RUN_COMPLETE_EVENT_KIND: &str = "func_run_complete";
+
+/// The func runs of a single [kind](FuncKind) within a [`WorkspaceUsageRollup`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunUsage {
+    /// The kind of func that was run.
+    pub func_kind: String,
+    /// The number of func runs that finished within the bucket.
+    pub run_count: i64,
+    /// The total time spent running funcs of this kind, in seconds.
+    pub run_seconds: f64,
+}
+
+/// The usage of a workspace within a single time bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceUsageRollup {
+    /// The workspace that the usage belongs to.
+    pub workspace_id: WorkspacePk,
+    /// The size of the bucket.
+    pub granularity: UsageGranularity,
+    /// The start of the bucket.
+    pub bucket_start: DateTime<Utc>,
+    /// The most recently observed number of resources on HEAD within the bucket, if any was
+    /// observed.
+    pub resource_count: Option<i64>,
+    /// The number of resources created within the bucket.
+    pub resources_created: i64,
+    /// The number of resources deleted within the bucket.
+    pub resources_deleted: i64,
+    /// The func runs that finished within the bucket, by kind.
+    pub func_runs: Vec<FuncRunUsage>,
+}
+
+impl WorkspaceUsageRollup {
+    /// Records the number of resources on HEAD at the given point in time. Only the latest
+    /// observation within each bucket is kept.
+    #[instrument(
+        name = "audit_database.usage.record_resource_count",
+        level = "debug",
+        skip_all,
+        fields(si.workspace.id = %workspace_id)
+    )]
+    pub async fn record_resource_count(
+        context: &AuditDatabaseContext,
+        workspace_id: WorkspacePk,
+        observed_at: DateTime<Utc>,
+        resource_count: usize,
+    ) -> Result<()> {
+        let client = context.pg_pool().get().await?;
+        for granularity in UsageGranularity::ALL {
+            client
+                .execute(
+                    "INSERT INTO workspace_usage_rollups (
+                        workspace_id,
+                        granularity,
+                        bucket_start,
+                        resource_count,
+                        resource_count_observed_at
+                    ) VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT (workspace_id, granularity, bucket_start) DO UPDATE SET
+                        resource_count = EXCLUDED.resource_count,
+                        resource_count_observed_at = EXCLUDED.resource_count_observed_at
+                    WHERE workspace_usage_rollups.resource_count_observed_at IS NULL
+                        OR workspace_usage_rollups.resource_count_observed_at <= EXCLUDED.resource_count_observed_at",
+                    &[
+                        &workspace_id.to_string(),
+                        &granularity.as_str(),
+                        &granularity.bucket_start(observed_at),
+                        &(resource_count as i64),
+                        &observed_at,
+                    ],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Records that a resource was created or deleted by the given func run at the given point in
+    /// time. Recording the same change for the same func run again (such as when a billing event is
+    /// redelivered) is a no-op.
+    #[instrument(
+        name = "audit_database.usage.record_resource_change",
+        level = "debug",
+        skip_all,
+        fields(si.workspace.id = %workspace_id, si.func_run.id = %func_run_id)
+    )]
+    pub async fn record_resource_change(
+        context: &AuditDatabaseContext,
+        workspace_id: WorkspacePk,
+        func_run_id: FuncRunId,
+        at: DateTime<Utc>,
+        change: ResourceChange,
+    ) -> Result<()> {
+        let (created, deleted): (i64, i64) = match change {
+            ResourceChange::Created => (1, 0),
+            ResourceChange::Deleted => (0, 1),
+        };
+
+        let mut client = context.pg_pool().get().await?;
+        let txn = client.transaction().await?;
+        if !Self::mark_recorded(&txn, func_run_id, change.as_str()).await? {
+            debug!("resource change already recorded, skipping");
+            return Ok(());
+        }
+        for granularity in UsageGranularity::ALL {
+            txn.execute(
+                "INSERT INTO workspace_usage_rollups (
+                    workspace_id,
+                    granularity,
+                    bucket_start,
+                    resources_created,
+                    resources_deleted
+                ) VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (workspace_id, granularity, bucket_start) DO UPDATE SET
+                    resources_created = workspace_usage_rollups.resources_created + EXCLUDED.resources_created,
+                    resources_deleted = workspace_usage_rollups.resources_deleted + EXCLUDED.resources_deleted",
+                &[
+                    &workspace_id.to_string(),
+                    &granularity.as_str(),
+                    &granularity.bucket_start(at),
+                    &created,
+                    &deleted,
+                ],
+            )
+            .await?;
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Records a func run that finished at the given point in time. Recording the same func run
+    /// again (such as when a billing event is redelivered) is a no-op.
+    #[instrument(
+        name = "audit_database.usage.record_func_run",
+        level = "debug",
+        skip_all,
+        fields(si.workspace.id = %workspace_id, si.func_run.id = %func_run_id)
+    )]
+    pub async fn record_func_run(
+        context: &AuditDatabaseContext,
+        workspace_id: WorkspacePk,
+        func_run_id: FuncRunId,
+        finished_at: DateTime<Utc>,
+        func_kind: FuncKind,
+        duration_ms: u64,
+    ) -> Result<()> {
+        let mut client = context.pg_pool().get().await?;
+        let txn = client.transaction().await?;
+        if !Self::mark_recorded(&txn, func_run_id, FUNC_RUN_COMPLETE_EVENT_KIND).await? {
+            debug!("func run already recorded, skipping");
+            return Ok(());
+        }
+        for granularity in UsageGranularity::ALL {
+            txn.execute(
+                "INSERT INTO workspace_func_run_usage_rollups (
+                    workspace_id,
+                    granularity,
+                    bucket_start,
+                    func_kind,
+                    run_count,
+                    run_milliseconds
+                ) VALUES ($1, $2, $3, $4, 1, $5)
+                ON CONFLICT (workspace_id, granularity, bucket_start, func_kind) DO UPDATE SET
+                    run_count = workspace_func_run_usage_rollups.run_count + 1,
+                    run_milliseconds = workspace_func_run_usage_rollups.run_milliseconds + EXCLUDED.run_milliseconds",
+                &[
+                    &workspace_id.to_string(),
+                    &granularity.as_str(),
+                    &granularity.bucket_start(finished_at),
+                    &func_kind.to_string(),
+                    &(duration_ms as i64),
+                ],
+            )
+            .await?;
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Marks the event of the given kind for the func run as recorded, returning false if it
+    /// already was.
+    async fn mark_recorded(
+        txn: &InstrumentedTransaction<'_>,
+        func_run_id: FuncRunId,
+        kind: &str,
+    ) -> Result<bool> {
+        let inserted = txn
+            .execute(
+                "INSERT INTO workspace_usage_recorded_events (func_run_id, kind) VALUES ($1, $2)
+                ON CONFLICT (func_run_id, kind) DO NOTHING",
+                &[&func_run_id.to_string(), &kind],
+            )
+            .await?;
+        Ok(inserted > 0)
+    }
+
+    /// Lists the usage of a workspace for every bucket starting within the given range, oldest
+    /// first. Buckets without any recorded usage are omitted.
+    ///
+    /// The range must not be longer than the granularity's [`max_range`](UsageGranularity::max_range).
+    #[instrument(
+        name = "audit_database.usage.list",
+        level = "debug",
+        skip_all,
+        fields(si.workspace.id = %workspace_id)
+    )]
+    pub async fn list(
+        context: &AuditDatabaseContext,
+        workspace_id: WorkspacePk,
+        granularity: UsageGranularity,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Self>> {
+        if start > end {
+            return Err(AuditDatabaseError::InvalidUsageRange(
+                "start must not be after end".to_string(),
+            ));
+        }
+        if end - start > granularity.max_range() {
+            return Err(AuditDatabaseError::InvalidUsageRange(format!(
+                "range must not be longer than {} days at {} granularity",
+                granularity.max_range().num_days(),
+                granularity.as_str(),
+            )));
+        }
+
+        let client = context.pg_pool().get().await?;
+        let workspace_id_string = workspace_id.to_string();
+
+        let mut rollups: BTreeMap<DateTime<Utc>, Self> = BTreeMap::new();
+        let empty_rollup = |bucket_start| Self {
+            workspace_id,
+            granularity,
+            bucket_start,
+            resource_count: None,
+            resources_created: 0,
+            resources_deleted: 0,
+            func_runs: Vec::new(),
+        };
+
+        for row in client
+            .query(
+                "SELECT * FROM workspace_usage_rollups
+                WHERE workspace_id = $1 AND granularity = $2 AND bucket_start >= $3 AND bucket_start < $4",
+                &[&workspace_id_string, &granularity.as_str(), &start, &end],
+            )
+            .await?
+        {
+            let bucket_start: DateTime<Utc> = row.try_get("bucket_start")?;
+            let rollup = rollups
+                .entry(bucket_start)
+                .or_insert_with(|| empty_rollup(bucket_start));
+            rollup.resource_count = row.try_get("resource_count")?;
+            rollup.resources_created = row.try_get("resources_created")?;
+            rollup.resources_deleted = row.try_get("resources_deleted")?;
+        }
+
+        for row in client
+            .query(
+                "SELECT * FROM workspace_func_run_usage_rollups
+                WHERE workspace_id = $1 AND granularity = $2 AND bucket_start >= $3 AND bucket_start < $4
+                ORDER BY func_kind",
+                &[&workspace_id_string, &granularity.as_str(), &start, &end],
+            )
+            .await?
+        {
+            let bucket_start: DateTime<Utc> = row.try_get("bucket_start")?;
+            let run_milliseconds: i64 = row.try_get("run_milliseconds")?;
+            rollups
+                .entry(bucket_start)
+                .or_insert_with(|| empty_rollup(bucket_start))
+                .func_runs
+                .push(FuncRunUsage {
+                    func_kind: row.try_get("func_kind")?,
+                    run_count: row.try_get("run_count")?,
+                    run_seconds: run_milliseconds as f64 / 1000.0,
+                });
+        }
+
+        Ok(rollups.into_values().collect())
+    }
+}
@@ -53,6 +53,7 @@ use si_events::{
     ChangeSetId,
     ChangeSetStatus,
     ComponentId,
+    FuncKind,
     FuncRunId,
     SchemaId,
     SchemaVariantId,
@@ -87,6 +88,8 @@ type BillingEventsResult<T> = Result<T, BillingEventsError>;
 pub enum BillingEventKind {
     /// An event that is published when a change set's status is updated.
     ChangeSetStatusUpdate,
+    /// An event that is published when a function run finishes, in any change set.
+    FuncRunComplete,
     /// An event that is published when the HEAD change set's pointer is updated.
     HeadChangeSetPointerUpdate,
     /// An event that is published when a resource is created.
@@ -127,6 +130,12 @@ pub struct BillingEvent {
     pub schema_name: Option<String>,
     /// The ID of the func run (conditional based on the event kind).
     pub func_run_id: Option<FuncRunId>,
+    /// The kind of the func that was run (conditional based on the event kind).
+    #[serde(default)]
+    pub func_kind: Option<FuncKind>,
+    /// How long the func run took, in milliseconds (conditional based on the event kind).
+    #[serde(default)]
+    pub func_run_duration_ms: Option<u64>,
 
     /// The kind of billing event.
     pub kind: BillingEventKind,
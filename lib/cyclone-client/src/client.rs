@@ -20,6 +20,7 @@ use cyclone_core::{
     CycloneRequestable,
     LivenessStatus,
     LivenessStatusParseError,
+    REPORT_RESOURCE_USAGE_QUERY,
     ReadinessStatus,
     ReadinessStatusParseError,
 };
@@ -305,7 +306,8 @@ where
     where
         Request: CycloneRequestable + Send + Sync,
     {
-        let stream = self.websocket_stream(request.websocket_path()).await?;
+        let path_and_query = format!("{}?{REPORT_RESOURCE_USAGE_QUERY}", request.websocket_path());
+        let stream = self.websocket_stream(path_and_query).await?;
         Ok(new_unstarted_execution(stream, request))
     }
 }
@@ -506,6 +508,7 @@ mod tests {
         ComponentViewWithGeometry,
         DebugRequest,
         FunctionResult,
+        FunctionRuntime,
         ManagementRequest,
         ProgressMessage,
         ResolverFunctionComponent,
@@ -837,6 +840,8 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            runtime: FunctionRuntime::LangJs,
+            limits: Default::default(),
         };
 
         // Start the protocol
@@ -947,6 +952,8 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            runtime: FunctionRuntime::LangJs,
+            limits: Default::default(),
         };
 
         // Start the protocol
@@ -1018,6 +1025,203 @@ mod tests {
         }
     }
 
+    /// A WebAssembly component whose `run` export returns its input unchanged.
+    const ECHO_COMPONENT_WAT: &str = r#"
+        (component
+            (core module $m
+                (memory (export "memory") 1)
+                (global $next (mut i32) (i32.const 1024))
+                (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (global.get $next))
+                    (global.set $next (i32.add (local.get $ptr) (local.get 3)))
+                    (local.get $ptr))
+                (func (export "run") (param i32 i32 i32 i32) (result i32)
+                    (i32.store8 (i32.const 0) (i32.const 0))
+                    (i32.store (i32.const 4) (local.get 2))
+                    (i32.store (i32.const 8) (local.get 3))
+                    (i32.const 0)))
+            (core instance $i (instantiate $m))
+            (func (export "run")
+                (param "handler" string)
+                (param "input" string)
+                (result (result string (error string)))
+                (canon lift
+                    (core func $i "run")
+                    (memory $i "memory")
+                    (realloc (func $i "cabi_realloc"))))
+        )
+    "#;
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_wasm_resolver() {
+        let mut builder = Config::builder();
+        let mut client = http_client_for_running_server(builder.enable_resolver(true)).await;
+
+        let req = ResolverFunctionRequest {
+            execution_id: "1234".to_string(),
+            handler: "doit".to_string(),
+            component: ResolverFunctionComponent {
+                data: ComponentView {
+                    properties: serde_json::json!({"salt": "n", "peppa": "pig"}),
+                    kind: ComponentKind::Standard,
+                },
+                parents: vec![],
+            },
+            response_type: cyclone_core::ResolverFunctionResponseType::Object,
+            code_base64: base64_encode(
+                wat::parse_str(ECHO_COMPONENT_WAT).expect("failed to parse component"),
+            ),
+            before: vec![],
+            runtime: FunctionRuntime::Wasm,
+            limits: Default::default(),
+        };
+
+        // Start the protocol
+        let mut progress = client
+            .prepare_execution(CycloneRequest::from_parts(req.clone(), Default::default()))
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        // The component writes no output
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(unexpected) => panic!("output stream should be done: {unexpected:?}"),
+            };
+        }
+        // The client asked for the resources used by the execution
+        assert!(
+            progress
+                .resource_usage()
+                .and_then(|usage| usage.execution_ms)
+                .is_some()
+        );
+        // Get the result
+        let result = progress.finish().await.expect("failed to return result");
+        match result {
+            FunctionResult::Success(success) => {
+                assert!(!success.unset);
+                assert_eq!(success.data, json!({"salt": "n", "peppa": "pig"}));
+            }
+            FunctionResult::Failure(failure) => {
+                panic!("result should be success; failure={failure:?}")
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_wasm_resolver_without_resource_usage() {
+        let mut builder = Config::builder();
+        let mut client = http_client_for_running_server(builder.enable_resolver(true)).await;
+
+        let req = ResolverFunctionRequest {
+            execution_id: "1234".to_string(),
+            handler: "doit".to_string(),
+            component: ResolverFunctionComponent {
+                data: ComponentView {
+                    properties: serde_json::json!({"salt": "n"}),
+                    kind: ComponentKind::Standard,
+                },
+                parents: vec![],
+            },
+            response_type: cyclone_core::ResolverFunctionResponseType::Object,
+            code_base64: base64_encode(
+                wat::parse_str(ECHO_COMPONENT_WAT).expect("failed to parse component"),
+            ),
+            before: vec![],
+            runtime: FunctionRuntime::Wasm,
+            limits: Default::default(),
+        };
+
+        // Connect the way clients which predate resource usage reporting do, without asking
+        // for it
+        let stream = client
+            .websocket_stream(req.websocket_path())
+            .await
+            .expect("failed to establish websocket stream");
+        let mut progress =
+            new_unstarted_execution(stream, CycloneRequest::from_parts(req, Default::default()))
+                .start()
+                .await
+                .expect("failed to start protocol");
+
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(unexpected) => panic!("output stream should be done: {unexpected:?}"),
+            };
+        }
+        assert_eq!(None, progress.resource_usage());
+        let result = progress.finish().await.expect("failed to return result");
+        match result {
+            FunctionResult::Success(success) => {
+                assert_eq!(success.data, json!({"salt": "n"}));
+            }
+            FunctionResult::Failure(failure) => {
+                panic!("result should be success; failure={failure:?}")
+            }
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+    async fn http_execute_wasm_resolver_checks_return_type() {
+        let mut builder = Config::builder();
+        let mut client = http_client_for_running_server(builder.enable_resolver(true)).await;
+
+        let req = ResolverFunctionRequest {
+            execution_id: "1234".to_string(),
+            handler: "doit".to_string(),
+            component: ResolverFunctionComponent {
+                data: ComponentView {
+                    properties: serde_json::json!({"salt": "n"}),
+                    kind: ComponentKind::Standard,
+                },
+                parents: vec![],
+            },
+            response_type: cyclone_core::ResolverFunctionResponseType::String,
+            code_base64: base64_encode(
+                wat::parse_str(ECHO_COMPONENT_WAT).expect("failed to parse component"),
+            ),
+            before: vec![],
+            runtime: FunctionRuntime::Wasm,
+            limits: Default::default(),
+        };
+
+        let mut progress = client
+            .prepare_execution(CycloneRequest::from_parts(req.clone(), Default::default()))
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(unexpected) => panic!("output stream should be done: {unexpected:?}"),
+            };
+        }
+        let result = progress.finish().await.expect("failed to return result");
+        match result {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => {
+                assert_eq!(failure.error().message, "Return type must be a string.");
+            }
+        }
+    }
+
     async fn execute_validation<C, Strm>(mut client: C)
     where
         Strm: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static,
@@ -1098,6 +1302,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            limits: Default::default(),
         };
 
         // Start the protocol
@@ -1193,6 +1398,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            limits: Default::default(),
         };
 
         // Start the protocol
@@ -1456,6 +1662,7 @@ mod tests {
             },
             components: HashMap::new(),
             variant_socket_map: HashMap::new(),
+            webhook_payload: None,
             code_base64: base64_encode(
                 r#"function manage(input) {
                     console.log('first');
@@ -1467,6 +1674,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            limits: Default::default(),
         };
 
         // Start the protocol
@@ -1550,6 +1758,7 @@ mod tests {
             },
             components: HashMap::new(),
             variant_socket_map: HashMap::new(),
+            webhook_payload: None,
             code_base64: base64_encode(
                 r#"function manage({ thisComponent }) {
                     console.log('first');
@@ -1561,6 +1770,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            limits: Default::default(),
         };
 
         // Start the protocol
@@ -10,6 +10,7 @@ use std::{
 use cyclone_core::{
     CycloneRequest,
     CycloneRequestable,
+    ExecutionResourceUsage,
     FunctionResult,
     Message,
     ProgressMessage,
@@ -130,6 +131,7 @@ where
         Self {
             stream: value.stream,
             result: None,
+            resource_usage: None,
         }
     }
 }
@@ -138,6 +140,14 @@ where
 pub struct ExecutionStarted<T, Success> {
     stream: WebSocketStream<T>,
     result: Option<FunctionResult<Success>>,
+    resource_usage: Option<ExecutionResourceUsage>,
+}
+
+impl<T, Success> ExecutionStarted<T, Success> {
+    /// Returns the resources consumed by the execution, once the server has reported them.
+    pub fn resource_usage(&self) -> Option<ExecutionResourceUsage> {
+        self.resource_usage
+    }
 }
 
 impl<T, Success> ExecutionStarted<T, Success>
@@ -180,6 +190,11 @@ where
                         Poll::Ready(Some(Ok(ProgressMessage::Heartbeat)))
                         //Poll::Pending
                     }
+                    // We got the resource usage of the execution, save it and continue
+                    Message::ResourceUsage(resource_usage) => {
+                        self.resource_usage = Some(resource_usage);
+                        Poll::Ready(Some(Ok(ProgressMessage::Heartbeat)))
+                    }
                     // We got a finish message
                     Message::Finish => {
                         if self.result.is_some() {
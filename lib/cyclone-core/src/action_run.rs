@@ -8,6 +8,7 @@ use telemetry_utils::metric;
 use crate::{
     BeforeFunction,
     CycloneRequestable,
+    ExecutionResourceLimits,
 };
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -18,6 +19,9 @@ pub struct ActionRunRequest {
     pub code_base64: String,
     pub args: serde_json::Value,
     pub before: Vec<BeforeFunction>,
+    /// Limits on the resources the execution may consume.
+    #[serde(default, skip_serializing_if = "ExecutionResourceLimits::is_unlimited")]
+    pub limits: ExecutionResourceLimits,
 }
 
 #[remain::sorted]
@@ -63,4 +67,8 @@ impl CycloneRequestable for ActionRunRequest {
     fn dec_run_metric(&self) {
         metric!(counter.function_run.action = -1);
     }
+
+    fn resource_limits(&self) -> ExecutionResourceLimits {
+        self.limits
+    }
 }
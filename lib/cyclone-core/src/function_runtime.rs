@@ -0,0 +1,23 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The runtime that cyclone uses to execute a function.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FunctionRuntime {
+    /// The function is JavaScript or TypeScript run by the lang-js server.
+    #[default]
+    LangJs,
+    /// The function is a base64 encoded WebAssembly component targeting the `si:function` world.
+    Wasm,
+}
+
+impl FunctionRuntime {
+    /// Returns true if the function is run by the lang-js server.
+    pub fn is_lang_js(&self) -> bool {
+        matches!(self, Self::LangJs)
+    }
+}
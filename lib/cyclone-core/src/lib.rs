@@ -16,6 +16,7 @@ mod before;
 mod canonical_command;
 mod component_view;
 mod debug;
+mod function_runtime;
 mod kill_execution;
 mod liveness;
 mod management;
@@ -24,6 +25,7 @@ mod progress;
 mod readiness;
 mod request;
 mod resolver_function;
+mod resource_usage;
 mod schema_variant_definition;
 mod sensitive_container;
 mod validation;
@@ -47,6 +49,7 @@ pub use debug::{
     DebugRequest,
     DebugResultSuccess,
 };
+pub use function_runtime::FunctionRuntime;
 pub use kill_execution::KillExecutionRequest;
 pub use liveness::{
     LivenessStatus,
@@ -80,6 +83,12 @@ pub use resolver_function::{
     ResolverFunctionResponseType,
     ResolverFunctionResultSuccess,
 };
+pub use resource_usage::{
+    ExecutionParams,
+    ExecutionResourceLimits,
+    ExecutionResourceUsage,
+    REPORT_RESOURCE_USAGE_QUERY,
+};
 pub use schema_variant_definition::{
     SchemaVariantDefinitionRequest,
     SchemaVariantDefinitionResultSuccess,
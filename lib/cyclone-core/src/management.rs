@@ -10,6 +10,7 @@ use telemetry_utils::metric;
 use crate::{
     BeforeFunction,
     CycloneRequestable,
+    ExecutionResourceLimits,
     component_view::ComponentViewWithGeometry,
 };
 
@@ -23,7 +24,13 @@ pub struct ManagementRequest {
     pub this_component: ComponentViewWithGeometry,
     pub components: HashMap<String, ComponentViewWithGeometry>,
     pub variant_socket_map: HashMap<String, usize>,
+    /// The payload delivered to the webhook that triggered this run, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_payload: Option<serde_json::Value>,
     pub before: Vec<BeforeFunction>,
+    /// Limits on the resources the execution may consume.
+    #[serde(default, skip_serializing_if = "ExecutionResourceLimits::is_unlimited")]
+    pub limits: ExecutionResourceLimits,
 }
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -65,4 +72,8 @@ impl CycloneRequestable for ManagementRequest {
     fn dec_run_metric(&self) {
         metric!(counter.function_run.management = -1);
     }
+
+    fn resource_limits(&self) -> ExecutionResourceLimits {
+        self.limits
+    }
 }
@@ -5,6 +5,8 @@ use serde::{
 };
 use strum::Display;
 
+use crate::ExecutionResourceUsage;
+
 /// A line of output, streamed from an executing function.
 ///
 /// An instance of this type typically maps to a single line of output from a process--either on
@@ -63,6 +65,8 @@ pub enum Message<R> {
     Finish,
     Heartbeat,
     OutputStream(OutputStream),
+    /// The resources consumed by the execution, sent after the result and before finishing.
+    ResourceUsage(ExecutionResourceUsage),
     Result(FunctionResult<R>),
     Start,
 }
@@ -154,6 +158,7 @@ pub enum FunctionResultFailureErrorKind {
     ActionFieldWrongType,
     InvalidReturnType,
     KilledExecution,
+    ResourceLimitExceeded,
     UserCodeException(String),
     VeritechServer,
 }
@@ -7,6 +7,8 @@ use serde::{
 use si_crypto::SensitiveStrings;
 use si_std::SensitiveString;
 
+use crate::ExecutionResourceLimits;
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CycloneRequest<R>
@@ -32,6 +34,10 @@ where
         self.request.websocket_path()
     }
 
+    pub fn request(&self) -> &R {
+        &self.request
+    }
+
     pub fn into_parts(self) -> (R, SensitiveStrings) {
         (self.request, self.sensitive_strings.into())
     }
@@ -45,4 +51,9 @@ pub trait CycloneRequestable {
     fn websocket_path(&self) -> &str;
     fn inc_run_metric(&self);
     fn dec_run_metric(&self);
+
+    /// The limits on the resources the execution may consume.
+    fn resource_limits(&self) -> ExecutionResourceLimits {
+        ExecutionResourceLimits::default()
+    }
 }
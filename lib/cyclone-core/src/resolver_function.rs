@@ -8,6 +8,8 @@ use telemetry_utils::metric;
 
 use crate::{
     ComponentView,
+    ExecutionResourceLimits,
+    FunctionRuntime,
     before::BeforeFunction,
     request::CycloneRequestable,
 };
@@ -21,6 +23,11 @@ pub struct ResolverFunctionRequest {
     pub response_type: ResolverFunctionResponseType,
     pub code_base64: String,
     pub before: Vec<BeforeFunction>,
+    #[serde(default, skip_serializing_if = "FunctionRuntime::is_lang_js")]
+    pub runtime: FunctionRuntime,
+    /// Limits on the resources the execution may consume.
+    #[serde(default, skip_serializing_if = "ExecutionResourceLimits::is_unlimited")]
+    pub limits: ExecutionResourceLimits,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
@@ -85,4 +92,8 @@ impl CycloneRequestable for ResolverFunctionRequest {
     fn dec_run_metric(&self) {
         metric!(counter.function_run.resolver = -1);
     }
+
+    fn resource_limits(&self) -> ExecutionResourceLimits {
+        self.limits
+    }
 }
@@ -0,0 +1,135 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The resources consumed by a single function execution.
+///
+/// Each measurement is optional since not every runtime can measure every resource (for example,
+/// CPU time is unknown for WebAssembly functions, which run inside the cyclone process itself).
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionResourceUsage {
+    /// Wall-clock time the request waited for a cyclone instance before executing, in
+    /// milliseconds.
+    ///
+    /// Measured by veritech rather than cyclone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queued_ms: Option<u64>,
+    /// Wall-clock time spent executing the function, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_ms: Option<u64>,
+    /// CPU time (user and system) spent executing the function, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_time_ms: Option<u64>,
+    /// The peak memory used while executing the function, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_memory_bytes: Option<u64>,
+}
+
+/// The query string a client adds to an execution's websocket path to ask for a
+/// [`Message::ResourceUsage`](crate::Message::ResourceUsage) message before the execution
+/// finishes.
+///
+/// Clients which don't ask for it (such as clients predating the message) never receive it.
+pub const REPORT_RESOURCE_USAGE_QUERY: &str = "reportResourceUsage=true";
+
+/// Query parameters accepted when starting an execution.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionParams {
+    /// Whether the client understands, and wants, the resource usage of the execution.
+    #[serde(default)]
+    pub report_resource_usage: bool,
+}
+
+/// Limits on the resources a single function execution may consume.
+///
+/// An execution exceeding one of its limits is stopped and fails with
+/// [`FunctionResultFailureErrorKind::ResourceLimitExceeded`](crate::FunctionResultFailureErrorKind::ResourceLimitExceeded).
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionResourceLimits {
+    /// The maximum memory the function may use, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_bytes: Option<u64>,
+    /// The maximum CPU time (user and system) the function may use, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_time_ms: Option<u64>,
+}
+
+impl ExecutionResourceLimits {
+    /// Returns true if no limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_memory_bytes.is_none() && self.max_cpu_time_ms.is_none()
+    }
+
+    /// Returns a description of the first limit exceeded by the given measurements, if any.
+    pub fn exceeded_by(
+        &self,
+        cpu_time_ms: Option<u64>,
+        memory_bytes: Option<u64>,
+    ) -> Option<String> {
+        match (self.max_cpu_time_ms, cpu_time_ms) {
+            (Some(max), Some(used)) if used > max => {
+                return Some(format!(
+                    "function exceeded its CPU time limit of {max}ms (used {used}ms)"
+                ));
+            }
+            _ => {}
+        }
+        match (self.max_memory_bytes, memory_bytes) {
+            (Some(max), Some(used)) if used > max => Some(format!(
+                "function exceeded its memory limit of {max} bytes (used {used} bytes)"
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_limits_are_never_exceeded() {
+        let limits = ExecutionResourceLimits::default();
+
+        assert!(limits.is_unlimited());
+        assert_eq!(None, limits.exceeded_by(Some(u64::MAX), Some(u64::MAX)));
+    }
+
+    #[test]
+    fn limits_are_exceeded_only_above_their_maximum() {
+        let limits = ExecutionResourceLimits {
+            max_memory_bytes: Some(1024),
+            max_cpu_time_ms: Some(100),
+        };
+
+        assert!(!limits.is_unlimited());
+        assert_eq!(None, limits.exceeded_by(Some(100), Some(1024)));
+        assert_eq!(None, limits.exceeded_by(None, None));
+        assert_eq!(
+            Some("function exceeded its CPU time limit of 100ms (used 101ms)".to_string()),
+            limits.exceeded_by(Some(101), Some(2048))
+        );
+        assert_eq!(
+            Some("function exceeded its memory limit of 1024 bytes (used 1025 bytes)".to_string()),
+            limits.exceeded_by(Some(100), Some(1025))
+        );
+    }
+
+    #[test]
+    fn resource_usage_is_only_reported_when_asked_for() {
+        let params: ExecutionParams = serde_json::from_str("{}").expect("failed to parse");
+        assert!(!params.report_resource_usage);
+
+        let params: ExecutionParams =
+            serde_json::from_str(r#"{"reportResourceUsage":true}"#).expect("failed to parse");
+        assert!(params.report_resource_usage);
+        assert_eq!(
+            Some(("reportResourceUsage", "true")),
+            REPORT_RESOURCE_USAGE_QUERY.split_once('=')
+        );
+    }
+}
@@ -78,6 +78,9 @@ pub struct Config {
 
     #[builder(setter(into), default = "false")]
     enable_process_gatherer: bool,
+
+    #[builder(default)]
+    wasm_http_allowlist: Vec<String>,
 }
 
 impl Config {
@@ -176,6 +179,12 @@ impl Config {
     pub fn enable_process_gatherer(&self) -> bool {
         self.enable_process_gatherer
     }
+
+    /// Gets a reference to the config's hosts that WebAssembly functions may send HTTP requests to.
+    #[must_use]
+    pub fn wasm_http_allowlist(&self) -> &[String] {
+        &self.wasm_http_allowlist
+    }
 }
 
 impl ConfigBuilder {
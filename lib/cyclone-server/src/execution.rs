@@ -16,6 +16,7 @@ use bytes_lines_codec::BytesLinesCodec;
 use cyclone_core::{
     CycloneRequest,
     CycloneRequestable,
+    ExecutionResourceUsage,
     FunctionResult,
     FunctionResultFailure,
     FunctionResultFailureError,
@@ -64,6 +65,7 @@ use tokio_util::codec::{
 
 use crate::{
     WebSocketMessage,
+    resource_monitor::ResourceMonitor,
     state::LangServerChild,
 };
 
@@ -135,6 +137,15 @@ where
     success_marker: PhantomData<Success>,
 }
 
+impl<Request, LangServerSuccess, Success> Execution<Request, LangServerSuccess, Success>
+where
+    Request: CycloneRequestable,
+{
+    pub fn lang_server_process_timeout(&self) -> Duration {
+        self.lang_server_process_timeout
+    }
+}
+
 impl<Request, LangServerSuccess, Success> Execution<Request, LangServerSuccess, Success>
 where
     Request: Serialize + DeserializeOwned + Unpin + core::fmt::Debug + CycloneRequestable,
@@ -146,13 +157,30 @@ where
         child: LangServerChild,
         ws: &mut WebSocket,
     ) -> Result<ExecutionStarted<LangServerSuccess, Success>> {
+        let cyclone_request = self.receive_request(ws).await?;
+        self.start_with_request(child, cyclone_request).await
+    }
+
+    /// Sends the start message and reads the request from the web socket, which lets a handler
+    /// inspect the request before deciding how to run it.
+    pub async fn receive_request(&self, ws: &mut WebSocket) -> Result<CycloneRequest<Request>> {
         // Send start is the initial communication before we read the request.
         Self::ws_send_start(ws).await?;
         // Read the request message from the web socket
-        let cyclone_request = Self::read_request(ws).await?;
+        Self::read_request(ws).await
+    }
+
+    /// Sends an already received request to the lang server child process.
+    pub async fn start_with_request(
+        self,
+        child: LangServerChild,
+        cyclone_request: CycloneRequest<Request>,
+    ) -> Result<ExecutionStarted<LangServerSuccess, Success>> {
         let (request, sensitive_strings) = cyclone_request.into_parts();
+        let execution_id = request.execution_id().to_owned();
         let inner = child.inner();
         let mut child_lock = inner.lock().await;
+        let monitor = ResourceMonitor::start(child_lock.id(), request.resource_limits());
 
         let stdin = child_lock
             .stdin
@@ -179,6 +207,8 @@ where
 
         Ok(ExecutionStarted {
             child,
+            execution_id,
+            monitor,
             stdout,
             stderr,
             sensitive_strings: Arc::new(sensitive_strings),
@@ -245,6 +275,8 @@ type SiJsonError<S> = <SymmetricalJson<SiMessage<S>> as Deserializer<SiMessage<S
 #[derive(Debug)]
 pub struct ExecutionStarted<LangServerSuccess, Success> {
     child: LangServerChild,
+    execution_id: String,
+    monitor: ResourceMonitor,
     stdout: SiFramed<SiMessage<LangServerSuccess>>,
     stderr: FramedRead<ChildStderr, BytesLinesCodec>,
     sensitive_strings: Arc<SensitiveStrings>,
@@ -319,10 +351,33 @@ where
             Result::<_>::Ok(())
         };
 
+        let limited_loop = async {
+            tokio::select! {
+                execution = receive_loop => Ok(execution),
+                message = self.monitor.limit_exceeded() => Err(message),
+            }
+        };
+
         let inner = self.child.inner();
         let mut child_lock = inner.lock().await;
-        match timeout(self.lang_server_process_timeout, receive_loop).await {
-            Ok(execution) => execution?,
+        let outcome = timeout(self.lang_server_process_timeout, limited_loop).await;
+        match outcome {
+            Ok(Ok(execution)) => execution?,
+            Ok(Err(message)) => {
+                // Exceeded a resource limit, measure what was used and then kill the child process
+                let resource_usage = self.monitor.usage();
+                process::child_shutdown(&mut child_lock, Some(process::Signal::SIGKILL), None)
+                    .await?;
+                drop(child_lock);
+
+                warn!(
+                    si.func_run.id = %self.execution_id,
+                    "shutdown child process due to exceeded resource limit: {message}"
+                );
+                Self::ws_send_limit_exceeded(ws, &self.execution_id, message).await?;
+
+                return Ok(ExecutionClosing::new(self.child, Some(resource_usage)));
+            }
             Err(err) => {
                 // Exceeded timeout, shutdown child process
                 process::child_shutdown(&mut child_lock, Some(process::Signal::SIGTERM), None)
@@ -335,11 +390,36 @@ where
                 ));
             }
         };
+        drop(child_lock);
 
-        Ok(ExecutionClosing {
-            child: self.child,
-            success_marker: PhantomData,
-        })
+        Ok(ExecutionClosing::new(
+            self.child,
+            Some(self.monitor.usage()),
+        ))
+    }
+
+    async fn ws_send_limit_exceeded(
+        ws: &mut WebSocket,
+        execution_id: &str,
+        message: String,
+    ) -> Result<()> {
+        let result = FunctionResult::<Success>::Failure(FunctionResultFailure::new(
+            execution_id,
+            FunctionResultFailureError {
+                kind: FunctionResultFailureErrorKind::ResourceLimitExceeded,
+                message,
+            },
+            crate::timestamp(),
+        ));
+        let msg = Message::Result(result)
+            .serialize_to_string()
+            .map_err(ExecutionError::JSONSerialize)?;
+
+        time::timeout(TX_TIMEOUT_SECS, ws.send(WebSocketMessage::Text(msg)))
+            .await
+            .map_err(ExecutionError::SendTimeout)?
+            .map_err(ExecutionError::WSSendIO)?;
+        Ok(())
     }
 
     fn filter_output(
@@ -358,18 +438,7 @@ where
         sensitive_strings: &SensitiveStrings,
     ) -> Result<()> {
         let mut value = serde_json::to_value(&result).map_err(ExecutionError::JSONSerialize)?;
-
-        let mut work_queue = vec![&mut value];
-        while let Some(work) = work_queue.pop() {
-            match work {
-                Value::Array(values) => work_queue.extend(values),
-                Value::Object(object) => object.values_mut().for_each(|v| work_queue.push(v)),
-                Value::String(string) if sensitive_strings.has_sensitive(string) => {
-                    *string = sensitive_strings.redact(string);
-                }
-                Value::String(_) | Value::Null | Value::Number(_) | Value::Bool(_) => {}
-            }
-        }
+        redact_value(&mut value, sensitive_strings);
 
         let mut filtered_result: LangServerResult<LangServerSuccess> =
             serde_json::from_value(value).map_err(ExecutionError::JSONDeserialize)?;
@@ -378,20 +447,55 @@ where
     }
 }
 
+/// Redacts any sensitive strings found anywhere within the value.
+pub(crate) fn redact_value(value: &mut Value, sensitive_strings: &SensitiveStrings) {
+    let mut work_queue = vec![value];
+    while let Some(work) = work_queue.pop() {
+        match work {
+            Value::Array(values) => work_queue.extend(values),
+            Value::Object(object) => object.values_mut().for_each(|v| work_queue.push(v)),
+            Value::String(string) if sensitive_strings.has_sensitive(string) => {
+                *string = sensitive_strings.redact(string);
+            }
+            Value::String(_) | Value::Null | Value::Number(_) | Value::Bool(_) => {}
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ExecutionClosing<Success> {
     child: LangServerChild,
+    resource_usage: Option<ExecutionResourceUsage>,
     success_marker: PhantomData<Success>,
 }
 
+impl<Success> ExecutionClosing<Success> {
+    /// Creates a closing execution which reports the resources used by the execution (if they
+    /// were measured and the client asked for them) and shuts down the child process once the
+    /// execution finishes.
+    pub(crate) fn new(
+        child: LangServerChild,
+        resource_usage: Option<ExecutionResourceUsage>,
+    ) -> Self {
+        Self {
+            child,
+            resource_usage,
+            success_marker: PhantomData,
+        }
+    }
+}
+
 impl<Success> ExecutionClosing<Success>
 where
     Success: Serialize,
 {
-    pub async fn finish(self, mut ws: WebSocket) -> Result<()> {
+    /// Finishes the execution, only sending its resource usage when `report_resource_usage` is
+    /// set since clients which didn't ask for it may not understand the message.
+    pub async fn finish(self, mut ws: WebSocket, report_resource_usage: bool) -> Result<()> {
         let inner = self.child.inner();
         let mut child_lock = inner.lock().await;
-        let finished = Self::ws_send_finish(&mut ws).await;
+        let resource_usage = self.resource_usage.filter(|_| report_resource_usage);
+        let finished = Self::ws_send_finish(&mut ws, resource_usage).await;
         let closed = Self::ws_close(ws).await;
         let shutdown =
             process::child_shutdown(&mut child_lock, Some(process::Signal::SIGTERM), None)
@@ -432,7 +536,20 @@ where
         }
     }
 
-    async fn ws_send_finish(ws: &mut WebSocket) -> Result<()> {
+    async fn ws_send_finish(
+        ws: &mut WebSocket,
+        resource_usage: Option<ExecutionResourceUsage>,
+    ) -> Result<()> {
+        if let Some(resource_usage) = resource_usage {
+            let msg = Message::<Success>::ResourceUsage(resource_usage)
+                .serialize_to_string()
+                .map_err(ExecutionError::JSONSerialize)?;
+            time::timeout(TX_TIMEOUT_SECS, ws.send(WebSocketMessage::Text(msg)))
+                .await
+                .map_err(ExecutionError::SendTimeout)?
+                .map_err(ExecutionError::WSSendIO)?;
+        }
+
         let msg = Message::<Success>::Finish
             .serialize_to_string()
             .map_err(ExecutionError::JSONSerialize)?;
@@ -10,6 +10,7 @@ use std::{
 use axum::{
     extract::{
         Extension,
+        Query,
         State,
         WebSocketUpgrade,
         ws::{
@@ -25,6 +26,8 @@ use cyclone_core::{
     CycloneRequestable,
     DebugRequest,
     DebugResultSuccess,
+    ExecutionParams,
+    FunctionRuntime,
     LivenessStatus,
     ManagementRequest,
     ManagementResultSuccess,
@@ -62,6 +65,7 @@ use crate::{
         LangServerProcessTimeout,
         WatchKeepalive,
     },
+    wasm::WasmRuntime,
     watch,
 };
 
@@ -130,22 +134,20 @@ pub async fn ws_execute_resolver(
     wsu: WebSocketUpgrade,
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
     State(child): State<LangServerChild>,
+    State(wasm_runtime): State<WasmRuntime>,
     limit_request_guard: LimitRequestGuard,
+    Query(params): Query<ExecutionParams>,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
     wsu.on_upgrade(move |socket| {
-        let request: PhantomData<ResolverFunctionRequest> = PhantomData;
-        let lang_server_success: PhantomData<LangServerResolverFunctionResultSuccess> = PhantomData;
-        let success: PhantomData<ResolverFunctionResultSuccess> = PhantomData;
-        handle_socket(
+        handle_resolver_socket(
             socket,
             lang_server_process_timeout.inner(),
             limit_request_guard,
-            request,
-            lang_server_success,
-            success,
             request_span.into_inner(),
             child,
+            wasm_runtime,
+            params.report_resource_usage,
         )
     })
 }
@@ -155,6 +157,7 @@ pub async fn ws_execute_validation(
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
     State(child): State<LangServerChild>,
     limit_request_guard: LimitRequestGuard,
+    Query(params): Query<ExecutionParams>,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
     wsu.on_upgrade(move |socket| {
@@ -170,6 +173,7 @@ pub async fn ws_execute_validation(
             success,
             request_span.into_inner(),
             child,
+            params.report_resource_usage,
         )
     })
 }
@@ -179,6 +183,7 @@ pub async fn ws_execute_action_run(
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
     State(child): State<LangServerChild>,
     limit_request_guard: LimitRequestGuard,
+    Query(params): Query<ExecutionParams>,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
     wsu.on_upgrade(move |socket| {
@@ -194,6 +199,7 @@ pub async fn ws_execute_action_run(
             success,
             request_span.into_inner(),
             child,
+            params.report_resource_usage,
         )
     })
 }
@@ -203,6 +209,7 @@ pub async fn ws_execute_schema_variant_definition(
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
     State(child): State<LangServerChild>,
     limit_request_guard: LimitRequestGuard,
+    Query(params): Query<ExecutionParams>,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
     wsu.on_upgrade(move |socket| {
@@ -218,6 +225,7 @@ pub async fn ws_execute_schema_variant_definition(
             success,
             request_span.into_inner(),
             child,
+            params.report_resource_usage,
         )
     })
 }
@@ -227,6 +235,7 @@ pub async fn ws_execute_management(
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
     State(child): State<LangServerChild>,
     limit_request_guard: LimitRequestGuard,
+    Query(params): Query<ExecutionParams>,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
     wsu.on_upgrade(move |socket| {
@@ -242,6 +251,7 @@ pub async fn ws_execute_management(
             success,
             request_span.into_inner(),
             child,
+            params.report_resource_usage,
         )
     })
 }
@@ -251,6 +261,7 @@ pub async fn ws_execute_debug(
     State(lang_server_process_timeout): State<LangServerProcessTimeout>,
     State(child): State<LangServerChild>,
     limit_request_guard: LimitRequestGuard,
+    Query(params): Query<ExecutionParams>,
     Extension(request_span): Extension<ParentSpan>,
 ) -> impl IntoResponse {
     wsu.on_upgrade(move |socket| {
@@ -266,6 +277,7 @@ pub async fn ws_execute_debug(
             success,
             request_span.into_inner(),
             child,
+            params.report_resource_usage,
         )
     })
 }
@@ -287,6 +299,7 @@ async fn handle_socket<Request, LangServerSuccess, Success>(
     success_marker: PhantomData<Success>,
     request_span: Span,
     child: LangServerChild,
+    report_resource_usage: bool,
 ) where
     Request: Serialize + DeserializeOwned + Unpin + fmt::Debug + CycloneRequestable,
     Success: Serialize + Unpin + fmt::Debug,
@@ -334,7 +347,103 @@ async fn handle_socket<Request, LangServerSuccess, Success>(
             return;
         }
     };
-    if let Err(err) = proto.finish(socket).await {
+    if let Err(err) = proto.finish(socket, report_resource_usage).await {
+        request_span.record_err(&err);
+        warn!(si.error.message = ?err, "failed to finish protocol");
+        return;
+    }
+
+    request_span.record_ok();
+}
+
+/// Handles a resolver function execution, which may either be run by the lang server or, when
+/// the request asks for it, as a WebAssembly component.
+async fn handle_resolver_socket(
+    mut socket: WebSocket,
+    lang_server_process_timeout: Option<u64>,
+    _limit_request_guard: LimitRequestGuard,
+    request_span: Span,
+    child: LangServerChild,
+    wasm_runtime: WasmRuntime,
+    report_resource_usage: bool,
+) {
+    let success_marker: PhantomData<ResolverFunctionResultSuccess> = PhantomData;
+    let execution: Execution<
+        ResolverFunctionRequest,
+        LangServerResolverFunctionResultSuccess,
+        ResolverFunctionResultSuccess,
+    > = execution::new(lang_server_process_timeout);
+
+    let cyclone_request = match execution.receive_request(&mut socket).await {
+        Ok(cyclone_request) => cyclone_request,
+        Err(err) => {
+            warn!(si.error.message = ?err, "failed to start protocol");
+            request_span.record_err(&err);
+            if let Err(err) =
+                fail_to_process(socket, "failed to start protocol", success_marker).await
+            {
+                warn!(error = ?err, "failed to fail execute resolver function");
+            };
+            return;
+        }
+    };
+
+    let proto = if cyclone_request.request().runtime == FunctionRuntime::Wasm {
+        let timeout = execution.lang_server_process_timeout();
+        let (request, sensitive_strings) = cyclone_request.into_parts();
+        match wasm_runtime
+            .execute_resolver(child, request, sensitive_strings, timeout, &mut socket)
+            .await
+        {
+            Ok(closing) => closing,
+            Err(err) => {
+                warn!(si.error.message = ?err, "failed to execute wasm function");
+                request_span.record_err(&err);
+                if let Err(err) = fail_to_process(
+                    socket,
+                    format!("failed to execute wasm function: {err:?}"),
+                    success_marker,
+                )
+                .await
+                {
+                    warn!(error = ?err, "failed to fail execute resolver function");
+                };
+                return;
+            }
+        }
+    } else {
+        let started = match execution.start_with_request(child, cyclone_request).await {
+            Ok(started) => started,
+            Err(err) => {
+                warn!(si.error.message = ?err, "failed to start protocol");
+                request_span.record_err(&err);
+                if let Err(err) =
+                    fail_to_process(socket, "failed to start protocol", success_marker).await
+                {
+                    warn!(error = ?err, "failed to fail execute resolver function");
+                };
+                return;
+            }
+        };
+        match started.process(&mut socket).await {
+            Ok(processed) => processed,
+            Err(err) => {
+                warn!(si.error.message = ?err, "failed to process protocol");
+                request_span.record_err(&err);
+                if let Err(err) = fail_to_process(
+                    socket,
+                    format!("failed to process protocol: {err:?}"),
+                    success_marker,
+                )
+                .await
+                {
+                    warn!(error = ?err, "failed to fail execute resolver function");
+                };
+                return;
+            }
+        }
+    };
+    if let Err(err) = proto.finish(socket, report_resource_usage).await {
         request_span.record_err(&err);
         warn!(si.error.message = ?err, "failed to finish protocol");
         return;
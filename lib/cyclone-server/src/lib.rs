@@ -4,6 +4,7 @@ mod extract;
 mod handlers;
 #[cfg(target_os = "linux")]
 pub mod process_gatherer;
+mod resource_monitor;
 mod result;
 mod routes;
 mod server;
@@ -13,6 +14,7 @@ mod tower;
 mod uds;
 #[cfg(target_os = "linux")]
 mod vsock;
+mod wasm;
 mod watch;
 
 pub use axum::extract::ws::Message as WebSocketMessage;
@@ -0,0 +1,236 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{
+        AtomicU64,
+        Ordering,
+    },
+    time::Duration,
+};
+
+use cyclone_core::{
+    ExecutionResourceLimits,
+    ExecutionResourceUsage,
+};
+use tokio::time::{
+    self,
+    Instant,
+};
+
+/// How often the resource usage of a running function is sampled to enforce its limits.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Measures, and enforces limits on, the resources used by the lang server process while it runs
+/// a function.
+///
+/// CPU time is measured relative to when monitoring started so that the work done by the lang
+/// server while booting isn't attributed to the function. Memory is the total resident set size
+/// of the lang server process and all of its descendants, since functions may spawn processes of
+/// their own. Measurements are only available on Linux.
+#[derive(Debug)]
+pub(crate) struct ResourceMonitor {
+    pid: Option<u32>,
+    limits: ExecutionResourceLimits,
+    started_at: Instant,
+    baseline_cpu_time_ms: Option<u64>,
+    peak_memory_bytes: AtomicU64,
+}
+
+impl ResourceMonitor {
+    /// Starts monitoring the process with the given id.
+    pub(crate) fn start(pid: Option<u32>, limits: ExecutionResourceLimits) -> Self {
+        Self {
+            pid,
+            limits,
+            started_at: Instant::now(),
+            baseline_cpu_time_ms: pid.and_then(process_cpu_time_ms),
+            peak_memory_bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Resolves once the process exceeds one of its limits, with a description of the exceeded
+    /// limit. Never resolves if there are no limits to enforce.
+    ///
+    /// Memory is sampled while this runs (even without limits) so that the peak memory used by
+    /// short-lived descendant processes is reported.
+    pub(crate) async fn limit_exceeded(&self) -> String {
+        if self.pid.is_none() {
+            return std::future::pending().await;
+        }
+
+        let mut interval = time::interval(SAMPLE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let memory_bytes = self.sample_memory_bytes();
+            if let Some(message) = self.limits.exceeded_by(self.cpu_time_ms(), memory_bytes) {
+                return message;
+            }
+        }
+    }
+
+    /// Returns the resources used since monitoring started.
+    pub(crate) fn usage(&self) -> ExecutionResourceUsage {
+        ExecutionResourceUsage {
+            queued_ms: None,
+            execution_ms: Some(duration_ms(self.started_at.elapsed())),
+            cpu_time_ms: self.cpu_time_ms(),
+            peak_memory_bytes: self.peak_memory_bytes(),
+        }
+    }
+
+    /// Measures the memory currently used by the process tree, recording it if it's a new peak.
+    fn sample_memory_bytes(&self) -> Option<u64> {
+        let memory_bytes = self.pid.and_then(process_tree_memory_bytes)?;
+        self.peak_memory_bytes
+            .fetch_max(memory_bytes, Ordering::Relaxed);
+        Some(memory_bytes)
+    }
+
+    /// Returns the largest of the sampled process tree totals and the peak of the lang server
+    /// process itself, which covers any spike between samples.
+    fn peak_memory_bytes(&self) -> Option<u64> {
+        let sampled = self.sample_memory_bytes();
+        let process_peak = self.pid.and_then(process_peak_memory_bytes);
+        if sampled.is_none() && process_peak.is_none() {
+            return None;
+        }
+
+        Some(
+            self.peak_memory_bytes
+                .load(Ordering::Relaxed)
+                .max(process_peak.unwrap_or_default()),
+        )
+    }
+
+    fn cpu_time_ms(&self) -> Option<u64> {
+        let cpu_time_ms = self.pid.and_then(process_cpu_time_ms)?;
+        Some(cpu_time_ms.saturating_sub(self.baseline_cpu_time_ms.unwrap_or_default()))
+    }
+}
+
+pub(crate) fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Returns the CPU time used by the process and the children it has waited on, in milliseconds.
+#[cfg(target_os = "linux")]
+fn process_cpu_time_ms(pid: u32) -> Option<u64> {
+    let stat = procfs::process::Process::new(i32::try_from(pid).ok()?)
+        .ok()?
+        .stat()
+        .ok()?;
+    let children_ticks = u64::try_from(stat.cutime.saturating_add(stat.cstime)).unwrap_or(0);
+    let ticks = stat
+        .utime
+        .saturating_add(stat.stime)
+        .saturating_add(children_ticks);
+
+    Some(ticks.saturating_mul(1000) / procfs::ticks_per_second().max(1))
+}
+
+/// Returns the ids of the given process and all of its descendants, given the parent of every
+/// process.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn process_tree(pid: i32, parents: &HashMap<i32, i32>) -> Vec<i32> {
+    let mut children: HashMap<i32, Vec<i32>> = HashMap::new();
+    for (&child, &parent) in parents {
+        children.entry(parent).or_default().push(child);
+    }
+
+    let mut tree = Vec::new();
+    let mut work_queue = vec![pid];
+    while let Some(pid) = work_queue.pop() {
+        tree.push(pid);
+        if let Some(children) = children.get(&pid) {
+            work_queue.extend(
+                children
+                    .iter()
+                    .copied()
+                    .filter(|child| !tree.contains(child)),
+            );
+        }
+    }
+    tree
+}
+
+/// Returns the total resident set size of the process and all of its descendants, in bytes.
+#[cfg(target_os = "linux")]
+fn process_tree_memory_bytes(pid: u32) -> Option<u64> {
+    let pid = i32::try_from(pid).ok()?;
+    let processes: HashMap<i32, procfs::process::Process> = procfs::process::all_processes()
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|process| (process.pid, process))
+        .collect();
+    let parents = processes
+        .iter()
+        .filter_map(|(&pid, process)| Some((pid, process.stat().ok()?.ppid)))
+        .collect();
+
+    // Processes may exit while the tree is walked, so only the root must still be running
+    let root = processes.get(&pid)?.status().ok()?.vmrss?;
+    let descendants: u64 = process_tree(pid, &parents)
+        .into_iter()
+        .filter(|&tree_pid| tree_pid != pid)
+        .filter_map(|tree_pid| processes.get(&tree_pid)?.status().ok()?.vmrss)
+        .sum();
+
+    Some(root.saturating_add(descendants).saturating_mul(1024))
+}
+
+#[cfg(target_os = "linux")]
+fn process_peak_memory_bytes(pid: u32) -> Option<u64> {
+    let status = procfs::process::Process::new(i32::try_from(pid).ok()?)
+        .ok()?
+        .status()
+        .ok()?;
+    status.vmhwm.map(|kib| kib.saturating_mul(1024))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_cpu_time_ms(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_tree_memory_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_peak_memory_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_tree_includes_all_descendants() {
+        let parents = HashMap::from([(2, 1), (3, 2), (4, 2), (5, 3), (6, 1), (7, 6)]);
+
+        let mut tree = process_tree(2, &parents);
+        tree.sort();
+
+        assert_eq!(vec![2, 3, 4, 5], tree);
+        assert_eq!(vec![7], process_tree(7, &parents));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn process_tree_memory_includes_child_processes() {
+        let pid = std::process::id();
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("failed to spawn child");
+        let child_memory = process_tree_memory_bytes(child.id()).expect("failed to measure child");
+        let with_child = process_tree_memory_bytes(pid).expect("failed to measure memory");
+        child.kill().expect("failed to kill child");
+        child.wait().expect("failed to wait on child");
+
+        assert!(child_memory > 0);
+        assert!(with_child > child_memory);
+    }
+}
@@ -38,6 +38,10 @@ use crate::{
     execution::ExecutionError,
     routes::routes,
     state::AppState,
+    wasm::{
+        WasmError,
+        WasmRuntime,
+    },
 };
 #[cfg(target_os = "linux")]
 use crate::{
@@ -59,6 +63,8 @@ pub enum ServerError {
     #[cfg(target_os = "linux")]
     #[error("Vsock incoming stream error")]
     Vsock(#[from] VsockIncomingStreamError),
+    #[error("wasm error: {0}")]
+    Wasm(#[from] WasmError),
     #[error("wrong incoming stream for {0} server: {1:?}")]
     WrongIncomingStream(&'static str, IncomingStream),
 }
@@ -238,11 +244,14 @@ async fn build_service(
 ) -> Result<(IntoMakeService<Router>, oneshot::Receiver<()>)> {
     let (shutdown_tx, shutdown_rx) = mpsc::channel(4);
 
+    let wasm_runtime = WasmRuntime::new(config.wasm_http_allowlist().iter().cloned())?;
+
     let state = AppState::new(
         config.lang_server_path(),
         telemetry_level,
         config.lang_server_function_timeout(),
         config.lang_server_process_timeout(),
+        wasm_runtime,
     )
     .await?;
 
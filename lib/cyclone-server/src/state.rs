@@ -22,7 +22,10 @@ use tokio::{
     },
 };
 
-use crate::execution::ExecutionError;
+use crate::{
+    execution::ExecutionError,
+    wasm::WasmRuntime,
+};
 type Result<T> = std::result::Result<T, ExecutionError>;
 
 #[derive(Clone, FromRef)]
@@ -30,6 +33,7 @@ pub struct AppState {
     child: LangServerChild,
     lang_server_process_timeout: LangServerProcessTimeout,
     telemetry_level: TelemetryLevel,
+    wasm_runtime: WasmRuntime,
 }
 
 impl AppState {
@@ -38,6 +42,7 @@ impl AppState {
         telemetry_level: Box<dyn telemetry::TelemetryLevel>,
         lang_server_function_timeout: Option<usize>,
         lang_server_process_timeout: Option<u64>,
+        wasm_runtime: WasmRuntime,
     ) -> Result<Self> {
         let mut cmd = Command::new(&lang_server_path);
         cmd.stdin(Stdio::piped())
@@ -62,6 +67,7 @@ impl AppState {
                 lang_server_process_timeout,
             )),
             telemetry_level: TelemetryLevel(Arc::new(telemetry_level)),
+            wasm_runtime,
         })
     }
 }
@@ -0,0 +1,703 @@
+//! Execution of functions compiled to WebAssembly components, as an alternative to running them
+//! with the lang-js server.
+//!
+//! Functions target the `si:function` world defined below. The host provides logging to the
+//! function's output stream, access to the decrypted secrets of the request and outbound HTTP to an
+//! allowlist of hosts. WASI is available to the guest, but without any filesystem, environment or
+//! network access of its own.
+
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+        VecDeque,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use axum::extract::ws::WebSocket;
+use base64::{
+    Engine as _,
+    alphabet,
+    engine::{
+        DecodePaddingMode,
+        GeneralPurpose,
+        GeneralPurposeConfig,
+    },
+};
+use cyclone_core::{
+    ExecutionResourceUsage,
+    FunctionResult,
+    FunctionResultFailure,
+    FunctionResultFailureError,
+    FunctionResultFailureErrorKind,
+    Message,
+    OutputStream,
+    ResolverFunctionRequest,
+    ResolverFunctionResponseType,
+    ResolverFunctionResultSuccess,
+};
+use serde_json::Value;
+use si_crypto::SensitiveStrings;
+use si_hash::Hash;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{
+    sync::mpsc,
+    time,
+};
+use wasmtime::{
+    Engine,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    component::{
+        Component,
+        Linker,
+        ResourceTable,
+    },
+};
+use wasmtime_wasi::{
+    WasiCtx,
+    WasiCtxBuilder,
+    WasiView,
+    pipe::MemoryOutputPipe,
+};
+
+use crate::{
+    WebSocketMessage,
+    execution::{
+        self,
+        ExecutionClosing,
+    },
+    resource_monitor,
+    state::LangServerChild,
+};
+
+/// Bindings generated from the world, kept apart from the `Result` alias of this module.
+mod bindings {
+    wasmtime::component::bindgen!({
+        inline: r#"
+            package si:function@0.1.0;
+
+            /// Services provided by cyclone to a function while it runs.
+            interface host {
+                /// The severity of a line of function output.
+                enum level {
+                    debug,
+                    info,
+                    warn,
+                    error,
+                }
+
+                /// Writes a line to the function's output stream.
+                log: func(level: level, message: string);
+
+                /// Returns the decrypted secrets made available to the function as a JSON encoded
+                /// array.
+                secrets: func() -> string;
+
+                record http-request {
+                    method: string,
+                    url: string,
+                    headers: list<tuple<string, string>>,
+                    body: option<list<u8>>,
+                }
+
+                record http-response {
+                    status: u16,
+                    headers: list<tuple<string, string>>,
+                    body: list<u8>,
+                }
+
+                /// Sends an HTTP request. Requests to hosts that are not on cyclone's allowlist are
+                /// rejected, and redirects are never followed.
+                fetch: func(request: http-request) -> result<http-response, string>;
+            }
+
+            world function {
+                import host;
+
+                /// Runs the named handler with the JSON encoded input.
+                ///
+                /// Returns the JSON encoded output, or an empty string to leave the value unset.
+                /// Returning an error fails the function run with the given message.
+                export run: func(handler: string, input: string) -> result<string, string>;
+            }
+        "#,
+        world: "function",
+        async: true,
+    });
+}
+
+use self::bindings::{
+    Function,
+    si::function::host::{
+        self,
+        HttpRequest,
+        HttpResponse,
+        Level,
+    },
+};
+
+/// How much fuel a guest may consume before yielding back to the async runtime, which lets the
+/// execution timeout interrupt functions that never return.
+const FUEL_ASYNC_YIELD_INTERVAL: u64 = 10_000;
+
+/// The maximum number of bytes a guest may write to WASI stdout or stderr.
+const MAX_WASI_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// The maximum number of bytes of an HTTP response body returned to a guest.
+const MAX_HTTP_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+
+/// The maximum number of compiled components kept around for reuse by later executions.
+const MAX_CACHED_COMPONENTS: usize = 64;
+
+/// Decodes function code whether or not it was encoded with padding, since the dal encodes it
+/// without.
+const CODE_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+const TX_TIMEOUT_SECS: Duration = Duration::from_secs(5);
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum WasmError {
+    #[error("failed to create wasm engine: {0}")]
+    Engine(#[source] wasmtime::Error),
+    #[error("failed to create http client: {0}")]
+    HttpClient(#[source] reqwest::Error),
+    #[error("failed to serialize json message")]
+    JSONSerialize(#[source] serde_json::Error),
+    #[error("failed to set up wasm linker: {0}")]
+    Linker(#[source] wasmtime::Error),
+    #[error("send timeout")]
+    SendTimeout(#[source] tokio::time::error::Elapsed),
+    #[error("failed to send websocket message")]
+    WSSendIO(#[source] axum::Error),
+}
+
+type Result<T> = std::result::Result<T, WasmError>;
+
+/// A runtime shared by every WebAssembly function executed by this cyclone instance.
+#[derive(Clone)]
+pub struct WasmRuntime {
+    engine: Engine,
+    linker: Arc<Linker<WasmState>>,
+    components: Arc<Mutex<ComponentCache>>,
+    http_client: reqwest::Client,
+    http_allowlist: Arc<HashSet<String>>,
+}
+
+impl std::fmt::Debug for WasmRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmRuntime")
+            .field("http_allowlist", &self.http_allowlist)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasmRuntime {
+    /// Creates a runtime that allows functions to send HTTP requests to the given hosts.
+    pub fn new(http_allowlist: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut config = wasmtime::Config::new();
+        config
+            .async_support(true)
+            .wasm_component_model(true)
+            .consume_fuel(true);
+        let engine = Engine::new(&config).map_err(WasmError::Engine)?;
+
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_async(&mut linker).map_err(WasmError::Linker)?;
+        Function::add_to_linker(&mut linker, |state: &mut WasmState| state)
+            .map_err(WasmError::Linker)?;
+
+        let http_client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(WasmError::HttpClient)?;
+
+        Ok(Self {
+            engine,
+            linker: Arc::new(linker),
+            components: Arc::new(Mutex::new(ComponentCache::default())),
+            http_client,
+            http_allowlist: Arc::new(
+                http_allowlist
+                    .into_iter()
+                    .map(|host| host.to_ascii_lowercase())
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Runs a resolver function compiled to a WebAssembly component, streaming its output over
+    /// the websocket and finally sending its result.
+    ///
+    /// Failures of the function itself (invalid components, traps, errors returned by the guest)
+    /// are reported as a failed [`FunctionResult`]. Only failures to talk to the client are
+    /// returned as errors.
+    ///
+    /// The memory limit of the request caps the linear memory of the guest, so growing beyond it
+    /// fails inside the function. The CPU time limit isn't enforced since the guest runs on the
+    /// cyclone process itself, which is also why only the execution time is reported.
+    pub async fn execute_resolver(
+        &self,
+        child: LangServerChild,
+        request: ResolverFunctionRequest,
+        sensitive_strings: SensitiveStrings,
+        timeout: Duration,
+        ws: &mut WebSocket,
+    ) -> Result<ExecutionClosing<ResolverFunctionResultSuccess>> {
+        let sensitive_strings = Arc::new(sensitive_strings);
+        let (output_tx, mut output_rx) = mpsc::unbounded_channel();
+        let started_at = time::Instant::now();
+
+        let run = self.run_resolver(&request, output_tx, timeout);
+        tokio::pin!(run);
+
+        let result = loop {
+            tokio::select! {
+                Some(output) = output_rx.recv() => {
+                    Self::ws_send_output(ws, output, &sensitive_strings).await?;
+                }
+                result = &mut run => break result,
+            }
+        };
+        // The run has finished, so any output still buffered can be sent before the result
+        while let Ok(output) = output_rx.try_recv() {
+            Self::ws_send_output(ws, output, &sensitive_strings).await?;
+        }
+
+        let mut result = match result {
+            Ok(output) => Self::resolver_result(&request, output),
+            Err(message) => Self::failure(
+                &request.execution_id,
+                FunctionResultFailureErrorKind::UserCodeException("WasmError".to_string()),
+                message,
+            ),
+        };
+        if let FunctionResult::Success(success) = &mut result {
+            execution::redact_value(&mut success.data, &sensitive_strings);
+        }
+        Self::ws_send(ws, Message::Result(result)).await?;
+
+        let resource_usage = ExecutionResourceUsage {
+            execution_ms: Some(resource_monitor::duration_ms(started_at.elapsed())),
+            ..Default::default()
+        };
+        Ok(ExecutionClosing::new(child, Some(resource_usage)))
+    }
+
+    /// Compiles, instantiates and calls the component, returning the raw output of the guest or a
+    /// message describing why it could not produce one.
+    async fn run_resolver(
+        &self,
+        request: &ResolverFunctionRequest,
+        output_tx: mpsc::UnboundedSender<OutputStream>,
+        timeout: Duration,
+    ) -> std::result::Result<String, String> {
+        let code = CODE_BASE64
+            .decode(&request.code_base64)
+            .map_err(|err| format!("function code is not valid base64: {err}"))?;
+        let component = self.component(code).await?;
+
+        let input = serde_json::to_string(&request.component.data.properties)
+            .map_err(|err| format!("failed to serialize function input: {err}"))?;
+        let secrets = Value::Array(
+            request
+                .before
+                .iter()
+                .map(|before| before.arg.clone())
+                .collect(),
+        )
+        .to_string();
+
+        let stdout = MemoryOutputPipe::new(MAX_WASI_OUTPUT_BYTES);
+        let stderr = MemoryOutputPipe::new(MAX_WASI_OUTPUT_BYTES);
+        let mut limits = StoreLimitsBuilder::new();
+        if let Some(max_memory_bytes) = request.limits.max_memory_bytes {
+            limits = limits.memory_size(usize::try_from(max_memory_bytes).unwrap_or(usize::MAX));
+        }
+        let state = WasmState {
+            execution_id: request.execution_id.clone(),
+            wasi: WasiCtxBuilder::new()
+                .stdout(stdout.clone())
+                .stderr(stderr.clone())
+                .build(),
+            table: ResourceTable::new(),
+            output_tx: output_tx.clone(),
+            secrets,
+            http_client: self.http_client.clone(),
+            http_allowlist: self.http_allowlist.clone(),
+            limits: limits.build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(u64::MAX)
+            .and_then(|_| store.fuel_async_yield_interval(Some(FUEL_ASYNC_YIELD_INTERVAL)))
+            .map_err(|err| format!("failed to configure wasm store: {err}"))?;
+
+        let call = async {
+            let bindings = Function::instantiate_async(&mut store, &component, &self.linker)
+                .await
+                .map_err(|err| format!("failed to instantiate wasm component: {err}"))?;
+            bindings
+                .call_run(&mut store, &request.handler, &input)
+                .await
+                .map_err(|err| format!("wasm function trapped: {err}"))?
+        };
+        let result = match time::timeout(timeout, call).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("wasm function timed out after {timeout:?}")),
+        };
+
+        // Anything the guest wrote through WASI rather than the host logger is still output
+        for (stream, pipe, level) in [("stdout", stdout, "info"), ("stderr", stderr, "warn")] {
+            for line in String::from_utf8_lossy(&pipe.contents()).lines() {
+                let _ = output_tx.send(OutputStream {
+                    execution_id: request.execution_id.clone(),
+                    stream: stream.to_string(),
+                    level: level.to_string(),
+                    group: None,
+                    message: line.to_string(),
+                    timestamp: crate::timestamp(),
+                });
+            }
+        }
+
+        result
+    }
+
+    /// Returns the compiled component for the given code, compiling it only if it isn't cached.
+    async fn component(&self, code: Vec<u8>) -> std::result::Result<Component, String> {
+        let hash = Hash::new(&code);
+        if let Some(component) = self
+            .components
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&hash)
+        {
+            return Ok(component);
+        }
+
+        let engine = self.engine.clone();
+        let component = tokio::task::spawn_blocking(move || Component::new(&engine, code))
+            .await
+            .map_err(|err| format!("failed to compile wasm component: {err}"))?
+            .map_err(|err| format!("function code is not a valid wasm component: {err}"))?;
+
+        self.components
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(hash, component.clone());
+        Ok(component)
+    }
+
+    /// Builds the result of a resolver function from the output of the guest, applying the same
+    /// return type checks as the lang-js server.
+    fn resolver_result(
+        request: &ResolverFunctionRequest,
+        output: String,
+    ) -> FunctionResult<ResolverFunctionResultSuccess> {
+        let data = if output.trim().is_empty() {
+            Value::Null
+        } else {
+            match serde_json::from_str(&output) {
+                Ok(data) => data,
+                Err(err) => {
+                    return Self::failure(
+                        &request.execution_id,
+                        FunctionResultFailureErrorKind::InvalidReturnType,
+                        format!("Return value must be valid JSON: {err}"),
+                    );
+                }
+            }
+        };
+
+        if data.is_null() {
+            return if is_nullable(&request.response_type) {
+                FunctionResult::Success(ResolverFunctionResultSuccess {
+                    execution_id: request.execution_id.clone(),
+                    data,
+                    unset: true,
+                    timestamp: crate::timestamp(),
+                })
+            } else {
+                Self::failure(
+                    &request.execution_id,
+                    FunctionResultFailureErrorKind::InvalidReturnType,
+                    "Return type cannot be null or undefined",
+                )
+            };
+        }
+
+        match check_return_type(&request.response_type, &data) {
+            Ok(()) => FunctionResult::Success(ResolverFunctionResultSuccess {
+                execution_id: request.execution_id.clone(),
+                data,
+                unset: false,
+                timestamp: crate::timestamp(),
+            }),
+            Err(message) => Self::failure(
+                &request.execution_id,
+                FunctionResultFailureErrorKind::InvalidReturnType,
+                message,
+            ),
+        }
+    }
+
+    fn failure(
+        execution_id: &str,
+        kind: FunctionResultFailureErrorKind,
+        message: impl Into<String>,
+    ) -> FunctionResult<ResolverFunctionResultSuccess> {
+        FunctionResult::Failure(FunctionResultFailure::new(
+            execution_id,
+            FunctionResultFailureError {
+                kind,
+                message: message.into(),
+            },
+            crate::timestamp(),
+        ))
+    }
+
+    async fn ws_send_output(
+        ws: &mut WebSocket,
+        mut output: OutputStream,
+        sensitive_strings: &SensitiveStrings,
+    ) -> Result<()> {
+        if sensitive_strings.has_sensitive(&output.message) {
+            output.message = sensitive_strings.redact(&output.message);
+        }
+        Self::ws_send(
+            ws,
+            Message::<ResolverFunctionResultSuccess>::OutputStream(output),
+        )
+        .await
+    }
+
+    async fn ws_send(
+        ws: &mut WebSocket,
+        message: Message<ResolverFunctionResultSuccess>,
+    ) -> Result<()> {
+        let msg = message
+            .serialize_to_string()
+            .map_err(WasmError::JSONSerialize)?;
+        time::timeout(TX_TIMEOUT_SECS, ws.send(WebSocketMessage::Text(msg)))
+            .await
+            .map_err(WasmError::SendTimeout)?
+            .map_err(WasmError::WSSendIO)?;
+        Ok(())
+    }
+}
+
+/// Mirrors the response types that the lang-js server allows to return `null`.
+fn is_nullable(response_type: &ResolverFunctionResponseType) -> bool {
+    matches!(
+        response_type,
+        ResolverFunctionResponseType::Array
+            | ResolverFunctionResponseType::Boolean
+            | ResolverFunctionResponseType::Integer
+            | ResolverFunctionResponseType::Json
+            | ResolverFunctionResponseType::Map
+            | ResolverFunctionResponseType::Object
+            | ResolverFunctionResponseType::String
+    )
+}
+
+/// Mirrors the return type checks performed by the lang-js server for resolver functions.
+fn check_return_type(
+    response_type: &ResolverFunctionResponseType,
+    data: &Value,
+) -> std::result::Result<(), String> {
+    match response_type {
+        ResolverFunctionResponseType::Array if !data.is_array() => {
+            Err("Return type must be an array.".to_string())
+        }
+        ResolverFunctionResponseType::Boolean if !data.is_boolean() => {
+            Err("Return type must be a boolean.".to_string())
+        }
+        ResolverFunctionResponseType::Integer if !(data.is_i64() || data.is_u64()) => {
+            Err("Return type must be an integer.".to_string())
+        }
+        ResolverFunctionResponseType::Map | ResolverFunctionResponseType::Object
+            if !data.is_object() =>
+        {
+            Err("Return type must be an object.".to_string())
+        }
+        ResolverFunctionResponseType::String if !data.is_string() => {
+            Err("Return type must be a string.".to_string())
+        }
+        ResolverFunctionResponseType::CodeGeneration => {
+            if !data.is_object() {
+                Err(
+                    "CodeGenerations must return an object with 'format' and 'code' fields"
+                        .to_string(),
+                )
+            } else if !data["format"].is_string() {
+                Err("The format field type must be a string".to_string())
+            } else if !data["code"].is_string() {
+                Err("The code field type must be a string".to_string())
+            } else {
+                Ok(())
+            }
+        }
+        ResolverFunctionResponseType::Qualification => {
+            if !data.is_object() {
+                return Err("A qualification must return an object.".to_string());
+            }
+            let Some(result) = data["result"].as_str() else {
+                return Err("Qualification result field type must be a string".to_string());
+            };
+            if !["warning", "failure", "success", "unknown"].contains(&result) {
+                Err(
+                    "Qualification result must be one of 'success' | 'warning' | 'failure'"
+                        .to_string(),
+                )
+            } else if result != "success" && !data["message"].is_string() {
+                Err("The Qualification message field type must be a string, and must be present unless the status is success".to_string())
+            } else {
+                Ok(())
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Compiled components keyed by the hash of their code, evicting the oldest once full.
+#[derive(Default)]
+struct ComponentCache {
+    components: HashMap<Hash, Component>,
+    order: VecDeque<Hash>,
+}
+
+impl ComponentCache {
+    fn get(&self, hash: &Hash) -> Option<Component> {
+        self.components.get(hash).cloned()
+    }
+
+    fn insert(&mut self, hash: Hash, component: Component) {
+        if self.components.insert(hash, component).is_some() {
+            return;
+        }
+        self.order.push_back(hash);
+        while self.order.len() > MAX_CACHED_COMPONENTS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.components.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// The state of a single execution, available to the host functions called by the guest.
+struct WasmState {
+    execution_id: String,
+    wasi: WasiCtx,
+    table: ResourceTable,
+    output_tx: mpsc::UnboundedSender<OutputStream>,
+    secrets: String,
+    http_client: reqwest::Client,
+    http_allowlist: Arc<HashSet<String>>,
+    limits: StoreLimits,
+}
+
+impl WasiView for WasmState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+impl host::Host for WasmState {
+    async fn log(&mut self, level: Level, message: String) {
+        let level = match level {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        };
+        // The receiver only goes away once the execution is over, at which point there is no one
+        // left to send output to
+        let _ = self.output_tx.send(OutputStream {
+            execution_id: self.execution_id.clone(),
+            stream: "output".to_string(),
+            level: level.to_string(),
+            group: None,
+            message,
+            timestamp: crate::timestamp(),
+        });
+    }
+
+    async fn secrets(&mut self) -> String {
+        self.secrets.clone()
+    }
+
+    async fn fetch(&mut self, request: HttpRequest) -> std::result::Result<HttpResponse, String> {
+        let url = reqwest::Url::parse(&request.url).map_err(|err| err.to_string())?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| format!("url has no host: {url}"))?
+            .to_ascii_lowercase();
+        if !self.http_allowlist.contains(&host) {
+            debug!(%host, "rejected http request from wasm function to host not on allowlist");
+            return Err(format!("host is not on the http allowlist: {host}"));
+        }
+
+        let method = reqwest::Method::from_bytes(request.method.to_ascii_uppercase().as_bytes())
+            .map_err(|err| err.to_string())?;
+        let mut builder = self.http_client.request(method, url);
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let mut response = builder.send().await.map_err(|err| err.to_string())?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+        if response
+            .content_length()
+            .is_some_and(|length| length > MAX_HTTP_RESPONSE_BYTES as u64)
+        {
+            return Err(format!(
+                "response body is larger than {MAX_HTTP_RESPONSE_BYTES} bytes"
+            ));
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|err| err.to_string())? {
+            if body.len() + chunk.len() > MAX_HTTP_RESPONSE_BYTES {
+                return Err(format!(
+                    "response body is larger than {MAX_HTTP_RESPONSE_BYTES} bytes"
+                ));
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
@@ -20,6 +20,8 @@ use si_frontend_mv_types::component::{
 };
 use telemetry::prelude::*;
 
+use crate::read_set;
+
 pub mod attribute_tree;
 pub mod component_diff;
 pub mod erased_components;
@@ -36,12 +38,22 @@ pub async fn assemble_in_list(
 ) -> crate::Result<ComponentInListMv> {
     let ctx = &ctx;
 
+    // Everything shown in the list is derived from the component's attribute tree and the
+    // contents of its schema variant and schema nodes. We intentionally do not depend on the
+    // schema variant subtree, since changes to props and functions of the variant do not affect
+    // the list entry.
+    read_set::record_node(ctx, component_id).await?;
+    read_set::record_subtree(Component::root_attribute_value_id(ctx, component_id).await?);
+
+    let component = Component::get_by_id(ctx, component_id).await?;
     let name = Component::name_by_id(ctx, component_id).await?;
     let color = Component::color_by_id(ctx, component_id).await?;
 
     let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
     let schema_variant = SchemaVariant::get_by_id(ctx, schema_variant_id).await?;
     let schema = SchemaVariant::schema_for_schema_variant_id(ctx, schema_variant_id).await?;
+    read_set::record_node(ctx, schema_variant_id).await?;
+    read_set::record_node(ctx, schema.id()).await?;
     let has_resource = Component::resource_by_id(ctx, component_id)
         .await?
         .is_some();
@@ -65,6 +77,7 @@ pub async fn assemble_in_list(
             Prop::find_prop_id_by_path_opt(ctx, schema_variant_id, &PropPath::new(prop_path_raw))
                 .await?
         {
+            read_set::record_node(ctx, prop_id).await?;
             let av_id_for_prop_id =
                 Component::attribute_value_for_prop_id(ctx, component_id, prop_id).await?;
             dal::AttributeValue::view(ctx, av_id_for_prop_id).await?
@@ -89,6 +102,8 @@ pub async fn assemble_in_list(
         to_delete,
         resource_id,
         has_socket_connections: false,
+        labels: component.labels().clone().into_iter().collect(),
+        owner: component.owner().map(ToOwned::to_owned),
     })
 }
 
@@ -109,6 +124,7 @@ pub fn map_diff_status(status: dal::component::diff::DiffStatus) -> ComponentDif
 pub async fn assemble(ctx: DalContext, component_id: ComponentId) -> crate::Result<ComponentMv> {
     let ctx = &ctx;
 
+    let component = Component::get_by_id(ctx, component_id).await?;
     let name = Component::name_by_id(ctx, component_id).await?;
     let color = Component::color_by_id(ctx, component_id).await?;
     let schema_variant_id = Component::schema_variant_id(ctx, component_id).await?;
@@ -158,6 +174,8 @@ pub async fn assemble(ctx: DalContext, component_id: ComponentId) -> crate::Resu
         resource_diff,
         is_secret_defining,
         to_delete,
+        labels: component.labels().clone().into_iter().collect(),
+        owner: component.owner().map(ToOwned::to_owned),
     })
 }
 
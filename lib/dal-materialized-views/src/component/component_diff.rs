@@ -323,6 +323,7 @@ async fn value_sources_are_same(
         (ValueSource::ValueSubscription(old_sub), ValueSource::ValueSubscription(new_sub)) => {
             subscriptions_are_same(old_ctx, old_sub, new_ctx, new_sub).await?
         }
+        (ValueSource::Variable(old_id), ValueSource::Variable(new_id)) => old_id == new_id,
 
         // Different types are different!
         // NOTE: Writing out all the possibilities so if a new source is added, it will have to be
@@ -333,7 +334,8 @@ async fn value_sources_are_same(
             | ValueSource::Prop(_)
             | ValueSource::Secret(_)
             | ValueSource::StaticArgumentValue(_)
-            | ValueSource::ValueSubscription(_),
+            | ValueSource::ValueSubscription(_)
+            | ValueSource::Variable(_),
             _,
         ) => false,
     })
@@ -10,16 +10,25 @@ use dal::{
         AttributePrototypeArgument,
         value_source::ValueSource,
     },
+    variable::Variable,
 };
 use si_frontend_mv_types::incoming_connections::{
     Connection,
     IncomingConnections as IncomingConnectionsMv,
     ManagementConnections as ManagementConnectionsMv,
 };
-use si_id::ComponentId;
+use si_id::{
+    AttributeValueId,
+    ComponentId,
+    PropId,
+    VariableId,
+};
 use telemetry::prelude::*;
 
-use crate::Result;
+use crate::{
+    Result,
+    read_set,
+};
 
 #[instrument(
     name = "dal_materialized_views.incoming_connections",
@@ -58,10 +67,26 @@ pub async fn assemble_management(
     })
 }
 
+/// The source side of a connection found while walking a component's attribute values.
+enum InProgressSource {
+    Prop {
+        from_component_id: ComponentId,
+        from_attribute_value_path: String,
+        from_attribute_value_id: AttributeValueId,
+        from_prop_id: PropId,
+        from_prop_path: String,
+    },
+    Variable {
+        variable_id: VariableId,
+        variable_name: String,
+    },
+}
+
 async fn prop_to_prop(ctx: &DalContext, component_id: ComponentId) -> Result<Vec<Connection>> {
     let mut connections = Vec::new();
 
     let root_attribute_value_id = Component::root_attribute_value_id(ctx, component_id).await?;
+    read_set::record_subtree(root_attribute_value_id);
     let mut work_queue = VecDeque::from([root_attribute_value_id]);
 
     while let Some(attribute_value_id) = work_queue.pop_front() {
@@ -76,36 +101,54 @@ async fn prop_to_prop(ctx: &DalContext, component_id: ComponentId) -> Result<Vec
         let ap_args = AttributePrototype::list_arguments(ctx, attribute_prototype_id).await?;
         let mut in_progress = Vec::with_capacity(ap_args.len());
         for attribute_prototype_argument_id in ap_args {
-            if let Some(ValueSource::ValueSubscription(subscription)) =
-                AttributePrototypeArgument::value_source_opt(ctx, attribute_prototype_argument_id)
-                    .await?
+            match AttributePrototypeArgument::value_source_opt(ctx, attribute_prototype_argument_id)
+                .await?
             {
-                // If we can successfully resolve the subscription, we have found a connection!
-                // Let's push the information we need into our "in progress" cache for later.
-                if let Some(from_attribute_value_id) = subscription.resolve(ctx).await? {
-                    let (_, from_attribute_value_path) =
-                        AttributeValue::path_from_root(ctx, from_attribute_value_id).await?;
-                    let from_component_id =
-                        AttributeValue::component_id(ctx, from_attribute_value_id).await?;
-                    let from_prop_id =
-                        AttributeValue::prop_id(ctx, from_attribute_value_id).await?;
-                    let from_prop_path = Prop::path_by_id(ctx, from_prop_id)
-                        .await?
-                        .with_replaced_sep_and_prefix("/");
-                    in_progress.push((
-                        from_component_id,
-                        from_attribute_value_path,
-                        from_attribute_value_id,
-                        from_prop_id,
-                        from_prop_path,
-                    ))
+                Some(ValueSource::ValueSubscription(subscription)) => {
+                    // The subscription path is resolved against the source component's attribute
+                    // tree, so any change beneath its root can change where (or whether) it
+                    // resolves.
+                    read_set::record_subtree(subscription.attribute_value_id);
+
+                    // If we can successfully resolve the subscription, we have found a connection!
+                    // Let's push the information we need into our "in progress" cache for later.
+                    if let Some(from_attribute_value_id) = subscription.resolve(ctx).await? {
+                        let (_, from_attribute_value_path) =
+                            AttributeValue::path_from_root(ctx, from_attribute_value_id).await?;
+                        let from_component_id =
+                            AttributeValue::component_id(ctx, from_attribute_value_id).await?;
+                        let from_prop_id =
+                            AttributeValue::prop_id(ctx, from_attribute_value_id).await?;
+                        read_set::record_node(ctx, from_prop_id).await?;
+                        let from_prop_path = Prop::path_by_id(ctx, from_prop_id)
+                            .await?
+                            .with_replaced_sep_and_prefix("/");
+                        in_progress.push(InProgressSource::Prop {
+                            from_component_id,
+                            from_attribute_value_path,
+                            from_attribute_value_id,
+                            from_prop_id,
+                            from_prop_path,
+                        })
+                    }
                 }
+                Some(ValueSource::Variable(variable_id)) => {
+                    // The variable's name is part of the connection, so a rename must rebuild us.
+                    read_set::record_node(ctx, variable_id).await?;
+                    let variable = Variable::get_by_id(ctx, variable_id).await?;
+                    in_progress.push(InProgressSource::Variable {
+                        variable_id,
+                        variable_name: variable.name().to_owned(),
+                    })
+                }
+                _ => {}
             }
         }
 
         // Only perform connections population setup if we have found connections.
         if !in_progress.is_empty() {
             let prop_id = AttributeValue::prop_id(ctx, attribute_value_id).await?;
+            read_set::record_node(ctx, prop_id).await?;
             let prop_path = Prop::path_by_id(ctx, prop_id)
                 .await?
                 .with_replaced_sep_and_prefix("/");
@@ -113,25 +156,38 @@ async fn prop_to_prop(ctx: &DalContext, component_id: ComponentId) -> Result<Vec
                 AttributeValue::path_from_root(ctx, attribute_value_id).await?;
             connections.reserve(in_progress.len());
 
-            for (
-                from_component_id,
-                from_attribute_value_path,
-                from_attribute_value_id,
-                from_prop_id,
-                from_prop_path,
-            ) in in_progress
-            {
-                connections.push(Connection::Prop {
-                    from_component_id,
-                    from_attribute_value_id,
-                    from_attribute_value_path,
-                    from_prop_id,
-                    from_prop_path,
-                    to_component_id: component_id,
-                    to_prop_id: prop_id,
-                    to_prop_path: prop_path.clone(),
-                    to_attribute_value_id: attribute_value_id,
-                    to_attribute_value_path: attribute_value_path.clone(),
+            for source in in_progress {
+                connections.push(match source {
+                    InProgressSource::Prop {
+                        from_component_id,
+                        from_attribute_value_path,
+                        from_attribute_value_id,
+                        from_prop_id,
+                        from_prop_path,
+                    } => Connection::Prop {
+                        from_component_id,
+                        from_attribute_value_id,
+                        from_attribute_value_path,
+                        from_prop_id,
+                        from_prop_path,
+                        to_component_id: component_id,
+                        to_prop_id: prop_id,
+                        to_prop_path: prop_path.clone(),
+                        to_attribute_value_id: attribute_value_id,
+                        to_attribute_value_path: attribute_value_path.clone(),
+                    },
+                    InProgressSource::Variable {
+                        variable_id,
+                        variable_name,
+                    } => Connection::Variable {
+                        variable_id,
+                        variable_name,
+                        to_component_id: component_id,
+                        to_prop_id: prop_id,
+                        to_prop_path: prop_path.clone(),
+                        to_attribute_value_id: attribute_value_id,
+                        to_attribute_value_path: attribute_value_path.clone(),
+                    },
                 })
             }
         }
@@ -62,6 +62,7 @@ pub mod incoming_connections;
 pub mod incoming_connections_list;
 pub mod luminork;
 pub mod mgmt_prototype_view_list;
+pub mod read_set;
 pub mod schema_variant;
 pub mod secret;
 pub mod view;
@@ -115,6 +116,8 @@ pub enum Error {
     SiPkg(#[from] si_pkg::SiPkgError),
     #[error("validation error: {0}")]
     Validation(#[from] dal::validation::ValidationError),
+    #[error("variable error: {0}")]
+    Variable(#[from] dal::variable::VariableError),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshot(#[from] Box<WorkspaceSnapshotError>),
 }
@@ -0,0 +1,135 @@
+//! Recording of the graph nodes that a materialized view builder read while assembling its view.
+//!
+//! Builders opt in by calling [`record_subtree`] and [`record_node`] for the nodes they depend
+//! on. Callers wrap the builder future with a [`ReadSetRecorder`] to collect the resulting [`ReadSet`], which
+//! can later be compared against the changes in a change batch to determine whether the view needs
+//! to be rebuilt at all.
+//!
+//! Since [`Change`](si_events::workspace_snapshot::Change) objects are produced by comparing
+//! merkle tree hashes, a node shows up as changed whenever anything beneath it has changed. There
+//! are two kinds of reads as a result:
+//!
+//! - a "subtree" read means the view depends on the node _and_ everything reachable from it, so
+//!   any change to that node invalidates the view
+//! - a "node" read means the view only depends on the data in the node weight itself, so a change
+//!   to that node only invalidates the view if its node hash differs from the one we recorded
+
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    future::Future,
+    sync::Arc,
+};
+
+use dal::{
+    ContentHash,
+    DalContext,
+    Ulid,
+};
+use parking_lot::Mutex;
+use si_id::EntityId;
+
+tokio::task_local! {
+    static CURRENT_READ_SET: Arc<Mutex<ReadSet>>;
+}
+
+/// The set of graph nodes read by a single materialized view build.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReadSet {
+    subtrees: HashSet<EntityId>,
+    nodes: HashMap<EntityId, ContentHash>,
+}
+
+impl ReadSet {
+    /// Returns true if nothing was recorded, meaning the builder does not track its reads.
+    pub fn is_empty(&self) -> bool {
+        self.subtrees.is_empty() && self.nodes.is_empty()
+    }
+
+    /// Determines if any of the changed entities could affect a view built from this read set.
+    ///
+    /// Subtree reads are affected by any change to the node. Node reads are only affected if the
+    /// node no longer exists, or if its node hash differs from the one recorded at build time.
+    /// `node_hash` looks up the current node hash of a changed node, if it still exists.
+    pub async fn is_affected_by(
+        &self,
+        changed_entity_ids: &HashSet<EntityId>,
+        node_hash: impl AsyncFn(EntityId) -> Option<ContentHash>,
+    ) -> bool {
+        if self
+            .subtrees
+            .iter()
+            .any(|id| changed_entity_ids.contains(id))
+        {
+            return true;
+        }
+
+        for (id, recorded_node_hash) in &self.nodes {
+            if !changed_entity_ids.contains(id) {
+                continue;
+            }
+
+            if node_hash(*id).await != Some(*recorded_node_hash) {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Collects the nodes recorded as read by a builder future wrapped with [`Self::track`].
+#[derive(Debug, Clone, Default)]
+pub struct ReadSetRecorder(Arc<Mutex<ReadSet>>);
+
+impl ReadSetRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps the builder future so that any reads it records are collected by this recorder.
+    pub fn track<F>(&self, future: F) -> impl Future<Output = F::Output> + use<F>
+    where
+        F: Future,
+    {
+        CURRENT_READ_SET.scope(self.0.clone(), future)
+    }
+
+    /// Returns the collected [`ReadSet`], or [`None`] if the builder did not record anything, as
+    /// that means it does not participate in read tracking and must always be rebuilt.
+    pub fn finish(self) -> Option<ReadSet> {
+        let read_set = self.0.lock().clone();
+        (!read_set.is_empty()).then_some(read_set)
+    }
+}
+
+/// Records that the current build depends on the node and everything reachable from it.
+///
+/// This is a no-op when called outside of [`ReadSetRecorder::track`].
+pub fn record_subtree(id: impl Into<Ulid>) {
+    let id: Ulid = id.into();
+    let _ = CURRENT_READ_SET.try_with(|read_set| read_set.lock().subtrees.insert(id.into()));
+}
+
+/// Records that the current build depends on the contents of the node weight itself, but not on
+/// anything beneath it.
+///
+/// This is a no-op when called outside of [`ReadSetRecorder::track`].
+pub async fn record_node(ctx: &DalContext, id: impl Into<Ulid>) -> crate::Result<()> {
+    if CURRENT_READ_SET.try_with(|_| ()).is_err() {
+        return Ok(());
+    }
+
+    let id: Ulid = id.into();
+    let node_hash = ctx
+        .workspace_snapshot()?
+        .get_node_weight(id)
+        .await?
+        .node_hash();
+    let _ =
+        CURRENT_READ_SET.try_with(|read_set| read_set.lock().nodes.insert(id.into(), node_hash));
+
+    Ok(())
+}
@@ -839,10 +839,12 @@ pub async fn edda_server(
         config.instance_id(),
         config.concurrency_limit(),
         config.parallel_build_limit(),
+        config.definition_migration_limit(),
         config.streaming_patches(),
         services_context,
         frigg_nats,
         config.quiescent_period(),
+        config.index_history_retention(),
         shutdown_token,
     )
     .await
@@ -945,6 +947,7 @@ pub async fn forklift_server(
             config.audit().insert_concurrency_limit,
         )),
         None,
+        None,
         si_db_pool,
         layer_cache_pool,
         layered_event_client,
@@ -51,6 +51,7 @@ use thiserror::Error;
 
 pub use crate::workspace_snapshot::traits::approval_requirement::{
     ApprovalRequirementApprover,
+    ApprovalRequirementComponentLabel,
     ApprovalRequirementRule,
 };
 use crate::{
@@ -59,7 +60,7 @@ use crate::{
     WsEvent,
     WsEventResult,
     WsPayload,
-    layer_db_types::ApprovalRequirementDefinitionContentV1,
+    layer_db_types::ApprovalRequirementDefinitionContentV2,
     workspace_snapshot::traits::approval_requirement::ApprovalRequirementExt,
 };
 
@@ -104,6 +105,30 @@ impl ApprovalRequirement {
             .map_err(Into::into)
     }
 
+    /// Creates a definition that applies to every component carrying the given label, instead of
+    /// to a single entity.
+    #[instrument(
+        name = "approval_requirement.new_component_label_definition",
+        level = "debug",
+        skip_all
+    )]
+    pub async fn new_component_label_definition(
+        ctx: &DalContext,
+        component_label: ApprovalRequirementComponentLabel,
+        minimum_approvers_count: usize,
+        approvers: HashSet<ApprovalRequirementApprover>,
+    ) -> Result<ApprovalRequirementDefinitionId> {
+        ctx.workspace_snapshot()?
+            .new_component_label_definition(
+                ctx,
+                component_label,
+                minimum_approvers_count,
+                approvers,
+            )
+            .await
+            .map_err(Into::into)
+    }
+
     #[instrument(
         name = "approval_requirement.remove_definition",
         level = "debug",
@@ -168,6 +193,7 @@ pub struct ApprovalRequirementDefinition {
     pub id: ApprovalRequirementDefinitionId,
     pub required_count: usize,
     pub approvers: HashSet<ApprovalRequirementApprover>,
+    pub component_label: Option<ApprovalRequirementComponentLabel>,
 }
 
 impl ApprovalRequirementDefinition {
@@ -200,17 +226,19 @@ impl ApprovalRequirementDefinition {
             id: ApprovalRequirementDefinitionId::new(),
             required_count: 0,
             approvers: HashSet::new(),
+            component_label: None,
         }
     }
 
     pub fn assemble(
         id: ApprovalRequirementDefinitionId,
-        content: ApprovalRequirementDefinitionContentV1,
+        content: ApprovalRequirementDefinitionContentV2,
     ) -> Self {
         Self {
             id,
             required_count: content.minimum,
             approvers: content.approvers,
+            component_label: content.component_label,
         }
     }
     pub async fn get_by_id(ctx: &DalContext, id: ApprovalRequirementDefinitionId) -> Result<Self> {
@@ -1,5 +1,8 @@
 use std::{
-    collections::HashMap,
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
     result,
 };
 
@@ -25,7 +28,13 @@ use super::{
         AttributePrototypeArgument,
         static_value::StaticArgumentValue,
     },
-    value::subscription::ValueSubscription,
+    value::{
+        string_template::{
+            StringTemplate,
+            StringTemplateBinding,
+        },
+        subscription::ValueSubscription,
+    },
 };
 use crate::{
     AttributePrototype,
@@ -38,15 +47,29 @@ use crate::{
     WsEvent,
     component::resource::ResourceData,
     func::intrinsics::IntrinsicFunc,
+    pkg::PkgError,
     prop::PropError,
-    workspace_snapshot::node_weight::{
-        NodeWeight,
-        reason_node_weight::Reason,
+    variable::{
+        Variable,
+        VariableId,
+    },
+    workspace_snapshot::{
+        content_address::ContentAddressDiscriminants,
+        node_weight::{
+            NodeWeight,
+            reason_node_weight::Reason,
+        },
     },
 };
 
 pub type Result<T> = result::Result<T, AttributesError>;
 
+impl From<PkgError> for AttributesError {
+    fn from(value: PkgError) -> Self {
+        Box::new(value).into()
+    }
+}
+
 #[remain::sorted]
 #[derive(thiserror::Error, Debug)]
 pub enum AttributesError {
@@ -70,6 +93,8 @@ pub enum AttributesError {
     Component(#[from] crate::ComponentError),
     #[error("func error: {0}")]
     Func(#[from] crate::FuncError),
+    #[error("pkg error: {0}")]
+    Pkg(#[from] Box<PkgError>),
     #[error("prop error: {0}")]
     Prop(#[from] PropError),
     #[error("serde json error: {0}")]
@@ -78,6 +103,10 @@ pub enum AttributesError {
     SourceComponentNotFound(String),
     #[error("transactions error: {0}")]
     Transactions(#[from] crate::TransactionsError),
+    #[error("variable error: {0}")]
+    Variable(#[from] crate::variable::VariableError),
+    #[error("variable not found: {0}")]
+    VariableNotFound(String),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshot(#[from] crate::WorkspaceSnapshotError),
     #[error("ws event error: {0}")]
@@ -186,6 +215,31 @@ pub struct AttributeUpdateCounts {
 ///         }
 ///       }
 ///
+/// - USE a variable: this will cause the value to always equal the value of a variable stored
+///   in the change set. Variables may be specified by their name or VariableId. When the
+///   variable changes, every attribute using it is updated.
+///
+///       {
+///         "/domain/Region": { "$source": { "variable": "prod.region" } }
+///       }
+///
+/// - TEMPLATE a string from several other attributes: each `${name}` placeholder in the template
+///   is bound to a value on another component (specified the same way as a subscription). The
+///   string is rendered natively whenever any bound value changes; if any bound value is unset,
+///   the attribute is unset too. Use `$$` for a literal `$`.
+///
+///       {
+///         "/domain/Resource": {
+///           "$source": {
+///             "template": "arn:aws:s3:::${bucket}/${prefix}",
+///             "bindings": {
+///               "bucket": { "component": "ComponentNameOrId", "path": "/domain/BucketName" },
+///               "prefix": { "component": "ComponentNameOrId", "path": "/domain/Prefix" }
+///             }
+///           }
+///         }
+///       }
+///
 /// - ESCAPE HATCH for setting a value: setting an attribute to `{ "$source": { "value": <value> } }`
 ///   has the same behavior as all the above cases. The reason this exists is, if you happen to
 ///   have an object with a "$source" key, the existing interface would treat that as an error.
@@ -237,6 +291,12 @@ async fn get_before_prop_value_source(
     ctx: &DalContext,
     av_id: AttributeValueId,
 ) -> Result<Option<PropValueSource>> {
+    if let Some(template) = AttributeValue::string_template(ctx, av_id).await? {
+        return Ok(Some(PropValueSource::StringTemplate {
+            template: template.template,
+        }));
+    }
+
     let prototype_id = AttributeValue::prototype_id(ctx, av_id).await?;
     let args = AttributePrototype::list_arguments(ctx, prototype_id).await?;
     let mut prop_value_sources = Vec::with_capacity(args.len());
@@ -266,6 +326,14 @@ async fn get_before_prop_value_source(
                 }
                 _ => PropValueSource::None,
             },
+            super::prototype::argument::value_source::ValueSource::Variable(variable_id) => {
+                let variable = Variable::get_by_id(ctx, variable_id).await?;
+                PropValueSource::Variable {
+                    value: variable.value().clone(),
+                    variable_id,
+                    variable_name: variable.name().to_owned(),
+                }
+            }
             _ => PropValueSource::None,
         };
         prop_value_sources.push(prop_source);
@@ -407,6 +475,64 @@ async fn update_attributes_inner(
                             _ => Some(PropValueSource::None),
                         };
                     }
+                    Source::Variable(variable_ident) => {
+                        let variable = variable_ident
+                            .resolve(ctx)
+                            .await?
+                            .ok_or(AttributesError::VariableNotFound(variable_ident.0))?;
+
+                        AttributeValue::set_to_variable(
+                            ctx,
+                            target_av_id,
+                            variable.id(),
+                            Reason::new_user_added(ctx),
+                        )
+                        .await?;
+
+                        after_value_source = Some(PropValueSource::Variable {
+                            value: variable.value().clone(),
+                            variable_id: variable.id(),
+                            variable_name: variable.name().to_owned(),
+                        });
+                    }
+                    Source::Template { template, bindings } => {
+                        counts.subscription_count += bindings.len();
+
+                        let mut resolved_bindings = BTreeMap::new();
+                        for (name, binding) in bindings {
+                            let component_id = binding.component.resolve(ctx).await?.ok_or(
+                                AttributesError::SourceComponentNotFound(binding.component.0),
+                            )?;
+                            resolved_bindings.insert(
+                                name,
+                                StringTemplateBinding {
+                                    component_id,
+                                    path: binding.path,
+                                },
+                            );
+                        }
+
+                        // Workspaces created before string templates existed won't have the
+                        // intrinsic yet.
+                        crate::pkg::find_or_install_intrinsic_func(
+                            ctx,
+                            IntrinsicFunc::StringTemplate,
+                        )
+                        .await?;
+
+                        AttributeValue::set_to_string_template(
+                            ctx,
+                            target_av_id,
+                            StringTemplate {
+                                template: template.clone(),
+                                bindings: resolved_bindings,
+                            },
+                            Reason::new_user_added(ctx),
+                        )
+                        .await?;
+
+                        after_value_source = Some(PropValueSource::StringTemplate { template });
+                    }
                 }
                 ctx.write_audit_log(
                     AuditLogKind::SetAttribute {
@@ -551,6 +677,17 @@ pub enum Source {
     // { value: <value> } - set value (null is a valid value to set it to)
     Value(serde_json::Value),
 
+    // { variable: "VariableNameOrId" } - set this value to a variable from the change set
+    Variable(VariableIdent),
+
+    // { template: "arn:aws:s3:::${bucket}", bindings: { bucket: { component: "ComponentNameOrId", path: "/domain/Name" } } }
+    // - render a string from several subscriptions
+    #[serde(untagged, rename_all = "camelCase")]
+    Template {
+        template: String,
+        bindings: BTreeMap<String, TemplateBinding>,
+    },
+
     // { component: "ComponentNameOrId", path: "/domain/Foo/Bar/0/Baz" } - subscribe this value to a path from a component
     #[serde(untagged, rename_all = "camelCase")]
     Subscription {
@@ -567,6 +704,14 @@ pub enum Source {
     },
 }
 
+/// Where a placeholder in a [`Source::Template`] gets its value from.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TemplateBinding {
+    pub component: ComponentIdent,
+    pub path: String,
+}
+
 /// Either raw value or a { "$source": ... } spec (JSON for the source/value for an attribute)
 /// Use TryInto<Option<Source> to get Source out of it. If $source is set wrong, you will BadSourceSpecError.
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -575,6 +720,8 @@ pub enum ValueOrSourceSpec {
     /// Explicit sources:
     /// - static value: { "$source": { value: ... } }
     /// - subscription: { "$source": { component: "ComponentNameOrId", path: "/domain/Foo/Bar/0/Baz" } }
+    /// - variable: { "$source": { variable: "VariableNameOrId" } }
+    /// - template: { "$source": { template: "${a}-${b}", bindings: { a: <subscription>, ... } } }
     /// - unset value: { "$source": null } or { "$source": {} } - unset value
     SourceSpec(SourceSpec),
     /// Catch errors: if it isn't a valid source, but has a "$source" field, treat it as an error
@@ -607,6 +754,15 @@ enum MaybeSource {
     Null,
 }
 
+impl ValueOrSourceSpec {
+    /// { "$source": null }: unsets the value (or removes it, if it's a map or array element)
+    pub fn unset() -> Self {
+        ValueOrSourceSpec::SourceSpec(SourceSpec {
+            source: MaybeSource::Null,
+        })
+    }
+}
+
 impl From<MaybeSource> for Option<Source> {
     fn from(from: MaybeSource) -> Self {
         match from {
@@ -656,9 +812,11 @@ impl From<Source> for ValueOrSourceSpec {
         match source {
             // If it's an object with $source as a key, "escape" it as { $source: <value> }
             Source::Value(value) => value.into(),
-            Source::Subscription { .. } => ValueOrSourceSpec::SourceSpec(SourceSpec {
-                source: MaybeSource::Source(source),
-            }),
+            Source::Subscription { .. } | Source::Template { .. } | Source::Variable(_) => {
+                ValueOrSourceSpec::SourceSpec(SourceSpec {
+                    source: MaybeSource::Source(source),
+                })
+            }
         }
     }
 }
@@ -761,6 +919,49 @@ impl FuncIdent {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, derive_more::From)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableIdent(String);
+
+impl From<VariableId> for VariableIdent {
+    fn from(id: VariableId) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl From<VariableIdent> for String {
+    fn from(ident: VariableIdent) -> Self {
+        ident.0
+    }
+}
+
+impl VariableIdent {
+    pub async fn resolve(&self, ctx: &DalContext) -> Result<Option<Variable>> {
+        if let Some(id) = self.resolve_as_id(ctx).await? {
+            return Ok(Some(Variable::get_by_id(ctx, id).await?));
+        }
+        // Otherwise, try to find it by name
+        Ok(Variable::find_by_name(ctx, &self.0).await?)
+    }
+
+    async fn resolve_as_id(&self, ctx: &DalContext) -> Result<Option<VariableId>> {
+        let Ok(id) = self.0.parse() else {
+            return Ok(None);
+        };
+
+        let Some(NodeWeight::Content(content)) =
+            ctx.workspace_snapshot()?.get_node_weight_opt(id).await
+        else {
+            return Ok(None);
+        };
+        if content.content_address_discriminants() != ContentAddressDiscriminants::Variable {
+            return Ok(None);
+        }
+
+        Ok(Some(id))
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, derive_more::From)]
 #[serde(rename = "camelCase")]
 pub struct AttributeValueIdent(String);
@@ -768,6 +768,8 @@ impl AttributePrototype {
                     (Some(IntrinsicFunc::Identity), Some(ValueSource::ValueSubscription(..))) => {
                         true
                     }
+                    // si:identity(<variable>) is just printed as <variable>
+                    (Some(IntrinsicFunc::Identity), Some(ValueSource::Variable(..))) => true,
                     _ => false,
                 };
                 if omit_function {
@@ -187,7 +187,7 @@ impl AttributePrototypeArgument {
         result: AttributePrototypeArgumentResult,
     );
 
-    // This can be used for InputSocketId, OutputSocketId, PropId, SecretId, or StaticArgumentValueId
+    // This can be used for InputSocketId, OutputSocketId, PropId, SecretId, StaticArgumentValueId, or VariableId
     implement_add_edge_to!(
         source_id: AttributePrototypeArgumentId,
         destination_id: Ulid,
@@ -379,7 +379,7 @@ impl AttributePrototypeArgument {
         let snap = ctx.workspace_snapshot()?;
         for (edge, _, target) in snap.edges_directed(apa_id, Direction::Outgoing).await? {
             match edge.kind() {
-                // Handle APA -- PrototypeArgumentValue -> Prop/Secret/InputSocket/OutputSocket/StaticArgumentValue
+                // Handle APA -- PrototypeArgumentValue -> Prop/Secret/InputSocket/OutputSocket/StaticArgumentValue/Variable
                 EdgeWeightKind::PrototypeArgumentValue => {
                     return Ok(Some(match snap.get_node_weight(target).await? {
                         NodeWeight::Prop(node) => ValueSource::Prop(node.id().into()),
@@ -394,6 +394,7 @@ impl AttributePrototypeArgument {
                             ContentAddress::StaticArgumentValue(..) => {
                                 ValueSource::StaticArgumentValue(node.id().into())
                             }
+                            ContentAddress::Variable(..) => ValueSource::Variable(node.id().into()),
                             other => {
                                 return Err(
                                     AttributePrototypeArgumentError::UnexpectedValueSourceContent(
@@ -479,7 +480,8 @@ impl AttributePrototypeArgument {
             | ValueSource::OutputSocket(_)
             | ValueSource::Prop(_)
             | ValueSource::Secret(_)
-            | ValueSource::StaticArgumentValue(_) => {
+            | ValueSource::StaticArgumentValue(_)
+            | ValueSource::Variable(_) => {
                 Self::add_edge_to_value(
                     ctx,
                     apa_id,
@@ -30,6 +30,10 @@ use crate::{
         },
         output::OutputSocketError,
     },
+    variable::{
+        Variable,
+        VariableId,
+    },
 };
 
 #[remain::sorted]
@@ -66,6 +70,7 @@ pub enum ValueSource {
     Secret(SecretId),
     StaticArgumentValue(StaticArgumentValueId),
     ValueSubscription(ValueSubscription),
+    Variable(VariableId),
 }
 
 impl ValueSource {
@@ -85,7 +90,10 @@ impl ValueSource {
             Self::Prop(prop_id) => {
                 Prop::all_attribute_values_everywhere_for_prop_id(ctx, *prop_id).await?
             }
-            Self::Secret(_) | Self::StaticArgumentValue(_) | Self::ValueSubscription(_) => {
+            Self::Secret(_)
+            | Self::StaticArgumentValue(_)
+            | Self::ValueSubscription(_)
+            | Self::Variable(_) => {
                 return Err(ValueSourceError::SourceHasNoValues(self.clone()));
             }
         })
@@ -101,6 +109,7 @@ impl ValueSource {
             ValueSource::ValueSubscription(ValueSubscription {
                 attribute_value_id, ..
             }) => attribute_value_id.into(),
+            ValueSource::Variable(id) => id.into(),
         }
     }
 
@@ -183,6 +192,7 @@ impl ValueSource {
                 StaticArgumentValue::fmt_title(ctx, static_value_id).await
             }
             ValueSource::ValueSubscription(subscription) => subscription.fmt_title(ctx).await,
+            &ValueSource::Variable(variable_id) => Variable::fmt_title(ctx, variable_id).await,
         })
     }
 }
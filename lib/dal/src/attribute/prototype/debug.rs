@@ -44,6 +44,10 @@ use crate::{
         input::InputSocketError,
         output::OutputSocketError,
     },
+    variable::{
+        Variable,
+        VariableError,
+    },
     workspace_snapshot::{
         WorkspaceSnapshotError,
         node_weight::NodeWeightError,
@@ -81,6 +85,8 @@ pub enum AttributePrototypeDebugViewError {
     SerdeJsonError(#[from] serde_json::Error),
     #[error("value source error: {0}")]
     ValueSourceError(#[from] Box<ValueSourceError>),
+    #[error("variable error: {0}")]
+    VariableError(#[from] Box<VariableError>),
     #[error("workspace snapshot error: {0}")]
     WorkspaceSnapshotError(#[from] Box<WorkspaceSnapshotError>),
 }
@@ -173,6 +179,17 @@ impl AttributePrototypeDebugView {
                         is_used: true,
                     }]
                 }
+                ValueSource::Variable(variable_id) => {
+                    let variable = Variable::get_by_id(ctx, variable_id).await?;
+                    vec![FuncArgDebugView {
+                        value: variable.value().clone(),
+                        name: func_arg_name.clone(),
+                        value_source: format!("{value_source:?}"),
+                        value_source_id: variable_id.into(),
+                        path: Some(variable.name().to_owned()),
+                        is_used: true,
+                    }]
+                }
                 ValueSource::Prop(prop_id) => {
                     let mut values = vec![];
 
@@ -346,6 +363,12 @@ impl From<ValueSourceError> for AttributePrototypeDebugViewError {
     }
 }
 
+impl From<VariableError> for AttributePrototypeDebugViewError {
+    fn from(value: VariableError) -> Self {
+        Box::new(value).into()
+    }
+}
+
 impl From<WorkspaceSnapshotError> for AttributePrototypeDebugViewError {
     fn from(value: WorkspaceSnapshotError) -> Self {
         Box::new(value).into()
@@ -28,6 +28,7 @@ use si_pkg::{
     KeyOrIndex,
 };
 use si_split_graph::SplitGraphError;
+use string_template::StringTemplate;
 use subscription::ValueSubscription;
 use telemetry::prelude::*;
 use thiserror::Error;
@@ -79,6 +80,7 @@ use crate::{
         argument::{
             FuncArgument,
             FuncArgumentError,
+            FuncArgumentId,
         },
         intrinsics::IntrinsicFunc,
         runner::{
@@ -97,6 +99,11 @@ use crate::{
         ValidationError,
         ValidationOutput,
     },
+    variable::{
+        Variable,
+        VariableError,
+        VariableId,
+    },
     workspace_snapshot::{
         WorkspaceSnapshotError,
         content_address::{
@@ -125,6 +132,7 @@ pub mod debug;
 pub mod default_subscription;
 pub mod dependent_value_graph;
 pub mod is_for;
+pub mod string_template;
 pub mod subscription;
 
 #[remain::sorted]
@@ -279,6 +287,8 @@ pub enum AttributeValueError {
     SingleValueMustHaveOneSubscription(AttributeValueId, PropKind, usize),
     #[error("Split  graph error: {0}")]
     SplitGraph(#[from] SplitGraphError),
+    #[error("invalid string template: {0}")]
+    StringTemplateInvalid(String),
     #[error("Cannot set subscription with function that isn't builtin or transformation")]
     SubscribingWithInvalidFunction,
     #[error(
@@ -309,6 +319,8 @@ pub enum AttributeValueError {
     Validation(#[from] Box<ValidationError>),
     #[error("value source error: {0}")]
     ValueSource(#[from] Box<ValueSourceError>),
+    #[error("variable error: {0}")]
+    Variable(#[from] Box<VariableError>),
     #[error("workspace error: {0}")]
     Workspace(String),
     #[error("workspace snapshot error: {0}")]
@@ -332,6 +344,11 @@ impl From<SecretError> for AttributeValueError {
         Box::new(value).into()
     }
 }
+impl From<VariableError> for AttributeValueError {
+    fn from(value: VariableError) -> Self {
+        Box::new(value).into()
+    }
+}
 
 impl From<AttributePrototypeError> for AttributeValueError {
     fn from(value: AttributePrototypeError) -> Self {
@@ -785,6 +802,10 @@ impl AttributeValue {
         // Collect metadata for which attribute values are used to execute the prototype function for this one.
         let mut input_attribute_value_ids = Vec::new();
 
+        // Keep track of subscription values separately, in case a string template needs to match
+        // them up with its placeholders.
+        let mut subscription_values = Vec::new();
+
         // Gather the raw func bindings args into a map.
         let mut func_binding_args: HashMap<String, Vec<Value>> = HashMap::new();
         let apa_ids = AttributePrototypeArgument::list_ids_for_prototype(ctx, prototype_id).await?;
@@ -803,9 +824,11 @@ impl AttributeValue {
                     let value = match subscription.resolve(ctx).await? {
                         Some(av_id) => Self::view(ctx, av_id).await?,
                         None => None,
-                    };
+                    }
+                    .unwrap_or(Value::Null);
+                    subscription_values.push((subscription, value.clone()));
 
-                    vec![value.unwrap_or(Value::Null)]
+                    vec![value]
                 }
                 ValueSource::StaticArgumentValue(static_argument_value_id) => {
                     vec![
@@ -817,6 +840,9 @@ impl AttributeValue {
                 ValueSource::Secret(secret_id) => {
                     vec![Secret::payload_for_prototype_execution(ctx, secret_id).await?]
                 }
+                ValueSource::Variable(variable_id) => {
+                    vec![Variable::get_by_id(ctx, variable_id).await?.value().clone()]
+                }
                 other_source @ ValueSource::InputSocket(..)
                 | other_source @ ValueSource::OutputSocket(..)
                 | other_source @ ValueSource::Prop(..) => {
@@ -843,6 +869,38 @@ impl AttributeValue {
                 .or_insert(values_for_arg);
         }
 
+        // String templates bind each subscription to a named placeholder, rather than passing
+        // them along as a list.
+        if !subscription_values.is_empty() {
+            let func_name = ctx
+                .workspace_snapshot()?
+                .get_node_weight(prototype_func_id)
+                .await?
+                .get_func_node_weight()?
+                .name()
+                .to_owned();
+            if func_name == IntrinsicFunc::StringTemplate.name() {
+                let template: StringTemplate = match func_binding_args
+                    .get(string_template::TEMPLATE_ARG_NAME)
+                    .and_then(|values| values.first())
+                {
+                    Some(value) => serde_json::from_value(value.to_owned())?,
+                    None => {
+                        return Err(
+                            AttributeValueError::EmptyAttributePrototypeArgumentsForGroup(
+                                string_template::TEMPLATE_ARG_NAME.to_owned(),
+                            ),
+                        );
+                    }
+                };
+                let bindings = template.bind_values(ctx, subscription_values).await?;
+                func_binding_args.insert(
+                    string_template::BINDINGS_ARG_NAME.to_owned(),
+                    vec![bindings],
+                );
+            }
+        }
+
         // The value map above could possibly have multiple values per func
         // argument name if there are multiple inputs (for example, more than
         // one connection to an input socket). We need to transform these vecs
@@ -2010,6 +2068,164 @@ impl AttributeValue {
         Ok(())
     }
 
+    /// Set the source of this attribute value to a [`Variable`].
+    ///
+    /// This overwrites or overrides any existing value. Whenever the variable's value changes,
+    /// this attribute value is enqueued for a dependent values update.
+    pub async fn set_to_variable(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        variable_id: VariableId,
+        reason: Reason,
+    ) -> AttributeValueResult<()> {
+        // Make sure the variable exists before we touch the prototype
+        Variable::get_by_id(ctx, variable_id).await?;
+
+        let func_id = Func::find_intrinsic(ctx, IntrinsicFunc::Identity).await?;
+        let prototype_id = AttributePrototype::new(ctx, func_id).await?.id();
+        Self::set_component_prototype_id(ctx, attribute_value_id, prototype_id, None).await?;
+
+        let arg_id = FuncArgument::single_arg_for_func(ctx, func_id).await?;
+        let apa = AttributePrototypeArgument::new(ctx, prototype_id, arg_id, variable_id).await?;
+        AttributePrototypeArgument::add_reason(ctx, apa.id(), reason).await?;
+
+        ctx.add_dependent_values_and_enqueue(vec![attribute_value_id])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set the source of this attribute value to a [`StringTemplate`], rendered natively from the
+    /// values it is bound to.
+    ///
+    /// This overwrites or overrides any existing value. Each binding becomes a subscription, so
+    /// the value is re-rendered whenever any bound value changes.
+    pub async fn set_to_string_template(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        template: StringTemplate,
+        reason: Reason,
+    ) -> AttributeValueResult<()> {
+        template.validate()?;
+        let prop_kind = Self::prop_kind(ctx, attribute_value_id).await?;
+        if prop_kind != PropKind::String {
+            return Err(AttributeValueError::TypeMismatch(
+                prop_kind,
+                "string template".to_owned(),
+            ));
+        }
+
+        // Make sure each bound path is valid for its component's schema. Bindings to the same
+        // value share a subscription.
+        let mut subscriptions = Vec::new();
+        for binding in template.bindings.values() {
+            let subscription = ValueSubscription::new(
+                ctx,
+                binding.component_id,
+                AttributePath::from_json_pointer(&binding.path),
+            )
+            .await?;
+            subscription.validate(ctx).await?;
+            if !subscriptions.contains(&subscription) {
+                subscriptions.push(subscription);
+            }
+        }
+
+        let func_id = Func::find_intrinsic(ctx, IntrinsicFunc::StringTemplate).await?;
+        let prototype_id = AttributePrototype::new(ctx, func_id).await?.id();
+        Self::set_component_prototype_id(ctx, attribute_value_id, prototype_id, None).await?;
+
+        let template_arg_id =
+            Self::string_template_arg_id(ctx, func_id, string_template::TEMPLATE_ARG_NAME).await?;
+        let apa = AttributePrototypeArgument::new_static_value(
+            ctx,
+            prototype_id,
+            template_arg_id,
+            serde_json::to_value(&template)?,
+        )
+        .await?;
+        AttributePrototypeArgument::add_reason(ctx, apa.id(), reason).await?;
+
+        let bindings_arg_id =
+            Self::string_template_arg_id(ctx, func_id, string_template::BINDINGS_ARG_NAME).await?;
+        for subscription in subscriptions {
+            let apa =
+                AttributePrototypeArgument::new(ctx, prototype_id, bindings_arg_id, subscription)
+                    .await?;
+            AttributePrototypeArgument::add_reason(ctx, apa.id(), reason).await?;
+        }
+
+        if sub_cycle_check(ctx).await? {
+            return Err(AttributeValueError::SubscriptionWouldCycle {
+                subscriber_av_id: attribute_value_id,
+                subscription: template.template,
+            });
+        }
+
+        ctx.add_dependent_values_and_enqueue(vec![attribute_value_id])
+            .await?;
+
+        Ok(())
+    }
+
+    async fn string_template_arg_id(
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: &str,
+    ) -> AttributeValueResult<FuncArgumentId> {
+        Ok(FuncArgument::find_by_name_for_func(ctx, name, func_id)
+            .await?
+            .ok_or_else(|| FuncArgumentError::NotFoundByNameForFunc(name.to_owned(), func_id))?
+            .id)
+    }
+
+    /// The [`StringTemplate`] this attribute value is set to, if it is set to one.
+    pub async fn string_template(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+    ) -> AttributeValueResult<Option<StringTemplate>> {
+        let Some(prototype_id) = Self::component_prototype_id(ctx, attribute_value_id).await?
+        else {
+            return Ok(None);
+        };
+        let func_id = AttributePrototype::func_id(ctx, prototype_id).await?;
+        if Func::intrinsic_kind(ctx, func_id).await? != Some(IntrinsicFunc::StringTemplate) {
+            return Ok(None);
+        }
+        for apa_id in AttributePrototype::list_arguments(ctx, prototype_id).await? {
+            if let ValueSource::StaticArgumentValue(static_value_id) =
+                AttributePrototypeArgument::value_source(ctx, apa_id).await?
+            {
+                let value = StaticArgumentValue::get_by_id(ctx, static_value_id)
+                    .await?
+                    .value;
+                return Ok(Some(serde_json::from_value(value)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The [`Variable`] this attribute value is set to, if it is set solely to a variable.
+    pub async fn variable_id(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+    ) -> AttributeValueResult<Option<VariableId>> {
+        let Some(prototype_id) = Self::component_prototype_id(ctx, attribute_value_id).await?
+        else {
+            return Ok(None);
+        };
+        let apa_ids = AttributePrototype::list_arguments(ctx, prototype_id).await?;
+        let [apa_id] = apa_ids.as_slice() else {
+            return Ok(None);
+        };
+        Ok(
+            match AttributePrototypeArgument::value_source(ctx, *apa_id).await? {
+                ValueSource::Variable(variable_id) => Some(variable_id),
+                _ => None,
+            },
+        )
+    }
+
     /// Subscriptions from this attribute value to others. If this attribute value is unset or
     /// is not set solely to subscriptions, this returns None.
     pub async fn subscriptions(
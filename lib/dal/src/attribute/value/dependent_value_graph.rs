@@ -46,9 +46,11 @@ use crate::{
     component::ControllingFuncData,
     dependency_graph::DependencyGraph,
     schema::leaf::LeafPrototype,
+    variable::Variable,
     workspace_snapshot::{
         DependentValueRoot,
         WorkspaceSnapshotSelector,
+        content_address::ContentAddressDiscriminants,
         edge_weight::EdgeWeightKindDiscriminants,
         node_weight::NodeWeightDiscriminants,
     },
@@ -172,7 +174,7 @@ impl DependentValueGraph {
 
             let node_weight = workspace_snapshot.get_node_weight(root_ulid).await?;
 
-            match node_weight.into() {
+            match NodeWeightDiscriminants::from(&node_weight) {
                 NodeWeightDiscriminants::AttributeValue => {
                     let initial_attribute_value_id: AttributeValueId = root_ulid.into();
 
@@ -238,6 +240,27 @@ impl DependentValueGraph {
                             .map(|d| WorkQueueValue::Initial(*d)),
                     );
                 }
+                NodeWeightDiscriminants::Content
+                    if node_weight
+                        .get_content_node_weight_of_kind(ContentAddressDiscriminants::Variable)
+                        .is_ok() =>
+                {
+                    // Variables work just like secrets: only their direct dependents go into the
+                    // graph, and they must be marked as needing to be processed.
+                    let direct_dependents =
+                        Variable::direct_dependent_attribute_values(ctx, root_ulid.into()).await?;
+                    self.values_that_need_to_execute_from_prototype_function
+                        .extend(
+                            direct_dependents
+                                .iter()
+                                .map(|id| DependentValue::AttributeValue(*id)),
+                        );
+                    values.extend(
+                        direct_dependents
+                            .iter()
+                            .map(|d| WorkQueueValue::Initial(*d)),
+                    );
+                }
                 discrim => {
                     warn!(%discrim, %root_ulid, "skipping dependent value graph generation for unsupported node weight");
                 }
@@ -0,0 +1,96 @@
+//! String template sources, e.g. `arn:aws:s3:::${bucket}/${prefix}`, where each placeholder is
+//! bound to a value on another component.
+//!
+//! A templated attribute value's prototype uses the `si:stringTemplate` intrinsic. The template
+//! itself (along with where each placeholder comes from) is stored as a static argument on the
+//! `template` func argument, and every bound value is a real [`ValueSubscription`] on the
+//! `bindings` func argument, so they participate in dependent value updates and the subscription
+//! graph like any other subscription.
+
+use std::collections::BTreeMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+use si_id::ComponentId;
+
+use super::{
+    AttributeValueError,
+    AttributeValueResult,
+    subscription::ValueSubscription,
+};
+use crate::{
+    DalContext,
+    func::backend::string_template,
+};
+
+/// The func argument holding the [`StringTemplate`] itself.
+pub const TEMPLATE_ARG_NAME: &str = "template";
+/// The func argument holding the subscriptions for each placeholder.
+pub const BINDINGS_ARG_NAME: &str = "bindings";
+
+/// A string template and the value each of its placeholders is bound to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StringTemplate {
+    pub template: String,
+    pub bindings: BTreeMap<String, StringTemplateBinding>,
+}
+
+/// Where the value for a single placeholder comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StringTemplateBinding {
+    pub component_id: ComponentId,
+    /// JSON pointer to the value on the component, e.g. `/domain/BucketName`.
+    pub path: String,
+}
+
+impl StringTemplate {
+    /// Ensures the template parses and that every placeholder has a binding.
+    pub fn validate(&self) -> AttributeValueResult<()> {
+        let placeholders = string_template::placeholders(&self.template)
+            .map_err(|err| AttributeValueError::StringTemplateInvalid(err.to_string()))?;
+        for name in placeholders {
+            if !self.bindings.contains_key(&name) {
+                return Err(AttributeValueError::StringTemplateInvalid(format!(
+                    "placeholder {name} has no binding"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the `bindings` func argument from the resolved values of this template's
+    /// subscriptions.
+    ///
+    /// Bindings are matched to subscriptions by component and path. A binding without a matching
+    /// subscription (say, because its component was deleted) is bound to null.
+    pub(crate) async fn bind_values(
+        &self,
+        ctx: &DalContext,
+        subscription_values: Vec<(ValueSubscription, Value)>,
+    ) -> AttributeValueResult<Value> {
+        let mut resolved = Vec::with_capacity(subscription_values.len());
+        for (subscription, value) in subscription_values {
+            let (component_id, path) = subscription.path_from_component(ctx).await?;
+            resolved.push((component_id, path, value));
+        }
+
+        let mut bindings = serde_json::Map::new();
+        for (name, binding) in &self.bindings {
+            let value = resolved
+                .iter()
+                .find(|(component_id, path, _)| {
+                    *component_id == binding.component_id && *path == binding.path
+                })
+                .map(|(_, _, value)| value.clone())
+                .unwrap_or(Value::Null);
+            bindings.insert(name.clone(), value);
+        }
+
+        Ok(Value::Object(bindings))
+    }
+}
@@ -15,9 +15,12 @@ use shuttle_server::{
     Shuttle,
     ShuttleError,
 };
-use si_events::audit_log::{
-    AuditLog,
-    AuditLogKind,
+use si_events::{
+    EventSessionId,
+    audit_log::{
+        AuditLog,
+        AuditLogKind,
+    },
 };
 use si_id::{
     ChangeSetId,
@@ -60,6 +63,17 @@ pub enum AuditLoggingError {
 
 type Result<T> = std::result::Result<T, AuditLoggingError>;
 
+/// An audit log written while a [`checkpoint`](DalContext::checkpoint) is held, along with
+/// where to publish it once the work it records is kept.
+#[derive(Debug, Clone)]
+pub(crate) struct CheckpointedAuditLog {
+    workspace_id: WorkspacePk,
+    change_set_id: ChangeSetId,
+    event_session_id: EventSessionId,
+    audit_log: AuditLog,
+    destination_change_set_id: ChangeSetId,
+}
+
 /// Publishes all pending [`AuditLogs`](AuditLog) to the audit logs stream for the event session.
 ///
 /// Provide the "override" [`EventSessionId`] if you'd like to use a different identifier than
@@ -237,26 +251,51 @@ pub(crate) async fn write(
     let destination_change_set_id =
         override_destination_change_set_id.unwrap_or(ctx.change_set_id());
 
-    let pending_events_stream = ctx.jetstream_streams().pending_events();
-    pending_events_stream
-        .publish_audit_log(
-            workspace_id,
-            ctx.change_set_id(),
-            ctx.event_session_id(),
-            &AuditLog::new(
-                ctx.events_actor(),
-                kind,
-                entity_name,
-                destination_change_set_id,
-                ctx.authentication_method(),
-            ),
+    let audit_log = CheckpointedAuditLog {
+        workspace_id,
+        change_set_id: ctx.change_set_id(),
+        event_session_id: ctx.event_session_id(),
+        audit_log: AuditLog::new(
+            ctx.events_actor(),
+            kind,
+            entity_name,
             destination_change_set_id,
-        )
-        .await?;
+            ctx.authentication_method(),
+        ),
+        destination_change_set_id,
+    };
 
-    // Increment counter for metrics
-    ctx.pending_audit_logs_count()
-        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    // While a checkpoint is held, the audit log is only published if the work is kept
+    if let Some(checkpointed) = ctx.checkpointed_audit_logs().lock().await.as_mut() {
+        checkpointed.push(audit_log);
+        return Ok(());
+    }
+
+    publish_checkpointed(ctx, vec![audit_log]).await
+}
+
+/// Publishes audit logs that were held back for a [`checkpoint`](DalContext::checkpoint) to the
+/// pending events stream.
+pub(crate) async fn publish_checkpointed(
+    ctx: &DalContext,
+    audit_logs: Vec<CheckpointedAuditLog>,
+) -> Result<()> {
+    let pending_events_stream = ctx.jetstream_streams().pending_events();
+    for audit_log in audit_logs {
+        pending_events_stream
+            .publish_audit_log(
+                audit_log.workspace_id,
+                audit_log.change_set_id,
+                audit_log.event_session_id,
+                &audit_log.audit_log,
+                audit_log.destination_change_set_id,
+            )
+            .await?;
+
+        // Increment counter for metrics
+        ctx.pending_audit_logs_count()
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
 
     Ok(())
 }
@@ -31,7 +31,10 @@ use billing_events::{
     BillingEventsError,
 };
 use chrono::Utc;
-use si_events::FuncRunId;
+use si_events::{
+    FuncRun,
+    FuncRunId,
+};
 use telemetry::prelude::*;
 use thiserror::Error;
 
@@ -111,6 +114,8 @@ pub async fn for_head_change_set_pointer_update(
         schema_id: None,
         schema_name: None,
         func_run_id: None,
+        func_kind: None,
+        func_run_duration_ms: None,
 
         kind: BillingEventKind::HeadChangeSetPointerUpdate,
     };
@@ -163,6 +168,8 @@ pub(crate) async fn for_change_set_status_update(
         schema_id: None,
         schema_name: None,
         func_run_id: None,
+        func_kind: None,
+        func_run_duration_ms: None,
 
         kind: BillingEventKind::ChangeSetStatusUpdate,
     };
@@ -219,6 +226,8 @@ pub(crate) async fn for_resource_create(
         schema_id: Some(schema_id),
         schema_name: Some(schema_name),
         func_run_id: Some(func_run_id),
+        func_kind: None,
+        func_run_duration_ms: None,
 
         kind: BillingEventKind::ResourceCreate,
     };
@@ -275,6 +284,8 @@ pub(crate) async fn for_resource_delete(
         schema_id: Some(schema_id),
         schema_name: Some(schema_name),
         func_run_id: Some(func_run_id),
+        func_kind: None,
+        func_run_duration_ms: None,
 
         kind: BillingEventKind::ResourceDelete,
     };
@@ -287,3 +298,51 @@ pub(crate) async fn for_resource_delete(
 
     Ok(())
 }
+
+#[instrument(
+    name = "billing_publish.for_func_run_complete",
+    level = "debug",
+    skip_all,
+    fields(si.func_run.id = %func_run.id())
+)]
+/// Publishes a billing event with the kind and duration of a finished func run. Unlike the other
+/// billing events, this is published for every change set since funcs run (and consume resources)
+/// regardless of whether the change set is HEAD.
+pub(crate) async fn for_func_run_complete(
+    ctx: &DalContext,
+    func_run: &FuncRun,
+) -> BillingPublishResult<()> {
+    let change_set = ctx.change_set()?;
+    let duration = func_run.updated_at() - func_run.created_at();
+
+    let workspace_id = func_run.workspace_pk();
+    let event = BillingEvent {
+        workspace_id,
+        workspace_snapshot_address: change_set.workspace_snapshot_address,
+        event_timestamp: Utc::now(),
+        change_set_status: change_set.status.into(),
+        change_set_id: func_run.change_set_id(),
+        merge_requested_by_user_id: change_set.merge_requested_by_user_id,
+
+        resource_count: None,
+
+        component_id: func_run.component_id(),
+        component_name: None,
+        schema_variant_id: None,
+        schema_id: None,
+        schema_name: None,
+        func_run_id: Some(func_run.id()),
+        func_kind: Some(func_run.function_kind()),
+        func_run_duration_ms: Some(duration.num_milliseconds().max(0) as u64),
+
+        kind: BillingEventKind::FuncRunComplete,
+    };
+
+    ctx.services_context()
+        .jetstream_streams()
+        .billing_events()
+        .publish_workspace_update(&workspace_id.to_string(), &event)
+        .await?;
+
+    Ok(())
+}
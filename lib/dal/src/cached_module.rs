@@ -14,9 +14,12 @@ use chrono::{
 use edda_client::EddaClient;
 use itertools::Itertools;
 use module_index_client::{
+    MODULE_BUNDLE_MANIFEST_VERSION,
+    ModuleBundleManifest,
     ModuleDetailsResponse,
     ModuleIndexClient,
     ModuleIndexClientError,
+    ModuleSignature,
 };
 use postgres_types::ToSql;
 use serde::{
@@ -32,6 +35,7 @@ pub use si_id::CachedModuleId;
 use si_id::UserPk;
 use si_pkg::{
     SiPkg,
+    SiPkgBundle,
     SiPkgError,
     SiPkgSchemaData,
     SiPkgSchemaVariantData,
@@ -57,6 +61,12 @@ const PLACEHOLDER_OWNER_USER_ID: &str = "-";
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum CachedModuleError {
+    #[error("module {0} in the bundle has hash {1}, but the manifest expects {2}")]
+    BundleHashMismatch(String, String, String),
+    #[error("module {0} is listed in the bundle manifest but missing from the bundle")]
+    BundleModuleMissing(String),
+    #[error("unsupported module bundle version {0}, expected at most {1}")]
+    BundleVersionUnsupported(u32, u32),
     #[error("edda client error: {0}")]
     EddaClient(#[from] edda_client::ClientError),
     #[error("join error: {0}")]
@@ -104,6 +114,8 @@ pub struct CachedModule {
     pub created_at: DateTime<Utc>,
     pub package_data: Option<Vec<u8>>,
     pub scoped_to_user_pk: Option<UserPk>,
+    /// The publisher signature of the module, if it was signed when uploaded to the module index.
+    pub signature: Option<ModuleSignature>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -139,6 +151,8 @@ impl TryFrom<PgRow> for CachedModule {
         let component_type = component_type_string.parse()?;
         let package_summary: Option<serde_json::Value> = row.try_get("package_summary")?;
         let package_summary = package_summary.map(serde_json::from_value).transpose()?;
+        let signature: Option<serde_json::Value> = row.try_get("signature")?;
+        let signature = signature.map(serde_json::from_value).transpose()?;
 
         Ok(Self {
             id: row.try_get("id")?,
@@ -155,6 +169,7 @@ impl TryFrom<PgRow> for CachedModule {
             created_at: row.try_get("created_at")?,
             package_data: row.try_get("package_data")?,
             scoped_to_user_pk: row.try_get("scoped_to_user_pk")?,
+            signature,
         })
     }
 }
@@ -173,7 +188,8 @@ const CACHED_MODULE_GET_FIELDS: &str = "
     created_at,
     package_data,
     scoped_to_user_pk,
-    package_summary
+    package_summary,
+    signature
 ";
 
 const CACHED_MODULE_LIST_FIELDS: &str = "
@@ -190,7 +206,8 @@ const CACHED_MODULE_LIST_FIELDS: &str = "
     created_at,
     NULL::bytea AS package_data,
     scoped_to_user_pk,
-    package_summary
+    package_summary,
+    signature
 ";
 
 const BATCH_SIZE: usize = 10;
@@ -294,6 +311,74 @@ impl CachedModule {
         Ok(new_modules)
     }
 
+    /// Loads the modules of an offline module bundle into the cache, for installations that
+    /// cannot reach the module index.
+    ///
+    /// Unlike [`Self::update_cached_modules`], nothing is removed from the cache: modules already
+    /// cached are left alone and only the missing ones are added.
+    pub async fn update_cached_modules_from_bundle(
+        ctx: &DalContext,
+        bundle_bytes: Vec<u8>,
+        edda_client: EddaClient,
+    ) -> CachedModuleResult<Vec<CachedModule>> {
+        let bundle =
+            slow_rt::spawn(async move { SiPkgBundle::load_from_bytes(&bundle_bytes) })?.await??;
+        let manifest: ModuleBundleManifest = bundle.manifest()?;
+        if manifest.version > MODULE_BUNDLE_MANIFEST_VERSION {
+            return Err(CachedModuleError::BundleVersionUnsupported(
+                manifest.version,
+                MODULE_BUNDLE_MANIFEST_VERSION,
+            ));
+        }
+        let bundled_ids: HashSet<String> = bundle.pkg_names().into_iter().collect();
+
+        let modules: HashMap<_, _> = manifest
+            .modules
+            .into_iter()
+            .map(|module| (module.latest_hash.to_owned(), module))
+            .collect();
+        let hashes = modules.keys().map(ToOwned::to_owned).collect_vec();
+        let uncached_hashes = CachedModule::find_missing_entries(ctx, hashes).await?;
+
+        let mut new_modules = vec![];
+        for hash_chunk in uncached_hashes.chunks(BATCH_SIZE) {
+            for uncached_hash in hash_chunk {
+                let Some(module) = modules.get(uncached_hash) else {
+                    continue;
+                };
+                if !bundled_ids.contains(&module.id) {
+                    return Err(CachedModuleError::BundleModuleMissing(module.id.to_owned()));
+                }
+
+                let pkg = bundle.pkg(&module.id)?;
+                let hash = pkg.hash()?.to_string();
+                if &hash != uncached_hash {
+                    return Err(CachedModuleError::BundleHashMismatch(
+                        module.id.to_owned(),
+                        hash,
+                        uncached_hash.to_owned(),
+                    ));
+                }
+
+                let module_bytes = Arc::new(pkg.write_to_bytes()?);
+                if let Some(new_cached_module) =
+                    Self::insert(ctx, module, module_bytes, None).await?
+                {
+                    new_modules.push(new_cached_module);
+                }
+            }
+
+            ctx.commit_no_rebase().await?;
+        }
+
+        // Ask edda to rebuild the deployment MVs, which include the cached modules
+        edda_client.rebuild_for_deployment().await?;
+
+        Self::update_missing_package_summaries(ctx).await?;
+
+        Ok(new_modules)
+    }
+
     async fn cache_modules(
         ctx: &DalContext,
         modules: &HashMap<String, ModuleDetailsResponse>,
@@ -579,10 +664,11 @@ impl CachedModule {
                     latest_hash,
                     created_at,
                     package_data,
-                    scoped_to_user_pk
+                    scoped_to_user_pk,
+                    signature
                 ) VALUES (
                     $1, $2, $3, $4, $5, $6,
-                    $7, $8, $9, $10, $11, $12, $13
+                    $7, $8, $9, $10, $11, $12, $13, $14
                 ) RETURNING
                     {CACHED_MODULE_LIST_FIELDS}
             "
@@ -613,6 +699,11 @@ impl CachedModule {
         );
 
         let bytes_ref = pkg_bytes.as_slice();
+        let signature = module_details
+            .signature
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
         let row = ctx
             .txns()
             .await?
@@ -635,6 +726,7 @@ impl CachedModule {
                     &module_details.created_at,
                     &bytes_ref,
                     &scoped_to_user_pk,
+                    &signature,
                 ],
             )
             .await?;
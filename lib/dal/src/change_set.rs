@@ -79,6 +79,7 @@ use crate::{
 
 pub mod approval;
 pub mod event;
+pub mod rebase;
 pub mod status;
 pub mod view;
 
@@ -270,6 +271,9 @@ pub struct ChangeSet {
     pub status: ChangeSetStatus,
     pub base_change_set_id: Option<ChangeSetId>,
     pub workspace_snapshot_address: WorkspaceSnapshotAddress,
+    /// The snapshot the change set was forked from, or last rebased onto. `None` if that isn't
+    /// known, such as for change sets forked before it was recorded.
+    pub base_snapshot_address: Option<WorkspaceSnapshotAddress>,
     pub workspace_id: Option<WorkspacePk>,
     pub merge_requested_by_user_id: Option<UserPk>,
     pub merge_requested_at: Option<DateTime<Utc>>,
@@ -292,6 +296,7 @@ impl TryFrom<PgRow> for ChangeSet {
             status,
             base_change_set_id: value.try_get("base_change_set_id")?,
             workspace_snapshot_address: value.try_get("workspace_snapshot_address")?,
+            base_snapshot_address: value.try_get("base_snapshot_address")?,
             workspace_id: value.try_get("workspace_id")?,
             merge_requested_by_user_id: value.try_get("merge_requested_by_user_id")?,
             merge_requested_at: value.try_get("merge_requested_at")?,
@@ -323,7 +328,7 @@ impl ChangeSet {
             .await?
             .pg()
             .query_one(
-                "INSERT INTO change_set_pointers (id, name, base_change_set_id, status, workspace_id, workspace_snapshot_address, created_by_user_id) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
+                "INSERT INTO change_set_pointers (id, name, base_change_set_id, status, workspace_id, workspace_snapshot_address, base_snapshot_address, created_by_user_id) VALUES ($1, $2, $3, $4, $5, $6, $6, $7) RETURNING *",
                 &[&change_set_id, &name, &base_change_set_id, &ChangeSetStatus::Open.to_string(), &workspace_id, &workspace_snapshot_address, &user_id],
             )
             .await?;
@@ -452,7 +457,85 @@ impl ChangeSet {
             )
             .await?;
 
-        // Record when old snapshot was last used
+        Self::record_snapshot_last_used(ctx, old_snapshot_address).await?;
+
+        self.workspace_snapshot_address = workspace_snapshot_address;
+
+        Ok(())
+    }
+
+    /// Updates the pointer like [`Self::update_pointer`], but only if the change set still
+    /// points at `expected_snapshot_address`. Returns false, leaving the pointer alone, if
+    /// something else has moved it.
+    #[instrument(
+        name = "change_set.compare_and_swap_pointer",
+        level = "debug",
+        skip_all
+    )]
+    pub async fn compare_and_swap_pointer(
+        &mut self,
+        ctx: &DalContext,
+        expected_snapshot_address: WorkspaceSnapshotAddress,
+        workspace_snapshot_address: WorkspaceSnapshotAddress,
+    ) -> ChangeSetResult<bool> {
+        let swapped = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "UPDATE change_set_pointers SET workspace_snapshot_address = $2, updated_at = CLOCK_TIMESTAMP()
+                 WHERE id = $1 AND workspace_snapshot_address = $3
+                 RETURNING id",
+                &[
+                    &self.id,
+                    &workspace_snapshot_address,
+                    &expected_snapshot_address,
+                ],
+            )
+            .await?
+            .is_some();
+        if !swapped {
+            return Ok(false);
+        }
+
+        Self::record_snapshot_last_used(ctx, expected_snapshot_address).await?;
+
+        self.workspace_snapshot_address = workspace_snapshot_address;
+
+        Ok(true)
+    }
+
+    /// Records the snapshot the change set is based on, such as after rebasing it onto HEAD.
+    /// `None` forgets it, for when the change set can no longer be compared against it.
+    #[instrument(
+        name = "change_set.update_base_snapshot_address",
+        level = "debug",
+        skip_all
+    )]
+    pub async fn update_base_snapshot_address(
+        &mut self,
+        ctx: &DalContext,
+        base_snapshot_address: Option<WorkspaceSnapshotAddress>,
+    ) -> ChangeSetResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "UPDATE change_set_pointers SET base_snapshot_address = $2, updated_at = CLOCK_TIMESTAMP() WHERE id = $1",
+                &[&self.id, &base_snapshot_address],
+            )
+            .await?;
+
+        self.base_snapshot_address = base_snapshot_address;
+
+        Ok(())
+    }
+
+    /// Records when the snapshot was last pointed at, for snapshot garbage collection.
+    async fn record_snapshot_last_used(
+        ctx: &DalContext,
+        snapshot_address: WorkspaceSnapshotAddress,
+    ) -> ChangeSetResult<()> {
         ctx.txns()
             .await?
             .pg()
@@ -461,12 +544,10 @@ impl ChangeSet {
                  VALUES ($1, CLOCK_TIMESTAMP(), CLOCK_TIMESTAMP())
                  ON CONFLICT (snapshot_id)
                  DO UPDATE SET last_used_at = CLOCK_TIMESTAMP()",
-                &[&old_snapshot_address.to_string()],
+                &[&snapshot_address.to_string()],
             )
             .await?;
 
-        self.workspace_snapshot_address = workspace_snapshot_address;
-
         Ok(())
     }
 
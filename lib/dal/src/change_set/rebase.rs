@@ -0,0 +1,549 @@
+//! This module contains the ability to explicitly rebase a [`ChangeSet`] onto the current HEAD.
+//!
+//! Open change sets already have HEAD's changes replayed onto them, but wherever the change set
+//! and HEAD disagree, the change set quietly keeps its own version. Detecting a rebase reports
+//! those disagreements as conflicts: attribute values set differently on each side and
+//! components deleted on one side but not the other. Rebasing then applies a resolution for each
+//! conflict and pulls in any components created on HEAD since the change set was forked.
+//!
+//! Whether a component that only exists on one side was created there or deleted on the other is
+//! decided by the change set's base snapshot, the one it was forked from or last rebased onto.
+//! The base snapshot also tells conflicts apart from plain local changes: an attribute value only
+//! conflicts if both sides changed it since the base, and a component deleted in the change set
+//! only conflicts if HEAD changed it since. Where the base isn't known, every disagreement is
+//! reported as a conflict for the user to resolve.
+
+#![warn(
+    bad_style,
+    clippy::missing_panics_doc,
+    clippy::panic,
+    clippy::panic_in_result_fn,
+    clippy::unwrap_in_result,
+    clippy::unwrap_used,
+    dead_code,
+    improper_ctypes,
+    missing_debug_implementations,
+    missing_docs,
+    no_mangle_generic_items,
+    non_shorthand_field_patterns,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unreachable_pub,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true
+)]
+
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+use si_events::workspace_snapshot::{
+    Change,
+    EntityKind,
+};
+use si_id::{
+    ChangeSetId,
+    EntityId,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use super::ChangeSetError;
+use crate::{
+    AttributePrototype,
+    AttributeValue,
+    AttributeValueId,
+    ChangeSet,
+    Component,
+    ComponentError,
+    ComponentId,
+    DalContext,
+    TransactionsError,
+    WorkspaceSnapshot,
+    WorkspaceSnapshotError,
+    attribute::{
+        prototype::{
+            AttributePrototypeError,
+            AttributePrototypeId,
+            argument::{
+                AttributePrototypeArgument,
+                AttributePrototypeArgumentError,
+                static_value::StaticArgumentValue,
+                value_source::ValueSource,
+            },
+        },
+        value::AttributeValueError,
+    },
+    workspace_snapshot::{
+        dependent_value_root::DependentValueRootError,
+        selector::{
+            WorkspaceSnapshotSelector,
+            WorkspaceSnapshotSelectorDiscriminants,
+        },
+        split_snapshot::SplitSnapshot,
+    },
+};
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ChangeSetRebaseError {
+    #[error("attribute prototype error: {0}")]
+    AttributePrototype(#[from] Box<AttributePrototypeError>),
+    #[error("attribute prototype argument error: {0}")]
+    AttributePrototypeArgument(#[from] Box<AttributePrototypeArgumentError>),
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] Box<AttributeValueError>),
+    #[error("cannot rebase the HEAD change set onto itself: {0}")]
+    CannotRebaseHead(ChangeSetId),
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
+    #[error("dependent value root error: {0}")]
+    DependentValueRoot(#[from] DependentValueRootError),
+    #[error("no resolution given for conflict: {0}")]
+    MissingResolution(EntityId),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("resolution given for unknown conflict: {0}")]
+    UnknownConflict(EntityId),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+impl From<AttributePrototypeError> for ChangeSetRebaseError {
+    fn from(value: AttributePrototypeError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<AttributePrototypeArgumentError> for ChangeSetRebaseError {
+    fn from(value: AttributePrototypeArgumentError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<AttributeValueError> for ChangeSetRebaseError {
+    fn from(value: AttributeValueError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<ComponentError> for ChangeSetRebaseError {
+    fn from(value: ComponentError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+type Result<T> = std::result::Result<T, ChangeSetRebaseError>;
+
+/// A place where a [`ChangeSet`] and HEAD disagree, which needs a [`RebaseResolution`] before
+/// the change set can be rebased.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RebaseConflict {
+    /// An attribute value that was set on the component to different values on each side since
+    /// the base snapshot.
+    #[serde(rename_all = "camelCase")]
+    AttributeValue {
+        /// The conflicting attribute value.
+        attribute_value_id: AttributeValueId,
+        /// The component the attribute value belongs to.
+        component_id: ComponentId,
+        /// The JSON pointer path to the attribute value within its component.
+        path: String,
+        /// The value on HEAD.
+        head_value: Option<Value>,
+        /// The value in the change set.
+        change_set_value: Option<Value>,
+    },
+    /// A component that exists on HEAD, and has changed there, but has been deleted in the change
+    /// set.
+    #[serde(rename_all = "camelCase")]
+    ComponentDeletedInChangeSet {
+        /// The deleted component.
+        component_id: ComponentId,
+    },
+    /// A component that exists in the change set but has been deleted on HEAD.
+    #[serde(rename_all = "camelCase")]
+    ComponentDeletedOnHead {
+        /// The deleted component.
+        component_id: ComponentId,
+    },
+}
+
+impl RebaseConflict {
+    /// The id of the entity in conflict, which is what resolutions are keyed by.
+    pub fn entity_id(&self) -> EntityId {
+        match self {
+            Self::AttributeValue {
+                attribute_value_id, ..
+            } => attribute_value_id.into_inner().into(),
+            Self::ComponentDeletedInChangeSet { component_id }
+            | Self::ComponentDeletedOnHead { component_id } => component_id.into_inner().into(),
+        }
+    }
+}
+
+/// Which side wins a [`RebaseConflict`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseResolution {
+    /// Keep the change set's version.
+    KeepChangeSet,
+    /// Take HEAD's version.
+    TakeHead,
+}
+
+/// What rebasing a [`ChangeSet`] onto HEAD involves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseReport {
+    /// Whether the change set has any changes of its own that applying it would make to its base.
+    pub has_local_changes: bool,
+    /// The conflicts that need a resolution, ordered by entity id.
+    pub conflicts: Vec<RebaseConflict>,
+    /// Components created on HEAD after the change set was forked that it doesn't have yet.
+    pub incoming_component_ids: Vec<ComponentId>,
+}
+
+/// Compares the [`ChangeSet`] in the provided [`DalContext`] with HEAD and reports the conflicts
+/// that rebasing it would have to resolve.
+#[instrument(name = "change_set.rebase.detect", level = "info", skip_all)]
+pub async fn detect(ctx: &DalContext) -> Result<RebaseReport> {
+    let change_set = ChangeSet::get_by_id(ctx, ctx.change_set_id()).await?;
+    if change_set.is_head(ctx).await? {
+        return Err(ChangeSetRebaseError::CannotRebaseHead(change_set.id));
+    }
+
+    let snapshot_kind: WorkspaceSnapshotSelectorDiscriminants = ctx.workspace_snapshot()?.into();
+    let has_local_changes = match snapshot_kind {
+        WorkspaceSnapshotSelectorDiscriminants::LegacySnapshot => change_set
+            .detect_updates_that_will_be_applied_legacy(ctx)
+            .await?
+            .is_some(),
+        WorkspaceSnapshotSelectorDiscriminants::SplitSnapshot => change_set
+            .detect_updates_that_will_be_applied_split(ctx)
+            .await?
+            .is_some(),
+    };
+
+    let head_ctx = ctx.clone_with_head().await?;
+    let base_ctx = match change_set.base_snapshot_address {
+        Some(base_snapshot_address) => {
+            let mut base_ctx = ctx.clone();
+            match snapshot_kind {
+                WorkspaceSnapshotSelectorDiscriminants::LegacySnapshot => base_ctx
+                    .set_workspace_snapshot(
+                        WorkspaceSnapshot::find(ctx, base_snapshot_address).await?,
+                    ),
+                WorkspaceSnapshotSelectorDiscriminants::SplitSnapshot => base_ctx
+                    .set_workspace_split_snapshot(
+                        SplitSnapshot::find(ctx, base_snapshot_address).await?,
+                    ),
+            }
+            Some(base_ctx)
+        }
+        None => None,
+    };
+    let changes = ctx.detect_changes_from_head().await?;
+    // What HEAD itself has changed since the base snapshot
+    let head_changed_entity_ids: Option<HashSet<EntityId>> = match &base_ctx {
+        Some(base_ctx) => Some(
+            changes_between(base_ctx, &head_ctx)
+                .await?
+                .into_iter()
+                .map(|change| change.entity_id)
+                .collect(),
+        ),
+        None => None,
+    };
+
+    let mut conflicts = Vec::new();
+    let mut incoming_component_ids = Vec::new();
+    let mut conflicting_component_ids = HashSet::new();
+
+    for change in changes
+        .iter()
+        .filter(|change| change.entity_kind == EntityKind::Component)
+    {
+        let component_id: ComponentId = change.entity_id.into_inner().into();
+        let in_base = match &base_ctx {
+            Some(base_ctx) => Some(Component::exists_by_id(base_ctx, component_id).await?),
+            None => None,
+        };
+
+        let changed_on_head = head_changed_entity_ids
+            .as_ref()
+            .is_none_or(|entity_ids| entity_ids.contains(&change.entity_id));
+
+        let on_head = component_to_delete(&head_ctx, component_id).await?;
+        let in_change_set = component_to_delete(ctx, component_id).await?;
+        let conflict = match (on_head, in_change_set) {
+            (Some(false), None) if in_base == Some(false) => {
+                incoming_component_ids.push(component_id);
+                None
+            }
+            // Deleting a component HEAD hasn't touched is just a change of our own
+            (Some(false), None | Some(true)) if changed_on_head => {
+                Some(RebaseConflict::ComponentDeletedInChangeSet { component_id })
+            }
+            (None, Some(false)) if in_base != Some(false) => {
+                Some(RebaseConflict::ComponentDeletedOnHead { component_id })
+            }
+            (Some(true), Some(false)) => {
+                Some(RebaseConflict::ComponentDeletedOnHead { component_id })
+            }
+            _ => None,
+        };
+
+        if let Some(conflict) = conflict {
+            conflicting_component_ids.insert(component_id);
+            conflicts.push(conflict);
+        }
+    }
+
+    for change in changes
+        .iter()
+        .filter(|change| change.entity_kind == EntityKind::AttributeValue)
+    {
+        let attribute_value_id: AttributeValueId = change.entity_id.into_inner().into();
+        if !node_exists(ctx, attribute_value_id).await?
+            || !node_exists(&head_ctx, attribute_value_id).await?
+        {
+            continue;
+        }
+
+        // Only values set on the component itself can conflict, everything else is computed
+        // from them
+        if AttributeValue::component_prototype_id(ctx, attribute_value_id)
+            .await?
+            .is_none()
+            && AttributeValue::component_prototype_id(&head_ctx, attribute_value_id)
+                .await?
+                .is_none()
+        {
+            continue;
+        }
+
+        let change_set_value = AttributeValue::get_by_id(ctx, attribute_value_id)
+            .await?
+            .value(ctx)
+            .await?;
+        let head_value = AttributeValue::get_by_id(&head_ctx, attribute_value_id)
+            .await?
+            .value(&head_ctx)
+            .await?;
+        if change_set_value == head_value {
+            continue;
+        }
+
+        // A value only one side has changed since the base snapshot is not a conflict
+        if let Some(base_ctx) = &base_ctx {
+            let base_value = if node_exists(base_ctx, attribute_value_id).await? {
+                AttributeValue::get_by_id(base_ctx, attribute_value_id)
+                    .await?
+                    .value(base_ctx)
+                    .await?
+            } else {
+                None
+            };
+            if base_value == change_set_value || base_value == head_value {
+                continue;
+            }
+        }
+
+        let (component_id, path) =
+            AttributeValue::path_from_component(ctx, attribute_value_id).await?;
+        // The component's conflict covers its values
+        if conflicting_component_ids.contains(&component_id) {
+            continue;
+        }
+
+        conflicts.push(RebaseConflict::AttributeValue {
+            attribute_value_id,
+            component_id,
+            path,
+            head_value,
+            change_set_value,
+        });
+    }
+
+    conflicts.sort_by_key(RebaseConflict::entity_id);
+    incoming_component_ids.sort();
+
+    Ok(RebaseReport {
+        has_local_changes,
+        conflicts,
+        incoming_component_ids,
+    })
+}
+
+/// Rebases the [`ChangeSet`] in the provided [`DalContext`] onto HEAD, applying the given
+/// resolution to each conflict [`detect`] reports and pulling in the components created on HEAD
+/// since the change set was forked. Every conflict needs a resolution. HEAD's current snapshot
+/// becomes the change set's base snapshot.
+///
+/// Returns the report the resolutions were applied to. The caller is responsible for committing.
+#[instrument(name = "change_set.rebase.rebase_onto_head", level = "info", skip_all)]
+pub async fn rebase_onto_head(
+    ctx: &DalContext,
+    resolutions: &HashMap<EntityId, RebaseResolution>,
+) -> Result<RebaseReport> {
+    let report = detect(ctx).await?;
+
+    let conflicting_entity_ids: HashSet<EntityId> = report
+        .conflicts
+        .iter()
+        .map(RebaseConflict::entity_id)
+        .collect();
+    if let Some(entity_id) = resolutions
+        .keys()
+        .find(|entity_id| !conflicting_entity_ids.contains(entity_id))
+    {
+        return Err(ChangeSetRebaseError::UnknownConflict(*entity_id));
+    }
+
+    let head_ctx = ctx.clone_with_head().await?;
+    for conflict in &report.conflicts {
+        let entity_id = conflict.entity_id();
+        let resolution = resolutions
+            .get(&entity_id)
+            .ok_or(ChangeSetRebaseError::MissingResolution(entity_id))?;
+        if *resolution == RebaseResolution::KeepChangeSet {
+            continue;
+        }
+
+        match conflict {
+            RebaseConflict::AttributeValue {
+                attribute_value_id, ..
+            } => {
+                match AttributeValue::component_prototype_id(&head_ctx, *attribute_value_id).await?
+                {
+                    Some(head_prototype_id) => {
+                        copy_component_prototype_from_head(
+                            ctx,
+                            &head_ctx,
+                            *attribute_value_id,
+                            head_prototype_id,
+                        )
+                        .await?
+                    }
+                    None => AttributeValue::use_default_prototype(ctx, *attribute_value_id).await?,
+                }
+            }
+            RebaseConflict::ComponentDeletedInChangeSet { component_id } => {
+                match Component::try_get_by_id(ctx, *component_id).await? {
+                    Some(component) => {
+                        component.set_to_delete(ctx, false).await?;
+                    }
+                    None => Component::restore_from_base_change_set(ctx, *component_id).await?,
+                }
+            }
+            RebaseConflict::ComponentDeletedOnHead { component_id } => {
+                if Component::exists_by_id(&head_ctx, *component_id).await? {
+                    Component::get_by_id(ctx, *component_id)
+                        .await?
+                        .set_to_delete(ctx, true)
+                        .await?;
+                } else {
+                    Component::remove(ctx, *component_id).await?;
+                }
+            }
+        }
+    }
+
+    for component_id in &report.incoming_component_ids {
+        Component::restore_from_base_change_set(ctx, *component_id).await?;
+    }
+
+    let head_snapshot_address = head_ctx.workspace_snapshot()?.id().await;
+    ChangeSet::get_by_id(ctx, ctx.change_set_id())
+        .await?
+        .update_base_snapshot_address(ctx, Some(head_snapshot_address))
+        .await?;
+
+    Ok(report)
+}
+
+/// Gives the attribute value a copy of the component prototype it has on HEAD, so subscriptions,
+/// templates and dynamic functions keep working instead of becoming HEAD's current value.
+async fn copy_component_prototype_from_head(
+    ctx: &DalContext,
+    head_ctx: &DalContext,
+    attribute_value_id: AttributeValueId,
+    head_prototype_id: AttributePrototypeId,
+) -> Result<()> {
+    let func_id = AttributePrototype::func_id(head_ctx, head_prototype_id).await?;
+    let prototype = AttributePrototype::new(ctx, func_id).await?;
+
+    for head_apa_id in
+        AttributePrototypeArgument::list_ids_for_prototype(head_ctx, head_prototype_id).await?
+    {
+        let func_arg_id =
+            AttributePrototypeArgument::func_argument_id(head_ctx, head_apa_id).await?;
+        match AttributePrototypeArgument::value_source(head_ctx, head_apa_id).await? {
+            // Static values belong to HEAD's prototype, so the copy needs its own
+            ValueSource::StaticArgumentValue(static_value_id) => {
+                let value = StaticArgumentValue::get_by_id(head_ctx, static_value_id)
+                    .await?
+                    .value;
+                AttributePrototypeArgument::new_static_value(ctx, prototype.id, func_arg_id, value)
+                    .await?;
+            }
+            value_source => {
+                AttributePrototypeArgument::new(ctx, prototype.id, func_arg_id, value_source)
+                    .await?;
+            }
+        }
+    }
+
+    AttributeValue::set_component_prototype_id(ctx, attribute_value_id, prototype.id, None).await?;
+    ctx.add_dependent_values_and_enqueue(vec![attribute_value_id])
+        .await?;
+
+    Ok(())
+}
+
+/// The changes between the snapshot in `from_ctx` and the one in `to_ctx`.
+async fn changes_between(from_ctx: &DalContext, to_ctx: &DalContext) -> Result<Vec<Change>> {
+    Ok(match from_ctx.workspace_snapshot()? {
+        WorkspaceSnapshotSelector::LegacySnapshot(from_snapshot) => {
+            from_snapshot
+                .detect_changes(&to_ctx.workspace_snapshot()?.as_legacy_snapshot()?)
+                .await?
+        }
+        WorkspaceSnapshotSelector::SplitSnapshot(from_snapshot) => {
+            from_snapshot
+                .detect_changes(&to_ctx.workspace_snapshot()?.as_split_snapshot()?)
+                .await?
+        }
+    })
+}
+
+/// Whether the component is marked for deletion, or [`None`] if it doesn't exist at all.
+async fn component_to_delete(ctx: &DalContext, component_id: ComponentId) -> Result<Option<bool>> {
+    Ok(Component::try_get_by_id(ctx, component_id)
+        .await?
+        .map(|component| component.to_delete()))
+}
+
+async fn node_exists(ctx: &DalContext, attribute_value_id: AttributeValueId) -> Result<bool> {
+    Ok(ctx
+        .workspace_snapshot()?
+        .get_node_weight_opt(attribute_value_id)
+        .await
+        .is_some())
+}
@@ -3,6 +3,7 @@
 
 use std::{
     collections::{
+        BTreeMap,
         HashMap,
         HashSet,
         VecDeque,
@@ -95,6 +96,7 @@ use crate::{
             self,
             AttributeValueIdent,
             Source,
+            TemplateBinding,
         },
         path::AttributePath,
         prototype::{
@@ -118,6 +120,7 @@ use crate::{
     change_set::ChangeSetError,
     change_status::ChangeStatus,
     code_view::CodeViewError,
+    component_template::ComponentTemplateMembership,
     diagram::{
         DiagramError,
         SummaryDiagramManagementEdge,
@@ -137,7 +140,7 @@ use crate::{
     implement_add_edge_to,
     layer_db_types::{
         ComponentContent,
-        ComponentContentV2,
+        ComponentContentV5,
     },
     module::{
         Module,
@@ -174,6 +177,7 @@ use crate::{
             NodeWeight,
             NodeWeightError,
             category_node_weight::CategoryNodeKind,
+            reason_node_weight::Reason,
         },
         traits::attribute_value::AttributeValueExt as _,
     },
@@ -256,6 +260,8 @@ pub enum ComponentError {
     InputSocketNotFoundForComponentId(InputSocketId, ComponentId),
     #[error("input socket {0} has more than one attribute value")]
     InputSocketTooManyAttributeValues(InputSocketId),
+    #[error("invalid label key {0:?}: label keys can't be empty or contain '='")]
+    InvalidLabelKey(String),
     #[error("layer db error: {0}")]
     LayerDb(#[from] si_layer_cache::LayerDbError),
     #[error("component {0} missing attribute value for code")]
@@ -482,12 +488,20 @@ pub struct Component {
     #[serde(flatten)]
     timestamp: Timestamp,
     to_delete: bool,
+    labels: BTreeMap<String, String>,
+    owner: Option<String>,
+    promoted_from: Option<String>,
+    template_instance: Option<ComponentTemplateMembership>,
 }
 
-impl From<Component> for ComponentContentV2 {
+impl From<Component> for ComponentContentV5 {
     fn from(value: Component) -> Self {
         Self {
             timestamp: value.timestamp,
+            labels: value.labels,
+            owner: value.owner,
+            promoted_from: value.promoted_from,
+            template_instance: value.template_instance,
         }
     }
 }
@@ -500,11 +514,15 @@ pub struct ControllingFuncData {
 }
 
 impl Component {
-    pub fn assemble(node_weight: &ComponentNodeWeight, content: ComponentContentV2) -> Self {
+    pub fn assemble(node_weight: &ComponentNodeWeight, content: ComponentContentV5) -> Self {
         Self {
             id: node_weight.id().into(),
             timestamp: content.timestamp,
             to_delete: node_weight.to_delete(),
+            labels: content.labels,
+            owner: content.owner,
+            promoted_from: content.promoted_from,
+            template_instance: content.template_instance,
         }
     }
 
@@ -520,6 +538,26 @@ impl Component {
         self.to_delete
     }
 
+    /// The key/value labels attached to the component (e.g. `team=payments`).
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    /// The team or user that owns the component, if any.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    /// The id of the component in another workspace this one was promoted from, if any.
+    pub fn promoted_from(&self) -> Option<&str> {
+        self.promoted_from.as_deref()
+    }
+
+    /// The component template instance this component was created by, if any.
+    pub fn template_instance(&self) -> Option<&ComponentTemplateMembership> {
+        self.template_instance.as_ref()
+    }
+
     pub async fn change_status(&self, ctx: &DalContext) -> ComponentResult<ChangeStatus> {
         let status = if self.exists_on_head(ctx).await? {
             if self.to_delete() {
@@ -969,7 +1007,8 @@ impl Component {
                     }
                     ValueSource::Secret(_)
                     | ValueSource::StaticArgumentValue(_)
-                    | ValueSource::ValueSubscription(_) => {
+                    | ValueSource::ValueSubscription(_)
+                    | ValueSource::Variable(_) => {
                         // Should we determine if this secret is still compatible?
                         new_value_sources.push((func_arg_id, source));
                     }
@@ -1116,6 +1155,29 @@ impl Component {
             return Ok(None);
         }
 
+        // String templates have an argument per binding, so handle them before we bail on
+        // multiple arguments.
+        if intrinsic == Some(IntrinsicFunc::StringTemplate) {
+            return Ok(AttributeValue::string_template(ctx, av_id)
+                .await?
+                .map(|template| Source::Template {
+                    template: template.template,
+                    bindings: template
+                        .bindings
+                        .into_iter()
+                        .map(|(name, binding)| {
+                            (
+                                name,
+                                TemplateBinding {
+                                    component: binding.component_id.into(),
+                                    path: binding.path,
+                                },
+                            )
+                        })
+                        .collect(),
+                }));
+        }
+
         // If it's got multiple or zero arguments, it's a dynamic function we can't handle;
         // treat it like it's unspecified.
         // TODO error instead of pretending it has no source!
@@ -1164,6 +1226,9 @@ impl Component {
                 }
                 Source::Value(StaticArgumentValue::get_by_id(ctx, value_id).await?.value)
             }
+            ValueSource::Variable(variable_id) if intrinsic == Some(IntrinsicFunc::Identity) => {
+                Source::Variable(variable_id.into())
+            }
             _ => {
                 // We don't support anything else
                 // TODO error instead of pretending it has no source!
@@ -1186,7 +1251,7 @@ impl Component {
     async fn try_get_node_weight_and_content(
         ctx: &DalContext,
         component_id: ComponentId,
-    ) -> ComponentResult<Option<(ComponentNodeWeight, ComponentContentV2)>> {
+    ) -> ComponentResult<Option<(ComponentNodeWeight, ComponentContentV5)>> {
         if let Some((component_node_weight, content_hash)) =
             Self::try_get_node_weight_and_content_hash(ctx, component_id).await?
         {
@@ -1208,7 +1273,7 @@ impl Component {
     async fn get_node_weight_and_content(
         ctx: &DalContext,
         component_id: ComponentId,
-    ) -> ComponentResult<(ComponentNodeWeight, ComponentContentV2)> {
+    ) -> ComponentResult<(ComponentNodeWeight, ComponentContentV5)> {
         Self::try_get_node_weight_and_content(ctx, component_id)
             .await?
             .ok_or(ComponentError::NotFound(component_id))
@@ -1965,7 +2030,7 @@ impl Component {
         let original_component = self.clone();
         let mut component = self;
 
-        let before = ComponentContentV2::from(component.clone());
+        let before = ComponentContentV5::from(component.clone());
         lambda(&mut component)?;
 
         // The `to_delete` lives on the node itself, not in the content, so we need to be a little
@@ -1983,10 +2048,10 @@ impl Component {
                 .await?;
         }
 
-        let updated = ComponentContentV2::from(component.clone());
+        let updated = ComponentContentV5::from(component.clone());
         if updated != before {
             let (hash, _) = ctx.layer_db().cas().write(
-                Arc::new(ComponentContent::V2(updated.clone()).into()),
+                Arc::new(ComponentContent::V5(updated.clone()).into()),
                 None,
                 ctx.events_tenancy(),
                 ctx.events_actor(),
@@ -2087,6 +2152,62 @@ impl Component {
         }
     }
 
+    /// Replaces the labels attached to the component. Label keys can't be empty or contain `=`,
+    /// since labels are searched for as `label:key=value`.
+    pub async fn set_labels(
+        self,
+        ctx: &DalContext,
+        labels: BTreeMap<String, String>,
+    ) -> ComponentResult<Self> {
+        if let Some(key) = labels
+            .keys()
+            .find(|key| key.is_empty() || key.contains('='))
+        {
+            return Err(ComponentError::InvalidLabelKey(key.to_owned()));
+        }
+
+        self.modify(ctx, |component| {
+            component.labels = labels;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Sets (or clears) the team or user that owns the component.
+    pub async fn set_owner(self, ctx: &DalContext, owner: Option<String>) -> ComponentResult<Self> {
+        self.modify(ctx, |component| {
+            component.owner = owner;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Records the id of the component in another workspace this one was promoted from.
+    pub async fn set_promoted_from(
+        self,
+        ctx: &DalContext,
+        promoted_from: Option<String>,
+    ) -> ComponentResult<Self> {
+        self.modify(ctx, |component| {
+            component.promoted_from = promoted_from;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Records (or clears) the component template instance this component belongs to.
+    pub async fn set_template_instance(
+        self,
+        ctx: &DalContext,
+        template_instance: Option<ComponentTemplateMembership>,
+    ) -> ComponentResult<Self> {
+        self.modify(ctx, |component| {
+            component.template_instance = template_instance;
+            Ok(())
+        })
+        .await
+    }
+
     pub async fn set_to_delete(self, ctx: &DalContext, to_delete: bool) -> ComponentResult<Self> {
         let component_id = self.id;
         let schema_variant_id = Self::schema_variant_id(ctx, component_id).await?;
@@ -2296,6 +2417,10 @@ impl Component {
             }
         }
 
+        for &pasted_component_id in &pasted_component_ids {
+            Self::rebind_pasted_string_templates(ctx, pasted_component_id, &to_pasted_id).await?;
+        }
+
         Ok(pasted_component_ids)
     }
 
@@ -2355,9 +2480,51 @@ impl Component {
             }
         }
 
+        for &pasted_component_id in &pasted_component_ids {
+            Self::rebind_pasted_string_templates(ctx, pasted_component_id, &to_pasted_id).await?;
+        }
+
         Ok(pasted_component_ids)
     }
 
+    /// Binds the string templates of a pasted component to the pasted copies of the components
+    /// they were bound to, the same way pasted subscribers are resubscribed above. The template
+    /// names its components, so resubscribing alone would leave it pointing at the originals.
+    async fn rebind_pasted_string_templates(
+        ctx: &DalContext,
+        pasted_component_id: ComponentId,
+        to_pasted_id: &HashMap<ComponentId, ComponentId>,
+    ) -> ComponentResult<()> {
+        let root_attribute_value_id =
+            Component::root_attribute_value_id(ctx, pasted_component_id).await?;
+        let mut work_queue = vec![root_attribute_value_id];
+        while let Some(av_id) = work_queue.pop() {
+            let Some(mut template) = AttributeValue::string_template(ctx, av_id).await? else {
+                work_queue.extend(AttributeValue::get_child_av_ids_in_order(ctx, av_id).await?);
+                continue;
+            };
+
+            let mut rebound = false;
+            for binding in template.bindings.values_mut() {
+                if let Some(&pasted_id) = to_pasted_id.get(&binding.component_id) {
+                    binding.component_id = pasted_id;
+                    rebound = true;
+                }
+            }
+            if rebound {
+                AttributeValue::set_to_string_template(
+                    ctx,
+                    av_id,
+                    template,
+                    Reason::new_user_added(ctx),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn add_to_view(
         ctx: &DalContext,
         component_id: ComponentId,
@@ -47,7 +47,7 @@ use crate::{
     diagram::geometry::Geometry,
     layer_db_types::{
         ComponentContent,
-        ComponentContentV2,
+        ComponentContentV5,
     },
     validation::ValidationOutput,
     workspace_snapshot::{
@@ -383,12 +383,16 @@ impl Component {
         schema_variant_id: SchemaVariantId,
         view_id: ViewId,
     ) -> ComponentResult<Self> {
-        let content = ComponentContentV2 {
+        let content = ComponentContentV5 {
             timestamp: Timestamp::now(),
+            labels: BTreeMap::new(),
+            owner: None,
+            promoted_from: None,
+            template_instance: None,
         };
 
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(ComponentContent::V2(content.clone()).into()),
+            Arc::new(ComponentContent::V5(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
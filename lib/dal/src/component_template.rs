@@ -0,0 +1,570 @@
+//! Component templates: a set of components (and the subscriptions between them) captured as a
+//! reusable, parameterized package that can be instantiated into any change set.
+//!
+//! [`save`] exports the components the same way [`promotion`](crate::promotion) does, replaces
+//! the attribute sources bound to parameters with `{ "$parameter": "<name>" }` placeholders, and
+//! stores the result as a new version of a [`SiPkgKind::ComponentTemplate`] package.
+//! [`instantiate`] renders a template with parameter values, prefixing every component name with
+//! the name of the instance, and promotes the rendered components into the change set.
+//! [`upgrade`] re-renders an instance with the latest version of its template, applying only what
+//! changed.
+//!
+//! Which instance (and template version) a component belongs to is recorded on the component
+//! itself, so instances live in the change set their components live in: they are created and
+//! upgraded along with it, and disappear if it is abandoned.
+
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+pub use si_db::ComponentTemplate;
+use si_events::CasValue;
+use si_pkg::{
+    ChangeSetSpec,
+    ComponentSpec,
+    PkgSpec,
+    SiPkg,
+    SiPkgError,
+    SiPkgKind,
+    SpecError,
+    TemplateParameterSpec,
+};
+use thiserror::Error;
+
+use crate::{
+    Component,
+    ComponentError,
+    ComponentId,
+    DalContext,
+    attribute::attributes::{
+        AttributeSources,
+        AttributeValueIdent,
+        AttributesError,
+        ComponentIdent,
+        Source,
+        TemplateBinding,
+        ValueOrSourceSpec,
+    },
+    promotion::{
+        self,
+        PromotedComponent,
+        PromotionError,
+        PromotionMatching,
+        PromotionOverrides,
+    },
+};
+
+/// The key of the placeholder object that stands in for a parameter's value in a template.
+pub const PARAMETER_KEY: &str = "$parameter";
+
+const NAME_PATH: &str = "/si/name";
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ComponentTemplateError {
+    #[error("attributes error: {0}")]
+    Attributes(#[from] Box<AttributesError>),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
+    #[error("component template storage error: {0}")]
+    Db(#[from] si_db::ComponentTemplateError),
+    #[error("template {0} has no components")]
+    EmptyTemplate(String),
+    #[error("instance {instance} was created from template {template}")]
+    InstanceNameInUse { instance: String, template: String },
+    #[error("template instance not found: {0}")]
+    InstanceNotFound(String),
+    #[error("no value given for parameter {0}, which has no default")]
+    MissingParameter(String),
+    #[error("template {0} is not a component template package")]
+    NotAComponentTemplate(String),
+    #[error("parameter bound on component {0}, which is not part of the template")]
+    ParameterBindingForUnknownComponent(String),
+    #[error("si pkg error: {0}")]
+    Pkg(#[from] SiPkgError),
+    #[error("promotion error: {0}")]
+    Promotion(#[from] Box<PromotionError>),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("si db error: {0}")]
+    SiDb(#[from] si_db::SiDbError),
+    #[error("spec error: {0}")]
+    Spec(#[from] SpecError),
+    #[error("component template not found: {0}")]
+    TemplateNotFound(String),
+    #[error("parameter {0} is used by the template but not declared")]
+    UndeclaredParameter(String),
+    #[error("value given for parameter {0}, which the template does not declare")]
+    UnknownParameter(String),
+}
+
+#[allow(missing_docs)]
+pub type ComponentTemplateResult<T> = Result<T, ComponentTemplateError>;
+
+/// Which attributes are set from which parameter, keyed by component name and then by attribute
+/// path.
+pub type TemplateParameterBindings = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Parameter values for an instance, keyed by parameter name. A value may be a `$source` spec,
+/// e.g. to subscribe to a component outside of the template.
+pub type TemplateParameterValues = BTreeMap<String, serde_json::Value>;
+
+/// The template instance a component belongs to, as recorded on the component.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ComponentTemplateMembership {
+    pub instance_name: String,
+    pub template_name: String,
+    /// The version of the template the component was created from or last upgraded to.
+    pub template_version: i32,
+    /// The parameter values given when the instance was created or last upgraded.
+    pub parameters: BTreeMap<String, CasValue>,
+}
+
+/// A set of components created from a component template, along with the version of the
+/// template and the parameters they were created with.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateInstance {
+    /// The name of the instance. The names of the components created for the instance are
+    /// prefixed with it.
+    pub name: String,
+    pub template_name: String,
+    /// The version of the template the instance was created from or last upgraded to.
+    pub template_version: i32,
+    /// The parameter values given when the instance was created or last upgraded.
+    pub parameters: TemplateParameterValues,
+    pub component_ids: Vec<ComponentId>,
+}
+
+impl ComponentTemplateInstance {
+    /// Lists the instances whose components are in the current change set.
+    pub async fn list(ctx: &DalContext) -> ComponentTemplateResult<Vec<Self>> {
+        let mut instances: BTreeMap<String, Self> = BTreeMap::new();
+        for component in Component::list(ctx).await? {
+            if component.to_delete() {
+                continue;
+            }
+            let Some(membership) = component.template_instance() else {
+                continue;
+            };
+            instances
+                .entry(membership.instance_name.to_owned())
+                .or_insert_with(|| Self {
+                    name: membership.instance_name.to_owned(),
+                    template_name: membership.template_name.to_owned(),
+                    template_version: membership.template_version,
+                    parameters: membership
+                        .parameters
+                        .iter()
+                        .map(|(name, value)| (name.to_owned(), value.to_owned().into()))
+                        .collect(),
+                    component_ids: vec![],
+                })
+                .component_ids
+                .push(component.id());
+        }
+
+        let mut instances: Vec<Self> = instances.into_values().collect();
+        for instance in &mut instances {
+            instance.component_ids.sort();
+        }
+        Ok(instances)
+    }
+
+    /// Fetches the named instance, if it has components in the current change set.
+    pub async fn get(ctx: &DalContext, name: &str) -> ComponentTemplateResult<Option<Self>> {
+        Ok(Self::list(ctx)
+            .await?
+            .into_iter()
+            .find(|instance| instance.name == name))
+    }
+
+    /// Lists the instances created from the named template in the current change set.
+    pub async fn list_for_template(
+        ctx: &DalContext,
+        template_name: &str,
+    ) -> ComponentTemplateResult<Vec<Self>> {
+        let mut instances = Self::list(ctx).await?;
+        instances.retain(|instance| instance.template_name == template_name);
+        Ok(instances)
+    }
+}
+
+/// The result of [`instantiate`] or [`upgrade`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateInstantiation {
+    pub instance: ComponentTemplateInstance,
+    /// What happened to each component of the template, in template order.
+    pub components: Vec<PromotedComponent>,
+}
+
+/// Saves the given components as a new version of the named template.
+///
+/// Subscriptions between the components are kept. Subscriptions to components that are not part
+/// of the template are kept by name, so those components must exist wherever the template is
+/// instantiated (or be bound to a parameter instead).
+pub async fn save(
+    ctx: &DalContext,
+    name: impl Into<String>,
+    description: Option<String>,
+    component_ids: &[ComponentId],
+    parameters: Vec<TemplateParameterSpec>,
+    bindings: TemplateParameterBindings,
+) -> ComponentTemplateResult<ComponentTemplate> {
+    let name = name.into();
+    let mut components = promotion::export_components(ctx, component_ids).await?;
+    if components.is_empty() {
+        return Err(ComponentTemplateError::EmptyTemplate(name));
+    }
+
+    for (component_name, paths) in bindings {
+        let Some(component) = components
+            .iter_mut()
+            .find(|component| component.name == component_name)
+        else {
+            return Err(ComponentTemplateError::ParameterBindingForUnknownComponent(
+                component_name,
+            ));
+        };
+
+        let mut sources = match component.sources.take() {
+            Some(serde_json::Value::Object(sources)) => sources,
+            _ => serde_json::Map::new(),
+        };
+        for (path, parameter) in paths {
+            if !parameters.iter().any(|declared| declared.name == parameter) {
+                return Err(ComponentTemplateError::UndeclaredParameter(parameter));
+            }
+            sources.insert(path, serde_json::json!({ PARAMETER_KEY: parameter }));
+        }
+        component.sources = Some(serde_json::Value::Object(sources));
+    }
+
+    let version = match ComponentTemplate::get_latest(ctx, &name).await? {
+        Some(latest) => latest.version + 1,
+        None => 1,
+    };
+    let created_by = ctx.history_actor().email(ctx).await?;
+
+    let spec = PkgSpec::builder()
+        .kind(SiPkgKind::ComponentTemplate)
+        .name(&name)
+        .version(version.to_string())
+        .description(description.clone().unwrap_or_default())
+        .created_by(created_by)
+        .template_parameters(parameters)
+        .change_set(
+            ChangeSetSpec::builder()
+                .name(&name)
+                .components(components)
+                .build()?,
+        )
+        .build()?;
+    let pkg_data = SiPkg::load_from_spec(spec)?.write_to_bytes()?;
+
+    Ok(ComponentTemplate::new(ctx, name, version, description, pkg_data).await?)
+}
+
+/// Creates the components of the latest version of a template in the current change set, named
+/// `<instance name>-<component name>`.
+///
+/// Instantiating an existing instance of the same template again brings its components back in
+/// line with the template, like [`upgrade`] does.
+pub async fn instantiate(
+    ctx: &DalContext,
+    template_name: &str,
+    instance_name: impl Into<String>,
+    values: TemplateParameterValues,
+) -> ComponentTemplateResult<TemplateInstantiation> {
+    let instance_name = instance_name.into();
+    match ComponentTemplateInstance::get(ctx, &instance_name).await? {
+        Some(existing) if existing.template_name != template_name => {
+            return Err(ComponentTemplateError::InstanceNameInUse {
+                instance: instance_name,
+                template: existing.template_name,
+            });
+        }
+        _ => {}
+    }
+
+    let template = ComponentTemplate::get_latest(ctx, template_name)
+        .await?
+        .ok_or_else(|| ComponentTemplateError::TemplateNotFound(template_name.to_owned()))?;
+    let spec = load_spec(&template).await?;
+
+    apply(ctx, &template, &spec, instance_name, values).await
+}
+
+/// Updates the components of an instance to the latest version of its template, keeping the
+/// parameter values it was created with (unless new ones are given).
+///
+/// Values for parameters the latest version no longer declares are dropped. Components that were
+/// removed from the template are left in place.
+pub async fn upgrade(
+    ctx: &DalContext,
+    instance_name: &str,
+    values: TemplateParameterValues,
+) -> ComponentTemplateResult<TemplateInstantiation> {
+    let instance = ComponentTemplateInstance::get(ctx, instance_name)
+        .await?
+        .ok_or_else(|| ComponentTemplateError::InstanceNotFound(instance_name.to_owned()))?;
+    let template = ComponentTemplate::get_latest(ctx, &instance.template_name)
+        .await?
+        .ok_or_else(|| ComponentTemplateError::TemplateNotFound(instance.template_name.clone()))?;
+    let spec = load_spec(&template).await?;
+
+    let mut merged = instance.parameters;
+    merged.extend(values);
+    merged.retain(|name, _| {
+        spec.template_parameters
+            .iter()
+            .any(|parameter| &parameter.name == name)
+    });
+
+    apply(ctx, &template, &spec, instance_name.to_owned(), merged).await
+}
+
+/// The parameters declared by a template.
+pub fn parameters(
+    template: &ComponentTemplate,
+) -> ComponentTemplateResult<Vec<TemplateParameterSpec>> {
+    let metadata = SiPkg::load_from_bytes(&template.pkg_data)?.metadata()?;
+    Ok(metadata.template_parameters().to_vec())
+}
+
+/// The name given to a template component in an instance.
+pub fn instance_component_name(instance_name: &str, component_name: &str) -> String {
+    format!("{instance_name}-{component_name}")
+}
+
+async fn load_spec(template: &ComponentTemplate) -> ComponentTemplateResult<PkgSpec> {
+    let spec = SiPkg::load_from_bytes(&template.pkg_data)?
+        .to_spec()
+        .await?;
+    if spec.kind != SiPkgKind::ComponentTemplate {
+        return Err(ComponentTemplateError::NotAComponentTemplate(
+            template.name.to_owned(),
+        ));
+    }
+    Ok(spec)
+}
+
+async fn apply(
+    ctx: &DalContext,
+    template: &ComponentTemplate,
+    spec: &PkgSpec,
+    instance_name: String,
+    values: TemplateParameterValues,
+) -> ComponentTemplateResult<TemplateInstantiation> {
+    let components = render(&instance_name, spec, &values)?;
+    let report = promotion::promote_components(
+        ctx,
+        &components,
+        PromotionOverrides::new(),
+        PromotionMatching::SourceId,
+    )
+    .await?;
+
+    // Only the values that were given are recorded, so that instances pick up changes to the
+    // defaults when they are upgraded. Components that were removed from the template stay part
+    // of the instance.
+    let membership = ComponentTemplateMembership {
+        instance_name: instance_name.to_owned(),
+        template_name: template.name.to_owned(),
+        template_version: template.version,
+        parameters: values
+            .into_iter()
+            .map(|(name, value)| (name, value.into()))
+            .collect(),
+    };
+    let mut component_ids: Vec<ComponentId> = report
+        .components
+        .iter()
+        .map(|promoted| promoted.component_id)
+        .collect();
+    if let Some(existing) = ComponentTemplateInstance::get(ctx, &instance_name).await? {
+        for component_id in existing.component_ids {
+            if !component_ids.contains(&component_id) {
+                component_ids.push(component_id);
+            }
+        }
+    }
+    for &component_id in &component_ids {
+        let component = Component::get_by_id(ctx, component_id).await?;
+        if component.template_instance() != Some(&membership) {
+            component
+                .set_template_instance(ctx, Some(membership.clone()))
+                .await?;
+        }
+    }
+
+    let instance = ComponentTemplateInstance::get(ctx, &instance_name)
+        .await?
+        .ok_or(ComponentTemplateError::InstanceNotFound(instance_name))?;
+
+    Ok(TemplateInstantiation {
+        instance,
+        components: report.components,
+    })
+}
+
+/// Renders the components of a template for an instance: parameter placeholders are replaced
+/// with their values, and components (and subscriptions between them) are renamed.
+fn render(
+    instance_name: &str,
+    spec: &PkgSpec,
+    values: &TemplateParameterValues,
+) -> ComponentTemplateResult<Vec<ComponentSpec>> {
+    if let Some(name) = values.keys().find(|name| {
+        !spec
+            .template_parameters
+            .iter()
+            .any(|parameter| &parameter.name == *name)
+    }) {
+        return Err(ComponentTemplateError::UnknownParameter(name.to_owned()));
+    }
+
+    let mut resolved = BTreeMap::new();
+    for parameter in &spec.template_parameters {
+        let value = values
+            .get(&parameter.name)
+            .or(parameter.default.as_ref())
+            .ok_or_else(|| ComponentTemplateError::MissingParameter(parameter.name.to_owned()))?;
+        resolved.insert(parameter.name.as_str(), value);
+    }
+
+    let components = spec
+        .change_sets
+        .first()
+        .map(|change_set| change_set.components.as_slice())
+        .unwrap_or_default();
+    if components.is_empty() {
+        return Err(ComponentTemplateError::EmptyTemplate(spec.name.to_owned()));
+    }
+    let names: HashSet<&str> = components
+        .iter()
+        .map(|component| component.name.as_str())
+        .collect();
+
+    let mut rendered = Vec::with_capacity(components.len());
+    for component in components {
+        let name = instance_component_name(instance_name, &component.name);
+
+        let mut raw_sources = match &component.sources {
+            Some(serde_json::Value::Object(sources)) => sources.to_owned(),
+            _ => serde_json::Map::new(),
+        };
+        for (path, value) in raw_sources.iter_mut() {
+            let replacement = if path == NAME_PATH {
+                serde_json::Value::String(name.to_owned())
+            } else if let Some(parameter) = parameter_name(value) {
+                resolved
+                    .get(parameter)
+                    .map(|value| (*value).to_owned())
+                    .ok_or_else(|| {
+                        ComponentTemplateError::UndeclaredParameter(parameter.to_owned())
+                    })?
+            } else {
+                continue;
+            };
+            *value = replacement;
+        }
+        let sources: AttributeSources =
+            serde_json::from_value(serde_json::Value::Object(raw_sources))?;
+
+        let mut renamed: Vec<(AttributeValueIdent, ValueOrSourceSpec)> =
+            Vec::with_capacity(sources.len());
+        for (path, value) in sources {
+            let value = match Option::<Source>::try_from(value)? {
+                Some(source) => rename_source(instance_name, &names, source).into(),
+                None => ValueOrSourceSpec::unset(),
+            };
+            renamed.push((path, value));
+        }
+
+        let mut component = component.to_owned();
+        component.name = name;
+        // Each instance gets its own copy of every template component
+        component.unique_id = format!("{}:{instance_name}:{}", spec.name, component.unique_id);
+        component.sources = Some(serde_json::to_value(AttributeSources::from(renamed))?);
+        rendered.push(component);
+    }
+
+    Ok(rendered)
+}
+
+/// Returns the parameter name if the value is a `{ "$parameter": "<name>" }` placeholder.
+fn parameter_name(value: &serde_json::Value) -> Option<&str> {
+    let object = value.as_object()?;
+    if object.len() != 1 {
+        return None;
+    }
+    object.get(PARAMETER_KEY)?.as_str()
+}
+
+/// Points subscriptions to other components of the template at the instance's components.
+fn rename_source(instance_name: &str, names: &HashSet<&str>, source: Source) -> Source {
+    let rename = |component: ComponentIdent| -> ComponentIdent {
+        let component_name = String::from(component);
+        if names.contains(component_name.as_str()) {
+            instance_component_name(instance_name, &component_name).into()
+        } else {
+            component_name.into()
+        }
+    };
+
+    match source {
+        Source::Value(_) | Source::Variable(_) => source,
+        Source::Template { template, bindings } => Source::Template {
+            template,
+            bindings: bindings
+                .into_iter()
+                .map(|(name, binding)| {
+                    (
+                        name,
+                        TemplateBinding {
+                            component: rename(binding.component),
+                            path: binding.path,
+                        },
+                    )
+                })
+                .collect(),
+        },
+        Source::Subscription {
+            component,
+            path,
+            func,
+            _keep_existing_subscriptions,
+        } => Source::Subscription {
+            component: rename(component),
+            path,
+            func,
+            _keep_existing_subscriptions,
+        },
+    }
+}
+
+impl From<AttributesError> for ComponentTemplateError {
+    fn from(value: AttributesError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<ComponentError> for ComponentTemplateError {
+    fn from(value: ComponentError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<PromotionError> for ComponentTemplateError {
+    fn from(value: PromotionError) -> Self {
+        Box::new(value).into()
+    }
+}
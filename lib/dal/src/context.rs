@@ -108,6 +108,7 @@ use crate::{
     audit_logging::{
         self,
         AuditLoggingError,
+        CheckpointedAuditLog,
     },
     change_set::{
         ChangeSet,
@@ -137,6 +138,7 @@ use crate::{
         WorkspaceSnapshotError,
         WorkspaceSnapshotResult,
         WorkspaceSnapshotSelector,
+        WorkspaceSnapshotSelectorCheckpoint,
         dependent_value_root::DependentValueRootError,
         graph::{
             RebaseBatch,
@@ -362,6 +364,7 @@ impl ConnectionState {
                     workspace_pk,
                     change_set_id,
                     updates_address,
+                    expected_snapshot_address,
                     event_session_id,
                 } = maybe_rebase
                 {
@@ -370,6 +373,7 @@ impl ConnectionState {
                         workspace_pk,
                         change_set_id,
                         updates_address,
+                        expected_snapshot_address,
                         event_session_id,
                     )
                     .await?;
@@ -405,6 +409,7 @@ impl ConnectionState {
                     workspace_pk,
                     change_set_id,
                     updates_address,
+                    expected_snapshot_address,
                     event_session_id,
                 } = maybe_rebase
                 {
@@ -413,6 +418,7 @@ impl ConnectionState {
                         workspace_pk,
                         change_set_id,
                         updates_address,
+                        expected_snapshot_address,
                         event_session_id,
                     )
                     .await?;
@@ -479,6 +485,18 @@ pub struct DalContext {
     cache: ConcurrentExtensions,
     /// Counter for audit logs published to pending_events stream during this event session
     pending_audit_logs_count: Arc<AtomicU64>,
+    /// Audit logs held back while a [`DalContextCheckpoint`] is held, see
+    /// [`DalContext::checkpoint`]
+    checkpointed_audit_logs: Arc<Mutex<Option<Vec<CheckpointedAuditLog>>>>,
+}
+
+/// The work pending in a [`DalContext`] at a point in time, taken with [`DalContext::checkpoint`].
+#[derive(Debug)]
+pub struct DalContextCheckpoint {
+    snapshot: WorkspaceSnapshotSelectorCheckpoint,
+    pending_publish_count: usize,
+    /// The number of audit logs held back for an enclosing checkpoint, if there is one.
+    audit_log_count: Option<usize>,
 }
 
 #[async_trait]
@@ -621,6 +639,7 @@ impl DalContext {
             workspace_pk,
             change_set_id,
             updates_address,
+            None,
             self.event_session_id,
         )
         .await
@@ -670,6 +689,7 @@ impl DalContext {
     async fn commit_internal(
         &self,
         rebase_batch: Option<RebaseBatchAddressKind>,
+        expected_snapshot_address: Option<WorkspaceSnapshotAddress>,
     ) -> TransactionsResult<()> {
         let maybe_rebase = match rebase_batch {
             Some(updates_address) => DelayedRebaseWithReply::WithUpdates {
@@ -677,6 +697,7 @@ impl DalContext {
                 workspace_pk: self.workspace_pk()?,
                 change_set_id: self.change_set_id(),
                 updates_address,
+                expected_snapshot_address,
                 event_session_id: self.event_session_id,
             },
             None => {
@@ -845,12 +866,25 @@ impl DalContext {
     #[instrument(name = "context.commit", level = "info", skip_all)]
     pub async fn commit(&self) -> TransactionsResult<()> {
         let rebase_batch = self.write_current_rebase_batch().await?;
-        self.commit_internal(rebase_batch).await
+        self.commit_internal(rebase_batch, None).await
+    }
+
+    /// Like [`Self::commit`], but the rebaser only performs the updates if the change set still
+    /// points at `expected_snapshot_address`. Otherwise the rebase fails and nothing is written to
+    /// the change set.
+    #[instrument(name = "context.commit_if_unchanged", level = "info", skip_all)]
+    pub async fn commit_if_unchanged(
+        &self,
+        expected_snapshot_address: WorkspaceSnapshotAddress,
+    ) -> TransactionsResult<()> {
+        let rebase_batch = self.write_current_rebase_batch().await?;
+        self.commit_internal(rebase_batch, Some(expected_snapshot_address))
+            .await
     }
 
     #[instrument(name = "context.commit_no_rebase", level = "info", skip_all)]
     pub async fn commit_no_rebase(&self) -> TransactionsResult<()> {
-        self.commit_internal(None).await
+        self.commit_internal(None, None).await
     }
 
     pub fn workspace_pk(&self) -> TransactionsResult<WorkspacePk> {
@@ -881,6 +915,80 @@ impl DalContext {
         &self.pending_audit_logs_count
     }
 
+    pub(crate) fn checkpointed_audit_logs(&self) -> &Mutex<Option<Vec<CheckpointedAuditLog>>> {
+        &self.checkpointed_audit_logs
+    }
+
+    /// Takes a checkpoint of the work pending in this context, so that the work done after it can
+    /// be undone with [`Self::restore`] without rolling back the transactions. This covers the
+    /// changes to the workspace snapshot, the [`WsEvents`](crate::WsEvent) waiting to be
+    /// published on commit and audit logs, which are held back until the checkpoint is given to
+    /// [`Self::keep`].
+    ///
+    /// This clones the working copy of the snapshot, so it isn't free for large graphs.
+    pub async fn checkpoint(&self) -> TransactionsResult<DalContextCheckpoint> {
+        let snapshot = self
+            .workspace_snapshot()
+            .map_err(Box::new)?
+            .checkpoint()
+            .await;
+        let pending_publish_count = self.txns().await?.nats().pending_publish_count().await;
+
+        let mut audit_logs = self.checkpointed_audit_logs.lock().await;
+        let audit_log_count = audit_logs.as_ref().map(Vec::len);
+        if audit_logs.is_none() {
+            *audit_logs = Some(Vec::new());
+        }
+
+        Ok(DalContextCheckpoint {
+            snapshot,
+            pending_publish_count,
+            audit_log_count,
+        })
+    }
+
+    /// Undoes the work done since the checkpoint was taken.
+    pub async fn restore(&self, checkpoint: DalContextCheckpoint) -> TransactionsResult<()> {
+        self.workspace_snapshot()
+            .map_err(Box::new)?
+            .restore(checkpoint.snapshot)
+            .await
+            .map_err(Box::new)?;
+        self.txns()
+            .await?
+            .nats()
+            .truncate_pending_publish(checkpoint.pending_publish_count)
+            .await;
+
+        let mut audit_logs = self.checkpointed_audit_logs.lock().await;
+        match checkpoint.audit_log_count {
+            Some(count) => {
+                if let Some(audit_logs) = audit_logs.as_mut() {
+                    audit_logs.truncate(count);
+                }
+            }
+            None => *audit_logs = None,
+        }
+
+        Ok(())
+    }
+
+    /// Keeps the work done since the checkpoint was taken, publishing the audit logs that were
+    /// held back for it (unless an enclosing checkpoint is still held).
+    pub async fn keep(&self, checkpoint: DalContextCheckpoint) -> TransactionsResult<()> {
+        if checkpoint.audit_log_count.is_some() {
+            return Ok(());
+        }
+
+        let audit_logs = self
+            .checkpointed_audit_logs
+            .lock()
+            .await
+            .take()
+            .unwrap_or_default();
+        Ok(audit_logging::publish_checkpointed(self, audit_logs).await?)
+    }
+
     pub fn layer_db(&self) -> DalLayerDb {
         self.services_context().layer_db().clone()
     }
@@ -943,6 +1051,7 @@ impl DalContext {
                 workspace_pk: self.workspace_pk()?,
                 change_set_id: self.change_set_id(),
                 updates_address,
+                expected_snapshot_address: None,
                 event_session_id: self.event_session_id,
             },
             None => {
@@ -1521,6 +1630,7 @@ impl DalContextBuilder {
             authentication_method: AuthenticationMethod::System,
             cache: Default::default(),
             pending_audit_logs_count: Arc::new(AtomicU64::new(0)),
+            checkpointed_audit_logs: Default::default(),
         })
     }
 
@@ -1549,6 +1659,7 @@ impl DalContextBuilder {
             authentication_method,
             cache: Default::default(),
             pending_audit_logs_count: Arc::new(AtomicU64::new(0)),
+            checkpointed_audit_logs: Default::default(),
         })
     }
 
@@ -1577,6 +1688,7 @@ impl DalContextBuilder {
             authentication_method: AuthenticationMethod::System,
             cache: Default::default(),
             pending_audit_logs_count: Arc::new(AtomicU64::new(0)),
+            checkpointed_audit_logs: Default::default(),
         };
 
         ctx.update_snapshot_to_visibility().await?;
@@ -1606,6 +1718,7 @@ impl DalContextBuilder {
             authentication_method: access_builder.authentication_method,
             cache: Default::default(),
             pending_audit_logs_count: Arc::new(AtomicU64::new(0)),
+            checkpointed_audit_logs: Default::default(),
         };
 
         // Update changeset so it's correct, but don't pull the snapshot yet
@@ -1644,6 +1757,7 @@ impl DalContextBuilder {
             authentication_method: request_context.authentication_method,
             cache: Default::default(),
             pending_audit_logs_count: Arc::new(AtomicU64::new(0)),
+            checkpointed_audit_logs: Default::default(),
         };
 
         if ctx.history_actor() != &HistoryActor::SystemInit {
@@ -1955,6 +2069,7 @@ impl Transactions {
             workspace_pk,
             change_set_id,
             updates_address,
+            expected_snapshot_address,
             event_session_id,
         } = maybe_rebase
         {
@@ -1965,6 +2080,7 @@ impl Transactions {
                 workspace_pk,
                 change_set_id,
                 updates_address,
+                expected_snapshot_address,
                 event_session_id,
             )
             .await?;
@@ -2000,6 +2116,7 @@ impl Transactions {
             workspace_pk,
             change_set_id,
             updates_address,
+            expected_snapshot_address,
             event_session_id,
         } = maybe_rebase
         {
@@ -2010,6 +2127,7 @@ impl Transactions {
                 workspace_pk,
                 change_set_id,
                 updates_address,
+                expected_snapshot_address,
                 event_session_id,
             )
             .await?;
@@ -2061,6 +2179,7 @@ enum DelayedRebaseWithReply<'a> {
         workspace_pk: WorkspacePk,
         change_set_id: ChangeSetId,
         updates_address: RebaseBatchAddressKind,
+        expected_snapshot_address: Option<WorkspaceSnapshotAddress>,
         event_session_id: EventSessionId,
     },
 }
@@ -2080,19 +2199,35 @@ async fn rebase_with_reply(
     workspace_pk: WorkspacePk,
     change_set_id: ChangeSetId,
     updates_address: RebaseBatchAddressKind,
+    expected_snapshot_address: Option<WorkspaceSnapshotAddress>,
     event_session_id: EventSessionId,
 ) -> TransactionsResult<()> {
     let timeout = Duration::from_secs(60);
     let metric_label = format!("{workspace_pk}:{change_set_id}");
     metric!(counter.dal.rebase_requested = 1, label = metric_label);
-    let (request_id, reply_fut) = rebaser
-        .enqueue_updates_with_reply(
-            workspace_pk,
-            change_set_id,
-            updates_address,
-            event_session_id,
-        )
-        .await?;
+    let (request_id, reply_fut) = match expected_snapshot_address {
+        Some(expected_snapshot_address) => {
+            rebaser
+                .enqueue_updates_if_unchanged_with_reply(
+                    workspace_pk,
+                    change_set_id,
+                    updates_address,
+                    expected_snapshot_address,
+                    event_session_id,
+                )
+                .await?
+        }
+        None => {
+            rebaser
+                .enqueue_updates_with_reply(
+                    workspace_pk,
+                    change_set_id,
+                    updates_address,
+                    event_session_id,
+                )
+                .await?
+        }
+    };
 
     let reply_fut = reply_fut.instrument(info_span!(
         "rebaser_client.await_response",
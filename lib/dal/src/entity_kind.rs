@@ -90,7 +90,9 @@ impl EntityKind {
             | EntityKindEvents::Reason
             | EntityKindEvents::CategoryDefaultSubscriptionSources
             | EntityKindEvents::CategoryOverlay
-            | EntityKindEvents::LeafPrototype => None,
+            | EntityKindEvents::CategoryVariable
+            | EntityKindEvents::LeafPrototype
+            | EntityKindEvents::Variable => None,
             EntityKindEvents::SchemaVariant => {
                 let variant_name = SchemaVariant::get_by_id(ctx, id.into_inner().into())
                     .await?
@@ -42,6 +42,7 @@ use strum::IntoEnumIterator;
 use telemetry::prelude::*;
 use thiserror::Error;
 use ulid::Ulid as CoreUlid;
+use veritech_client::ExecutionResourceLimits;
 
 use self::backend::{
     FuncBackendKind,
@@ -146,7 +147,7 @@ pub type FuncResult<T> = Result<T, FuncError>;
 
 impl From<Func> for FuncContent {
     fn from(value: Func) -> Self {
-        Self::V3(FuncContentV3 {
+        Self::V5(FuncContentV5 {
             timestamp: value.timestamp,
             display_name: value.display_name,
             description: value.description,
@@ -160,6 +161,9 @@ impl From<Func> for FuncContent {
             code_blake3: value.code_blake3,
             is_locked: value.is_locked,
             is_transformation: value.is_transformation,
+            memoization_enabled: value.memoization_enabled,
+            max_memory_bytes: value.max_memory_bytes,
+            max_cpu_time_ms: value.max_cpu_time_ms,
         })
     }
 }
@@ -181,7 +185,7 @@ pub use si_id::{
     FuncId,
 };
 
-use crate::layer_db_types::FuncContentV3;
+use crate::layer_db_types::FuncContentV5;
 
 /// A `Func` is the declaration of the existence of a function. It has a name,
 /// and corresponds to a given function backend (and its associated return types).
@@ -209,10 +213,17 @@ pub struct Func {
     pub code_blake3: ContentHash,
     pub is_locked: bool,
     pub is_transformation: bool,
+    /// Enables reusing the results of previous executions with the same arguments. Only funcs that
+    /// are deterministic should opt in.
+    pub memoization_enabled: bool,
+    /// The maximum memory a single execution may use, in bytes. Unlimited when unset.
+    pub max_memory_bytes: Option<u64>,
+    /// The maximum CPU time a single execution may use, in milliseconds. Unlimited when unset.
+    pub max_cpu_time_ms: Option<u64>,
 }
 
 impl Func {
-    pub fn assemble(node_weight: &FuncNodeWeight, content: FuncContentV3) -> Self {
+    pub fn assemble(node_weight: &FuncNodeWeight, content: FuncContentV5) -> Self {
         Self {
             id: node_weight.id().into(),
             name: node_weight.name().to_owned(),
@@ -231,6 +242,9 @@ impl Func {
             code_blake3: content.code_blake3,
             is_locked: content.is_locked,
             is_transformation: content.is_transformation,
+            memoization_enabled: content.memoization_enabled,
+            max_memory_bytes: content.max_memory_bytes,
+            max_cpu_time_ms: content.max_cpu_time_ms,
         }
     }
 
@@ -292,7 +306,7 @@ impl Func {
             ContentHash::new("".as_bytes())
         };
 
-        let content = FuncContentV3 {
+        let content = FuncContentV5 {
             timestamp,
             display_name: display_name.map(Into::into),
             description: description.map(Into::into),
@@ -306,10 +320,13 @@ impl Func {
             code_blake3,
             is_locked: false,
             is_transformation,
+            memoization_enabled: false,
+            max_memory_bytes: None,
+            max_cpu_time_ms: None,
         };
 
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(FuncContent::V3(content.clone()).into()),
+            Arc::new(FuncContent::V5(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -405,6 +422,9 @@ impl Func {
             code_blake3,
             is_locked: false,
             is_transformation: false,
+            memoization_enabled: false,
+            max_memory_bytes: None,
+            max_cpu_time_ms: None,
         }
     }
 
@@ -574,6 +594,14 @@ impl Func {
         IntrinsicFunc::maybe_from_str(&self.name).is_some()
     }
 
+    /// Returns the limits on the resources a single execution of this func may consume.
+    pub fn resource_limits(&self) -> ExecutionResourceLimits {
+        ExecutionResourceLimits {
+            max_memory_bytes: self.max_memory_bytes,
+            max_cpu_time_ms: self.max_cpu_time_ms,
+        }
+    }
+
     pub async fn modify_by_id<L>(ctx: &DalContext, id: FuncId, lambda: L) -> FuncResult<Func>
     where
         L: FnOnce(&mut Func) -> FuncResult<()>,
@@ -823,6 +851,18 @@ impl Func {
             self.is_transformation,
         )
         .await?;
+        let new_func = if self.memoization_enabled || !self.resource_limits().is_unlimited() {
+            new_func
+                .modify(ctx, |func| {
+                    func.memoization_enabled = self.memoization_enabled;
+                    func.max_memory_bytes = self.max_memory_bytes;
+                    func.max_cpu_time_ms = self.max_cpu_time_ms;
+                    Ok(())
+                })
+                .await?
+        } else {
+            new_func
+        };
 
         for arg in FuncArgument::list_for_func(ctx, self.id)
             .await
@@ -908,6 +948,7 @@ impl Func {
             arguments,
             types: Some(types),
             is_transformation: self.is_transformation,
+            memoization_enabled: self.memoization_enabled,
         })
     }
     // helper to get updated types to fire WSEvents so SDF can decide when these events need to fire
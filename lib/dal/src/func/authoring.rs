@@ -797,6 +797,46 @@ impl FuncAuthoringClient {
         Ok(updated_func)
     }
 
+    /// Enables or disables reusing the results of previous executions of the [`Func`] with the
+    /// same arguments. Only funcs that are deterministic should enable it.
+    /// Returns an error if the [`Func`] is currently locked (unless it has overlay bindings)
+    #[instrument(
+        level = "info",
+        name = "func.authoring.set_memoization_enabled",
+        skip(ctx)
+    )]
+    pub async fn set_memoization_enabled(
+        ctx: &DalContext,
+        func_id: FuncId,
+        memoization_enabled: bool,
+    ) -> FuncAuthoringResult<Func> {
+        let updated_func = Func::modify_by_id(ctx, func_id, |func| {
+            func.memoization_enabled = memoization_enabled;
+            Ok(())
+        })
+        .await?;
+        Ok(updated_func)
+    }
+
+    /// Sets the limits on the resources a single execution of the [`Func`] may consume. Executions
+    /// exceeding a limit are stopped and fail.
+    /// Returns an error if the [`Func`] is currently locked (unless it has overlay bindings)
+    #[instrument(level = "info", name = "func.authoring.set_resource_limits", skip(ctx))]
+    pub async fn set_resource_limits(
+        ctx: &DalContext,
+        func_id: FuncId,
+        max_memory_bytes: Option<u64>,
+        max_cpu_time_ms: Option<u64>,
+    ) -> FuncAuthoringResult<Func> {
+        let updated_func = Func::modify_by_id(ctx, func_id, |func| {
+            func.max_memory_bytes = max_memory_bytes;
+            func.max_cpu_time_ms = max_cpu_time_ms;
+            Ok(())
+        })
+        .await?;
+        Ok(updated_func)
+    }
+
     /// Compiles types corresponding to "lang-js".
     pub fn compile_langjs_types() -> &'static str {
         ts_types::compile_langjs_types()
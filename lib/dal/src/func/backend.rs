@@ -1,3 +1,8 @@
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
 use async_trait::async_trait;
 use serde::{
     Deserialize,
@@ -21,6 +26,8 @@ use veritech_client::{
     ActionRunResultSuccess,
     BeforeFunction,
     Client as VeritechClient,
+    ExecutionResourceLimits,
+    ExecutionResourceUsage,
     FunctionResult,
     FunctionResultFailureErrorKind,
     OutputStream,
@@ -52,6 +59,7 @@ pub mod normalize_to_array;
 pub mod object;
 pub mod resource_payload_to_value;
 pub mod string;
+pub mod string_template;
 pub mod validation;
 
 #[remain::sorted]
@@ -77,6 +85,14 @@ pub enum FuncBackendError {
     SendError,
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("string template bindings must be an object, got: {0}")]
+    StringTemplateInvalidBindings(serde_json::Value),
+    #[error("string template has an empty or unterminated placeholder: {0}")]
+    StringTemplateInvalidPlaceholder(String),
+    #[error("string template references unbound placeholder: {0}")]
+    StringTemplateMissingBinding(String),
+    #[error("string template must be a string, got: {0}")]
+    StringTemplateNotAString(serde_json::Value),
     #[error("unable to decode ulid")]
     Ulid(#[from] ulid::DecodeError),
     #[error("veritech client error: {0}")]
@@ -132,6 +148,11 @@ pub enum FuncBackendKind {
     NormalizeToArray,
     Float,
     Debug,
+    /// A [`Func`](crate::Func) compiled to a WebAssembly component that resolves an attribute
+    /// value, run by cyclone instead of lang-js.
+    WasmAttribute,
+    /// Renders a string template from a set of bound values, natively in the dal.
+    StringTemplate,
 }
 
 impl From<FuncBackendKind> for si_events::FuncBackendKind {
@@ -163,6 +184,8 @@ impl From<FuncBackendKind> for si_events::FuncBackendKind {
             }
             FuncBackendKind::NormalizeToArray => si_events::FuncBackendKind::NormalizeToArray,
             FuncBackendKind::Debug => si_events::FuncBackendKind::Debug,
+            FuncBackendKind::WasmAttribute => si_events::FuncBackendKind::WasmAttribute,
+            FuncBackendKind::StringTemplate => si_events::FuncBackendKind::StringTemplate,
         }
     }
 }
@@ -196,6 +219,8 @@ impl From<si_events::FuncBackendKind> for FuncBackendKind {
             }
             si_events::FuncBackendKind::NormalizeToArray => FuncBackendKind::NormalizeToArray,
             si_events::FuncBackendKind::Debug => FuncBackendKind::Debug,
+            si_events::FuncBackendKind::WasmAttribute => FuncBackendKind::WasmAttribute,
+            si_events::FuncBackendKind::StringTemplate => FuncBackendKind::StringTemplate,
         }
     }
 }
@@ -371,6 +396,22 @@ impl TryFrom<FuncBackendResponseType> for ResolverFunctionResponseType {
 
 impl ToLabelList for FuncBackendKind {}
 
+/// Holds the resources consumed by a dispatched execution, once veritech has reported them.
+#[derive(Debug, Clone, Default)]
+pub struct FuncResourceUsageRecorder(Arc<Mutex<Option<ExecutionResourceUsage>>>);
+
+impl FuncResourceUsageRecorder {
+    pub fn record(&self, resource_usage: Option<ExecutionResourceUsage>) {
+        if let Ok(mut recorded) = self.0.lock() {
+            *recorded = resource_usage;
+        }
+    }
+
+    pub fn take(&self) -> Option<ExecutionResourceUsage> {
+        self.0.lock().ok().and_then(|mut recorded| recorded.take())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FuncDispatchContext {
     pub veritech: VeritechClient,
@@ -378,6 +419,9 @@ pub struct FuncDispatchContext {
     pub func_run_id: FuncRunId,
     pub workspace_id: WorkspaceId,
     pub change_set_id: ChangeSetId,
+    /// The limits for the execution, set from the [`Func`] being dispatched.
+    pub resource_limits: ExecutionResourceLimits,
+    pub resource_usage: FuncResourceUsageRecorder,
 }
 
 impl FuncDispatchContext {
@@ -395,6 +439,8 @@ impl FuncDispatchContext {
                 func_run_id,
                 workspace_id,
                 change_set_id,
+                resource_limits: ExecutionResourceLimits::default(),
+                resource_usage: FuncResourceUsageRecorder::default(),
             },
             rx,
         )
@@ -438,7 +484,7 @@ pub trait FuncDispatch: std::fmt::Debug {
     /// This private function creates the "request" to send to veritech in a shape that it
     /// likes. The request's type is [`Self`].
     fn create(
-        context: FuncDispatchContext,
+        mut context: FuncDispatchContext,
         func: &Func,
         args: &serde_json::Value,
         before: Vec<BeforeFunction>,
@@ -452,6 +498,7 @@ pub trait FuncDispatch: std::fmt::Debug {
             .handler
             .as_deref()
             .ok_or_else(|| FuncBackendError::DispatchMissingHandler(func.id))?;
+        context.resource_limits = func.resource_limits();
         let value = Self::new(context, code_base64, handler, args, before);
         Ok(value)
     }
@@ -55,15 +55,18 @@ impl FuncDispatch for FuncBackendDebug {
     }
 
     async fn dispatch(self: Box<Self>) -> FuncBackendResult<FunctionResult<Self::Output>> {
+        let resource_usage = self.context.resource_usage.clone();
         let (veritech, output_tx, workspace_id, change_set_id) = self.context.into_inner();
-        Ok(veritech
+        let response = veritech
             .execute_debug(
                 output_tx,
                 &self.request,
                 &workspace_id.to_string(),
                 &change_set_id.to_string(),
             )
-            .await?)
+            .await?;
+        resource_usage.record(response.resource_usage);
+        Ok(response.result)
     }
 }
 
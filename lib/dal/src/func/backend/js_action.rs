@@ -49,6 +49,7 @@ impl FuncDispatch for FuncBackendJsAction {
             code_base64: code_base64.into(),
             args: args.0,
             before,
+            limits: context.resource_limits,
         };
 
         Box::new(Self { context, request })
@@ -57,6 +58,7 @@ impl FuncDispatch for FuncBackendJsAction {
     /// This private function dispatches the assembled request to veritech for execution.
     /// This is the "last hop" function in the dal before using the veritech client directly.
     async fn dispatch(self: Box<Self>) -> FuncBackendResult<FunctionResult<Self::Output>> {
+        let resource_usage = self.context.resource_usage.clone();
         let (veritech, output_tx, workspace_id, change_set_id) = self.context.into_inner();
         let value = veritech
             .execute_action_run(
@@ -66,7 +68,8 @@ impl FuncDispatch for FuncBackendJsAction {
                 &change_set_id.to_string(),
             )
             .await?;
-        let value = match value {
+        resource_usage.record(value.resource_usage);
+        let value = match value.result {
             FunctionResult::Success(value) => {
                 if let Some(message) = &value.error {
                     output_tx
@@ -7,6 +7,7 @@ use serde::{
 use veritech_client::{
     BeforeFunction,
     FunctionResult,
+    FunctionRuntime,
     ResolverFunctionComponent,
     ResolverFunctionRequest,
     ResolverFunctionResponseType,
@@ -20,10 +21,39 @@ use crate::func::backend::{
     FuncDispatchContext,
 };
 
+/// Prefixes the message of the results produced in place of failed qualification and code
+/// generation executions.
+const FAILURE_MESSAGE_PREFIX: &str = "Function execution failed: ";
+
+/// Returns true if the value is the result produced in place of a failed execution (rather than
+/// the result of the function itself) for response types that don't surface failures as errors.
+pub fn is_failure_fallback(
+    response_type: &ResolverFunctionResponseType,
+    value: &serde_json::Value,
+) -> bool {
+    let is_failure_message = || {
+        value
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|message| message.starts_with(FAILURE_MESSAGE_PREFIX))
+    };
+
+    match response_type {
+        ResolverFunctionResponseType::Qualification => {
+            value.get("result").and_then(serde_json::Value::as_str) == Some("failure")
+                && is_failure_message()
+        }
+        ResolverFunctionResponseType::CodeGeneration => is_failure_message(),
+        _ => false,
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct FuncBackendJsAttributeArgs {
     pub component: ResolverFunctionComponent,
     pub response_type: ResolverFunctionResponseType,
+    #[serde(default)]
+    pub runtime: FunctionRuntime,
 }
 
 #[derive(Debug)]
@@ -51,12 +81,15 @@ impl FuncDispatch for FuncBackendJsAttribute {
             response_type: args.response_type,
             code_base64: code_base64.into(),
             before,
+            runtime: args.runtime,
+            limits: context.resource_limits,
         };
 
         Box::new(Self { context, request })
     }
 
     async fn dispatch(self: Box<Self>) -> FuncBackendResult<FunctionResult<Self::Output>> {
+        let resource_usage = self.context.resource_usage.clone();
         let (veritech, output_tx, workspace_id, change_set_id) = self.context.into_inner();
         let value = veritech
             .execute_resolver_function(
@@ -66,7 +99,8 @@ impl FuncDispatch for FuncBackendJsAttribute {
                 &change_set_id.to_string(),
             )
             .await?;
-        let value = match value {
+        resource_usage.record(value.resource_usage);
+        let value = match value.result {
             FunctionResult::Failure(failure) => match &self.request.response_type {
                 ResolverFunctionResponseType::Action
                 | ResolverFunctionResponseType::Array
@@ -87,7 +121,7 @@ impl FuncDispatch for FuncBackendJsAttribute {
                         execution_id: failure.execution_id().to_owned(),
                         data: serde_json::json!({
                             "result": "failure",
-                            "message": format!("Function execution failed: {}", failure.error().message),
+                            "message": format!("{FAILURE_MESSAGE_PREFIX}{}", failure.error().message),
                         }),
                         unset: false,
                         timestamp: u64::try_from(std::cmp::max(Utc::now().timestamp(), 0))
@@ -100,7 +134,7 @@ impl FuncDispatch for FuncBackendJsAttribute {
                         data: serde_json::json!({
                             "format": "json",
                             "code": "null",
-                            "message": format!("Function execution failed: {}", failure.error().message),
+                            "message": format!("{FAILURE_MESSAGE_PREFIX}{}", failure.error().message),
                         }),
                         unset: false,
                         timestamp: u64::try_from(std::cmp::max(Utc::now().timestamp(), 0))
@@ -44,6 +44,7 @@ impl FuncDispatch for FuncBackendJsSchemaVariantDefinition {
     }
 
     async fn dispatch(self: Box<Self>) -> FuncBackendResult<FunctionResult<Self::Output>> {
+        let resource_usage = self.context.resource_usage.clone();
         let (veritech, output_tx, workspace_id, change_set_id) = self.context.into_inner();
         let value = veritech
             .execute_schema_variant_definition(
@@ -53,7 +54,8 @@ impl FuncDispatch for FuncBackendJsSchemaVariantDefinition {
                 &change_set_id.to_string(),
             )
             .await?;
-        let value = match value {
+        resource_usage.record(value.resource_usage);
+        let value = match value.result {
             FunctionResult::Failure(failure) => FunctionResult::Success(Self::Output {
                 execution_id: failure.execution_id().to_owned(),
                 definition: serde_json::Value::Null,
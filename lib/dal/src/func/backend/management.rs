@@ -26,6 +26,8 @@ pub struct FuncBackendManagementArgs {
     components: HashMap<String, ComponentViewWithGeometry>,
     current_view: String,
     variant_socket_map: HashMap<String, usize>,
+    #[serde(default)]
+    webhook_payload: Option<serde_json::Value>,
 }
 
 #[derive(Debug)]
@@ -54,22 +56,27 @@ impl FuncDispatch for FuncBackendManagement {
             components: args.components,
             current_view: args.current_view,
             variant_socket_map: args.variant_socket_map,
+            webhook_payload: args.webhook_payload,
             before,
+            limits: context.resource_limits,
         };
 
         Box::new(Self { context, request })
     }
 
     async fn dispatch(self: Box<Self>) -> FuncBackendResult<FunctionResult<Self::Output>> {
+        let resource_usage = self.context.resource_usage.clone();
         let (veritech, output_tx, workspace_id, change_set_id) = self.context.into_inner();
-        Ok(veritech
+        let response = veritech
             .execute_management(
                 output_tx,
                 &self.request,
                 &workspace_id.to_string(),
                 &change_set_id.to_string(),
             )
-            .await?)
+            .await?;
+        resource_usage.record(response.resource_usage);
+        Ok(response.result)
     }
 }
 
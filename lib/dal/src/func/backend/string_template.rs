@@ -0,0 +1,205 @@
+//! Native rendering of string templates such as `arn:aws:s3:::${bucket}/${prefix}`.
+//!
+//! Placeholders are written as `${name}` and are filled in from a map of bound values. A literal
+//! `$` can be escaped as `$$`; any other `$` is left as-is.
+
+use async_trait::async_trait;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+
+use crate::func::backend::{
+    FuncBackend,
+    FuncBackendError,
+    FuncBackendResult,
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendStringTemplateArgs {
+    pub template: Option<Value>,
+    pub bindings: Option<Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendStringTemplate {
+    args: FuncBackendStringTemplateArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendStringTemplate {
+    type Args = FuncBackendStringTemplateArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(self: Box<Self>) -> FuncBackendResult<(Option<Value>, Option<Value>)> {
+        // The template is either given directly, or as the stored template source (an object
+        // with the template alongside where each placeholder is bound from).
+        let template = match self.args.template {
+            Some(Value::String(template)) => template,
+            Some(Value::Object(mut source)) => match source.remove("template") {
+                Some(Value::String(template)) => template,
+                _ => {
+                    return Err(FuncBackendError::StringTemplateNotAString(Value::Object(
+                        source,
+                    )));
+                }
+            },
+            Some(Value::Null) | None => return Ok((None, None)),
+            Some(other) => return Err(FuncBackendError::StringTemplateNotAString(other)),
+        };
+        let bindings = match self.args.bindings {
+            Some(Value::Object(bindings)) => bindings,
+            Some(Value::Null) | None => serde_json::Map::new(),
+            Some(other) => return Err(FuncBackendError::StringTemplateInvalidBindings(other)),
+        };
+
+        let value = render(&template, &bindings)?.map(Value::String);
+        Ok((value.clone(), value))
+    }
+}
+
+/// A piece of a parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSegment<'a> {
+    Literal(String),
+    Placeholder(&'a str),
+}
+
+/// Splits a template into literal text and `${name}` placeholders.
+pub fn parse(template: &str) -> FuncBackendResult<Vec<TemplateSegment<'_>>> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(index) = rest.find('$') {
+        literal.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+        if let Some(after_escape) = after.strip_prefix('$') {
+            literal.push('$');
+            rest = after_escape;
+        } else if let Some(after_brace) = after.strip_prefix('{') {
+            let end = after_brace.find('}').ok_or_else(|| {
+                FuncBackendError::StringTemplateInvalidPlaceholder(template.to_owned())
+            })?;
+            let name = after_brace[..end].trim();
+            if name.is_empty() {
+                return Err(FuncBackendError::StringTemplateInvalidPlaceholder(
+                    template.to_owned(),
+                ));
+            }
+            if !literal.is_empty() {
+                segments.push(TemplateSegment::Literal(std::mem::take(&mut literal)));
+            }
+            segments.push(TemplateSegment::Placeholder(name));
+            rest = &after_brace[end + 1..];
+        } else {
+            literal.push('$');
+            rest = after;
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        segments.push(TemplateSegment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+/// Returns the distinct placeholder names used by a template, in order of first appearance.
+pub fn placeholders(template: &str) -> FuncBackendResult<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    for segment in parse(template)? {
+        if let TemplateSegment::Placeholder(name) = segment {
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_owned());
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Renders a template with the given bindings.
+///
+/// Strings are inserted as-is and any other JSON value is inserted in its serialized form. If a
+/// bound value is null (e.g. its source has not been set yet), the whole template renders to
+/// `None` rather than producing a partial string. Referencing a name that has no binding at all is
+/// an error.
+pub fn render(
+    template: &str,
+    bindings: &serde_json::Map<String, Value>,
+) -> FuncBackendResult<Option<String>> {
+    let mut rendered = String::with_capacity(template.len());
+    for segment in parse(template)? {
+        match segment {
+            TemplateSegment::Literal(literal) => rendered.push_str(&literal),
+            TemplateSegment::Placeholder(name) => match bindings.get(name) {
+                Some(Value::Null) => return Ok(None),
+                Some(Value::String(value)) => rendered.push_str(value),
+                Some(value) => rendered.push_str(&value.to_string()),
+                None => {
+                    return Err(FuncBackendError::StringTemplateMissingBinding(
+                        name.to_owned(),
+                    ));
+                }
+            },
+        }
+    }
+    Ok(Some(rendered))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn bindings(value: Value) -> serde_json::Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!("test bindings must be an object"),
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "arn:aws:s3:::${bucket}/${ prefix }",
+            &bindings(json!({ "bucket": "my-bucket", "prefix": "logs" })),
+        )
+        .expect("render");
+        assert_eq!(Some("arn:aws:s3:::my-bucket/logs".to_owned()), rendered);
+    }
+
+    #[test]
+    fn renders_non_string_values_and_escapes() {
+        let rendered =
+            render("$$${port} costs $5", &bindings(json!({ "port": 8080 }))).expect("render");
+        assert_eq!(Some("$8080 costs $5".to_owned()), rendered);
+    }
+
+    #[test]
+    fn null_binding_renders_nothing() {
+        let rendered =
+            render("${a}-${b}", &bindings(json!({ "a": "x", "b": null }))).expect("render");
+        assert_eq!(None, rendered);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(render("${missing}", &serde_json::Map::new()).is_err());
+        assert!(parse("${unterminated").is_err());
+        assert!(parse("${}").is_err());
+    }
+
+    #[test]
+    fn lists_placeholders() {
+        assert_eq!(
+            vec!["a".to_owned(), "b".to_owned()],
+            placeholders("${a}/${b}/${a}").expect("placeholders")
+        );
+    }
+}
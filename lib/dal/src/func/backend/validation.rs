@@ -54,6 +54,7 @@ impl FuncDispatch for FuncBackendValidation {
     }
 
     async fn dispatch(self: Box<Self>) -> FuncBackendResult<FunctionResult<Self::Output>> {
+        let resource_usage = self.context.resource_usage.clone();
         let (veritech, output_tx, workspace_id, change_set_id) = self.context.into_inner();
         let value = veritech
             .execute_validation(
@@ -63,7 +64,8 @@ impl FuncDispatch for FuncBackendValidation {
                 &change_set_id.to_string(),
             )
             .await?;
-        Ok(value)
+        resource_usage.record(value.resource_usage);
+        Ok(value.result)
     }
 }
 
@@ -971,7 +971,10 @@ async fn validate_intrinsic_inputs(
                 return Err(FuncBindingError::InvalidIntrinsicBinding);
             }
         }
-        IntrinsicFunc::Validation => return Err(FuncBindingError::InvalidIntrinsicBinding),
+        // string templates are only set on components, through attribute sources
+        IntrinsicFunc::StringTemplate | IntrinsicFunc::Validation => {
+            return Err(FuncBindingError::InvalidIntrinsicBinding);
+        }
     };
     Ok(())
 }
@@ -38,6 +38,7 @@ pub enum IntrinsicFunc {
     SetMap,
     SetObject,
     SetString,
+    StringTemplate,
     Unset,
     Validation,
 }
@@ -59,6 +60,7 @@ impl IntrinsicFunc {
             IntrinsicFunc::Identity
             | IntrinsicFunc::NormalizeToArray
             | IntrinsicFunc::ResourcePayloadToValue
+            | IntrinsicFunc::StringTemplate
             | IntrinsicFunc::Validation => true,
         }
     }
@@ -77,6 +79,7 @@ impl IntrinsicFunc {
             IntrinsicFunc::Identity
             | IntrinsicFunc::NormalizeToArray
             | IntrinsicFunc::ResourcePayloadToValue
+            | IntrinsicFunc::StringTemplate
             | IntrinsicFunc::Validation
             | IntrinsicFunc::Unset => return None,
         })
@@ -267,6 +270,26 @@ impl IntrinsicFunc {
                         .map_err(FuncError::IntrinsicSpecCreation)?,
                 );
             }
+            Self::StringTemplate => {
+                builder
+                    .unique_id("4f1d8a0c63b2e97f5a1c3d6e8b7f2a9c0e4d6b1a3f5c7e9d2b4a6c8e0f1a3b5d");
+                data_builder.backend_kind(FuncSpecBackendKind::StringTemplate);
+                data_builder.response_type(FuncSpecBackendResponseType::String);
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("template")
+                        .kind(FuncArgumentKind::Any)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+                builder.argument(
+                    FuncArgumentSpec::builder()
+                        .name("bindings")
+                        .kind(FuncArgumentKind::Object)
+                        .build()
+                        .map_err(FuncError::IntrinsicSpecCreation)?,
+                );
+            }
             Self::Unset => {
                 builder
                     .unique_id("8143ff98fbe8954bb3ab89ee521335d45ba9a42b7b79289eff53b503c4392c37");
@@ -311,6 +334,7 @@ impl IntrinsicFunc {
             Self::SetObject => "si:setObject",
             Self::SetJson => "si:setJson",
             Self::SetString => "si:setString",
+            Self::StringTemplate => "si:stringTemplate",
             Self::Unset => "si:unset",
             Self::Validation => "si:validation",
         }
@@ -329,6 +353,7 @@ impl IntrinsicFunc {
             "si:setObject" => Self::SetObject,
             "si:setJson" => Self::SetJson,
             "si:setString" => Self::SetString,
+            "si:stringTemplate" => Self::StringTemplate,
             "si:unset" => Self::Unset,
             "si:validation" => Self::Validation,
             _ => {
@@ -72,11 +72,13 @@ impl FuncKind {
         func_backend_response_type: FuncBackendResponseType,
     ) -> FuncResult<FuncKind> {
         Ok(match func_backend_kind {
-            FuncBackendKind::JsAttribute => match func_backend_response_type {
-                FuncBackendResponseType::CodeGeneration => FuncKind::CodeGeneration,
-                FuncBackendResponseType::Qualification => FuncKind::Qualification,
-                _ => FuncKind::Attribute,
-            },
+            FuncBackendKind::JsAttribute | FuncBackendKind::WasmAttribute => {
+                match func_backend_response_type {
+                    FuncBackendResponseType::CodeGeneration => FuncKind::CodeGeneration,
+                    FuncBackendResponseType::Qualification => FuncKind::Qualification,
+                    _ => FuncKind::Attribute,
+                }
+            }
             FuncBackendKind::JsAction => FuncKind::Action,
             FuncBackendKind::JsAuthentication => FuncKind::Authentication,
             FuncBackendKind::JsSchemaVariantDefinition => FuncKind::SchemaVariantDefinition,
@@ -94,6 +96,7 @@ impl FuncKind {
             | FuncBackendKind::Object
             | FuncBackendKind::ResourcePayloadToValue
             | FuncBackendKind::String
+            | FuncBackendKind::StringTemplate
             | FuncBackendKind::Unset
             | FuncBackendKind::Validation => FuncKind::Intrinsic,
             FuncBackendKind::JsReconciliation | FuncBackendKind::JsValidation => {
@@ -25,8 +25,13 @@ use si_events::{
     FuncRunId,
     FuncRunLog,
     FuncRunLogId,
+    FuncRunResourceUsage,
     FuncRunState,
     FuncRunValue,
+    func_result_memo::{
+        FuncResultMemo,
+        FuncResultMemoKey,
+    },
 };
 use si_layer_cache::LayerDbError;
 use telemetry::prelude::*;
@@ -38,12 +43,15 @@ use tokio::sync::{
 use ulid::Ulid;
 use veritech_client::{
     BeforeFunction,
+    ExecutionResourceUsage,
     FunctionResult,
     FunctionResultFailure,
     FunctionResultFailureErrorKind,
+    FunctionRuntime,
     KillExecutionRequest,
     OutputStream,
     ResolverFunctionComponent,
+    ResolverFunctionResponseType,
     VeritechValueEncryptError,
     encrypt_value_tree,
 };
@@ -51,6 +59,7 @@ use veritech_client::{
 use super::{
     backend::{
         FuncBackend,
+        FuncBackendResult,
         FuncDispatch,
         FuncDispatchContext,
         InvalidResolverFunctionTypeError,
@@ -63,6 +72,7 @@ use super::{
         integer::FuncBackendInteger,
         js_action::FuncBackendJsAction,
         js_attribute::{
+            self,
             FuncBackendJsAttribute,
             FuncBackendJsAttributeArgs,
         },
@@ -74,6 +84,7 @@ use super::{
         object::FuncBackendObject,
         resource_payload_to_value::FuncBackendResourcePayloadToValue,
         string::FuncBackendString,
+        string_template::FuncBackendStringTemplate,
         validation::FuncBackendValidation,
     },
     intrinsics::IntrinsicFunc,
@@ -122,6 +133,7 @@ use crate::{
         },
         value::AttributeValueError,
     },
+    billing_publish,
     func::backend::FuncBackendError,
     management::prototype::ManagementPrototypeId,
     prop::PropError,
@@ -1461,9 +1473,11 @@ impl FuncRunner {
         update_fn: impl FnOnce(&mut FuncRun),
     ) -> FuncRunnerResult<()> {
         let mut func_run = FuncRunDb::try_read(ctx, id).await?;
+        let previous_state = func_run.state();
         update_fn(&mut func_run);
 
-        FuncRunDb::upsert(ctx, func_run).await?;
+        FuncRunDb::upsert(ctx, func_run.clone()).await?;
+        Self::publish_billing_event_if_finished(ctx, previous_state, &func_run).await;
 
         Ok(())
     }
@@ -1481,17 +1495,71 @@ impl FuncRunner {
         )
         .await?;
 
+        let previous_state = func_run.state();
         update_fn(&mut func_run);
 
         FuncRunDb::upsert(ctx, func_run.clone()).await?;
+        Self::publish_billing_event_if_finished(ctx, previous_state, &func_run).await;
 
         Ok(())
     }
 
+    /// Publishes a billing event for the func run if the update moved it into a finished state.
+    ///
+    /// Failing to publish is logged rather than returned since it must not fail the func run.
+    async fn publish_billing_event_if_finished(
+        ctx: &DalContext,
+        previous_state: FuncRunState,
+        func_run: &FuncRun,
+    ) {
+        let is_finished = |state| {
+            matches!(
+                state,
+                FuncRunState::Success | FuncRunState::Failure | FuncRunState::Killed
+            )
+        };
+        if is_finished(previous_state) || !is_finished(func_run.state()) {
+            return;
+        }
+
+        if let Err(err) = billing_publish::for_func_run_complete(ctx, func_run).await {
+            warn!(
+                si.error.message = ?err,
+                si.func_run.id = %func_run.id(),
+                "failed to publish billing event for finished func run"
+            );
+        }
+    }
+
     pub fn id(&self) -> FuncRunId {
         self.func_run.id()
     }
 
+    /// Returns the key the result of executing the attribute [`Func`] with the given arguments is
+    /// memoized under, or [`None`] if the func hasn't opted in to memoization.
+    pub fn memo_key(
+        ctx: &DalContext,
+        func: &Func,
+        args: &serde_json::Value,
+    ) -> FuncRunnerResult<Option<FuncResultMemoKey>> {
+        if !func.memoization_enabled {
+            return Ok(None);
+        }
+
+        let func_identity = format!(
+            "{}:{}:{}:{}",
+            func.code_blake3,
+            func.handler.as_deref().unwrap_or_default(),
+            func.backend_kind.as_ref(),
+            func.backend_response_type.as_ref(),
+        );
+        Ok(Some(FuncResultMemoKey::new(
+            ctx.workspace_pk()?,
+            &func_identity,
+            args,
+        )))
+    }
+
     pub async fn execute(
         self,
         ctx: DalContext,
@@ -1890,6 +1958,114 @@ impl FuncRunnerExecutionTask {
         }
     }
 
+    /// Executes an attribute [`Func`], reusing the result of a previous execution of the same code
+    /// with the same arguments when one has been memoized.
+    ///
+    /// Only funcs that opted in with [`Func::memoization_enabled`] are memoized, and only for
+    /// executions without before functions, since those are the only ones that can't observe
+    /// secrets. Failures to read or write memoized results are logged and otherwise ignored.
+    async fn execute_attribute_func(
+        ctx: &DalContext,
+        func_dispatch_context: FuncDispatchContext,
+        func: &Func,
+        properties: &serde_json::Value,
+        before: Vec<BeforeFunction>,
+        response_type: ResolverFunctionResponseType,
+        runtime: FunctionRuntime,
+    ) -> FuncRunnerResult<FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)>>
+    {
+        let memo_key = if before.is_empty() {
+            FuncRunner::memo_key(ctx, func, properties)?
+        } else {
+            None
+        };
+
+        if let Some(key) = memo_key {
+            match ctx.layer_db().func_result_memo().read(&key).await {
+                Ok(Some(memo)) => {
+                    debug!(
+                        si.func.id = %func.id,
+                        si.func_result_memo.key = %key,
+                        "reusing memoized attribute func result"
+                    );
+                    return Ok(Ok((memo.unprocessed_value(), memo.value())));
+                }
+                Ok(None) => {}
+                Err(err) => warn!(
+                    si.error.message = ?err,
+                    si.func.id = %func.id,
+                    "failed to read memoized attribute func result"
+                ),
+            }
+        }
+
+        let args = FuncBackendJsAttributeArgs {
+            component: ResolverFunctionComponent {
+                data: veritech_client::ComponentView {
+                    properties: properties.to_owned(),
+                    ..Default::default()
+                },
+                parents: Vec::new(),
+            },
+            response_type: response_type.clone(),
+            runtime,
+        };
+        let result = FuncBackendJsAttribute::create_and_execute(
+            func_dispatch_context,
+            func,
+            &serde_json::to_value(args)?,
+            before,
+        )
+        .await;
+
+        if let (Some(key), Ok((unprocessed_value, value))) = (memo_key, &result) {
+            let is_failure_fallback = value
+                .as_ref()
+                .is_some_and(|value| js_attribute::is_failure_fallback(&response_type, value));
+            if !is_failure_fallback {
+                if let Err(err) = ctx.layer_db().func_result_memo().write(
+                    key,
+                    Arc::new(FuncResultMemo::new(
+                        unprocessed_value.clone(),
+                        value.clone(),
+                    )),
+                    ctx.events_tenancy(),
+                    ctx.events_actor(),
+                ) {
+                    warn!(
+                        si.error.message = ?err,
+                        si.func.id = %func.id,
+                        "failed to write memoized attribute func result"
+                    );
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Persists the resources consumed by the execution of the func run. Failing to do so is
+    /// logged rather than returned since it must not fail the func run.
+    async fn record_resource_usage(
+        ctx: &DalContext,
+        func_run_id: FuncRunId,
+        resource_usage: ExecutionResourceUsage,
+    ) {
+        let resource_usage = FuncRunResourceUsage {
+            queued_ms: resource_usage.queued_ms,
+            execution_ms: resource_usage.execution_ms,
+            cpu_time_ms: resource_usage.cpu_time_ms,
+            peak_memory_bytes: resource_usage.peak_memory_bytes,
+        };
+        if let Err(err) = FuncRunDb::set_resource_usage(ctx, func_run_id, &resource_usage).await {
+            warn!(
+                si.error.message = ?err,
+                si.func_run.id = %func_run_id,
+                "failed to record resource usage for func run"
+            );
+        }
+    }
+
     async fn try_run(self) -> FuncRunnerResult<()> {
         if !self.func.is_intrinsic() {
             FuncRunner::update_run(&self.ctx, self.func_run.id(), |func_run| {
@@ -1898,6 +2074,7 @@ impl FuncRunnerExecutionTask {
             .await?;
         }
 
+        let resource_usage = self.func_dispatch_context.resource_usage.clone();
         let execution_result = match self.func_run.backend_kind().into() {
             FuncBackendKind::JsAction => {
                 FuncBackendJsAction::create_and_execute(
@@ -1908,7 +2085,7 @@ impl FuncRunnerExecutionTask {
                 )
                 .await
             }
-            FuncBackendKind::JsAttribute => {
+            backend_kind @ (FuncBackendKind::JsAttribute | FuncBackendKind::WasmAttribute) => {
                 // NOTE(nick): changing the behavior is not great because the spans will imply that
                 // a different form of execution ran vs. what actually happened. Why do this here
                 // then? This is the last possible moment before the function executes. Not only
@@ -1921,7 +2098,7 @@ impl FuncRunnerExecutionTask {
                             si.func_run.func.id = %self.func.id,
                             si.func_run.func.name = %self.func.name,
                             si.func_run.func.backend_kind = %self.func_run.backend_kind(),
-                            "ignoring JsAttribute func backend kind for ResourcePayloadToValue intrinsic"
+                            "ignoring attribute func backend kind for ResourcePayloadToValue intrinsic"
                         );
                         FuncBackendResourcePayloadToValue::create_and_execute(&self.args).await
                     }
@@ -1931,28 +2108,26 @@ impl FuncRunnerExecutionTask {
                             si.func_run.func.id = %self.func.id,
                             si.func_run.func.name = %self.func.name,
                             si.func_run.func.backend_kind = %self.func_run.backend_kind(),
-                            "ignoring JsAttribute func backend kind for NormalizeToArray intrinsic"
+                            "ignoring attribute func backend kind for NormalizeToArray intrinsic"
                         );
                         FuncBackendNormalizeToArray::create_and_execute(&self.args).await
                     }
                     Some(_) | None => {
-                        let args = FuncBackendJsAttributeArgs {
-                            component: ResolverFunctionComponent {
-                                data: veritech_client::ComponentView {
-                                    properties: self.args.to_owned(),
-                                    ..Default::default()
-                                },
-                                parents: Vec::new(),
-                            },
-                            response_type: self.func.backend_response_type.try_into()?,
+                        let runtime = if backend_kind == FuncBackendKind::WasmAttribute {
+                            FunctionRuntime::Wasm
+                        } else {
+                            FunctionRuntime::LangJs
                         };
-                        FuncBackendJsAttribute::create_and_execute(
+                        Self::execute_attribute_func(
+                            &self.ctx,
                             self.func_dispatch_context,
                             &self.func,
-                            &serde_json::to_value(args)?,
+                            &self.args,
                             self.before,
+                            self.func.backend_response_type.try_into()?,
+                            runtime,
                         )
-                        .await
+                        .await?
                     }
                 }
             }
@@ -2015,6 +2190,9 @@ impl FuncRunnerExecutionTask {
             FuncBackendKind::NormalizeToArray => {
                 FuncBackendNormalizeToArray::create_and_execute(&self.args).await
             }
+            FuncBackendKind::StringTemplate => {
+                FuncBackendStringTemplate::create_and_execute(&self.args).await
+            }
             FuncBackendKind::Debug => {
                 FuncBackendDebug::create_and_execute(
                     self.func_dispatch_context,
@@ -2026,6 +2204,10 @@ impl FuncRunnerExecutionTask {
             }
         };
 
+        if let Some(resource_usage) = resource_usage.take() {
+            Self::record_resource_usage(&self.ctx, self.func_run.id(), resource_usage).await;
+        }
+
         match execution_result {
             Ok((mut unprocessed_value, mut value)) => {
                 // We so sorry - this is the way that the old code
@@ -224,6 +224,7 @@ impl ManagementFuncJob {
         &self,
         ctx: &DalContext,
         execution_state_id: ManagementFuncJobStateId,
+        webhook_payload: Option<serde_json::Value>,
     ) -> ManagementFuncJobResult<JobCompletionState> {
         let mut ctx_clone = ctx.clone();
         // Loop for 5_000 * 50 ms (= 250 seconds max) and then mark job as failed if not ready
@@ -237,6 +238,7 @@ impl ManagementFuncJob {
                 self.prototype_id,
                 self.component_id,
                 self.view_id.into(),
+                webhook_payload,
             )
             .await?;
 
@@ -409,8 +411,12 @@ impl ManagementFuncJob {
                     self.change_set_id,
                 ))?;
         let execution_state_id = pending_execution.id();
+        let webhook_payload = pending_execution.webhook_payload().clone();
 
-        match self.run_inner(ctx, execution_state_id).await {
+        match self
+            .run_inner(ctx, execution_state_id, webhook_payload)
+            .await
+        {
             Ok(completion_state) => Ok(completion_state),
             Err(err) => {
                 let failure_message = err.to_string();
@@ -1,4 +1,5 @@
 use std::collections::{
+    BTreeMap,
     HashMap,
     HashSet,
 };
@@ -39,8 +40,12 @@ use crate::{
         ActionCompletionStatus,
         prototype::ActionKind,
     },
-    approval_requirement::ApprovalRequirementApprover,
+    approval_requirement::{
+        ApprovalRequirementApprover,
+        ApprovalRequirementComponentLabel,
+    },
     attribute::path::AttributePath,
+    component_template::ComponentTemplateMembership,
     func::argument::FuncArgumentKind,
     prop::WidgetOptions,
     property_editor::schema::WidgetKind,
@@ -93,6 +98,7 @@ pub enum ContentTypes {
     View(ViewContent),
     ApprovalRequirementDefinition(ApprovalRequirementDefinitionContent),
     AttributePaths(AttributePathsContent),
+    Variable(VariableContent),
 }
 
 macro_rules! impl_into_content_types {
@@ -151,6 +157,7 @@ impl_into_content_types!(ManagementPrototype);
 impl_into_content_types!(Geometry);
 impl_into_content_types!(View);
 impl_into_content_types!(ApprovalRequirementDefinition);
+impl_into_content_types!(Variable);
 
 // Here we've broken the Foo, FooContent convention so we need to implement
 // these traits manually
@@ -256,15 +263,43 @@ pub struct AttributePrototypeContentV1 {
 pub enum ComponentContent {
     V1(ComponentContentV1),
     V2(ComponentContentV2),
+    V3(ComponentContentV3),
+    V4(ComponentContentV4),
+    V5(ComponentContentV5),
 }
 
 impl ComponentContent {
-    pub fn extract(self) -> ComponentContentV2 {
+    pub fn extract(self) -> ComponentContentV5 {
         match self {
-            ComponentContent::V1(v1) => ComponentContentV2 {
+            ComponentContent::V1(v1) => ComponentContentV5 {
                 timestamp: v1.timestamp,
+                labels: BTreeMap::new(),
+                owner: None,
+                promoted_from: None,
+                template_instance: None,
+            },
+            ComponentContent::V2(v2) => ComponentContentV5 {
+                timestamp: v2.timestamp,
+                labels: BTreeMap::new(),
+                owner: None,
+                promoted_from: None,
+                template_instance: None,
+            },
+            ComponentContent::V3(v3) => ComponentContentV5 {
+                timestamp: v3.timestamp,
+                labels: v3.labels,
+                owner: v3.owner,
+                promoted_from: None,
+                template_instance: None,
             },
-            ComponentContent::V2(v2) => v2,
+            ComponentContent::V4(v4) => ComponentContentV5 {
+                timestamp: v4.timestamp,
+                labels: v4.labels,
+                owner: v4.owner,
+                promoted_from: v4.promoted_from,
+                template_instance: None,
+            },
+            ComponentContent::V5(v5) => v5,
         }
     }
 }
@@ -283,6 +318,32 @@ pub struct ComponentContentV2 {
     pub timestamp: Timestamp,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentContentV3 {
+    pub timestamp: Timestamp,
+    pub labels: BTreeMap<String, String>,
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentContentV4 {
+    pub timestamp: Timestamp,
+    pub labels: BTreeMap<String, String>,
+    pub owner: Option<String>,
+    /// The id of the component in another workspace this one was promoted from, if any.
+    pub promoted_from: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentContentV5 {
+    pub timestamp: Timestamp,
+    pub labels: BTreeMap<String, String>,
+    pub owner: Option<String>,
+    pub promoted_from: Option<String>,
+    /// The component template instance the component was created by, if any.
+    pub template_instance: Option<ComponentTemplateMembership>,
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum ViewContent {
     V1(ViewContentV1),
@@ -327,6 +388,8 @@ pub enum FuncContent {
     V1(FuncContentV1),
     V2(FuncContentV2),
     V3(FuncContentV3),
+    V4(FuncContentV4),
+    V5(FuncContentV5),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -378,11 +441,54 @@ pub struct FuncContentV3 {
     // Transformation funcs always have a single argument, and aren't created in the context of a schema variant
     pub is_transformation: bool,
 }
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncContentV4 {
+    pub timestamp: Timestamp,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub hidden: bool,
+    pub builtin: bool,
+    pub backend_response_type: FuncBackendResponseType,
+    pub backend_kind: FuncBackendKind,
+    pub handler: Option<String>,
+    pub code_base64: Option<String>,
+    /// A hash of the code above
+    pub code_blake3: ContentHash,
+    pub is_locked: bool,
+    // Transformation funcs always have a single argument, and aren't created in the context of a schema variant
+    pub is_transformation: bool,
+    // Only deterministic funcs opt in to having their results reused for the same arguments
+    pub memoization_enabled: bool,
+}
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FuncContentV5 {
+    pub timestamp: Timestamp,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub hidden: bool,
+    pub builtin: bool,
+    pub backend_response_type: FuncBackendResponseType,
+    pub backend_kind: FuncBackendKind,
+    pub handler: Option<String>,
+    pub code_base64: Option<String>,
+    /// A hash of the code above
+    pub code_blake3: ContentHash,
+    pub is_locked: bool,
+    // Transformation funcs always have a single argument, and aren't created in the context of a schema variant
+    pub is_transformation: bool,
+    // Only deterministic funcs opt in to having their results reused for the same arguments
+    pub memoization_enabled: bool,
+    // Limits on the resources a single execution may consume, unlimited when unset
+    pub max_memory_bytes: Option<u64>,
+    pub max_cpu_time_ms: Option<u64>,
+}
 
 impl FuncContent {
-    pub fn extract(self) -> FuncContentV3 {
+    pub fn extract(self) -> FuncContentV5 {
         match self {
-            FuncContent::V1(v1) => FuncContentV3 {
+            FuncContent::V1(v1) => FuncContentV5 {
                 timestamp: v1.timestamp,
                 hidden: v1.hidden,
                 display_name: v1.display_name,
@@ -396,8 +502,11 @@ impl FuncContent {
                 code_base64: v1.code_base64,
                 code_blake3: v1.code_blake3,
                 is_transformation: false,
+                memoization_enabled: false,
+                max_memory_bytes: None,
+                max_cpu_time_ms: None,
             },
-            FuncContent::V2(v1) => FuncContentV3 {
+            FuncContent::V2(v1) => FuncContentV5 {
                 timestamp: v1.timestamp,
                 hidden: v1.hidden,
                 display_name: v1.display_name,
@@ -411,8 +520,47 @@ impl FuncContent {
                 code_base64: v1.code_base64,
                 code_blake3: v1.code_blake3,
                 is_transformation: false,
+                memoization_enabled: false,
+                max_memory_bytes: None,
+                max_cpu_time_ms: None,
+            },
+            FuncContent::V3(v3) => FuncContentV5 {
+                timestamp: v3.timestamp,
+                hidden: v3.hidden,
+                display_name: v3.display_name,
+                link: v3.link,
+                description: v3.description,
+                is_locked: v3.is_locked,
+                builtin: v3.builtin,
+                backend_response_type: v3.backend_response_type,
+                backend_kind: v3.backend_kind,
+                handler: v3.handler,
+                code_base64: v3.code_base64,
+                code_blake3: v3.code_blake3,
+                is_transformation: v3.is_transformation,
+                memoization_enabled: false,
+                max_memory_bytes: None,
+                max_cpu_time_ms: None,
+            },
+            FuncContent::V4(v4) => FuncContentV5 {
+                timestamp: v4.timestamp,
+                hidden: v4.hidden,
+                display_name: v4.display_name,
+                link: v4.link,
+                description: v4.description,
+                is_locked: v4.is_locked,
+                builtin: v4.builtin,
+                backend_response_type: v4.backend_response_type,
+                backend_kind: v4.backend_kind,
+                handler: v4.handler,
+                code_base64: v4.code_base64,
+                code_blake3: v4.code_blake3,
+                is_transformation: v4.is_transformation,
+                memoization_enabled: v4.memoization_enabled,
+                max_memory_bytes: None,
+                max_cpu_time_ms: None,
             },
-            FuncContent::V3(v3) => v3,
+            FuncContent::V5(v5) => v5,
         }
     }
 }
@@ -827,6 +975,22 @@ pub struct ManagementPrototypeContentV1 {
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum ApprovalRequirementDefinitionContent {
     V1(ApprovalRequirementDefinitionContentV1),
+    V2(ApprovalRequirementDefinitionContentV2),
+}
+
+impl ApprovalRequirementDefinitionContent {
+    pub fn extract(self) -> ApprovalRequirementDefinitionContentV2 {
+        match self {
+            ApprovalRequirementDefinitionContent::V1(v1) => {
+                ApprovalRequirementDefinitionContentV2 {
+                    minimum: v1.minimum,
+                    approvers: v1.approvers,
+                    component_label: None,
+                }
+            }
+            ApprovalRequirementDefinitionContent::V2(v2) => v2,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -835,6 +999,15 @@ pub struct ApprovalRequirementDefinitionContentV1 {
     pub approvers: HashSet<ApprovalRequirementApprover>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ApprovalRequirementDefinitionContentV2 {
+    pub minimum: usize,
+    pub approvers: HashSet<ApprovalRequirementApprover>,
+    /// When set, the definition applies to every component carrying this label rather than to
+    /// the entity it is attached to.
+    pub component_label: Option<ApprovalRequirementComponentLabel>,
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum AttributePathsContent {
     V1(AttributePathsContentV1),
@@ -848,3 +1021,23 @@ impl From<Vec<AttributePath>> for AttributePathsContent {
         AttributePathsContent::V1(AttributePathsContentV1(paths))
     }
 }
+
+#[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
+pub enum VariableContent {
+    V1(VariableContentV1),
+}
+
+impl VariableContent {
+    pub fn extract(self) -> VariableContentV1 {
+        let VariableContent::V1(content) = self;
+        content
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct VariableContentV1 {
+    pub timestamp: Timestamp,
+    pub name: String,
+    pub value: CasValue,
+    pub description: Option<String>,
+}
@@ -33,6 +33,7 @@ pub mod change_set;
 pub mod change_status;
 pub mod code_view;
 pub mod component;
+pub mod component_template;
 pub mod context;
 pub mod dependency_graph;
 pub mod diagram;
@@ -49,6 +50,7 @@ pub mod management;
 pub mod module;
 pub mod pkg;
 pub mod policy;
+pub mod promotion;
 pub mod prompt_override;
 pub mod prop;
 pub mod property_editor;
@@ -64,6 +66,7 @@ pub mod standard_connection;
 pub mod status;
 pub mod user;
 pub mod validation;
+pub mod variable;
 pub mod workspace;
 pub mod workspace_integrations;
 pub mod workspace_snapshot;
@@ -105,6 +108,7 @@ pub use context::{
     Connections,
     DalContext,
     DalContextBuilder,
+    DalContextCheckpoint,
     DalLayerDb,
     RequestContext,
     ServicesContext,
@@ -281,6 +285,8 @@ pub enum MigrationMode {
     BackfillFuncRuns,
     BackfillLayerCache,
     GarbageCollectSnapshots,
+    MigrateSplitSnapshots,
+    RollbackSplitSnapshots,
     Run,
     RunAndQuit,
     Skip,
@@ -317,6 +323,14 @@ impl MigrationMode {
     pub fn is_backfill_func_runs(&self) -> bool {
         matches!(self, Self::BackfillFuncRuns)
     }
+
+    pub fn is_migrate_split_snapshots(&self) -> bool {
+        matches!(self, Self::MigrateSplitSnapshots)
+    }
+
+    pub fn is_rollback_split_snapshots(&self) -> bool {
+        matches!(self, Self::RollbackSplitSnapshots)
+    }
 }
 
 #[cfg(test)]
@@ -337,6 +351,14 @@ mod tests {
                 "garbageCollectSnapshots",
                 MigrationMode::GarbageCollectSnapshots.to_string()
             );
+            assert_eq!(
+                "migrateSplitSnapshots",
+                MigrationMode::MigrateSplitSnapshots.to_string()
+            );
+            assert_eq!(
+                "rollbackSplitSnapshots",
+                MigrationMode::RollbackSplitSnapshots.to_string()
+            );
             assert_eq!("run", MigrationMode::Run.to_string());
             assert_eq!("runAndQuit", MigrationMode::RunAndQuit.to_string());
             assert_eq!("skip", MigrationMode::Skip.to_string());
@@ -348,6 +370,14 @@ mod tests {
                 MigrationMode::GarbageCollectSnapshots,
                 "garbageCollectSnapshots".parse().expect("failed to parse")
             );
+            assert_eq!(
+                MigrationMode::MigrateSplitSnapshots,
+                "migrateSplitSnapshots".parse().expect("failed to parse")
+            );
+            assert_eq!(
+                MigrationMode::RollbackSplitSnapshots,
+                "rollbackSplitSnapshots".parse().expect("failed to parse")
+            );
             assert_eq!(MigrationMode::Run, "run".parse().expect("failed to parse"));
             assert_eq!(
                 MigrationMode::RunAndQuit,
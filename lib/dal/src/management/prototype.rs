@@ -623,6 +623,7 @@ impl ManagementPrototype {
         id: ManagementPrototypeId,
         manager_component_id: ComponentId,
         view_id: Option<ViewId>,
+        webhook_payload: Option<serde_json::Value>,
     ) -> ManagementPrototypeResult<(
         HashMap<String, ManagementGeometry>,
         HashMap<String, ComponentId>,
@@ -668,12 +669,15 @@ impl ManagementPrototype {
 
         let variant_socket_map = Self::variant_socket_map(ctx).await?;
 
-        let args = serde_json::json!({
+        let mut args = serde_json::json!({
             "current_view": current_view,
             "this_component": manager_component,
             "components": managed_components,
             "variant_socket_map": variant_socket_map,
         });
+        if let (Some(payload), Some(args)) = (webhook_payload, args.as_object_mut()) {
+            args.insert("webhook_payload".to_string(), payload);
+        }
 
         let (func_run_id, result_channel) =
             FuncRunner::run_management(ctx, id, manager_component_id, management_func_id, args)
@@ -831,7 +835,8 @@ impl ManagementPrototype {
         view_id: Option<ViewId>,
     ) -> ManagementPrototypeResult<ManagementPrototypeExecution> {
         let (geometries, placeholders, run_channel, func_run_id) =
-            ManagementPrototype::start_execution(ctx, prototype_id, component_id, view_id).await?;
+            ManagementPrototype::start_execution(ctx, prototype_id, component_id, view_id, None)
+                .await?;
 
         ManagementPrototype::finalize_execution(
             ctx,
@@ -12,6 +12,7 @@ pub use import::{
     ImportOptions,
     Thing,
     ThingMap,
+    find_or_install_intrinsic_func,
     import_func,
     import_funcs_for_module_update,
     import_only_new_funcs,
@@ -86,6 +87,9 @@ use crate::{
 
 pub mod export;
 pub mod import;
+mod signature;
+
+pub use signature::verify_module_signature;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum UpdateMode {
@@ -113,6 +117,8 @@ pub enum PkgError {
     AttributeValueError(#[from] Box<AttributeValueError>),
     #[error("change set error: {0}")]
     ChangeSet(#[from] Box<ChangeSetError>),
+    #[error("component templates are not installed as modules")]
+    ComponentTemplateNotSupported,
     #[error("connection annotation error: {0}")]
     ConnectionAnnotation(#[from] Box<ConnectionAnnotationError>),
     #[error("expected data on an SiPkg node, but none found: {0}")]
@@ -131,6 +137,8 @@ pub enum PkgError {
     IntrinsicFuncSpecsMultipleForName(String),
     #[error("found no intrinsic func specs for name: {0}")]
     IntrinsicFuncSpecsNoneForName(String),
+    #[error("module signature does not match the module contents (signing key: {0})")]
+    InvalidModuleSignature(String),
     #[error("management prototype error: {0}")]
     ManagementPrototype(#[from] Box<ManagementPrototypeError>),
     #[error("Missing Func {1} for AttributePrototype {0}")]
@@ -149,12 +157,16 @@ pub enum PkgError {
     MissingUniqueIdForNode(String),
     #[error("module error: {0}")]
     Module(#[from] Box<ModuleError>),
+    #[error("module trust store error: {0}")]
+    ModuleTrustStore(#[from] si_db::ModuleTrustStoreError),
     #[error("output socket error: {0}")]
     OutputSocket(#[from] Box<OutputSocketError>),
     #[error("output socket {0} missing attribute prototype")]
     OutputSocketMissingPrototype(OutputSocketId),
     #[error("Package with that hash already installed: {0}")]
     PackageAlreadyInstalled(String),
+    #[error("package signing error: {0}")]
+    PackageSigning(#[from] si_crypto::PackageSigningError),
     #[error("si pkg error: {0}")]
     Pkg(#[from] SiPkgError),
     #[error("pkg spec error: {0}")]
@@ -183,6 +195,10 @@ pub enum PkgError {
     Transactions(#[from] Box<TransactionsError>),
     #[error("ulid decode error: {0}")]
     UlidDecode(#[from] ulid::DecodeError),
+    #[error("refusing to install unsigned module (hash: {0})")]
+    UnsignedModule(String),
+    #[error("refusing to install module signed by untrusted key: {0}")]
+    UntrustedModuleSigner(String),
     #[error("url parse error: {0}")]
     Url(#[from] ParseError),
     #[error("workspace error: {0}")]
@@ -336,6 +352,8 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::ResourcePayloadToValue => Self::ResourcePayloadToValue,
             FuncBackendKind::NormalizeToArray => Self::NormalizeToArray,
             FuncBackendKind::Debug => Self::Debug,
+            FuncBackendKind::WasmAttribute => Self::WasmAttribute,
+            FuncBackendKind::StringTemplate => Self::StringTemplate,
         }
     }
 }
@@ -365,6 +383,8 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::ResourcePayloadToValue => Self::ResourcePayloadToValue,
             FuncSpecBackendKind::NormalizeToArray => Self::NormalizeToArray,
             FuncSpecBackendKind::Debug => Self::Debug,
+            FuncSpecBackendKind::WasmAttribute => Self::WasmAttribute,
+            FuncSpecBackendKind::StringTemplate => Self::StringTemplate,
         }
     }
 }
@@ -148,6 +148,7 @@ impl PkgExporter {
 
     pub async fn export_as_bytes(&mut self, ctx: &DalContext) -> PkgResult<Vec<u8>> {
         match self.kind {
+            SiPkgKind::ComponentTemplate => return Err(PkgError::ComponentTemplateNotSupported),
             SiPkgKind::Module => info!("Building module package"),
             SiPkgKind::WorkspaceBackup => return Err(PkgError::WorkspaceExportNotSupported()),
         }
@@ -936,7 +937,8 @@ impl PkgExporter {
                     // something that the user can toggle?
                     ValueSource::Secret(_)
                     | ValueSource::StaticArgumentValue(_)
-                    | ValueSource::ValueSubscription(_) => {
+                    | ValueSource::ValueSubscription(_)
+                    | ValueSource::Variable(_) => {
                         // do nothing as this is irrelevant for the schema variant!
                     }
                 }
@@ -1066,7 +1068,9 @@ impl PkgExporter {
             match intrinsic {
                 // NOTE(nick): we only want to export intrinsics that were not special case funcs. If
                 // we want that to change, we may need a graph migration.
-                IntrinsicFunc::ResourcePayloadToValue | IntrinsicFunc::NormalizeToArray => {
+                IntrinsicFunc::ResourcePayloadToValue
+                | IntrinsicFunc::NormalizeToArray
+                | IntrinsicFunc::StringTemplate => {
                     let intrinsic_name = intrinsic.name();
 
                     // Notice that we only want to find the func with the intrinsic kind (the new
@@ -1183,6 +1187,7 @@ impl PkgExporter {
         }
 
         match self.kind {
+            SiPkgKind::ComponentTemplate => return Err(PkgError::ComponentTemplateNotSupported),
             SiPkgKind::Module => {
                 let (funcs, _, schemas, _, _) = self.export_change_set(ctx).await?;
                 pkg_spec_builder.funcs(funcs);
@@ -1207,7 +1212,9 @@ impl PkgExporter {
             // NOTE(nick): we only want to export intrinsics that were not special case funcs. If
             // we want that to change, we may need a graph migration.
             match intrinsic {
-                IntrinsicFunc::ResourcePayloadToValue | IntrinsicFunc::NormalizeToArray => {
+                IntrinsicFunc::ResourcePayloadToValue
+                | IntrinsicFunc::NormalizeToArray
+                | IntrinsicFunc::StringTemplate => {
                     // Notice that we only want to find the func with the intrinsic kind (the new
                     // version). If we cannot, we just skip it because it is not guaranteed to be
                     // here without a graph migration.
@@ -41,6 +41,7 @@ use super::{
     PkgError,
     PkgResult,
     UpdateMode,
+    verify_module_signature,
 };
 use crate::{
     AttributePrototype,
@@ -152,7 +153,9 @@ async fn import_change_set(
     for func_spec in funcs {
         if let Some(intrinsic) = IntrinsicFunc::maybe_from_str(func_spec.name()) {
             let maybe_func_id = match intrinsic {
-                IntrinsicFunc::ResourcePayloadToValue | IntrinsicFunc::NormalizeToArray => {
+                IntrinsicFunc::ResourcePayloadToValue
+                | IntrinsicFunc::NormalizeToArray
+                | IntrinsicFunc::StringTemplate => {
                     Func::find_id_by_name_and_kind(ctx, func_spec.name(), FuncKind::Intrinsic)
                         .await?
                 }
@@ -374,6 +377,7 @@ pub async fn import_pkg_from_pkg(
     let mut change_set_things = ThingMap::new();
 
     match metadata.kind() {
+        SiPkgKind::ComponentTemplate => Err(PkgError::ComponentTemplateNotSupported),
         SiPkgKind::Module => {
             let (installed_schema_variant_ids, _, _) = import_change_set(
                 ctx,
@@ -398,6 +402,9 @@ pub async fn import_pkg(ctx: &DalContext, pkg_file_path: impl AsRef<Path>) -> Pk
     println!("Importing package from {:?}", pkg_file_path.as_ref());
     let pkg = SiPkg::load_from_file(&pkg_file_path).await?;
 
+    // Modules on disk don't come with a signature, so they're only installed if the workspace
+    // accepts unsigned modules
+    verify_module_signature(ctx, &pkg, None).await?;
     import_pkg_from_pkg(ctx, &pkg, None).await?;
 
     Ok(pkg)
@@ -1245,6 +1252,7 @@ pub async fn import_only_new_funcs(
         if let Some(intrinsic) = IntrinsicFunc::maybe_from_str(func_spec.name()) {
             if intrinsic == IntrinsicFunc::ResourcePayloadToValue
                 || intrinsic == IntrinsicFunc::NormalizeToArray
+                || intrinsic == IntrinsicFunc::StringTemplate
             {
                 if let Some(func_id) =
                     Func::find_id_by_name_and_kind(ctx, func_spec.name(), FuncKind::Intrinsic)
@@ -2016,46 +2024,51 @@ async fn create_prop(
     Ok(Some(ParentPropInfo { prop_id: prop.id() }))
 }
 
-pub async fn attach_resource_payload_to_value(
+/// Finds the given [`IntrinsicFunc`], installing it from the intrinsic funcs package if this
+/// workspace predates it.
+pub async fn find_or_install_intrinsic_func(
     ctx: &DalContext,
-    schema_variant_id: SchemaVariantId,
-) -> PkgResult<()> {
-    let name = "si:resourcePayloadToValue";
-    let func_id = if let Some(func_id) =
-        Func::find_id_by_name_and_kind(ctx, name, FuncKind::Intrinsic).await?
-    {
-        func_id
-    } else {
-        trace!(
-            "installing the intrinsic version of 'si:resourcePayloadToValue' (neither found nor was specified in the package spec)"
-        );
+    intrinsic: IntrinsicFunc,
+) -> PkgResult<FuncId> {
+    let name = intrinsic.name();
+    if let Some(func_id) = Func::find_id_by_name_and_kind(ctx, name, FuncKind::Intrinsic).await? {
+        return Ok(func_id);
+    }
 
-        // If we did not find it by this point, the package did not specify it.
-        let unsafe_to_install_intrinsic_funcs_pkg =
-            SiPkg::load_from_spec(IntrinsicFunc::pkg_spec()?)?;
-        let mut intrinsic_func_specs =
-            unsafe_to_install_intrinsic_funcs_pkg.funcs_for_name(name)?;
-        let intrinsic_func_spec = intrinsic_func_specs
-            .pop()
-            .ok_or(PkgError::IntrinsicFuncSpecsNoneForName(name.to_owned()))?;
-        if !intrinsic_func_specs.is_empty() {
-            return Err(PkgError::IntrinsicFuncSpecsMultipleForName(name.to_owned()));
-        }
+    trace!(
+        "installing the intrinsic version of '{name}' (neither found nor was specified in the package spec)"
+    );
 
-        // Create the func and arguments without interacting with the thing map and import flow. We
-        // do that because the package did not specify it.
-        let func = create_func(ctx, &intrinsic_func_spec, false).await?;
-        for argument in intrinsic_func_spec.arguments()? {
-            if FuncArgument::find_by_name_for_func(ctx, argument.name(), func.id)
-                .await?
-                .is_none()
-            {
-                create_func_argument(ctx, func.id, &argument).await?;
-            }
+    let unsafe_to_install_intrinsic_funcs_pkg = SiPkg::load_from_spec(IntrinsicFunc::pkg_spec()?)?;
+    let mut intrinsic_func_specs = unsafe_to_install_intrinsic_funcs_pkg.funcs_for_name(name)?;
+    let intrinsic_func_spec = intrinsic_func_specs
+        .pop()
+        .ok_or(PkgError::IntrinsicFuncSpecsNoneForName(name.to_owned()))?;
+    if !intrinsic_func_specs.is_empty() {
+        return Err(PkgError::IntrinsicFuncSpecsMultipleForName(name.to_owned()));
+    }
+
+    // Create the func and arguments without interacting with the thing map and import flow. We
+    // do that because no package specified it.
+    let func = create_func(ctx, &intrinsic_func_spec, false).await?;
+    for argument in intrinsic_func_spec.arguments()? {
+        if FuncArgument::find_by_name_for_func(ctx, argument.name(), func.id)
+            .await?
+            .is_none()
+        {
+            create_func_argument(ctx, func.id, &argument).await?;
         }
+    }
 
-        func.id
-    };
+    Ok(func.id)
+}
+
+pub async fn attach_resource_payload_to_value(
+    ctx: &DalContext,
+    schema_variant_id: SchemaVariantId,
+) -> PkgResult<()> {
+    let func_id =
+        find_or_install_intrinsic_func(ctx, IntrinsicFunc::ResourcePayloadToValue).await?;
 
     let func_argument_id = FuncArgument::find_by_name_for_func(ctx, "payload", func_id)
         .await?
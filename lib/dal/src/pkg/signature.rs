@@ -0,0 +1,67 @@
+//! Verification of the publisher signatures of modules against the trust store of a workspace.
+
+use module_index_client::ModuleSignature;
+use si_crypto::{
+    PackageSignature,
+    PackageVerifyingKey,
+};
+use si_db::ModuleTrustStore;
+use si_pkg::SiPkg;
+use telemetry::prelude::*;
+
+use super::{
+    PkgError,
+    PkgResult,
+};
+use crate::DalContext;
+
+/// Checks the publisher signature of a module against the trust store of the workspace before the
+/// module is installed.
+///
+/// A signature that does not match the module is always refused. Unsigned modules and modules
+/// signed by a key the workspace does not trust are only refused if the trust store is strict.
+pub async fn verify_module_signature(
+    ctx: &DalContext,
+    pkg: &SiPkg,
+    signature: Option<&ModuleSignature>,
+) -> PkgResult<()> {
+    let hash = pkg.hash()?.to_string();
+    let trust_store = ModuleTrustStore::get(ctx).await?;
+
+    let Some(signature) = signature else {
+        if trust_store.strict {
+            return Err(PkgError::UnsignedModule(hash));
+        }
+        debug!(module_hash = %hash, "installing unsigned module");
+        return Ok(());
+    };
+
+    // Prefer the key the workspace trusts over the one shipped alongside the signature so that a
+    // module index cannot vouch for a key it made up.
+    let trusted_key = trust_store.trusted_key(&signature.key_id);
+    let public_key = trusted_key
+        .map(|key| key.public_key.as_str())
+        .unwrap_or(signature.public_key.as_str());
+
+    let verifying_key = PackageVerifyingKey::from_base64(public_key)?;
+    let module_signature = PackageSignature::from_base64(&signature.signature)?;
+    if verifying_key.key_id() != signature.key_id || !verifying_key.verify(&hash, &module_signature)
+    {
+        return Err(PkgError::InvalidModuleSignature(
+            signature.key_id.to_owned(),
+        ));
+    }
+
+    if trusted_key.is_none() {
+        if trust_store.strict {
+            return Err(PkgError::UntrustedModuleSigner(signature.key_id.to_owned()));
+        }
+        warn!(
+            module_hash = %hash,
+            key_id = %signature.key_id,
+            "installing module signed by a key this workspace does not trust"
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,543 @@
+//! Promotion of components from one workspace to another (e.g. dev -> staging -> prod).
+//!
+//! [`export_components`] turns a selection of components into si-pkg
+//! [`ComponentSpecs`](ComponentSpec) whose `sources` are portable: every component, variable and
+//! function they reference is identified by name rather than by id, since ids differ between
+//! workspaces.
+//! [`promote_components`] maps those specs onto the components of another workspace that were
+//! promoted from the same source component, creating any that don't exist yet, and sets only the
+//! attributes that differ. Per-environment overrides replace the promoted source for specific
+//! attribute paths.
+
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_pkg::{
+    ComponentSpec,
+    ComponentSpecVariant,
+    PositionSpec,
+    SpecError,
+};
+use thiserror::Error;
+
+use crate::{
+    Component,
+    ComponentError,
+    ComponentId,
+    DalContext,
+    Func,
+    FuncError,
+    FuncId,
+    Schema,
+    SchemaError,
+    SchemaVariantError,
+    attribute::attributes::{
+        self,
+        AttributeSources,
+        AttributeValueIdent,
+        AttributesError,
+        ComponentIdent,
+        FuncIdent,
+        Source,
+        TemplateBinding,
+        ValueOrSourceSpec,
+        VariableIdent,
+    },
+    diagram::{
+        DiagramError,
+        geometry::Geometry,
+        view::View,
+    },
+};
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum PromotionError {
+    #[error("attributes error: {0}")]
+    Attributes(#[from] Box<AttributesError>),
+    #[error("component error: {0}")]
+    Component(#[from] Box<ComponentError>),
+    #[error("component {0} is marked for deletion and cannot be promoted")]
+    ComponentMarkedForDeletion(ComponentId),
+    #[error(
+        "a component named {0} already exists in the target workspace but was not promoted from this one"
+    )]
+    ComponentNameInUse(String),
+    #[error("component {name} is a {existing_schema} in the target workspace, not a {schema}")]
+    ComponentSchemaMismatch {
+        name: String,
+        schema: String,
+        existing_schema: String,
+    },
+    #[error("diagram error: {0}")]
+    Diagram(#[from] Box<DiagramError>),
+    #[error("func error: {0}")]
+    Func(#[from] Box<FuncError>),
+    #[error("invalid sources for promoted component {0}: {1}")]
+    InvalidSources(String, serde_json::Error),
+    #[error("override given for component {0}, which is not being promoted")]
+    OverrideForUnknownComponent(String),
+    #[error("schema error: {0}")]
+    Schema(#[from] Box<SchemaError>),
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] Box<SchemaVariantError>),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("spec error: {0}")]
+    Spec(#[from] SpecError),
+    #[error(
+        "{path} subscribes through function {func_id}, which only exists in this workspace and cannot be promoted"
+    )]
+    UnsupportedSubscriptionFunc { path: String, func_id: FuncId },
+    #[error("component {0} uses a workspace variant and cannot be promoted")]
+    UnsupportedVariant(String),
+}
+
+#[allow(missing_docs)]
+pub type PromotionResult<T> = Result<T, PromotionError>;
+
+/// How [`promote_components`] finds the component each promoted component is applied to.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PromotionMatching {
+    /// Only components that were promoted from the same source component.
+    #[default]
+    SourceId,
+    /// Components that were promoted from the same source component, falling back to a component
+    /// with the same name that wasn't promoted from anywhere (e.g. one created by hand before
+    /// promotions were set up).
+    SourceIdThenName,
+}
+
+/// Per-environment attribute overrides, keyed by component name. Each override replaces the
+/// promoted source for its path (or adds it, if the promoted component doesn't set that path).
+pub type PromotionOverrides = BTreeMap<String, AttributeSources>;
+
+/// What promoting a single component changed in the target workspace.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromotedComponent {
+    pub component_id: ComponentId,
+    pub name: String,
+    /// Whether the component did not exist in the target workspace yet.
+    pub created: bool,
+    /// Attribute paths whose value or source was set.
+    pub updated_paths: Vec<String>,
+    /// Attribute paths that were unset (or removed, for map and array elements).
+    pub removed_paths: Vec<String>,
+    /// Whether the component's labels or owner were changed.
+    pub metadata_updated: bool,
+}
+
+impl PromotedComponent {
+    /// True if the target component already matched the promoted one.
+    pub fn is_unchanged(&self) -> bool {
+        !self.created
+            && self.updated_paths.is_empty()
+            && self.removed_paths.is_empty()
+            && !self.metadata_updated
+    }
+}
+
+/// The result of [`promote_components`], in the order the components were given.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromotionReport {
+    pub components: Vec<PromotedComponent>,
+}
+
+/// Exports the given components as specs that can be promoted into another workspace with
+/// [`promote_components`].
+///
+/// Subscriptions are kept, with the components they subscribe to identified by name. Those
+/// components must either be promoted alongside these or already exist in the target workspace.
+pub async fn export_components(
+    ctx: &DalContext,
+    component_ids: &[ComponentId],
+) -> PromotionResult<Vec<ComponentSpec>> {
+    let default_view_id = View::get_id_for_default(ctx).await?;
+
+    let mut specs = Vec::with_capacity(component_ids.len());
+    for &component_id in component_ids {
+        let component = Component::get_by_id(ctx, component_id).await?;
+        if component.to_delete() {
+            return Err(PromotionError::ComponentMarkedForDeletion(component_id));
+        }
+        let name = component.name(ctx).await?;
+        let variant = component.schema_variant(ctx).await?;
+        let schema = variant.schema(ctx).await?;
+
+        let position =
+            match Geometry::try_get_by_component_and_view(ctx, component_id, default_view_id)
+                .await?
+            {
+                Some(geometry) => PositionSpec::builder()
+                    .x(geometry.x().to_string())
+                    .y(geometry.y().to_string())
+                    .width(geometry.width().map(|width| width.to_string()))
+                    .height(geometry.height().map(|height| height.to_string()))
+                    .build()?,
+                None => PositionSpec::builder()
+                    .x("0")
+                    .y("0")
+                    .width(None::<String>)
+                    .height(None::<String>)
+                    .build()?,
+            };
+
+        let mut sources = Vec::new();
+        for (path, source) in Component::sources(ctx, component_id).await? {
+            let source = portable_source(ctx, path.path(), source).await?;
+            sources.push((path, source));
+        }
+        let sources = AttributeSources::from(sources).validate_user_can_set_directly();
+
+        let mut builder = ComponentSpec::builder();
+        builder
+            .name(name)
+            .position(position)
+            .variant(ComponentSpecVariant::BuiltinVariant {
+                schema_name: schema.name().to_owned(),
+                variant_name: variant.version().to_owned(),
+            })
+            .needs_destroy(false)
+            .deletion_user_pk(None::<String>)
+            .unique_id(component_id.to_string())
+            .deleted(false)
+            .sources(serde_json::to_value(sources)?)
+            .labels(component.labels().clone());
+        if let Some(owner) = component.owner() {
+            builder.owner(owner);
+        }
+        specs.push(builder.build()?);
+    }
+
+    Ok(specs)
+}
+
+/// Promotes components exported with [`export_components`] into the workspace `ctx` points at
+/// (typically a freshly forked change set).
+///
+/// Each spec is matched to the component that was promoted from the same source component (its
+/// `unique_id`), or by name if `matching` allows it, and must have the same schema. If there is no
+/// such component, it is created with the default variant of that schema. Attributes that already
+/// match are left alone; everything else is set to the promoted source (or the override for that
+/// path), and attributes the promoted component doesn't set are unset.
+pub async fn promote_components(
+    ctx: &DalContext,
+    components: &[ComponentSpec],
+    mut overrides: PromotionOverrides,
+    matching: PromotionMatching,
+) -> PromotionResult<PromotionReport> {
+    if let Some(name) = overrides
+        .keys()
+        .find(|name| !components.iter().any(|spec| &spec.name == *name))
+    {
+        return Err(PromotionError::OverrideForUnknownComponent(name.to_owned()));
+    }
+
+    let mut promoted_from = HashMap::new();
+    for component in Component::list(ctx).await? {
+        if let Some(source_id) = component.promoted_from() {
+            promoted_from.insert(source_id.to_owned(), component.id());
+        }
+    }
+
+    // Find or create every component first, so subscriptions between promoted components
+    // resolve no matter what order they come in.
+    let mut targets = Vec::with_capacity(components.len());
+    for spec in components {
+        let schema_name = match &spec.variant {
+            ComponentSpecVariant::BuiltinVariant { schema_name, .. }
+            | ComponentSpecVariant::UpdateVariant { schema_name, .. } => schema_name,
+            ComponentSpecVariant::WorkspaceVariant { .. } => {
+                return Err(PromotionError::UnsupportedVariant(spec.name.to_owned()));
+            }
+        };
+
+        let existing = match promoted_from.get(&spec.unique_id) {
+            Some(&component_id) => Some(component_id),
+            None => match Component::find_by_name(ctx, &spec.name).await? {
+                Some(component_id) => {
+                    let component = Component::get_by_id(ctx, component_id).await?;
+                    if matching != PromotionMatching::SourceIdThenName
+                        || component.promoted_from().is_some()
+                    {
+                        return Err(PromotionError::ComponentNameInUse(spec.name.to_owned()));
+                    }
+                    component
+                        .set_promoted_from(ctx, Some(spec.unique_id.to_owned()))
+                        .await?;
+                    Some(component_id)
+                }
+                None => None,
+            },
+        };
+
+        let target = match existing {
+            Some(component_id) => {
+                let existing_schema = Component::schema_for_component_id(ctx, component_id).await?;
+                if !existing_schema.name().eq_ignore_ascii_case(schema_name) {
+                    return Err(PromotionError::ComponentSchemaMismatch {
+                        name: spec.name.to_owned(),
+                        schema: schema_name.to_owned(),
+                        existing_schema: existing_schema.name().to_owned(),
+                    });
+                }
+                (component_id, false)
+            }
+            None => (create_component(ctx, spec, schema_name).await?, true),
+        };
+        targets.push(target);
+    }
+
+    let mut report = PromotionReport::default();
+    for (spec, (component_id, created)) in components.iter().zip(targets) {
+        let mut desired: Vec<(AttributeValueIdent, ValueOrSourceSpec)> = match &spec.sources {
+            Some(sources) => {
+                let sources: AttributeSources = serde_json::from_value(sources.to_owned())
+                    .map_err(|err| PromotionError::InvalidSources(spec.name.to_owned(), err))?;
+                sources.0
+            }
+            None => vec![],
+        };
+        for (path, value) in overrides.remove(&spec.name).unwrap_or_default().0 {
+            match desired
+                .iter_mut()
+                .find(|(desired_path, _)| *desired_path == path)
+            {
+                Some(entry) => entry.1 = value,
+                None => desired.push((path, value)),
+            }
+        }
+        let desired = portable_sources(ctx, desired).await?;
+
+        let current = AttributeSources::from(Component::sources(ctx, component_id).await?)
+            .validate_user_can_set_directly();
+        let current = portable_sources(ctx, current.0).await?;
+
+        // Unset whatever the promoted component doesn't set, last first so array indices stay
+        // valid, and skipping anything whose parent is being removed anyway.
+        let mut removed_paths: Vec<String> = current
+            .iter()
+            .filter(|(path, _)| {
+                desired
+                    .iter()
+                    .all(|(desired_path, source)| desired_path != path || source.is_none())
+            })
+            .map(|(path, _)| path.to_owned())
+            .collect();
+        removed_paths = removed_paths
+            .iter()
+            .rev()
+            .filter(|path| {
+                !removed_paths
+                    .iter()
+                    .any(|other| path.starts_with(&format!("{other}/")))
+            })
+            .cloned()
+            .collect();
+
+        let mut updated_paths = Vec::new();
+        let mut updates: Vec<(AttributeValueIdent, ValueOrSourceSpec)> = removed_paths
+            .iter()
+            .map(|path| (AttributeValueIdent::new(path), ValueOrSourceSpec::unset()))
+            .collect();
+        for (path, source) in desired {
+            let Some(source) = source else {
+                continue;
+            };
+            let unchanged = current.iter().any(|(current_path, current)| {
+                *current_path == path && current.as_ref() == Some(&source)
+            });
+            if !unchanged {
+                updates.push((AttributeValueIdent::new(&path), source.into()));
+                updated_paths.push(path);
+            }
+        }
+
+        if !updates.is_empty() {
+            attributes::update_attributes(ctx, component_id, AttributeSources::from(updates))
+                .await?;
+        }
+
+        let component = Component::get_by_id(ctx, component_id).await?;
+        let metadata_updated =
+            component.labels() != &spec.labels || component.owner() != spec.owner.as_deref();
+        if metadata_updated {
+            component
+                .set_labels(ctx, spec.labels.to_owned())
+                .await?
+                .set_owner(ctx, spec.owner.to_owned())
+                .await?;
+        }
+
+        report.components.push(PromotedComponent {
+            component_id,
+            name: spec.name.to_owned(),
+            created,
+            updated_paths,
+            removed_paths,
+            metadata_updated,
+        });
+    }
+
+    Ok(report)
+}
+
+async fn create_component(
+    ctx: &DalContext,
+    spec: &ComponentSpec,
+    schema_name: &str,
+) -> PromotionResult<ComponentId> {
+    let schema = Schema::get_or_install_by_name(ctx, schema_name).await?;
+    let variant_id = Schema::get_or_install_default_variant(ctx, schema.id()).await?;
+    let view_id = View::get_id_for_default(ctx).await?;
+    let mut component = Component::new(ctx, spec.name.to_owned(), variant_id, view_id)
+        .await?
+        .set_promoted_from(ctx, Some(spec.unique_id.to_owned()))
+        .await?;
+
+    // Keep the component where it was in the source workspace, if we can make sense of it
+    if let (Ok(x), Ok(y)) = (spec.position.x.parse(), spec.position.y.parse()) {
+        let width = spec.position.width.as_deref().and_then(|w| w.parse().ok());
+        let height = spec.position.height.as_deref().and_then(|h| h.parse().ok());
+        component
+            .set_geometry(ctx, view_id, x, y, width, height)
+            .await?;
+    }
+
+    Ok(component.id())
+}
+
+/// Resolves each (path, source) pair to a portable [`Source`] (or `None` if it unsets the path).
+async fn portable_sources(
+    ctx: &DalContext,
+    sources: Vec<(AttributeValueIdent, ValueOrSourceSpec)>,
+) -> PromotionResult<Vec<(String, Option<Source>)>> {
+    let mut portable = Vec::with_capacity(sources.len());
+    for (path, value) in sources {
+        let path = String::from(path);
+        let source = match Option::<Source>::try_from(value)? {
+            Some(source) => Some(portable_source(ctx, &path, source).await?),
+            None => None,
+        };
+        portable.push((path, source));
+    }
+    Ok(portable)
+}
+
+/// Replaces the ids of any components, variables and functions a source refers to with their
+/// names. References that can't be resolved in this workspace are left as they are.
+async fn portable_source(ctx: &DalContext, path: &str, source: Source) -> PromotionResult<Source> {
+    Ok(match source {
+        Source::Value(_) => source,
+        Source::Variable(variable) => match variable.resolve(ctx).await? {
+            Some(variable) => Source::Variable(VariableIdent::from(variable.name().to_owned())),
+            None => Source::Variable(variable),
+        },
+        Source::Template { template, bindings } => {
+            let mut portable_bindings = BTreeMap::new();
+            for (name, binding) in bindings {
+                portable_bindings.insert(
+                    name,
+                    TemplateBinding {
+                        component: portable_component(ctx, binding.component).await?,
+                        path: binding.path,
+                    },
+                );
+            }
+            Source::Template {
+                template,
+                bindings: portable_bindings,
+            }
+        }
+        Source::Subscription {
+            component,
+            path: source_path,
+            func,
+            _keep_existing_subscriptions: _,
+        } => {
+            let func = match func {
+                Some(func) => Some(portable_func(ctx, path, func).await?),
+                None => None,
+            };
+            Source::Subscription {
+                component: portable_component(ctx, component).await?,
+                path: source_path,
+                func,
+                _keep_existing_subscriptions: None,
+            }
+        }
+    })
+}
+
+async fn portable_component(
+    ctx: &DalContext,
+    component: ComponentIdent,
+) -> PromotionResult<ComponentIdent> {
+    Ok(match component.resolve(ctx).await? {
+        Some(component_id) => Component::name_by_id(ctx, component_id).await?.into(),
+        None => component,
+    })
+}
+
+async fn portable_func(
+    ctx: &DalContext,
+    path: &str,
+    func: FuncIdent,
+) -> PromotionResult<FuncIdent> {
+    let Some(func_id) = func.resolve(ctx).await? else {
+        return Ok(func);
+    };
+    match Func::intrinsic_kind(ctx, func_id).await? {
+        Some(intrinsic) => Ok(intrinsic.name().to_owned().into()),
+        None => Err(PromotionError::UnsupportedSubscriptionFunc {
+            path: path.to_owned(),
+            func_id,
+        }),
+    }
+}
+
+impl From<AttributesError> for PromotionError {
+    fn from(value: AttributesError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<ComponentError> for PromotionError {
+    fn from(value: ComponentError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<DiagramError> for PromotionError {
+    fn from(value: DiagramError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<FuncError> for PromotionError {
+    fn from(value: FuncError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<SchemaError> for PromotionError {
+    fn from(value: SchemaError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl From<SchemaVariantError> for PromotionError {
+    fn from(value: SchemaVariantError) -> Self {
+        Box::new(value).into()
+    }
+}
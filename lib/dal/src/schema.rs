@@ -62,6 +62,7 @@ use crate::{
         ImportOptions,
         PkgError,
         import_pkg_from_pkg,
+        verify_module_signature,
     },
     workspace_snapshot::{
         WorkspaceSnapshotError,
@@ -683,6 +684,7 @@ impl Schema {
         mut module: CachedModule,
     ) -> SchemaResult<Schema> {
         let si_pkg = module.si_pkg(ctx).await?;
+        verify_module_signature(ctx, &si_pkg, module.signature.as_ref()).await?;
         import_pkg_from_pkg(
             ctx,
             &si_pkg,
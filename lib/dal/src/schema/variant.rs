@@ -1940,6 +1940,7 @@ impl SchemaVariant {
                     | IntrinsicFunc::SetMap
                     | IntrinsicFunc::SetObject
                     | IntrinsicFunc::SetString
+                    | IntrinsicFunc::StringTemplate
                     | IntrinsicFunc::Validation => {} //not returning these at the moment!
                 },
             }
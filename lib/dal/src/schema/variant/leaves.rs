@@ -44,7 +44,10 @@ impl SchemaVariant {
         let func = Func::get_by_id(ctx, func_id).await?;
 
         // Ensure the func matches what we need.
-        if func.backend_kind != FuncBackendKind::JsAttribute {
+        if !matches!(
+            func.backend_kind,
+            FuncBackendKind::JsAttribute | FuncBackendKind::WasmAttribute
+        ) {
             return Err(SchemaVariantError::LeafFunctionMustBeJsAttribute(func.id));
         }
         if func.backend_response_type != leaf_kind.into() {
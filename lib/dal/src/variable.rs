@@ -0,0 +1,456 @@
+//! This module contains [`Variable`], a named value stored in the change set that attribute values
+//! can use as their source (e.g. `{ "$source": { "variable": "prod.region" } }`).
+//!
+//! Variables are content nodes hanging off of the variable category node. Attribute prototype
+//! arguments point at them with a [`PrototypeArgumentValue`](EdgeWeightKind::PrototypeArgumentValue)
+//! edge, just like secrets and static values, so changing a variable enqueues every attribute value
+//! using it for a dependent values update.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
+
+use chrono::Utc;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_events::{
+    ContentHash,
+    Timestamp,
+    ulid::Ulid,
+};
+pub use si_id::VariableId;
+use si_layer_cache::LayerDbError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    AttributePrototype,
+    AttributeValueId,
+    ComponentId,
+    DalContext,
+    EdgeWeightKind,
+    EdgeWeightKindDiscriminants,
+    HelperError,
+    TransactionsError,
+    WorkspaceSnapshotError,
+    attribute::{
+        prototype::{
+            AttributePrototypeError,
+            argument::{
+                AttributePrototypeArgument,
+                AttributePrototypeArgumentError,
+            },
+        },
+        value::{
+            AttributeValue,
+            AttributeValueError,
+        },
+    },
+    implement_add_edge_to,
+    layer_db_types::{
+        VariableContent,
+        VariableContentV1,
+    },
+    workspace_snapshot::{
+        content_address::{
+            ContentAddress,
+            ContentAddressDiscriminants,
+        },
+        dependent_value_root::DependentValueRootError,
+        node_weight::{
+            ContentNodeWeight,
+            NodeWeight,
+            NodeWeightError,
+            category_node_weight::CategoryNodeKind,
+        },
+    },
+};
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum VariableError {
+    #[error("attribute prototype error: {0}")]
+    AttributePrototype(#[from] Box<AttributePrototypeError>),
+    #[error("attribute prototype argument error: {0}")]
+    AttributePrototypeArgument(#[from] Box<AttributePrototypeArgumentError>),
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] Box<AttributeValueError>),
+    #[error("dependent value root error: {0}")]
+    DependentValueRoot(#[from] DependentValueRootError),
+    #[error("helper error: {0}")]
+    Helper(#[from] HelperError),
+    #[error(
+        "invalid variable name {0:?}: names must be non-empty and only contain letters, digits, '_', '-' and '.'"
+    )]
+    InvalidName(String),
+    #[error("layer db error: {0}")]
+    LayerDb(#[from] LayerDbError),
+    #[error("variable name already in use: {0}")]
+    NameAlreadyInUse(String),
+    #[error("node weight error: {0}")]
+    NodeWeight(#[from] NodeWeightError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("variable {0} is used by {1} attribute prototype argument(s) and cannot be removed")]
+    VariableInUse(VariableId, usize),
+    #[error("variable not found: {0}")]
+    VariableNotFound(VariableId),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+#[allow(missing_docs)]
+pub type VariableResult<T> = Result<T, VariableError>;
+
+/// A named JSON value stored in the change set, which attribute values can be set to.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    id: VariableId,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    name: String,
+    value: serde_json::Value,
+    description: Option<String>,
+}
+
+impl From<Variable> for VariableContentV1 {
+    fn from(value: Variable) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            name: value.name,
+            value: value.value.into(),
+            description: value.description,
+        }
+    }
+}
+
+impl Variable {
+    fn assemble(id: VariableId, content: VariableContentV1) -> Self {
+        Self {
+            id,
+            timestamp: content.timestamp,
+            name: content.name,
+            value: content.value.into(),
+            description: content.description,
+        }
+    }
+
+    implement_add_edge_to!(
+        source_id: Ulid,
+        destination_id: VariableId,
+        add_fn: add_category_edge,
+        discriminant: EdgeWeightKindDiscriminants::Use,
+        result: VariableResult,
+    );
+
+    pub fn id(&self) -> VariableId {
+        self.id
+    }
+
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &serde_json::Value {
+        &self.value
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Creates a new [`Variable`]. Names must be unique within the change set.
+    pub async fn new(
+        ctx: &DalContext,
+        name: impl Into<String>,
+        value: serde_json::Value,
+        description: Option<String>,
+    ) -> VariableResult<Self> {
+        let name = name.into();
+        Self::validate_name(&name)?;
+        if Self::find_by_name(ctx, &name).await?.is_some() {
+            return Err(VariableError::NameAlreadyInUse(name));
+        }
+
+        let content = VariableContentV1 {
+            timestamp: Timestamp::now(),
+            name,
+            value: value.into(),
+            description,
+        };
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(VariableContent::V1(content.clone()).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let id = workspace_snapshot.generate_ulid().await?;
+        let lineage_id = workspace_snapshot.generate_ulid().await?;
+        workspace_snapshot
+            .add_or_replace_node(NodeWeight::new_content(
+                id,
+                lineage_id,
+                ContentAddress::Variable(hash),
+            ))
+            .await?;
+
+        // Root --> Variable Category --> Variable (this)
+        let category_id = workspace_snapshot
+            .get_or_create_static_category_node(CategoryNodeKind::Variable)
+            .await?;
+        Self::add_category_edge(ctx, category_id, id.into(), EdgeWeightKind::new_use()).await?;
+
+        Ok(Self::assemble(id.into(), content))
+    }
+
+    pub async fn get_by_id(ctx: &DalContext, id: VariableId) -> VariableResult<Self> {
+        let node_weight = Self::node_weight(ctx, id).await?;
+        let content = Self::get_content(ctx, id, node_weight.content_hash()).await?;
+        Ok(Self::assemble(id, content))
+    }
+
+    pub async fn find_by_name(ctx: &DalContext, name: &str) -> VariableResult<Option<Self>> {
+        Ok(Self::list(ctx)
+            .await?
+            .into_iter()
+            .find(|variable| variable.name == name))
+    }
+
+    /// Lists all [`Variables`](Variable) in the change set, sorted by name.
+    #[instrument(name = "variable.list", level = "debug", skip_all)]
+    pub async fn list(ctx: &DalContext) -> VariableResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        // The category is created on demand, so there may be no variables at all yet
+        let Some(category_id) = workspace_snapshot
+            .get_category_node(CategoryNodeKind::Variable)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut node_weights = vec![];
+        for id in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind(category_id, EdgeWeightKindDiscriminants::Use)
+            .await?
+        {
+            node_weights.push(
+                workspace_snapshot
+                    .get_node_weight(id)
+                    .await?
+                    .get_content_node_weight_of_kind(ContentAddressDiscriminants::Variable)?,
+            );
+        }
+
+        let hashes: Vec<ContentHash> = node_weights.iter().map(|w| w.content_hash()).collect();
+        let contents: HashMap<ContentHash, VariableContent> = ctx
+            .layer_db()
+            .cas()
+            .try_read_many_as(hashes.as_slice())
+            .await?;
+
+        let mut variables = Vec::with_capacity(node_weights.len());
+        for node_weight in node_weights {
+            let content = contents.get(&node_weight.content_hash()).cloned().ok_or(
+                WorkspaceSnapshotError::MissingContentFromStore(node_weight.id()),
+            )?;
+            variables.push(Self::assemble(node_weight.id().into(), content.extract()));
+        }
+        variables.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(variables)
+    }
+
+    /// Renames the [`Variable`] and updates its description. Attribute values reference the
+    /// variable by id, so they do not need to be updated.
+    pub async fn update_metadata(
+        self,
+        ctx: &DalContext,
+        name: impl Into<String>,
+        description: Option<String>,
+    ) -> VariableResult<Self> {
+        let name = name.into();
+        if name != self.name {
+            Self::validate_name(&name)?;
+            if Self::find_by_name(ctx, &name).await?.is_some() {
+                return Err(VariableError::NameAlreadyInUse(name));
+            }
+        }
+
+        self.modify(ctx, |variable| {
+            variable.name = name;
+            variable.description = description;
+        })
+        .await
+    }
+
+    /// Sets the value of the [`Variable`] and enqueues every attribute value using it for a
+    /// dependent values update.
+    pub async fn set_value(
+        self,
+        ctx: &DalContext,
+        value: serde_json::Value,
+    ) -> VariableResult<Self> {
+        if value == self.value {
+            return Ok(self);
+        }
+
+        let variable = self
+            .modify(ctx, |variable| {
+                variable.value = value;
+            })
+            .await?;
+
+        ctx.add_dependent_values_and_enqueue(vec![variable.id])
+            .await?;
+
+        Ok(variable)
+    }
+
+    /// Removes the [`Variable`]. Fails if any attribute value is still using it as its source.
+    pub async fn remove(ctx: &DalContext, id: VariableId) -> VariableResult<()> {
+        let users = AttributePrototypeArgument::list_references_to_value_source(ctx, id)
+            .await
+            .map_err(Box::new)?;
+        if !users.is_empty() {
+            return Err(VariableError::VariableInUse(id, users.len()));
+        }
+
+        ctx.workspace_snapshot()?.remove_node_by_id(id).await?;
+
+        Ok(())
+    }
+
+    /// Find all [`AttributeValues`](AttributeValue) that _directly_ use the [`Variable`] as their
+    /// source.
+    pub async fn direct_dependent_attribute_values(
+        ctx: &DalContext,
+        id: VariableId,
+    ) -> VariableResult<Vec<AttributeValueId>> {
+        let mut attribute_value_ids = vec![];
+        for apa_id in AttributePrototypeArgument::list_references_to_value_source(ctx, id)
+            .await
+            .map_err(Box::new)?
+        {
+            let prototype_id = AttributePrototypeArgument::prototype_id(ctx, apa_id)
+                .await
+                .map_err(Box::new)?;
+            attribute_value_ids.extend(
+                AttributePrototype::attribute_value_ids(ctx, prototype_id)
+                    .await
+                    .map_err(Box::new)?,
+            );
+        }
+
+        Ok(attribute_value_ids)
+    }
+
+    /// Find all [`Components`](crate::Component) with an attribute value using the [`Variable`].
+    pub async fn list_dependent_component_ids(
+        ctx: &DalContext,
+        id: VariableId,
+    ) -> VariableResult<Vec<ComponentId>> {
+        let mut component_ids = vec![];
+        for attribute_value_id in Self::direct_dependent_attribute_values(ctx, id).await? {
+            let component_id = AttributeValue::component_id(ctx, attribute_value_id)
+                .await
+                .map_err(Box::new)?;
+            if !component_ids.contains(&component_id) {
+                component_ids.push(component_id);
+            }
+        }
+
+        Ok(component_ids)
+    }
+
+    /// Get the name of the variable, formatted for debugging/display.
+    pub async fn fmt_title(ctx: &DalContext, id: VariableId) -> String {
+        match Self::get_by_id(ctx, id).await {
+            Ok(variable) => format!("variable {} ({})", variable.name, id),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    fn validate_name(name: &str) -> VariableResult<()> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if !valid {
+            return Err(VariableError::InvalidName(name.to_owned()));
+        }
+        Ok(())
+    }
+
+    async fn node_weight(ctx: &DalContext, id: VariableId) -> VariableResult<ContentNodeWeight> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        if !workspace_snapshot.node_exists(id).await {
+            return Err(VariableError::VariableNotFound(id));
+        }
+
+        Ok(workspace_snapshot
+            .get_node_weight(id)
+            .await?
+            .get_content_node_weight_of_kind(ContentAddressDiscriminants::Variable)?)
+    }
+
+    async fn get_content(
+        ctx: &DalContext,
+        id: VariableId,
+        hash: ContentHash,
+    ) -> VariableResult<VariableContentV1> {
+        let content: VariableContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&hash)
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(id.into()))?;
+
+        // If we had a v2, then there would be migration logic here.
+        Ok(content.extract())
+    }
+
+    async fn modify<L>(self, ctx: &DalContext, lambda: L) -> VariableResult<Self>
+    where
+        L: FnOnce(&mut Self),
+    {
+        let mut variable = self;
+
+        let before = VariableContentV1::from(variable.clone());
+        lambda(&mut variable);
+        if before == VariableContentV1::from(variable.clone()) {
+            return Ok(variable);
+        }
+        variable.timestamp.updated_at = Utc::now();
+
+        let updated = VariableContentV1::from(variable.clone());
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(VariableContent::V1(updated).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+        ctx.workspace_snapshot()?
+            .update_content(variable.id.into(), hash)
+            .await?;
+
+        Ok(variable)
+    }
+}
+
+impl From<AttributeValueError> for VariableError {
+    fn from(value: AttributeValueError) -> Self {
+        Box::new(value).into()
+    }
+}
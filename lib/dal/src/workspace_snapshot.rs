@@ -117,7 +117,10 @@ pub mod update;
 
 pub use dependent_value_root::DependentValueRoot;
 pub use petgraph::Direction;
-pub use selector::WorkspaceSnapshotSelector;
+pub use selector::{
+    WorkspaceSnapshotSelector,
+    WorkspaceSnapshotSelectorCheckpoint,
+};
 pub use si_id::WorkspaceSnapshotNodeId as NodeId;
 pub use traits::{
     entity_kind::EntityKindExt,
@@ -219,6 +222,8 @@ pub enum WorkspaceSnapshotError {
     SplitSnapshotSubGraphMissingAtAddress(WorkspaceSnapshotAddress),
     #[error("split snapshot supergraph missing at address: {0}")]
     SplitSnapshotSuperGraphMissingAtAddress(WorkspaceSnapshotAddress),
+    #[error("{0} is not supported for split snapshots")]
+    SplitSnapshotUnsupported(&'static str),
     #[error("Too many edges of kind {1} found with node id {0:?} as the source")]
     TooManyEdgesOfKind(Ulid, EdgeWeightKindDiscriminants),
     #[error("transactions error: {0}")]
@@ -310,6 +315,15 @@ pub struct WorkspaceSnapshot {
     prop_suggestions: Arc<PropSuggestionsCache>,
 }
 
+/// The uncommitted changes to a [`WorkspaceSnapshot`] at some point in an edit session, taken
+/// with [`WorkspaceSnapshot::checkpoint`] so that the changes made after it can be undone with
+/// [`WorkspaceSnapshot::restore`].
+#[derive(Debug, Clone)]
+pub struct WorkspaceSnapshotCheckpoint {
+    working_copy: Option<WorkspaceSnapshotGraphVCurrent>,
+    dvu_roots: HashSet<DependentValueRoot>,
+}
+
 /// A pretty dumb attempt to make enabling the cycle check more ergonomic. This
 /// will reset the cycle check to false on drop, if nothing else is holding onto
 /// the cycle check besides the guard being dropped and the workspace snapshot.
@@ -636,6 +650,22 @@ impl WorkspaceSnapshot {
         }
     }
 
+    /// Take a copy of the changes made to the working copy so far. This clones the working
+    /// copy, so it isn't free for large graphs.
+    pub async fn checkpoint(&self) -> WorkspaceSnapshotCheckpoint {
+        WorkspaceSnapshotCheckpoint {
+            working_copy: self.working_copy.read().await.clone(),
+            dvu_roots: self.dvu_roots.lock().await.clone(),
+        }
+    }
+
+    /// Discard all changes made to the working copy since the checkpoint was taken
+    pub async fn restore(&self, checkpoint: WorkspaceSnapshotCheckpoint) {
+        *self.working_copy.write().await = checkpoint.working_copy;
+        *self.dvu_roots.lock().await = checkpoint.dvu_roots;
+        self.clear_prop_suggestions_cache();
+    }
+
     pub async fn serialized(&self) -> WorkspaceSnapshotResult<Vec<u8>> {
         let graph = self.working_copy().await.clone();
         Ok(si_layer_cache::db::serialize::to_vec(&WorkspaceSnapshotGraph::V4(graph))?.0)
@@ -51,6 +51,7 @@ pub enum ContentAddress {
     View(ContentHash),
     ApprovalRequirementDefinition(ContentHash),
     AttributePaths(ContentHash),
+    Variable(ContentHash),
 }
 
 impl ContentAddress {
@@ -80,7 +81,8 @@ impl ContentAddress {
             | ContentAddress::View(id)
             | ContentAddress::ManagementPrototype(id)
             | ContentAddress::ApprovalRequirementDefinition(id)
-            | ContentAddress::AttributePaths(id) => Some(*id),
+            | ContentAddress::AttributePaths(id)
+            | ContentAddress::Variable(id) => Some(*id),
         }
         .unwrap_or_default()
     }
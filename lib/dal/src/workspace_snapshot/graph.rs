@@ -50,6 +50,7 @@ use crate::{
 
 pub mod correct_transforms;
 pub mod detector;
+pub mod repair;
 mod tests;
 pub mod traits;
 pub mod v4;
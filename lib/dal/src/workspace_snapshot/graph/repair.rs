@@ -0,0 +1,413 @@
+//! Automated corrections for the issues found by the [graph validator](super::validator).
+//!
+//! Repairs operate directly on a [`WorkspaceSnapshotGraphVCurrent`] so they can be run against a
+//! snapshot downloaded to disk as well as the one belonging to a change set. They only touch the
+//! graph: no dependent values are enqueued, so recreated attribute values start out empty and
+//! pick up their values the next time the dependent values update runs over them.
+
+use std::collections::HashSet;
+
+use petgraph::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_id::{
+    AttributePrototypeArgumentId,
+    AttributeValueId,
+    PropId,
+    ulid::Ulid,
+};
+
+use crate::{
+    prop::PropKind,
+    workspace_snapshot::{
+        edge_weight::{
+            EdgeWeight,
+            EdgeWeightKind,
+            EdgeWeightKindDiscriminants,
+        },
+        graph::{
+            WorkspaceSnapshotGraphResult,
+            WorkspaceSnapshotGraphVCurrent,
+            validator::{
+                ValidationIssue,
+                validate_graph,
+                validate_graph_with_text,
+            },
+        },
+        node_weight::{
+            NodeWeight,
+            OrderingNodeWeight,
+        },
+    },
+};
+
+/// A single correction made to the graph while repairing a validation issue
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Repair {
+    /// An attribute value was created for a child prop of an object that was missing one
+    CreatedAttributeValue {
+        parent: AttributeValueId,
+        prop: PropId,
+        attribute_value: AttributeValueId,
+    },
+    /// An ordering node was created for a container that was missing one
+    CreatedOrderingNode { container: Ulid, ordering: Ulid },
+    /// An attribute value was removed, along with any arguments subscribing to it
+    RemovedAttributeValue {
+        attribute_value: AttributeValueId,
+        removed_subscribers: Vec<AttributePrototypeArgumentId>,
+    },
+    /// An ordering node was brought back in line with its container's children
+    UpdatedOrdering {
+        ordering: Ulid,
+        added: Vec<Ulid>,
+        removed: Vec<Ulid>,
+    },
+}
+
+/// The outcome of repairing a single validation issue
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairReport {
+    pub issue: ValidationIssue,
+    pub message: String,
+    /// The corrections made for this issue. Empty if there is no safe correction, or if an
+    /// earlier repair already took care of it.
+    pub repairs: Vec<Repair>,
+    /// Whether the issue is gone once every repair has been made
+    pub resolved: bool,
+}
+
+/// Validate the graph and repair every issue that has a safe correction.
+///
+/// Orphaned nodes are cleaned up and the merkle tree hash is recalculated afterwards, so the graph
+/// is ready to be persisted. Run this against a clone of the graph for a dry run.
+pub fn repair_graph(
+    graph: &mut WorkspaceSnapshotGraphVCurrent,
+) -> WorkspaceSnapshotGraphResult<Vec<RepairReport>> {
+    let issues = {
+        let graph: &WorkspaceSnapshotGraphVCurrent = graph;
+        validate_graph_with_text(&graph)?
+    };
+
+    let mut reports = Vec::with_capacity(issues.len());
+    for (issue, message) in issues {
+        let repairs = issue.repair(graph)?;
+        reports.push(RepairReport {
+            issue,
+            message,
+            repairs,
+            resolved: false,
+        });
+    }
+
+    graph.cleanup_and_merkle_tree_hash()?;
+
+    let remaining = {
+        let graph: &WorkspaceSnapshotGraphVCurrent = graph;
+        validate_graph(&graph)?
+    };
+    for report in &mut reports {
+        report.resolved = !remaining.contains(&report.issue);
+    }
+
+    Ok(reports)
+}
+
+impl ValidationIssue {
+    /// Make the corrections for this issue, returning what was changed.
+    ///
+    /// Issues without a safe correction (subscription cycles, and arguments with a missing or
+    /// ambiguous value) are left alone. Nodes that no longer exist, e.g. because an earlier repair
+    /// removed them, are skipped.
+    pub fn repair(
+        &self,
+        graph: &mut WorkspaceSnapshotGraphVCurrent,
+    ) -> WorkspaceSnapshotGraphResult<Vec<Repair>> {
+        match self {
+            ValidationIssue::ChildOrderingMismatch { node, .. }
+            | ValidationIssue::MissingOrderingNode { node } => {
+                match graph.get_node_index_by_id_opt(*node) {
+                    Some(container) => repair_ordering(graph, container),
+                    None => Ok(vec![]),
+                }
+            }
+            ValidationIssue::CyclicSubscriptions
+            | ValidationIssue::MissingValue { .. }
+            | ValidationIssue::MultipleValues { .. } => Ok(vec![]),
+            ValidationIssue::DuplicateAttributeValue { duplicate, .. }
+            | ValidationIssue::DuplicateAttributeValueWithDifferentValues { duplicate, .. } => {
+                remove_attribute_value(graph, *duplicate)
+            }
+            ValidationIssue::MissingChildAttributeValues {
+                object,
+                missing_children,
+            } => create_missing_child_attribute_values(graph, *object, missing_children),
+            ValidationIssue::OrderingDuplicateEntry { ordering, .. }
+            | ValidationIssue::OrderingNodeMismatch { ordering, .. } => {
+                let Some(ordering) = graph.get_node_index_by_id_opt(*ordering) else {
+                    return Ok(vec![]);
+                };
+                match graph.source_opt(ordering, EdgeWeightKindDiscriminants::Ordering)? {
+                    Some(container) => repair_ordering(graph, container),
+                    None => Ok(vec![]),
+                }
+            }
+            ValidationIssue::UnknownChildAttributeValue { child } => {
+                remove_attribute_value(graph, *child)
+            }
+        }
+    }
+}
+
+/// The kind of edge a container uses to point at the children its ordering node orders
+fn child_edge_kind(node: &NodeWeight) -> Option<EdgeWeightKindDiscriminants> {
+    match node {
+        NodeWeight::AttributeValue(_) => Some(EdgeWeightKindDiscriminants::Contain),
+        NodeWeight::Prop(_) => Some(EdgeWeightKindDiscriminants::Use),
+        _ => None,
+    }
+}
+
+/// Make a container's ordering node order exactly its children, creating the ordering node if it
+/// is missing.
+///
+/// Entries already in the order keep their position; duplicates and entries that aren't children
+/// are dropped, and children missing from the order are appended. `Ordinal` edges are added and
+/// removed to match. For containers whose children can't be determined, the `Ordinal` edges are
+/// treated as the source of truth.
+fn repair_ordering(
+    graph: &mut WorkspaceSnapshotGraphVCurrent,
+    container: NodeIndex,
+) -> WorkspaceSnapshotGraphResult<Vec<Repair>> {
+    let mut repairs = vec![];
+    let container_weight = graph.get_node_weight(container)?;
+    let container_id = container_weight.id();
+    let child_kind = child_edge_kind(container_weight);
+
+    let ordering = match graph.ordering_node_index_for_container(container)? {
+        Some(ordering) => ordering,
+        None => {
+            if child_kind.is_none() {
+                return Ok(repairs);
+            }
+            let ordering_id = graph.generate_ulid()?;
+            let lineage_id = graph.generate_ulid()?;
+            let ordering = graph.add_or_replace_node(NodeWeight::Ordering(
+                OrderingNodeWeight::new(ordering_id, lineage_id),
+            ))?;
+            graph.add_edge(
+                container,
+                EdgeWeight::new(EdgeWeightKind::Ordering),
+                ordering,
+            )?;
+            repairs.push(Repair::CreatedOrderingNode {
+                container: container_id,
+                ordering: ordering_id,
+            });
+            ordering
+        }
+    };
+
+    let ordering_weight = graph
+        .get_node_weight(ordering)?
+        .get_ordering_node_weight()?;
+    let ordinal_targets: Vec<NodeIndex> = graph
+        .targets(ordering, EdgeWeightKindDiscriminants::Ordinal)
+        .collect();
+    let child_indexes: Vec<NodeIndex> = match child_kind {
+        Some(kind) => graph.targets(container, kind).collect(),
+        None => ordinal_targets.clone(),
+    };
+    let mut children = Vec::with_capacity(child_indexes.len());
+    for child in child_indexes {
+        children.push((graph.get_node_weight(child)?.id(), child));
+    }
+    children.sort();
+    let child_ids: HashSet<Ulid> = children.iter().map(|(id, _)| *id).collect();
+
+    // Keep the existing order for entries that are children, then append the rest
+    let mut seen = HashSet::new();
+    let mut new_order = vec![];
+    let mut removed = vec![];
+    for &entry in ordering_weight.order() {
+        if child_ids.contains(&entry) && seen.insert(entry) {
+            new_order.push(entry);
+        } else {
+            removed.push(entry);
+        }
+    }
+    let mut added = vec![];
+    for &(child_id, _) in &children {
+        if seen.insert(child_id) {
+            new_order.push(child_id);
+            added.push(child_id);
+        }
+    }
+
+    // Point the ordering node at exactly the children
+    let mut ordinal_changed = false;
+    for target in ordinal_targets.iter().copied() {
+        if !child_ids.contains(&graph.get_node_weight(target)?.id()) {
+            graph.remove_edge(ordering, target, EdgeWeightKindDiscriminants::Ordinal)?;
+            ordinal_changed = true;
+        }
+    }
+    for &(_, child) in &children {
+        if !ordinal_targets.contains(&child) {
+            graph.add_edge(ordering, EdgeWeight::new(EdgeWeightKind::Ordinal), child)?;
+            ordinal_changed = true;
+        }
+    }
+
+    if !added.is_empty() || !removed.is_empty() || ordinal_changed {
+        let mut ordering_weight = ordering_weight;
+        ordering_weight.set_order(new_order);
+        let ordering_id = ordering_weight.id();
+        graph.add_or_replace_node(NodeWeight::Ordering(ordering_weight))?;
+        repairs.push(Repair::UpdatedOrdering {
+            ordering: ordering_id,
+            added,
+            removed,
+        });
+    }
+
+    Ok(repairs)
+}
+
+/// Remove an attribute value (and, once the graph is cleaned up, everything beneath it), along
+/// with any arguments subscribing to it, since those subscriptions would no longer lead anywhere
+fn remove_attribute_value(
+    graph: &mut WorkspaceSnapshotGraphVCurrent,
+    attribute_value_id: AttributeValueId,
+) -> WorkspaceSnapshotGraphResult<Vec<Repair>> {
+    let Some(attribute_value) = graph.get_node_index_by_id_opt(attribute_value_id) else {
+        return Ok(vec![]);
+    };
+
+    let subscribers: Vec<NodeIndex> = graph
+        .sources(
+            attribute_value,
+            EdgeWeightKindDiscriminants::ValueSubscription,
+        )
+        .collect();
+    let mut removed_subscribers = Vec::with_capacity(subscribers.len());
+    for subscriber in subscribers {
+        removed_subscribers.push(graph.get_node_weight(subscriber)?.id().into());
+        remove_node_and_edges(graph, subscriber)?;
+    }
+
+    remove_node_and_edges(graph, attribute_value)?;
+
+    Ok(vec![Repair::RemovedAttributeValue {
+        attribute_value: attribute_value_id,
+        removed_subscribers,
+    }])
+}
+
+/// Remove every edge to and from a node (keeping the ordering nodes of its parents up to date),
+/// then the node itself
+fn remove_node_and_edges(
+    graph: &mut WorkspaceSnapshotGraphVCurrent,
+    node: NodeIndex,
+) -> WorkspaceSnapshotGraphResult<()> {
+    let node_id = graph.get_node_weight(node)?.id();
+    let edges: Vec<(NodeIndex, NodeIndex, EdgeWeightKindDiscriminants)> = graph
+        .edges_directed(node, Outgoing)
+        .chain(graph.edges_directed(node, Incoming))
+        .map(|edge| (edge.source(), edge.target(), edge.weight().kind().into()))
+        .collect();
+    for (source, target, kind) in edges {
+        graph.remove_edge(source, target, kind)?;
+    }
+
+    graph.remove_node(node);
+    graph.remove_node_id(node_id);
+
+    Ok(())
+}
+
+/// Recreate the attribute values for child props of an object that don't have one
+fn create_missing_child_attribute_values(
+    graph: &mut WorkspaceSnapshotGraphVCurrent,
+    object_id: AttributeValueId,
+    missing_children: &HashSet<PropId>,
+) -> WorkspaceSnapshotGraphResult<Vec<Repair>> {
+    let Some(object) = graph.get_node_index_by_id_opt(object_id) else {
+        return Ok(vec![]);
+    };
+
+    // Another repair may have filled some of these in already
+    let existing_child_props: HashSet<NodeIndex> = graph
+        .targets(object, EdgeWeightKindDiscriminants::Contain)
+        .filter_map(|child| graph.target_opt(child, EdgeWeightKind::Prop).ok().flatten())
+        .collect();
+
+    let mut missing_children: Vec<PropId> = missing_children.iter().copied().collect();
+    missing_children.sort();
+
+    let mut repairs = vec![];
+    for prop_id in missing_children {
+        let Some(prop) = graph.get_node_index_by_id_opt(prop_id) else {
+            continue;
+        };
+        if existing_child_props.contains(&prop) {
+            continue;
+        }
+        create_attribute_value_for_prop(graph, object, prop, &mut repairs)?;
+    }
+
+    Ok(repairs)
+}
+
+/// Create an empty attribute value for `prop` beneath `parent`. Objects get attribute values for
+/// each of their child props too, so the new value doesn't immediately fail validation itself.
+fn create_attribute_value_for_prop(
+    graph: &mut WorkspaceSnapshotGraphVCurrent,
+    parent: NodeIndex,
+    prop: NodeIndex,
+    repairs: &mut Vec<Repair>,
+) -> WorkspaceSnapshotGraphResult<()> {
+    let prop_weight = graph.get_node_weight(prop)?;
+    let prop_id: PropId = prop_weight.id().into();
+    let prop_kind = prop_weight.as_prop_node_weight()?.kind();
+    let parent_id: AttributeValueId = graph.get_node_weight(parent)?.id().into();
+
+    let id = graph.generate_ulid()?;
+    let lineage_id = graph.generate_ulid()?;
+    let node = NodeWeight::new_attribute_value(id, lineage_id, None, None);
+    let attribute_value = if prop_kind.is_container() {
+        graph.add_ordered_node(node)?
+    } else {
+        graph.add_or_replace_node(node)?
+    };
+    graph.add_ordered_edge(
+        parent,
+        EdgeWeight::new(EdgeWeightKind::Contain(None)),
+        attribute_value,
+    )?;
+    graph.add_edge(attribute_value, EdgeWeight::new(EdgeWeightKind::Prop), prop)?;
+    repairs.push(Repair::CreatedAttributeValue {
+        parent: parent_id,
+        prop: prop_id,
+        attribute_value: id.into(),
+    });
+
+    if prop_kind == PropKind::Object {
+        let child_props = match graph.ordered_children_for_node(prop)? {
+            Some(child_props) => child_props,
+            None => graph
+                .targets(prop, EdgeWeightKindDiscriminants::Use)
+                .collect(),
+        };
+        for child_prop in child_props {
+            create_attribute_value_for_prop(graph, attribute_value, child_prop, repairs)?;
+        }
+    }
+
+    Ok(())
+}
@@ -19,6 +19,7 @@ mod detect_changes;
 mod detect_updates;
 mod exclusive_outgoing_edges;
 mod rebase;
+mod repair;
 
 #[allow(dead_code)]
 fn add_prop_nodes_to_graph<'a, 'b>(
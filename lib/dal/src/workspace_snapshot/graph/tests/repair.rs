@@ -0,0 +1,223 @@
+#[allow(clippy::panic)]
+#[allow(clippy::panic_in_result_fn)]
+#[cfg(test)]
+mod test {
+    use petgraph::graph::NodeIndex;
+    use si_events::{
+        ContentHash,
+        ulid::Ulid,
+    };
+
+    use crate::{
+        EdgeWeight,
+        EdgeWeightKind,
+        EdgeWeightKindDiscriminants,
+        PropKind,
+        WorkspaceSnapshotGraphVCurrent,
+        workspace_snapshot::{
+            graph::{
+                WorkspaceSnapshotGraphResult,
+                repair::{
+                    Repair,
+                    repair_graph,
+                },
+                validator::{
+                    ValidationIssue,
+                    validate_graph,
+                },
+            },
+            node_weight::NodeWeight,
+        },
+    };
+
+    fn add_prop(
+        graph: &mut WorkspaceSnapshotGraphVCurrent,
+        parent: NodeIndex,
+        name: &str,
+        kind: PropKind,
+    ) -> WorkspaceSnapshotGraphResult<(Ulid, NodeIndex)> {
+        let id = graph.generate_ulid()?;
+        let prop = NodeWeight::new_prop(id, id, kind, name, ContentHash::new(name.as_bytes()));
+        let index = if kind.is_container() {
+            graph.add_ordered_node(prop)?
+        } else {
+            graph.add_or_replace_node(prop)?
+        };
+        graph.add_ordered_edge(parent, EdgeWeight::new(EdgeWeightKind::new_use()), index)?;
+        Ok((id, index))
+    }
+
+    fn add_attribute_value(
+        graph: &mut WorkspaceSnapshotGraphVCurrent,
+        parent: NodeIndex,
+        prop: NodeIndex,
+    ) -> WorkspaceSnapshotGraphResult<(Ulid, NodeIndex)> {
+        let id = graph.generate_ulid()?;
+        let index =
+            graph.add_or_replace_node(NodeWeight::new_attribute_value(id, id, None, None))?;
+        graph.add_ordered_edge(
+            parent,
+            EdgeWeight::new(EdgeWeightKind::Contain(None)),
+            index,
+        )?;
+        graph.add_edge(index, EdgeWeight::new(EdgeWeightKind::Prop), prop)?;
+        Ok((id, index))
+    }
+
+    #[test]
+    fn repair_child_ordering_mismatch() -> WorkspaceSnapshotGraphResult<()> {
+        let mut graph = WorkspaceSnapshotGraphVCurrent::new_for_unit_tests()?;
+        let root = graph.root();
+        let (object_id, object) = add_prop(&mut graph, root, "object", PropKind::Object)?;
+        let (ordered_id, _) = add_prop(&mut graph, object, "ordered", PropKind::String)?;
+
+        // Add a child without going through the ordering node
+        let unordered_id = graph.generate_ulid()?;
+        let unordered = graph.add_or_replace_node(NodeWeight::new_prop(
+            unordered_id,
+            unordered_id,
+            PropKind::String,
+            "unordered",
+            ContentHash::new(b"unordered"),
+        ))?;
+        graph.add_edge(
+            object,
+            EdgeWeight::new(EdgeWeightKind::new_use()),
+            unordered,
+        )?;
+
+        assert!(matches!(
+            validate_graph(&&graph)?.as_slice(),
+            [ValidationIssue::ChildOrderingMismatch { node, .. }] if *node == object_id
+        ));
+
+        let reports = repair_graph(&mut graph)?;
+
+        assert_eq!(1, reports.len());
+        assert!(reports[0].resolved);
+        assert!(matches!(
+            reports[0].repairs.as_slice(),
+            [Repair::UpdatedOrdering { added, removed, .. }]
+                if *added == [unordered_id] && removed.is_empty()
+        ));
+        assert_eq!(
+            Some(vec![ordered_id, unordered_id]),
+            graph
+                .ordering_node_for_container(object)?
+                .map(|ordering| ordering.order().clone())
+        );
+        assert!(validate_graph(&&graph)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_missing_child_attribute_values() -> WorkspaceSnapshotGraphResult<()> {
+        let mut graph = WorkspaceSnapshotGraphVCurrent::new_for_unit_tests()?;
+        let root = graph.root();
+        let (_, object_prop) = add_prop(&mut graph, root, "object", PropKind::Object)?;
+        let (name_prop_id, _) = add_prop(&mut graph, object_prop, "name", PropKind::String)?;
+        let (nested_prop_id, nested_prop) =
+            add_prop(&mut graph, object_prop, "nested", PropKind::Object)?;
+        let (leaf_prop_id, _) = add_prop(&mut graph, nested_prop, "leaf", PropKind::String)?;
+
+        // An object attribute value with none of its children
+        let object_av_id = graph.generate_ulid()?;
+        let object_av = graph.add_ordered_node(NodeWeight::new_attribute_value(
+            object_av_id,
+            object_av_id,
+            None,
+            None,
+        ))?;
+        graph.add_edge(root, EdgeWeight::new(EdgeWeightKind::new_use()), object_av)?;
+        graph.add_edge(
+            object_av,
+            EdgeWeight::new(EdgeWeightKind::Prop),
+            object_prop,
+        )?;
+
+        let reports = repair_graph(&mut graph)?;
+
+        assert_eq!(1, reports.len());
+        assert!(reports[0].resolved);
+        let created_props: Vec<Ulid> = reports[0]
+            .repairs
+            .iter()
+            .filter_map(|repair| match repair {
+                Repair::CreatedAttributeValue { prop, .. } => Some((*prop).into()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(3, created_props.len());
+        for prop_id in [name_prop_id, nested_prop_id, leaf_prop_id] {
+            assert!(created_props.contains(&prop_id));
+        }
+        assert_eq!(
+            2,
+            graph
+                .targets(object_av, EdgeWeightKindDiscriminants::Contain)
+                .count()
+        );
+        assert!(validate_graph(&&graph)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_duplicate_attribute_value() -> WorkspaceSnapshotGraphResult<()> {
+        let mut graph = WorkspaceSnapshotGraphVCurrent::new_for_unit_tests()?;
+        let root = graph.root();
+        let (_, object_prop) = add_prop(&mut graph, root, "object", PropKind::Object)?;
+        let (_, name_prop) = add_prop(&mut graph, object_prop, "name", PropKind::String)?;
+
+        let object_av_id = graph.generate_ulid()?;
+        let object_av = graph.add_ordered_node(NodeWeight::new_attribute_value(
+            object_av_id,
+            object_av_id,
+            None,
+            None,
+        ))?;
+        graph.add_edge(root, EdgeWeight::new(EdgeWeightKind::new_use()), object_av)?;
+        graph.add_edge(
+            object_av,
+            EdgeWeight::new(EdgeWeightKind::Prop),
+            object_prop,
+        )?;
+        let (first_id, _) = add_attribute_value(&mut graph, object_av, name_prop)?;
+        let (second_id, _) = add_attribute_value(&mut graph, object_av, name_prop)?;
+
+        let issues = validate_graph(&&graph)?;
+        let [
+            ValidationIssue::DuplicateAttributeValue {
+                original,
+                duplicate,
+            },
+        ] = issues.as_slice()
+        else {
+            panic!("expected a single duplicate attribute value issue, got {issues:?}");
+        };
+        assert_ne!(original, duplicate);
+        let duplicate: Ulid = (*duplicate).into();
+
+        let reports = repair_graph(&mut graph)?;
+
+        assert_eq!(1, reports.len());
+        assert!(reports[0].resolved);
+        assert!(graph.get_node_index_by_id_opt(duplicate).is_none());
+        let kept = if duplicate == first_id {
+            second_id
+        } else {
+            first_id
+        };
+        assert!(graph.get_node_index_by_id_opt(kept).is_some());
+        assert_eq!(
+            Some(vec![kept]),
+            graph
+                .ordering_node_for_container(object_av)?
+                .map(|ordering| ordering.order().clone())
+        );
+        assert!(validate_graph(&&graph)?.is_empty());
+
+        Ok(())
+    }
+}
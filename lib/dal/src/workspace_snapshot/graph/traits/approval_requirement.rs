@@ -37,6 +37,14 @@ pub enum ApprovalRequirementApprover {
     User(UserPk),
 }
 
+/// Selects the components carrying a label, so that an approval requirement definition can apply
+/// to all of them at once.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRequirementComponentLabel {
+    pub key: String,
+    pub value: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct ApprovalRequirementRule {
     pub entity_id: EntityId,
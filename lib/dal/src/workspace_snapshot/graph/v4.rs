@@ -1099,6 +1099,7 @@ impl WorkspaceSnapshotGraphV4 {
                             ContentAddressDiscriminants::ValidationPrototype => "black",
                             ContentAddressDiscriminants::View => "black",
                             ContentAddressDiscriminants::AttributePaths => "blue",
+                            ContentAddressDiscriminants::Variable => "purple",
                         };
                         (discrim.to_string(), color)
                     }
@@ -1131,6 +1132,7 @@ impl WorkspaceSnapshotGraphV4 {
                             "black",
                         ),
                         CategoryNodeKind::Overlays => ("Overlay (Category)".into(), "black"),
+                        CategoryNodeKind::Variable => ("Variables (Category)".into(), "black"),
                     },
                     NodeWeight::Component(component) => (
                         "Component".to_string(),
@@ -232,13 +232,14 @@ impl ValidateNode for AttributeValueNodeWeight {
                     });
                     continue;
                 }
-                let content = graph
+                let child_attr_node = graph
                     .get_node_weight(child_attr)?
-                    .get_attribute_value_node_weight()?
-                    .value();
-                if let Some(orig_content) = attr_content.insert(child_attr_prop, content) {
-                    let original = graph.get_node_weight(child_attr)?.id().into();
-                    let duplicate = graph.get_node_weight(child_attr)?.id().into();
+                    .get_attribute_value_node_weight()?;
+                let content = child_attr_node.value();
+                let duplicate = child_attr_node.id().into();
+                if let Some((orig_content, original)) =
+                    attr_content.insert(child_attr_prop, (content, duplicate))
+                {
                     if content == orig_content {
                         issues.push(ValidationIssue::DuplicateAttributeValue {
                             original,
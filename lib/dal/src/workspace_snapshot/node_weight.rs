@@ -1444,6 +1444,7 @@ impl From<&NodeWeight> for EntityKind {
                 CategoryNodeKind::DiagramObject => EntityKind::CategoryDiagramObject,
                 CategoryNodeKind::DefaultSubscriptionSources => EntityKind::CategoryDiagramObject,
                 CategoryNodeKind::Overlays => EntityKind::CategoryOverlay,
+                CategoryNodeKind::Variable => EntityKind::CategoryVariable,
             },
             NodeWeight::Component(_) => EntityKind::Component,
             NodeWeight::Content(content_node_weight) => match content_node_weight
@@ -1482,6 +1483,7 @@ impl From<&NodeWeight> for EntityKind {
                 }
                 // This is not a content node so should never be encountered here
                 ContentAddressDiscriminants::AttributePaths => EntityKind::LeafPrototype,
+                ContentAddressDiscriminants::Variable => EntityKind::Variable,
             },
             NodeWeight::DependentValueRoot(_) => EntityKind::DependentValueRoot,
             NodeWeight::Func(_) => EntityKind::Func,
@@ -43,10 +43,12 @@ pub enum CategoryNodeKind {
     DiagramObject,
     DefaultSubscriptionSources,
     Overlays,
+    Variable,
 }
 
 const DEFAULT_SUBSCRIPTION_SOURCE_CATEGORY_ID_STR: &str = "0000DNP8N22X0A8S4CV2APWX3A";
 const OVERLAY_CATEGORY_ID_STR: &str = "0000BQJPTG3PZ3XQG9M3W7BSDR";
+const VARIABLE_CATEGORY_ID_STR: &str = "0000C4QKD8XW6V7M2R9T3JZN5H";
 
 impl CategoryNodeKind {
     /// Adding a new category node to an existing workspacewithout migrating a
@@ -66,6 +68,7 @@ impl CategoryNodeKind {
             | CategoryNodeKind::View
             | CategoryNodeKind::DiagramObject => None,
             CategoryNodeKind::Overlays => Ulid::from_string(OVERLAY_CATEGORY_ID_STR).ok(),
+            CategoryNodeKind::Variable => Ulid::from_string(VARIABLE_CATEGORY_ID_STR).ok(),
             CategoryNodeKind::DefaultSubscriptionSources => {
                 Ulid::from_string(DEFAULT_SUBSCRIPTION_SOURCE_CATEGORY_ID_STR).ok()
             }
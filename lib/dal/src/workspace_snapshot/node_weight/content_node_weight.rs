@@ -164,6 +164,7 @@ impl ContentNodeWeight {
                 ));
             }
             ContentAddress::AttributePaths(_) => ContentAddress::AttributePaths(content_hash),
+            ContentAddress::Variable(_) => ContentAddress::Variable(content_hash),
         };
 
         self.content_address = new_address;
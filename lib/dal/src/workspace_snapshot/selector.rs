@@ -49,6 +49,7 @@ use super::{
     InputSocketExt,
     SchemaVariantExt,
     WorkspaceSnapshot,
+    WorkspaceSnapshotCheckpoint,
     WorkspaceSnapshotError,
     WorkspaceSnapshotResult,
     graph::LineageId,
@@ -56,7 +57,10 @@ use super::{
         NodeWeight,
         category_node_weight::CategoryNodeKind,
     },
-    split_snapshot::SplitSnapshot,
+    split_snapshot::{
+        SplitSnapshot,
+        SplitSnapshotCheckpoint,
+    },
 };
 use crate::{
     DalContext,
@@ -67,6 +71,7 @@ use crate::{
     approval_requirement::{
         ApprovalRequirement,
         ApprovalRequirementApprover,
+        ApprovalRequirementComponentLabel,
         ApprovalRequirementDefinition,
     },
     attribute::{
@@ -113,6 +118,14 @@ pub enum WorkspaceSnapshotSelector {
     SplitSnapshot(Arc<SplitSnapshot>),
 }
 
+/// A checkpoint of the uncommitted changes to either kind of snapshot, taken with
+/// [`WorkspaceSnapshotSelector::checkpoint`].
+#[derive(Clone, Debug)]
+pub enum WorkspaceSnapshotSelectorCheckpoint {
+    LegacySnapshot(WorkspaceSnapshotCheckpoint),
+    SplitSnapshot(SplitSnapshotCheckpoint),
+}
+
 impl WorkspaceSnapshotSelector {
     pub async fn address(&self) -> WorkspaceSnapshotAddress {
         match self {
@@ -375,11 +388,7 @@ impl WorkspaceSnapshotSelector {
                     ));
                 };
 
-                let node_weight = CategoryNodeWeight::new(
-                    static_id,
-                    static_id,
-                    CategoryNodeKind::DefaultSubscriptionSources,
-                );
+                let node_weight = CategoryNodeWeight::new(static_id, static_id, kind);
                 self.add_or_replace_node(super::NodeWeight::Category(node_weight))
                     .await?;
                 let root_id = self.root().await?;
@@ -698,6 +707,38 @@ impl WorkspaceSnapshotSelector {
             Self::SplitSnapshot(snapshot) => snapshot.revert().await,
         }
     }
+
+    pub async fn checkpoint(&self) -> WorkspaceSnapshotSelectorCheckpoint {
+        match self {
+            Self::LegacySnapshot(snapshot) => {
+                WorkspaceSnapshotSelectorCheckpoint::LegacySnapshot(snapshot.checkpoint().await)
+            }
+            Self::SplitSnapshot(snapshot) => {
+                WorkspaceSnapshotSelectorCheckpoint::SplitSnapshot(snapshot.checkpoint().await)
+            }
+        }
+    }
+
+    /// Discard all changes made since the checkpoint was taken. The checkpoint must have been
+    /// taken from a snapshot of the same kind.
+    pub async fn restore(
+        &self,
+        checkpoint: WorkspaceSnapshotSelectorCheckpoint,
+    ) -> WorkspaceSnapshotResult<()> {
+        match (self, checkpoint) {
+            (
+                Self::LegacySnapshot(snapshot),
+                WorkspaceSnapshotSelectorCheckpoint::LegacySnapshot(checkpoint),
+            ) => snapshot.restore(checkpoint).await,
+            (
+                Self::SplitSnapshot(snapshot),
+                WorkspaceSnapshotSelectorCheckpoint::SplitSnapshot(checkpoint),
+            ) => snapshot.restore(checkpoint).await,
+            _ => return Err(WorkspaceSnapshotError::UnexpectedSnapshotKind(self.into())),
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -723,6 +764,37 @@ impl ApprovalRequirementExt for WorkspaceSnapshotSelector {
         }
     }
 
+    async fn new_component_label_definition(
+        &self,
+        ctx: &DalContext,
+        component_label: ApprovalRequirementComponentLabel,
+        minimum_approvers_count: usize,
+        approvers: HashSet<ApprovalRequirementApprover>,
+    ) -> WorkspaceSnapshotResult<ApprovalRequirementDefinitionId> {
+        match self {
+            Self::LegacySnapshot(snapshot) => {
+                snapshot
+                    .new_component_label_definition(
+                        ctx,
+                        component_label,
+                        minimum_approvers_count,
+                        approvers,
+                    )
+                    .await
+            }
+            Self::SplitSnapshot(snapshot) => {
+                snapshot
+                    .new_component_label_definition(
+                        ctx,
+                        component_label,
+                        minimum_approvers_count,
+                        approvers,
+                    )
+                    .await
+            }
+        }
+    }
+
     async fn remove_definition(
         &self,
         approval_requirement_definition_id: ApprovalRequirementDefinitionId,
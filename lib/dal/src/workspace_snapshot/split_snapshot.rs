@@ -107,6 +107,7 @@ use crate::{
     approval_requirement::{
         ApprovalRequirement,
         ApprovalRequirementApprover,
+        ApprovalRequirementComponentLabel,
         ApprovalRequirementDefinition,
     },
     component::ComponentResult,
@@ -271,6 +272,13 @@ pub struct SplitSnapshot {
     prop_suggestions: Arc<PropSuggestionsCache>,
 }
 
+/// The uncommitted changes to a [`SplitSnapshot`], see [`SplitSnapshot::checkpoint`].
+#[derive(Debug, Clone)]
+pub struct SplitSnapshotCheckpoint {
+    working_copy: Option<SplitSnapshotGraphVCurrent>,
+    dvu_roots: HashSet<DependentValueRoot>,
+}
+
 impl SplitSnapshot {
     pub async fn id(&self) -> WorkspaceSnapshotAddress {
         *self.address.lock().await
@@ -1315,6 +1323,22 @@ impl SplitSnapshot {
         }
     }
 
+    /// Take a copy of the changes made to the working copy so far, so that the changes made
+    /// after can be undone with [`Self::restore`]. This clones the working copy, so it isn't
+    /// free for large graphs.
+    pub async fn checkpoint(&self) -> SplitSnapshotCheckpoint {
+        SplitSnapshotCheckpoint {
+            working_copy: self.working_copy.read().await.clone(),
+            dvu_roots: self.dvu_roots.lock().await.clone(),
+        }
+    }
+
+    pub async fn restore(&self, checkpoint: SplitSnapshotCheckpoint) {
+        *self.working_copy.write().await = checkpoint.working_copy;
+        *self.dvu_roots.lock().await = checkpoint.dvu_roots;
+        self.clear_prop_suggestions_cache();
+    }
+
     pub async fn correct_transforms(
         &self,
         updates: Vec<UpdateVCurrent>,
@@ -1359,6 +1383,18 @@ impl ApprovalRequirementExt for SplitSnapshot {
         Ok(ApprovalRequirementDefinitionId::new())
     }
 
+    async fn new_component_label_definition(
+        &self,
+        _ctx: &DalContext,
+        _component_label: ApprovalRequirementComponentLabel,
+        _minimum_approvers_count: usize,
+        _approvers: HashSet<ApprovalRequirementApprover>,
+    ) -> WorkspaceSnapshotResult<ApprovalRequirementDefinitionId> {
+        Err(WorkspaceSnapshotError::SplitSnapshotUnsupported(
+            "component label approval requirements",
+        ))
+    }
+
     async fn remove_definition(
         &self,
         _approval_requirement_definition_id: ApprovalRequirementDefinitionId,
@@ -1,5 +1,6 @@
 use std::{
     collections::{
+        BTreeMap,
         HashMap,
         HashSet,
     },
@@ -10,7 +11,10 @@ use async_trait::async_trait;
 use si_events::{
     ContentHash,
     merkle_tree_hash::MerkleTreeHash,
-    workspace_snapshot::Change,
+    workspace_snapshot::{
+        Change,
+        EntityKind,
+    },
 };
 use si_id::{
     ApprovalRequirementDefinitionId,
@@ -21,6 +25,7 @@ use si_id::{
 
 pub use crate::workspace_snapshot::graph::traits::approval_requirement::{
     ApprovalRequirementApprover,
+    ApprovalRequirementComponentLabel,
     ApprovalRequirementRule,
 };
 use crate::{
@@ -36,13 +41,15 @@ use crate::{
     },
     layer_db_types::{
         ApprovalRequirementDefinitionContent,
-        ApprovalRequirementDefinitionContentV1,
+        ApprovalRequirementDefinitionContentV2,
+        ComponentContent,
     },
     workspace_snapshot::{
         WorkspaceSnapshotResult,
         graph::traits::approval_requirement::ApprovalRequirementExt as ApprovalRequirementExtGraph,
         node_weight::{
             NodeWeight,
+            category_node_weight::CategoryNodeKind,
             traits::SiNodeWeight,
         },
     },
@@ -58,6 +65,14 @@ pub trait ApprovalRequirementExt {
         approvers: HashSet<ApprovalRequirementApprover>,
     ) -> WorkspaceSnapshotResult<ApprovalRequirementDefinitionId>;
 
+    async fn new_component_label_definition(
+        &self,
+        ctx: &DalContext,
+        component_label: ApprovalRequirementComponentLabel,
+        minimum_approvers_count: usize,
+        approvers: HashSet<ApprovalRequirementApprover>,
+    ) -> WorkspaceSnapshotResult<ApprovalRequirementDefinitionId>;
+
     async fn remove_definition(
         &self,
         approval_requirement_definition_id: ApprovalRequirementDefinitionId,
@@ -119,9 +134,10 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             .await?
             .ok_or(WorkspaceSnapshotError::MissingContentFromStore(id.into()))?;
 
-        // This should always expect the newest version since we migrate the world when we perform graph migrations.
-        let ApprovalRequirementDefinitionContent::V1(inner) = content;
-        Ok(ApprovalRequirementDefinition::assemble(id, inner))
+        Ok(ApprovalRequirementDefinition::assemble(
+            id,
+            content.extract(),
+        ))
     }
     async fn new_definition(
         &self,
@@ -130,31 +146,34 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
         minimum_approvers_count: usize,
         approvers: HashSet<ApprovalRequirementApprover>,
     ) -> WorkspaceSnapshotResult<ApprovalRequirementDefinitionId> {
-        let content = ApprovalRequirementDefinitionContentV1 {
+        let content = ApprovalRequirementDefinitionContentV2 {
             minimum: minimum_approvers_count,
             approvers,
+            component_label: None,
         };
 
-        let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(ApprovalRequirementDefinitionContent::V1(content.clone()).into()),
-            None,
-            ctx.events_tenancy(),
-            ctx.events_actor(),
-        )?;
-
-        let id = self.generate_ulid().await?;
-        let lineage_id = self.generate_ulid().await?;
-        let node_weight = NodeWeight::new_approval_requirement_definition(id, lineage_id, hash);
-        self.add_or_replace_node(node_weight).await?;
+        add_definition(self, ctx, entity_id, content).await
+    }
 
-        self.add_edge(
-            entity_id,
-            EdgeWeight::new(EdgeWeightKind::ApprovalRequirementDefinition),
-            id,
-        )
-        .await?;
+    async fn new_component_label_definition(
+        &self,
+        ctx: &DalContext,
+        component_label: ApprovalRequirementComponentLabel,
+        minimum_approvers_count: usize,
+        approvers: HashSet<ApprovalRequirementApprover>,
+    ) -> WorkspaceSnapshotResult<ApprovalRequirementDefinitionId> {
+        let content = ApprovalRequirementDefinitionContentV2 {
+            minimum: minimum_approvers_count,
+            approvers,
+            component_label: Some(component_label),
+        };
 
-        Ok(id.into())
+        // Label definitions don't belong to any one component, so they hang off of the component
+        // category node instead.
+        let category_id = self
+            .get_category_node_or_err(CategoryNodeKind::Component)
+            .await?;
+        add_definition(self, ctx, category_id, content).await
     }
 
     async fn remove_definition(
@@ -179,8 +198,7 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             .await?
             .ok_or(WorkspaceSnapshotError::MissingContentFromStore(id.into()))?;
 
-        // This should always expect the newest version since we migrate the world when we perform graph migrations.
-        let ApprovalRequirementDefinitionContent::V1(mut inner) = content;
+        let mut inner = content.extract();
 
         // Only update the content store and node if the approver wasn't already in the set.
         if inner
@@ -188,7 +206,7 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             .insert(ApprovalRequirementApprover::User(user_id))
         {
             let (hash, _) = ctx.layer_db().cas().write(
-                Arc::new(ApprovalRequirementDefinitionContent::V1(inner).into()),
+                Arc::new(ApprovalRequirementDefinitionContent::V2(inner).into()),
                 None,
                 ctx.events_tenancy(),
                 ctx.events_actor(),
@@ -216,8 +234,7 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             .await?
             .ok_or(WorkspaceSnapshotError::MissingContentFromStore(id.into()))?;
 
-        // This should always expect the newest version since we migrate the world when we perform graph migrations.
-        let ApprovalRequirementDefinitionContent::V1(mut inner) = content;
+        let mut inner = content.extract();
 
         // Only update the content store and node if the approver already existed in the set.
         if inner
@@ -225,7 +242,7 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             .remove(&ApprovalRequirementApprover::User(user_id))
         {
             let (hash, _) = ctx.layer_db().cas().write(
-                Arc::new(ApprovalRequirementDefinitionContent::V1(inner).into()),
+                Arc::new(ApprovalRequirementDefinitionContent::V2(inner).into()),
                 None,
                 ctx.events_tenancy(),
                 ctx.events_actor(),
@@ -253,8 +270,61 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             .await
             .approval_requirements_for_changes(workspace_id, changes)?;
 
+        let component_label_definitions = component_label_definitions(self, ctx).await?;
+        // Labels are read from HEAD as well as from the change set, so that removing a label in
+        // the change set doesn't also remove the approvals it requires.
+        let head_snapshot = if component_label_definitions.is_empty() {
+            None
+        } else {
+            Some(ctx.clone_with_head().await?.workspace_snapshot()?)
+        };
+
         let mut cache = HashMap::new();
         for bag in bags {
+            // Changed components also need the approvals required for any of their labels.
+            if bag.entity_kind == EntityKind::Component && !component_label_definitions.is_empty() {
+                let mut label_sets = vec![
+                    component_labels(
+                        ctx,
+                        bag.entity_id,
+                        self.get_node_weight_opt(bag.entity_id).await,
+                    )
+                    .await?,
+                ];
+                if let Some(head_snapshot) = &head_snapshot {
+                    label_sets.push(
+                        component_labels(
+                            ctx,
+                            bag.entity_id,
+                            head_snapshot.get_node_weight_opt(bag.entity_id).await,
+                        )
+                        .await?,
+                    );
+                }
+                results.extend(
+                    component_label_definitions
+                        .iter()
+                        .filter(|(_, content)| {
+                            content.component_label.as_ref().is_some_and(|label| {
+                                label_sets
+                                    .iter()
+                                    .any(|labels| labels.get(&label.key) == Some(&label.value))
+                            })
+                        })
+                        .map(|(id, content)| {
+                            ApprovalRequirement::Explicit(ApprovalRequirementExplicit {
+                                id: *id,
+                                rule: ApprovalRequirementRule {
+                                    entity_id: bag.entity_id,
+                                    entity_kind: bag.entity_kind,
+                                    minimum: content.minimum,
+                                    approvers: content.approvers.to_owned(),
+                                },
+                            })
+                        }),
+                );
+            }
+
             // For the explicit requirements, build a cache of hashes.
             for approval_requirement_definition_id in
                 bag.explicit_approval_requirement_definition_ids
@@ -293,8 +363,7 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
         // requirements.
         for (hash, (approval_requirement_definition_id, entity_id, entity_kind)) in cache {
             if let Some(content) = content_map.get(&hash) {
-                // NOTE(nick): if we had a v2, then there would be migration logic here.
-                let ApprovalRequirementDefinitionContent::V1(inner) = content;
+                let inner = content.to_owned().extract();
 
                 results.push(ApprovalRequirement::Explicit(ApprovalRequirementExplicit {
                     id: approval_requirement_definition_id,
@@ -336,10 +405,12 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
                 .await
                 .get_node_weight_by_id(approval_requirement_definition_id)?
                 .get_approval_requirement_definition_node_weight()?;
-            let Some(ApprovalRequirementDefinitionContent::V1(definition_content)) = ctx
+            let Some(definition_content) = ctx
                 .layer_db()
                 .cas()
-                .try_read_as(&definition_node_weight.content_hash())
+                .try_read_as::<ApprovalRequirementDefinitionContent>(
+                    &definition_node_weight.content_hash(),
+                )
                 .await?
             else {
                 return Err(WorkspaceSnapshotError::MissingContentFromStore(
@@ -348,7 +419,7 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             };
             results.push(ApprovalRequirementDefinition::assemble(
                 approval_requirement_definition_id,
-                definition_content,
+                definition_content.extract(),
             ));
         }
 
@@ -365,3 +436,100 @@ impl ApprovalRequirementExt for WorkspaceSnapshot {
             .entity_id_for_approval_requirement(id)?)
     }
 }
+
+async fn add_definition(
+    snapshot: &WorkspaceSnapshot,
+    ctx: &DalContext,
+    entity_id: Ulid,
+    content: ApprovalRequirementDefinitionContentV2,
+) -> WorkspaceSnapshotResult<ApprovalRequirementDefinitionId> {
+    let (hash, _) = ctx.layer_db().cas().write(
+        Arc::new(ApprovalRequirementDefinitionContent::V2(content).into()),
+        None,
+        ctx.events_tenancy(),
+        ctx.events_actor(),
+    )?;
+
+    let id = snapshot.generate_ulid().await?;
+    let lineage_id = snapshot.generate_ulid().await?;
+    let node_weight = NodeWeight::new_approval_requirement_definition(id, lineage_id, hash);
+    snapshot.add_or_replace_node(node_weight).await?;
+
+    snapshot
+        .add_edge(
+            entity_id,
+            EdgeWeight::new(EdgeWeightKind::ApprovalRequirementDefinition),
+            id,
+        )
+        .await?;
+
+    Ok(id.into())
+}
+
+/// Finds the definitions that apply to components carrying a label.
+async fn component_label_definitions(
+    snapshot: &WorkspaceSnapshot,
+    ctx: &DalContext,
+) -> WorkspaceSnapshotResult<
+    Vec<(
+        ApprovalRequirementDefinitionId,
+        ApprovalRequirementDefinitionContentV2,
+    )>,
+> {
+    let Some(category_id) = snapshot
+        .get_category_node(CategoryNodeKind::Component)
+        .await?
+    else {
+        return Ok(Vec::new());
+    };
+    let Some(ids) = snapshot
+        .working_copy()
+        .await
+        .approval_requirement_definitions_for_entity_id_opt(category_id.into())?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut definitions = Vec::new();
+    for id in ids {
+        let node_weight = snapshot
+            .working_copy()
+            .await
+            .get_node_weight_by_id(id)?
+            .get_approval_requirement_definition_node_weight()?;
+        let content: ApprovalRequirementDefinitionContent = ctx
+            .layer_db()
+            .cas()
+            .try_read_as(&node_weight.content_hash())
+            .await?
+            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(id.into()))?;
+        let content = content.extract();
+        if content.component_label.is_some() {
+            definitions.push((id, content));
+        }
+    }
+
+    Ok(definitions)
+}
+
+/// Reads the labels of a component from its node weight, which are empty if the component
+/// doesn't exist in the snapshot the node weight was looked up in.
+async fn component_labels(
+    ctx: &DalContext,
+    component_id: EntityId,
+    node_weight: Option<NodeWeight>,
+) -> WorkspaceSnapshotResult<BTreeMap<String, String>> {
+    let Some(node_weight) = node_weight else {
+        return Ok(BTreeMap::new());
+    };
+    let content: ComponentContent = ctx
+        .layer_db()
+        .cas()
+        .try_read_as(&node_weight.content_hash())
+        .await?
+        .ok_or(WorkspaceSnapshotError::MissingContentFromStore(
+            component_id.into(),
+        ))?;
+
+    Ok(content.extract().labels)
+}
@@ -25,6 +25,7 @@ use serde_json::json;
 use si_id::ComponentId;
 
 pub mod default_subscriptions;
+pub mod string_template;
 
 // AV subscribes to name AV on same component
 #[test(enable_veritech)]
@@ -0,0 +1,326 @@
+use dal::{
+    Component,
+    DalContext,
+    attribute::attributes::AttributeSources,
+    component::subscription_graph::SubscriptionGraph,
+    diagram::view::View,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        attribute::value,
+        change_set,
+        component,
+        schema::variant,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+// A string template combines several subscriptions into one value, and re-renders when any of
+// them change.
+#[test(enable_veritech)]
+async fn string_template_from_multiple_subscriptions(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "bucket",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "BucketName", kind: "string" },
+                        { name: "Prefix", kind: "string" },
+                        { name: "Port", kind: "integer" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    variant::create(
+        ctx,
+        "policy",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "Resource", kind: "string" },
+                        { name: "Endpoint", kind: "string" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    let bucket_id = component::create_and_set(
+        ctx,
+        "bucket",
+        "bucket",
+        json!({
+            "/domain/BucketName": "my-bucket",
+            "/domain/Prefix": "logs",
+            "/domain/Port": 8443,
+        }),
+    )
+    .await?;
+    let policy_id = component::create(ctx, "policy", "policy").await?;
+
+    component::update(
+        ctx,
+        "policy",
+        json!({
+            "/domain/Resource": {
+                "$source": {
+                    "template": "arn:aws:s3:::${bucket}/${prefix}",
+                    "bindings": {
+                        "bucket": { "component": "bucket", "path": "/domain/BucketName" },
+                        "prefix": { "component": "bucket", "path": "/domain/Prefix" },
+                    },
+                },
+            },
+            "/domain/Endpoint": {
+                "$source": {
+                    "template": "${name}:${port}",
+                    "bindings": {
+                        "name": { "component": bucket_id.to_string(), "path": "/domain/BucketName" },
+                        "port": { "component": "bucket", "path": "/domain/Port" },
+                    },
+                },
+            },
+        }),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+    assert_eq!(
+        json!({
+            "Resource": "arn:aws:s3:::my-bucket/logs",
+            "Endpoint": "my-bucket:8443",
+        }),
+        component::domain(ctx, "policy").await?
+    );
+
+    // The template is reported as the source, with components resolved to ids
+    assert_eq!(
+        json!({
+            "/si/name": "policy",
+            "/si/type": "component",
+            "/domain/Resource": {
+                "$source": {
+                    "template": "arn:aws:s3:::${bucket}/${prefix}",
+                    "bindings": {
+                        "bucket": { "component": bucket_id.to_string(), "path": "/domain/BucketName" },
+                        "prefix": { "component": bucket_id.to_string(), "path": "/domain/Prefix" },
+                    },
+                },
+            },
+            "/domain/Endpoint": {
+                "$source": {
+                    "template": "${name}:${port}",
+                    "bindings": {
+                        "name": { "component": bucket_id.to_string(), "path": "/domain/BucketName" },
+                        "port": { "component": bucket_id.to_string(), "path": "/domain/Port" },
+                    },
+                },
+            },
+        }),
+        serde_json::to_value(AttributeSources::from(
+            Component::sources(ctx, policy_id).await?
+        ))?
+    );
+
+    // The bindings are real subscriptions
+    let subscription_graph = SubscriptionGraph::new(ctx).await?;
+    assert_eq!(
+        vec![bucket_id],
+        subscription_graph.inner().direct_dependencies_of(policy_id)
+    );
+
+    // Changing any bound value re-renders the template
+    value::set(ctx, ("bucket", "/domain/Prefix"), "archive").await?;
+    change_set::commit(ctx).await?;
+    assert_eq!(
+        json!({
+            "Resource": "arn:aws:s3:::my-bucket/archive",
+            "Endpoint": "my-bucket:8443",
+        }),
+        component::domain(ctx, "policy").await?
+    );
+
+    // Templating a value back onto a source would create a cycle
+    assert!(
+        component::update(
+            ctx,
+            "bucket",
+            json!({
+                "/domain/Prefix": {
+                    "$source": {
+                        "template": "${resource}",
+                        "bindings": {
+                            "resource": { "component": "policy", "path": "/domain/Resource" },
+                        },
+                    },
+                },
+            }),
+        )
+        .await
+        .is_err()
+    );
+
+    Ok(())
+}
+
+// Templates must bind every placeholder and can only be used on string attributes.
+#[test(enable_veritech)]
+async fn string_template_validation(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "test",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "Name", kind: "string" },
+                        { name: "Count", kind: "integer" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    component::create_and_set(ctx, "test", "source", json!({ "/domain/Name": "a" })).await?;
+    component::create(ctx, "test", "target").await?;
+
+    // Unbound placeholder
+    assert!(
+        component::update(
+            ctx,
+            "target",
+            json!({
+                "/domain/Name": {
+                    "$source": {
+                        "template": "${name}-${missing}",
+                        "bindings": {
+                            "name": { "component": "source", "path": "/domain/Name" },
+                        },
+                    },
+                },
+            }),
+        )
+        .await
+        .is_err()
+    );
+
+    // Unterminated placeholder
+    assert!(
+        component::update(
+            ctx,
+            "target",
+            json!({
+                "/domain/Name": {
+                    "$source": { "template": "${name", "bindings": {} },
+                },
+            }),
+        )
+        .await
+        .is_err()
+    );
+
+    // Non-string destination
+    assert!(
+        component::update(
+            ctx,
+            "target",
+            json!({
+                "/domain/Count": {
+                    "$source": {
+                        "template": "${name}",
+                        "bindings": {
+                            "name": { "component": "source", "path": "/domain/Name" },
+                        },
+                    },
+                },
+            }),
+        )
+        .await
+        .is_err()
+    );
+
+    // Templates without placeholders are fine, and escapes are honored
+    component::update(
+        ctx,
+        "target",
+        json!({
+            "/domain/Name": { "$source": { "template": "$${literal}", "bindings": {} } },
+        }),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+    assert_eq!(
+        json!({ "Name": "${literal}" }),
+        component::domain(ctx, "target").await?
+    );
+
+    Ok(())
+}
+
+// Duplicating a templated component alongside the component it is bound to binds the copy to the
+// copied component, while duplicating it alone keeps it bound to the original.
+#[test(enable_veritech)]
+async fn string_template_duplicated_with_its_sources(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "test",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "Name", kind: "string" },
+                        { name: "Resource", kind: "string" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    let source_id =
+        component::create_and_set(ctx, "test", "source", json!({ "/domain/Name": "a" })).await?;
+    let target_id = component::create(ctx, "test", "target").await?;
+    component::update(
+        ctx,
+        "target",
+        json!({
+            "/domain/Resource": {
+                "$source": {
+                    "template": "arn:${name}",
+                    "bindings": {
+                        "name": { "component": "source", "path": "/domain/Name" },
+                    },
+                },
+            },
+        }),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    let view_id = View::get_id_for_default(ctx).await?;
+    Component::duplicate(ctx, view_id, vec![source_id, target_id], "both-").await?;
+    Component::duplicate(ctx, view_id, vec![target_id], "alone-").await?;
+    value::set(ctx, ("both-source", "/domain/Name"), "b").await?;
+    change_set::commit(ctx).await?;
+
+    assert_eq!(
+        json!({ "Resource": "arn:a" }),
+        component::domain(ctx, "target").await?
+    );
+    assert_eq!(
+        json!({ "Resource": "arn:b" }),
+        component::domain(ctx, "both-target").await?
+    );
+    assert_eq!(
+        json!({ "Resource": "arn:a" }),
+        component::domain(ctx, "alone-target").await?
+    );
+
+    Ok(())
+}
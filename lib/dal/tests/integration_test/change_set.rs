@@ -31,6 +31,7 @@ use si_events::{
 };
 
 mod approval;
+mod rebase;
 
 #[test]
 async fn open_change_sets(ctx: &mut DalContext) {
@@ -632,3 +633,52 @@ async fn update_pointer_tracks_last_used(ctx: &mut DalContext) {
         .expect("could not get snapshot_id");
     assert_eq!(snapshot_id, old_snapshot.to_string());
 }
+
+#[test]
+async fn commit_if_unchanged_refuses_moved_change_set(ctx: &mut DalContext) {
+    let expected = ctx
+        .workspace_snapshot()
+        .expect("could not get workspace snapshot")
+        .id()
+        .await;
+
+    let first =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "first")
+            .await
+            .expect("could not create component");
+    ctx.commit_if_unchanged(expected)
+        .await
+        .expect("could not commit unchanged change set");
+    ctx.update_snapshot_to_visibility()
+        .await
+        .expect("could not update snapshot to visibility");
+    assert!(
+        Component::list_ids(ctx)
+            .await
+            .expect("could not list components")
+            .contains(&first.id())
+    );
+
+    // The change set has moved on since `expected`, so this commit must not apply
+    let second =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "second")
+            .await
+            .expect("could not create component");
+    assert!(
+        ctx.commit_if_unchanged(expected)
+            .await
+            .is_err_and(|e| TransactionsErrorDiscriminants::RebaseFailed == e.into())
+    );
+
+    ctx.restart_connections()
+        .await
+        .expect("could not restart connections");
+    ctx.update_snapshot_to_visibility()
+        .await
+        .expect("could not update snapshot to visibility");
+    let component_ids = Component::list_ids(ctx)
+        .await
+        .expect("could not list components");
+    assert!(component_ids.contains(&first.id()));
+    assert!(!component_ids.contains(&second.id()));
+}
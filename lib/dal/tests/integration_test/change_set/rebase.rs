@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use dal::{
+    ChangeSet,
+    Component,
+    DalContext,
+    change_set::rebase::{
+        self,
+        ChangeSetRebaseError,
+        RebaseConflict,
+        RebaseResolution,
+    },
+};
+use dal_test::{
+    helpers::{
+        attribute::value,
+        component,
+        create_component_for_default_schema_name_in_default_view,
+        get_attribute_value_for_component,
+        update_attribute_value_for_component,
+    },
+    prelude::*,
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+#[test]
+async fn resolve_attribute_value_conflict_with_head(ctx: &mut DalContext) -> Result<()> {
+    // Put a component on HEAD and fork from it.
+    let component_id =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "original")
+            .await?
+            .id();
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    let change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+
+    // Rename it on HEAD from another change set, which gets replayed onto ours.
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+    update_attribute_value_for_component(
+        ctx,
+        component_id,
+        &["root", "si", "name"],
+        json!("on head"),
+    )
+    .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    ChangeSetTestHelpers::switch_to_change_set(ctx, change_set.id).await?;
+
+    // Set the name to something else in the change set.
+    update_attribute_value_for_component(
+        ctx,
+        component_id,
+        &["root", "si", "name"],
+        json!("in change set"),
+    )
+    .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    // Only the change set has changed the name since its base snapshot, so that's no conflict.
+    let report = rebase::detect(ctx).await?;
+    assert!(report.has_local_changes);
+    assert!(report.conflicts.is_empty());
+
+    // Had HEAD's rename not been replayed, both sides would have changed it.
+    ChangeSet::get_by_id(ctx, change_set.id)
+        .await?
+        .update_base_snapshot_address(ctx, change_set.base_snapshot_address)
+        .await?;
+    let attribute_value_id = Component::get_by_id(ctx, component_id)
+        .await?
+        .attribute_values_for_prop(ctx, &["root", "si", "name"])
+        .await?
+        .pop()
+        .ok_or_eyre("si.name attribute value not found")?;
+    let report = rebase::detect(ctx).await?;
+    assert_eq!(
+        vec![RebaseConflict::AttributeValue {
+            attribute_value_id,
+            component_id,
+            path: "/si/name".to_owned(),
+            head_value: Some(json!("on head")),
+            change_set_value: Some(json!("in change set")),
+        }], // expected
+        report.conflicts, // actual
+    );
+
+    // Take HEAD's value.
+    let resolutions = HashMap::from([(
+        attribute_value_id.into_inner().into(),
+        RebaseResolution::TakeHead,
+    )]);
+    rebase::rebase_onto_head(ctx, &resolutions).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(
+        json!("on head"), // expected
+        get_attribute_value_for_component(ctx, component_id, &["root", "si", "name"]).await?, // actual
+    );
+    assert!(rebase::detect(ctx).await?.conflicts.is_empty());
+
+    Ok(())
+}
+
+#[test]
+async fn take_head_subscription(ctx: &mut DalContext) -> Result<()> {
+    // Put two components on HEAD and fork from it.
+    let source_id = component::create(ctx, "swifty", "source").await?;
+    let subscriber_id = component::create(ctx, "swifty", "subscriber").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    let change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+
+    // Subscribe one's name to the other's on HEAD from another change set.
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+    value::subscribe(ctx, (subscriber_id, "/si/name"), (source_id, "/si/name")).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    ChangeSetTestHelpers::switch_to_change_set(ctx, change_set.id).await?;
+
+    // Set it to a value in the change set, as if HEAD's subscription hadn't been replayed.
+    value::set(ctx, (subscriber_id, "/si/name"), "in change set").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSet::get_by_id(ctx, change_set.id)
+        .await?
+        .update_base_snapshot_address(ctx, change_set.base_snapshot_address)
+        .await?;
+
+    let attribute_value_id = value::id(ctx, (subscriber_id, "/si/name")).await?;
+    assert_eq!(
+        vec![RebaseConflict::AttributeValue {
+            attribute_value_id,
+            component_id: subscriber_id,
+            path: "/si/name".to_owned(),
+            head_value: Some(json!("source")),
+            change_set_value: Some(json!("in change set")),
+        }], // expected
+        rebase::detect(ctx).await?.conflicts, // actual
+    );
+
+    // Taking HEAD's side takes its subscription, not just its current value.
+    let resolutions = HashMap::from([(
+        attribute_value_id.into_inner().into(),
+        RebaseResolution::TakeHead,
+    )]);
+    rebase::rebase_onto_head(ctx, &resolutions).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert!(value::has_subscription(ctx, (subscriber_id, "/si/name")).await?);
+    value::set(ctx, (source_id, "/si/name"), "renamed").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(
+        json!("renamed"),                                    // expected
+        value::get(ctx, (subscriber_id, "/si/name")).await?, // actual
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn keep_component_deleted_in_change_set(ctx: &mut DalContext) -> Result<()> {
+    // Put a component on HEAD and fork from it.
+    let component_id =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "original")
+            .await?
+            .id();
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    let change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+
+    // Rename it on HEAD from another change set, which gets replayed onto ours.
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+    update_attribute_value_for_component(
+        ctx,
+        component_id,
+        &["root", "si", "name"],
+        json!("on head"),
+    )
+    .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    ChangeSetTestHelpers::switch_to_change_set(ctx, change_set.id).await?;
+
+    // Delete it in the change set.
+    Component::get_by_id(ctx, component_id)
+        .await?
+        .delete(ctx)
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    // HEAD hasn't changed it since the change set's base snapshot, so the deletion is no
+    // conflict.
+    assert!(rebase::detect(ctx).await?.conflicts.is_empty());
+
+    // Had HEAD's rename not been replayed, it would be.
+    ChangeSet::get_by_id(ctx, change_set.id)
+        .await?
+        .update_base_snapshot_address(ctx, change_set.base_snapshot_address)
+        .await?;
+    let report = rebase::detect(ctx).await?;
+    assert_eq!(
+        vec![RebaseConflict::ComponentDeletedInChangeSet { component_id }], // expected
+        report.conflicts,                                                   // actual
+    );
+
+    // Every conflict needs a resolution.
+    let result = rebase::rebase_onto_head(ctx, &HashMap::new()).await;
+    assert!(matches!(
+        result,
+        Err(ChangeSetRebaseError::MissingResolution(_))
+    ));
+
+    // Keep the deletion.
+    let resolutions = HashMap::from([(
+        component_id.into_inner().into(),
+        RebaseResolution::KeepChangeSet,
+    )]);
+    rebase::rebase_onto_head(ctx, &resolutions).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let deleted = Component::try_get_by_id(ctx, component_id)
+        .await?
+        .is_none_or(|component| component.to_delete());
+    assert!(deleted);
+
+    Ok(())
+}
+
+#[test]
+async fn replaying_head_moves_base_snapshot(ctx: &mut DalContext) -> Result<()> {
+    let change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+    let head_change_set_id = ctx.get_workspace_default_change_set_id().await?;
+    assert_eq!(
+        Some(
+            ChangeSet::get_by_id(ctx, head_change_set_id)
+                .await?
+                .workspace_snapshot_address
+        ), // expected
+        change_set.base_snapshot_address, // actual
+    );
+
+    // Put a component on HEAD from another change set, which gets replayed onto ours.
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+    let component_id =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "elsewhere")
+            .await?
+            .id();
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    let head_snapshot_address = ChangeSet::get_by_id(ctx, head_change_set_id)
+        .await?
+        .workspace_snapshot_address;
+
+    ChangeSetTestHelpers::switch_to_change_set(ctx, change_set.id).await?;
+    assert_eq!(
+        Some(head_snapshot_address), // expected
+        ChangeSet::get_by_id(ctx, change_set.id)
+            .await?
+            .base_snapshot_address, // actual
+    );
+    assert!(Component::exists_by_id(ctx, component_id).await?);
+    assert!(rebase::detect(ctx).await?.conflicts.is_empty());
+
+    Ok(())
+}
+
+#[test]
+async fn unknown_base_snapshot_leaves_components_to_the_user(ctx: &mut DalContext) -> Result<()> {
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+    let component_id =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "in change set")
+            .await?
+            .id();
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    // The base snapshot tells a component created in the change set apart from one deleted on
+    // HEAD.
+    assert!(rebase::detect(ctx).await?.conflicts.is_empty());
+
+    // Without it, there's no telling.
+    ChangeSet::get_by_id(ctx, ctx.change_set_id())
+        .await?
+        .update_base_snapshot_address(ctx, None)
+        .await?;
+    assert_eq!(
+        vec![RebaseConflict::ComponentDeletedOnHead { component_id }], // expected
+        rebase::detect(ctx).await?.conflicts,                          // actual
+    );
+
+    // Rebasing records HEAD's snapshot as the new base.
+    let resolutions = HashMap::from([(
+        component_id.into_inner().into(),
+        RebaseResolution::KeepChangeSet,
+    )]);
+    rebase::rebase_onto_head(ctx, &resolutions).await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let head_change_set_id = ctx.get_workspace_default_change_set_id().await?;
+    assert_eq!(
+        Some(
+            ChangeSet::get_by_id(ctx, head_change_set_id)
+                .await?
+                .workspace_snapshot_address
+        ), // expected
+        ChangeSet::get_by_id(ctx, ctx.change_set_id())
+            .await?
+            .base_snapshot_address, // actual
+    );
+    assert!(Component::exists_by_id(ctx, component_id).await?);
+    assert!(rebase::detect(ctx).await?.conflicts.is_empty());
+
+    Ok(())
+}
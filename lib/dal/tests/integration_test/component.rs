@@ -41,6 +41,7 @@ mod delete;
 mod duplicate;
 mod get_code;
 mod get_diff;
+mod labels;
 mod paste;
 mod property_order;
 mod set_type;
@@ -0,0 +1,166 @@
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
+
+use dal::{
+    Component,
+    ComponentError,
+    DalContext,
+    UserPk,
+    approval_requirement::{
+        ApprovalRequirement,
+        ApprovalRequirementApprover,
+        ApprovalRequirementComponentLabel,
+    },
+};
+use dal_test::{
+    Result,
+    helpers::{
+        ChangeSetTestHelpers,
+        create_component_for_default_schema_name_in_default_view,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn set_labels_and_owner(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "black star")
+            .await?;
+    let component_id = component.id();
+    assert!(component.labels().is_empty());
+    assert_eq!(None, component.owner());
+
+    let labels = BTreeMap::from([
+        ("env".to_owned(), "prod".to_owned()),
+        ("team".to_owned(), "payments".to_owned()),
+    ]);
+    component
+        .set_labels(ctx, labels.clone())
+        .await?
+        .set_owner(ctx, Some("payments".to_owned()))
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let component = Component::get_by_id(ctx, component_id).await?;
+    assert_eq!(&labels, component.labels());
+    assert_eq!(Some("payments"), component.owner());
+
+    // Keys can't be used in "key=value" search terms if they contain "="
+    let result = component
+        .set_labels(ctx, BTreeMap::from([("a=b".to_owned(), "c".to_owned())]))
+        .await;
+    assert!(matches!(result, Err(ComponentError::InvalidLabelKey(key)) if key == "a=b"));
+
+    Ok(())
+}
+
+#[test]
+async fn label_approval_requirement(ctx: &mut DalContext) -> Result<()> {
+    let approver = ApprovalRequirementApprover::User(UserPk::new());
+    let definition_id = ApprovalRequirement::new_component_label_definition(
+        ctx,
+        ApprovalRequirementComponentLabel {
+            key: "team".to_owned(),
+            value: "payments".to_owned(),
+        },
+        1,
+        HashSet::from([approver.clone()]),
+    )
+    .await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+
+    let labeled =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "labeled")
+            .await?;
+    let labeled_id = labeled.id();
+    labeled
+        .set_labels(
+            ctx,
+            BTreeMap::from([("team".to_owned(), "payments".to_owned())]),
+        )
+        .await?;
+    create_component_for_default_schema_name_in_default_view(ctx, "starfield", "unlabeled").await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let changes = ctx.detect_changes_from_head().await?;
+    let (requirements, _) = ApprovalRequirement::list(ctx, &changes).await?;
+    let label_requirements: Vec<_> = requirements
+        .iter()
+        .filter_map(|requirement| match requirement {
+            ApprovalRequirement::Explicit(explicit) if explicit.id == definition_id => {
+                Some(&explicit.rule)
+            }
+            _ => None,
+        })
+        .collect();
+
+    // Only the labeled component requires the label's approvers
+    assert_eq!(1, label_requirements.len());
+    assert_eq!(
+        labeled_id.into_inner(),
+        label_requirements[0].entity_id.into_inner()
+    );
+    assert_eq!(1, label_requirements[0].minimum);
+    assert_eq!(HashSet::from([approver]), label_requirements[0].approvers);
+
+    Ok(())
+}
+
+#[test]
+async fn label_approval_requirement_after_removing_label(ctx: &mut DalContext) -> Result<()> {
+    let approver = ApprovalRequirementApprover::User(UserPk::new());
+    let definition_id = ApprovalRequirement::new_component_label_definition(
+        ctx,
+        ApprovalRequirementComponentLabel {
+            key: "team".to_owned(),
+            value: "payments".to_owned(),
+        },
+        1,
+        HashSet::from([approver.clone()]),
+    )
+    .await?;
+    let labeled =
+        create_component_for_default_schema_name_in_default_view(ctx, "starfield", "labeled")
+            .await?;
+    let labeled_id = labeled.id();
+    labeled
+        .set_labels(
+            ctx,
+            BTreeMap::from([("team".to_owned(), "payments".to_owned())]),
+        )
+        .await?;
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx).await?;
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx).await?;
+
+    // Removing the label is itself a change to a component carrying it on HEAD
+    Component::get_by_id(ctx, labeled_id)
+        .await?
+        .set_labels(ctx, BTreeMap::new())
+        .await?;
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let changes = ctx.detect_changes_from_head().await?;
+    let (requirements, _) = ApprovalRequirement::list(ctx, &changes).await?;
+    let label_requirements: Vec<_> = requirements
+        .iter()
+        .filter_map(|requirement| match requirement {
+            ApprovalRequirement::Explicit(explicit) if explicit.id == definition_id => {
+                Some(&explicit.rule)
+            }
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(1, label_requirements.len());
+    assert_eq!(
+        labeled_id.into_inner(),
+        label_requirements[0].entity_id.into_inner()
+    );
+    assert_eq!(HashSet::from([approver]), label_requirements[0].approvers);
+
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use dal::{
+    DalContext,
+    component_template::{
+        self,
+        ComponentTemplateInstance,
+        TemplateParameterBindings,
+        TemplateParameterValues,
+    },
+};
+use dal_test::{
+    Result,
+    helpers::{
+        change_set,
+        component,
+        schema::variant,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use si_pkg::TemplateParameterSpec;
+
+// Save a bucket and a policy subscribing to it as a template, instantiate it with parameters,
+// then change the template and upgrade the instance to the new version.
+#[test(enable_veritech)]
+async fn instantiate_and_upgrade_component_template(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "bucket",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "BucketName", kind: "string" },
+                        { name: "Region", kind: "string" },
+                        { name: "Extra", kind: "string" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    variant::create(
+        ctx,
+        "policy",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "Region", kind: "string" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    change_set::apply_and_refork(ctx).await?;
+
+    let bucket_id = component::create_and_set(
+        ctx,
+        "bucket",
+        "bucket",
+        json!({
+            "/domain/BucketName": "logs",
+            "/domain/Region": "us-east-1",
+        }),
+    )
+    .await?;
+    let policy_id = component::create_and_set(
+        ctx,
+        "policy",
+        "policy",
+        json!({
+            "/domain/Region": { "$source": { "component": "bucket", "path": "/domain/Region" } },
+        }),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    let parameters = vec![
+        TemplateParameterSpec::builder()
+            .name("region")
+            .default(json!("us-east-1"))
+            .build()?,
+        TemplateParameterSpec::builder()
+            .name("bucket_name")
+            .build()?,
+    ];
+    let bindings: TemplateParameterBindings = serde_json::from_value(json!({
+        "bucket": { "/domain/BucketName": "bucket_name", "/domain/Region": "region" },
+    }))?;
+    let template = component_template::save(
+        ctx,
+        "storage",
+        None,
+        &[bucket_id, policy_id],
+        parameters.clone(),
+        bindings.clone(),
+    )
+    .await?;
+    assert_eq!(1, template.version);
+
+    // Parameters without a default must be given
+    assert!(
+        component_template::instantiate(ctx, "storage", "qa", TemplateParameterValues::new())
+            .await
+            .is_err()
+    );
+
+    let values: TemplateParameterValues =
+        serde_json::from_value(json!({ "bucket_name": "dev-logs" }))?;
+    let instantiation = component_template::instantiate(ctx, "storage", "dev", values).await?;
+    change_set::commit(ctx).await?;
+
+    assert_eq!(1, instantiation.instance.template_version);
+    let dev_bucket_id = component::id(ctx, "dev-bucket").await?;
+    let dev_policy_id = component::id(ctx, "dev-policy").await?;
+    let mut dev_component_ids = vec![dev_bucket_id, dev_policy_id];
+    dev_component_ids.sort();
+    assert_eq!(dev_component_ids, instantiation.instance.component_ids);
+    assert!(
+        instantiation
+            .components
+            .iter()
+            .all(|component| component.created)
+    );
+    assert_eq!(
+        json!({
+            "BucketName": "dev-logs",
+            "Region": "us-east-1",
+        }),
+        component::domain(ctx, "dev-bucket").await?
+    );
+    // The subscription points at the instance's bucket, not the original one
+    component::update(ctx, "dev-bucket", json!({ "/domain/Region": "eu-west-1" })).await?;
+    change_set::commit(ctx).await?;
+    assert_eq!(
+        json!({ "Region": "eu-west-1" }),
+        component::domain(ctx, "dev-policy").await?
+    );
+
+    // Add an attribute to the template and upgrade the instance
+    component::update(ctx, "bucket", json!({ "/domain/Extra": "extra" })).await?;
+    change_set::commit(ctx).await?;
+    let template = component_template::save(
+        ctx,
+        "storage",
+        None,
+        &[bucket_id, policy_id],
+        parameters,
+        bindings,
+    )
+    .await?;
+    assert_eq!(2, template.version);
+
+    let upgrade = component_template::upgrade(ctx, "dev", TemplateParameterValues::new()).await?;
+    change_set::commit(ctx).await?;
+
+    assert_eq!(2, upgrade.instance.template_version);
+    let bucket = &upgrade.components[0];
+    assert!(!bucket.created);
+    assert_eq!(
+        vec!["/domain/Region".to_owned(), "/domain/Extra".to_owned()],
+        bucket.updated_paths
+    );
+    assert!(upgrade.components[1].is_unchanged());
+    assert_eq!(
+        json!({
+            "BucketName": "dev-logs",
+            "Region": "us-east-1",
+            "Extra": "extra",
+        }),
+        component::domain(ctx, "dev-bucket").await?
+    );
+
+    // The instance is recorded on its components, so renaming one doesn't lose track of it
+    component::update(ctx, "dev-bucket", json!({ "/si/name": "dev-logs" })).await?;
+    change_set::commit(ctx).await?;
+    let upgrade = component_template::upgrade(ctx, "dev", TemplateParameterValues::new()).await?;
+    change_set::commit(ctx).await?;
+    assert!(
+        upgrade
+            .components
+            .iter()
+            .all(|component| !component.created)
+    );
+    assert_eq!(dev_component_ids, upgrade.instance.component_ids);
+
+    // ... and it is only known in the change set it was created in
+    let other = change_set::fork(ctx).await?;
+    assert!(
+        ComponentTemplateInstance::get(&other, "dev")
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
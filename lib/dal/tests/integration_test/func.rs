@@ -15,6 +15,7 @@ use pretty_assertions_sorted::assert_eq;
 mod argument;
 mod authoring;
 mod debug;
+mod memoization;
 
 #[test]
 async fn summary(ctx: &mut DalContext) {
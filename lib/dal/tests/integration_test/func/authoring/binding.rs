@@ -781,6 +781,10 @@ async fn return_the_right_bindings(ctx: &mut DalContext, nw: &WorkspaceSignup) -
                                         // should only be one input right now
                                         assert_eq!(maybe_valid_args.len(), 1);
                                     }
+                                    // string templates always carry the template itself
+                                    IntrinsicFunc::StringTemplate => {
+                                        assert!(!attribute_binding.argument_bindings.is_empty());
+                                    }
                                     IntrinsicFunc::Unset => {
                                         assert!(attribute_binding.argument_bindings.is_empty());
                                     }
@@ -843,6 +847,10 @@ async fn return_the_right_bindings(ctx: &mut DalContext, nw: &WorkspaceSignup) -
                                         // should only be one or zero input right now
                                         assert!(maybe_valid_args.len() < 2);
                                     }
+                                    // string templates always carry the template itself
+                                    IntrinsicFunc::StringTemplate => {
+                                        assert!(!attribute_binding.argument_bindings.is_empty());
+                                    }
                                     // unset has no args
                                     IntrinsicFunc::Unset => {
                                         assert!(attribute_binding.argument_bindings.is_empty());
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use dal::{
+    AttributeValueId,
+    Component,
+    DalContext,
+    Func,
+    FuncId,
+    Prop,
+    func::{
+        authoring::FuncAuthoringClient,
+        binding::AttributeFuncDestination,
+        runner::FuncRunner,
+    },
+    prop::PropPath,
+};
+use dal_test::{
+    Result,
+    helpers::{
+        create_component_for_schema_variant_on_default_view,
+        create_unlocked_variant_copy_for_schema_name,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+use si_events::func_result_memo::FuncResultMemo;
+
+async fn run(
+    ctx: &DalContext,
+    attribute_value_id: AttributeValueId,
+    func_id: FuncId,
+    args: serde_json::Value,
+) -> Result<Option<serde_json::Value>> {
+    let result = FuncRunner::run_attribute_value(ctx, attribute_value_id, func_id, args)
+        .await?
+        .await??;
+    Ok(result.value().cloned())
+}
+
+#[test(enable_veritech)]
+async fn attribute_func_results_are_reused_only_when_opted_in(ctx: &mut DalContext) -> Result<()> {
+    let schema_variant_id = create_unlocked_variant_copy_for_schema_name(ctx, "swifty").await?;
+    let prop_id = Prop::find_prop_id_by_path(
+        ctx,
+        schema_variant_id,
+        &PropPath::new(["root", "domain", "name"]),
+    )
+    .await?;
+    let func = FuncAuthoringClient::create_new_attribute_func(
+        ctx,
+        Some("memoized".to_string()),
+        None,
+        AttributeFuncDestination::Prop(prop_id),
+        Vec::new(),
+    )
+    .await?;
+    FuncAuthoringClient::save_code(
+        ctx,
+        func.id,
+        "async function main(input: Input): Promise<Output> {\n  return \"computed\";\n}\n",
+    )
+    .await?;
+    let component =
+        create_component_for_schema_variant_on_default_view(ctx, schema_variant_id).await?;
+    let attribute_value_id =
+        Component::attribute_value_for_prop_id(ctx, component.id(), prop_id).await?;
+
+    // Funcs don't opt in by default, so nothing is memoized for them.
+    let func = Func::get_by_id(ctx, func.id).await?;
+    assert!(!func.memoization_enabled);
+    let args = json!({"name": "computed"});
+    assert!(FuncRunner::memo_key(ctx, &func, &args)?.is_none());
+    assert_eq!(
+        Some(json!("computed")),
+        run(ctx, attribute_value_id, func.id, args.clone()).await?
+    );
+
+    let func = FuncAuthoringClient::set_memoization_enabled(ctx, func.id, true).await?;
+    let memo_key = FuncRunner::memo_key(ctx, &func, &args)?.expect("func opted in");
+
+    // A miss runs the func and memoizes its result.
+    assert!(
+        ctx.layer_db()
+            .func_result_memo()
+            .read(&memo_key)
+            .await?
+            .is_none()
+    );
+    assert_eq!(
+        Some(json!("computed")),
+        run(ctx, attribute_value_id, func.id, args.clone()).await?
+    );
+    let memo = ctx
+        .layer_db()
+        .func_result_memo()
+        .read(&memo_key)
+        .await?
+        .expect("result should be memoized");
+    assert_eq!(Some(json!("computed")), memo.value());
+
+    // A hit reuses the memoized result rather than running the func.
+    let seeded_args = json!({"name": "seeded"});
+    let seeded_key = FuncRunner::memo_key(ctx, &func, &seeded_args)?.expect("func opted in");
+    ctx.layer_db().func_result_memo().write(
+        seeded_key,
+        Arc::new(FuncResultMemo::new(
+            Some(json!("memoized")),
+            Some(json!("memoized")),
+        )),
+        ctx.events_tenancy(),
+        ctx.events_actor(),
+    )?;
+    assert_eq!(
+        Some(json!("memoized")),
+        run(ctx, attribute_value_id, func.id, seeded_args.clone()).await?
+    );
+
+    // Opting back out runs the func again even though a memo exists.
+    let func = FuncAuthoringClient::set_memoization_enabled(ctx, func.id, false).await?;
+    assert_eq!(
+        Some(json!("computed")),
+        run(ctx, attribute_value_id, func.id, seeded_args).await?
+    );
+
+    Ok(())
+}
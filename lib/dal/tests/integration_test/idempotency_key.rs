@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use dal::DalContext;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use si_db::IdempotencyKey;
+
+const WINDOW: Duration = Duration::from_secs(60 * 60);
+const LEASE: Duration = Duration::from_secs(60);
+
+#[test]
+async fn reserve_replays_completed_requests(ctx: &mut DalContext) {
+    let reserved = IdempotencyKey::reserve(ctx, "key", "hash", WINDOW, LEASE)
+        .await
+        .expect("could not reserve key");
+    assert!(reserved.is_none());
+
+    // Still in flight
+    let existing = IdempotencyKey::reserve(ctx, "key", "hash", WINDOW, LEASE)
+        .await
+        .expect("could not reserve key")
+        .expect("key should already be reserved");
+    assert!(!existing.is_complete());
+
+    IdempotencyKey::complete(ctx, "key", 200, b"done")
+        .await
+        .expect("could not complete key");
+    let existing = IdempotencyKey::reserve(ctx, "key", "hash", WINDOW, LEASE)
+        .await
+        .expect("could not reserve key")
+        .expect("key should already be reserved");
+    assert!(existing.is_complete());
+    assert_eq!(Some(200), existing.status_code);
+    assert_eq!(Some(b"done".to_vec()), existing.response_body);
+    assert_eq!(None, existing.lease_expires_at);
+}
+
+#[test]
+async fn release_allows_retrying(ctx: &mut DalContext) {
+    IdempotencyKey::reserve(ctx, "key", "hash", WINDOW, LEASE)
+        .await
+        .expect("could not reserve key");
+    IdempotencyKey::release(ctx, "key")
+        .await
+        .expect("could not release key");
+
+    let reserved = IdempotencyKey::reserve(ctx, "key", "hash", WINDOW, LEASE)
+        .await
+        .expect("could not reserve key");
+    assert!(reserved.is_none());
+}
+
+#[test]
+async fn reserve_takes_over_expired_leases(ctx: &mut DalContext) {
+    IdempotencyKey::reserve(ctx, "key", "first", WINDOW, Duration::ZERO)
+        .await
+        .expect("could not reserve key");
+
+    // The first request never completed and its lease has run out
+    let reserved = IdempotencyKey::reserve(ctx, "key", "second", WINDOW, LEASE)
+        .await
+        .expect("could not reserve key");
+    assert!(reserved.is_none());
+
+    // The second request holds an active lease, so the key can't be taken over again
+    let existing = IdempotencyKey::reserve(ctx, "key", "third", WINDOW, LEASE)
+        .await
+        .expect("could not reserve key")
+        .expect("key should already be reserved");
+    assert_eq!("second", existing.request_hash);
+    assert!(!existing.is_complete());
+}
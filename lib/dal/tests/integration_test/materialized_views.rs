@@ -1,4 +1,7 @@
-use std::collections::HashSet;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 
 use dal::{
     Component,
@@ -615,6 +618,8 @@ async fn component(ctx: &DalContext) -> Result<()> {
             resource_diff,
             is_secret_defining,
             to_delete: false,
+            labels: HashMap::new(),
+            owner: None,
         }, // expected
         component // actual
     );
@@ -636,6 +641,8 @@ async fn component(ctx: &DalContext) -> Result<()> {
             resource_id,
             diff_status,
             has_socket_connections: false,
+            labels: HashMap::new(),
+            owner: None,
         },
         component_in_list
     );
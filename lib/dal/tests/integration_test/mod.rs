@@ -7,11 +7,13 @@ mod audit_logging;
 mod authoring;
 mod change_set;
 mod component;
+mod component_template;
 mod cycle_check_guard;
 mod dependent_values_update;
 mod deserialize;
 mod diagram;
 mod func;
+mod idempotency_key;
 mod input_sources;
 mod management;
 mod materialized_views;
@@ -20,6 +22,7 @@ mod module;
 mod node_weight;
 mod pkg;
 mod policy_report;
+mod promotion;
 mod prompt_overrides;
 mod prop;
 mod property_editor;
@@ -28,8 +31,12 @@ mod rebaser;
 mod resource_metadata;
 mod schema;
 mod secret;
+mod snapshot_checkpoint;
 mod split_snapshot;
 mod summary_generator;
+mod usage_rollups;
 mod validations;
+mod variable;
 mod view;
 mod workspace;
+mod workspace_webhook;
@@ -44,6 +44,8 @@ use si_pkg::{
     SiPkg,
 };
 
+mod signature;
+
 #[test(enable_veritech)]
 async fn import_pkg_from_pkg_set_latest_default(ctx: &mut DalContext) -> Result<()> {
     // Let's create a new asset
@@ -0,0 +1,181 @@
+use dal::{
+    DalContext,
+    pkg::{
+        PkgError,
+        verify_module_signature,
+    },
+};
+use dal_test::{
+    Result,
+    test,
+};
+use module_index_client::ModuleSignature;
+use pretty_assertions_sorted::assert_eq;
+use si_crypto::PackageSigningKey;
+use si_db::ModuleTrustStore;
+use si_pkg::{
+    PkgSpec,
+    SiPkg,
+};
+
+fn pkg(name: &str) -> Result<SiPkg> {
+    let spec = PkgSpec::builder()
+        .name(name)
+        .created_by("sally@systeminit.com")
+        .version("0")
+        .build()?;
+    Ok(SiPkg::load_from_spec(spec)?)
+}
+
+fn sign(signing_key: &PackageSigningKey, pkg: &SiPkg) -> Result<ModuleSignature> {
+    let verifying_key = signing_key.verifying_key();
+    Ok(ModuleSignature {
+        key_id: verifying_key.key_id(),
+        public_key: verifying_key.to_base64(),
+        signature: signing_key.sign(pkg.hash()?.to_string()).to_base64(),
+    })
+}
+
+async fn trust(ctx: &DalContext, signing_key: &PackageSigningKey) -> Result<()> {
+    let verifying_key = signing_key.verifying_key();
+    ModuleTrustStore::add_key(
+        ctx,
+        verifying_key.key_id(),
+        verifying_key.to_base64(),
+        Some("publisher".to_owned()),
+    )
+    .await?;
+    Ok(())
+}
+
+#[test]
+async fn unsigned_modules_are_refused_only_when_strict(ctx: &DalContext) -> Result<()> {
+    let pkg = pkg("unsigned")?;
+
+    verify_module_signature(ctx, &pkg, None).await?;
+
+    ModuleTrustStore::set_strict(ctx, true).await?;
+    let result = verify_module_signature(ctx, &pkg, None).await;
+    assert!(matches!(result, Err(PkgError::UnsignedModule(_))));
+
+    Ok(())
+}
+
+#[test]
+async fn untrusted_signers_are_refused_only_when_strict(ctx: &DalContext) -> Result<()> {
+    let pkg = pkg("untrusted")?;
+    let signature = sign(&PackageSigningKey::generate(), &pkg)?;
+
+    verify_module_signature(ctx, &pkg, Some(&signature)).await?;
+
+    ModuleTrustStore::set_strict(ctx, true).await?;
+    let result = verify_module_signature(ctx, &pkg, Some(&signature)).await;
+    assert!(matches!(
+        result,
+        Err(PkgError::UntrustedModuleSigner(key_id)) if key_id == signature.key_id
+    ));
+
+    Ok(())
+}
+
+#[test]
+async fn trusted_signers_are_accepted_when_strict(ctx: &DalContext) -> Result<()> {
+    let pkg = pkg("trusted")?;
+    let signing_key = PackageSigningKey::generate();
+    trust(ctx, &signing_key).await?;
+    ModuleTrustStore::set_strict(ctx, true).await?;
+
+    verify_module_signature(ctx, &pkg, Some(&sign(&signing_key, &pkg)?)).await?;
+
+    Ok(())
+}
+
+#[test]
+async fn tampered_signatures_are_always_refused(ctx: &DalContext) -> Result<()> {
+    let pkg = pkg("tampered")?;
+    let signing_key = PackageSigningKey::generate();
+    trust(ctx, &signing_key).await?;
+
+    // Signed over a different module
+    let signature = ModuleSignature {
+        signature: sign(&signing_key, &self::pkg("other")?)?.signature,
+        ..sign(&signing_key, &pkg)?
+    };
+
+    for strict in [false, true] {
+        ModuleTrustStore::set_strict(ctx, strict).await?;
+        let result = verify_module_signature(ctx, &pkg, Some(&signature)).await;
+        assert!(matches!(result, Err(PkgError::InvalidModuleSignature(_))));
+    }
+
+    Ok(())
+}
+
+#[test]
+async fn key_id_mismatches_are_always_refused(ctx: &DalContext) -> Result<()> {
+    let pkg = pkg("mismatched")?;
+    let trusted_key = PackageSigningKey::generate();
+    trust(ctx, &trusted_key).await?;
+
+    // Signed by another key, but claiming to be the trusted one
+    let signature = ModuleSignature {
+        key_id: trusted_key.verifying_key().key_id(),
+        ..sign(&PackageSigningKey::generate(), &pkg)?
+    };
+    let result = verify_module_signature(ctx, &pkg, Some(&signature)).await;
+    assert!(matches!(result, Err(PkgError::InvalidModuleSignature(_))));
+
+    // The shipped key doesn't have the claimed id
+    let untrusted_key = PackageSigningKey::generate();
+    let signature = ModuleSignature {
+        key_id: "made-up".to_owned(),
+        ..sign(&untrusted_key, &pkg)?
+    };
+    let result = verify_module_signature(ctx, &pkg, Some(&signature)).await;
+    assert!(matches!(result, Err(PkgError::InvalidModuleSignature(_))));
+
+    Ok(())
+}
+
+#[test]
+async fn trust_store_crud(ctx: &DalContext) -> Result<()> {
+    let trust_store = ModuleTrustStore::get(ctx).await?;
+    assert!(!trust_store.strict);
+    assert!(trust_store.trusted_keys.is_empty());
+
+    let verifying_key = PackageSigningKey::generate().verifying_key();
+    let key_id = verifying_key.key_id();
+    ModuleTrustStore::add_key(
+        ctx,
+        key_id.clone(),
+        verifying_key.to_base64(),
+        Some("before".to_owned()),
+    )
+    .await?;
+    // Adding the key again only replaces its label
+    let key = ModuleTrustStore::add_key(
+        ctx,
+        key_id.clone(),
+        verifying_key.to_base64(),
+        Some("after".to_owned()),
+    )
+    .await?;
+    assert_eq!(Some("after".to_owned()), key.label);
+
+    ModuleTrustStore::set_strict(ctx, true).await?;
+    let trust_store = ModuleTrustStore::get(ctx).await?;
+    assert!(trust_store.strict);
+    assert_eq!(1, trust_store.trusted_keys.len());
+    assert_eq!(
+        Some(verifying_key.to_base64()),
+        trust_store
+            .trusted_key(&key_id)
+            .map(|key| key.public_key.clone())
+    );
+
+    assert!(ModuleTrustStore::remove_key(ctx, &key_id).await?);
+    assert!(!ModuleTrustStore::remove_key(ctx, &key_id).await?);
+    assert!(ModuleTrustStore::get(ctx).await?.trusted_keys.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use dal::{
+    DalContext,
+    promotion::{
+        self,
+        PromotionError,
+        PromotionMatching,
+        PromotionOverrides,
+    },
+};
+use dal_test::{
+    Result,
+    helpers::{
+        change_set,
+        component,
+        schema::variant,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+// Promote components (with a subscription between them) from one environment to another, keeping
+// a per-environment override, then promote again and make sure only the differences are applied.
+#[test(enable_veritech)]
+async fn promote_components_between_environments(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "bucket",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "BucketName", kind: "string" },
+                        { name: "Region", kind: "string" },
+                        { name: "Extra", kind: "string" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    variant::create(
+        ctx,
+        "policy",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "Region", kind: "string" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    change_set::apply_and_refork(ctx).await?;
+
+    // Model everything in "dev"
+    let dev_bucket_id = component::create_and_set(
+        ctx,
+        "bucket",
+        "bucket",
+        json!({
+            "/domain/BucketName": "logs",
+            "/domain/Region": "us-east-1",
+            "/domain/Extra": "extra",
+        }),
+    )
+    .await?;
+    component::create_and_set(
+        ctx,
+        "policy",
+        "policy",
+        json!({
+            "/domain/Region": { "$source": { "component": "bucket", "path": "/domain/Region" } },
+        }),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+
+    let component_ids = vec![
+        component::id(ctx, "policy").await?,
+        component::id(ctx, "bucket").await?,
+    ];
+    let specs = promotion::export_components(ctx, &component_ids).await?;
+
+    // Promote into "prod", which has its own region
+    let overrides: PromotionOverrides = serde_json::from_value(json!({
+        "bucket": { "/domain/Region": "us-west-2" },
+    }))?;
+    let mut prod = change_set::fork(ctx).await?;
+    let report = promotion::promote_components(
+        &prod,
+        &specs,
+        overrides.clone(),
+        PromotionMatching::SourceId,
+    )
+    .await?;
+    change_set::commit(&mut prod).await?;
+
+    assert!(report.components.iter().all(|promoted| promoted.created));
+    let prod_bucket_id = component::id(&prod, "bucket").await?;
+    assert_ne!(dev_bucket_id, prod_bucket_id);
+    assert_eq!(
+        json!({
+            "BucketName": "logs",
+            "Region": "us-west-2",
+            "Extra": "extra",
+        }),
+        component::domain(&prod, "bucket").await?
+    );
+    // The subscription was mapped onto the prod bucket
+    assert_eq!(
+        json!({ "Region": "us-west-2" }),
+        component::domain(&prod, "policy").await?
+    );
+
+    // Change dev and promote again: only the differences are applied
+    component::update(
+        ctx,
+        "bucket",
+        json!({
+            "/domain/BucketName": "audit-logs",
+            "/domain/Extra": { "$source": null },
+        }),
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+    let specs = promotion::export_components(ctx, &component_ids).await?;
+
+    let report =
+        promotion::promote_components(&prod, &specs, overrides, PromotionMatching::SourceId)
+            .await?;
+    change_set::commit(&mut prod).await?;
+
+    let policy = &report.components[0];
+    assert!(policy.is_unchanged());
+    let bucket = &report.components[1];
+    assert_eq!(prod_bucket_id, bucket.component_id);
+    assert!(!bucket.created);
+    assert_eq!(vec!["/domain/BucketName".to_owned()], bucket.updated_paths);
+    assert_eq!(vec!["/domain/Extra".to_owned()], bucket.removed_paths);
+    assert_eq!(
+        json!({
+            "BucketName": "audit-logs",
+            "Region": "us-west-2",
+        }),
+        component::domain(&prod, "bucket").await?
+    );
+
+    Ok(())
+}
+
+// Components are matched by the component they were promoted from, so renaming a component
+// renames the promoted component rather than creating a new one.
+#[test(enable_veritech)]
+async fn promote_renamed_component(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "bucket",
+        r#"
+            function main() {
+                return { props: [{ name: "Name", kind: "string" }] };
+            }
+        "#,
+    )
+    .await?;
+    change_set::apply_and_refork(ctx).await?;
+
+    let bucket_id =
+        component::create_and_set(ctx, "bucket", "logs", json!({ "/domain/Name": "logs" })).await?;
+    change_set::commit(ctx).await?;
+    let specs = promotion::export_components(ctx, &[bucket_id]).await?;
+
+    let mut prod = change_set::fork(ctx).await?;
+    let report = promotion::promote_components(
+        &prod,
+        &specs,
+        PromotionOverrides::new(),
+        PromotionMatching::SourceId,
+    )
+    .await?;
+    change_set::commit(&mut prod).await?;
+    let prod_bucket_id = report.components[0].component_id;
+
+    component::update(ctx, "logs", json!({ "/si/name": "audit-logs" })).await?;
+    change_set::commit(ctx).await?;
+    let specs = promotion::export_components(ctx, &[bucket_id]).await?;
+
+    let report = promotion::promote_components(
+        &prod,
+        &specs,
+        PromotionOverrides::new(),
+        PromotionMatching::SourceId,
+    )
+    .await?;
+    change_set::commit(&mut prod).await?;
+
+    let bucket = &report.components[0];
+    assert_eq!(prod_bucket_id, bucket.component_id);
+    assert!(!bucket.created);
+    assert_eq!(vec!["/si/name".to_owned()], bucket.updated_paths);
+    assert_eq!(prod_bucket_id, component::id(&prod, "audit-logs").await?);
+
+    Ok(())
+}
+
+// A component with the same name that wasn't promoted is only adopted when asked to, and must
+// have the same schema on both sides.
+#[test(enable_veritech)]
+async fn promote_components_by_name(ctx: &mut DalContext) -> Result<()> {
+    for schema in ["bucket", "policy"] {
+        variant::create(
+            ctx,
+            schema,
+            r#"
+                function main() {
+                    return { props: [{ name: "Name", kind: "string" }] };
+                }
+            "#,
+        )
+        .await?;
+    }
+    change_set::apply_and_refork(ctx).await?;
+
+    let mut prod = change_set::fork(ctx).await?;
+    let prod_bucket_id = component::create(&prod, "bucket", "bucket").await?;
+    component::create(&prod, "policy", "thing").await?;
+    change_set::commit(&mut prod).await?;
+
+    let bucket_id = component::create(ctx, "bucket", "bucket").await?;
+    let thing_id = component::create(ctx, "bucket", "thing").await?;
+    change_set::commit(ctx).await?;
+
+    let specs = promotion::export_components(ctx, &[bucket_id]).await?;
+    assert!(matches!(
+        promotion::promote_components(
+            &prod,
+            &specs,
+            PromotionOverrides::new(),
+            PromotionMatching::SourceId,
+        )
+        .await,
+        Err(PromotionError::ComponentNameInUse(_))
+    ));
+    let report = promotion::promote_components(
+        &prod,
+        &specs,
+        PromotionOverrides::new(),
+        PromotionMatching::SourceIdThenName,
+    )
+    .await?;
+    assert_eq!(prod_bucket_id, report.components[0].component_id);
+    assert!(!report.components[0].created);
+
+    let specs = promotion::export_components(ctx, &[thing_id]).await?;
+    assert!(matches!(
+        promotion::promote_components(
+            &prod,
+            &specs,
+            PromotionOverrides::new(),
+            PromotionMatching::SourceIdThenName,
+        )
+        .await,
+        Err(PromotionError::ComponentSchemaMismatch { .. })
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use std::sync::atomic::Ordering;
+
+use dal::{
+    Component,
+    DalContext,
+    WsEvent,
+    diagram::view::View,
+    workspace_snapshot::DependentValueRoot,
+};
+use dal_test::{
+    Result,
+    helpers::create_component_for_default_schema_name,
+    prelude::ChangeSetTestHelpers,
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use si_events::audit_log::AuditLogKind;
+
+#[test]
+async fn restore_discards_changes_since_checkpoint(ctx: &mut DalContext) -> Result<()> {
+    let view_id = View::get_id_for_default(ctx).await?;
+    let kept = create_component_for_default_schema_name(ctx, "starfield", "kept", view_id)
+        .await?
+        .id();
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert!(
+        DependentValueRoot::get_dependent_value_roots(ctx)
+            .await?
+            .is_empty()
+    );
+
+    let snapshot = ctx.workspace_snapshot()?;
+    let checkpoint = snapshot.checkpoint().await;
+
+    let root =
+        DependentValueRoot::Unfinished(Component::root_attribute_value_id(ctx, kept).await?.into());
+    DependentValueRoot::add_dependent_value_root(ctx, root).await?;
+    create_component_for_default_schema_name(ctx, "starfield", "discarded", view_id).await?;
+    assert_eq!(2, Component::list_ids(ctx).await?.len());
+
+    snapshot.restore(checkpoint).await?;
+
+    assert_eq!(vec![kept], Component::list_ids(ctx).await?);
+    assert!(
+        DependentValueRoot::get_dependent_value_roots(ctx)
+            .await?
+            .is_empty()
+    );
+
+    // The root was discarded along with the graph, so it can be added again
+    DependentValueRoot::add_dependent_value_root(ctx, root).await?;
+    assert_eq!(
+        vec![root],
+        DependentValueRoot::get_dependent_value_roots(ctx).await?
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn restore_keeps_changes_made_before_checkpoint(ctx: &mut DalContext) -> Result<()> {
+    let view_id = View::get_id_for_default(ctx).await?;
+    let kept = create_component_for_default_schema_name(ctx, "starfield", "kept", view_id)
+        .await?
+        .id();
+
+    let snapshot = ctx.workspace_snapshot()?;
+    let checkpoint = snapshot.checkpoint().await;
+    Component::remove(ctx, kept).await?;
+    assert!(Component::list_ids(ctx).await?.is_empty());
+
+    snapshot.restore(checkpoint).await?;
+    assert_eq!(vec![kept], Component::list_ids(ctx).await?);
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert_eq!(vec![kept], Component::list_ids(ctx).await?);
+
+    Ok(())
+}
+
+#[test]
+async fn restore_discards_events_and_audit_logs_since_checkpoint(
+    ctx: &mut DalContext,
+) -> Result<()> {
+    let pending_publish_count = ctx.txns().await?.nats().pending_publish_count().await;
+    let audit_log_count = ctx.pending_audit_logs_count().load(Ordering::Relaxed);
+
+    let checkpoint = ctx.checkpoint().await?;
+    let view = View::new(ctx, "discarded").await?;
+    WsEvent::change_set_written(ctx, ctx.change_set_id())
+        .await?
+        .publish_on_commit(ctx)
+        .await?;
+    ctx.write_audit_log(
+        AuditLogKind::CreateView { view_id: view.id() },
+        "discarded".to_owned(),
+    )
+    .await?;
+    assert_eq!(
+        pending_publish_count + 1,
+        ctx.txns().await?.nats().pending_publish_count().await
+    );
+    // The audit log is held back until the checkpoint is kept
+    assert_eq!(
+        audit_log_count,
+        ctx.pending_audit_logs_count().load(Ordering::Relaxed)
+    );
+
+    ctx.restore(checkpoint).await?;
+    assert!(View::find_by_name(ctx, "discarded").await?.is_none());
+    assert_eq!(
+        pending_publish_count,
+        ctx.txns().await?.nats().pending_publish_count().await
+    );
+
+    let checkpoint = ctx.checkpoint().await?;
+    let view = View::new(ctx, "kept").await?;
+    ctx.write_audit_log(
+        AuditLogKind::CreateView { view_id: view.id() },
+        "kept".to_owned(),
+    )
+    .await?;
+    ctx.keep(checkpoint).await?;
+    assert_eq!(
+        audit_log_count + 1,
+        ctx.pending_audit_logs_count().load(Ordering::Relaxed)
+    );
+
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    assert!(View::find_by_name(ctx, "kept").await?.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use audit_database::{
+    AuditDatabaseContext,
+    AuditDatabaseError,
+    ResourceChange,
+    UsageGranularity,
+    WorkspaceUsageRollup,
+};
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use dal::DalContext;
+use dal_test::{
+    Result,
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use si_events::{
+    FuncKind,
+    FuncRunId,
+};
+
+fn at(timestamp: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(timestamp)?.with_timezone(&Utc))
+}
+
+#[test]
+async fn rollups_are_bucketed_by_hour_and_day(
+    ctx: &mut DalContext,
+    audit_database_context: AuditDatabaseContext,
+) -> Result<()> {
+    let context = audit_database_context;
+    let workspace_id = ctx.workspace_pk()?;
+
+    WorkspaceUsageRollup::record_resource_change(
+        &context,
+        workspace_id,
+        FuncRunId::new(),
+        at("2025-06-03T10:15:00Z")?,
+        ResourceChange::Created,
+    )
+    .await?;
+    WorkspaceUsageRollup::record_resource_change(
+        &context,
+        workspace_id,
+        FuncRunId::new(),
+        at("2025-06-03T11:45:00Z")?,
+        ResourceChange::Created,
+    )
+    .await?;
+    WorkspaceUsageRollup::record_func_run(
+        &context,
+        workspace_id,
+        FuncRunId::new(),
+        at("2025-06-03T10:20:00Z")?,
+        FuncKind::Action,
+        1500,
+    )
+    .await?;
+    // Only the latest resource count observed within a bucket is kept, regardless of the order
+    // the observations arrive in.
+    WorkspaceUsageRollup::record_resource_count(
+        &context,
+        workspace_id,
+        at("2025-06-03T10:30:00Z")?,
+        5,
+    )
+    .await?;
+    WorkspaceUsageRollup::record_resource_count(
+        &context,
+        workspace_id,
+        at("2025-06-03T10:10:00Z")?,
+        3,
+    )
+    .await?;
+
+    let start = at("2025-06-03T00:00:00Z")?;
+    let end = at("2025-06-04T00:00:00Z")?;
+
+    let hourly =
+        WorkspaceUsageRollup::list(&context, workspace_id, UsageGranularity::Hour, start, end)
+            .await?;
+    assert_eq!(
+        vec![
+            (at("2025-06-03T10:00:00Z")?, Some(5), 1, 1),
+            (at("2025-06-03T11:00:00Z")?, None, 1, 0),
+        ],
+        hourly
+            .iter()
+            .map(|rollup| (
+                rollup.bucket_start,
+                rollup.resource_count,
+                rollup.resources_created,
+                rollup.func_runs.len(),
+            ))
+            .collect::<Vec<_>>()
+    );
+
+    let daily =
+        WorkspaceUsageRollup::list(&context, workspace_id, UsageGranularity::Day, start, end)
+            .await?;
+    assert_eq!(1, daily.len());
+    let day = &daily[0];
+    assert_eq!(start, day.bucket_start);
+    assert_eq!(Some(5), day.resource_count);
+    assert_eq!(2, day.resources_created);
+    assert_eq!(0, day.resources_deleted);
+    assert_eq!(1, day.func_runs.len());
+    assert_eq!("Action", day.func_runs[0].func_kind);
+    assert_eq!(1, day.func_runs[0].run_count);
+    assert_eq!(1.5, day.func_runs[0].run_seconds);
+
+    Ok(())
+}
+
+#[test]
+async fn redelivered_events_are_counted_once(
+    ctx: &mut DalContext,
+    audit_database_context: AuditDatabaseContext,
+) -> Result<()> {
+    let context = audit_database_context;
+    let workspace_id = ctx.workspace_pk()?;
+    let timestamp = at("2025-06-03T10:15:00Z")?;
+
+    let create_func_run_id = FuncRunId::new();
+    let delete_func_run_id = FuncRunId::new();
+    for _ in 0..2 {
+        WorkspaceUsageRollup::record_resource_change(
+            &context,
+            workspace_id,
+            create_func_run_id,
+            timestamp,
+            ResourceChange::Created,
+        )
+        .await?;
+        WorkspaceUsageRollup::record_resource_change(
+            &context,
+            workspace_id,
+            delete_func_run_id,
+            timestamp,
+            ResourceChange::Deleted,
+        )
+        .await?;
+        WorkspaceUsageRollup::record_func_run(
+            &context,
+            workspace_id,
+            create_func_run_id,
+            timestamp,
+            FuncKind::Action,
+            1000,
+        )
+        .await?;
+    }
+
+    let usage = WorkspaceUsageRollup::list(
+        &context,
+        workspace_id,
+        UsageGranularity::Day,
+        at("2025-06-03T00:00:00Z")?,
+        at("2025-06-04T00:00:00Z")?,
+    )
+    .await?;
+    assert_eq!(1, usage.len());
+    assert_eq!(1, usage[0].resources_created);
+    assert_eq!(1, usage[0].resources_deleted);
+    assert_eq!(1, usage[0].func_runs[0].run_count);
+    assert_eq!(1.0, usage[0].func_runs[0].run_seconds);
+
+    Ok(())
+}
+
+#[test]
+async fn list_rejects_invalid_ranges(
+    ctx: &mut DalContext,
+    audit_database_context: AuditDatabaseContext,
+) -> Result<()> {
+    let context = audit_database_context;
+    let workspace_id = ctx.workspace_pk()?;
+    let end = at("2025-06-03T00:00:00Z")?;
+
+    let result = WorkspaceUsageRollup::list(
+        &context,
+        workspace_id,
+        UsageGranularity::Day,
+        end + TimeDelta::days(1),
+        end,
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(AuditDatabaseError::InvalidUsageRange(_))
+    ));
+
+    let result = WorkspaceUsageRollup::list(
+        &context,
+        workspace_id,
+        UsageGranularity::Hour,
+        end - TimeDelta::days(32),
+        end,
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(AuditDatabaseError::InvalidUsageRange(_))
+    ));
+
+    let usage = WorkspaceUsageRollup::list(
+        &context,
+        workspace_id,
+        UsageGranularity::Day,
+        end - UsageGranularity::Day.max_range(),
+        end,
+    )
+    .await?;
+    assert!(usage.is_empty());
+
+    Ok(())
+}
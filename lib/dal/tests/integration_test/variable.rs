@@ -0,0 +1,158 @@
+use dal::{
+    Component,
+    DalContext,
+    attribute::attributes::{
+        self,
+        AttributeSources,
+    },
+    variable::{
+        Variable,
+        VariableError,
+    },
+};
+use dal_test::{
+    Result,
+    helpers::{
+        change_set,
+        component,
+        schema::variant,
+    },
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+// Test that attributes set to a variable follow the variable's value, and that a variable cannot
+// be removed while attributes still use it.
+#[test(enable_veritech)]
+async fn attribute_set_to_variable(ctx: &mut DalContext) -> Result<()> {
+    variant::create(
+        ctx,
+        "test",
+        r#"
+            function main() {
+                return {
+                    props: [
+                        { name: "Region", kind: "string" },
+                        { name: "Other", kind: "string" },
+                    ]
+                };
+            }
+        "#,
+    )
+    .await?;
+    let component_id = component::create(ctx, "test", "test").await?;
+    let variable = Variable::new(ctx, "prod.region", json!("us-east-1"), None).await?;
+
+    // Set an attribute to the variable by name
+    attributes::update_attributes(
+        ctx,
+        component_id,
+        serde_json::from_value(json!({
+            "/domain/Region": { "$source": { "variable": "prod.region" } },
+            "/domain/Other": "other",
+        }))?,
+    )
+    .await?;
+    change_set::commit(ctx).await?;
+    assert_eq!(
+        json!({
+            "Region": "us-east-1",
+            "Other": "other",
+        }),
+        component::domain(ctx, "test").await?
+    );
+    assert_eq!(
+        json!({
+            "/si/name": "test",
+            "/si/type": "component",
+            "/domain/Region": { "$source": { "variable": variable.id().to_string() } },
+            "/domain/Other": "other",
+        }),
+        serde_json::to_value(AttributeSources::from(
+            Component::sources(ctx, component_id).await?
+        ))?
+    );
+
+    // Changing the variable's value flows through to the attribute
+    let variable = variable.set_value(ctx, json!("us-west-2")).await?;
+    change_set::commit(ctx).await?;
+    assert_eq!(
+        json!({
+            "Region": "us-west-2",
+            "Other": "other",
+        }),
+        component::domain(ctx, "test").await?
+    );
+
+    // Renaming the variable does not affect the attribute, which references it by id
+    let variable = variable
+        .update_metadata(ctx, "production.region", None)
+        .await?;
+    change_set::commit(ctx).await?;
+    assert_eq!(
+        json!({
+            "Region": "us-west-2",
+            "Other": "other",
+        }),
+        component::domain(ctx, "test").await?
+    );
+    assert_eq!(
+        vec![component_id],
+        Variable::list_dependent_component_ids(ctx, variable.id()).await?
+    );
+
+    // The variable cannot be removed while it is in use
+    assert!(matches!(
+        Variable::remove(ctx, variable.id()).await,
+        Err(VariableError::VariableInUse(..))
+    ));
+
+    // Once nothing uses it, it can be removed
+    attributes::update_attributes(
+        ctx,
+        component_id,
+        serde_json::from_value(json!({
+            "/domain/Region": "eu-west-1",
+        }))?,
+    )
+    .await?;
+    Variable::remove(ctx, variable.id()).await?;
+    change_set::commit(ctx).await?;
+    assert!(Variable::list(ctx).await?.is_empty());
+    assert_eq!(
+        json!({
+            "Region": "eu-west-1",
+            "Other": "other",
+        }),
+        component::domain(ctx, "test").await?
+    );
+
+    Ok(())
+}
+
+// Test that variable names are validated and must be unique.
+#[test]
+async fn variable_names(ctx: &DalContext) -> Result<()> {
+    Variable::new(ctx, "prod.region", json!("us-east-1"), None).await?;
+
+    assert!(matches!(
+        Variable::new(ctx, "prod.region", json!("us-west-2"), None).await,
+        Err(VariableError::NameAlreadyInUse(..))
+    ));
+    assert!(matches!(
+        Variable::new(ctx, "", json!(1), None).await,
+        Err(VariableError::InvalidName(..))
+    ));
+    assert!(matches!(
+        Variable::new(ctx, "has space", json!(1), None).await,
+        Err(VariableError::InvalidName(..))
+    ));
+
+    let variable = Variable::find_by_name(ctx, "prod.region")
+        .await?
+        .expect("variable should exist");
+    assert_eq!(&json!("us-east-1"), variable.value());
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use dal::DalContext;
+use dal_test::{
+    Result,
+    helpers::create_component_for_default_schema_name_in_default_view,
+    test,
+};
+use pretty_assertions_sorted::assert_eq;
+use ring::hmac;
+use si_db::{
+    WorkspaceWebhook,
+    WorkspaceWebhookError,
+};
+use si_id::ManagementPrototypeId;
+
+fn sign(signing_secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, signing_secret.as_bytes());
+    format!("sha256={}", hex::encode(hmac::sign(&key, body)))
+}
+
+#[test]
+async fn verify_signed_delivery(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "lego")
+            .await?;
+    let (webhook, signing_secret) = WorkspaceWebhook::new(
+        ctx,
+        ctx.symmetric_crypto_service(),
+        "github-push".to_string(),
+        component.id(),
+        ManagementPrototypeId::new(),
+    )
+    .await?;
+    ctx.commit_no_rebase().await?;
+
+    let webhook = WorkspaceWebhook::get_for_workspace(ctx, ctx.workspace_pk()?, webhook.id)
+        .await?
+        .expect("webhook should exist");
+    let body = br#"{"ref":"refs/heads/main"}"#;
+    let signature = sign(&signing_secret, body);
+
+    assert!(webhook.verify_signature(ctx.symmetric_crypto_service(), body, &signature)?);
+    assert!(!webhook.verify_signature(
+        ctx.symmetric_crypto_service(),
+        br#"{"ref":"refs/heads/evil"}"#,
+        &signature,
+    )?);
+    assert!(!webhook.verify_signature(
+        ctx.symmetric_crypto_service(),
+        body,
+        &sign("not the secret", body),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+async fn signing_secret_is_encrypted_at_rest(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "lego")
+            .await?;
+    let (webhook, signing_secret) = WorkspaceWebhook::new(
+        ctx,
+        ctx.symmetric_crypto_service(),
+        "github-push".to_string(),
+        component.id(),
+        ManagementPrototypeId::new(),
+    )
+    .await?;
+
+    let row = ctx
+        .txns()
+        .await?
+        .pg()
+        .query_one(
+            "SELECT * FROM workspace_webhooks WHERE id = $1",
+            &[&webhook.id],
+        )
+        .await?;
+    let crypted: Vec<u8> = row.try_get("signing_secret_crypted")?;
+    assert!(!crypted.is_empty());
+    assert!(
+        !crypted
+            .windows(signing_secret.len())
+            .any(|window| window == signing_secret.as_bytes())
+    );
+
+    Ok(())
+}
+
+#[test]
+async fn webhook_names_are_unique_in_a_workspace(ctx: &mut DalContext) -> Result<()> {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "lego")
+            .await?;
+    let (webhook, _) = WorkspaceWebhook::new(
+        ctx,
+        ctx.symmetric_crypto_service(),
+        "github-push".to_string(),
+        component.id(),
+        ManagementPrototypeId::new(),
+    )
+    .await?;
+
+    let result = WorkspaceWebhook::new(
+        ctx,
+        ctx.symmetric_crypto_service(),
+        "github-push".to_string(),
+        component.id(),
+        ManagementPrototypeId::new(),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceWebhookError::NameAlreadyInUse(name)) if name == "github-push"
+    ));
+
+    let webhooks = WorkspaceWebhook::list(ctx).await?;
+    assert_eq!(
+        vec![webhook.id],
+        webhooks
+            .iter()
+            .map(|webhook| webhook.id)
+            .collect::<Vec<_>>()
+    );
+
+    assert!(WorkspaceWebhook::delete(ctx, webhook.id).await?);
+    assert!(WorkspaceWebhook::list(ctx).await?.is_empty());
+
+    Ok(())
+}
@@ -12,6 +12,10 @@ use edda_core::{
             NewChangeSetRequest,
             NewChangeSetRequestVCurrent,
         },
+        rebuild_changed_definitions_request::{
+            RebuildChangedDefinitionsRequest,
+            RebuildChangedDefinitionsRequestVCurrent,
+        },
         rebuild_request::{
             RebuildRequest,
             RebuildRequestVCurrent,
@@ -154,6 +158,38 @@ impl Client {
         .await
     }
 
+    /// Requests that only the materialized views whose definitions have changed since the change
+    /// set's index was built are rebuilt.
+    #[instrument(
+        name = "edda.client.rebuild_changed_definitions_for_change_set"
+        level = "info",
+        skip_all,
+        fields (
+            si.workspace.id = %workspace_id,
+            si.change_set.id = %change_set_id,
+        )
+    )]
+    pub async fn rebuild_changed_definitions_for_change_set(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+    ) -> Result<RequestId> {
+        let id = RequestId::new();
+        let request =
+            RebuildChangedDefinitionsRequest::new(RebuildChangedDefinitionsRequestVCurrent { id });
+        let mut info = ContentInfo::from(&request);
+        let (content_type, payload) = request.to_vec()?;
+        info.content_type = content_type.into();
+
+        self.publish_inner(
+            Some(ChangeSetLocator::new(workspace_id, change_set_id)),
+            id,
+            payload.into(),
+            info,
+        )
+        .await
+    }
+
     #[instrument(
         name = "edda.client.new_change_set"
         level = "info",
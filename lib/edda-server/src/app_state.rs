@@ -9,6 +9,7 @@ use si_data_nats::{
     NatsClient,
     async_nats::jetstream,
 };
+use tokio::sync::Semaphore;
 use tokio_util::{
     sync::CancellationToken,
     task::TaskTracker,
@@ -27,6 +28,7 @@ pub(crate) struct AppState {
     pub(crate) frigg: FriggStore,
     pub(crate) edda_updates: EddaUpdates,
     pub(crate) parallel_build_limit: usize,
+    pub(crate) definition_migration_permits: Arc<Semaphore>,
     pub(crate) requests_stream: jetstream::stream::Stream,
     pub(crate) ctx_builder: DalContextBuilder,
     pub(crate) quiescent_period: Duration,
@@ -43,6 +45,7 @@ impl AppState {
         frigg: FriggStore,
         edda_updates: EddaUpdates,
         parallel_build_limit: usize,
+        definition_migration_permits: Arc<Semaphore>,
         requests_stream: jetstream::stream::Stream,
         ctx_builder: DalContextBuilder,
         quiescent_period: Duration,
@@ -55,6 +58,7 @@ impl AppState {
             frigg,
             edda_updates,
             parallel_build_limit,
+            definition_migration_permits,
             requests_stream,
             ctx_builder,
             quiescent_period,
@@ -49,6 +49,7 @@ use thiserror::Error;
 use tokio::{
     sync::{
         Notify,
+        Semaphore,
         watch,
     },
     time,
@@ -67,6 +68,7 @@ use crate::{
         CompressedChangeSetRequest,
     },
     compressing_stream::CompressingStream,
+    materialized_view::MvReadSets,
     updates::EddaUpdates,
 };
 
@@ -86,6 +88,7 @@ pub(crate) struct ChangeSetProcessorTask {
     _metadata: Arc<ServerMetadata>,
     workspace_id: WorkspacePk,
     change_set_id: ChangeSetId,
+    frigg: FriggStore,
     inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
 }
 
@@ -100,6 +103,7 @@ impl ChangeSetProcessorTask {
         frigg: FriggStore,
         edda_updates: EddaUpdates,
         parallel_build_limit: usize,
+        definition_migration_permits: Arc<Semaphore>,
         workspace_id: WorkspacePk,
         change_set_id: ChangeSetId,
         ctx_builder: DalContextBuilder,
@@ -118,9 +122,11 @@ impl ChangeSetProcessorTask {
             workspace_id,
             change_set_id,
             nats,
-            frigg,
+            frigg.clone(),
             edda_updates,
+            MvReadSets::new(),
             parallel_build_limit,
+            definition_migration_permits,
             ctx_builder,
             server_tracker,
         );
@@ -201,6 +207,7 @@ impl ChangeSetProcessorTask {
             _metadata: metadata,
             workspace_id,
             change_set_id,
+            frigg,
             inner: Box::new(inner_fut),
         }
     }
@@ -209,6 +216,22 @@ impl ChangeSetProcessorTask {
         self.inner.await.map_err(Error::Naxum)?;
         metric!(counter.change_set_processor_task.change_set_task = -1);
 
+        // Now that the change set has gone quiet, drop any index versions that have aged out of
+        // the retention period. This is best effort, as the next task for this change set will
+        // try again.
+        if let Err(err) = self
+            .frigg
+            .prune_change_set_index_history(self.workspace_id, self.change_set_id)
+            .await
+        {
+            warn!(
+                si.error.message = ?err,
+                si.workspace.id = %self.workspace_id,
+                si.change_set.id = %self.change_set_id,
+                "failed to prune change set index history",
+            );
+        }
+
         debug!(
             task = Self::NAME,
             si.workspace.id = %self.workspace_id,
@@ -319,6 +342,7 @@ mod handlers {
         Ulid,
         WorkspacePk,
         WorkspaceSnapshotAddress,
+        variable::Variable,
     };
     use frigg::FriggStore;
     use naxum::{
@@ -345,7 +369,10 @@ mod handlers {
     use super::app_state::AppState;
     use crate::{
         api_types::change_set_request::CompressedChangeSetRequest,
-        materialized_view,
+        materialized_view::{
+            self,
+            MvReadSets,
+        },
         updates::EddaUpdates,
     };
 
@@ -362,6 +389,9 @@ mod handlers {
         /// When failing to create a DAL context
         #[error("error creating a dal ctx: {0}")]
         DalTransactions(#[from] dal::TransactionsError),
+        /// When the permits for definition migrations are no longer available
+        #[error("definition migration permit error: {0}")]
+        DefinitionMigrationPermit(#[from] tokio::sync::AcquireError),
         #[error("frigg error: {0}")]
         Frigg(#[from] frigg::Error),
         #[error("layerdb error: {0}")]
@@ -370,6 +400,8 @@ mod handlers {
         MaterializedView(#[from] materialized_view::MaterializedViewError),
         #[error("schema error: {0}")]
         Schema(#[from] dal::SchemaError),
+        #[error("variable error: {0}")]
+        Variable(#[from] dal::variable::VariableError),
         /// When failing to find the workspace
         #[error("workspace error: {0}")]
         Workspace(#[from] dal::WorkspaceError),
@@ -403,7 +435,9 @@ mod handlers {
             nats: _,
             frigg,
             edda_updates,
+            mv_read_sets,
             parallel_build_limit,
+            definition_migration_permits,
             ctx_builder,
             server_tracker: _,
         } = state;
@@ -431,7 +465,9 @@ mod handlers {
             &ctx,
             &frigg,
             &edda_updates,
+            &mv_read_sets,
             parallel_build_limit,
+            &definition_migration_permits,
             subject,
             workspace_id,
             change_set_id,
@@ -459,7 +495,9 @@ mod handlers {
         ctx: &DalContext,
         frigg: &FriggStore,
         edda_updates: &EddaUpdates,
+        mv_read_sets: &MvReadSets,
         parallel_build_limit: usize,
+        definition_migration_permits: &Semaphore,
         subject: Subject,
         workspace_id: WorkspacePk,
         change_set_id: ChangeSetId,
@@ -509,6 +547,7 @@ mod handlers {
                         ctx,
                         frigg,
                         edda_updates,
+                        mv_read_sets,
                         parallel_build_limit,
                         change_set_id,
                         to_snapshot_address, // both snapshot addrs will use `to`
@@ -523,6 +562,7 @@ mod handlers {
                         ctx,
                         frigg,
                         edda_updates,
+                        mv_read_sets,
                         parallel_build_limit,
                         None,
                         "explicit rebuild",
@@ -537,6 +577,7 @@ mod handlers {
                     ctx,
                     frigg,
                     edda_updates,
+                    mv_read_sets,
                     parallel_build_limit,
                     None,
                     "explicit rebuild",
@@ -545,16 +586,16 @@ mod handlers {
                 .map_err(Into::into)
             }
             CompressedChangeSetRequest::RebuildChangedDefinitions { .. } => {
-                // Rebuild only change set MVs with outdated definition checksums
-                // Since we're not processing incremental changes, use build_all_mv_for_change_set
-                // which will detect and rebuild only outdated definitions
-                materialized_view::build_all_mv_for_change_set(
+                // Rebuild only change set MVs with outdated definition checksums. These are
+                // requested in the background for every change set that is read after a deploy,
+                // so limit how many run at once across all workspaces.
+                let _permit = definition_migration_permits.acquire().await?;
+                materialized_view::migrate_outdated_mv_for_change_set(
                     ctx,
                     frigg,
                     edda_updates,
+                    mv_read_sets,
                     parallel_build_limit,
-                    None,
-                    "selective rebuild based on definition checksums",
                 )
                 .await
                 .map_err(Into::into)
@@ -567,7 +608,7 @@ mod handlers {
             } => {
                 // Index exists
                 if frigg
-                    .get_change_set_index(workspace_id, change_set_id)
+                    .get_change_set_index_allowing_outdated(workspace_id, change_set_id)
                     .await
                     .map_err(|err| span.record_err(err))?
                     .is_some()
@@ -596,6 +637,7 @@ mod handlers {
                         ctx,
                         frigg,
                         edda_updates,
+                        mv_read_sets,
                         parallel_build_limit,
                         change_set_id,
                         from_snapshot_address,
@@ -614,6 +656,7 @@ mod handlers {
                         ctx,
                         frigg,
                         edda_updates,
+                        mv_read_sets,
                         parallel_build_limit,
                         None,
                         build_reason,
@@ -631,7 +674,8 @@ mod handlers {
     /// means we should report a change in some other thing, even though that thing
     /// has not actually changed. For example, if an overlay function has been added
     /// to a schema, we need to recalculate the materialized views for the schema
-    /// variants under that schema.
+    /// variants under that schema. Likewise, renaming a variable changes the incoming
+    /// connections of every component that uses it.
     #[instrument(
         level = "info",
         name = "edda.requests.change_set.process.post_process_changes",
@@ -641,6 +685,8 @@ mod handlers {
         let mut overlay_category_changed = false;
         let mut changed_schemas = BTreeSet::new();
         let mut changed_variants = BTreeSet::new();
+        let mut changed_variables = BTreeSet::new();
+        let mut changed_components = BTreeSet::new();
         for change in changes.iter() {
             match change.entity_kind {
                 EntityKind::CategoryOverlay => {
@@ -654,6 +700,14 @@ mod handlers {
                     let id: Ulid = change.entity_id.into();
                     changed_variants.insert(id);
                 }
+                EntityKind::Variable => {
+                    let id: Ulid = change.entity_id.into();
+                    changed_variables.insert(id);
+                }
+                EntityKind::Component => {
+                    let id: Ulid = change.entity_id.into();
+                    changed_components.insert(id);
+                }
                 _ => {}
             }
         }
@@ -682,6 +736,36 @@ mod handlers {
             }
         }
 
+        for changed_variable_id in changed_variables {
+            // A removed variable has no dependents left to report.
+            if !ctx
+                .workspace_snapshot()?
+                .node_exists(changed_variable_id)
+                .await
+            {
+                continue;
+            }
+            let component_ids =
+                Variable::list_dependent_component_ids(ctx, changed_variable_id.into()).await?;
+            for component_id in component_ids {
+                let component_ulid: Ulid = component_id.into();
+                if !changed_components.insert(component_ulid) {
+                    continue;
+                }
+                let merkle_tree_hash = ctx
+                    .workspace_snapshot()?
+                    .get_node_weight(component_id)
+                    .await?
+                    .merkle_tree_hash();
+
+                changes.push(Change {
+                    entity_id: component_ulid.into(),
+                    entity_kind: EntityKind::Component,
+                    merkle_tree_hash,
+                });
+            }
+        }
+
         Ok(())
     }
 
@@ -690,6 +774,7 @@ mod handlers {
         ctx: &DalContext,
         frigg: &FriggStore,
         edda_updates: &EddaUpdates,
+        mv_read_sets: &MvReadSets,
         parallel_build_limit: usize,
         change_set_id: ChangeSetId,
         from_snapshot_address: WorkspaceSnapshotAddress,
@@ -716,6 +801,7 @@ mod handlers {
             ctx,
             frigg,
             edda_updates,
+            mv_read_sets,
             parallel_build_limit,
             change_set_id,
             from_snapshot_address,
@@ -750,6 +836,8 @@ mod handlers {
 mod app_state {
     //! Application state for a change set processor.
 
+    use std::sync::Arc;
+
     use dal::DalContextBuilder;
     use frigg::FriggStore;
     use si_data_nats::NatsClient;
@@ -757,9 +845,13 @@ mod app_state {
         ChangeSetId,
         WorkspacePk,
     };
+    use tokio::sync::Semaphore;
     use tokio_util::task::TaskTracker;
 
-    use crate::updates::EddaUpdates;
+    use crate::{
+        materialized_view::MvReadSets,
+        updates::EddaUpdates,
+    };
 
     /// Application state.
     #[derive(Clone, Debug)]
@@ -775,8 +867,12 @@ mod app_state {
         pub(crate) frigg: FriggStore,
         /// Publishes patch and index update messages
         pub(crate) edda_updates: EddaUpdates,
+        /// Read sets of the materialized views built for the change set
+        pub(crate) mv_read_sets: MvReadSets,
         /// Parallelism limit for materialized view builds
         pub(crate) parallel_build_limit: usize,
+        /// Limits how many change sets migrate outdated materialized view definitions at once
+        pub(crate) definition_migration_permits: Arc<Semaphore>,
         /// DAL context builder for each processing request
         pub(crate) ctx_builder: DalContextBuilder,
         /// A task tracker for server-level tasks that can outlive the lifetime of a change set
@@ -794,7 +890,9 @@ mod app_state {
             nats: NatsClient,
             frigg: FriggStore,
             edda_updates: EddaUpdates,
+            mv_read_sets: MvReadSets,
             parallel_build_limit: usize,
+            definition_migration_permits: Arc<Semaphore>,
             ctx_builder: DalContextBuilder,
             server_tracker: TaskTracker,
         ) -> Self {
@@ -804,7 +902,9 @@ mod app_state {
                 nats,
                 frigg,
                 edda_updates,
+                mv_read_sets,
                 parallel_build_limit,
+                definition_migration_permits,
                 ctx_builder,
                 server_tracker,
             }
@@ -29,10 +29,15 @@ use ulid::Ulid;
 
 const DEFAULT_CONCURRENCY_LIMIT: Option<usize> = None;
 const DEFAULT_PARALLEL_BUILD_LIMIT: usize = 50;
+const DEFAULT_DEFINITION_MIGRATION_LIMIT: usize = 4;
 
 const DEFAULT_QUIESCENT_PERIOD_SECS: u64 = 60 * 10;
 const DEFAULT_QUIESCENT_PERIOD: Duration = Duration::from_secs(DEFAULT_QUIESCENT_PERIOD_SECS);
 
+const DEFAULT_INDEX_HISTORY_RETENTION_SECS: u64 = 60 * 60 * 24 * 14;
+const DEFAULT_INDEX_HISTORY_RETENTION: Duration =
+    Duration::from_secs(DEFAULT_INDEX_HISTORY_RETENTION_SECS);
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -68,6 +73,9 @@ pub struct Config {
     #[builder(default = "default_parallel_build_limit()")]
     parallel_build_limit: usize,
 
+    #[builder(default = "default_definition_migration_limit()")]
+    definition_migration_limit: usize,
+
     #[builder(default = "default_streaming_patches()")]
     streaming_patches: bool,
 
@@ -89,6 +97,9 @@ pub struct Config {
     #[builder(default = "default_quiescent_period()")]
     quiescent_period: Duration,
 
+    #[builder(default = "default_index_history_retention()")]
+    index_history_retention: Duration,
+
     #[builder(default = "default_service_endpoints_config()")]
     service_endpoints: ServiceEndpointsConfig,
 }
@@ -108,6 +119,12 @@ impl Config {
         self.parallel_build_limit
     }
 
+    /// Gets the config's limit on how many change sets can have their outdated materialized view
+    /// definitions rebuilt at the same time.
+    pub fn definition_migration_limit(&self) -> usize {
+        self.definition_migration_limit
+    }
+
     /// Gets whether edda should stream patches, or send as a single batch.
     pub fn streaming_patches(&self) -> bool {
         self.streaming_patches
@@ -157,6 +174,11 @@ impl Config {
         self.quiescent_period
     }
 
+    /// Gets how long past versions of change set indexes are kept for point-in-time reads
+    pub fn index_history_retention(&self) -> Duration {
+        self.index_history_retention
+    }
+
     /// Gets a reference to the config's service endpoints configuration.
     #[must_use]
     pub fn service_endpoints(&self) -> &ServiceEndpointsConfig {
@@ -175,6 +197,8 @@ pub struct ConfigFile {
     edda_concurrency_limit: Option<usize>,
     #[serde(default = "default_parallel_build_limit")]
     edda_parallel_build_limit: usize,
+    #[serde(default = "default_definition_migration_limit")]
+    definition_migration_limit: usize,
     #[serde(default = "default_streaming_patches")]
     streaming_patches: bool,
     #[serde(default)]
@@ -189,6 +213,8 @@ pub struct ConfigFile {
     layer_db_config: LayerDbConfig,
     #[serde(default = "default_quiescent_period_secs")]
     quiescent_period_secs: u64,
+    #[serde(default = "default_index_history_retention_secs")]
+    index_history_retention_secs: u64,
     #[serde(default = "default_service_endpoints_config")]
     service_endpoints: ServiceEndpointsConfig,
 }
@@ -199,6 +225,7 @@ impl Default for ConfigFile {
             instance_id: random_instance_id(),
             edda_concurrency_limit: default_concurrency_limit(),
             edda_parallel_build_limit: default_parallel_build_limit(),
+            definition_migration_limit: default_definition_migration_limit(),
             streaming_patches: default_streaming_patches(),
             pg: Default::default(),
             nats: Default::default(),
@@ -206,6 +233,7 @@ impl Default for ConfigFile {
             symmetric_crypto_service: default_symmetric_crypto_config(),
             layer_db_config: default_layer_db_config(),
             quiescent_period_secs: default_quiescent_period_secs(),
+            index_history_retention_secs: default_index_history_retention_secs(),
             service_endpoints: default_service_endpoints_config(),
         }
     }
@@ -230,8 +258,10 @@ impl TryFrom<ConfigFile> for Config {
         config.concurrency_limit(value.edda_concurrency_limit);
         config.streaming_patches(value.streaming_patches);
         config.parallel_build_limit(value.edda_parallel_build_limit);
+        config.definition_migration_limit(value.definition_migration_limit);
         config.instance_id(value.instance_id);
         config.quiescent_period(Duration::from_secs(value.quiescent_period_secs));
+        config.index_history_retention(Duration::from_secs(value.index_history_retention_secs));
         config.service_endpoints(value.service_endpoints);
         config.build().map_err(Into::into)
     }
@@ -249,6 +279,10 @@ fn default_parallel_build_limit() -> usize {
     DEFAULT_PARALLEL_BUILD_LIMIT
 }
 
+fn default_definition_migration_limit() -> usize {
+    DEFAULT_DEFINITION_MIGRATION_LIMIT
+}
+
 fn default_streaming_patches() -> bool {
     false
 }
@@ -273,6 +307,14 @@ fn default_quiescent_period_secs() -> u64 {
     DEFAULT_QUIESCENT_PERIOD_SECS
 }
 
+fn default_index_history_retention() -> Duration {
+    DEFAULT_INDEX_HISTORY_RETENTION
+}
+
+fn default_index_history_retention_secs() -> u64 {
+    DEFAULT_INDEX_HISTORY_RETENTION_SECS
+}
+
 fn default_service_endpoints_config() -> ServiceEndpointsConfig {
     ServiceEndpointsConfig::new(0)
 }
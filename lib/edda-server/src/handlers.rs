@@ -128,6 +128,7 @@ async fn run_deployment_processor_task(state: AppState, subject_str: &str) -> Re
         frigg,
         edda_updates,
         parallel_build_limit,
+        definition_migration_permits: _,
         requests_stream,
         ctx_builder,
         quiescent_period,
@@ -272,6 +273,7 @@ async fn run_change_set_processor_task(
         frigg,
         edda_updates,
         parallel_build_limit,
+        definition_migration_permits,
         requests_stream,
         ctx_builder,
         quiescent_period,
@@ -317,6 +319,7 @@ async fn run_change_set_processor_task(
         frigg,
         edda_updates,
         parallel_build_limit,
+        definition_migration_permits,
         workspace.id,
         change_set.id,
         ctx_builder,
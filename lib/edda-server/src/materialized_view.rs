@@ -1,5 +1,8 @@
 use std::{
-    collections::BinaryHeap,
+    collections::{
+        BinaryHeap,
+        HashSet,
+    },
     future::Future,
     sync::Arc,
     time::Duration,
@@ -24,6 +27,10 @@ use dal::{
         SlowRuntimeError,
     },
 };
+use dal_materialized_views::read_set::{
+    ReadSet,
+    ReadSetRecorder,
+};
 use frigg::{
     FriggError,
     FriggStore,
@@ -98,17 +105,25 @@ use crate::updates::{
 
 pub mod change_set;
 pub mod deployment;
+pub mod read_sets;
 
 // Re-export public functions from submodules
 pub use change_set::{
     build_all_mv_for_change_set,
     build_mv_for_changes_in_change_set,
+    migrate_outdated_mv_for_change_set,
     try_reuse_mv_index_for_new_change_set,
 };
 pub use deployment::{
     build_all_mvs_for_deployment,
     build_outdated_mvs_for_deployment,
 };
+pub use read_sets::MvReadSets;
+use read_sets::{
+    ReadSetsByMv,
+    RecordedReadSet,
+    can_skip_build,
+};
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -177,6 +192,7 @@ macro_rules! spawn_build_mv_task {
 pub type BuildMvInnerReturn = (
     Vec<FrontendObject>,
     Vec<ObjectPatch>,
+    Vec<RecordedReadSet>,
     u128,
     Duration,
     Duration,
@@ -191,8 +207,10 @@ pub type BuildMvInnerReturn = (
     fields(
         si.workspace.id = %workspace_pk,
         si.change_set_id = %change_set_id,
+        si.edda.mv.skipped_count = Empty,
     ),
 )]
+#[allow(clippy::too_many_arguments)]
 pub async fn build_mv_inner(
     ctx: &DalContext,
     frigg: &FriggStore,
@@ -201,23 +219,57 @@ pub async fn build_mv_inner(
     workspace_pk: si_id::WorkspacePk,
     change_set_id: ChangeSetId,
     changes: &[Change],
+    outdated_changes: &[Change],
+    outdated_mv_kinds: &HashSet<ReferenceKind>,
+    previous_read_sets: &ReadSetsByMv,
 ) -> Result<BuildMvInnerReturn, MaterializedViewError> {
+    let span = current_span_for_instrument_at!("debug");
+
     let mut frontend_objects = Vec::new();
     let mut patches = Vec::new();
+    let mut recorded_read_sets = Vec::new();
     let mut build_tasks = JoinSet::new();
     let mut queued_mv_builds = BinaryHeap::new();
 
     let maybe_mv_index = Arc::new(
         frigg
-            .get_change_set_index(workspace_pk, change_set_id)
+            .get_change_set_index_allowing_outdated(workspace_pk, change_set_id)
             .await?
             .map(|r| r.0),
     );
 
+    let changed_entity_ids: HashSet<EntityId> =
+        changes.iter().map(|change| change.entity_id).collect();
+    let workspace_snapshot = ctx.workspace_snapshot()?;
+    let node_hash = async |id: EntityId| {
+        workspace_snapshot
+            .get_node_weight_opt(id)
+            .await
+            .map(|node_weight| node_weight.node_hash())
+    };
+    let mut skipped_count: usize = 0;
+    let mut queued_mv_kinds = HashSet::new();
+
     // Queue everything so we can let the priority queue determine the order everything is built.
     for &change in changes {
         for mv_inventory_item in ::inventory::iter::<MaterializedViewInventoryItem>() {
             if mv_inventory_item.should_build_for_change(change) {
+                // If the previous build of this MV recorded what it read, and none of it has
+                // changed, the MV in the index is still up to date.
+                if can_skip_build(
+                    previous_read_sets,
+                    mv_inventory_item.kind(),
+                    change.entity_id,
+                    &changed_entity_ids,
+                    &node_hash,
+                )
+                .await
+                {
+                    skipped_count += 1;
+                    continue;
+                }
+
+                queued_mv_kinds.insert((change.entity_id, mv_inventory_item.kind()));
                 queued_mv_builds.push(QueuedBuildMvTask {
                     change,
                     mv_kind: mv_inventory_item.kind(),
@@ -226,6 +278,23 @@ pub async fn build_mv_inner(
             }
         }
     }
+    // Entities that trigger an outdated MV kind only need that kind rebuilt, and can't rely on a
+    // read set recorded with the previous definition.
+    for &change in outdated_changes {
+        for mv_inventory_item in ::inventory::iter::<MaterializedViewInventoryItem>() {
+            if outdated_mv_kinds.contains(&mv_inventory_item.kind())
+                && mv_inventory_item.should_build_for_change(change)
+                && queued_mv_kinds.insert((change.entity_id, mv_inventory_item.kind()))
+            {
+                queued_mv_builds.push(QueuedBuildMvTask {
+                    change,
+                    mv_kind: mv_inventory_item.kind(),
+                    priority: mv_inventory_item.build_priority(),
+                });
+            }
+        }
+    }
+    span.record("si.edda.mv.skipped_count", skipped_count);
 
     let mut build_total_elapsed = Duration::from_nanos(0);
     let mut build_count: u128 = 0;
@@ -265,13 +334,28 @@ pub async fn build_mv_inner(
         }
 
         if let Some(join_result) = build_tasks.join_next().await {
-            let (kind, mv_id, build_duration, entity_id, entity_kind, execution_result) =
-                join_result?;
+            let (
+                kind,
+                mv_id,
+                build_duration,
+                entity_id,
+                entity_kind,
+                maybe_read_set,
+                execution_result,
+            ) = join_result?;
             metric!(
                 counter.edda.mv_build = -1,
                 label = format!("{workspace_pk}:{change_set_id}:{kind}")
             );
 
+            // Only keep the read set of a successful build, so that a failed build is always
+            // retried on the next change.
+            recorded_read_sets.push((
+                kind,
+                entity_id,
+                maybe_read_set.filter(|_| execution_result.is_ok()),
+            ));
+
             match execution_result {
                 Ok((maybe_patch, maybe_frontend_object)) => {
                     // We need to make sure the frontend object is inserted into the store first so that
@@ -317,6 +401,7 @@ pub async fn build_mv_inner(
     Ok((
         frontend_objects,
         patches,
+        recorded_read_sets,
         build_count,
         build_total_elapsed,
         build_max_elapsed,
@@ -330,6 +415,7 @@ pub type BuildMvTaskResult = (
     Duration,
     EntityId,
     EntityKind,
+    Option<ReadSet>,
     Result<(Option<ObjectPatch>, Option<FrontendObject>), MaterializedViewError>,
 );
 
@@ -359,13 +445,14 @@ where
     debug!(kind = %mv_kind, id = %mv_id, "Building MV");
     let start = Instant::now();
 
+    let read_set_recorder = ReadSetRecorder::new();
     let result = build_mv_for_graph_task_inner(
         &ctx,
         &frigg,
         change,
         mv_id.clone(),
         mv_kind,
-        build_mv_future,
+        read_set_recorder.track(build_mv_future),
         maybe_mv_index,
     )
     .await;
@@ -376,6 +463,7 @@ where
         start.elapsed(),
         change.entity_id,
         change.entity_kind,
+        read_set_recorder.finish(),
         result,
     )
 }
@@ -11,7 +11,10 @@ use si_events::{
     workspace_snapshot::Change,
 };
 use si_frontend_mv_types::{
-    definition_checksum::materialized_view_definition_checksums,
+    definition_checksum::{
+        materialized_view_definition_checksums,
+        outdated_definition_kinds,
+    },
     index::change_set::ChangeSetMvIndexV2,
     object::{
         FrontendObject,
@@ -27,15 +30,13 @@ use si_frontend_mv_types::{
         ReferenceKind,
     },
 };
-use si_id::{
-    ChangeSetId,
-    EntityId,
-};
+use si_id::ChangeSetId;
 use telemetry::prelude::*;
 
 use crate::{
     materialized_view::{
         MaterializedViewError,
+        MvReadSets,
         build_mv_inner,
     },
     updates::EddaUpdates,
@@ -137,6 +138,7 @@ pub async fn build_all_mv_for_change_set(
     ctx: &DalContext,
     frigg: &FriggStore,
     edda_updates: &EddaUpdates,
+    mv_read_sets: &MvReadSets,
     parallel_build_limit: usize,
     from_index_checksum: Option<String>,
     reason_message: &'static str,
@@ -154,6 +156,7 @@ pub async fn build_all_mv_for_change_set(
     let (
         frontend_objects,
         patches,
+        recorded_read_sets,
         build_count,
         build_total_elapsed,
         build_max_elapsed,
@@ -166,6 +169,9 @@ pub async fn build_all_mv_for_change_set(
         ctx.workspace_pk()?,
         ctx.change_set_id(),
         &changes,
+        &[],
+        &HashSet::new(),
+        &Default::default(),
     )
     .await?;
     span.record("si.edda.mv.count", build_count);
@@ -213,6 +219,9 @@ pub async fn build_all_mv_for_change_set(
             &mv_index_frontend_object,
         )
         .await?;
+    mv_read_sets
+        .replace(to_index_checksum, recorded_read_sets)
+        .await;
 
     edda_updates
         .publish_change_set_patch_batch(patch_batch)
@@ -264,6 +273,7 @@ pub async fn build_mv_for_changes_in_change_set(
     ctx: &DalContext,
     frigg: &FriggStore,
     edda_updates: &EddaUpdates,
+    mv_read_sets: &MvReadSets,
     parallel_build_limit: usize,
     change_set_id: ChangeSetId,
     from_snapshot_address: WorkspaceSnapshotAddress,
@@ -275,8 +285,10 @@ pub async fn build_mv_for_changes_in_change_set(
     let span = current_span_for_instrument_at!("info");
     span.record("si.workspace.id", workspace_id.to_string());
 
-    let (index_frontend_object, index_kv_revision) = frigg
-        .get_change_set_index(ctx.workspace_pk()?, change_set_id)
+    // An index built for outdated MV definitions is still used as the base of an incremental
+    // build, with the outdated kinds rebuilt alongside the explicit changes.
+    let (index_frontend_object, index_kv_revision, _outdated_kinds) = frigg
+        .get_change_set_index_allowing_outdated(ctx.workspace_pk()?, change_set_id)
         .await?
         .ok_or_else(|| MaterializedViewError::NoIndexForIncrementalBuild {
             workspace_pk: workspace_id,
@@ -291,6 +303,7 @@ pub async fn build_mv_for_changes_in_change_set(
                 ctx,
                 frigg,
                 edda_updates,
+                mv_read_sets,
                 parallel_build_limit,
                 Some(index_frontend_object.checksum.clone()),
                 "fallback due to index upgrade",
@@ -305,6 +318,7 @@ pub async fn build_mv_for_changes_in_change_set(
                 ctx,
                 frigg,
                 edda_updates,
+                mv_read_sets,
                 parallel_build_limit,
                 Some(index_frontend_object.checksum.clone()),
                 "fallback due to index parse error",
@@ -313,44 +327,31 @@ pub async fn build_mv_for_changes_in_change_set(
         }
     };
 
-    // Always check for outdated definitions and combine with explicit changes
-    let (outdated_changes, outdated_mv_kind_count) =
+    // Always check for outdated definitions and build them alongside the explicit changes
+    let (outdated_changes, outdated_mv_kinds) =
         get_changes_for_outdated_definitions(ctx, &mv_index).await?;
     debug!(
         "building for outdated definition changes: {:?}",
         outdated_changes
     );
-    span.record("si.edda.mv.outdated_mv.kind_count", outdated_mv_kind_count);
-
-    // Combine and deduplicate changes by entity_id
-    let mut combined_changes_map: std::collections::HashMap<EntityId, Change> =
-        std::collections::HashMap::new();
-
-    // Add explicit changes
-    for &change in changes {
-        combined_changes_map.insert(change.entity_id, change);
-    }
-
-    // Add outdated definition changes (this will naturally deduplicate by entity_id)
-    for change in outdated_changes {
-        combined_changes_map.insert(change.entity_id, change);
-    }
-
-    let combined_changes: Vec<Change> = combined_changes_map.into_values().collect();
-    span.record("si.edda.mv.combined_changes.count", combined_changes.len());
-
-    debug!("combined deduplicated changes: {:?}", combined_changes);
+    span.record("si.edda.mv.outdated_mv.kind_count", outdated_mv_kinds.len());
+    span.record(
+        "si.edda.mv.combined_changes.count",
+        changes.len() + outdated_changes.len(),
+    );
 
-    // If no changes after deduplication, we are done
-    if combined_changes.is_empty() {
+    // If there are neither explicit changes nor outdated definitions, we are done
+    if changes.is_empty() && outdated_changes.is_empty() {
         debug!("No changes to process");
         return Ok(());
     }
 
     let from_index_checksum = index_frontend_object.checksum;
+    let previous_read_sets = mv_read_sets.for_index(&from_index_checksum).await;
     let (
         frontend_objects,
         patches,
+        recorded_read_sets,
         build_count,
         build_total_elapsed,
         build_max_elapsed,
@@ -362,9 +363,13 @@ pub async fn build_mv_for_changes_in_change_set(
         edda_updates,
         workspace_id,
         change_set_id,
-        &combined_changes,
+        changes,
+        &outdated_changes,
+        &outdated_mv_kinds,
+        &previous_read_sets,
     )
     .await?;
+    drop(previous_read_sets);
     span.record("si.edda.mv.count", build_count);
     if build_count > 0 {
         span.record(
@@ -427,7 +432,7 @@ pub async fn build_mv_for_changes_in_change_set(
     let index_patch = ObjectPatch {
         kind: ReferenceKind::ChangeSetMvIndex.to_string(),
         id: new_mv_index_frontend_object.id.clone(),
-        from_checksum: from_index_checksum,
+        from_checksum: from_index_checksum.clone(),
         to_checksum: to_index_checksum,
         patch,
     };
@@ -447,6 +452,13 @@ pub async fn build_mv_for_changes_in_change_set(
             index_kv_revision,
         )
         .await?;
+    mv_read_sets
+        .update(
+            &from_index_checksum,
+            new_mv_index_frontend_object.checksum.to_owned(),
+            recorded_read_sets,
+        )
+        .await;
 
     edda_updates
         .publish_change_set_patch_batch(patch_batch)
@@ -458,42 +470,101 @@ pub async fn build_mv_for_changes_in_change_set(
     Ok(())
 }
 
+/// Rebuilds only the MVs whose definitions have changed since the change set's [`ChangeSetMvIndex`]
+/// was built, keeping everything else in the index as is. Until this finishes, the existing index
+/// (and the objects it points to) continue to be served.
+///
+/// Falls back to building everything if there is no index yet, or if the index was not built for
+/// the change set's current snapshot.
+#[instrument(
+    name = "materialized_view.migrate_outdated_mv_for_change_set",
+    level = "info",
+    skip_all,
+    fields(
+        si.workspace.id = Empty,
+        si.change_set.id = %ctx.change_set_id(),
+        si.edda.mv.outdated_mv.kind_count = Empty,
+    ),
+)]
+pub async fn migrate_outdated_mv_for_change_set(
+    ctx: &DalContext,
+    frigg: &FriggStore,
+    edda_updates: &EddaUpdates,
+    mv_read_sets: &MvReadSets,
+    parallel_build_limit: usize,
+) -> Result<(), MaterializedViewError> {
+    let span = current_span_for_instrument_at!("info");
+    let workspace_id = ctx.workspace_pk()?;
+    let change_set_id = ctx.change_set_id();
+    span.record("si.workspace.id", workspace_id.to_string());
+
+    let Some((index_frontend_object, _index_kv_revision, outdated_kinds)) = frigg
+        .get_change_set_index_allowing_outdated(workspace_id, change_set_id)
+        .await?
+    else {
+        return build_all_mv_for_change_set(
+            ctx,
+            frigg,
+            edda_updates,
+            mv_read_sets,
+            parallel_build_limit,
+            None,
+            "initial build with changed definitions",
+        )
+        .await;
+    };
+    span.record("si.edda.mv.outdated_mv.kind_count", outdated_kinds.len());
+
+    if outdated_kinds.is_empty() {
+        debug!("index is already up to date with the current definitions");
+        return Ok(());
+    }
+
+    // Only the outdated kinds are rebuilt, so everything else in the index needs to already
+    // reflect the current snapshot.
+    let snapshot_address = ctx.workspace_snapshot()?.address().await;
+    if index_frontend_object.id != snapshot_address.to_string() {
+        return build_all_mv_for_change_set(
+            ctx,
+            frigg,
+            edda_updates,
+            mv_read_sets,
+            parallel_build_limit,
+            Some(index_frontend_object.checksum),
+            "selective rebuild for an index behind the current snapshot",
+        )
+        .await;
+    }
+
+    build_mv_for_changes_in_change_set(
+        ctx,
+        frigg,
+        edda_updates,
+        mv_read_sets,
+        parallel_build_limit,
+        change_set_id,
+        snapshot_address,
+        snapshot_address,
+        &[],
+    )
+    .await
+}
+
 /// Helper function to determine which entities need MaterializedView rebuilds
 /// due to outdated definition checksums. Returns synthetic Change objects
-/// for those entities and the count of outdated MV kinds. This is inlined into build_mv_for_changes_in_change_set.
+/// for those entities and the outdated MV kinds, which are the only kinds that should be
+/// rebuilt for the synthetic changes. This is inlined into build_mv_for_changes_in_change_set.
 /// Only works with V2 indexes - V1 indexes should be handled by the caller with a fallback to build_all_mv_for_change_set.
 async fn get_changes_for_outdated_definitions(
     ctx: &DalContext,
     mv_index: &si_frontend_mv_types::index::change_set::ChangeSetMvIndexV2,
-) -> Result<(Vec<Change>, usize), MaterializedViewError> {
-    // Get current definition checksums from the registry
-    let current_definition_checksums =
-        si_frontend_mv_types::definition_checksum::materialized_view_definition_checksums();
-
-    // Get the stored definition checksums from the V2 index
-    let existing_definition_checksums = mv_index.definition_checksums.clone();
-
-    // Check which MV types have outdated definitions
-    let outdated_mv_types: std::collections::HashSet<String> = current_definition_checksums
-        .iter()
-        .filter_map(|(mv_kind_str, current_checksum)| {
-            let existing_checksum = existing_definition_checksums.get(mv_kind_str);
-            match existing_checksum {
-                Some(existing) if existing != current_checksum => Some(mv_kind_str.clone()),
-                None => {
-                    // New MV type
-                    Some(mv_kind_str.clone())
-                }
-                _ => None, // Checksum matches, no update needed
-            }
-        })
-        .collect();
-
-    let outdated_mv_kind_count = outdated_mv_types.len();
+) -> Result<(Vec<Change>, HashSet<ReferenceKind>), MaterializedViewError> {
+    // Check which MV types have outdated definitions (or did not exist when the index was built)
+    let outdated_mv_types = outdated_definition_kinds(&mv_index.definition_checksums);
 
     if outdated_mv_types.is_empty() {
         debug!("No outdated definitions found");
-        return Ok((Vec::new(), 0));
+        return Ok((Vec::new(), HashSet::new()));
     }
 
     debug!(
@@ -543,5 +614,10 @@ async fn get_changes_for_outdated_definitions(
         outdated_mv_types,
     );
 
-    Ok((filtered_changes, outdated_mv_kind_count))
+    let outdated_mv_kinds = outdated_inventory_items
+        .iter()
+        .map(|item| item.kind())
+        .collect();
+
+    Ok((filtered_changes, outdated_mv_kinds))
 }
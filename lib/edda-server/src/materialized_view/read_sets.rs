@@ -0,0 +1,242 @@
+//! Tracks the [`ReadSet`] recorded for each materialized view built for a change set, so that
+//! incremental builds can skip views whose reads do not intersect a batch of changes.
+
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    sync::Arc,
+};
+
+use dal::ContentHash;
+use dal_materialized_views::read_set::ReadSet;
+use si_frontend_mv_types::reference::ReferenceKind;
+use si_id::EntityId;
+use tokio::sync::Mutex;
+
+/// The [`ReadSet`] recorded by a single build, keyed by the kind and triggering entity of the
+/// materialized view. A [`None`] read set means the view was removed, or that it does not track
+/// its reads.
+pub type RecordedReadSet = (ReferenceKind, EntityId, Option<ReadSet>);
+
+/// Recorded read sets, keyed by the kind and triggering entity of the materialized view.
+pub type ReadSetsByMv = HashMap<(ReferenceKind, EntityId), ReadSet>;
+
+/// The read sets of the materialized views in a change set's index.
+///
+/// Read sets are only held in memory for the lifetime of a change set processor task, and are only
+/// trusted when the index in frigg is the one that they were recorded for. Whenever that is not
+/// the case (a restart, or the index was written by someone else), every view is rebuilt as it was
+/// before read sets existed.
+#[derive(Clone, Debug, Default)]
+pub struct MvReadSets {
+    inner: Arc<Mutex<MvReadSetsInner>>,
+}
+
+#[derive(Debug, Default)]
+struct MvReadSetsInner {
+    index_checksum: Option<String>,
+    read_sets: Arc<ReadSetsByMv>,
+}
+
+impl MvReadSets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the read sets recorded for the index with the given checksum, or nothing if the
+    /// recorded read sets belong to a different index.
+    pub async fn for_index(&self, index_checksum: &str) -> Arc<ReadSetsByMv> {
+        let inner = self.inner.lock().await;
+        if inner.index_checksum.as_deref() == Some(index_checksum) {
+            inner.read_sets.clone()
+        } else {
+            Arc::default()
+        }
+    }
+
+    /// Records the read sets of an incremental build that went from the index with
+    /// `from_index_checksum` to the one with `index_checksum`. Views that were not rebuilt keep
+    /// their previously recorded read set, as long as those were recorded for the index the build
+    /// started from. Otherwise only the views that were rebuilt have a read set afterwards.
+    pub async fn update(
+        &self,
+        from_index_checksum: &str,
+        index_checksum: String,
+        recorded: Vec<RecordedReadSet>,
+    ) {
+        let mut inner = self.inner.lock().await;
+        if inner.index_checksum.as_deref() != Some(from_index_checksum) {
+            inner.read_sets = Arc::default();
+        }
+
+        let read_sets = Arc::make_mut(&mut inner.read_sets);
+        for (kind, entity_id, maybe_read_set) in recorded {
+            match maybe_read_set {
+                Some(read_set) => {
+                    read_sets.insert((kind, entity_id), read_set);
+                }
+                None => {
+                    read_sets.remove(&(kind, entity_id));
+                }
+            }
+        }
+        inner.index_checksum = Some(index_checksum);
+    }
+
+    /// Replaces all read sets with the ones recorded while building the index with the given
+    /// checksum from scratch.
+    pub async fn replace(&self, index_checksum: String, recorded: Vec<RecordedReadSet>) {
+        let read_sets = recorded
+            .into_iter()
+            .filter_map(|(kind, entity_id, maybe_read_set)| {
+                maybe_read_set.map(|read_set| ((kind, entity_id), read_set))
+            })
+            .collect();
+
+        let mut inner = self.inner.lock().await;
+        inner.read_sets = Arc::new(read_sets);
+        inner.index_checksum = Some(index_checksum);
+    }
+}
+
+/// Whether building the view of the given kind for a changed entity can be skipped, because the
+/// previous build of it recorded what it read and none of the changes affect that. Views without
+/// a recorded read set are always built.
+///
+/// `node_hash` looks up the current node hash of a changed node, if it still exists.
+pub async fn can_skip_build(
+    previous_read_sets: &ReadSetsByMv,
+    kind: ReferenceKind,
+    entity_id: EntityId,
+    changed_entity_ids: &HashSet<EntityId>,
+    node_hash: impl AsyncFn(EntityId) -> Option<ContentHash>,
+) -> bool {
+    match previous_read_sets.get(&(kind, entity_id)) {
+        Some(read_set) => !read_set.is_affected_by(changed_entity_ids, node_hash).await,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn read_sets_are_only_used_for_the_index_they_were_recorded_for() {
+        let mv_read_sets = MvReadSets::new();
+        let entity_id = EntityId::generate();
+        let other_entity_id = EntityId::generate();
+
+        mv_read_sets
+            .replace(
+                "first".to_string(),
+                vec![
+                    (
+                        ReferenceKind::ComponentInList,
+                        entity_id,
+                        Some(ReadSet::default()),
+                    ),
+                    (ReferenceKind::ComponentList, other_entity_id, None),
+                ],
+            )
+            .await;
+
+        let read_sets = mv_read_sets.for_index("first").await;
+        assert_eq!(1, read_sets.len());
+        assert!(read_sets.contains_key(&(ReferenceKind::ComponentInList, entity_id)));
+        assert!(mv_read_sets.for_index("second").await.is_empty());
+
+        mv_read_sets
+            .update(
+                "first",
+                "second".to_string(),
+                vec![
+                    (ReferenceKind::ComponentInList, entity_id, None),
+                    (
+                        ReferenceKind::IncomingConnections,
+                        entity_id,
+                        Some(ReadSet::default()),
+                    ),
+                ],
+            )
+            .await;
+
+        assert!(mv_read_sets.for_index("first").await.is_empty());
+        let read_sets = mv_read_sets.for_index("second").await;
+        assert_eq!(1, read_sets.len());
+        assert!(read_sets.contains_key(&(ReferenceKind::IncomingConnections, entity_id)));
+    }
+
+    #[tokio::test]
+    async fn views_are_not_skipped_with_read_sets_recorded_for_another_index() {
+        let mv_read_sets = MvReadSets::new();
+        let entity_id = EntityId::generate();
+        let other_entity_id = EntityId::generate();
+        let changed_entity_ids = HashSet::from([entity_id, other_entity_id]);
+        let node_hash = async |_: EntityId| -> Option<ContentHash> { None };
+
+        mv_read_sets
+            .replace(
+                "first".to_string(),
+                vec![(
+                    ReferenceKind::ComponentInList,
+                    entity_id,
+                    Some(ReadSet::default()),
+                )],
+            )
+            .await;
+
+        // A build from the index the read sets were recorded for skips the unaffected view.
+        let read_sets = mv_read_sets.for_index("first").await;
+        assert!(
+            can_skip_build(
+                &read_sets,
+                ReferenceKind::ComponentInList,
+                entity_id,
+                &changed_entity_ids,
+                &node_hash,
+            )
+            .await
+        );
+
+        // Someone else wrote the index, so a build from it has no read sets and only records the
+        // views it rebuilt.
+        assert!(mv_read_sets.for_index("written elsewhere").await.is_empty());
+        mv_read_sets
+            .update(
+                "written elsewhere",
+                "second".to_string(),
+                vec![(
+                    ReferenceKind::ComponentInList,
+                    other_entity_id,
+                    Some(ReadSet::default()),
+                )],
+            )
+            .await;
+
+        // The view built by someone else must not be skipped based on our stale read set.
+        let read_sets = mv_read_sets.for_index("second").await;
+        assert!(
+            !can_skip_build(
+                &read_sets,
+                ReferenceKind::ComponentInList,
+                entity_id,
+                &changed_entity_ids,
+                &node_hash,
+            )
+            .await
+        );
+        assert!(
+            can_skip_build(
+                &read_sets,
+                ReferenceKind::ComponentInList,
+                other_entity_id,
+                &changed_entity_ids,
+                &node_hash,
+            )
+            .await
+        );
+    }
+}
@@ -68,6 +68,7 @@ use si_data_pg::{
 };
 use telemetry::prelude::*;
 use telemetry_utils::metric;
+use tokio::sync::Semaphore;
 use tokio_util::{
     sync::CancellationToken,
     task::TaskTracker,
@@ -177,10 +178,12 @@ impl Server {
             config.instance_id().to_string(),
             config.concurrency_limit(),
             config.parallel_build_limit(),
+            config.definition_migration_limit(),
             config.streaming_patches(),
             services_context,
             frigg_nats,
             config.quiescent_period(),
+            config.index_history_retention(),
             shutdown_token,
         )
         .await
@@ -193,10 +196,12 @@ impl Server {
         instance_id: impl Into<String>,
         concurrency_limit: Option<usize>,
         parallel_build_limit: usize,
+        definition_migration_limit: usize,
         streaming_patches: bool,
         services_context: ServicesContext,
         frigg_nats: NatsClient,
         quiescent_period: Duration,
+        index_history_retention: Duration,
         shutdown_token: CancellationToken,
     ) -> Result<Self> {
         let metadata = Arc::new(ServerMetadata {
@@ -225,6 +230,7 @@ impl Server {
                 frigg_nats,
                 frigg_kv(&frigg_context, prefix.as_deref()).await?,
             )
+            .with_index_history_retention(index_history_retention)
         };
 
         let edda_updates = EddaUpdates::new(
@@ -242,6 +248,7 @@ impl Server {
             frigg,
             edda_updates,
             parallel_build_limit,
+            Arc::new(Semaphore::new(definition_migration_limit)),
             requests_stream,
             ctx_builder,
             quiescent_period,
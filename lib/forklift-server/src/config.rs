@@ -63,6 +63,9 @@ pub struct Config {
     #[builder(default = "default_enable_audit_logs_app()")]
     enable_audit_logs_app: bool,
 
+    #[builder(default = "default_enable_billing_usage_rollups()")]
+    enable_billing_usage_rollups: bool,
+
     #[builder(default)]
     audit: AuditDatabaseConfig,
 
@@ -109,6 +112,12 @@ impl Config {
         self.enable_audit_logs_app
     }
 
+    /// Indicates whether or not billing events will be aggregated into usage rollups in the audit
+    /// database.
+    pub fn enable_billing_usage_rollups(&self) -> bool {
+        self.enable_billing_usage_rollups
+    }
+
     /// Gets a reference to the audit database config.
     pub fn audit(&self) -> &AuditDatabaseConfig {
         &self.audit
@@ -139,6 +148,8 @@ pub struct ConfigFile {
     pub data_warehouse_stream_name: Option<String>,
     #[serde(default = "default_enable_audit_logs_app")]
     pub enable_audit_logs_app: bool,
+    #[serde(default = "default_enable_billing_usage_rollups")]
+    pub enable_billing_usage_rollups: bool,
     #[serde(default)]
     pub audit: AuditDatabaseConfig,
     #[serde(default)]
@@ -155,6 +166,7 @@ impl Default for ConfigFile {
             nats: Default::default(),
             data_warehouse_stream_name: default_data_warehouse_stream_name(),
             enable_audit_logs_app: default_enable_audit_logs_app(),
+            enable_billing_usage_rollups: default_enable_billing_usage_rollups(),
             audit: Default::default(),
             snapshot_eviction: Default::default(),
             service_endpoints: default_service_endpoints_config(),
@@ -178,6 +190,7 @@ impl TryFrom<ConfigFile> for Config {
             nats: value.nats,
             data_warehouse_stream_name: value.data_warehouse_stream_name,
             enable_audit_logs_app: value.enable_audit_logs_app,
+            enable_billing_usage_rollups: value.enable_billing_usage_rollups,
             audit: value.audit,
             snapshot_eviction: value.snapshot_eviction,
             service_endpoints: value.service_endpoints,
@@ -201,6 +214,10 @@ fn default_enable_audit_logs_app() -> bool {
     false
 }
 
+fn default_enable_billing_usage_rollups() -> bool {
+    false
+}
+
 fn default_service_endpoints_config() -> ServiceEndpointsConfig {
     ServiceEndpointsConfig::new(0)
 }
@@ -113,6 +113,12 @@ impl Server {
             None
         };
 
+        let usage_rollups_context = if config.enable_billing_usage_rollups() {
+            Some(AuditDatabaseContext::from_config(config.audit()).await?)
+        } else {
+            None
+        };
+
         // Initialize pools for eviction task
         let si_db_pool = Self::create_si_db_pool(&config.snapshot_eviction().si_db).await?;
         let layer_cache_pool =
@@ -132,6 +138,7 @@ impl Server {
             config.instance_id(),
             config.concurrency_limit(),
             audit_bag,
+            usage_rollups_context,
             config.data_warehouse_stream_name(),
             si_db_pool,
             layer_cache_pool,
@@ -151,6 +158,7 @@ impl Server {
         instance_id: &str,
         concurrency_limit: usize,
         audit_bag: Option<(AuditDatabaseContext, usize)>,
+        usage_rollups_context: Option<AuditDatabaseContext>,
         data_warehouse_stream_name: Option<&str>,
         si_db_pool: PgPool,
         layer_cache_pool: PgPool,
@@ -185,6 +193,7 @@ impl Server {
             connection_metadata,
             concurrency_limit,
             data_warehouse_stream_name,
+            usage_rollups_context,
             token.clone(),
         )
         .await?;
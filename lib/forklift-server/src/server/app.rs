@@ -66,6 +66,7 @@ pub(crate) async fn billing_events(
     connection_metadata: Arc<ConnectionMetadata>,
     concurrency_limit: usize,
     data_warehouse_stream_name: Option<&str>,
+    usage_rollups_context: Option<AuditDatabaseContext>,
     token: CancellationToken,
 ) -> Result<Box<dyn Future<Output = io::Result<()>> + Unpin + Send>> {
     Ok(billing_events::build_and_run(
@@ -74,6 +75,7 @@ pub(crate) async fn billing_events(
         connection_metadata,
         concurrency_limit,
         data_warehouse_stream_name,
+        usage_rollups_context,
         token,
     )
     .await?)
@@ -11,6 +11,7 @@ use app_state::{
     AppState,
     NoopAppState,
 };
+use audit_database::AuditDatabaseContext;
 use billing_events::{
     BillingEventsError,
     BillingEventsWorkQueue,
@@ -86,6 +87,7 @@ pub(crate) async fn build_and_run(
     connection_metadata: Arc<ConnectionMetadata>,
     concurrency_limit: usize,
     data_warehouse_stream_name: Option<&str>,
+    usage_rollups_context: Option<AuditDatabaseContext>,
     token: CancellationToken,
 ) -> Result<Box<dyn Future<Output = io::Result<()>> + Unpin + Send>> {
     let incoming = {
@@ -104,11 +106,15 @@ pub(crate) async fn build_and_run(
             .await?
     };
 
+    if usage_rollups_context.is_some() {
+        info!("billing events will be aggregated into usage rollups");
+    }
+
     let inner = match data_warehouse_stream_name {
         Some(stream_name) => {
             info!(%stream_name, "creating billing events app in data warehouse stream delivery mode...");
             let client = DataWarehouseStreamClient::new(stream_name).await?;
-            let state = AppState::new(client);
+            let state = AppState::new(client, usage_rollups_context);
             build_app(
                 state,
                 connection_metadata,
@@ -119,7 +125,7 @@ pub(crate) async fn build_and_run(
         }
         None => {
             info!("creating billing events app in no-op mode...");
-            let state = NoopAppState::new();
+            let state = NoopAppState::new(usage_rollups_context);
             build_noop_app(
                 state,
                 connection_metadata,
@@ -1,24 +1,34 @@
+use audit_database::AuditDatabaseContext;
 use data_warehouse_stream_client::DataWarehouseStreamClient;
 
 #[derive(Debug, Clone)]
 pub(crate) struct AppState {
     pub(crate) data_warehouse_stream_client: DataWarehouseStreamClient,
+    pub(crate) usage_rollups_context: Option<AuditDatabaseContext>,
 }
 
 impl AppState {
-    pub(crate) fn new(data_warehouse_stream_client: DataWarehouseStreamClient) -> Self {
+    pub(crate) fn new(
+        data_warehouse_stream_client: DataWarehouseStreamClient,
+        usage_rollups_context: Option<AuditDatabaseContext>,
+    ) -> Self {
         Self {
             data_warehouse_stream_client,
+            usage_rollups_context,
         }
     }
 }
 
 // NOTE(nick,fletcher): we need an app state for all naxum apps at the time of writing, even if they are unused.
 #[derive(Debug, Clone)]
-pub(crate) struct NoopAppState {}
+pub(crate) struct NoopAppState {
+    pub(crate) usage_rollups_context: Option<AuditDatabaseContext>,
+}
 
 impl NoopAppState {
-    pub(crate) fn new() -> Self {
-        Self {}
+    pub(crate) fn new(usage_rollups_context: Option<AuditDatabaseContext>) -> Self {
+        Self {
+            usage_rollups_context,
+        }
     }
 }
@@ -1,4 +1,13 @@
-use billing_events::BillingEvent;
+use audit_database::{
+    AuditDatabaseContext,
+    AuditDatabaseError,
+    ResourceChange,
+    WorkspaceUsageRollup,
+};
+use billing_events::{
+    BillingEvent,
+    BillingEventKind,
+};
 use data_warehouse_stream_client::DataWarehouseStreamClientError;
 use naxum::{
     Json,
@@ -20,6 +29,8 @@ use super::app_state::{
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub(crate) enum HandlerError {
+    #[error("audit database error: {0}")]
+    AuditDatabase(#[from] AuditDatabaseError),
     #[error("data warehouse stream client error: {0}")]
     DataWarehouseStreamClient(#[from] DataWarehouseStreamClientError),
     #[error("serde json error: {0}")]
@@ -45,6 +56,10 @@ pub(crate) async fn process_request(
     span.record("si.workspace.id", request.workspace_id.to_string());
     span.record("si.change_set.id", request.change_set_id.to_string());
 
+    if let Some(context) = &state.usage_rollups_context {
+        record_usage(context, &request).await?;
+    }
+
     let serialized_request = serde_json::to_vec(&request)?;
     state
         .data_warehouse_stream_client
@@ -56,7 +71,7 @@ pub(crate) async fn process_request(
 }
 
 pub(crate) async fn process_request_noop(
-    State(_state): State<NoopAppState>,
+    State(state): State<NoopAppState>,
     _subject: Subject,
     Json(request): Json<BillingEvent>,
 ) -> HandlerResult<()> {
@@ -65,6 +80,10 @@ pub(crate) async fn process_request_noop(
     span.record("si.workspace.id", request.workspace_id.to_string());
     span.record("si.change_set.id", request.change_set_id.to_string());
 
+    if let Some(context) = &state.usage_rollups_context {
+        record_usage(context, &request).await?;
+    }
+
     info!(
         kind = ?request.kind,
         ?request,
@@ -72,3 +91,66 @@ pub(crate) async fn process_request_noop(
     );
     Ok(())
 }
+
+/// Aggregates the billing event into the workspace's usage rollups.
+///
+/// Resource changes and func runs are deduplicated by func run, so redelivered events are only
+/// counted once.
+async fn record_usage(context: &AuditDatabaseContext, event: &BillingEvent) -> HandlerResult<()> {
+    match event.kind {
+        BillingEventKind::ChangeSetStatusUpdate | BillingEventKind::HeadChangeSetPointerUpdate => {
+            if let Some(resource_count) = event.resource_count {
+                WorkspaceUsageRollup::record_resource_count(
+                    context,
+                    event.workspace_id,
+                    event.event_timestamp,
+                    resource_count,
+                )
+                .await?;
+            }
+        }
+        BillingEventKind::FuncRunComplete => {
+            if let (Some(func_run_id), Some(func_kind), Some(duration_ms)) = (
+                event.func_run_id,
+                event.func_kind,
+                event.func_run_duration_ms,
+            ) {
+                WorkspaceUsageRollup::record_func_run(
+                    context,
+                    event.workspace_id,
+                    func_run_id,
+                    event.event_timestamp,
+                    func_kind,
+                    duration_ms,
+                )
+                .await?;
+            }
+        }
+        BillingEventKind::ResourceCreate => {
+            if let Some(func_run_id) = event.func_run_id {
+                WorkspaceUsageRollup::record_resource_change(
+                    context,
+                    event.workspace_id,
+                    func_run_id,
+                    event.event_timestamp,
+                    ResourceChange::Created,
+                )
+                .await?;
+            }
+        }
+        BillingEventKind::ResourceDelete => {
+            if let Some(func_run_id) = event.func_run_id {
+                WorkspaceUsageRollup::record_resource_change(
+                    context,
+                    event.workspace_id,
+                    func_run_id,
+                    event.event_timestamp,
+                    ResourceChange::Deleted,
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
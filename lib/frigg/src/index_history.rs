@@ -0,0 +1,473 @@
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use futures::TryStreamExt as _;
+use kv_history::History;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_data_nats::{
+    Subject,
+    async_nats::jetstream::{
+        consumer::{
+            DeliverPolicy,
+            ReplayPolicy,
+            push::OrderedConfig,
+        },
+        kv::Operation,
+    },
+};
+use si_frontend_mv_types::{
+    index::change_set::{
+        ChangeSetIndexPointerValueV2,
+        ChangeSetMvIndexVersion,
+    },
+    object::FrontendObject,
+};
+use si_id::{
+    ChangeSetId,
+    WorkspacePk,
+};
+use telemetry::prelude::*;
+
+use crate::{
+    Domain,
+    Error,
+    FriggError,
+    FriggStore,
+    KvRevision,
+    Result,
+    Scope,
+    kv_history,
+};
+
+/// Identifies a past version of a change set index.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IndexAt {
+    /// The index as written at exactly this revision of the change set index pointer.
+    Revision(KvRevision),
+    /// The most recent index that had been written at or before this point in time.
+    Timestamp(DateTime<Utc>),
+}
+
+impl IndexAt {
+    /// Builds an [`IndexAt`] from request parameters, returning [`None`] unless exactly one of
+    /// them is given.
+    pub fn from_revision_or_timestamp(
+        revision: Option<u64>,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> Option<Self> {
+        match (revision, timestamp) {
+            (Some(revision), None) => Some(Self::Revision(revision.into())),
+            (None, Some(timestamp)) => Some(Self::Timestamp(timestamp)),
+            _ => None,
+        }
+    }
+}
+
+/// A recorded version of a change set index pointer.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetIndexHistoryEntry {
+    /// The revision of the change set index pointer when this version was written.
+    pub revision: u64,
+    /// When this version was written.
+    pub recorded_at: DateTime<Utc>,
+    /// The index pointer as it was written.
+    pub index_pointer: ChangeSetIndexPointerValueV2,
+}
+
+impl FriggStore {
+    /// Records a newly written change set index pointer so that the index can later be read as
+    /// it was at this revision.
+    ///
+    /// The pointer has already been written by the time this is called, so a failure here must
+    /// not fail the write. Instead, the version is logged and left out of the history.
+    ///
+    /// This is a no-op unless the store was configured with an index history retention period.
+    pub(crate) async fn record_change_set_index_history(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: &str,
+        index_pointer: &ChangeSetIndexPointerValueV2,
+        revision: KvRevision,
+    ) {
+        if self.index_history_retention.is_none() {
+            return;
+        }
+
+        if let Err(err) = self
+            .put_change_set_index_history_entry(
+                workspace_id,
+                change_set_id,
+                index_pointer,
+                revision,
+            )
+            .await
+        {
+            warn!(
+                si.error.message = ?err,
+                si.workspace.id = %workspace_id,
+                si.change_set.id = %change_set_id,
+                revision = revision.0,
+                "failed to record change set index history",
+            );
+        }
+    }
+
+    async fn put_change_set_index_history_entry(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: &str,
+        index_pointer: &ChangeSetIndexPointerValueV2,
+        revision: KvRevision,
+    ) -> Result<()> {
+        let entry = ChangeSetIndexHistoryEntry {
+            revision: revision.0,
+            recorded_at: Utc::now(),
+            index_pointer: index_pointer.clone(),
+        };
+        let value = serde_json::to_vec(&entry).map_err(Error::Serialize)?;
+        self.store
+            .put(
+                Self::change_set_index_history_key(
+                    workspace_id,
+                    change_set_id,
+                    &revision.0.to_string(),
+                ),
+                value.into(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Lists the recorded versions of a change set index, oldest first.
+    #[instrument(
+        name = "frigg.list_change_set_index_history",
+        level = "debug",
+        skip_all,
+        fields(
+            si.workspace.id = %workspace_id,
+            si.change_set.id = %change_set_id,
+        )
+    )]
+    pub async fn list_change_set_index_history(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+    ) -> Result<Vec<ChangeSetIndexHistoryEntry>> {
+        let filter_subject =
+            Self::change_set_index_history_key(workspace_id, &change_set_id.to_string(), "*")
+                .into_string();
+
+        let mut history_consumer = self
+            .store
+            .stream
+            .create_consumer(OrderedConfig {
+                deliver_subject: self.nats.new_inbox(),
+                description: Some("kv index history consumer".to_string()),
+                filter_subject,
+                replay_policy: ReplayPolicy::Instant,
+                deliver_policy: DeliverPolicy::LastPerSubject,
+                ..Default::default()
+            })
+            .await?;
+
+        // An empty stream would never yield a message, so we need to check for that up front
+        if history_consumer.info().await?.num_pending == 0 {
+            return Ok(Vec::new());
+        }
+
+        let history = History {
+            done: false,
+            subscription: history_consumer.messages().await?,
+            prefix: self.store.prefix.clone(),
+            bucket: self.store.name.clone(),
+        };
+
+        let mut entries: Vec<ChangeSetIndexHistoryEntry> = history
+            .try_filter(|entry| futures::future::ready(matches!(entry.operation, Operation::Put)))
+            .map_err(Error::KvHistory)
+            .and_then(|entry| async move {
+                serde_json::from_slice(entry.value.as_ref()).map_err(Error::Deserialize)
+            })
+            .try_collect()
+            .await?;
+        entries.sort_by_key(|entry| entry.revision);
+
+        Ok(entries)
+    }
+
+    /// Gets the change set index as it was at a point in the past.
+    ///
+    /// Returns [`None`] if no version of the index was recorded at (or before) the requested
+    /// point, for example if it is older than the retention period.
+    #[instrument(
+        name = "frigg.get_change_set_index_at",
+        level = "debug",
+        skip_all,
+        fields(
+            si.workspace.id = %workspace_id,
+            si.change_set.id = %change_set_id,
+        )
+    )]
+    pub async fn get_change_set_index_at(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+        at: IndexAt,
+    ) -> Result<Option<(FrontendObject, KvRevision)>> {
+        let maybe_entry = match at {
+            IndexAt::Revision(revision) => {
+                let key = Self::change_set_index_history_key(
+                    workspace_id,
+                    &change_set_id.to_string(),
+                    &revision.0.to_string(),
+                );
+                match self.get_object_raw_bytes(&key).await? {
+                    Some((bytes, _)) => Some(
+                        serde_json::from_slice::<ChangeSetIndexHistoryEntry>(bytes.as_ref())
+                            .map_err(Error::Deserialize)?,
+                    ),
+                    None => None,
+                }
+            }
+            IndexAt::Timestamp(timestamp) => latest_entry_at(
+                self.list_change_set_index_history(workspace_id, change_set_id)
+                    .await?,
+                timestamp,
+            ),
+        };
+        let Some(entry) = maybe_entry else {
+            return Ok(None);
+        };
+
+        let object = self
+            .get_change_set_index_object(entry.index_pointer)
+            .await?;
+
+        Ok(Some((object, KvRevision(entry.revision))))
+    }
+
+    /// Gets an object as it was in the change set index at a point in the past.
+    #[instrument(
+        name = "frigg.get_workspace_object_at",
+        level = "debug",
+        skip_all,
+        fields(
+            si.workspace.id = %workspace_id,
+            si.change_set.id = %change_set_id,
+            si.frontend_object.id = %id,
+            si.frontend_object.kind = %kind,
+        )
+    )]
+    pub async fn get_workspace_object_at(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+        kind: &str,
+        id: &str,
+        at: IndexAt,
+    ) -> Result<Option<FrontendObject>> {
+        let Some((index, _)) = self
+            .get_change_set_index_at(workspace_id, change_set_id, at)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let mv_list = match serde_json::from_value(index.data).map_err(FriggError::Deserialize)? {
+            ChangeSetMvIndexVersion::V1(v1_index) => v1_index.mv_list,
+            ChangeSetMvIndexVersion::V2(v2_index) => v2_index.mv_list,
+        };
+
+        match mv_list
+            .into_iter()
+            .find(|index_entry| index_entry.kind == kind && index_entry.id == id)
+        {
+            Some(index_entry) => Ok(Some(
+                self.get_workspace_object(workspace_id, kind, id, &index_entry.checksum)
+                    .await?
+                    .ok_or_else(|| FriggError::ObjectNotFoundForChangesetIndex {
+                        workspace_id,
+                        change_set_id,
+                        kind: kind.to_string(),
+                        id: id.to_string(),
+                    })?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Removes recorded versions of a change set index that are older than the retention period,
+    /// always keeping the most recent one so the current index can still be found by timestamp.
+    ///
+    /// This is a no-op unless the store was configured with an index history retention period.
+    #[instrument(
+        name = "frigg.prune_change_set_index_history",
+        level = "debug",
+        skip_all,
+        fields(
+            si.workspace.id = %workspace_id,
+            si.change_set.id = %change_set_id,
+            si.frigg.pruned_count = Empty,
+        )
+    )]
+    pub async fn prune_change_set_index_history(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+    ) -> Result<()> {
+        let Some(cutoff) = self
+            .index_history_retention
+            .and_then(|retention| TimeDelta::from_std(retention).ok())
+            .and_then(|retention| Utc::now().checked_sub_signed(retention))
+        else {
+            return Ok(());
+        };
+        let span = current_span_for_instrument_at!("debug");
+
+        let entries = self
+            .list_change_set_index_history(workspace_id, change_set_id)
+            .await?;
+
+        let mut pruned_count: usize = 0;
+        for entry in expired_entries(entries, cutoff) {
+            self.store
+                .purge(Self::change_set_index_history_key(
+                    workspace_id,
+                    &change_set_id.to_string(),
+                    &entry.revision.to_string(),
+                ))
+                .await?;
+            pruned_count += 1;
+        }
+        span.record("si.frigg.pruned_count", pruned_count);
+
+        Ok(())
+    }
+
+    #[inline]
+    fn change_set_index_history_key(
+        workspace_id: WorkspacePk,
+        change_set_id: &str,
+        revision: &str,
+    ) -> Subject {
+        Subject::from(format!(
+            "{}.{}.{workspace_id}.{change_set_id}.{revision}",
+            Domain::IndexHistory.as_ref(),
+            Scope::ChangeSet.as_ref()
+        ))
+    }
+}
+
+/// Finds the most recent entry recorded at or before the timestamp, given entries oldest first.
+fn latest_entry_at(
+    entries: Vec<ChangeSetIndexHistoryEntry>,
+    timestamp: DateTime<Utc>,
+) -> Option<ChangeSetIndexHistoryEntry> {
+    entries
+        .into_iter()
+        .rev()
+        .find(|entry| entry.recorded_at <= timestamp)
+}
+
+/// Finds the entries recorded before the cutoff, given entries oldest first. The latest entry is
+/// never included, even if it is older than the cutoff.
+fn expired_entries(
+    mut entries: Vec<ChangeSetIndexHistoryEntry>,
+    cutoff: DateTime<Utc>,
+) -> Vec<ChangeSetIndexHistoryEntry> {
+    entries.pop();
+    entries
+        .into_iter()
+        .take_while(|entry| entry.recorded_at < cutoff)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::TimeZone as _;
+
+    use super::*;
+
+    fn entry(revision: u64, recorded_at_secs: i64) -> ChangeSetIndexHistoryEntry {
+        ChangeSetIndexHistoryEntry {
+            revision,
+            recorded_at: at(recorded_at_secs),
+            index_pointer: ChangeSetIndexPointerValueV2 {
+                index_object_key: format!("index.{revision}"),
+                snapshot_address: format!("snapshot-{revision}"),
+                definition_checksums: HashMap::new(),
+                index_checksum: format!("checksum-{revision}"),
+            },
+        }
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(secs, 0)
+            .single()
+            .expect("valid timestamp")
+    }
+
+    fn revisions(entries: impl IntoIterator<Item = ChangeSetIndexHistoryEntry>) -> Vec<u64> {
+        entries.into_iter().map(|entry| entry.revision).collect()
+    }
+
+    #[test]
+    fn index_at_requires_exactly_one_of_revision_or_timestamp() {
+        assert_eq!(
+            Some(IndexAt::Revision(KvRevision(3))),
+            IndexAt::from_revision_or_timestamp(Some(3), None)
+        );
+        assert_eq!(
+            Some(IndexAt::Timestamp(at(100))),
+            IndexAt::from_revision_or_timestamp(None, Some(at(100)))
+        );
+        assert_eq!(None, IndexAt::from_revision_or_timestamp(None, None));
+        assert_eq!(
+            None,
+            IndexAt::from_revision_or_timestamp(Some(3), Some(at(100)))
+        );
+    }
+
+    #[test]
+    fn latest_entry_at_picks_the_newest_entry_not_after_the_timestamp() {
+        let entries = vec![entry(1, 100), entry(4, 200), entry(9, 300)];
+
+        assert_eq!(None, latest_entry_at(entries.clone(), at(99)));
+        assert_eq!(
+            vec![1],
+            revisions(latest_entry_at(entries.clone(), at(100)))
+        );
+        assert_eq!(
+            vec![4],
+            revisions(latest_entry_at(entries.clone(), at(299)))
+        );
+        assert_eq!(vec![9], revisions(latest_entry_at(entries, at(1_000))));
+    }
+
+    #[test]
+    fn expired_entries_stop_at_the_cutoff() {
+        let entries = vec![entry(1, 100), entry(4, 200), entry(9, 300)];
+
+        assert_eq!(
+            Vec::<u64>::new(),
+            revisions(expired_entries(entries.clone(), at(100)))
+        );
+        assert_eq!(vec![1, 4], revisions(expired_entries(entries, at(250))));
+    }
+
+    #[test]
+    fn expired_entries_keep_the_latest_entry() {
+        let entries = vec![entry(1, 100), entry(4, 200)];
+
+        assert_eq!(vec![1], revisions(expired_entries(entries, at(1_000))));
+        assert!(expired_entries(Vec::new(), at(1_000)).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     result,
     str::Utf8Error,
+    time::Duration,
 };
 
 use bytes::Bytes;
@@ -31,9 +32,15 @@ use strum::AsRefStr;
 use thiserror::Error;
 
 mod deployment;
+mod index_history;
 mod kv_history;
 mod workspace;
 
+pub use index_history::{
+    ChangeSetIndexHistoryEntry,
+    IndexAt,
+};
+
 const NATS_KV_BUCKET_NAME: &str = "FRIGG";
 
 #[remain::sorted]
@@ -57,6 +64,8 @@ pub enum Error {
     GetKeyValue(#[from] async_nats::jetstream::context::KeyValueError),
     #[error("index object not found at key: {0}")]
     IndexObjectNotFound(Subject),
+    #[error("kv history error: {0}")]
+    KvHistory(#[from] kv_history::WatcherError),
     #[error("nats request error: {0}")]
     NatsRequest(#[from] RequestError),
     #[error("object kind was expected to be 'MvIndex' but was '{0}'")]
@@ -72,6 +81,8 @@ pub enum Error {
     },
     #[error("object listed in deployment index not found: kind: {kind}, id: {id}")]
     ObjectNotFoundForDeploymentIndex { kind: String, id: String },
+    #[error("purge error: {0}")]
+    Purge(#[from] kv::PurgeError),
     #[error("put error: {0}")]
     Put(#[from] kv::PutError),
     #[error("error serializing kv value: {0}")]
@@ -99,11 +110,18 @@ impl From<u64> for KvRevision {
     }
 }
 
+impl From<KvRevision> for u64 {
+    fn from(value: KvRevision) -> Self {
+        value.0
+    }
+}
+
 #[remain::sorted]
 #[derive(AsRefStr, Debug, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 enum Domain {
     Index,
+    IndexHistory,
     Object,
 }
 
@@ -120,11 +138,24 @@ enum Scope {
 pub struct FriggStore {
     nats: NatsClient,
     store: kv::Store,
+    index_history_retention: Option<Duration>,
 }
 
 impl FriggStore {
     pub fn new(nats: NatsClient, store: kv::Store) -> Self {
-        Self { nats, store }
+        Self {
+            nats,
+            store,
+            index_history_retention: None,
+        }
+    }
+
+    /// Records every version of the change set indexes written through this store, keeping them
+    /// for at least the given period so they can be read with
+    /// [`get_change_set_index_at`](Self::get_change_set_index_at).
+    pub fn with_index_history_retention(mut self, retention: Duration) -> Self {
+        self.index_history_retention = Some(retention);
+        self
     }
 
     async fn get_object_raw_bytes(&self, key: &Subject) -> Result<Option<(Bytes, KvRevision)>> {
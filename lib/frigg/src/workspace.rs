@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use kv_history::{
     History,
     Keys,
@@ -18,7 +20,10 @@ use si_data_nats::{
     },
 };
 use si_frontend_mv_types::{
-    definition_checksum::materialized_view_definition_checksums,
+    definition_checksum::{
+        materialized_view_definition_checksums,
+        outdated_definition_kinds,
+    },
     index::change_set::{
         ChangeSetIndexPointerValueV2,
         ChangeSetIndexPointerVersion,
@@ -271,6 +276,13 @@ impl FriggStore {
         };
         let value = serde_json::to_vec(&index_pointer_value).map_err(Error::Serialize)?;
         let new_revision = self.store.create(index_pointer_key, value.into()).await?;
+        self.record_change_set_index_history(
+            workspace_id,
+            change_set_id,
+            &index_pointer_value,
+            new_revision.into(),
+        )
+        .await;
 
         Ok(new_revision.into())
     }
@@ -299,6 +311,13 @@ impl FriggStore {
 
         let value = serde_json::to_vec(&index_pointer_value).map_err(Error::Serialize)?;
         let new_revision = self.store.create(index_pointer_key, value.into()).await?;
+        self.record_change_set_index_history(
+            workspace_id,
+            change_set_id,
+            &index_pointer_value,
+            new_revision.into(),
+        )
+        .await;
 
         Ok(new_revision.into())
     }
@@ -341,6 +360,13 @@ impl FriggStore {
             .store
             .update(index_pointer_key, value.into(), revision.0)
             .await?;
+        self.record_change_set_index_history(
+            workspace_id,
+            change_set_id,
+            &index_pointer_value,
+            new_revision.into(),
+        )
+        .await;
 
         Ok(new_revision.into())
     }
@@ -379,6 +405,13 @@ impl FriggStore {
         let value = serde_json::to_vec(&index_pointer_value).map_err(Error::Serialize)?;
 
         let new_revision = self.store.put(index_pointer_key, value.into()).await?;
+        self.record_change_set_index_history(
+            workspace_id,
+            change_set_id,
+            &index_pointer_value,
+            new_revision.into(),
+        )
+        .await;
 
         Ok(new_revision.into())
     }
@@ -416,6 +449,56 @@ impl FriggStore {
             return Ok(None);
         }
 
+        let object = self
+            .get_change_set_index_object(index_pointer_value)
+            .await?;
+
+        Ok(Some((object, revision)))
+    }
+
+    /// Gets the change set `MvIndex`, even if it was built for outdated MV definitions, along with
+    /// the MV kinds whose definitions have changed since it was built.
+    ///
+    /// This allows serving (and incrementally migrating) an existing index while the outdated
+    /// kinds are rebuilt, rather than treating the whole index as missing.
+    #[instrument(
+        name = "frigg.get_change_set_index_allowing_outdated",
+        level = "debug",
+        skip_all,
+        fields(
+            si.workspace.id = %workspace_id,
+            si.change_set.id = %change_set_id,
+            si.frigg.outdated_kind_count = Empty,
+        )
+    )]
+    pub async fn get_change_set_index_allowing_outdated(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+    ) -> Result<Option<(FrontendObject, KvRevision, HashSet<String>)>> {
+        let span = current_span_for_instrument_at!("debug");
+
+        let Some((index_pointer_value, revision)) = self
+            .get_change_set_index_pointer_value(workspace_id, change_set_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let outdated_kinds = outdated_definition_kinds(&index_pointer_value.definition_checksums);
+        span.record("si.frigg.outdated_kind_count", outdated_kinds.len());
+
+        let object = self
+            .get_change_set_index_object(index_pointer_value)
+            .await?;
+
+        Ok(Some((object, revision, outdated_kinds)))
+    }
+
+    pub(crate) async fn get_change_set_index_object(
+        &self,
+        index_pointer_value: ChangeSetIndexPointerValueV2,
+    ) -> Result<FrontendObject> {
         // If we have a dangling pointer, that is an error and not a "None" case.
         let object_key = index_pointer_value.index_object_key;
         let bytes = self
@@ -427,9 +510,8 @@ impl FriggStore {
                 monotonic!(frigg_get_change_set_index_object_not_found = 1);
                 Error::IndexObjectNotFound(object_key.into())
             })?;
-        let object = serde_json::from_slice(bytes.as_ref()).map_err(Error::Deserialize)?;
 
-        Ok(Some((object, revision)))
+        serde_json::from_slice(bytes.as_ref()).map_err(Error::Deserialize)
     }
 
     #[instrument(
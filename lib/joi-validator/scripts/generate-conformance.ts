@@ -0,0 +1,129 @@
+// Records how Joi describes and validates each case, for the conformance test in src/test.rs.
+//
+// Run from lib/joi-validator after changing the cases:
+//
+//   deno run --allow-write=conformance.json scripts/generate-conformance.ts
+import Joi from "npm:joi@17.13.3";
+
+const limit = new Date("2020-01-01T00:00:00.000Z");
+
+const cases: [string, Joi.Schema, unknown][] = [
+  // date
+  ["date base", Joi.date(), "2020-01-01"],
+  ["date base timestamp", Joi.date(), 1577836800000],
+  ["date base invalid", Joi.date(), "not a date"],
+  ["date base boolean", Joi.date(), true],
+  ["date iso", Joi.date().iso(), "2020-01-01T10:20:30Z"],
+  ["date iso invalid", Joi.date().iso(), "01/02/2020"],
+  ["date javascript", Joi.date().timestamp(), "1577836800000"],
+  ["date javascript invalid", Joi.date().timestamp(), "abc"],
+  ["date unix", Joi.date().timestamp("unix"), 1577836800],
+  ["date unix invalid", Joi.date().timestamp("unix"), "abc"],
+  ["date min", Joi.date().min(limit), "2019-12-31"],
+  ["date max", Joi.date().max(limit), "2020-01-02"],
+  ["date greater", Joi.date().greater(limit), "2020-01-01"],
+  ["date less", Joi.date().less(limit), "2020-01-01"],
+  ["date label", Joi.date().label("Start"), "nope"],
+  ["date required", Joi.date().required(), undefined],
+
+  // alternatives
+  ["alternatives types", Joi.alternatives().try(Joi.string(), Joi.number()), true],
+  ["alternatives single", Joi.alternatives().try(Joi.string().max(2)), "abc"],
+  [
+    "alternatives complex",
+    Joi.alternatives().try(Joi.string().max(2), Joi.number()),
+    "abc",
+  ],
+  [
+    "alternatives match",
+    Joi.alternatives().try(Joi.number().min(10), Joi.number().max(1)),
+    5,
+  ],
+  [
+    "alternatives valids",
+    Joi.alternatives().try(Joi.string().valid("a", "b"), Joi.number()),
+    "c",
+  ],
+  [
+    "alternatives one",
+    Joi.alternatives().try(Joi.string(), Joi.number()).match("one"),
+    "5",
+  ],
+  [
+    "alternatives all",
+    Joi.alternatives().try(Joi.number().min(1), Joi.number().max(3)).match("all"),
+    5,
+  ],
+  [
+    "alternatives any",
+    Joi.alternatives().try(Joi.string(), Joi.number()).match("one"),
+    true,
+  ],
+
+  // array
+  ["array base", Joi.array(), "a"],
+  ["array item", Joi.array().items(Joi.string()), ["a", 1]],
+  ["array includes", Joi.array().items(Joi.string(), Joi.boolean()), ["a", true, 1]],
+  ["array min", Joi.array().min(2), [1]],
+  ["array max", Joi.array().max(1), [1, 2]],
+  ["array length", Joi.array().length(2), [1]],
+  ["array single", Joi.array().items(Joi.string()).single(), "a"],
+  ["array json", Joi.array().items(Joi.number()), "[1, 2]"],
+  [
+    "array nested",
+    Joi.object({ tags: Joi.array().items(Joi.string()) }),
+    { tags: ["a", 1] },
+  ],
+
+  // object
+  ["object required key", Joi.object({ a: Joi.string().required() }), {}],
+  ["object unknown", Joi.object({ a: Joi.string() }), { a: "x", b: 1 }],
+  ["object allow unknown", Joi.object({ a: Joi.string() }).unknown(), { b: 1 }],
+  ["object any keys", Joi.object(), { b: 1 }],
+  ["object base string", Joi.object(), "x"],
+  ["object base array", Joi.object(), []],
+  ["object json", Joi.object({ a: Joi.number() }), '{ "a": 1 }'],
+  ["object pattern", Joi.object().pattern(/^x-/, Joi.string()), { "x-a": 1 }],
+  ["object pattern unknown", Joi.object().pattern(/^x-/, Joi.string()), { a: "a" }],
+  ["object min", Joi.object().min(2), { a: 1 }],
+  ["object max", Joi.object().max(1), { a: 1, b: 2 }],
+  ["object length", Joi.object().length(1), {}],
+  [
+    "object nested",
+    Joi.object({ a: Joi.object({ b: Joi.number() }) }),
+    { a: { b: "x" } },
+  ],
+  [
+    "object child label",
+    Joi.object({ a: Joi.number().label("Count") }),
+    { a: "x" },
+  ],
+
+  // valid values
+  ["valid one of", Joi.string().valid("a", "b"), "c"],
+  ["valid single", Joi.string().valid("a"), "c"],
+  ["valid numbers", Joi.number().valid(1, 2), 3],
+  ["valid other type", Joi.string().valid("a", "b"), 1],
+  ["valid allow", Joi.string().min(3).allow("a"), "a"],
+  ["invalid", Joi.string().invalid("a"), "a"],
+  ["number unsafe", Joi.number(), 9007199254740992],
+];
+
+const results = cases.map(([name, schema, value]) => {
+  const { error } = schema.validate(value);
+  const detail = error?.details[0];
+  return {
+    name,
+    schema: schema.describe(),
+    // Undefined values are left out, so they stay distinct from null
+    ...(value === undefined ? {} : { value }),
+    error: detail
+      ? { message: detail.message, path: detail.path, type: detail.type }
+      : null,
+  };
+});
+
+await Deno.writeTextFile(
+  "conformance.json",
+  JSON.stringify(results, null, 2) + "\n",
+);
@@ -1,8 +1,171 @@
 use serde::Deserialize;
 
+use crate::{
+    Report,
+    StringOrNumber,
+    generic,
+    rule_err,
+    to_template_string,
+};
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
 pub struct Validator {
-    pub matches: Vec<crate::Validator>,
+    pub matches: Vec<Match>,
+    #[serde(flatten)]
+    pub base: generic::Validator<serde_json::Value, Flags>,
+}
+
+impl Validator {
+    pub(crate) fn validate(
+        self,
+        value: &Option<serde_json::Value>,
+        path: &[StringOrNumber],
+        label: Option<&str>,
+    ) -> Result<(), Report> {
+        let report = |error| Report::new(path, label, value, error);
+
+        self.base.validate_presence(value).map_err(report)?;
+        let Some(json) = value else {
+            return Ok(());
+        };
+        if self.base.validate_value(Some(json)).map_err(report)? {
+            return Ok(());
+        }
+
+        match self.base.flags.extra_flags.r#match {
+            None | Some(Mode::Any) => {
+                let mut failures = Vec::with_capacity(self.matches.len());
+                for Match { schema } in self.matches {
+                    let valids = schema.allowed_values();
+                    match schema.check(value, path) {
+                        Ok(()) => return Ok(()),
+                        Err(failure) => failures.push((valids, failure)),
+                    }
+                }
+                Err(Self::failure(failures, path).unwrap_or_else(report))
+            }
+            Some(mode) => {
+                let total = self.matches.len();
+                let matched = self
+                    .matches
+                    .into_iter()
+                    .filter(|Match { schema }| schema.clone().check(value, path).is_ok())
+                    .count();
+                if matched == 0 {
+                    Err(report(rule_err(
+                        "alternatives.any",
+                        "does not match any of the allowed types",
+                    )))
+                } else if mode == Mode::One && matched > 1 {
+                    Err(report(rule_err(
+                        "alternatives.one",
+                        "matches more than one allowed type",
+                    )))
+                } else if mode == Mode::All && matched < total {
+                    Err(report(rule_err(
+                        "alternatives.all",
+                        "does not match all of the required types",
+                    )))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    // Picks the error Joi reports when no alternative matched: the failure itself if there was
+    // only one, the list of expected types if every alternative failed on its type or allowed
+    // values, the only failure that got past its type check, or a generic mismatch.
+    fn failure(
+        mut failures: Vec<(Vec<serde_json::Value>, Report)>,
+        path: &[StringOrNumber],
+    ) -> Result<Report, (String, String)> {
+        if failures.len() == 1 {
+            if let Some((_, failure)) = failures.pop() {
+                return Ok(failure);
+            }
+        }
+        if failures.is_empty() {
+            return Err(rule_err(
+                "alternatives.any",
+                "does not match any of the allowed types",
+            ));
+        }
+
+        let mut types = Vec::new();
+        let mut complex = Vec::new();
+        for (valids, failure) in failures {
+            if failure.path.len() != path.len() {
+                complex.push(failure);
+                continue;
+            }
+            if failure.r#type == "any.only" {
+                for valid in valids {
+                    if !types.contains(&valid) {
+                        types.push(valid);
+                    }
+                }
+                continue;
+            }
+            match failure.r#type.split_once('.') {
+                Some((r#type, "base")) => {
+                    let r#type = serde_json::Value::from(r#type);
+                    if !types.contains(&r#type) {
+                        types.push(r#type);
+                    }
+                }
+                _ => complex.push(failure),
+            }
+        }
+
+        match (complex.pop(), complex.is_empty()) {
+            (None, _) => Err(rule_err(
+                "alternatives.types",
+                format!(
+                    "must be one of {}",
+                    to_template_string(&serde_json::Value::Array(types))
+                ),
+            )),
+            (Some(failure), true) => Ok(failure),
+            (Some(_), false) => Err(rule_err(
+                "alternatives.match",
+                "does not match any of the allowed types",
+            )),
+        }
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push("alternatives.any");
+        rule_names.extend(
+            self.matches
+                .iter()
+                .flat_map(|Match { schema }| schema.rule_names()),
+        );
+        rule_names
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Match {
+    pub schema: crate::Validator,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    r#match: Option<Mode>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum Mode {
+    All,
+    Any,
+    One,
 }
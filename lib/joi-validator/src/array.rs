@@ -0,0 +1,176 @@
+use serde::Deserialize;
+
+use crate::{
+    Args,
+    Report,
+    StringOrNumber,
+    child_path,
+    generic,
+    require,
+    rule_err,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Validator {
+    #[serde(default)]
+    rules: Vec<Rule>,
+    #[serde(default)]
+    items: Vec<crate::Validator>,
+    #[serde(flatten)]
+    pub base: generic::Validator<serde_json::Value, Flags>,
+}
+
+impl Validator {
+    pub(crate) fn validate(
+        self,
+        value: &Option<serde_json::Value>,
+        path: &[StringOrNumber],
+        label: Option<&str>,
+    ) -> Result<(), Report> {
+        let report = |error| Report::new(path, label, value, error);
+
+        self.base.validate_presence(value).map_err(report)?;
+        let Some(json) = value else {
+            return Ok(());
+        };
+        // Joi parses strings that look like JSON arrays
+        let json = match json {
+            serde_json::Value::String(string) if string.trim_start().starts_with('[') => {
+                serde_json::from_str(string).unwrap_or_else(|_| json.clone())
+            }
+            json => json.clone(),
+        };
+        if self.base.validate_value(Some(&json)).map_err(report)? {
+            return Ok(());
+        }
+        let values = match json {
+            serde_json::Value::Array(values) => values,
+            json if self.base.flags.extra_flags.single => vec![json],
+            _ => return Err(report(rule_err("array.base", "must be an array"))),
+        };
+
+        // Joi checks items where the items rule was added, which is first unless it says otherwise
+        let mut items = Some(self.items);
+        if !self.rules.iter().any(|rule| matches!(rule, Rule::Items)) {
+            Self::validate_items(items.take(), &values, path, label)?;
+        }
+        for rule in self.rules {
+            match rule {
+                Rule::Items => Self::validate_items(items.take(), &values, path, label)?,
+                rule => rule.validate(&values).map_err(report)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_items(
+        items: Option<Vec<crate::Validator>>,
+        values: &[serde_json::Value],
+        path: &[StringOrNumber],
+        label: Option<&str>,
+    ) -> Result<(), Report> {
+        let Some(items) = items.filter(|items| !items.is_empty()) else {
+            return Ok(());
+        };
+        for (index, value) in values.iter().enumerate() {
+            let path = child_path(path, StringOrNumber::Number(index));
+            let value = Some(value.clone());
+            // With a single item schema, Joi reports its error directly
+            if let [item] = items.as_slice() {
+                item.clone().check(&value, &path)?;
+                continue;
+            }
+            if !items
+                .iter()
+                .any(|item| item.clone().check(&value, &path).is_ok())
+            {
+                return Err(Report::new(
+                    &path,
+                    label,
+                    &value,
+                    rule_err("array.includes", "does not match any of the allowed types"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push("array.base");
+        if self.items.len() > 1 {
+            rule_names.push("array.includes");
+        }
+        rule_names.extend(self.rules.iter().filter_map(Rule::rule_name));
+        rule_names.extend(self.items.iter().flat_map(crate::Validator::rule_names));
+        rule_names
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+#[serde(tag = "name")]
+enum Rule {
+    Items,
+    Length(Args<Limit>),
+    Max(Args<Limit>),
+    Min(Args<Limit>),
+    // Has(Args<Schema>),
+    // Ordered,
+    // Sort(Args<SortOptions>),
+    // Unique(Args<UniqueOptions>),
+}
+
+impl Rule {
+    fn validate(self, values: &[serde_json::Value]) -> Result<(), (String, String)> {
+        match self {
+            Self::Items => Ok(()),
+            Self::Length(rule) => require(
+                values.len() == rule.args.limit,
+                "array.length",
+                format!("must contain {} items", rule.args.limit),
+            ),
+            Self::Max(rule) => require(
+                values.len() <= rule.args.limit,
+                "array.max",
+                format!(
+                    "must contain less than or equal to {} items",
+                    rule.args.limit
+                ),
+            ),
+            Self::Min(rule) => require(
+                values.len() >= rule.args.limit,
+                "array.min",
+                format!("must contain at least {} items", rule.args.limit),
+            ),
+        }
+    }
+
+    fn rule_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Items => None,
+            Self::Length(_) => Some("array.length"),
+            Self::Max(_) => Some("array.max"),
+            Self::Min(_) => Some("array.min"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct Limit {
+    limit: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    // Accept a single value as an array of one
+    #[serde(default)]
+    single: bool,
+}
@@ -35,11 +35,13 @@ impl Validator {
                     }
                 }
                 _ => None,
+            };
+            if self.base.validate_value(value.as_ref())? {
+                return Ok(());
             }
-            .ok_or(rule_err("boolean.base", "must be a boolean"))?;
+            let value = value.ok_or(rule_err("boolean.base", "must be a boolean"))?;
 
             // Now that we have the boolean, validate it
-            self.base.validate_value(&value)?;
             for rule in self.rules {
                 rule.validate(&value)?;
             }
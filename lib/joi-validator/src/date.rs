@@ -1,31 +1,303 @@
-use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use std::sync::LazyLock;
 
-use crate::{generic, Args, Limit};
+use chrono::{
+    DateTime,
+    FixedOffset,
+    NaiveDate,
+    SecondsFormat,
+    TimeZone,
+    Utc,
+};
+use regex::Regex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-pub type Validator = generic::Validator<DateTime<Utc>, Rule, Flags>;
+use crate::{
+    Args,
+    generic,
+    require,
+    rule_err,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Validator {
+    #[serde(default)]
+    rules: Vec<Rule>,
+    #[serde(flatten)]
+    pub base: generic::Validator<JsDate, Flags>,
+}
+
+impl Validator {
+    pub fn validate(self, value: &Option<serde_json::Value>) -> Result<(), (String, String)> {
+        self.base.validate_presence(value)?;
+        if let Some(value) = value {
+            let format = self.base.flags.extra_flags.format;
+            let date = parse(value, format);
+            if self.base.validate_value(date.as_ref())? {
+                return Ok(());
+            }
+            let date = match (date, format, value) {
+                (Some(date), _, _) => date,
+                // Joi only reports the format for strings it tried to parse
+                (None, Some(format), serde_json::Value::String(_)) => {
+                    return Err(rule_err(
+                        "date.format",
+                        format!("must be in {} format", format.description()),
+                    ));
+                }
+                (None, _, _) => return Err(rule_err("date.base", "must be a valid date")),
+            };
+
+            // Now that we have the date, validate it
+            for rule in self.rules {
+                rule.validate(&date)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push("date.base");
+        if self.base.flags.extra_flags.format.is_some() {
+            rule_names.push("date.format");
+        }
+        rule_names.extend(self.rules.iter().map(Rule::rule_name));
+        rule_names
+    }
+}
+
+// Converts a value to a date the way Joi does: numbers are timestamps, and strings are either
+// numeric timestamps or dates in one of the common forms JavaScript's Date accepts.
+fn parse(value: &serde_json::Value, format: Option<Format>) -> Option<JsDate> {
+    let millis = match (value, format) {
+        (serde_json::Value::String(string), Some(Format::Iso)) => return parse_iso(string),
+        (serde_json::Value::Number(_), Some(Format::Iso)) => return None,
+        (serde_json::Value::Number(number), _) => number.as_f64()?,
+        (serde_json::Value::String(string), format) => match string.parse::<f64>() {
+            Ok(number) if NUMERIC.is_match(string) => number,
+            _ if format.is_some() => return None,
+            _ => {
+                return parse_iso(string).or_else(|| {
+                    DateTime::parse_from_rfc2822(string)
+                        .ok()
+                        .map(|date| JsDate(date.to_utc()))
+                });
+            }
+        },
+        _ => return None,
+    };
+    let millis = match format {
+        Some(Format::Unix) => millis * 1000.0,
+        _ => millis,
+    };
+    // JavaScript truncates fractional milliseconds
+    DateTime::from_timestamp_millis(millis.trunc() as i64).map(JsDate)
+}
+
+static NUMERIC: LazyLock<Regex> = LazyLock::new(|| {
+    #[allow(clippy::unwrap_used)]
+    Regex::new(r"^[+-]?\d+(\.\d+)?$").unwrap()
+});
+
+static ISO_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    #[allow(clippy::unwrap_used)]
+    Regex::new(concat!(
+        r"^(\d{4})(?:-(\d{2})(?:-(\d{2})",
+        r"(?:[T ](\d{2}):(\d{2})(?::(\d{2})(?:[.,](\d+))?)?",
+        r"(Z|[+-]\d{2}(?::?\d{2})?)?)?)?)?$",
+    ))
+    .unwrap()
+});
+
+// Parses the ISO 8601 forms JavaScript understands. Times without an offset are read as UTC.
+fn parse_iso(value: &str) -> Option<JsDate> {
+    let captures = ISO_DATE.captures(value)?;
+    let number = |index: usize, default: u32| -> Option<u32> {
+        captures
+            .get(index)
+            .map_or(Some(default), |capture| capture.as_str().parse().ok())
+    };
+    let millis = captures.get(7).map_or(Some(0), |fraction| {
+        format!("{:0<3}", &fraction.as_str()[..fraction.len().min(3)])
+            .parse()
+            .ok()
+    })?;
+
+    let date = NaiveDate::from_ymd_opt(
+        captures.get(1)?.as_str().parse().ok()?,
+        number(2, 1)?,
+        number(3, 1)?,
+    )?
+    .and_hms_milli_opt(number(4, 0)?, number(5, 0)?, number(6, 0)?, millis)?;
+
+    let offset = match captures.get(8).map(|offset| offset.as_str()) {
+        None | Some("Z") => FixedOffset::east_opt(0)?,
+        Some(offset) => {
+            let digits = offset[1..].replace(':', "");
+            let hours: i32 = digits.get(..2)?.parse().ok()?;
+            let minutes: i32 = digits
+                .get(2..)
+                .filter(|m| !m.is_empty())
+                .unwrap_or("0")
+                .parse()
+                .ok()?;
+            let seconds = (hours * 60 + minutes) * 60;
+            if offset.starts_with('-') {
+                FixedOffset::west_opt(seconds)?
+            } else {
+                FixedOffset::east_opt(seconds)?
+            }
+        }
+    };
+
+    offset
+        .from_local_datetime(&date)
+        .single()
+        .map(|date| JsDate(date.to_utc()))
+}
+
+// A date that compares like a JavaScript Date and renders like Date.prototype.toISOString()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "String")]
+pub struct JsDate(DateTime<Utc>);
+
+impl TryFrom<String> for JsDate {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_iso(&value).ok_or_else(|| format!("invalid date: {value}"))
+    }
+}
+
+impl Serialize for JsDate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl std::fmt::Display for JsDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+}
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
 #[serde(tag = "name")]
-pub enum Rule {
-    Greater(Args<Limit<DateTime<Utc>>>),
-    Less(Args<Limit<DateTime<Utc>>>),
-    Max(Args<Limit<DateTime<Utc>>>),
-    Min(Args<Limit<DateTime<Utc>>>),
+enum Rule {
+    Greater(Args<Limit>),
+    Less(Args<Limit>),
+    Max(Args<Limit>),
+    Min(Args<Limit>),
+}
+
+impl Rule {
+    fn validate(&self, value: &JsDate) -> Result<(), (String, String)> {
+        match self {
+            Rule::Greater(rule) => require(
+                value > &rule.args.date.resolve(),
+                "date.greater",
+                format!("must be greater than {}", rule.args.date),
+            ),
+            Rule::Less(rule) => require(
+                value < &rule.args.date.resolve(),
+                "date.less",
+                format!("must be less than {}", rule.args.date),
+            ),
+            Rule::Max(rule) => require(
+                value <= &rule.args.date.resolve(),
+                "date.max",
+                format!("must be less than or equal to {}", rule.args.date),
+            ),
+            Rule::Min(rule) => require(
+                value >= &rule.args.date.resolve(),
+                "date.min",
+                format!("must be greater than or equal to {}", rule.args.date),
+            ),
+        }
+    }
+
+    fn rule_name(&self) -> &'static str {
+        match self {
+            Rule::Greater(_) => "date.greater",
+            Rule::Less(_) => "date.less",
+            Rule::Max(_) => "date.max",
+            Rule::Min(_) => "date.min",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
-pub struct Flags {
-    pub format: Option<Format>,
+struct Limit {
+    date: DateLimit,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(untagged)]
+enum DateLimit {
+    Now(Now),
+    Date(JsDate),
+}
+
+impl DateLimit {
+    fn resolve(&self) -> JsDate {
+        match self {
+            DateLimit::Now(_) => {
+                // Joi compares against the current time, to the millisecond
+                let now = Utc::now();
+                DateTime::from_timestamp_millis(now.timestamp_millis()).map_or(JsDate(now), JsDate)
+            }
+            DateLimit::Date(date) => *date,
+        }
+    }
+}
+
+// Joi quotes the limit in its messages, and shows "now" rather than the time it compared to
+impl std::fmt::Display for DateLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateLimit::Now(_) => f.write_str("\"now\""),
+            DateLimit::Date(date) => write!(f, "\"{date}\""),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub enum Format {
+enum Now {
+    Now,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    format: Option<Format>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Format {
     Iso,        // Joi.date().iso()
     Javascript, // Joi.date().timestamp()
+    Unix,       // Joi.date().timestamp("unix")
+}
+
+impl Format {
+    fn description(&self) -> &'static str {
+        match self {
+            Format::Iso => "ISO 8601 date",
+            Format::Javascript => "timestamp or number of milliseconds",
+            Format::Unix => "timestamp or number of seconds",
+        }
+    }
 }
@@ -5,7 +5,11 @@ use serde::{
     Serialize,
 };
 
-use crate::require;
+use crate::{
+    require,
+    rule_err,
+    to_template_string,
+};
 
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -30,7 +34,10 @@ pub struct Validator<T, ExtraFlags> {
 }
 
 impl<T: PartialEq + Serialize + Debug, ExtraFlags> Validator<T, ExtraFlags> {
-    pub fn validate_value(&self, value: &T) -> Result<(), (String, String)> {
+    // Checks the allowed and invalid values before the type is checked, as Joi does. The value
+    // is None if it could not be converted to the type. Returns true if the value is explicitly
+    // allowed, in which case the type and rules are not checked.
+    pub fn validate_value(&self, value: Option<&T>) -> Result<bool, (String, String)> {
         self.validate_valid_values(value)
     }
 
@@ -42,26 +49,40 @@ impl<T: PartialEq + Serialize + Debug, ExtraFlags> Validator<T, ExtraFlags> {
         }
     }
 
-    fn validate_valid_values(&self, value: &T) -> Result<(), (String, String)> {
-        // Check if it's one of the allowed values
-        if self.flags.only {
-            if let Some(allow) = &self.allow {
-                require(
-                    allow.contains(value),
+    pub fn allowed_values(&self) -> Vec<serde_json::Value> {
+        self.allow
+            .iter()
+            .flatten()
+            .filter_map(|value| serde_json::to_value(value).ok())
+            .collect()
+    }
+
+    fn validate_valid_values(&self, value: Option<&T>) -> Result<bool, (String, String)> {
+        if let Some(allow) = &self.allow {
+            if value.is_some_and(|value| allow.contains(value)) {
+                return Ok(true);
+            }
+            // Check if it's one of the allowed values
+            if self.flags.only {
+                return Err(rule_err(
                     "any.only",
-                    format!("must be one of {:?}", self.allow),
-                )?;
+                    format!(
+                        "must be {}{}",
+                        if allow.len() == 1 { "" } else { "one of " },
+                        to_template_string(&serde_json::Value::Array(self.allowed_values()))
+                    ),
+                ));
             }
         }
         // Check for invalid values
         if let Some(invalid) = &self.invalid {
             require(
-                !invalid.contains(value),
+                !value.is_some_and(|value| invalid.contains(value)),
                 "any.invalid",
                 "contains an invalid value",
             )?;
         }
-        Ok(())
+        Ok(false)
     }
 
     pub fn rule_names(&self) -> Vec<&'static str> {
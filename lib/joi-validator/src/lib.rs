@@ -32,12 +32,13 @@ use serde::{
 };
 use serde_with::serde_as;
 
-// These are commented out until we decide to support them in any way.
-// pub mod alternatives;
-// pub mod date;
+mod alternatives;
+mod array;
 mod boolean;
+mod date;
 pub mod generic;
 mod number;
+mod object;
 mod string;
 #[cfg(test)]
 mod test;
@@ -54,10 +55,12 @@ pub enum Error {
 #[serde(deny_unknown_fields)]
 #[serde(tag = "type")]
 pub enum Validator {
-    // Alternatives(alternatives::Validator),
+    Alternatives(alternatives::Validator),
+    Array(array::Validator),
     Boolean(boolean::Validator),
-    // Date(date::Validator),
+    Date(date::Validator),
     Number(number::Validator),
+    Object(object::Validator),
     String(string::Validator),
 }
 
@@ -65,38 +68,40 @@ impl Validator {
     // Takes in an optional value, validates it according to the rules, and returns a response
     // in Joi format.
     pub fn validate(
-        mut self,
+        self,
         value: &Option<serde_json::Value>,
     ) -> ValidateResponse<Option<serde_json::Value>> {
-        let label = self.take_label().unwrap_or("value".into());
-        let result = match self {
-            Validator::Boolean(validator) => validator.validate(value),
-            Validator::Number(validator) => validator.validate(value),
-            Validator::String(validator) => validator.validate(value),
-        };
         ValidateResponse {
             value: value.clone(),
-            error: match result {
-                Ok(()) => None,
-                Err((r#type, message)) => Some(ValidateError {
-                    _original: value.clone(),
-                    details: vec![ValidateErrorDetails {
-                        message: format!("{} {}", Self::to_json_string(&label), message),
-                        r#type,
-                        path: vec![],
-                        context: ValidateContext {
-                            label,
-                            value: value.clone(),
-                            extra: None,
-                        },
-                    }],
-                }),
-            },
+            error: self.check(value, &[]).err().map(|report| ValidateError {
+                _original: value.clone(),
+                details: vec![report.into_details()],
+            }),
             warning: None,
             artifacts: None,
         }
     }
 
+    // Validates a value at the given path, stopping at the first error like Joi's abortEarly.
+    fn check(
+        mut self,
+        value: &Option<serde_json::Value>,
+        path: &[StringOrNumber],
+    ) -> Result<(), Report> {
+        let label = self.take_label();
+        let label = label.as_deref();
+        let result = match self {
+            Validator::Alternatives(validator) => return validator.validate(value, path, label),
+            Validator::Array(validator) => return validator.validate(value, path, label),
+            Validator::Boolean(validator) => validator.validate(value),
+            Validator::Date(validator) => validator.validate(value),
+            Validator::Number(validator) => validator.validate(value),
+            Validator::Object(validator) => return validator.validate(value, path, label),
+            Validator::String(validator) => validator.validate(value),
+        };
+        result.map_err(|error| Report::new(path, label, value, error))
+    }
+
     // Outputs a JSON string, with quotes and escapes
     fn to_json_string(string: &str) -> String {
         serde_json::Value::from(string).to_string()
@@ -104,21 +109,139 @@ impl Validator {
 
     fn take_label(&mut self) -> Option<String> {
         match self {
+            Validator::Alternatives(validator) => validator.base.flags.label.take(),
+            Validator::Array(validator) => validator.base.flags.label.take(),
             Validator::Boolean(validator) => validator.base.flags.label.take(),
+            Validator::Date(validator) => validator.base.flags.label.take(),
             Validator::Number(validator) => validator.base.flags.label.take(),
+            Validator::Object(validator) => validator.base.flags.label.take(),
             Validator::String(validator) => validator.base.flags.label.take(),
         }
     }
 
+    // The allowed values, as reported by Joi when a value is not one of them
+    fn allowed_values(&self) -> Vec<serde_json::Value> {
+        match self {
+            Validator::Alternatives(validator) => validator.base.allowed_values(),
+            Validator::Array(validator) => validator.base.allowed_values(),
+            Validator::Boolean(validator) => validator.base.allowed_values(),
+            Validator::Date(validator) => validator.base.allowed_values(),
+            Validator::Number(validator) => validator.base.allowed_values(),
+            Validator::Object(validator) => validator.base.allowed_values(),
+            Validator::String(validator) => validator.base.allowed_values(),
+        }
+    }
+
     pub fn rule_names(&self) -> Vec<&'static str> {
         match self {
+            Validator::Alternatives(validator) => validator.rule_names(),
+            Validator::Array(validator) => validator.rule_names(),
             Validator::Boolean(validator) => validator.rule_names(),
+            Validator::Date(validator) => validator.rule_names(),
             Validator::Number(validator) => validator.rule_names(),
+            Validator::Object(validator) => validator.rule_names(),
             Validator::String(validator) => validator.rule_names(),
         }
     }
 }
 
+// A single validation failure, located at a path inside the validated value.
+#[derive(Debug, Clone)]
+struct Report {
+    r#type: String,
+    message: String,
+    path: Vec<StringOrNumber>,
+    label: String,
+    // Boxed to keep results that carry a report small
+    value: Box<Option<serde_json::Value>>,
+}
+
+impl Report {
+    fn new(
+        path: &[StringOrNumber],
+        label: Option<&str>,
+        value: &Option<serde_json::Value>,
+        (r#type, message): (String, String),
+    ) -> Self {
+        Self {
+            r#type,
+            message,
+            path: path.to_vec(),
+            label: label.map_or_else(|| path_label(path), Into::into),
+            value: Box::new(value.clone()),
+        }
+    }
+
+    fn into_details(self) -> ValidateErrorDetails<Option<serde_json::Value>> {
+        let extra = self
+            .path
+            .last()
+            .map(|key| serde_json::json!({ "key": key }));
+        ValidateErrorDetails {
+            message: format!(
+                "{} {}",
+                Validator::to_json_string(&self.label),
+                self.message
+            ),
+            r#type: self.r#type,
+            path: self.path,
+            context: ValidateContext {
+                label: self.label,
+                value: *self.value,
+                extra,
+            },
+        }
+    }
+}
+
+// Joi labels unlabelled values by their path, e.g. "tags[0].name"
+fn path_label(path: &[StringOrNumber]) -> String {
+    let mut label = String::new();
+    for segment in path {
+        match segment {
+            StringOrNumber::String(key) => {
+                if !label.is_empty() {
+                    label.push('.');
+                }
+                label.push_str(key);
+            }
+            StringOrNumber::Number(index) => label.push_str(&format!("[{index}]")),
+        }
+    }
+    if label.is_empty() {
+        "value".into()
+    } else {
+        label
+    }
+}
+
+fn child_path(path: &[StringOrNumber], segment: StringOrNumber) -> Vec<StringOrNumber> {
+    let mut path = path.to_vec();
+    path.push(segment);
+    path
+}
+
+// Renders a value the way Joi's error templates do: strings without quotes, numbers as
+// JavaScript prints them and arrays as "[a, b]"
+fn to_template_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(string) => string.clone(),
+        serde_json::Value::Number(number) => number
+            .as_f64()
+            .map_or_else(|| number.to_string(), |number| number.to_string()),
+        serde_json::Value::Array(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(to_template_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        serde_json::Value::Object(_) => "[object Object]".into(),
+        serde_json::Value::Bool(_) | serde_json::Value::Null => value.to_string(),
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
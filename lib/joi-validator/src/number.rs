@@ -25,7 +25,7 @@ impl Validator {
             require(
                 (JS_MIN_SAFE_INTEGER..=JS_MAX_SAFE_INTEGER).contains(&value),
                 "number.unsafe",
-                "unsafe number",
+                "must be a safe number",
             )?;
         }
         Ok(())
@@ -40,12 +40,14 @@ impl Validator {
                 serde_json::Value::Number(number) => number.as_f64(),
                 serde_json::Value::String(string) => string.trim().parse().ok(),
                 _ => None,
+            };
+            if self.base.validate_value(value.as_ref())? {
+                return Ok(());
             }
-            .ok_or_else(|| rule_err("number.base", "must be a number"))?;
+            let value = value.ok_or_else(|| rule_err("number.base", "must be a number"))?;
 
             // Now that we have the float, validate it
             self.validate_safe(value)?;
-            self.base.validate_value(&value)?;
             for rule in self.rules {
                 rule.validate(&value)?;
             }
@@ -0,0 +1,235 @@
+use indexmap::IndexMap;
+use regex::{
+    Regex,
+    RegexBuilder,
+};
+use serde::Deserialize;
+
+use crate::{
+    Args,
+    Report,
+    StringOrNumber,
+    child_path,
+    generic,
+    require,
+    rule_err,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Validator {
+    #[serde(default)]
+    rules: Vec<Rule>,
+    // Known keys, in the order Joi checks them. None allows any key.
+    keys: Option<IndexMap<String, crate::Validator>>,
+    #[serde(default)]
+    patterns: Vec<Pattern>,
+    #[serde(flatten)]
+    pub base: generic::Validator<serde_json::Value, Flags>,
+}
+
+impl Validator {
+    pub(crate) fn validate(
+        self,
+        value: &Option<serde_json::Value>,
+        path: &[StringOrNumber],
+        label: Option<&str>,
+    ) -> Result<(), Report> {
+        let report = |error| Report::new(path, label, value, error);
+
+        self.base.validate_presence(value).map_err(report)?;
+        let Some(json) = value else {
+            return Ok(());
+        };
+        // Joi parses strings that look like JSON objects
+        let json = match json {
+            serde_json::Value::String(string) if string.trim_start().starts_with('{') => {
+                serde_json::from_str(string).unwrap_or_else(|_| json.clone())
+            }
+            json => json.clone(),
+        };
+        if self.base.validate_value(Some(&json)).map_err(report)? {
+            return Ok(());
+        }
+        let serde_json::Value::Object(object) = json else {
+            return Err(report(rule_err("object.base", "must be of type object")));
+        };
+
+        let mut unprocessed: Vec<&String> = object.keys().collect();
+        if let Some(keys) = &self.keys {
+            for (key, schema) in keys {
+                unprocessed.retain(|unprocessed| *unprocessed != key);
+                schema.clone().check(
+                    &object.get(key).cloned(),
+                    &child_path(path, StringOrNumber::String(key.clone())),
+                )?;
+            }
+        }
+
+        let mut unknown = Vec::new();
+        for key in unprocessed {
+            let path = child_path(path, StringOrNumber::String(key.clone()));
+            let mut matched = false;
+            for pattern in &self.patterns {
+                if !pattern.regex.0.is_match(key) {
+                    continue;
+                }
+                matched = true;
+                pattern
+                    .rule
+                    .clone()
+                    .check(&object.get(key).cloned(), &path)?;
+                if !pattern.fallthrough {
+                    break;
+                }
+            }
+            if !matched {
+                unknown.push((key, path));
+            }
+        }
+
+        // Declaring keys or patterns makes every other key unknown
+        let declared = self.keys.is_some() || !self.patterns.is_empty();
+        if declared && !self.base.flags.extra_flags.unknown {
+            if let Some((key, path)) = unknown.first() {
+                // Joi reports unknown keys without the object's label
+                return Err(Report::new(
+                    path,
+                    None,
+                    &object.get(*key).cloned(),
+                    rule_err("object.unknown", "is not allowed"),
+                ));
+            }
+        }
+
+        for rule in self.rules {
+            rule.validate(object.len()).map_err(report)?;
+        }
+        Ok(())
+    }
+
+    pub fn rule_names(&self) -> Vec<&'static str> {
+        let mut rule_names = self.base.rule_names();
+        rule_names.push("object.base");
+        if !self.base.flags.extra_flags.unknown {
+            rule_names.push("object.unknown");
+        }
+        rule_names.extend(self.rules.iter().map(Rule::rule_name));
+        rule_names.extend(
+            self.keys
+                .iter()
+                .flat_map(IndexMap::values)
+                .chain(self.patterns.iter().map(|pattern| pattern.rule.as_ref()))
+                .flat_map(crate::Validator::rule_names),
+        );
+        rule_names
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct Pattern {
+    regex: JsRegex,
+    rule: Box<crate::Validator>,
+    #[serde(default)]
+    fallthrough: bool,
+}
+
+// A regular expression as Joi describes it, e.g. "/^x-/i"
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+struct JsRegex(Regex);
+
+impl TryFrom<String> for JsRegex {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (source, flags) = value
+            .strip_prefix('/')
+            .and_then(|value| value.rsplit_once('/'))
+            .ok_or_else(|| format!("invalid regex: {value}"))?;
+        let mut builder = RegexBuilder::new(source);
+        for flag in flags.chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                'u' => builder.unicode(true),
+                flag => return Err(format!("unsupported regex flag {flag}: {value}")),
+            };
+        }
+        builder
+            .build()
+            .map(Self)
+            .map_err(|error| format!("invalid regex {value}: {error}"))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+#[serde(tag = "name")]
+enum Rule {
+    Length(Args<Limit>),
+    Max(Args<Limit>),
+    Min(Args<Limit>),
+}
+
+impl Rule {
+    fn validate(self, keys: usize) -> Result<(), (String, String)> {
+        match self {
+            Self::Length(rule) => require(
+                keys == rule.args.limit,
+                "object.length",
+                format!("must have {}", Self::keys(rule.args.limit)),
+            ),
+            Self::Max(rule) => require(
+                keys <= rule.args.limit,
+                "object.max",
+                format!(
+                    "must have less than or equal to {}",
+                    Self::keys(rule.args.limit)
+                ),
+            ),
+            Self::Min(rule) => require(
+                keys >= rule.args.limit,
+                "object.min",
+                format!("must have at least {}", Self::keys(rule.args.limit)),
+            ),
+        }
+    }
+
+    fn keys(limit: usize) -> String {
+        if limit == 1 {
+            "1 key".into()
+        } else {
+            format!("{limit} keys")
+        }
+    }
+
+    fn rule_name(&self) -> &'static str {
+        match self {
+            Self::Length(_) => "object.length",
+            Self::Max(_) => "object.max",
+            Self::Min(_) => "object.min",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+struct Limit {
+    limit: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Flags {
+    // Set true to allow keys that are not declared
+    #[serde(default)]
+    unknown: bool,
+}
@@ -20,18 +20,23 @@ pub struct Validator {
 impl Validator {
     pub fn validate(self, value: &Option<serde_json::Value>) -> Result<(), (String, String)> {
         self.base.validate_presence(value)?;
-        match value {
-            Some(serde_json::Value::String(value)) => {
-                // Now that we have the string, validate it
-                self.base.validate_value(value)?;
-                for rule in self.rules {
-                    rule.validate(value)?;
-                }
-                Ok(())
-            }
-            Some(_) => Err(rule_err("string.base", "must be a string")),
-            None => Ok(()),
+        let Some(value) = value else {
+            return Ok(());
+        };
+        let value = match value {
+            serde_json::Value::String(value) => Some(value),
+            _ => None,
+        };
+        if self.base.validate_value(value)? {
+            return Ok(());
         }
+        let value = value.ok_or_else(|| rule_err("string.base", "must be a string"))?;
+
+        // Now that we have the string, validate it
+        for rule in self.rules {
+            rule.validate(value)?;
+        }
+        Ok(())
     }
 
     pub fn rule_names(&self) -> Vec<&'static str> {
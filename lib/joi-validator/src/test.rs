@@ -60,7 +60,143 @@ mod any {
     fn unsupported_types() -> Result<()> {
         unsupported(r#"{ "type": "any" }"#)?;
         unsupported(r#"{ "type": "alternatives" }"#)?;
-        unsupported(r#"{ "type": "date" }"#)?;
+        Ok(())
+    }
+}
+
+mod alternatives {
+    use super::*;
+
+    #[test]
+    fn try_schemas() -> Result<()> {
+        let joi = r#"{ "type": "alternatives", "matches": [
+            { "schema": { "type": "string" } },
+            { "schema": { "type": "number" } }
+        ] }"#;
+        valid(joi, "a")?;
+        valid(joi, 10)?;
+        valid_opt(joi, None)?;
+        invalid(joi, true)?;
+        invalid(joi, json!([]))?;
+        invalid(joi, json!(null))?;
+        Ok(())
+    }
+
+    #[test]
+    fn match_one() -> Result<()> {
+        let joi = r#"{ "type": "alternatives", "flags": { "match": "one" }, "matches": [
+            { "schema": { "type": "string" } },
+            { "schema": { "type": "number" } }
+        ] }"#;
+        valid(joi, "a")?;
+        valid(joi, 10)?;
+        invalid(joi, "10")?;
+        invalid(joi, true)?;
+        Ok(())
+    }
+
+    #[test]
+    fn match_all() -> Result<()> {
+        let joi = r#"{ "type": "alternatives", "flags": { "match": "all" }, "matches": [
+            { "schema": { "type": "number", "rules": [ { "name": "min", "args": { "limit": 1 } } ] } },
+            { "schema": { "type": "number", "rules": [ { "name": "max", "args": { "limit": 3 } } ] } }
+        ] }"#;
+        valid(joi, 1)?;
+        valid(joi, 3)?;
+        invalid(joi, 0)?;
+        invalid(joi, 4)?;
+        invalid(joi, "a")?;
+        Ok(())
+    }
+
+    #[test]
+    fn required() -> Result<()> {
+        let joi = r#"{ "type": "alternatives", "flags": { "presence": "required" }, "matches": [
+            { "schema": { "type": "string" } }
+        ] }"#;
+        valid(joi, "a")?;
+        invalid_opt(joi, None)?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_basics() -> Result<()> {
+        unsupported(
+            r#"{ "type": "alternatives", "matches": [ { "schema": { "type": "any" } } ] }"#,
+        )?;
+        unsupported(r#"{ "type": "alternatives", "matches": [ { "ref": "a", "is": {} } ] }"#)?;
+        unsupported(r#"{ "type": "alternatives", "matches": [], "flags": { "match": "some" } }"#)?;
+        unsupported(r#"{ "type": "alternatives", "matches": [], "extra": true }"#)?;
+        Ok(())
+    }
+}
+
+mod array {
+    use super::*;
+
+    #[test]
+    fn type_array() -> Result<()> {
+        let joi = r#"{ "type": "array" }"#;
+        valid_opt(joi, None)?;
+        valid(joi, json!([]))?;
+        valid(joi, json!([1, "a", null]))?;
+        valid(joi, "[1, 2]")?;
+        invalid(joi, "a")?;
+        invalid(joi, 10)?;
+        invalid(joi, json!({}))?;
+        invalid(joi, json!(null))?;
+        Ok(())
+    }
+
+    #[test]
+    fn items() -> Result<()> {
+        let joi = r#"{ "type": "array", "items": [ { "type": "string" } ] }"#;
+        valid(joi, json!([]))?;
+        valid(joi, json!(["a", "b"]))?;
+        invalid(joi, json!(["a", 1]))?;
+
+        let joi =
+            r#"{ "type": "array", "items": [ { "type": "string" }, { "type": "boolean" } ] }"#;
+        valid(joi, json!(["a", true]))?;
+        invalid(joi, json!(["a", true, 1]))?;
+        Ok(())
+    }
+
+    #[test]
+    fn min_max_length() -> Result<()> {
+        let joi = r#"{ "type": "array", "rules": [
+            { "name": "min", "args": { "limit": 1 } },
+            { "name": "max", "args": { "limit": 2 } }
+        ] }"#;
+        invalid(joi, json!([]))?;
+        valid(joi, json!([1]))?;
+        valid(joi, json!([1, 2]))?;
+        invalid(joi, json!([1, 2, 3]))?;
+
+        let joi =
+            r#"{ "type": "array", "rules": [ { "name": "length", "args": { "limit": 2 } } ] }"#;
+        invalid(joi, json!([1]))?;
+        valid(joi, json!([1, 2]))?;
+        invalid(joi, json!([1, 2, 3]))?;
+        unsupported(r#"{ "type": "array", "rules": [ { "name": "min", "args": { } } ] }"#)?;
+        Ok(())
+    }
+
+    #[test]
+    fn single() -> Result<()> {
+        let joi = r#"{ "type": "array", "flags": { "single": true }, "items": [ { "type": "string" } ] }"#;
+        valid(joi, "a")?;
+        valid(joi, json!(["a"]))?;
+        invalid(joi, 1)?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_rules() -> Result<()> {
+        unsupported(r#"{ "type": "array", "rules": [ { "name": "has" } ] }"#)?;
+        unsupported(r#"{ "type": "array", "rules": [ { "name": "unique" } ] }"#)?;
+        unsupported(r#"{ "type": "array", "ordered": [ { "type": "string" } ] }"#)?;
+        unsupported(r#"{ "type": "array", "flags": { "sparse": true } }"#)?;
         Ok(())
     }
 }
@@ -230,6 +366,116 @@ mod boolean {
     }
 }
 
+// Cases recorded from Joi itself by scripts/generate-conformance.ts
+mod conformance {
+    use super::*;
+
+    #[test]
+    fn matches_joi() -> Result<()> {
+        let cases: Vec<serde_json::Value> =
+            serde_json::from_str(include_str!("../conformance.json"))?;
+        for case in cases {
+            let name = &case["name"];
+            let validator: Validator = serde_json::from_value(case["schema"].clone())
+                .map_err(|err| eyre!("{name}: unsupported schema: {err}"))?;
+            // A missing value is undefined, which is not the same as null
+            let response = validator.validate(&case.get("value").cloned());
+            let error = response
+                .error
+                .and_then(|error| error.details.into_iter().next())
+                .map(|details| {
+                    json!({
+                        "message": details.message,
+                        "path": details.path,
+                        "type": details.r#type,
+                    })
+                });
+            let expected = &case["error"];
+            if error.as_ref().unwrap_or(&serde_json::Value::Null) != expected {
+                return Err(eyre!("{name}: expected {expected}, got {error:?}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+mod date {
+    use super::*;
+
+    #[test]
+    fn type_date() -> Result<()> {
+        let joi = r#"{ "type": "date" }"#;
+        valid_opt(joi, None)?;
+        valid(joi, "2020-01-01")?;
+        valid(joi, "2020-01-01T10:20:30.400Z")?;
+        valid(joi, "2020-01-01T10:20:30+02:00")?;
+        valid(joi, "Wed, 01 Jan 2020 00:00:00 GMT")?;
+        valid(joi, 1577836800000_i64)?;
+        valid(joi, "1577836800000")?;
+        invalid(joi, "a")?;
+        invalid(joi, "2020-13-01")?;
+        invalid(joi, true)?;
+        invalid(joi, json!(null))?;
+        Ok(())
+    }
+
+    #[test]
+    fn formats() -> Result<()> {
+        let joi = r#"{ "type": "date", "flags": { "format": "iso" } }"#;
+        valid(joi, "2020-01-01")?;
+        invalid(joi, "01/02/2020")?;
+        invalid(joi, 1577836800000_i64)?;
+
+        let joi = r#"{ "type": "date", "flags": { "format": "javascript" } }"#;
+        valid(joi, 1577836800000_i64)?;
+        valid(joi, "1577836800000")?;
+        invalid(joi, "2020-01-01")?;
+
+        let joi = r#"{ "type": "date", "flags": { "format": "unix" } }"#;
+        valid(joi, 1577836800)?;
+        valid(joi, "1577836800.5")?;
+        invalid(joi, "a")?;
+        unsupported(r#"{ "type": "date", "flags": { "format": ["YYYY"] } }"#)?;
+        Ok(())
+    }
+
+    #[test]
+    fn min_max() -> Result<()> {
+        let joi = r#"{ "type": "date", "rules": [
+            { "name": "min", "args": { "date": "2020-01-01T00:00:00.000Z" } },
+            { "name": "less", "args": { "date": "2021-01-01T00:00:00.000Z" } }
+        ] }"#;
+        invalid(joi, "2019-12-31T23:59:59.999Z")?;
+        valid(joi, "2020-01-01")?;
+        valid(joi, "2020-12-31T23:59:59.999Z")?;
+        invalid(joi, "2021-01-01")?;
+
+        let joi = r#"{ "type": "date", "rules": [
+            { "name": "greater", "args": { "date": "2020-01-01T00:00:00.000Z" } },
+            { "name": "max", "args": { "date": "now" } }
+        ] }"#;
+        invalid(joi, "2020-01-01")?;
+        valid(joi, "2020-01-02")?;
+        invalid(joi, "9999-01-01")?;
+        unsupported(
+            r#"{ "type": "date", "rules": [ { "name": "min", "args": { "date": "a" } } ] }"#,
+        )?;
+        unsupported(
+            r#"{ "type": "date", "rules": [ { "name": "min", "args": { "date": { "ref": { "path": ["a"] } } } } ] }"#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn valid_values() -> Result<()> {
+        let joi = r#"{ "type": "date", "flags": { "only": true }, "allow": ["2020-01-01T00:00:00.000Z"] }"#;
+        valid(joi, "2020-01-01")?;
+        valid(joi, 1577836800000_i64)?;
+        invalid(joi, "2020-01-02")?;
+        Ok(())
+    }
+}
+
 mod number {
     use std::f64::consts::PI;
 
@@ -590,6 +836,95 @@ mod number {
     }
 }
 
+mod object {
+    use super::*;
+
+    #[test]
+    fn type_object() -> Result<()> {
+        let joi = r#"{ "type": "object" }"#;
+        valid_opt(joi, None)?;
+        valid(joi, json!({}))?;
+        valid(joi, json!({ "a": 1 }))?;
+        valid(joi, r#"{ "a": 1 }"#)?;
+        invalid(joi, "a")?;
+        invalid(joi, json!([]))?;
+        invalid(joi, json!(null))?;
+        Ok(())
+    }
+
+    #[test]
+    fn keys() -> Result<()> {
+        let joi = r#"{ "type": "object", "keys": {
+            "a": { "type": "string", "flags": { "presence": "required" } },
+            "b": { "type": "number" }
+        } }"#;
+        valid(joi, json!({ "a": "x" }))?;
+        valid(joi, json!({ "a": "x", "b": 1 }))?;
+        invalid(joi, json!({ "b": 1 }))?;
+        invalid(joi, json!({ "a": "x", "b": "y" }))?;
+        invalid(joi, json!({ "a": "x", "c": 1 }))?;
+
+        let joi = r#"{ "type": "object", "flags": { "unknown": true }, "keys": { "a": { "type": "string" } } }"#;
+        valid(joi, json!({ "a": "x", "c": 1 }))?;
+
+        let joi = r#"{ "type": "object", "keys": {} }"#;
+        valid(joi, json!({}))?;
+        invalid(joi, json!({ "a": 1 }))?;
+        Ok(())
+    }
+
+    #[test]
+    fn patterns() -> Result<()> {
+        let joi = r#"{ "type": "object", "patterns": [
+            { "regex": "/^x-/i", "rule": { "type": "string" } }
+        ] }"#;
+        valid(joi, json!({ "x-a": "a", "X-b": "b" }))?;
+        invalid(joi, json!({ "x-a": 1 }))?;
+        invalid(joi, json!({ "a": "a" }))?;
+
+        let joi = r#"{ "type": "object", "patterns": [
+            { "regex": "/^x-/", "rule": { "type": "string" }, "fallthrough": true },
+            { "regex": "/a$/", "rule": { "type": "string", "rules": [ { "name": "max", "args": { "limit": 1 } } ] } }
+        ] }"#;
+        valid(joi, json!({ "x-a": "a" }))?;
+        invalid(joi, json!({ "x-a": "ab" }))?;
+        unsupported(
+            r#"{ "type": "object", "patterns": [ { "regex": "/a/g", "rule": { "type": "string" } } ] }"#,
+        )?;
+        unsupported(
+            r#"{ "type": "object", "patterns": [ { "schema": { "type": "string" }, "rule": { "type": "string" } } ] }"#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn min_max_length() -> Result<()> {
+        let joi = r#"{ "type": "object", "rules": [
+            { "name": "min", "args": { "limit": 1 } },
+            { "name": "max", "args": { "limit": 2 } }
+        ] }"#;
+        invalid(joi, json!({}))?;
+        valid(joi, json!({ "a": 1 }))?;
+        valid(joi, json!({ "a": 1, "b": 2 }))?;
+        invalid(joi, json!({ "a": 1, "b": 2, "c": 3 }))?;
+
+        let joi =
+            r#"{ "type": "object", "rules": [ { "name": "length", "args": { "limit": 1 } } ] }"#;
+        valid(joi, json!({ "a": 1 }))?;
+        invalid(joi, json!({ "a": 1, "b": 2 }))?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_rules() -> Result<()> {
+        unsupported(r#"{ "type": "object", "rules": [ { "name": "and" } ] }"#)?;
+        unsupported(r#"{ "type": "object", "rules": [ { "name": "instance" } ] }"#)?;
+        unsupported(r#"{ "type": "object", "dependencies": [] }"#)?;
+        unsupported(r#"{ "type": "object", "renames": [] }"#)?;
+        Ok(())
+    }
+}
+
 mod string {
     use std::f64::consts::PI;
 
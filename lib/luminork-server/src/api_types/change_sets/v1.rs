@@ -1,11 +1,13 @@
 use dal::{
     ChangeSetId,
     ChangeSetStatus,
+    ComponentId,
 };
 use serde::{
     Deserialize,
     Serialize,
 };
+use si_id::FuncRunId;
 use utoipa::ToSchema;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
@@ -20,3 +22,57 @@ pub struct ChangeSetViewV1 {
     #[schema(value_type = bool)]
     pub is_head: bool,
 }
+
+/// An event in a change set's event stream. New variants may be added, so clients should ignore
+/// types they don't recognize.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChangeSetEventV1 {
+    /// Actions in the change set were added, removed or changed state.
+    ActionsUpdated,
+    /// The change set was abandoned.
+    ChangeSetAbandoned,
+    /// The change set was applied to its base change set.
+    #[serde(rename_all = "camelCase")]
+    ChangeSetApplied {
+        #[schema(value_type = String)]
+        to_change_set_id: ChangeSetId,
+    },
+    /// The change set's status changed.
+    #[serde(rename_all = "camelCase")]
+    ChangeSetStatusChanged {
+        #[schema(value_type = String)]
+        from_status: ChangeSetStatus,
+        #[schema(value_type = String)]
+        to_status: ChangeSetStatus,
+    },
+    /// A management function run finished.
+    #[serde(rename_all = "camelCase")]
+    FuncRunCompleted {
+        #[schema(value_type = String)]
+        func_run_id: FuncRunId,
+        #[schema(value_type = String)]
+        component_id: ComponentId,
+    },
+    /// The change set's materialized views were updated.
+    #[serde(rename_all = "camelCase")]
+    IndexUpdated { index_checksum: String },
+    /// Events may have been missed, so anything derived from earlier events should be fetched
+    /// again.
+    Resync,
+}
+
+impl ChangeSetEventV1 {
+    /// The name used for the event in the stream.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ActionsUpdated => "actionsUpdated",
+            Self::ChangeSetAbandoned => "changeSetAbandoned",
+            Self::ChangeSetApplied { .. } => "changeSetApplied",
+            Self::ChangeSetStatusChanged { .. } => "changeSetStatusChanged",
+            Self::FuncRunCompleted { .. } => "funcRunCompleted",
+            Self::IndexUpdated { .. } => "indexUpdated",
+            Self::Resync => "resync",
+        }
+    }
+}
@@ -12,7 +12,10 @@ use serde::{
     Deserialize,
     Serialize,
 };
-use si_db::FuncRunLogDb;
+use si_db::{
+    FuncRunDb,
+    FuncRunLogDb,
+};
 use si_events::{
     ActionKind,
     ActionResultState,
@@ -22,6 +25,7 @@ use si_events::{
     FuncKind,
     FuncRun,
     FuncRunLog,
+    FuncRunResourceUsage,
     FuncRunState,
     OutputLine,
 };
@@ -149,6 +153,7 @@ pub struct FuncRunViewV1 {
         "finalized": true
     }))]
     logs: Option<FuncRunLogViewV1>,
+    resource_usage: Option<FuncRunResourceUsageViewV1>,
     #[schema(value_type = String, example = "2025-03-27T19:41:58.493298051Z")]
     created_at: DateTime<Utc>,
     #[schema(value_type = String, example = "2025-03-27T19:42:02.192033089Z")]
@@ -189,6 +194,32 @@ pub struct FuncRunLogViewV1 {
     finalized: bool,
 }
 
+/// The resources consumed by executing the function. Measurements that weren't available for the
+/// function's runtime are null.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunResourceUsageViewV1 {
+    #[schema(example = 12)]
+    queued_ms: Option<u64>,
+    #[schema(example = 3698)]
+    execution_ms: Option<u64>,
+    #[schema(example = 840)]
+    cpu_time_ms: Option<u64>,
+    #[schema(example = 98566144)]
+    peak_memory_bytes: Option<u64>,
+}
+
+impl From<FuncRunResourceUsage> for FuncRunResourceUsageViewV1 {
+    fn from(resource_usage: FuncRunResourceUsage) -> Self {
+        Self {
+            queued_ms: resource_usage.queued_ms,
+            execution_ms: resource_usage.execution_ms,
+            cpu_time_ms: resource_usage.cpu_time_ms,
+            peak_memory_bytes: resource_usage.peak_memory_bytes,
+        }
+    }
+}
+
 impl From<&OutputLine> for OutputLineViewV1 {
     fn from(output_line: &OutputLine) -> Self {
         Self {
@@ -278,6 +309,10 @@ impl FuncRunViewV1 {
             .await?
             .map(|v| v.into());
 
+        let resource_usage = FuncRunDb::read_resource_usage(ctx, func_run.id())
+            .await?
+            .map(Into::into);
+
         Ok(FuncRunViewV1 {
             id: func_run.id(),
             state: func_run.state(),
@@ -305,6 +340,7 @@ impl FuncRunViewV1 {
             function_code_base64: code_base64,
             result_value,
             logs,
+            resource_usage,
             created_at: func_run.created_at(),
             updated_at: func_run.updated_at(),
         })
@@ -0,0 +1,719 @@
+//! Per-workspace streams of [change set events](ChangeSetEventV1), built from the WsEvents and
+//! edda index updates published on NATS.
+//!
+//! Each workspace that has a client streaming its events gets a task that translates those
+//! messages into events and keeps the most recent ones, so that a client which reconnects can
+//! resume from the last event it saw. Once no client has been streaming a workspace's events for
+//! a while, the task stops and the events it kept are dropped.
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use dal::{
+    ChangeSetId,
+    ChangeSetStatus,
+    ComponentId,
+    WorkspacePk,
+};
+use nats_multiplexer_client::{
+    MultiplexerClientError,
+    MultiplexerRequestPayload,
+};
+use sdf_core::nats_multiplexer::NatsMultiplexerClients;
+use serde::Deserialize;
+use si_data_nats::Subject;
+use si_id::FuncRunId;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::sync::{
+    Mutex,
+    broadcast::{
+        self,
+        error::RecvError,
+    },
+};
+use tokio_util::sync::CancellationToken;
+use ulid::Ulid;
+
+use crate::api_types::change_sets::v1::ChangeSetEventV1;
+
+/// The header clients send to resume a stream after the event with the given id.
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// How many events are kept per workspace for clients to resume from.
+const REPLAY_CAPACITY: usize = 1000;
+/// How long a workspace's events keep being collected after its last client disconnects, so that
+/// the client can resume when it reconnects.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const INDEX_UPDATE_SUBJECT_SUFFIX: &str = ".IndexUpdate";
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum EventStreamError {
+    #[error("edda updates multiplexer client error: {0}")]
+    EddaUpdatesMultiplexerClient(String),
+    #[error("nats multiplexer client error: {0}")]
+    MultiplexerClient(#[from] MultiplexerClientError),
+    #[error("try lock error: {0}")]
+    TryLock(#[from] tokio::sync::TryLockError),
+}
+
+pub type EventStreamResult<T> = Result<T, EventStreamError>;
+
+/// An event along with the id clients use to resume after it.
+#[derive(Clone, Debug)]
+pub struct StreamedEvent {
+    /// `None` for events that aren't sent with an id, which clients can't resume from.
+    pub id: Option<Ulid>,
+    /// `None` for events that concern every change set in the workspace.
+    pub change_set_id: Option<ChangeSetId>,
+    pub event: ChangeSetEventV1,
+}
+
+impl StreamedEvent {
+    /// An event telling the client that it may have missed events.
+    pub fn resync() -> Self {
+        Self {
+            id: None,
+            change_set_id: None,
+            event: ChangeSetEventV1::Resync,
+        }
+    }
+
+    /// Whether the event should be sent to a client streaming the given change set's events.
+    pub fn is_for(&self, change_set_id: ChangeSetId) -> bool {
+        self.change_set_id.is_none_or(|id| id == change_set_id)
+    }
+}
+
+/// A subscription to a workspace's events.
+#[derive(Debug)]
+pub struct EventSubscription {
+    /// The kept events after the one the client resumed from, or `None` if that event is no
+    /// longer kept (in which case the client may have missed events).
+    pub replayed: Option<Vec<StreamedEvent>>,
+    pub receiver: broadcast::Receiver<StreamedEvent>,
+}
+
+/// The event streams of every workspace that currently has one.
+#[derive(Clone, Debug, Default)]
+pub struct EventStreams {
+    workspaces: Arc<Mutex<HashMap<WorkspacePk, Arc<WorkspaceEvents>>>>,
+}
+
+impl EventStreams {
+    /// Subscribes to a workspace's events, starting its event stream if it doesn't have one.
+    ///
+    /// If `last_event_id` is given, the events kept after it are returned so that the client can
+    /// resume where it left off.
+    pub async fn subscribe(
+        &self,
+        workspace_pk: WorkspacePk,
+        last_event_id: Option<Ulid>,
+        nats_multiplexer_clients: &NatsMultiplexerClients,
+        subject_prefix: Option<&str>,
+        shutdown_token: CancellationToken,
+    ) -> EventStreamResult<EventSubscription> {
+        let mut workspaces = self.workspaces.lock().await;
+
+        let events = match workspaces.get(&workspace_pk) {
+            Some(events) => events.clone(),
+            None => {
+                let ws_receiver = nats_multiplexer_clients
+                    .ws
+                    .try_lock()?
+                    .receiver(Subject::from(format!(
+                        "si.workspace_pk.{workspace_pk}.event"
+                    )))
+                    .await?;
+                let edda_receiver = nats_multiplexer_clients
+                    .edda_updates
+                    .messages_for_workspace(subject_prefix, workspace_pk)
+                    .await
+                    .map_err(|err| {
+                        EventStreamError::EddaUpdatesMultiplexerClient(err.to_string())
+                    })?;
+
+                let events = Arc::new(WorkspaceEvents::new());
+                tokio::spawn(self.clone().collect(
+                    workspace_pk,
+                    events.clone(),
+                    ws_receiver,
+                    edda_receiver,
+                    shutdown_token,
+                ));
+                workspaces.insert(workspace_pk, events.clone());
+                events
+            }
+        };
+
+        Ok(events.subscribe(last_event_id).await)
+    }
+
+    async fn collect(
+        self,
+        workspace_pk: WorkspacePk,
+        events: Arc<WorkspaceEvents>,
+        mut ws_receiver: broadcast::Receiver<MultiplexerRequestPayload>,
+        mut edda_receiver: broadcast::Receiver<MultiplexerRequestPayload>,
+        shutdown_token: CancellationToken,
+    ) {
+        let mut idle_check = tokio::time::interval(IDLE_CHECK_INTERVAL);
+        let mut idle_since = None;
+
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => break,
+                received = ws_receiver.recv() => match received {
+                    Ok(payload) => {
+                        if let Some((change_set_id, event)) =
+                            event_from_ws_event(payload.nats_message.payload())
+                        {
+                            events.push(change_set_id, event).await;
+                        }
+                    }
+                    Err(RecvError::Lagged(count)) => {
+                        warn!(si.workspace.id = %workspace_pk, count, "event stream missed ws events");
+                        events.lost().await;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                received = edda_receiver.recv() => match received {
+                    Ok(payload) => {
+                        if let Some((change_set_id, event)) = event_from_edda_update(
+                            payload.nats_message.subject().as_str(),
+                            payload.nats_message.payload(),
+                        ) {
+                            events.push(change_set_id, event).await;
+                        }
+                    }
+                    Err(RecvError::Lagged(count)) => {
+                        warn!(si.workspace.id = %workspace_pk, count, "event stream missed index updates");
+                        events.lost().await;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = idle_check.tick() => {
+                    if events.subscriber_count().await > 0 {
+                        idle_since = None;
+                        continue;
+                    }
+
+                    if idle_since.get_or_insert_with(Instant::now).elapsed() < IDLE_TIMEOUT {
+                        continue;
+                    }
+
+                    // Subscribing happens with the workspaces locked, so nobody can subscribe
+                    // between checking for subscribers and removing the stream
+                    let mut workspaces = self.workspaces.lock().await;
+                    if events.subscriber_count().await == 0 {
+                        workspaces.remove(&workspace_pk);
+                        return;
+                    }
+                }
+            }
+        }
+
+        self.workspaces.lock().await.remove(&workspace_pk);
+    }
+}
+
+#[derive(Debug)]
+struct WorkspaceEvents {
+    // The kept events and the sender are locked together, so that a subscriber gets every event
+    // exactly once, whether replayed or received
+    inner: Mutex<WorkspaceEventsInner>,
+}
+
+#[derive(Debug)]
+struct WorkspaceEventsInner {
+    kept: VecDeque<StreamedEvent>,
+    sender: broadcast::Sender<StreamedEvent>,
+}
+
+impl WorkspaceEvents {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(REPLAY_CAPACITY);
+        Self {
+            inner: Mutex::new(WorkspaceEventsInner {
+                kept: VecDeque::with_capacity(REPLAY_CAPACITY),
+                sender,
+            }),
+        }
+    }
+
+    async fn subscribe(&self, last_event_id: Option<Ulid>) -> EventSubscription {
+        let inner = self.inner.lock().await;
+
+        let replayed = match last_event_id {
+            Some(last_event_id) => inner
+                .kept
+                .iter()
+                .position(|event| event.id == Some(last_event_id))
+                .map(|position| inner.kept.iter().skip(position + 1).cloned().collect()),
+            None => Some(Vec::new()),
+        };
+
+        EventSubscription {
+            replayed,
+            receiver: inner.sender.subscribe(),
+        }
+    }
+
+    async fn push(&self, change_set_id: ChangeSetId, event: ChangeSetEventV1) {
+        let event = StreamedEvent {
+            id: Some(Ulid::new()),
+            change_set_id: Some(change_set_id),
+            event,
+        };
+
+        let mut inner = self.inner.lock().await;
+        if inner.kept.len() == REPLAY_CAPACITY {
+            inner.kept.pop_front();
+        }
+        inner.kept.push_back(event.clone());
+        // Having no subscribers isn't an error, the event is kept for clients that resume
+        let _ = inner.sender.send(event);
+    }
+
+    /// Records that events may have been missed, so that clients can't resume from before this
+    /// point and current subscribers know to catch up.
+    async fn lost(&self) {
+        let mut inner = self.inner.lock().await;
+        inner.kept.clear();
+        let _ = inner.sender.send(StreamedEvent::resync());
+    }
+
+    async fn subscriber_count(&self) -> usize {
+        self.inner.lock().await.sender.receiver_count()
+    }
+}
+
+#[derive(Deserialize)]
+struct WsEventEnvelope {
+    change_set_id: Option<ChangeSetId>,
+    payload: WsEventPayload,
+}
+
+#[derive(Deserialize)]
+struct WsEventPayload {
+    kind: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeSetIdPayload {
+    change_set_id: ChangeSetId,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeSetAppliedPayload {
+    change_set_id: ChangeSetId,
+    to_rebase_change_set_id: ChangeSetId,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeSetStatusChangedPayload {
+    from_status: ChangeSetStatus,
+    change_set: ChangeSetStatusPayload,
+}
+
+#[derive(Deserialize)]
+struct ChangeSetStatusPayload {
+    id: ChangeSetId,
+    status: ChangeSetStatus,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManagementFuncExecutedPayload {
+    manager_component_id: ComponentId,
+    func_run_id: FuncRunId,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexUpdatePayload {
+    meta: ChangeSetIdPayload,
+    index_checksum: String,
+}
+
+/// Translates the WsEvents that have a [`ChangeSetEventV1`] equivalent, ignoring the rest.
+fn event_from_ws_event(payload: &[u8]) -> Option<(ChangeSetId, ChangeSetEventV1)> {
+    let envelope: WsEventEnvelope = serde_json::from_slice(payload).ok()?;
+    let data = envelope.payload.data;
+
+    match envelope.payload.kind.as_str() {
+        "ActionsListUpdated" => Some((
+            serde_json::from_value(data).ok()?,
+            ChangeSetEventV1::ActionsUpdated,
+        )),
+        "ChangeSetAbandoned" => {
+            let payload: ChangeSetIdPayload = serde_json::from_value(data).ok()?;
+            Some((payload.change_set_id, ChangeSetEventV1::ChangeSetAbandoned))
+        }
+        "ChangeSetApplied" => {
+            let payload: ChangeSetAppliedPayload = serde_json::from_value(data).ok()?;
+            Some((
+                payload.change_set_id,
+                ChangeSetEventV1::ChangeSetApplied {
+                    to_change_set_id: payload.to_rebase_change_set_id,
+                },
+            ))
+        }
+        "ChangeSetStatusChanged" => {
+            let payload: ChangeSetStatusChangedPayload = serde_json::from_value(data).ok()?;
+            Some((
+                payload.change_set.id,
+                ChangeSetEventV1::ChangeSetStatusChanged {
+                    from_status: payload.from_status,
+                    to_status: payload.change_set.status,
+                },
+            ))
+        }
+        "ManagementFuncExecuted" => {
+            let payload: ManagementFuncExecutedPayload = serde_json::from_value(data).ok()?;
+            Some((
+                envelope.change_set_id?,
+                ChangeSetEventV1::FuncRunCompleted {
+                    func_run_id: payload.func_run_id,
+                    component_id: payload.manager_component_id,
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Translates index updates, ignoring the other edda updates.
+fn event_from_edda_update(
+    subject: &str,
+    payload: &[u8],
+) -> Option<(ChangeSetId, ChangeSetEventV1)> {
+    if !subject.ends_with(INDEX_UPDATE_SUBJECT_SUFFIX) {
+        return None;
+    }
+
+    let payload: IndexUpdatePayload = serde_json::from_slice(payload).ok()?;
+    Some((
+        payload.meta.change_set_id,
+        ChangeSetEventV1::IndexUpdated {
+            index_checksum: payload.index_checksum,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+
+    use color_eyre::Result;
+    use dal::{
+        WsEvent,
+        WsPayload,
+    };
+    use edda_core::nats::subject;
+    use pretty_assertions_sorted::assert_eq;
+    use serde_json::json;
+    use si_frontend_mv_types::object::patch::{
+        ChangesetIndexUpdate,
+        ChangesetUpdateMeta,
+    };
+
+    use super::*;
+
+    fn ws_event(
+        change_set_id: ChangeSetId,
+        kind: &str,
+        data: serde_json::Value,
+    ) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&json!({
+            "version": 1,
+            "workspace_pk": WorkspacePk::new(),
+            "change_set_id": change_set_id,
+            "actor": null,
+            "request_ulid": null,
+            "payload": { "kind": kind, "data": data },
+        }))?)
+    }
+
+    fn ids(events: &[StreamedEvent]) -> Vec<Option<Ulid>> {
+        events.iter().map(|event| event.id).collect()
+    }
+
+    #[tokio::test]
+    async fn subscribe_resumes_after_last_event_id() -> Result<()> {
+        let events = WorkspaceEvents::new();
+        let change_set_id = ChangeSetId::new();
+        for _ in 0..3 {
+            events
+                .push(change_set_id, ChangeSetEventV1::ActionsUpdated)
+                .await;
+        }
+        let kept: Vec<_> = events.inner.lock().await.kept.iter().cloned().collect();
+
+        let subscription = events.subscribe(kept[0].id).await;
+        assert_eq!(
+            Some(ids(&kept[1..])),
+            subscription.replayed.as_deref().map(ids)
+        );
+
+        let subscription = events.subscribe(kept[2].id).await;
+        assert_eq!(Some(Vec::new()), subscription.replayed.as_deref().map(ids));
+
+        // Starting fresh replays nothing
+        let subscription = events.subscribe(None).await;
+        assert_eq!(Some(Vec::new()), subscription.replayed.as_deref().map(ids));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn subscribe_cannot_resume_from_unknown_or_lost_events() -> Result<()> {
+        let events = WorkspaceEvents::new();
+        let change_set_id = ChangeSetId::new();
+        events
+            .push(change_set_id, ChangeSetEventV1::ActionsUpdated)
+            .await;
+        let last_event_id = events.inner.lock().await.kept[0].id;
+
+        assert!(events.subscribe(Some(Ulid::new())).await.replayed.is_none());
+
+        let mut subscription = events.subscribe(None).await;
+        events.lost().await;
+        assert!(events.subscribe(last_event_id).await.replayed.is_none());
+        assert_eq!(
+            ChangeSetEventV1::Resync,
+            subscription.receiver.recv().await?.event
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn subscribe_receives_later_events_once() -> Result<()> {
+        let events = WorkspaceEvents::new();
+        let change_set_id = ChangeSetId::new();
+        events
+            .push(change_set_id, ChangeSetEventV1::ActionsUpdated)
+            .await;
+        let last_event_id = events.inner.lock().await.kept[0].id;
+
+        let mut subscription = events.subscribe(last_event_id).await;
+        events
+            .push(change_set_id, ChangeSetEventV1::ChangeSetAbandoned)
+            .await;
+
+        let received = subscription.receiver.recv().await?;
+        assert_eq!(ChangeSetEventV1::ChangeSetAbandoned, received.event);
+        assert!(received.is_for(change_set_id));
+        assert!(!received.is_for(ChangeSetId::new()));
+        assert!(subscription.receiver.try_recv().is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn kept_events_are_bounded() -> Result<()> {
+        let events = WorkspaceEvents::new();
+        let change_set_id = ChangeSetId::new();
+        events
+            .push(change_set_id, ChangeSetEventV1::ActionsUpdated)
+            .await;
+        let first_event_id = events.inner.lock().await.kept[0].id;
+
+        for _ in 0..REPLAY_CAPACITY {
+            events
+                .push(change_set_id, ChangeSetEventV1::ActionsUpdated)
+                .await;
+        }
+
+        assert_eq!(REPLAY_CAPACITY, events.inner.lock().await.kept.len());
+        assert!(events.subscribe(first_event_id).await.replayed.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn event_from_ws_event_translates_actions_list_updated() -> Result<()> {
+        let change_set_id = ChangeSetId::new();
+        let ws_event = WsEvent::new_raw(
+            WorkspacePk::new(),
+            Some(change_set_id),
+            None,
+            None,
+            WsPayload::ActionsListUpdated(change_set_id),
+        )
+        .await?;
+
+        assert_eq!(
+            Some((change_set_id, ChangeSetEventV1::ActionsUpdated)),
+            event_from_ws_event(&serde_json::to_vec(&ws_event)?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_from_ws_event_translates_change_set_events() -> Result<()> {
+        let change_set_id = ChangeSetId::new();
+        let head_id = ChangeSetId::new();
+
+        assert_eq!(
+            Some((change_set_id, ChangeSetEventV1::ChangeSetAbandoned)),
+            event_from_ws_event(&ws_event(
+                change_set_id,
+                "ChangeSetAbandoned",
+                json!({ "changeSetId": change_set_id, "userPk": null }),
+            )?)
+        );
+        assert_eq!(
+            Some((
+                change_set_id,
+                ChangeSetEventV1::ChangeSetApplied {
+                    to_change_set_id: head_id
+                }
+            )),
+            event_from_ws_event(&ws_event(
+                head_id,
+                "ChangeSetApplied",
+                json!({
+                    "changeSetId": change_set_id,
+                    "toRebaseChangeSetId": head_id,
+                    "userPk": null,
+                }),
+            )?)
+        );
+        assert_eq!(
+            Some((
+                change_set_id,
+                ChangeSetEventV1::ChangeSetStatusChanged {
+                    from_status: ChangeSetStatus::Open,
+                    to_status: ChangeSetStatus::NeedsApproval,
+                }
+            )),
+            event_from_ws_event(&ws_event(
+                change_set_id,
+                "ChangeSetStatusChanged",
+                json!({
+                    "fromStatus": ChangeSetStatus::Open,
+                    "changeSet": {
+                        "id": change_set_id,
+                        "status": ChangeSetStatus::NeedsApproval,
+                    },
+                }),
+            )?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_from_ws_event_translates_management_func_executed() -> Result<()> {
+        let change_set_id = ChangeSetId::new();
+        let component_id = ComponentId::new();
+        let func_run_id = FuncRunId::new();
+
+        assert_eq!(
+            Some((
+                change_set_id,
+                ChangeSetEventV1::FuncRunCompleted {
+                    func_run_id,
+                    component_id,
+                }
+            )),
+            event_from_ws_event(&ws_event(
+                change_set_id,
+                "ManagementFuncExecuted",
+                json!({
+                    "prototypeId": Ulid::new(),
+                    "managerComponentId": component_id,
+                    "funcRunId": func_run_id,
+                }),
+            )?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_from_ws_event_ignores_other_events() -> Result<()> {
+        let change_set_id = ChangeSetId::new();
+
+        assert_eq!(
+            None,
+            event_from_ws_event(&ws_event(
+                change_set_id,
+                "ComponentUpdated",
+                json!({ "componentId": ComponentId::new() }),
+            )?)
+        );
+        // Malformed events are skipped rather than ending the stream
+        assert_eq!(
+            None,
+            event_from_ws_event(&ws_event(change_set_id, "ChangeSetAbandoned", json!({}),)?)
+        );
+        assert_eq!(None, event_from_ws_event(b"not json"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_from_edda_update_translates_index_updates() -> Result<()> {
+        let workspace_pk = WorkspacePk::new();
+        let change_set_id = ChangeSetId::new();
+        let index_update = ChangesetIndexUpdate::new(
+            ChangesetUpdateMeta {
+                workspace_id: workspace_pk,
+                change_set_id,
+                to_index_checksum: "to".to_string(),
+                from_index_checksum: "from".to_string(),
+            },
+            "checksum".to_string(),
+            None,
+        );
+        let subject =
+            subject::workspace_update_for(None, &workspace_pk.to_string(), index_update.kind());
+
+        assert_eq!(
+            Some((
+                change_set_id,
+                ChangeSetEventV1::IndexUpdated {
+                    index_checksum: "checksum".to_string()
+                }
+            )),
+            event_from_edda_update(subject.as_str(), &serde_json::to_vec(&index_update)?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_from_edda_update_ignores_other_updates() -> Result<()> {
+        let workspace_pk = WorkspacePk::new();
+        let subject =
+            subject::workspace_update_for(None, &workspace_pk.to_string(), "PatchMessage");
+        let payload = serde_json::to_vec(&json!({
+            "meta": { "changeSetId": ChangeSetId::new() },
+            "indexChecksum": "checksum",
+        }))?;
+
+        assert_eq!(None, event_from_edda_update(subject.as_str(), &payload));
+
+        Ok(())
+    }
+}
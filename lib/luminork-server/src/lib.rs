@@ -23,6 +23,7 @@ mod api_types;
 mod app;
 mod app_state;
 mod config;
+mod event_stream;
 mod extract;
 mod init;
 pub mod key_generation;
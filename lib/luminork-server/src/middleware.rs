@@ -1,6 +1,15 @@
+mod idempotency;
 mod workspace_permission;
 
-pub use self::workspace_permission::{
-    WorkspacePermission,
-    WorkspacePermissionLayer,
+pub use self::{
+    idempotency::{
+        IDEMPOTENCY_KEY_HEADER,
+        IDEMPOTENT_REPLAYED_HEADER,
+        Idempotency,
+        IdempotencyLayer,
+    },
+    workspace_permission::{
+        WorkspacePermission,
+        WorkspacePermissionLayer,
+    },
 };
@@ -0,0 +1,454 @@
+use std::{
+    task::{
+        Context,
+        Poll,
+    },
+    time::Duration,
+};
+
+use axum::{
+    RequestPartsExt as _,
+    body::{
+        Body,
+        Bytes,
+        Full,
+        HttpBody as _,
+    },
+    http::{
+        HeaderValue,
+        Method,
+        Request,
+        StatusCode,
+        header,
+        request::Parts,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use futures::future::BoxFuture;
+use si_db::IdempotencyKey;
+use si_events::ContentHash;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tower::{
+    Layer,
+    Service,
+};
+
+use crate::{
+    AppState,
+    extract::workspace::WorkspaceAuthorization,
+    service::v1::common::ApiError,
+};
+
+/// The header clients set to make a write safe to retry.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// The header set on responses that were replayed rather than produced by handling the request.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// How long the response to a request is kept for replaying.
+const WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long a key is held for a request that hasn't completed, in case the request never does.
+const LEASE: Duration = Duration::from_secs(10 * 60);
+const MAX_KEY_LENGTH: usize = 255;
+/// The largest request body that is read to hash it, which is the largest body any route takes
+/// (schema uploads).
+const MAX_BODY_BYTES: usize = 20 * 1024 * 1024;
+
+/// Makes writes that carry an `Idempotency-Key` header safe to retry: the first request with a
+/// key is handled as usual and its response is kept, per workspace, for [`WINDOW`]. Retrying the
+/// request with the same key replays that response instead of handling the request again.
+///
+/// Server errors aren't kept, so that a request that failed that way can be retried. Reusing a
+/// key for a different request is rejected, as is retrying a request that is still in progress.
+/// A request that is dropped before it completes gives its key up, and a key is only held for
+/// [`LEASE`] while its request is in progress, in case the server handling it goes away.
+///
+/// This must be layered inside the endpoint authorization, since it needs the workspace.
+#[derive(Clone)]
+pub struct IdempotencyLayer {
+    state: AppState,
+}
+
+impl IdempotencyLayer {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+impl<S> Layer<S> for IdempotencyLayer {
+    type Service = Idempotency<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Idempotency {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Idempotency<S> {
+    inner: S,
+    state: AppState,
+}
+
+impl<S> Service<Request<Body>> for Idempotency<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut me = self.clone();
+
+        Box::pin(async move {
+            let is_write = !matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+            let key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+                Some(key) if is_write => key.to_owned(),
+                _ => return me.inner.call(req).await,
+            };
+            let key = match key.to_str() {
+                Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_owned(),
+                _ => {
+                    return Ok(error_response(
+                        StatusCode::BAD_REQUEST,
+                        format!(
+                            "{IDEMPOTENCY_KEY_HEADER} must be between 1 and {MAX_KEY_LENGTH} visible ASCII characters"
+                        ),
+                    ));
+                }
+            };
+
+            let (mut parts, body) = req.into_parts();
+            let ctx = match parts.extract_with_state(&me.state).await {
+                Ok(WorkspaceAuthorization {
+                    ctx_without_snapshot,
+                    ..
+                }) => ctx_without_snapshot,
+                Err(err) => return Ok(err.into_response()),
+            };
+            let body = match read_body(body, MAX_BODY_BYTES).await {
+                Ok(body) => body,
+                Err(ReadBodyError::TooLarge) => {
+                    return Ok(error_response(
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        format!("request body is larger than {MAX_BODY_BYTES} bytes"),
+                    ));
+                }
+                Err(ReadBodyError::Hyper(err)) => {
+                    return Ok(error_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("could not read request body: {err}"),
+                    ));
+                }
+            };
+            let request_hash = request_hash(&parts, &body);
+
+            let reserved =
+                match IdempotencyKey::reserve(&ctx, &key, &request_hash, WINDOW, LEASE).await {
+                    Ok(reserved) => reserved,
+                    Err(err) => return Ok(internal_error_response(err)),
+                };
+            if let Err(err) = ctx.commit_no_rebase().await {
+                return Ok(internal_error_response(err));
+            }
+
+            match reserved {
+                None => {}
+                Some(existing) if existing.request_hash != request_hash => {
+                    return Ok(error_response(
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        format!(
+                            "{IDEMPOTENCY_KEY_HEADER} {key:?} was already used for a different request"
+                        ),
+                    ));
+                }
+                Some(existing) => {
+                    return Ok(match (existing.status_code, existing.response_body) {
+                        (Some(status_code), response_body) => {
+                            replayed_response(status_code, response_body.unwrap_or_default())
+                        }
+                        (None, _) => error_response(
+                            StatusCode::CONFLICT,
+                            format!(
+                                "a request with {IDEMPOTENCY_KEY_HEADER} {key:?} is still in progress"
+                            ),
+                        ),
+                    });
+                }
+            }
+
+            let reservation = Reservation {
+                ctx,
+                key: key.clone(),
+                kept: false,
+            };
+            let response = me
+                .inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await?;
+            let ctx = reservation.keep();
+
+            if response.status().is_server_error() {
+                if let Err(err) = release(&ctx, &key).await {
+                    error!(si.error.message = ?err, "could not release idempotency key");
+                }
+                return Ok(response);
+            }
+
+            let (response_parts, response_body) = response.into_parts();
+            let response_body = match hyper::body::to_bytes(response_body).await {
+                Ok(response_body) => response_body,
+                Err(err) => {
+                    // The request was handled, so keep the key reserved until its lease runs out
+                    // rather than risk it being applied twice right away.
+                    error!(si.error.message = ?err, "could not read response body");
+                    return Ok(internal_error_response(err));
+                }
+            };
+            if let Err(err) =
+                complete(&ctx, &key, response_parts.status.as_u16(), &response_body).await
+            {
+                error!(si.error.message = ?err, "could not store idempotent response");
+            }
+
+            Ok(Response::from_parts(
+                response_parts,
+                axum::body::boxed(Full::from(response_body)),
+            ))
+        })
+    }
+}
+
+/// Gives up the key of a request that is dropped before it completes, for example because the
+/// client went away, so that the request can be retried straight away.
+struct Reservation {
+    ctx: dal::DalContext,
+    key: String,
+    kept: bool,
+}
+
+impl Reservation {
+    /// Keeps the key reserved, once the request has been handled.
+    fn keep(mut self) -> dal::DalContext {
+        self.kept = true;
+        self.ctx.clone()
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if self.kept {
+            return;
+        }
+
+        let ctx = self.ctx.clone();
+        let key = std::mem::take(&mut self.key);
+        tokio::spawn(async move {
+            if let Err(err) = release(&ctx, &key).await {
+                error!(si.error.message = ?err, "could not release idempotency key");
+            }
+        });
+    }
+}
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+enum ReadBodyError {
+    #[error("hyper error: {0}")]
+    Hyper(#[from] hyper::Error),
+    #[error("request body is too large")]
+    TooLarge,
+}
+
+/// Reads the whole body, unless it is larger than `limit` bytes.
+async fn read_body(mut body: Body, limit: usize) -> Result<Bytes, ReadBodyError> {
+    if body.size_hint().lower() > limit as u64 {
+        return Err(ReadBodyError::TooLarge);
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > limit {
+            return Err(ReadBodyError::TooLarge);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(Bytes::from(bytes))
+}
+
+/// A hash of everything that makes up the request, so that a key reused for a different request
+/// can be told apart from a retry.
+fn request_hash(parts: &Parts, body: &[u8]) -> String {
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or_else(|| parts.uri.path());
+
+    let mut request = Vec::with_capacity(body.len() + path_and_query.len() + 16);
+    request.extend_from_slice(parts.method.as_str().as_bytes());
+    request.push(b' ');
+    request.extend_from_slice(path_and_query.as_bytes());
+    request.push(b'\n');
+    request.extend_from_slice(body);
+
+    ContentHash::new(&request).to_string()
+}
+
+async fn complete(
+    ctx: &dal::DalContext,
+    key: &str,
+    status_code: u16,
+    response_body: &[u8],
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    IdempotencyKey::complete(ctx, key, status_code, response_body).await?;
+    ctx.commit_no_rebase().await?;
+    Ok(())
+}
+
+async fn release(
+    ctx: &dal::DalContext,
+    key: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    IdempotencyKey::release(ctx, key).await?;
+    ctx.commit_no_rebase().await?;
+    Ok(())
+}
+
+fn replayed_response(status_code: i32, body: Vec<u8>) -> Response {
+    let status = u16::try_from(status_code)
+        .ok()
+        .and_then(|status_code| StatusCode::from_u16(status_code).ok())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    let has_body = !body.is_empty();
+    let mut response = (status, body).into_response();
+    let headers = response.headers_mut();
+    // Every luminork response with a body is JSON
+    if has_body {
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+    } else {
+        headers.remove(header::CONTENT_TYPE);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    ApiError::new(status, message).into_response()
+}
+
+fn internal_error_response(err: impl std::fmt::Display) -> Response {
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic_in_result_fn)]
+
+    use color_eyre::Result;
+    use pretty_assertions_sorted::assert_eq;
+
+    use super::*;
+
+    fn parts(method: Method, uri: &str) -> Result<Parts> {
+        let (parts, _) = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(())?
+            .into_parts();
+        Ok(parts)
+    }
+
+    #[test]
+    fn request_hash_covers_method_path_query_and_body() -> Result<()> {
+        let hash = request_hash(&parts(Method::POST, "/components?force=true")?, b"{}");
+
+        assert_eq!(
+            hash,
+            request_hash(&parts(Method::POST, "/components?force=true")?, b"{}")
+        );
+        assert_ne!(
+            hash,
+            request_hash(&parts(Method::PUT, "/components?force=true")?, b"{}")
+        );
+        assert_ne!(
+            hash,
+            request_hash(&parts(Method::POST, "/components?force=false")?, b"{}")
+        );
+        assert_ne!(
+            hash,
+            request_hash(&parts(Method::POST, "/components")?, b"{}")
+        );
+        assert_ne!(
+            hash,
+            request_hash(&parts(Method::POST, "/components?force=true")?, b"[]")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_body_stops_at_limit() -> Result<()> {
+        let body = read_body(Body::from("0123456789"), 10).await?;
+        assert_eq!(b"0123456789".as_slice(), body.as_ref());
+
+        assert!(matches!(
+            read_body(Body::from("0123456789"), 9).await,
+            Err(ReadBodyError::TooLarge)
+        ));
+
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            for _ in 0..4 {
+                if sender.send_data(Bytes::from_static(b"0123")).await.is_err() {
+                    break;
+                }
+            }
+        });
+        assert!(matches!(
+            read_body(body, 10).await,
+            Err(ReadBodyError::TooLarge)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn replayed_response_is_marked() {
+        let response = replayed_response(201, b"{\"id\":1}".to_vec());
+        assert_eq!(StatusCode::CREATED, response.status());
+        assert_eq!(
+            Some("application/json"),
+            response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+        );
+        assert_eq!(
+            Some("true"),
+            response
+                .headers()
+                .get(IDEMPOTENT_REPLAYED_HEADER)
+                .and_then(|value| value.to_str().ok())
+        );
+
+        let response = replayed_response(204, Vec::new());
+        assert_eq!(StatusCode::NO_CONTENT, response.status());
+        assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+    }
+}
@@ -20,7 +20,10 @@ use axum::{
 };
 use hyper::{
     Method,
-    header,
+    header::{
+        self,
+        HeaderName,
+    },
 };
 use serde_json::{
     Value,
@@ -48,6 +51,11 @@ use crate::{
         AppState,
         ApplicationRuntimeMode,
     },
+    event_stream::LAST_EVENT_ID_HEADER,
+    middleware::{
+        IDEMPOTENCY_KEY_HEADER,
+        IDEMPOTENT_REPLAYED_HEADER,
+    },
     service::{
         v1,
         workspace_management,
@@ -246,9 +254,17 @@ pub fn routes(state: AppState) -> Router {
                     header::CACHE_CONTROL,
                     header::CONTENT_LANGUAGE,
                     header::PRAGMA,
+                    header::IF_MATCH,
+                    HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
+                    HeaderName::from_static(LAST_EVENT_ID_HEADER),
                 ])
                 // Expose headers that clients might need to access
-                .expose_headers([header::CONTENT_LENGTH, header::CONTENT_TYPE])
+                .expose_headers([
+                    header::CONTENT_LENGTH,
+                    header::CONTENT_TYPE,
+                    header::ETAG,
+                    HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER),
+                ])
                 // Allow standard methods
                 .allow_methods([
                     Method::GET,
@@ -182,6 +182,21 @@ fn match_query(
                 "category" => terms
                     .iter()
                     .any(|t| t.match_str(&component_in_list.schema_category)),
+                // label:key matches components with that label, label:key=value also checks the
+                // value
+                "label" | "labels" => {
+                    if terms.is_empty() {
+                        !component_in_list.labels.is_empty()
+                    } else {
+                        terms
+                            .iter()
+                            .any(|t| t.match_label(&component_in_list.labels))
+                    }
+                }
+                "owner" => match &component_in_list.owner {
+                    Some(owner) => terms.is_empty() || terms.iter().any(|t| t.match_str(owner)),
+                    None => false,
+                },
                 // A component is upgradeable if its schema variant ID is not the latest
                 "isupgradeable" | "isupgradable" | "upgradeable" | "upgradable" => {
                     let is_latest =
@@ -330,6 +330,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_label_attr() -> Result<()> {
+        assert_eq!(
+            parse("label:team=payments")?,
+            SearchQuery::MatchAttr {
+                name: "label".to_string(),
+                terms: vec![SearchTerm::Match("team=payments".to_string())]
+            }
+        );
+        Ok(())
+    }
+
     #[test]
     fn parse_empty_attr() -> Result<()> {
         assert_eq!(
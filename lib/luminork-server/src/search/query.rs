@@ -1,4 +1,7 @@
-use std::str::FromStr;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+};
 
 use ulid::Ulid;
 
@@ -119,6 +122,27 @@ impl SearchTerm {
         term.eq_ignore_ascii_case("null")
     }
 
+    /// Match a query term like "team" or "team=payments" against a component's labels
+    ///
+    /// The key must match exactly (ignoring case); the value is matched like any other string.
+    pub fn match_label(&self, labels: &HashMap<String, String>) -> bool {
+        let (key, value) = match self.as_str().split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (self.as_str(), None),
+        };
+        let value_term = value.map(|value| match self {
+            SearchTerm::Match(_) => SearchTerm::Match(value.to_string()),
+            SearchTerm::Exact(_) => SearchTerm::Exact(value.to_string()),
+            SearchTerm::StartsWith(_) => SearchTerm::StartsWith(value.to_string()),
+        });
+        labels.iter().any(|(label_key, label_value)| {
+            label_key.eq_ignore_ascii_case(key)
+                && value_term
+                    .as_ref()
+                    .is_none_or(|value_term| value_term.match_str(label_value))
+        })
+    }
+
     /// Match a query term like "01F8MECHZX3TBDSZ7XRADM79XE" against a ULID
     pub fn match_ulid(&self, value: impl Into<Ulid>) -> bool {
         let value = value.into();
@@ -234,6 +258,23 @@ mod tests {
         assert!(!term.match_str("foobario")); // something else entirely of the same length
     }
 
+    #[test]
+    fn match_label() {
+        let labels = HashMap::from([
+            ("team".to_string(), "payments".to_string()),
+            ("env".to_string(), "prod".to_string()),
+        ]);
+        assert!(SearchTerm::Match("team".to_string()).match_label(&labels)); // key only
+        assert!(SearchTerm::Match("Team=Payments".to_string()).match_label(&labels)); // case
+        assert!(SearchTerm::Match("team=pay".to_string()).match_label(&labels)); // partial value
+        assert!(SearchTerm::Exact("team=payments".to_string()).match_label(&labels)); // exact
+        assert!(!SearchTerm::Exact("team=pay".to_string()).match_label(&labels)); // exact partial
+        assert!(!SearchTerm::Match("tea".to_string()).match_label(&labels)); // partial key
+        assert!(!SearchTerm::Match("team=prod".to_string()).match_label(&labels)); // other value
+        assert!(!SearchTerm::Match("owner".to_string()).match_label(&labels)); // missing key
+        assert!(!SearchTerm::Match("team".to_string()).match_label(&HashMap::new())); // no labels
+    }
+
     #[test]
     fn match_null() {
         assert!(SearchTerm::Match("null".to_string()).match_null());
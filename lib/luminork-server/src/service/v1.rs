@@ -10,11 +10,18 @@ mod components;
 mod debug_funcs;
 mod funcs;
 mod management_funcs;
+mod module_trust;
 mod policy_reports;
+mod preconditions;
+mod promotions;
 mod schemas;
 mod search;
 mod secrets;
+mod templates;
+mod usage;
 mod user;
+mod variables;
+mod webhooks;
 mod workspaces;
 
 pub use actions::{
@@ -32,6 +39,12 @@ pub use change_sets::{
     },
     delete::DeleteChangeSetV1Response,
     get::GetChangeSetV1Response,
+    history::{
+        ChangeSetHistoryV1Response,
+        ChangeSetVersionV1,
+        GetObjectAtV1Request,
+        GetObjectAtV1Response,
+    },
     list::ListChangeSetV1Response,
     merge_status::{
         MergeStatusV1Response,
@@ -39,6 +52,13 @@ pub use change_sets::{
         MergeStatusV1ResponseActionComponent,
     },
     purge_open::PurgeOpenChangeSetsV1Response,
+    rebase::{
+        RebaseChangeSetV1Request,
+        RebaseChangeSetV1Response,
+        RebaseConflictKindV1,
+        RebaseConflictV1,
+        RebaseResolutionV1,
+    },
     review::{
         ChangeSetReviewV1Response,
         ComponentReviewV1,
@@ -63,6 +83,14 @@ pub use components::{
         AddActionV1Response,
     },
     add_to_view::AddToViewV1Request,
+    bulk_operations::{
+        BulkComponentOperationResultV1,
+        BulkComponentOperationStatusV1,
+        BulkComponentOperationV1,
+        BulkComponentOperationsV1Request,
+        BulkComponentOperationsV1Response,
+        apply_bulk_operations,
+    },
     create_component::{
         CreateComponentV1Request,
         CreateComponentV1Response,
@@ -125,6 +153,7 @@ pub use funcs::{
         UnlockFuncV1Response,
     },
     update_func::{
+        UpdateFuncResourceLimitsV1,
         UpdateFuncV1Request,
         UpdateFuncV1Response,
     },
@@ -135,10 +164,29 @@ pub use management_funcs::{
     ManagementFuncsResult,
     get_management_func_run_state::GetManagementFuncJobStateV1Response,
 };
+pub use module_trust::{
+    AddTrustedPublisherKeyV1Request,
+    GetModuleTrustV1Response,
+    ModuleTrustError,
+    ModuleTrustResult,
+    RemoveTrustedPublisherKeyV1Response,
+    TrustedPublisherKeyV1,
+    TrustedPublisherKeyV1RequestPath,
+    UpdateModuleTrustV1Request,
+};
 pub use policy_reports::{
     UploadPolicyReportV1Request,
     UploadPolicyReportV1Response,
 };
+pub use promotions::{
+    ExportPromotionV1Request,
+    ExportPromotionV1Response,
+    PromoteComponentsV1Request,
+    PromoteComponentsV1Response,
+    PromotedComponentV1,
+    PromotionsError,
+    PromotionsResult,
+};
 pub use schemas::{
     DetachFuncBindingV1Response,
     GetSchemaV1Response,
@@ -190,12 +238,78 @@ pub use search::{
     SearchV1Request,
     SearchV1Response,
 };
+pub use templates::{
+    TemplateInstanceV1RequestPath,
+    TemplateInstanceViewV1,
+    TemplateParameterV1,
+    TemplateV1RequestPath,
+    TemplateViewV1,
+    TemplatesError,
+    TemplatesResult,
+    instantiate_template::{
+        InstantiateTemplateV1Request,
+        InstantiateTemplateV1Response,
+    },
+    list_instances::ListTemplateInstancesV1Response,
+    list_templates::ListTemplatesV1Response,
+    save_template::{
+        SaveTemplateV1Request,
+        SaveTemplateV1Response,
+    },
+    upgrade_instance::{
+        UpgradeTemplateInstanceV1Request,
+        UpgradeTemplateInstanceV1Response,
+    },
+};
+pub use usage::{
+    FuncRunUsageV1,
+    GetWorkspaceUsageV1Params,
+    GetWorkspaceUsageV1Response,
+    UsageError,
+    UsageGranularityV1,
+    UsageResult,
+    WorkspaceUsageV1,
+};
+pub use variables::{
+    VariableV1RequestPath,
+    VariableViewV1,
+    VariablesError,
+    VariablesResult,
+    create_variable::{
+        CreateVariableV1Request,
+        CreateVariableV1Response,
+    },
+    delete_variable::DeleteVariableV1Response,
+    list_variables::ListVariablesV1Response,
+    update_variable::{
+        UpdateVariableV1Request,
+        UpdateVariableV1Response,
+    },
+};
+pub use webhooks::{
+    WebhookDeliveryV1RequestPath,
+    WebhookV1RequestPath,
+    WebhookViewV1,
+    WebhooksError,
+    WebhooksResult,
+    create_webhook::{
+        CreateWebhookV1Request,
+        CreateWebhookV1Response,
+    },
+    delete_webhook::DeleteWebhookV1Response,
+    deliver_webhook::DeliverWebhookV1Response,
+    list_webhooks::ListWebhooksV1Response,
+};
 pub use workspaces::WorkspaceError;
 
-pub use crate::api_types::func_run::v1::{
-    FuncRunLogViewV1,
-    FuncRunViewV1,
-    OutputLineViewV1,
+pub use crate::api_types::{
+    change_sets::v1::ChangeSetEventV1,
+    func_run::v1::{
+        FuncRunLogViewV1,
+        FuncRunResourceUsageViewV1,
+        FuncRunViewV1,
+        OutputLineViewV1,
+    },
 };
 
 /// OpenAPI documentation for v1 API
@@ -219,6 +333,11 @@ pub use crate::api_types::func_run::v1::{
         change_sets::request_approval::request_approval,
         change_sets::purge_open::purge_open,
         change_sets::review::review_change_set,
+        change_sets::events::stream_events,
+        change_sets::rebase::get_rebase,
+        change_sets::rebase::rebase_change_set,
+        change_sets::history::list_history,
+        change_sets::history::get_object_at,
         components::get_component::get_component,
         components::create_component::create_component,
         components::list_components::list_components,
@@ -229,6 +348,7 @@ pub use crate::api_types::func_run::v1::{
         components::execute_management_function::execute_management_function,
         components::add_action::add_action,
         components::add_to_view::add_to_view,
+        components::bulk_operations::bulk_operations,
         components::manage_component::manage_component,
         components::duplicate_components::duplicate_components,
         components::upgrade_component::upgrade_component,
@@ -275,8 +395,28 @@ pub use crate::api_types::func_run::v1::{
         secrets::delete_secret::delete_secret,
         secrets::update_secret::update_secret,
         secrets::get_secrets::get_secrets,
+        variables::create_variable::create_variable,
+        variables::delete_variable::delete_variable,
+        variables::list_variables::list_variables,
+        variables::update_variable::update_variable,
         search::search,
         policy_reports::upload::upload_policy_report,
+        webhooks::create_webhook::create_webhook,
+        webhooks::list_webhooks::list_webhooks,
+        webhooks::delete_webhook::delete_webhook,
+        webhooks::deliver_webhook::deliver_webhook,
+        usage::get_workspace_usage,
+        module_trust::get_module_trust,
+        module_trust::update_module_trust,
+        module_trust::add_trusted_publisher_key,
+        module_trust::remove_trusted_publisher_key,
+        promotions::export_promotion,
+        promotions::promote_components,
+        templates::list_templates::list_templates,
+        templates::save_template::save_template,
+        templates::instantiate_template::instantiate_template,
+        templates::list_instances::list_instances,
+        templates::upgrade_instance::upgrade_instance,
     ),
     components(
         schemas(
@@ -292,6 +432,16 @@ pub use crate::api_types::func_run::v1::{
             MergeStatusV1ResponseAction,
             MergeStatusV1ResponseActionComponent,
             ChangeSetReviewV1Response,
+            ChangeSetEventV1,
+            RebaseChangeSetV1Request,
+            RebaseChangeSetV1Response,
+            RebaseConflictV1,
+            RebaseConflictKindV1,
+            RebaseResolutionV1,
+            ChangeSetHistoryV1Response,
+            ChangeSetVersionV1,
+            GetObjectAtV1Request,
+            GetObjectAtV1Response,
             ComponentReviewV1,
             ReviewSummaryV1,
             SimplifiedAttributeDiffV1,
@@ -308,6 +458,11 @@ pub use crate::api_types::func_run::v1::{
             AddActionV1Request,
             AddToViewV1Request,
             ActionReference,
+            BulkComponentOperationsV1Request,
+            BulkComponentOperationsV1Response,
+            BulkComponentOperationV1,
+            BulkComponentOperationResultV1,
+            BulkComponentOperationStatusV1,
             ListSchemaV1Response,
             SchemaV1RequestPath,
             SchemaVariantV1RequestPath,
@@ -335,11 +490,13 @@ pub use crate::api_types::func_run::v1::{
             FindComponentV1Params,
             FuncRunV1RequestPath,
             FuncRunLogViewV1,
+            FuncRunResourceUsageViewV1,
             FuncRunViewV1,
             OutputLineViewV1,
             GetFuncV1Response,
             GetFuncRunV1Response,
             UpdateFuncV1Request,
+            UpdateFuncResourceLimitsV1,
             UpdateFuncV1Response,
             UnlockFuncV1Request,
             UnlockFuncV1Response,
@@ -380,6 +537,51 @@ pub use crate::api_types::func_run::v1::{
             GetDebugFuncJobStateV1Response,
             UploadPolicyReportV1Request,
             UploadPolicyReportV1Response,
+            VariableViewV1,
+            VariableV1RequestPath,
+            CreateVariableV1Request,
+            CreateVariableV1Response,
+            ListVariablesV1Response,
+            UpdateVariableV1Request,
+            UpdateVariableV1Response,
+            DeleteVariableV1Response,
+            WebhookViewV1,
+            WebhookV1RequestPath,
+            WebhookDeliveryV1RequestPath,
+            CreateWebhookV1Request,
+            CreateWebhookV1Response,
+            ListWebhooksV1Response,
+            DeleteWebhookV1Response,
+            DeliverWebhookV1Response,
+            GetWorkspaceUsageV1Params,
+            GetWorkspaceUsageV1Response,
+            WorkspaceUsageV1,
+            FuncRunUsageV1,
+            UsageGranularityV1,
+            GetModuleTrustV1Response,
+            UpdateModuleTrustV1Request,
+            AddTrustedPublisherKeyV1Request,
+            TrustedPublisherKeyV1,
+            TrustedPublisherKeyV1RequestPath,
+            RemoveTrustedPublisherKeyV1Response,
+            ExportPromotionV1Request,
+            ExportPromotionV1Response,
+            PromoteComponentsV1Request,
+            PromoteComponentsV1Response,
+            PromotedComponentV1,
+            TemplateViewV1,
+            TemplateParameterV1,
+            TemplateInstanceViewV1,
+            TemplateV1RequestPath,
+            TemplateInstanceV1RequestPath,
+            ListTemplatesV1Response,
+            SaveTemplateV1Request,
+            SaveTemplateV1Response,
+            InstantiateTemplateV1Request,
+            InstantiateTemplateV1Response,
+            ListTemplateInstancesV1Response,
+            UpgradeTemplateInstanceV1Request,
+            UpgradeTemplateInstanceV1Response,
         )
     ),
     tags(
@@ -389,16 +591,24 @@ pub use crate::api_types::func_run::v1::{
         (name = "schemas", description = "Schema management endpoints"),
         (name = "actions", description = "Action management endpoints"),
         (name = "secrets", description = "Secret management endpoints"),
+        (name = "variables", description = "Variable management endpoints"),
         (name = "funcs", description = "Functions management endpoints"),
         (name = "debug_funcs", description = "Debug function endpoints"),
         (name = "management_funcs", description = "Management functions endpoints"),
-        (name = "policy_reports", description = "Policy report endpoints")
+        (name = "policy_reports", description = "Policy report endpoints"),
+        (name = "webhooks", description = "Webhook endpoints"),
+        (name = "usage", description = "Workspace usage endpoints"),
+        (name = "module_trust", description = "Module signature trust endpoints"),
+        (name = "promotions", description = "Component promotion endpoints"),
+        (name = "templates", description = "Component template endpoints")
     )
 )]
 pub struct V1ApiDoc;
 
 pub fn routes(state: AppState) -> Router<AppState> {
-    Router::new().nest("/w", workspaces::routes(state))
+    Router::new()
+        .nest("/w", workspaces::routes(state))
+        .nest("/webhooks", webhooks::delivery_routes())
 }
 
 pub fn get_openapi() -> utoipa::openapi::OpenApi {
@@ -0,0 +1,125 @@
+use axum::{
+    Extension,
+    extract::State,
+    http::HeaderMap,
+    response::sse::{
+        Event,
+        KeepAlive,
+        Sse,
+    },
+};
+use futures::{
+    Stream,
+    StreamExt,
+    stream,
+};
+use sdf_core::nats_multiplexer::NatsMultiplexerClients;
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+use ulid::Ulid;
+
+use super::ChangeSetResult;
+use crate::{
+    api_types::change_sets::v1::ChangeSetEventV1,
+    event_stream::{
+        EventStreams,
+        LAST_EVENT_ID_HEADER,
+        StreamedEvent,
+    },
+    extract::{
+        PosthogEventTracker,
+        change_set::ChangeSetDalContext,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/events",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("Last-Event-ID" = Option<String>, Header, description = "The id of the last event received, to resume the stream after it")
+    ),
+    tag = "change_sets",
+    summary = "Stream Change Set events",
+    description = "Streams the change set's events as server-sent events, named after the event type and with the event as JSON data. Events have an id, which can be sent as `Last-Event-ID` when reconnecting to receive the events that were missed. When missed events can't be replayed, a `resync` event is sent first, after which anything derived from earlier events should be fetched again.",
+    responses(
+        (status = 200, description = "Change Set event stream", body = ChangeSetEventV1, content_type = "text/event-stream"),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn stream_events(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    headers: HeaderMap,
+    State(nats_multiplexer_clients): State<NatsMultiplexerClients>,
+    State(shutdown_token): State<CancellationToken>,
+    Extension(event_streams): Extension<EventStreams>,
+) -> ChangeSetResult<Sse<impl Stream<Item = Result<Event, axum::Error>>>> {
+    let change_set_id = ctx.change_set_id();
+    // An id that can't be parsed can't be resumed from either
+    let last_event_id = headers.get(LAST_EVENT_ID_HEADER).map(|value| {
+        value
+            .to_str()
+            .ok()
+            .and_then(|value| value.parse::<Ulid>().ok())
+    });
+
+    let subscription = event_streams
+        .subscribe(
+            ctx.workspace_pk()?,
+            last_event_id.flatten(),
+            &nats_multiplexer_clients,
+            ctx.services_context()
+                .nats_conn()
+                .metadata()
+                .subject_prefix(),
+            shutdown_token.clone(),
+        )
+        .await?;
+
+    tracker.track(
+        ctx,
+        "api_stream_change_set_events",
+        json!({
+            "resumed": last_event_id.is_some(),
+        }),
+    );
+
+    let replayed = match subscription.replayed {
+        Some(replayed) if last_event_id != Some(None) => replayed
+            .into_iter()
+            .filter(|event| event.is_for(change_set_id))
+            .collect(),
+        _ => vec![StreamedEvent::resync()],
+    };
+    let received = stream::unfold(subscription.receiver, move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) if event.is_for(change_set_id) => return Some((event, receiver)),
+                Ok(_) => {}
+                Err(RecvError::Lagged(_)) => return Some((StreamedEvent::resync(), receiver)),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let events = stream::iter(replayed)
+        .chain(received)
+        .map(|event| into_sse_event(&event))
+        .take_until(shutdown_token.cancelled_owned());
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+fn into_sse_event(event: &StreamedEvent) -> Result<Event, axum::Error> {
+    let sse_event = Event::default().event(event.event.name());
+    let sse_event = match event.id {
+        Some(id) => sse_event.id(id.to_string()),
+        None => sse_event,
+    };
+
+    sse_event.json_data(&event.event)
+}
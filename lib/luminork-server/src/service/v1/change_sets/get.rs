@@ -1,4 +1,7 @@
-use axum::response::Json;
+use axum::{
+    http::HeaderName,
+    response::Json,
+};
 use dal::change_set::ChangeSet;
 use serde::{
     Deserialize,
@@ -14,6 +17,10 @@ use crate::{
         PosthogEventTracker,
         change_set::ChangeSetDalContext,
     },
+    service::v1::preconditions::{
+        change_set_entity_tag,
+        entity_tag_header,
+    },
 };
 
 #[utoipa::path(
@@ -26,7 +33,9 @@ use crate::{
     tag = "change_sets",
     summary = "Get a Change Set by Change Set Id",
     responses(
-        (status = 200, description = "Change details retrieved successfully", body = GetChangeSetV1Response),
+        (status = 200, description = "Change details retrieved successfully", body = GetChangeSetV1Response, headers(
+            ("ETag" = String, description = "The change set's entity tag, for use in If-Match preconditions")
+        )),
         (status = 401, description = "Unauthorized - Invalid or missing token"),
         (status = 404, description = "Change Set not found"),
         (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
@@ -35,7 +44,7 @@ use crate::{
 pub async fn get_change_set(
     ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
     tracker: PosthogEventTracker,
-) -> ChangeSetResult<Json<GetChangeSetV1Response>> {
+) -> ChangeSetResult<([(HeaderName, String); 1], Json<GetChangeSetV1Response>)> {
     tracker.track(ctx, "api_get_change_set", json!({}));
 
     let change_set = ChangeSet::get_by_id(ctx, ctx.change_set_id()).await?;
@@ -47,9 +56,12 @@ pub async fn get_change_set(
         is_head: change_set.clone().is_head(ctx).await?,
     };
 
-    Ok(Json(GetChangeSetV1Response {
-        change_set: change_set_vew,
-    }))
+    Ok((
+        entity_tag_header(&change_set_entity_tag(ctx)?),
+        Json(GetChangeSetV1Response {
+            change_set: change_set_vew,
+        }),
+    ))
 }
 
 #[derive(Deserialize, Serialize, Debug, ToSchema)]
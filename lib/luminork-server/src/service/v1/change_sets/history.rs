@@ -0,0 +1,201 @@
+use axum::{
+    extract::{
+        Path,
+        Query,
+    },
+    response::Json,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use frigg::IndexAt;
+use sdf_extract::{
+    FriggStore,
+    change_set::ChangeSetAuthorization,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Value,
+    json,
+};
+use utoipa::{
+    IntoParams,
+    ToSchema,
+};
+
+use super::{
+    ChangeSetError,
+    ChangeSetResult,
+};
+use crate::extract::PosthogEventTracker;
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/history",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "change_sets",
+    summary = "List the past versions of a Change Set",
+    description = "Lists the recorded versions of the change set's index, oldest first. Versions older than the server's retention period are not kept, apart from the latest one.",
+    responses(
+        (status = 200, description = "Change Set history retrieved successfully", body = ChangeSetHistoryV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn list_history(
+    FriggStore(ref frigg): FriggStore,
+    ChangeSetAuthorization {
+        workspace_id,
+        change_set_id,
+        ..
+    }: ChangeSetAuthorization,
+    tracker: PosthogEventTracker,
+) -> ChangeSetResult<Json<ChangeSetHistoryV1Response>> {
+    let versions: Vec<ChangeSetVersionV1> = frigg
+        .list_change_set_index_history(workspace_id, change_set_id)
+        .await?
+        .into_iter()
+        .map(|entry| ChangeSetVersionV1 {
+            revision: entry.revision,
+            recorded_at: entry.recorded_at,
+            snapshot_address: entry.index_pointer.snapshot_address,
+            index_checksum: entry.index_pointer.index_checksum,
+        })
+        .collect();
+
+    tracker.track_no_ctx(
+        workspace_id,
+        change_set_id,
+        "api_list_change_set_history",
+        json!({
+            "versions": versions.len(),
+        }),
+    );
+
+    Ok(Json(ChangeSetHistoryV1Response { versions }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/history/objects/{kind}/{id}",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("kind" = String, Path, description = "Kind of the object, such as ComponentInList"),
+        ("id" = String, Path, description = "Object identifier"),
+        GetObjectAtV1Request,
+    ),
+    tag = "change_sets",
+    summary = "Get an object as it was in a past version of a Change Set",
+    description = "Reads the object from the change set as it was at a revision listed in the change set's history, or at a point in time. Exactly one of `revision` or `timestamp` must be given.",
+    responses(
+        (status = 200, description = "Object retrieved successfully", body = GetObjectAtV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Object not found at the requested point"),
+        (status = 422, description = "Validation error - Neither or both of revision and timestamp given", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn get_object_at(
+    FriggStore(ref frigg): FriggStore,
+    ChangeSetAuthorization {
+        workspace_id,
+        change_set_id,
+        ..
+    }: ChangeSetAuthorization,
+    tracker: PosthogEventTracker,
+    Path(ObjectAtV1RequestPath { kind, id }): Path<ObjectAtV1RequestPath>,
+    Query(request): Query<GetObjectAtV1Request>,
+) -> ChangeSetResult<Json<GetObjectAtV1Response>> {
+    let at = IndexAt::from_revision_or_timestamp(request.revision, request.timestamp).ok_or_else(
+        || {
+            ChangeSetError::Validation(
+                "exactly one of revision or timestamp must be provided".to_string(),
+            )
+        },
+    )?;
+
+    let object = frigg
+        .get_workspace_object_at(workspace_id, change_set_id, &kind, &id, at)
+        .await?
+        .ok_or_else(|| ChangeSetError::ObjectNotFoundAt {
+            kind: kind.clone(),
+            id: id.clone(),
+        })?;
+
+    tracker.track_no_ctx(
+        workspace_id,
+        change_set_id,
+        "api_get_change_set_object_at",
+        json!({
+            "kind": kind,
+            "by_revision": request.revision.is_some(),
+        }),
+    );
+
+    Ok(Json(GetObjectAtV1Response {
+        kind: object.kind,
+        id: object.id,
+        checksum: object.checksum,
+        data: object.data,
+    }))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ObjectAtV1RequestPath {
+    pub kind: String,
+    pub id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, IntoParams, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[into_params(style = Form, parameter_in = Query)]
+pub struct GetObjectAtV1Request {
+    /// A revision from the change set's history
+    #[param(required = false, nullable = true)]
+    #[schema(example = 42)]
+    pub revision: Option<u64>,
+    /// Read the latest version recorded at or before this time
+    #[param(value_type = String, required = false, nullable = true)]
+    #[schema(value_type = Option<String>, example = "2025-06-03T12:00:00Z")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetHistoryV1Response {
+    pub versions: Vec<ChangeSetVersionV1>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetVersionV1 {
+    #[schema(example = 42)]
+    pub revision: u64,
+    #[schema(value_type = String, example = "2025-06-03T12:00:00Z")]
+    pub recorded_at: DateTime<Utc>,
+    #[schema(example = "f3a1a0b6c1d2e3f4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8")]
+    pub snapshot_address: String,
+    #[schema(example = "b6c1d2e3f4a5b6c7")]
+    pub index_checksum: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetObjectAtV1Response {
+    #[schema(example = "ComponentInList")]
+    pub kind: String,
+    #[schema(example = "01H9ZQD35JPMBGHH69BT0Q79AA")]
+    pub id: String,
+    #[schema(example = "b6c1d2e3f4a5b6c7")]
+    pub checksum: String,
+    #[schema(value_type = Object)]
+    pub data: Value,
+}
@@ -6,22 +6,31 @@ use axum::{
         Response,
     },
 };
-use dal::ChangeSetId;
+use dal::{
+    ChangeSetId,
+    change_set::rebase::ChangeSetRebaseError,
+};
 use frigg::FriggError;
 use sdf_core::EddaClientError;
 use thiserror::Error;
 
 pub mod create;
 pub mod delete;
+pub mod events;
 pub mod force_apply;
 pub mod get;
+pub mod history;
 pub mod list;
 pub mod merge_status;
 pub mod purge_open;
+pub mod rebase;
 pub mod request_approval;
 pub mod review;
 
-use super::common::ErrorIntoResponse;
+use super::{
+    common::ErrorIntoResponse,
+    preconditions::PreconditionError,
+};
 
 pub type ChangeSetResult<T> = Result<T, ChangeSetError>;
 
@@ -42,16 +51,24 @@ pub enum ChangeSetError {
     ChangeSetMvs(#[from] sdf_core::change_set_mvs::ChangeSetMvsError),
     #[error("change set not found: {0}")]
     ChangeSetNotFound(ChangeSetId),
+    #[error("change set rebase error: {0}")]
+    ChangeSetRebase(#[from] ChangeSetRebaseError),
     #[error("component error: {0}")]
     Component(#[from] dal::ComponentError),
     #[error("edda client error: {0}")]
     EddaClient(#[from] EddaClientError),
+    #[error("event stream error: {0}")]
+    EventStream(#[from] crate::event_stream::EventStreamError),
     #[error("frigg error: {0}")]
     Frigg(#[from] FriggError),
     #[error("func error: {0}")]
     Func(#[from] dal::FuncError),
     #[error("no diff available for head change set")]
     HeadDiffNotAvailable,
+    #[error("object not found at the requested point: kind: {kind}, id: {id}")]
+    ObjectNotFoundAt { kind: String, id: String },
+    #[error("{0}")]
+    Precondition(#[from] PreconditionError),
     #[error("schema error: {0}")]
     Schema(#[from] dal::SchemaError),
     #[error("schema variant error: {0}")]
@@ -83,7 +100,18 @@ impl ErrorIntoResponse for ChangeSetError {
             ),
             ChangeSetError::CannotAbandonHead => (StatusCode::BAD_REQUEST, self.to_string()),
             ChangeSetError::CannotMergeHead => (StatusCode::BAD_REQUEST, self.to_string()),
+            ChangeSetError::ChangeSetRebase(ChangeSetRebaseError::CannotRebaseHead(_)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            ChangeSetError::ChangeSetRebase(
+                ChangeSetRebaseError::MissingResolution(_)
+                | ChangeSetRebaseError::UnknownConflict(_),
+            ) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
             ChangeSetError::HeadDiffNotAvailable => (StatusCode::BAD_REQUEST, self.to_string()),
+            ChangeSetError::ObjectNotFoundAt { .. } => (StatusCode::NOT_FOUND, self.to_string()),
+            ChangeSetError::Precondition(PreconditionError::Failed(_)) => {
+                (StatusCode::PRECONDITION_FAILED, self.to_string())
+            }
             ChangeSetError::Validation(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         }
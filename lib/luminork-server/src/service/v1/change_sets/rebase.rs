@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::rejection::JsonRejection,
+    response::Json,
+};
+use dal::{
+    AttributeValueId,
+    ComponentId,
+    change_set::rebase::{
+        self,
+        RebaseConflict,
+        RebaseReport,
+        RebaseResolution,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Value,
+    json,
+};
+use si_id::EntityId;
+use utoipa::ToSchema;
+
+use super::{
+    ChangeSetError,
+    ChangeSetResult,
+};
+use crate::{
+    extract::{
+        PosthogEventTracker,
+        change_set::ChangeSetDalContext,
+    },
+    service::v1::preconditions::IfMatch,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/rebase",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "change_sets",
+    summary = "Get the conflicts of rebasing a Change Set onto HEAD",
+    description = "Compares the change set with HEAD and reports where they disagree: attribute values set to different values on each side, and components deleted on one side but not the other. Each conflict needs a resolution when rebasing.",
+    responses(
+        (status = 200, description = "Change Set rebase conflicts retrieved successfully", body = RebaseChangeSetV1Response),
+        (status = 400, description = "Bad request - Cannot rebase HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn get_rebase(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+) -> ChangeSetResult<Json<RebaseChangeSetV1Response>> {
+    let report = rebase::detect(ctx).await?;
+
+    tracker.track(
+        ctx,
+        "api_get_change_set_rebase",
+        json!({
+            "conflicts": report.conflicts.len(),
+        }),
+    );
+
+    Ok(Json(report.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/rebase",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only rebase if the change set's entity tag still matches one of these, as returned in the `ETag` header when getting the change set"),
+    ),
+    tag = "change_sets",
+    summary = "Rebase a Change Set onto HEAD",
+    description = "Applies a resolution to every conflict with HEAD, keyed by the conflict's id, and pulls in the components created on HEAD since the change set was created. Every current conflict needs a resolution, so fetch the conflicts again if this fails with 422.",
+    request_body = RebaseChangeSetV1Request,
+    responses(
+        (status = 200, description = "Change Set rebased successfully", body = RebaseChangeSetV1Response),
+        (status = 400, description = "Bad request - Cannot rebase HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 412, description = "Precondition Failed - If-Match entity tag doesn't match", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Missing or unknown conflict resolutions", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn rebase_change_set(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    if_match: IfMatch,
+    payload: Result<Json<RebaseChangeSetV1Request>, JsonRejection>,
+) -> ChangeSetResult<Json<RebaseChangeSetV1Response>> {
+    let Json(payload) = payload?;
+    if_match.check_change_set(ctx)?;
+
+    let mut resolutions = HashMap::new();
+    for (conflict_id, resolution) in payload.resolutions {
+        let entity_id: EntityId = conflict_id.parse().map_err(|_| {
+            ChangeSetError::Validation(format!("invalid conflict id: {conflict_id}"))
+        })?;
+        resolutions.insert(entity_id, resolution.into());
+    }
+
+    let report = rebase::rebase_onto_head(ctx, &resolutions).await?;
+
+    tracker.track(
+        ctx,
+        "api_rebase_change_set",
+        json!({
+            "conflicts": report.conflicts.len(),
+            "incoming_components": report.incoming_component_ids.len(),
+        }),
+    );
+
+    if_match.commit(ctx).await?;
+
+    Ok(Json(report.into()))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseChangeSetV1Request {
+    #[schema(example = json!({
+        "01H9ZQD35JPMBGHH69BT0Q79VY": "takeHead",
+        "01H9ZQD35JPMBGHH69BT0Q79AB": "keepChangeSet"
+    }))]
+    pub resolutions: HashMap<String, RebaseResolutionV1>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseResolutionV1 {
+    KeepChangeSet,
+    TakeHead,
+}
+
+impl From<RebaseResolutionV1> for RebaseResolution {
+    fn from(value: RebaseResolutionV1) -> Self {
+        match value {
+            RebaseResolutionV1::KeepChangeSet => Self::KeepChangeSet,
+            RebaseResolutionV1::TakeHead => Self::TakeHead,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(example = json!({
+    "hasLocalChanges": true,
+    "conflicts": [
+        {
+            "id": "01H9ZQD35JPMBGHH69BT0Q79VY",
+            "kind": "attributeValue",
+            "componentId": "01H9ZQD35JPMBGHH69BT0Q79AB",
+            "attributeValueId": "01H9ZQD35JPMBGHH69BT0Q79VY",
+            "path": "/domain/InstanceType",
+            "headValue": "t3.large",
+            "changeSetValue": "t3.micro"
+        }
+    ],
+    "incomingComponentIds": []
+}))]
+pub struct RebaseChangeSetV1Response {
+    pub has_local_changes: bool,
+    pub conflicts: Vec<RebaseConflictV1>,
+    #[schema(value_type = Vec<String>)]
+    pub incoming_component_ids: Vec<ComponentId>,
+}
+
+impl From<RebaseReport> for RebaseChangeSetV1Response {
+    fn from(value: RebaseReport) -> Self {
+        Self {
+            has_local_changes: value.has_local_changes,
+            conflicts: value.conflicts.into_iter().map(Into::into).collect(),
+            incoming_component_ids: value.incoming_component_ids,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseConflictKindV1 {
+    AttributeValue,
+    ComponentDeletedInChangeSet,
+    ComponentDeletedOnHead,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseConflictV1 {
+    /// The id to key this conflict's resolution by
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub id: EntityId,
+    pub kind: RebaseConflictKindV1,
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79AB")]
+    pub component_id: ComponentId,
+    #[schema(value_type = Option<String>, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub attribute_value_id: Option<AttributeValueId>,
+    #[schema(example = "/domain/InstanceType")]
+    pub path: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub head_value: Option<Value>,
+    #[schema(value_type = Option<Object>)]
+    pub change_set_value: Option<Value>,
+}
+
+impl From<RebaseConflict> for RebaseConflictV1 {
+    fn from(value: RebaseConflict) -> Self {
+        let id = value.entity_id();
+        match value {
+            RebaseConflict::AttributeValue {
+                attribute_value_id,
+                component_id,
+                path,
+                head_value,
+                change_set_value,
+            } => Self {
+                id,
+                kind: RebaseConflictKindV1::AttributeValue,
+                component_id,
+                attribute_value_id: Some(attribute_value_id),
+                path: Some(path),
+                head_value,
+                change_set_value,
+            },
+            RebaseConflict::ComponentDeletedInChangeSet { component_id } => Self {
+                id,
+                kind: RebaseConflictKindV1::ComponentDeletedInChangeSet,
+                component_id,
+                attribute_value_id: None,
+                path: None,
+                head_value: None,
+                change_set_value: None,
+            },
+            RebaseConflict::ComponentDeletedOnHead { component_id } => Self {
+                id,
+                kind: RebaseConflictKindV1::ComponentDeletedOnHead,
+                component_id,
+                attribute_value_id: None,
+                path: None,
+                head_value: None,
+                change_set_value: None,
+            },
+        }
+    }
+}
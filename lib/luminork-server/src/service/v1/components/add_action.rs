@@ -34,6 +34,7 @@ use crate::{
     service::v1::{
         ComponentV1RequestPath,
         ComponentsError,
+        preconditions::IfMatch,
     },
 };
 
@@ -43,7 +44,8 @@ use crate::{
     params(
         ("workspace_id" = String, Path, description = "Workspace identifier"),
         ("change_set_id" = String, Path, description = "Change Set identifier"),
-        ("component_id" = String, Path, description = "Component identifier")
+        ("component_id" = String, Path, description = "Component identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only apply the write if the component's entity tag (or the change set's) still matches one of these, as returned in the `ETag` header when getting the component or change set")
     ),
     tag = "components",
     request_body = AddActionV1Request,
@@ -53,7 +55,7 @@ use crate::{
         (status = 401, description = "Unauthorized - Invalid or missing token"),
         (status = 404, description = "Component or function not found"),
         (status = 409, description = "action already enqueued", body = crate::service::v1::common::ApiError),
-        (status = 412, description = "Precondition Failed - View not found or duplicate function name", body = crate::service::v1::common::ApiError),
+        (status = 412, description = "Precondition Failed - View not found, duplicate function name or If-Match entity tag doesn't match", body = crate::service::v1::common::ApiError),
         (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
         (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
     )
@@ -61,6 +63,7 @@ use crate::{
 pub async fn add_action(
     ChangeSetDalContext(ref ctx): ChangeSetDalContext,
     tracker: PosthogEventTracker,
+    if_match: IfMatch,
     Path(ComponentV1RequestPath { component_id }): Path<ComponentV1RequestPath>,
     payload: Result<Json<AddActionV1Request>, axum::extract::rejection::JsonRejection>,
 ) -> Result<Json<AddActionV1Response>, ComponentsError> {
@@ -69,9 +72,32 @@ pub async fn add_action(
     if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
         return Err(ComponentsError::NotPermittedOnHead);
     }
+    if_match.check_component(ctx, component_id).await?;
 
-    let action_prototype_id =
-        resolve_action_function_reference(ctx, component_id, &payload.action).await?;
+    let func_name = enqueue_action(ctx, component_id, &payload.action).await?;
+
+    tracker.track(
+        ctx,
+        "api_queue_action",
+        json!({
+            "component_id": component_id,
+            "action_func_name": func_name,
+        }),
+    );
+
+    if_match.commit(ctx).await?;
+
+    Ok(Json(AddActionV1Response { success: true }))
+}
+
+/// Enqueues the referenced action for a component, refusing to enqueue a second create, destroy,
+/// update or refresh action. Returns the name of the action function.
+pub(super) async fn enqueue_action(
+    ctx: &dal::DalContext,
+    component_id: ComponentId,
+    action: &ActionReference,
+) -> Result<String, ComponentsError> {
+    let action_prototype_id = resolve_action_function_reference(ctx, component_id, action).await?;
     let prototype = ActionPrototype::get_by_id(ctx, action_prototype_id).await?;
 
     match prototype.kind {
@@ -89,15 +115,6 @@ pub async fn add_action(
 
     Action::new(ctx, action_prototype_id, component_id.into()).await?;
 
-    tracker.track(
-        ctx,
-        "api_queue_action",
-        json!({
-            "component_id": component_id,
-            "action_func_name": func.name.clone(),
-        }),
-    );
-
     ctx.write_audit_log(
         AuditLogKind::AddAction {
             prototype_id: prototype.id,
@@ -107,13 +124,11 @@ pub async fn add_action(
             func_name: func.name.clone(),
             component_id: Some(component_id),
         },
-        func.name,
+        func.name.clone(),
     )
     .await?;
 
-    ctx.commit().await?;
-
-    Ok(Json(AddActionV1Response { success: true }))
+    Ok(func.name)
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
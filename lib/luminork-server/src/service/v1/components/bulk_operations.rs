@@ -0,0 +1,466 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use axum::response::Json;
+use dal::{
+    Component,
+    ComponentError,
+    ComponentId,
+    DalContext,
+    attribute::attributes::AttributeSources,
+    component::delete::{
+        self,
+        ComponentDeletionStatus,
+    },
+    diagram::{
+        geometry::RawGeometry,
+        view::View,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use si_events::audit_log::AuditLogKind;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    ComponentReference,
+    add_action::{
+        ActionReference,
+        enqueue_action,
+    },
+    create_component::create_component_for_schema_name,
+    resolve_component_reference,
+};
+use crate::{
+    extract::{
+        PosthogEventTracker,
+        change_set::ChangeSetDalContext,
+    },
+    service::v1::{
+        ComponentsError,
+        common::{
+            ApiError,
+            ErrorIntoResponse,
+        },
+        preconditions::IfMatch,
+    },
+};
+
+/// The most operations a single bulk request may contain.
+const MAX_OPERATIONS: usize = 1000;
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/components/bulk",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only apply the write if the change set's entity tag still matches one of these, as returned in the `ETag` header when getting the change set"),
+    ),
+    tag = "components",
+    request_body = BulkComponentOperationsV1Request,
+    summary = "Apply many component operations at once",
+    description = "Applies create, update, delete, add action and add to view operations in order, with a single commit (and so a single rebase and dependent values update) at the end. Each operation gets its own result. Without `atomic`, a failed operation doesn't stop the ones after it, and none of its changes are committed. With `atomic`, nothing is committed unless every operation succeeds, which is cheaper for large change sets as the changes of each operation don't need to be kept in case it fails.",
+    responses(
+        (status = 200, description = "Operations applied, see the per-operation results", body = BulkComponentOperationsV1Response),
+        (status = 400, description = "Bad Request - Changes not permitted on HEAD change set", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 412, description = "Precondition failed - If-Match entity tag doesn't match", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn bulk_operations(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    if_match: IfMatch,
+    payload: Result<
+        Json<BulkComponentOperationsV1Request>,
+        axum::extract::rejection::JsonRejection,
+    >,
+) -> Result<Json<BulkComponentOperationsV1Response>, ComponentsError> {
+    let Json(payload) = payload?;
+
+    if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
+        return Err(ComponentsError::NotPermittedOnHead);
+    }
+    if_match.check_change_set(ctx)?;
+
+    let atomic = payload.atomic;
+    let response = apply_bulk_operations(ctx, payload).await?;
+
+    tracker.track(
+        ctx,
+        "api_bulk_component_operations",
+        json!({
+            "operations": response.results.len(),
+            "failed": response
+                .results
+                .iter()
+                .filter(|result| result.status == BulkComponentOperationStatusV1::Failed)
+                .count(),
+            "atomic": atomic,
+            "committed": response.committed,
+        }),
+    );
+
+    // Without a commit, everything the operations changed is dropped along with the context
+    if response.committed {
+        if_match.commit(ctx).await?;
+    }
+
+    Ok(Json(response))
+}
+
+/// Applies the operations of a bulk request in order, leaving the commit to the caller.
+pub async fn apply_bulk_operations(
+    ctx: &DalContext,
+    request: BulkComponentOperationsV1Request,
+) -> Result<BulkComponentOperationsV1Response, ComponentsError> {
+    if request.operations.len() > MAX_OPERATIONS {
+        return Err(ComponentsError::Validation(format!(
+            "a bulk request can contain at most {MAX_OPERATIONS} operations, got {}",
+            request.operations.len()
+        )));
+    }
+
+    let mut state = BulkState {
+        component_list: Component::list_ids(ctx).await?,
+        head_components: HashSet::new(),
+        socket_map: HashMap::new(),
+        socket_map_head: HashMap::new(),
+        base_change_set_ctx: None,
+    };
+
+    let mut results = Vec::with_capacity(request.operations.len());
+    let mut failed = false;
+    for (index, operation) in request.operations.into_iter().enumerate() {
+        if failed && request.atomic {
+            results.push(BulkComponentOperationResultV1 {
+                index,
+                status: BulkComponentOperationStatusV1::Skipped,
+                component_id: None,
+                error: None,
+            });
+            continue;
+        }
+
+        // Without `atomic`, the operations that succeed get committed even if others fail, so
+        // the changes (and events and audit logs) of an operation that fails partway through have
+        // to be undone. Checkpoints clone the snapshot, so they are only taken when needed.
+        let checkpoint = if !request.atomic && operation.can_fail_partway(ctx).await? {
+            Some(ctx.checkpoint().await?)
+        } else {
+            None
+        };
+
+        let result = match apply_operation(ctx, &mut state, operation).await {
+            Ok(component_id) => {
+                if let Some(checkpoint) = checkpoint {
+                    ctx.keep(checkpoint).await?;
+                }
+                BulkComponentOperationResultV1 {
+                    index,
+                    status: BulkComponentOperationStatusV1::Succeeded,
+                    component_id: Some(component_id),
+                    error: None,
+                }
+            }
+            Err(err) => {
+                failed = true;
+                if let Some(checkpoint) = checkpoint {
+                    ctx.restore(checkpoint).await?;
+                }
+                let (status, message) = err.status_and_message();
+                BulkComponentOperationResultV1 {
+                    index,
+                    status: BulkComponentOperationStatusV1::Failed,
+                    component_id: None,
+                    error: Some(ApiError::new(status, message)),
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    Ok(BulkComponentOperationsV1Response {
+        committed: !(failed && request.atomic),
+        results,
+    })
+}
+
+/// What the operations of a request share, so that it only gets computed once.
+struct BulkState {
+    /// Components that exist in the change set, for resolving references by name. Components
+    /// created or deleted by earlier operations are added or removed as they go.
+    component_list: Vec<ComponentId>,
+    head_components: HashSet<ComponentId>,
+    socket_map: HashMap<si_id::SchemaVariantId, Vec<si_frontend_types::DiagramSocket>>,
+    socket_map_head: HashMap<si_id::SchemaVariantId, Vec<si_frontend_types::DiagramSocket>>,
+    base_change_set_ctx: Option<DalContext>,
+}
+
+async fn apply_operation(
+    ctx: &DalContext,
+    state: &mut BulkState,
+    operation: BulkComponentOperationV1,
+) -> Result<ComponentId, ComponentsError> {
+    match operation {
+        BulkComponentOperationV1::Create {
+            name,
+            schema_name,
+            view_name,
+            attributes,
+            use_working_copy,
+        } => {
+            let (component, variant) = create_component_for_schema_name(
+                ctx,
+                name,
+                &schema_name,
+                view_name,
+                use_working_copy.unwrap_or(false),
+            )
+            .await?;
+            let component_id = component.id();
+
+            if !attributes.is_empty() {
+                dal::update_attributes(ctx, component_id, attributes).await?;
+            }
+
+            let name = component.name(ctx).await?;
+            ctx.write_audit_log(
+                AuditLogKind::CreateComponent {
+                    name: name.clone(),
+                    component_id,
+                    schema_variant_id: variant.id(),
+                    schema_variant_name: variant.display_name().to_string(),
+                },
+                name,
+            )
+            .await?;
+
+            state.component_list.push(component_id);
+            Ok(component_id)
+        }
+        BulkComponentOperationV1::UpdateAttributes {
+            component,
+            attributes,
+        } => {
+            let component_id =
+                resolve_component_reference(ctx, &component, &state.component_list).await?;
+            let name = Component::name_by_id(ctx, component_id).await?;
+
+            dal::update_attributes(ctx, component_id, attributes).await?;
+
+            ctx.write_audit_log(
+                AuditLogKind::UpdateComponent {
+                    component_id,
+                    component_name: name.clone(),
+                },
+                name,
+            )
+            .await?;
+
+            Ok(component_id)
+        }
+        BulkComponentOperationV1::Delete { component } => {
+            let component_id =
+                resolve_component_reference(ctx, &component, &state.component_list).await?;
+
+            let base_change_set_ctx = match state.base_change_set_ctx.take() {
+                Some(base_change_set_ctx) => base_change_set_ctx,
+                None => ctx.clone_with_base().await?,
+            };
+            state
+                .head_components
+                .extend(Component::exists_on_head_by_ids(ctx, &[component_id]).await?);
+            let result = delete::delete_and_process(
+                ctx,
+                false,
+                &state.head_components,
+                &mut state.socket_map,
+                &mut state.socket_map_head,
+                &base_change_set_ctx,
+                component_id,
+            )
+            .await;
+            state.base_change_set_ctx = Some(base_change_set_ctx);
+
+            if result? == ComponentDeletionStatus::Deleted {
+                state.component_list.retain(|id| *id != component_id);
+            }
+
+            Ok(component_id)
+        }
+        BulkComponentOperationV1::AddAction { component, action } => {
+            let component_id =
+                resolve_component_reference(ctx, &component, &state.component_list).await?;
+
+            enqueue_action(ctx, component_id, &action).await?;
+
+            Ok(component_id)
+        }
+        BulkComponentOperationV1::AddToView {
+            component,
+            view_name,
+        } => {
+            let component_id =
+                resolve_component_reference(ctx, &component, &state.component_list).await?;
+
+            // Look up view by name, create if it doesn't exist
+            let view = match View::find_by_name(ctx, &view_name).await? {
+                Some(view) => view,
+                None => View::new(ctx, &view_name).await?,
+            };
+            // Being in the view already is fine, so that requests can be retried
+            match Component::add_to_view(ctx, component_id, view.id(), RawGeometry::default()).await
+            {
+                Ok(_) | Err(ComponentError::ComponentAlreadyInView(_, _)) => Ok(component_id),
+                Err(err) => Err(err.into()),
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(example = json!({
+    "atomic": false,
+    "operations": [
+        {
+            "op": "create",
+            "name": "my-vpc",
+            "schemaName": "AWS::EC2::VPC",
+            "attributes": {"/domain/CidrBlock": "10.0.0.0/16"}
+        },
+        {
+            "op": "updateAttributes",
+            "component": {"component": "my-subnet"},
+            "attributes": {
+                "/domain/VpcId": {"$source": {"component": "my-vpc", "path": "/resource_value/VpcId"}}
+            }
+        },
+        {"op": "addAction", "component": {"component": "my-vpc"}, "action": {"function": "Create Asset"}},
+        {"op": "addToView", "component": {"component": "my-vpc"}, "viewName": "Networking"},
+        {"op": "delete", "component": {"componentId": "01H9ZQD35JPMBGHH69BT0Q79VY"}}
+    ]
+}))]
+pub struct BulkComponentOperationsV1Request {
+    /// The operations to apply, in order. Later operations can refer by name to components
+    /// created by earlier ones.
+    pub operations: Vec<BulkComponentOperationV1>,
+    /// If true, nothing is committed unless every operation succeeds, and the operations after
+    /// the first failure are skipped.
+    #[serde(default)]
+    #[schema(example = false)]
+    pub atomic: bool,
+}
+
+/// A single operation in a bulk request.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum BulkComponentOperationV1 {
+    /// Create a component, like the create component endpoint.
+    #[serde(rename_all = "camelCase")]
+    Create {
+        #[schema(example = "my-vpc")]
+        name: String,
+        #[schema(example = "AWS::EC2::VPC")]
+        schema_name: String,
+        #[schema(example = "Networking")]
+        #[serde(default)]
+        view_name: Option<String>,
+        #[serde(default)]
+        #[schema(value_type = std::collections::BTreeMap<String, serde_json::Value>)]
+        attributes: AttributeSources,
+        #[serde(default)]
+        use_working_copy: Option<bool>,
+    },
+    /// Set or unset attributes of a component.
+    #[serde(rename_all = "camelCase")]
+    UpdateAttributes {
+        component: ComponentReference,
+        #[schema(value_type = std::collections::BTreeMap<String, serde_json::Value>)]
+        attributes: AttributeSources,
+    },
+    /// Delete a component, or mark it for deletion if it exists on HEAD.
+    #[serde(rename_all = "camelCase")]
+    Delete { component: ComponentReference },
+    /// Enqueue an action for a component.
+    #[serde(rename_all = "camelCase")]
+    AddAction {
+        component: ComponentReference,
+        action: ActionReference,
+    },
+    /// Add a component to a view, creating the view if it doesn't exist.
+    #[serde(rename_all = "camelCase")]
+    AddToView {
+        component: ComponentReference,
+        #[schema(example = "Networking")]
+        view_name: String,
+    },
+}
+
+impl BulkComponentOperationV1 {
+    /// Whether the operation can fail after it has started changing the change set. The others
+    /// look up everything that can be missing before making a single change.
+    async fn can_fail_partway(&self, ctx: &DalContext) -> Result<bool, ComponentsError> {
+        Ok(match self {
+            Self::Create { .. } | Self::UpdateAttributes { .. } | Self::Delete { .. } => true,
+            Self::AddAction { .. } => false,
+            // Creating the view is a change of its own
+            Self::AddToView { view_name, .. } => {
+                View::find_by_name(ctx, view_name).await?.is_none()
+            }
+        })
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BulkComponentOperationStatusV1 {
+    Succeeded,
+    Failed,
+    /// Not attempted, because an earlier operation of an atomic request failed.
+    Skipped,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkComponentOperationResultV1 {
+    /// The position of the operation in the request.
+    #[schema(example = 0)]
+    pub index: usize,
+    pub status: BulkComponentOperationStatusV1,
+    /// The component the operation applied to, if it succeeded.
+    #[schema(value_type = Option<String>, example = "01H9ZQD35JPMBGHH69BT0Q79AA")]
+    pub component_id: Option<ComponentId>,
+    /// Why the operation failed, with the status code the equivalent single-component endpoint
+    /// would have returned.
+    pub error: Option<ApiError>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(example = json!({
+    "committed": true,
+    "results": [
+        {"index": 0, "status": "succeeded", "componentId": "01H9ZQD35JPMBGHH69BT0Q79AA", "error": null},
+        {"index": 1, "status": "failed", "componentId": null, "error": {"message": "component not found: my-subnet", "statusCode": 404, "code": null}}
+    ]
+}))]
+pub struct BulkComponentOperationsV1Response {
+    /// Whether the changes made by the operations were committed to the change set.
+    #[schema(example = true)]
+    pub committed: bool,
+    /// One result per operation, in the order of the request.
+    pub results: Vec<BulkComponentOperationResultV1>,
+}
@@ -34,6 +34,7 @@ use crate::{
     service::v1::{
         ComponentViewV1,
         ComponentsError,
+        preconditions::IfMatch,
     },
 };
 
@@ -43,6 +44,7 @@ use crate::{
     params(
         ("workspace_id" = String, Path, description = "Workspace identifier"),
         ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only apply the write if the change set's entity tag still matches one of these, as returned in the `ETag` header when getting the change set"),
     ),
     tag = "components",
     request_body = CreateComponentV1Request,
@@ -51,7 +53,7 @@ use crate::{
         (status = 200, description = "Component created successfully", body = CreateComponentV1Response),
         (status = 401, description = "Unauthorized - Invalid or missing token"),
         (status = 404, description = "Component not found"),
-        (status = 412, description = "Precondition Failed - View not found", body = crate::service::v1::common::ApiError),
+        (status = 412, description = "Precondition Failed - View not found or If-Match entity tag doesn't match", body = crate::service::v1::common::ApiError),
         (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
         (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
     )
@@ -60,6 +62,7 @@ use crate::{
 pub async fn create_component(
     ChangeSetDalContext(ref ctx): ChangeSetDalContext,
     tracker: PosthogEventTracker,
+    if_match: IfMatch,
     payload: Result<Json<CreateComponentV1Request>, axum::extract::rejection::JsonRejection>,
 ) -> Result<Json<CreateComponentV1Response>, ComponentsError> {
     let Json(payload) = payload?;
@@ -67,61 +70,18 @@ pub async fn create_component(
     if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
         return Err(ComponentsError::NotPermittedOnHead);
     }
+    if_match.check_change_set(ctx)?;
 
-    let schema_id =
-        match CachedModule::find_latest_for_schema_name(ctx, payload.schema_name.as_str()).await? {
-            Some(module) => module.schema_id,
-            None => match Schema::get_by_name_opt(ctx, payload.schema_name.as_str()).await? {
-                Some(schema) => schema.id(),
-                None => return Err(ComponentsError::SchemaNameNotFound(payload.schema_name)),
-            },
-        };
-    // Ensure that the schema is installed, get the default variant id
-    let mut variant_id = Schema::get_or_install_default_variant(ctx, schema_id).await?;
-
-    // Determine which variant to use based on use_working_copy flag
-    if payload.use_working_copy.unwrap_or(false) {
-        // User wants to use the unlocked (working copy) variant
-        match SchemaVariant::get_unlocked_for_schema(ctx, schema_id).await? {
-            Some(unlocked_variant) => {
-                // An unlocked variant already exists, use it
-                variant_id = unlocked_variant.id();
-            }
-            None => {
-                // No unlocked variant exists, so we should throw an error
-                return Err(ComponentsError::NoWorkingCopy(schema_id));
-            }
-        }
-    };
-
-    let variant = SchemaVariant::get_by_id(ctx, variant_id).await?;
-
-    let view_id: ViewId;
-    if let Some(view_name) = payload.view_name {
-        if let Some(view) = View::find_by_name(ctx, view_name.as_str()).await? {
-            view_id = view.id();
-        } else {
-            let view = View::new(ctx, view_name.as_str()).await?;
-            view_id = view.id()
-        }
-    } else {
-        let default_view = View::get_id_for_default(ctx).await?;
-        view_id = default_view
-    };
-
-    let mut component = Component::new(ctx, payload.name, variant_id, view_id).await?;
+    let (component, variant) = create_component_for_schema_name(
+        ctx,
+        payload.name,
+        &payload.schema_name,
+        payload.view_name,
+        payload.use_working_copy.unwrap_or(false),
+    )
+    .await?;
+    let variant_id = variant.id();
     let comp_name = component.name(ctx).await?;
-    let initial_geometry = component.geometry(ctx, view_id).await?;
-    component
-        .set_geometry(
-            ctx,
-            view_id,
-            0,
-            0,
-            initial_geometry.width(),
-            initial_geometry.height(),
-        )
-        .await?;
 
     tracker.track(
         ctx,
@@ -190,13 +150,79 @@ pub async fn create_component(
         }),
     );
 
-    ctx.commit().await?;
+    if_match.commit(ctx).await?;
 
     Ok(Json(CreateComponentV1Response {
         component: ComponentViewV1::assemble(ctx, component.id()).await?,
     }))
 }
 
+/// Creates a component from the default variant of the named schema (or its working copy), in
+/// the named view or the default one. Views that don't exist yet are created.
+#[allow(deprecated)]
+pub(super) async fn create_component_for_schema_name(
+    ctx: &dal::DalContext,
+    name: String,
+    schema_name: &str,
+    view_name: Option<String>,
+    use_working_copy: bool,
+) -> Result<(Component, SchemaVariant), ComponentsError> {
+    let schema_id = match CachedModule::find_latest_for_schema_name(ctx, schema_name).await? {
+        Some(module) => module.schema_id,
+        None => match Schema::get_by_name_opt(ctx, schema_name).await? {
+            Some(schema) => schema.id(),
+            None => return Err(ComponentsError::SchemaNameNotFound(schema_name.to_owned())),
+        },
+    };
+    // Ensure that the schema is installed, get the default variant id
+    let mut variant_id = Schema::get_or_install_default_variant(ctx, schema_id).await?;
+
+    // Determine which variant to use based on use_working_copy flag
+    if use_working_copy {
+        // User wants to use the unlocked (working copy) variant
+        match SchemaVariant::get_unlocked_for_schema(ctx, schema_id).await? {
+            Some(unlocked_variant) => {
+                // An unlocked variant already exists, use it
+                variant_id = unlocked_variant.id();
+            }
+            None => {
+                // No unlocked variant exists, so we should throw an error
+                return Err(ComponentsError::NoWorkingCopy(schema_id));
+            }
+        }
+    };
+
+    let variant = SchemaVariant::get_by_id(ctx, variant_id).await?;
+
+    let view_id: ViewId;
+    if let Some(view_name) = view_name {
+        if let Some(view) = View::find_by_name(ctx, view_name.as_str()).await? {
+            view_id = view.id();
+        } else {
+            let view = View::new(ctx, view_name.as_str()).await?;
+            view_id = view.id()
+        }
+    } else {
+        let default_view = View::get_id_for_default(ctx).await?;
+        view_id = default_view
+    };
+
+    let mut component = Component::new(ctx, name, variant_id, view_id).await?;
+    let initial_geometry = component.geometry(ctx, view_id).await?;
+    component
+        .set_geometry(
+            ctx,
+            view_id,
+            0,
+            0,
+            initial_geometry.width(),
+            initial_geometry.height(),
+        )
+        .await?;
+
+    Ok((component, variant))
+}
+
 #[derive(Deserialize, Serialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateComponentV1Request {
@@ -28,7 +28,10 @@ use crate::{
         PosthogEventTracker,
         change_set::ChangeSetDalContext,
     },
-    service::v1::ComponentsError,
+    service::v1::{
+        ComponentsError,
+        preconditions::IfMatch,
+    },
 };
 
 #[utoipa::path(
@@ -37,7 +40,8 @@ use crate::{
     params(
         ("workspace_id" = String, Path, description = "Workspace identifier"),
         ("change_set_id" = String, Path, description = "Change Set identifier"),
-        ("component_id" = String, Path, description = "Component identifier")
+        ("component_id" = String, Path, description = "Component identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only apply the write if the component's entity tag (or the change set's) still matches one of these, as returned in the `ETag` header when getting the component or change set")
     ),
     tag = "components",
     summary = "Delete a component",
@@ -45,17 +49,20 @@ use crate::{
         (status = 200, description = "Component deleted successfully", body = DeleteComponentV1Response),
         (status = 401, description = "Unauthorized - Invalid or missing token"),
         (status = 404, description = "Component not found"),
+        (status = 412, description = "Precondition failed - If-Match entity tag doesn't match", body = crate::service::v1::common::ApiError),
         (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
     )
 )]
 pub async fn delete_component(
     ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
     tracker: PosthogEventTracker,
+    if_match: IfMatch,
     Path(ComponentV1RequestPath { component_id }): Path<ComponentV1RequestPath>,
 ) -> Result<Json<DeleteComponentV1Response>, ComponentsError> {
     if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
         return Err(ComponentsError::NotPermittedOnHead);
     }
+    if_match.check_component(ctx, component_id).await?;
 
     let head_components: HashSet<ComponentId> =
         Component::exists_on_head_by_ids(ctx, &[component_id]).await?;
@@ -87,7 +94,7 @@ pub async fn delete_component(
         }),
     );
 
-    ctx.commit().await?;
+    if_match.commit(ctx).await?;
 
     Ok(Json(DeleteComponentV1Response {
         status: match status {
@@ -2,12 +2,14 @@ use std::collections::HashMap;
 
 use axum::{
     extract::Path,
+    http::HeaderName,
     response::Json,
 };
 use dal::{
     ActionPrototypeId,
     Component,
 };
+use sdf_extract::FriggStore;
 use serde::Serialize;
 use serde_json::json;
 use si_id::ManagementPrototypeId;
@@ -21,9 +23,15 @@ use super::{
     ComponentViewV1,
     ComponentsError,
 };
-use crate::extract::{
-    PosthogEventTracker,
-    change_set::ChangeSetDalContext,
+use crate::{
+    extract::{
+        PosthogEventTracker,
+        change_set::ChangeSetDalContext,
+    },
+    service::v1::preconditions::{
+        component_entity_tag,
+        entity_tag_header,
+    },
 };
 
 #[derive(Serialize, Debug, ToSchema)]
@@ -97,7 +105,9 @@ pub struct GetComponentV1ResponseActionFunction {
     tag = "components",
     summary = "Get a component by component Id",
     responses(
-        (status = 200, description = "Component retrieved successfully", body = GetComponentV1Response),
+        (status = 200, description = "Component retrieved successfully", body = GetComponentV1Response, headers(
+            ("ETag" = String, description = "The component's entity tag, for use in If-Match preconditions")
+        )),
         (status = 401, description = "Unauthorized - Invalid or missing token"),
         (status = 404, description = "Component not found"),
         (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
@@ -105,9 +115,10 @@ pub struct GetComponentV1ResponseActionFunction {
 )]
 pub async fn get_component(
     ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    FriggStore(ref frigg): FriggStore,
     tracker: PosthogEventTracker,
     Path(ComponentV1RequestPath { component_id }): Path<ComponentV1RequestPath>,
-) -> Result<Json<GetComponentV1Response>, ComponentsError> {
+) -> Result<([(HeaderName, String); 1], Json<GetComponentV1Response>), ComponentsError> {
     let (management_functions, action_functions) =
         super::get_component_functions(ctx, component_id).await?;
 
@@ -119,11 +130,16 @@ pub async fn get_component(
         }),
     );
 
-    Ok(Json(GetComponentV1Response {
-        component: ComponentViewV1::assemble(ctx, component_id).await?,
-        management_functions,
-        action_functions,
-    }))
+    let entity_tag = component_entity_tag(ctx, frigg, component_id).await?;
+
+    Ok((
+        entity_tag_header(&entity_tag),
+        Json(GetComponentV1Response {
+            component: ComponentViewV1::assemble(ctx, component_id).await?,
+            management_functions,
+            action_functions,
+        }),
+    ))
 }
 
 pub async fn into_front_end_type(
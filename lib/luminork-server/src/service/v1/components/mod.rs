@@ -1,4 +1,7 @@
-use std::collections::VecDeque;
+use std::collections::{
+    BTreeMap,
+    VecDeque,
+};
 
 use axum::{
     Router,
@@ -71,6 +74,7 @@ use crate::AppState;
 
 pub mod add_action;
 pub mod add_to_view;
+pub mod bulk_operations;
 pub mod create_component;
 pub mod delete_component;
 pub mod duplicate_components;
@@ -161,6 +165,8 @@ pub enum ComponentsError {
     NoWorkingCopy(SchemaId),
     #[error("output socket error: {0}")]
     OutputSocket(#[from] dal::socket::output::OutputSocketError),
+    #[error("{0}")]
+    Precondition(#[from] crate::service::v1::preconditions::PreconditionError),
     #[error("prop error: {0}")]
     Prop(#[from] dal::prop::PropError),
     #[error("qualification error: {0}")]
@@ -251,6 +257,9 @@ impl crate::service::v1::common::ErrorIntoResponse for ComponentsError {
             ComponentsError::Component(dal::ComponentError::NotFound(_)) => {
                 (StatusCode::NOT_FOUND, self.to_string())
             }
+            ComponentsError::Component(dal::ComponentError::InvalidLabelKey(_)) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
             ComponentsError::ComponentNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
             ComponentsError::ComponentNotRestorable(_) => {
                 (StatusCode::PRECONDITION_FAILED, self.to_string())
@@ -286,6 +295,9 @@ impl crate::service::v1::common::ErrorIntoResponse for ComponentsError {
                 (StatusCode::PRECONDITION_FAILED, self.to_string())
             }
             ComponentsError::Validation(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            ComponentsError::Precondition(
+                crate::service::v1::preconditions::PreconditionError::Failed(_),
+            ) => (StatusCode::PRECONDITION_FAILED, self.to_string()),
             ComponentsError::InvalidSecretValue(_) => {
                 (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
             }
@@ -481,6 +493,12 @@ pub struct ComponentViewV1 {
     // The secretId that the component is connected to
     #[schema(value_type = String)]
     pub secret_id: Option<SecretId>,
+    // key/value labels used to route reviews and reports
+    #[schema(value_type = std::collections::BTreeMap<String, String>, example = json!({"team": "payments"}))]
+    pub labels: BTreeMap<String, String>,
+    // the team or user that owns the component
+    #[schema(example = "payments")]
+    pub owner: Option<String>,
 
     #[schema(
         value_type = std::collections::BTreeMap<String, serde_json::Value>,
@@ -714,6 +732,8 @@ impl ComponentViewV1 {
             views,
             attributes,
             secret_id,
+            labels: component.labels().clone(),
+            owner: component.owner().map(ToOwned::to_owned),
         };
         Ok(result)
     }
@@ -730,6 +750,7 @@ pub fn routes() -> Router<AppState> {
             post(duplicate_components::duplicate_components),
         )
         .route("/add_to_view", post(add_to_view::add_to_view))
+        .route("/bulk", post(bulk_operations::bulk_operations))
         .nest(
             "/:component_id",
             Router::new()
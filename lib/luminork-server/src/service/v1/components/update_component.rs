@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
 
 use axum::{
     extract::Path,
@@ -43,6 +46,7 @@ use crate::{
     service::v1::{
         ComponentsError,
         components::get_component::into_front_end_type,
+        preconditions::IfMatch,
     },
 };
 
@@ -52,7 +56,8 @@ use crate::{
     params(
         ("workspace_id" = String, Path, description = "Workspace identifier"),
         ("change_set_id" = String, Path, description = "Change Set identifier"),
-        ("component_id" = String, Path, description = "Component identifier")
+        ("component_id" = String, Path, description = "Component identifier"),
+        ("If-Match" = Option<String>, Header, description = "Only apply the write if the component's entity tag (or the change set's) still matches one of these, as returned in the `ETag` header when getting the component or change set")
     ),
     tag = "components",
     summary = "Update a component",
@@ -61,7 +66,7 @@ use crate::{
         (status = 200, description = "Component updated successfully", body = UpdateComponentV1Response),
         (status = 401, description = "Unauthorized - Invalid or missing token"),
         (status = 404, description = "Component not found"),
-        (status = 412, description = "Precondition failed - Duplicate component name"),
+        (status = 412, description = "Precondition failed - Duplicate component name or If-Match entity tag doesn't match"),
         (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
         (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
     )
@@ -69,6 +74,7 @@ use crate::{
 pub async fn update_component(
     ChangeSetDalContext(ref ctx): ChangeSetDalContext,
     tracker: PosthogEventTracker,
+    if_match: IfMatch,
     Path(ComponentV1RequestPath { component_id }): Path<ComponentV1RequestPath>,
     payload: Result<Json<UpdateComponentV1Request>, axum::extract::rejection::JsonRejection>,
 ) -> Result<Json<UpdateComponentV1Response>, ComponentsError> {
@@ -77,6 +83,7 @@ pub async fn update_component(
     if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
         return Err(ComponentsError::NotPermittedOnHead);
     }
+    if_match.check_component(ctx, component_id).await?;
 
     let component = Component::get_by_id(ctx, component_id).await?;
 
@@ -134,6 +141,21 @@ pub async fn update_component(
         }
     }
 
+    if let Some(labels) = payload.labels {
+        Component::get_by_id(ctx, component_id)
+            .await?
+            .set_labels(ctx, labels)
+            .await?;
+    }
+
+    if let Some(owner) = payload.owner {
+        let owner = Some(owner).filter(|owner| !owner.is_empty());
+        Component::get_by_id(ctx, component_id)
+            .await?
+            .set_owner(ctx, owner)
+            .await?;
+    }
+
     if let Some(resource_id) = payload.resource_id {
         let resource_prop_path = ["root", "si", "resourceId"];
         let resource_prop_id =
@@ -179,7 +201,7 @@ pub async fn update_component(
         }),
     );
 
-    ctx.commit().await?;
+    if_match.commit(ctx).await?;
 
     Ok(Json(UpdateComponentV1Response {
         component: ComponentViewV1::assemble(ctx, component_id).await?,
@@ -195,6 +217,14 @@ pub struct UpdateComponentV1Request {
     #[schema(example = "i-12345678")]
     pub resource_id: Option<String>,
 
+    /// Replaces the component's labels.
+    #[schema(value_type = Option<std::collections::BTreeMap<String, String>>, example = json!({"team": "payments"}))]
+    pub labels: Option<BTreeMap<String, String>>,
+
+    /// Sets the team or user that owns the component. An empty string clears the owner.
+    #[schema(example = "payments")]
+    pub owner: Option<String>,
+
     #[serde(default)]
     #[schema(example = json!({"secretDefinitionName": "secretId", "secretDefinitionName": "secretName"}))]
     pub secrets: HashMap<SecretPropKey, serde_json::Value>,
@@ -237,6 +267,8 @@ pub struct UpdateComponentV1Response {
         "resourceId": "i-1234567890abcdef0",
         "toDelete": false,
         "canBeUpgraded": true,
+        "labels": {"team": "payments"},
+        "owner": "payments",
         "connections": [],
         "views": [
             {
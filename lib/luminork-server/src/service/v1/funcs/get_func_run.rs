@@ -107,6 +107,12 @@ use crate::{
                 ],
                 "finalized": true
               },
+              "resourceUsage": {
+                "queuedMs": 12,
+                "executionMs": 3698,
+                "cpuTimeMs": 840,
+                "peakMemoryBytes": 98566144
+              },
               "createdAt": "2025-03-27T19:41:58.493298051Z",
               "updatedAt": "2025-03-27T19:42:02.192033089Z"
             }
@@ -92,6 +92,48 @@ pub async fn update_func(
         );
     }
 
+    if let Some(memoization_enabled) = payload
+        .memoization_enabled
+        .filter(|enabled| *enabled != func.memoization_enabled)
+    {
+        let updated_func =
+            FuncAuthoringClient::set_memoization_enabled(ctx, func_id, memoization_enabled).await?;
+
+        tracker.track(
+            ctx,
+            "api_update_func_memoization",
+            serde_json::json!({
+                "func_id": func_id,
+                "func_name": updated_func.name.clone(),
+                "memoization_enabled": memoization_enabled,
+            }),
+        );
+    }
+
+    if let Some(limits) = payload.resource_limits.filter(|limits| {
+        limits.max_memory_bytes != func.max_memory_bytes
+            || limits.max_cpu_time_ms != func.max_cpu_time_ms
+    }) {
+        let updated_func = FuncAuthoringClient::set_resource_limits(
+            ctx,
+            func_id,
+            limits.max_memory_bytes,
+            limits.max_cpu_time_ms,
+        )
+        .await?;
+
+        tracker.track(
+            ctx,
+            "api_update_func_resource_limits",
+            serde_json::json!({
+                "func_id": func_id,
+                "func_name": updated_func.name.clone(),
+                "max_memory_bytes": limits.max_memory_bytes,
+                "max_cpu_time_ms": limits.max_cpu_time_ms,
+            }),
+        );
+    }
+
     FuncAuthoringClient::save_code(ctx, func_id, payload.code).await?;
     tracker.track(
         ctx,
@@ -117,6 +159,27 @@ pub struct UpdateFuncV1Request {
     pub description: Option<String>,
     #[schema(value_type = String, example = "<!-- String escaped Typescript code here -->")]
     pub code: String,
+    /// Set to true for funcs that are deterministic, so that their results are reused for later
+    /// executions with the same arguments instead of running the func again
+    #[serde(default)]
+    #[schema(value_type = Option<bool>, example = false)]
+    pub memoization_enabled: Option<bool>,
+    /// Replaces the limits on the resources a single execution of the func may consume
+    #[serde(default)]
+    pub resource_limits: Option<UpdateFuncResourceLimitsV1>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFuncResourceLimitsV1 {
+    /// The maximum memory an execution may use, in bytes. Unlimited when null
+    #[serde(default)]
+    #[schema(value_type = Option<u64>, example = 268435456)]
+    pub max_memory_bytes: Option<u64>,
+    /// The maximum CPU time an execution may use, in milliseconds. Unlimited when null
+    #[serde(default)]
+    #[schema(value_type = Option<u64>, example = 60000)]
+    pub max_cpu_time_ms: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Debug, ToSchema)]
@@ -0,0 +1,301 @@
+use axum::{
+    Json,
+    Router,
+    extract::{
+        Path,
+        rejection::JsonRejection,
+    },
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response,
+    },
+    routing::{
+        delete,
+        get,
+        post,
+        put,
+    },
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use sdf_extract::workspace::WorkspaceDalContext;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use si_crypto::{
+    PackageSigningError,
+    PackageVerifyingKey,
+};
+use si_db::{
+    ModuleTrustStore,
+    ModuleTrustStoreError,
+    TrustedPublisherKey,
+};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    extract::PosthogEventTracker,
+    service::v1::common::ErrorIntoResponse,
+};
+
+pub type ModuleTrustResult<T> = Result<T, ModuleTrustError>;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ModuleTrustError {
+    #[error("invalid publisher key: {0}")]
+    InvalidPublisherKey(#[from] PackageSigningError),
+    #[error("module trust store error: {0}")]
+    ModuleTrustStore(#[from] ModuleTrustStoreError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+    #[error("trusted publisher key not found: {0}")]
+    TrustedKeyNotFound(String),
+    #[error("validation error: {0}")]
+    Validation(String),
+}
+
+impl ErrorIntoResponse for ModuleTrustError {
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            ModuleTrustError::InvalidPublisherKey(_) | ModuleTrustError::Validation(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            ModuleTrustError::TrustedKeyNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for ModuleTrustError {
+    fn into_response(self) -> Response {
+        self.to_api_response()
+    }
+}
+
+impl From<JsonRejection> for ModuleTrustError {
+    fn from(rejection: JsonRejection) -> Self {
+        ModuleTrustError::Validation(format!("Invalid JSON: {rejection}"))
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct TrustedPublisherKeyV1RequestPath {
+    pub key_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedPublisherKeyV1 {
+    #[schema(example = "4c2b1e0f9a...")]
+    pub key_id: String,
+    /// The base64 encoded ed25519 public key.
+    #[schema(example = "m3Rz0m0dVx1U3l0ZzJmY2ZkYjY0ZTUxZTFhNTk4ZjAwNzA")]
+    pub public_key: String,
+    #[schema(example = "System Initiative")]
+    pub label: Option<String>,
+    #[schema(value_type = String, example = "2025-01-01T00:00:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TrustedPublisherKey> for TrustedPublisherKeyV1 {
+    fn from(value: TrustedPublisherKey) -> Self {
+        Self {
+            key_id: value.key_id,
+            public_key: value.public_key,
+            label: value.label,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetModuleTrustV1Response {
+    /// Whether unsigned modules and modules signed by untrusted keys are refused.
+    pub strict: bool,
+    pub trusted_keys: Vec<TrustedPublisherKeyV1>,
+}
+
+impl From<ModuleTrustStore> for GetModuleTrustV1Response {
+    fn from(value: ModuleTrustStore) -> Self {
+        Self {
+            strict: value.strict,
+            trusted_keys: value.trusted_keys.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateModuleTrustV1Request {
+    pub strict: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AddTrustedPublisherKeyV1Request {
+    /// The base64 encoded ed25519 public key of the publisher.
+    pub public_key: String,
+    pub label: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveTrustedPublisherKeyV1Response {
+    pub success: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/module-trust",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+    ),
+    tag = "module_trust",
+    summary = "Get the publisher keys a workspace trusts to sign the modules installed into it",
+    responses(
+        (status = 200, description = "Trust store retrieved successfully", body = GetModuleTrustV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn get_module_trust(
+    WorkspaceDalContext(ref ctx): WorkspaceDalContext,
+) -> ModuleTrustResult<Json<GetModuleTrustV1Response>> {
+    let trust_store = ModuleTrustStore::get(ctx).await?;
+
+    Ok(Json(trust_store.into()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/w/{workspace_id}/module-trust",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+    ),
+    tag = "module_trust",
+    summary = "Set whether a workspace refuses unsigned and untrusted modules",
+    request_body = UpdateModuleTrustV1Request,
+    responses(
+        (status = 200, description = "Trust store updated successfully", body = GetModuleTrustV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn update_module_trust(
+    WorkspaceDalContext(ref ctx): WorkspaceDalContext,
+    tracker: PosthogEventTracker,
+    payload: Result<Json<UpdateModuleTrustV1Request>, JsonRejection>,
+) -> ModuleTrustResult<Json<GetModuleTrustV1Response>> {
+    let Json(payload) = payload?;
+
+    ModuleTrustStore::set_strict(ctx, payload.strict).await?;
+
+    tracker.track(
+        ctx,
+        "api_update_module_trust",
+        json!({ "strict": payload.strict }),
+    );
+
+    let trust_store = ModuleTrustStore::get(ctx).await?;
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(trust_store.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/module-trust/keys",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+    ),
+    tag = "module_trust",
+    summary = "Trust a publisher key to sign the modules installed into a workspace",
+    request_body = AddTrustedPublisherKeyV1Request,
+    responses(
+        (status = 200, description = "Publisher key trusted successfully", body = TrustedPublisherKeyV1),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 422, description = "Validation error - Invalid publisher key", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn add_trusted_publisher_key(
+    WorkspaceDalContext(ref ctx): WorkspaceDalContext,
+    tracker: PosthogEventTracker,
+    payload: Result<Json<AddTrustedPublisherKeyV1Request>, JsonRejection>,
+) -> ModuleTrustResult<Json<TrustedPublisherKeyV1>> {
+    let Json(payload) = payload?;
+
+    let verifying_key = PackageVerifyingKey::from_base64(&payload.public_key)?;
+    let key = ModuleTrustStore::add_key(
+        ctx,
+        verifying_key.key_id(),
+        verifying_key.to_base64(),
+        payload.label,
+    )
+    .await?;
+
+    tracker.track(
+        ctx,
+        "api_add_trusted_publisher_key",
+        json!({ "key_id": key.key_id }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(key.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/w/{workspace_id}/module-trust/keys/{key_id}",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("key_id" = String, Path, description = "Publisher key identifier"),
+    ),
+    tag = "module_trust",
+    summary = "Stop trusting a publisher key",
+    responses(
+        (status = 200, description = "Publisher key removed successfully", body = RemoveTrustedPublisherKeyV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Publisher key not trusted", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn remove_trusted_publisher_key(
+    WorkspaceDalContext(ref ctx): WorkspaceDalContext,
+    tracker: PosthogEventTracker,
+    Path(TrustedPublisherKeyV1RequestPath { key_id }): Path<TrustedPublisherKeyV1RequestPath>,
+) -> ModuleTrustResult<Json<RemoveTrustedPublisherKeyV1Response>> {
+    if !ModuleTrustStore::remove_key(ctx, &key_id).await? {
+        return Err(ModuleTrustError::TrustedKeyNotFound(key_id));
+    }
+
+    tracker.track(
+        ctx,
+        "api_remove_trusted_publisher_key",
+        json!({ "key_id": key_id }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(RemoveTrustedPublisherKeyV1Response { success: true }))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_module_trust))
+        .route("/", put(update_module_trust))
+        .route("/keys", post(add_trusted_publisher_key))
+        .route("/keys/:key_id", delete(remove_trusted_publisher_key))
+}
@@ -0,0 +1,304 @@
+//! `If-Match` preconditions, so that clients can make sure a write doesn't overwrite changes they
+//! haven't seen.
+//!
+//! A change set's entity tag is the address of its workspace snapshot, so it changes with every
+//! write to the change set. A component's entity tag is the checksum of its materialized view, so
+//! it only changes when the component does.
+//!
+//! Checking a precondition and committing the write aren't one step, so writes with preconditions
+//! are committed with [`IfMatch::commit`], which has the rebaser refuse the write if the change
+//! set was written to in between.
+
+use std::convert::Infallible;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{
+        HeaderMap,
+        HeaderName,
+        header,
+        request::Parts,
+    },
+};
+use dal::{
+    ChangeSet,
+    ComponentId,
+    DalContext,
+    TransactionsError,
+    slow_rt,
+};
+use si_events::WorkspaceSnapshotAddress;
+use si_frontend_mv_types::{
+    checksum::FrontendChecksum,
+    index::change_set::ChangeSetMvIndexVersion,
+    reference::ReferenceKind,
+};
+use thiserror::Error;
+
+use crate::AppState;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum PreconditionError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] dal::ChangeSetError),
+    #[error("If-Match precondition failed, the current entity tag is \"{0}\"")]
+    Failed(String),
+    #[error("frigg error: {0}")]
+    Frigg(#[from] frigg::Error),
+    #[error("join error: {0}")]
+    Join(#[from] tokio::task::JoinError),
+    #[error("materialized views error: {0}")]
+    MaterializedViews(#[from] dal_materialized_views::Error),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("slow runtime error: {0}")]
+    SlowRuntime(#[from] dal::slow_rt::SlowRuntimeError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+}
+
+pub type PreconditionResult<T> = Result<T, PreconditionError>;
+
+/// The entity tags from the `If-Match` header, if there is one. Without the header, every
+/// precondition holds.
+#[derive(Clone, Debug)]
+pub struct IfMatch {
+    entity_tags: Option<Vec<String>>,
+    frigg: frigg::FriggStore,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for IfMatch {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            entity_tags: parse_entity_tags(&parts.headers),
+            frigg: state.frigg().clone(),
+        })
+    }
+}
+
+impl IfMatch {
+    fn matches(&self, entity_tag: &str) -> bool {
+        entity_tags_match(self.entity_tags.as_deref(), entity_tag)
+    }
+
+    /// Checks that the change set hasn't changed since the client fetched it.
+    pub fn check_change_set(&self, ctx: &DalContext) -> PreconditionResult<()> {
+        if self.entity_tags.is_none() {
+            return Ok(());
+        }
+
+        let entity_tag = change_set_entity_tag(ctx)?;
+        if self.matches(&entity_tag) {
+            Ok(())
+        } else {
+            Err(PreconditionError::Failed(entity_tag))
+        }
+    }
+
+    /// Checks that the component hasn't changed since the client fetched it. The change set's
+    /// entity tag is accepted too.
+    pub async fn check_component(
+        &self,
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> PreconditionResult<()> {
+        if self.entity_tags.is_none() || self.matches(&change_set_entity_tag(ctx)?) {
+            return Ok(());
+        }
+
+        let entity_tag = component_entity_tag(ctx, &self.frigg, component_id).await?;
+        if self.matches(&entity_tag) {
+            Ok(())
+        } else {
+            Err(PreconditionError::Failed(entity_tag))
+        }
+    }
+
+    /// Commits the changes made by the request. With an `If-Match` header, the changes are only
+    /// written if the change set hasn't changed since `ctx` was built, so that nothing can be
+    /// written between checking the preconditions and committing. The client can retry if the
+    /// precondition fails here, as it is checked again against the current change set.
+    pub async fn commit(&self, ctx: &DalContext) -> PreconditionResult<()> {
+        if self.entity_tags.is_none() {
+            ctx.commit().await?;
+            return Ok(());
+        }
+
+        let expected_snapshot_address = ctx.change_set()?.workspace_snapshot_address;
+        match ctx.commit_if_unchanged(expected_snapshot_address).await {
+            Ok(()) => Ok(()),
+            Err(err @ TransactionsError::RebaseFailed(..)) => {
+                match moved_snapshot_address(ctx, expected_snapshot_address).await? {
+                    Some(snapshot_address) => {
+                        Err(PreconditionError::Failed(snapshot_address.to_string()))
+                    }
+                    None => Err(err.into()),
+                }
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// The entity tags from the `If-Match` headers, or `None` if there aren't any.
+fn parse_entity_tags(headers: &HeaderMap) -> Option<Vec<String>> {
+    let mut entity_tags: Option<Vec<String>> = None;
+    for value in headers.get_all(header::IF_MATCH) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        entity_tags.get_or_insert_with(Vec::new).extend(
+            value
+                .split(',')
+                .map(|entity_tag| entity_tag.trim())
+                .filter(|entity_tag| !entity_tag.is_empty())
+                .map(|entity_tag| {
+                    // Weak entity tags are compared as if they were strong
+                    let entity_tag = entity_tag.strip_prefix("W/").unwrap_or(entity_tag);
+                    entity_tag.trim_matches('"').to_owned()
+                }),
+        );
+    }
+
+    entity_tags
+}
+
+fn entity_tags_match(entity_tags: Option<&[String]>, entity_tag: &str) -> bool {
+    match entity_tags {
+        Some(entity_tags) => entity_tags
+            .iter()
+            .any(|candidate| candidate == "*" || candidate == entity_tag),
+        None => true,
+    }
+}
+
+/// The address the change set points at now, if it has moved on from `expected_snapshot_address`.
+async fn moved_snapshot_address(
+    ctx: &DalContext,
+    expected_snapshot_address: WorkspaceSnapshotAddress,
+) -> PreconditionResult<Option<WorkspaceSnapshotAddress>> {
+    // A failed commit leaves the connections of `ctx` unusable
+    let mut ctx = ctx.clone();
+    ctx.restart_connections().await?;
+    let change_set = ChangeSet::get_by_id(&ctx, ctx.change_set_id()).await?;
+
+    Ok(
+        (change_set.workspace_snapshot_address != expected_snapshot_address)
+            .then_some(change_set.workspace_snapshot_address),
+    )
+}
+
+/// The `ETag` header for an entity tag.
+pub fn entity_tag_header(entity_tag: &str) -> [(HeaderName, String); 1] {
+    [(header::ETAG, format!("\"{entity_tag}\""))]
+}
+
+/// The entity tag of the change set `ctx` points at, without quotes.
+pub fn change_set_entity_tag(ctx: &DalContext) -> PreconditionResult<String> {
+    Ok(ctx.change_set()?.workspace_snapshot_address.to_string())
+}
+
+/// The entity tag of a component, without quotes. This is the checksum of the materialized view
+/// built for the component, which is only assembled again if the change set's index isn't up to
+/// date with the change set yet.
+pub async fn component_entity_tag(
+    ctx: &DalContext,
+    frigg: &frigg::FriggStore,
+    component_id: ComponentId,
+) -> PreconditionResult<String> {
+    if let Some(checksum) = indexed_component_checksum(ctx, frigg, component_id).await? {
+        return Ok(checksum);
+    }
+
+    let component = slow_rt::spawn(dal_materialized_views::component::assemble(
+        ctx.clone(),
+        component_id,
+    ))?
+    .await??;
+
+    Ok(component.checksum().to_string())
+}
+
+async fn indexed_component_checksum(
+    ctx: &DalContext,
+    frigg: &frigg::FriggStore,
+    component_id: ComponentId,
+) -> PreconditionResult<Option<String>> {
+    let change_set = ctx.change_set()?;
+    let Some((index, _)) = frigg
+        .get_change_set_index(ctx.workspace_pk()?, change_set.id)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let (snapshot_address, mv_list) = match serde_json::from_value(index.data)? {
+        ChangeSetMvIndexVersion::V1(index) => (index.snapshot_address, index.mv_list),
+        ChangeSetMvIndexVersion::V2(index) => (index.snapshot_address, index.mv_list),
+    };
+    if snapshot_address != change_set.workspace_snapshot_address.to_string() {
+        return Ok(None);
+    }
+
+    let kind: &'static str = ReferenceKind::Component.into();
+    let id = component_id.to_string();
+    Ok(mv_list
+        .into_iter()
+        .find(|reference| reference.kind == kind && reference.id == id)
+        .map(|reference| reference.checksum))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use pretty_assertions_sorted::assert_eq;
+
+    use super::*;
+
+    fn headers(values: &[&'static str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(header::IF_MATCH, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn without_header_everything_matches() {
+        let entity_tags = parse_entity_tags(&HeaderMap::new());
+        assert_eq!(None, entity_tags);
+        assert!(entity_tags_match(entity_tags.as_deref(), "abc"));
+    }
+
+    #[test]
+    fn parses_lists_across_headers() {
+        let entity_tags = parse_entity_tags(&headers(&["\"abc\", W/\"def\"", "\"ghi\""]));
+        assert_eq!(
+            Some(vec!["abc".to_owned(), "def".to_owned(), "ghi".to_owned()]),
+            entity_tags
+        );
+        assert!(entity_tags_match(entity_tags.as_deref(), "def"));
+        assert!(entity_tags_match(entity_tags.as_deref(), "ghi"));
+        assert!(!entity_tags_match(entity_tags.as_deref(), "xyz"));
+    }
+
+    #[test]
+    fn wildcard_matches_anything() {
+        let entity_tags = parse_entity_tags(&headers(&["*"]));
+        assert!(entity_tags_match(entity_tags.as_deref(), "abc"));
+    }
+
+    #[test]
+    fn empty_header_matches_nothing() {
+        let entity_tags = parse_entity_tags(&headers(&[""]));
+        assert_eq!(Some(vec![]), entity_tags);
+        assert!(!entity_tags_match(entity_tags.as_deref(), "abc"));
+    }
+}
@@ -0,0 +1,310 @@
+use axum::{
+    Json,
+    Router,
+    extract::rejection::JsonRejection,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response,
+    },
+    routing::post,
+};
+use dal::{
+    ChangeSet,
+    ComponentId,
+    DalContext,
+    WsEvent,
+    promotion::{
+        self,
+        PromotedComponent,
+        PromotionError,
+        PromotionMatching,
+        PromotionOverrides,
+    },
+};
+use sdf_core::change_set_mvs::create_index_for_new_change_set_and_watch;
+use sdf_extract::{
+    EddaClient as EddaClientExtractor,
+    FriggStore,
+    workspace::WorkspaceDalContext,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use si_events::audit_log::AuditLogKind;
+use si_pkg::ComponentSpec;
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::{
+    AppState,
+    api_types::change_sets::v1::ChangeSetViewV1,
+    extract::PosthogEventTracker,
+    service::v1::common::ErrorIntoResponse,
+};
+
+pub type PromotionsResult<T> = Result<T, PromotionsError>;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum PromotionsError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] dal::ChangeSetError),
+    #[error("edda client error: {0}")]
+    EddaClient(#[from] sdf_core::EddaClientError),
+    #[error("frigg error: {0}")]
+    Frigg(#[from] frigg::FriggError),
+    #[error("promotion error: {0}")]
+    Promotion(#[from] PromotionError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+    #[error("validation error: {0}")]
+    Validation(String),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] dal::WorkspaceSnapshotError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] dal::WsEventError),
+}
+
+impl ErrorIntoResponse for PromotionsError {
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            PromotionsError::Promotion(
+                PromotionError::ComponentMarkedForDeletion(_)
+                | PromotionError::ComponentNameInUse(_)
+                | PromotionError::ComponentSchemaMismatch { .. }
+                | PromotionError::InvalidSources(..)
+                | PromotionError::OverrideForUnknownComponent(_)
+                | PromotionError::UnsupportedSubscriptionFunc { .. }
+                | PromotionError::UnsupportedVariant(_),
+            )
+            | PromotionsError::Validation(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for PromotionsError {
+    fn into_response(self) -> Response {
+        self.to_api_response()
+    }
+}
+
+impl From<JsonRejection> for PromotionsError {
+    fn from(rejection: JsonRejection) -> Self {
+        PromotionsError::Validation(format!("Invalid JSON: {rejection}"))
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPromotionV1Request {
+    /// The components to promote, along with any components they subscribe to that may not exist
+    /// in the target workspace yet.
+    #[schema(value_type = Vec<String>, example = json!(["01H9ZQD35JPMBGHH69BT0Q79AA"]))]
+    pub component_ids: Vec<ComponentId>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPromotionV1Response {
+    /// Component specs to pass along to the promote endpoint of the target workspace.
+    #[schema(value_type = Vec<Object>)]
+    pub components: Vec<ComponentSpec>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PromoteComponentsV1Request {
+    #[schema(example = "Promote from dev")]
+    pub change_set_name: String,
+    /// Component specs exported from the source workspace.
+    #[schema(value_type = Vec<Object>)]
+    pub components: Vec<ComponentSpec>,
+    /// Attribute values or sources to use in this workspace instead of the promoted ones, keyed by
+    /// component name and then by attribute path.
+    #[serde(default)]
+    #[schema(value_type = Object, example = json!({"my-bucket": {"/domain/Region": "us-west-2"}}))]
+    pub overrides: PromotionOverrides,
+    /// How promoted components are matched to components in this workspace: `sourceId` only
+    /// matches components promoted from the same source component, while `sourceIdThenName` also
+    /// adopts a component with the same name that wasn't promoted from anywhere yet.
+    #[serde(default)]
+    #[schema(value_type = String, example = "sourceId")]
+    pub matching: PromotionMatching,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PromotedComponentV1 {
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79AA")]
+    pub component_id: ComponentId,
+    #[schema(example = "my-bucket")]
+    pub name: String,
+    pub created: bool,
+    #[schema(example = json!(["/domain/BucketName"]))]
+    pub updated_paths: Vec<String>,
+    #[schema(example = json!(["/domain/Tags/0"]))]
+    pub removed_paths: Vec<String>,
+    /// Whether the component's labels or owner were changed.
+    pub metadata_updated: bool,
+}
+
+impl From<PromotedComponent> for PromotedComponentV1 {
+    fn from(value: PromotedComponent) -> Self {
+        Self {
+            component_id: value.component_id,
+            name: value.name,
+            created: value.created,
+            updated_paths: value.updated_paths,
+            removed_paths: value.removed_paths,
+            metadata_updated: value.metadata_updated,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PromoteComponentsV1Response {
+    /// The change set the promoted components were applied to.
+    pub change_set: ChangeSetViewV1,
+    pub components: Vec<PromotedComponentV1>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/promotions/export",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+    ),
+    tag = "promotions",
+    summary = "Export components from HEAD so they can be promoted into another workspace",
+    request_body = ExportPromotionV1Request,
+    responses(
+        (status = 200, description = "Components exported successfully", body = ExportPromotionV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 422, description = "Validation error - Components cannot be promoted", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn export_promotion(
+    WorkspaceDalContext(ref ctx): WorkspaceDalContext,
+    tracker: PosthogEventTracker,
+    payload: Result<Json<ExportPromotionV1Request>, JsonRejection>,
+) -> PromotionsResult<Json<ExportPromotionV1Response>> {
+    let Json(payload) = payload?;
+
+    let components = promotion::export_components(ctx, &payload.component_ids).await?;
+
+    tracker.track(
+        ctx,
+        "api_export_promotion",
+        json!({ "component_count": components.len() }),
+    );
+
+    Ok(Json(ExportPromotionV1Response { components }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/promotions",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+    ),
+    tag = "promotions",
+    summary = "Promote exported components into a new change set",
+    description = "Creates a change set and applies the exported components to it, matching them to the components previously promoted from the same source components (or by name, if asked to). Only attributes that differ are changed; overrides replace the promoted value for specific attribute paths. If promotion fails, the change set is abandoned.",
+    request_body = PromoteComponentsV1Request,
+    responses(
+        (status = 200, description = "Components promoted successfully", body = PromoteComponentsV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 422, description = "Validation error - Components cannot be promoted", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn promote_components(
+    WorkspaceDalContext(mut ctx): WorkspaceDalContext,
+    EddaClientExtractor(edda_client): EddaClientExtractor,
+    FriggStore(frigg): FriggStore,
+    tracker: PosthogEventTracker,
+    payload: Result<Json<PromoteComponentsV1Request>, JsonRejection>,
+) -> PromotionsResult<Json<PromoteComponentsV1Response>> {
+    let Json(PromoteComponentsV1Request {
+        change_set_name,
+        components,
+        overrides,
+        matching,
+    }) = payload?;
+
+    let change_set = ChangeSet::fork_head(&ctx, &change_set_name).await?;
+    ctx.write_audit_log(AuditLogKind::CreateChangeSet, change_set_name.clone())
+        .await?;
+    WsEvent::change_set_created(&ctx, change_set.id, change_set.workspace_snapshot_address)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+    ctx.commit_no_rebase().await?;
+
+    create_index_for_new_change_set_and_watch(
+        &frigg,
+        &edda_client,
+        ctx.workspace_pk()?,
+        change_set.id,
+        ctx.change_set_id(),
+        ctx.workspace_snapshot()?.address().await,
+    )
+    .await?;
+
+    ctx.update_visibility_and_snapshot_to_visibility(change_set.id)
+        .await?;
+    let report = match promotion::promote_components(&ctx, &components, overrides, matching).await {
+        Ok(report) => report,
+        Err(err) => {
+            // Don't leave behind a change set for a promotion that never happened
+            abandon_change_set(&ctx, change_set).await?;
+            return Err(err.into());
+        }
+    };
+
+    tracker.track(
+        &ctx,
+        "api_promote_components",
+        json!({
+            "change_set_name": change_set_name,
+            "component_count": report.components.len(),
+            "created_count": report.components.iter().filter(|c| c.created).count(),
+        }),
+    );
+
+    let change_set_view = ChangeSetViewV1 {
+        id: change_set.id,
+        name: change_set.name.clone(),
+        status: change_set.status,
+        is_head: false,
+    };
+    ctx.commit().await?;
+
+    Ok(Json(PromoteComponentsV1Response {
+        change_set: change_set_view,
+        components: report.components.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Discards whatever the failed promotion did and abandons the change set it was made in.
+async fn abandon_change_set(ctx: &DalContext, mut change_set: ChangeSet) -> PromotionsResult<()> {
+    ctx.rollback().await?;
+    let head_ctx = ctx.clone_with_head().await?;
+    change_set.abandon(&head_ctx).await?;
+    head_ctx.commit_no_rebase().await?;
+    Ok(())
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(promote_components))
+        .route("/export", post(export_promotion))
+}
@@ -105,6 +105,7 @@ pub async fn contribute(
             Some(schema_variant_id.to_string()),
             Some(schema_variant_version.clone()),
             Some(false),
+            None,
         )
         .await?;
 
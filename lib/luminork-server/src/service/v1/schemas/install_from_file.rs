@@ -12,6 +12,7 @@ use dal::{
         import_funcs_for_module_update,
         import_pkg_from_pkg,
         import_schema_variant,
+        verify_module_signature,
     },
 };
 use sdf_extract::{
@@ -102,6 +103,11 @@ pub async fn install_from_file(
     let module_string = String::from_utf8_lossy(&module_bytes);
     let spec: PkgSpec = serde_json::from_str(&module_string)?;
     let pkg = SiPkg::load_from_spec(spec)?;
+    // Uploaded files carry no publisher signature, so they can only be installed into workspaces
+    // that do not require one.
+    verify_module_signature(ctx, &pkg, None)
+        .await
+        .map_err(SchemaError::Pkg)?;
 
     // Validate that the package has exactly one schema
     let schemas = pkg.schemas()?;
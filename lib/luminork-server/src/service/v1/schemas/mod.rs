@@ -254,6 +254,11 @@ impl crate::service::v1::common::ErrorIntoResponse for SchemaError {
                 (StatusCode::NOT_FOUND, self.to_string())
             }
             SchemaError::PkgFileError(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            SchemaError::Pkg(
+                dal::pkg::PkgError::InvalidModuleSignature(_)
+                | dal::pkg::PkgError::UnsignedModule(_)
+                | dal::pkg::PkgError::UntrustedModuleSigner(_),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
             SchemaError::SerdeJson(_) => (StatusCode::BAD_REQUEST, self.to_string()),
             SchemaError::Multipart(_) => (StatusCode::BAD_REQUEST, self.to_string()),
             SchemaError::SiPkg(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
@@ -0,0 +1,113 @@
+use axum::{
+    extract::Path,
+    response::Json,
+};
+use dal::component_template::{
+    self,
+    TemplateParameterValues,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    TemplateInstanceViewV1,
+    TemplateV1RequestPath,
+    TemplatesError,
+    TemplatesResult,
+};
+use crate::{
+    extract::{
+        PosthogEventTracker,
+        change_set::ChangeSetDalContext,
+    },
+    service::v1::PromotedComponentV1,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/templates/{template_name}/instantiate",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("template_name" = String, Path, description = "Template name")
+    ),
+    tag = "templates",
+    request_body = InstantiateTemplateV1Request,
+    summary = "Create the components of a template in a change set",
+    description = "Creates the components of the latest version of the template, named '<instanceName>-<component name>', with the given parameter values.",
+    responses(
+        (status = 200, description = "Template instantiated successfully", body = InstantiateTemplateV1Response),
+        (status = 400, description = "Bad Request - Not permitted on HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Template not found", body = crate::service::v1::common::ApiError),
+        (status = 409, description = "Conflict - The instance name is used by another template", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Invalid parameters", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn instantiate_template(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(TemplateV1RequestPath { template_name }): Path<TemplateV1RequestPath>,
+    payload: Result<Json<InstantiateTemplateV1Request>, axum::extract::rejection::JsonRejection>,
+) -> TemplatesResult<Json<InstantiateTemplateV1Response>> {
+    let Json(payload) = payload?;
+
+    if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
+        return Err(TemplatesError::NotPermittedOnHead);
+    }
+
+    let instantiation = component_template::instantiate(
+        ctx,
+        &template_name,
+        payload.instance_name,
+        payload.parameters,
+    )
+    .await?;
+
+    tracker.track(
+        ctx,
+        "api_instantiate_component_template",
+        json!({
+            "template_name": template_name,
+            "template_version": instantiation.instance.template_version,
+            "component_count": instantiation.components.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(InstantiateTemplateV1Response {
+        instance: instantiation.instance.into(),
+        components: instantiation
+            .components
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateV1Request {
+    #[schema(example = "dev")]
+    pub instance_name: String,
+    /// Parameter values keyed by parameter name. A value may be a `$source` spec.
+    #[serde(default)]
+    #[schema(value_type = Object, example = json!({"bucket_name": "dev-logs"}))]
+    pub parameters: TemplateParameterValues,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateV1Response {
+    pub instance: TemplateInstanceViewV1,
+    pub components: Vec<PromotedComponentV1>,
+}
@@ -0,0 +1,55 @@
+use axum::{
+    extract::Path,
+    response::Json,
+};
+use dal::component_template::ComponentTemplateInstance;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    TemplateInstanceViewV1,
+    TemplateV1RequestPath,
+    TemplatesResult,
+};
+use crate::extract::change_set::ChangeSetDalContext;
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/templates/{template_name}/instances",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("template_name" = String, Path, description = "Template name")
+    ),
+    tag = "templates",
+    summary = "List the instances of a component template",
+    responses(
+        (status = 200, description = "Instances retrieved successfully", body = ListTemplateInstancesV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn list_instances(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    Path(TemplateV1RequestPath { template_name }): Path<TemplateV1RequestPath>,
+) -> TemplatesResult<Json<ListTemplateInstancesV1Response>> {
+    let instances = ComponentTemplateInstance::list_for_template(ctx, &template_name)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListTemplateInstancesV1Response { instances }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTemplateInstancesV1Response {
+    pub instances: Vec<TemplateInstanceViewV1>,
+}
@@ -0,0 +1,49 @@
+use axum::response::Json;
+use dal::component_template::ComponentTemplate;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    TemplateViewV1,
+    TemplatesResult,
+};
+use crate::extract::change_set::ChangeSetDalContext;
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/templates",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "templates",
+    summary = "List the latest version of every component template in the workspace",
+    responses(
+        (status = 200, description = "Templates retrieved successfully", body = ListTemplatesV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn list_templates(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+) -> TemplatesResult<Json<ListTemplatesV1Response>> {
+    let templates = ComponentTemplate::list_latest(ctx)
+        .await?
+        .into_iter()
+        .map(TemplateViewV1::try_from_template)
+        .collect::<TemplatesResult<_>>()?;
+
+    Ok(Json(ListTemplatesV1Response { templates }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTemplatesV1Response {
+    pub templates: Vec<TemplateViewV1>,
+}
@@ -0,0 +1,254 @@
+use axum::{
+    Router,
+    extract::rejection::JsonRejection,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{
+        get,
+        post,
+    },
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use dal::{
+    ComponentId,
+    component_template::{
+        self,
+        ComponentTemplate,
+        ComponentTemplateError,
+        ComponentTemplateInstance,
+        TemplateParameterValues,
+    },
+    promotion::PromotionError,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_pkg::TemplateParameterSpec;
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::AppState;
+
+pub mod instantiate_template;
+pub mod list_instances;
+pub mod list_templates;
+pub mod save_template;
+pub mod upgrade_instance;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum TemplatesError {
+    #[error("component template error: {0}")]
+    ComponentTemplate(#[from] ComponentTemplateError),
+    #[error("component template storage error: {0}")]
+    Db(#[from] si_db::ComponentTemplateError),
+    #[error("changes not permitted on HEAD change set")]
+    NotPermittedOnHead,
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+    #[error("validation error: {0}")]
+    Validation(String),
+}
+
+pub type TemplatesResult<T> = Result<T, TemplatesError>;
+
+impl IntoResponse for TemplatesError {
+    fn into_response(self) -> axum::response::Response {
+        use crate::service::v1::common::ErrorIntoResponse;
+        self.to_api_response()
+    }
+}
+
+impl From<JsonRejection> for TemplatesError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonDataError(_) => {
+                TemplatesError::Validation(format!("Invalid JSON data format: {rejection}"))
+            }
+            JsonRejection::JsonSyntaxError(_) => {
+                TemplatesError::Validation(format!("Invalid JSON syntax: {rejection}"))
+            }
+            JsonRejection::MissingJsonContentType(_) => TemplatesError::Validation(
+                "Request must have Content-Type: application/json header".to_string(),
+            ),
+            _ => TemplatesError::Validation(format!("JSON validation error: {rejection}")),
+        }
+    }
+}
+
+impl crate::service::v1::common::ErrorIntoResponse for TemplatesError {
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            TemplatesError::NotPermittedOnHead => (StatusCode::BAD_REQUEST, self.to_string()),
+            TemplatesError::ComponentTemplate(
+                ComponentTemplateError::InstanceNotFound(_)
+                | ComponentTemplateError::TemplateNotFound(_),
+            ) => (StatusCode::NOT_FOUND, self.to_string()),
+            TemplatesError::ComponentTemplate(ComponentTemplateError::InstanceNameInUse {
+                ..
+            })
+            | TemplatesError::ComponentTemplate(ComponentTemplateError::Db(
+                si_db::ComponentTemplateError::VersionConflict(..),
+            ))
+            | TemplatesError::Db(si_db::ComponentTemplateError::VersionConflict(..)) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
+            TemplatesError::ComponentTemplate(
+                ComponentTemplateError::EmptyTemplate(_)
+                | ComponentTemplateError::MissingParameter(_)
+                | ComponentTemplateError::ParameterBindingForUnknownComponent(_)
+                | ComponentTemplateError::UndeclaredParameter(_)
+                | ComponentTemplateError::UnknownParameter(_),
+            )
+            | TemplatesError::Validation(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            TemplatesError::ComponentTemplate(ComponentTemplateError::Promotion(err))
+                if matches!(
+                    **err,
+                    PromotionError::ComponentMarkedForDeletion(_)
+                        | PromotionError::ComponentNameInUse(_)
+                        | PromotionError::ComponentSchemaMismatch { .. }
+                        | PromotionError::UnsupportedSubscriptionFunc { .. }
+                        | PromotionError::UnsupportedVariant(_)
+                ) =>
+            {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct TemplateV1RequestPath {
+    #[schema(example = "storage")]
+    pub template_name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct TemplateInstanceV1RequestPath {
+    #[schema(example = "storage")]
+    pub template_name: String,
+    #[schema(example = "dev")]
+    pub instance_name: String,
+}
+
+/// A parameter declared by a template.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateParameterV1 {
+    #[schema(example = "region")]
+    pub name: String,
+    #[schema(example = "Region to create the bucket in")]
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The value used when an instance doesn't provide one. Parameters without a default are
+    /// required.
+    #[schema(value_type = Option<Object>, example = json!("us-east-1"))]
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
+}
+
+impl From<TemplateParameterSpec> for TemplateParameterV1 {
+    fn from(spec: TemplateParameterSpec) -> Self {
+        Self {
+            name: spec.name,
+            description: spec.description,
+            default: spec.default,
+        }
+    }
+}
+
+impl From<TemplateParameterV1> for TemplateParameterSpec {
+    fn from(parameter: TemplateParameterV1) -> Self {
+        Self {
+            name: parameter.name,
+            description: parameter.description,
+            default: parameter.default,
+        }
+    }
+}
+
+/// The latest version of a component template.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateViewV1 {
+    #[schema(example = "storage")]
+    pub name: String,
+    #[schema(example = 2)]
+    pub version: i32,
+    #[schema(example = "A bucket with a policy")]
+    pub description: Option<String>,
+    pub parameters: Vec<TemplateParameterV1>,
+    #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl TemplateViewV1 {
+    fn try_from_template(template: ComponentTemplate) -> TemplatesResult<Self> {
+        let parameters = component_template::parameters(&template)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(Self {
+            name: template.name,
+            version: template.version,
+            description: template.description,
+            parameters,
+            created_at: template.created_at,
+        })
+    }
+}
+
+/// A set of components created from a template.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateInstanceViewV1 {
+    #[schema(example = "dev")]
+    pub name: String,
+    #[schema(example = "storage")]
+    pub template_name: String,
+    /// The version of the template the instance was created from or last upgraded to.
+    #[schema(example = 1)]
+    pub template_version: i32,
+    /// The parameter values given when the instance was created or last upgraded.
+    #[schema(value_type = Object, example = json!({"bucket_name": "dev-logs"}))]
+    pub parameters: TemplateParameterValues,
+    /// The components of the instance in this change set.
+    #[schema(value_type = Vec<String>, example = json!(["01H9ZQD35JPMBGHH69BT0Q79AA"]))]
+    pub component_ids: Vec<ComponentId>,
+}
+
+impl From<ComponentTemplateInstance> for TemplateInstanceViewV1 {
+    fn from(instance: ComponentTemplateInstance) -> Self {
+        Self {
+            name: instance.name,
+            template_name: instance.template_name,
+            template_version: instance.template_version,
+            parameters: instance.parameters,
+            component_ids: instance.component_ids,
+        }
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_templates::list_templates))
+        .route("/", post(save_template::save_template))
+        .nest(
+            "/:template_name",
+            Router::new()
+                .route(
+                    "/instantiate",
+                    post(instantiate_template::instantiate_template),
+                )
+                .route("/instances", get(list_instances::list_instances))
+                .route(
+                    "/instances/:instance_name/upgrade",
+                    post(upgrade_instance::upgrade_instance),
+                ),
+        )
+}
@@ -0,0 +1,106 @@
+use axum::response::Json;
+use dal::{
+    ComponentId,
+    component_template::{
+        self,
+        TemplateParameterBindings,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    TemplateParameterV1,
+    TemplateViewV1,
+    TemplatesResult,
+};
+use crate::extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/templates",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "templates",
+    request_body = SaveTemplateV1Request,
+    summary = "Save components as a new version of a component template",
+    description = "Captures the components (and the subscriptions between them) as they are in this change set. Attributes listed in the bindings are replaced by the value of a parameter when the template is instantiated.",
+    responses(
+        (status = 200, description = "Template saved successfully", body = SaveTemplateV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 409, description = "Conflict - This version of the template was saved concurrently", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn save_template(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    payload: Result<Json<SaveTemplateV1Request>, axum::extract::rejection::JsonRejection>,
+) -> TemplatesResult<Json<SaveTemplateV1Response>> {
+    let Json(payload) = payload?;
+
+    let template = component_template::save(
+        ctx,
+        payload.name,
+        payload.description,
+        &payload.component_ids,
+        payload.parameters.into_iter().map(Into::into).collect(),
+        payload.bindings,
+    )
+    .await?;
+
+    tracker.track(
+        ctx,
+        "api_save_component_template",
+        json!({
+            "template_name": template.name,
+            "template_version": template.version,
+            "component_count": payload.component_ids.len(),
+        }),
+    );
+
+    // Templates are stored outside of the workspace snapshot, so there is nothing to rebase
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(SaveTemplateV1Response {
+        template: TemplateViewV1::try_from_template(template)?,
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveTemplateV1Request {
+    #[schema(example = "storage")]
+    pub name: String,
+    #[schema(value_type = Option<String>, example = "A bucket with a policy")]
+    #[serde(default)]
+    pub description: Option<String>,
+    #[schema(value_type = Vec<String>, example = json!(["01H9ZQD35JPMBGHH69BT0Q79AA"]))]
+    pub component_ids: Vec<ComponentId>,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameterV1>,
+    /// Which attributes are set from which parameter, keyed by component name and then by
+    /// attribute path.
+    #[serde(default)]
+    #[schema(value_type = Object, example = json!({"bucket": {"/domain/Region": "region"}}))]
+    pub bindings: TemplateParameterBindings,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveTemplateV1Response {
+    pub template: TemplateViewV1,
+}
@@ -0,0 +1,116 @@
+use axum::{
+    extract::Path,
+    response::Json,
+};
+use dal::component_template::{
+    self,
+    ComponentTemplateError,
+    ComponentTemplateInstance,
+    TemplateParameterValues,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    TemplateInstanceV1RequestPath,
+    TemplateInstanceViewV1,
+    TemplatesError,
+    TemplatesResult,
+};
+use crate::{
+    extract::{
+        PosthogEventTracker,
+        change_set::ChangeSetDalContext,
+    },
+    service::v1::PromotedComponentV1,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/templates/{template_name}/instances/{instance_name}/upgrade",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("template_name" = String, Path, description = "Template name"),
+        ("instance_name" = String, Path, description = "Instance name")
+    ),
+    tag = "templates",
+    request_body = UpgradeTemplateInstanceV1Request,
+    summary = "Upgrade an instance to the latest version of its template",
+    description = "Applies the differences between the instance's components and the latest version of the template. The instance keeps its parameter values unless new ones are given.",
+    responses(
+        (status = 200, description = "Instance upgraded successfully", body = UpgradeTemplateInstanceV1Response),
+        (status = 400, description = "Bad Request - Not permitted on HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Template or instance not found", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Invalid parameters", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn upgrade_instance(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(TemplateInstanceV1RequestPath {
+        template_name,
+        instance_name,
+    }): Path<TemplateInstanceV1RequestPath>,
+    payload: Result<
+        Json<UpgradeTemplateInstanceV1Request>,
+        axum::extract::rejection::JsonRejection,
+    >,
+) -> TemplatesResult<Json<UpgradeTemplateInstanceV1Response>> {
+    let Json(payload) = payload?;
+
+    if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
+        return Err(TemplatesError::NotPermittedOnHead);
+    }
+
+    match ComponentTemplateInstance::get(ctx, &instance_name).await? {
+        Some(instance) if instance.template_name == template_name => {}
+        _ => {
+            return Err(ComponentTemplateError::InstanceNotFound(instance_name).into());
+        }
+    }
+
+    let upgrade = component_template::upgrade(ctx, &instance_name, payload.parameters).await?;
+
+    tracker.track(
+        ctx,
+        "api_upgrade_component_template_instance",
+        json!({
+            "template_name": template_name,
+            "template_version": upgrade.instance.template_version,
+            "changed_count": upgrade.components.iter().filter(|c| !c.is_unchanged()).count(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(UpgradeTemplateInstanceV1Response {
+        instance: upgrade.instance.into(),
+        components: upgrade.components.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeTemplateInstanceV1Request {
+    /// Parameter values to change, keyed by parameter name.
+    #[serde(default)]
+    #[schema(value_type = Object, example = json!({"region": "us-west-2"}))]
+    pub parameters: TemplateParameterValues,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeTemplateInstanceV1Response {
+    pub instance: TemplateInstanceViewV1,
+    pub components: Vec<PromotedComponentV1>,
+}
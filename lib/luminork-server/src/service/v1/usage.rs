@@ -0,0 +1,219 @@
+use audit_database::{
+    FuncRunUsage,
+    UsageGranularity,
+    WorkspaceUsageRollup,
+};
+use axum::{
+    Json,
+    extract::{
+        Query,
+        State,
+    },
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use sdf_extract::workspace::WorkspaceDalContext;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use thiserror::Error;
+use utoipa::{
+    IntoParams,
+    ToSchema,
+};
+
+use crate::{
+    AppState,
+    extract::PosthogEventTracker,
+    service::v1::common::ErrorIntoResponse,
+};
+
+/// How far back usage is reported when no start is given.
+const DEFAULT_USAGE_LOOKBACK_DAYS: i64 = 7;
+
+pub type UsageResult<T> = Result<T, UsageError>;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum UsageError {
+    #[error("audit database error: {0}")]
+    AuditDatabase(#[from] audit_database::AuditDatabaseError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+}
+
+impl ErrorIntoResponse for UsageError {
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            UsageError::AuditDatabase(audit_database::AuditDatabaseError::InvalidUsageRange(_)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for UsageError {
+    fn into_response(self) -> Response {
+        self.to_api_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum UsageGranularityV1 {
+    Hour,
+    Day,
+}
+
+impl From<UsageGranularityV1> for UsageGranularity {
+    fn from(value: UsageGranularityV1) -> Self {
+        match value {
+            UsageGranularityV1::Hour => Self::Hour,
+            UsageGranularityV1::Day => Self::Day,
+        }
+    }
+}
+
+impl From<UsageGranularity> for UsageGranularityV1 {
+    fn from(value: UsageGranularity) -> Self {
+        match value {
+            UsageGranularity::Hour => Self::Hour,
+            UsageGranularity::Day => Self::Day,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, IntoParams, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[into_params(style = Form, parameter_in = Query)]
+pub struct GetWorkspaceUsageV1Params {
+    /// The size of the buckets to report usage in. Defaults to "day".
+    #[param(required = false, nullable = true)]
+    pub granularity: Option<UsageGranularityV1>,
+    /// The start of the reported range (inclusive, RFC 3339). Defaults to seven days before the
+    /// end of the range. The range may be at most 31 days long for "hour" and 366 days for "day".
+    #[param(value_type = String, required = false, nullable = true)]
+    #[schema(value_type = Option<String>)]
+    pub start: Option<DateTime<Utc>>,
+    /// The end of the reported range (exclusive, RFC 3339). Defaults to now.
+    #[param(value_type = String, required = false, nullable = true)]
+    #[schema(value_type = Option<String>)]
+    pub end: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunUsageV1 {
+    #[schema(example = "Action")]
+    pub func_kind: String,
+    #[schema(example = 12)]
+    pub run_count: i64,
+    #[schema(example = 34.5)]
+    pub run_seconds: f64,
+}
+
+impl From<FuncRunUsage> for FuncRunUsageV1 {
+    fn from(value: FuncRunUsage) -> Self {
+        Self {
+            func_kind: value.func_kind,
+            run_count: value.run_count,
+            run_seconds: value.run_seconds,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceUsageV1 {
+    #[schema(value_type = String, example = "2025-01-01T00:00:00Z")]
+    pub bucket_start: DateTime<Utc>,
+    pub granularity: UsageGranularityV1,
+    #[schema(example = 42)]
+    pub resource_count: Option<i64>,
+    #[schema(example = 3)]
+    pub resources_created: i64,
+    #[schema(example = 1)]
+    pub resources_deleted: i64,
+    pub func_runs: Vec<FuncRunUsageV1>,
+}
+
+impl From<WorkspaceUsageRollup> for WorkspaceUsageV1 {
+    fn from(value: WorkspaceUsageRollup) -> Self {
+        Self {
+            bucket_start: value.bucket_start,
+            granularity: value.granularity.into(),
+            resource_count: value.resource_count,
+            resources_created: value.resources_created,
+            resources_deleted: value.resources_deleted,
+            func_runs: value.func_runs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWorkspaceUsageV1Response {
+    pub usage: Vec<WorkspaceUsageV1>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/usage",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        GetWorkspaceUsageV1Params,
+    ),
+    tag = "usage",
+    summary = "Get the resource and function run usage of a workspace, rolled up by hour or day",
+    responses(
+        (status = 200, description = "Usage retrieved successfully", body = GetWorkspaceUsageV1Response),
+        (status = 400, description = "Invalid time range, or a range too long for the granularity"),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn get_workspace_usage(
+    WorkspaceDalContext(ref ctx): WorkspaceDalContext,
+    State(state): State<AppState>,
+    tracker: PosthogEventTracker,
+    Query(params): Query<GetWorkspaceUsageV1Params>,
+) -> UsageResult<Json<GetWorkspaceUsageV1Response>> {
+    let granularity = params.granularity.unwrap_or(UsageGranularityV1::Day);
+    let end = params.end.unwrap_or_else(Utc::now);
+    let start = params
+        .start
+        .unwrap_or(end - TimeDelta::days(DEFAULT_USAGE_LOOKBACK_DAYS));
+
+    let usage = WorkspaceUsageRollup::list(
+        state.audit_database_context(),
+        ctx.workspace_pk()?,
+        granularity.into(),
+        start,
+        end,
+    )
+    .await?;
+
+    tracker.track(
+        ctx,
+        "api_get_workspace_usage",
+        json!({
+            "granularity": granularity,
+            "bucket_count": usage.len(),
+        }),
+    );
+
+    Ok(Json(GetWorkspaceUsageV1Response {
+        usage: usage.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -0,0 +1,87 @@
+use axum::response::Json;
+use dal::variable::Variable;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    VariableViewV1,
+    VariablesError,
+    VariablesResult,
+};
+use crate::extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/variables",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "variables",
+    request_body = CreateVariableV1Request,
+    summary = "Create a variable",
+    responses(
+        (status = 200, description = "Variable created successfully", body = CreateVariableV1Response),
+        (status = 400, description = "Bad Request - Not permitted on HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 409, description = "Conflict - A variable with this name already exists", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn create_variable(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    payload: Result<Json<CreateVariableV1Request>, axum::extract::rejection::JsonRejection>,
+) -> VariablesResult<Json<CreateVariableV1Response>> {
+    let Json(payload) = payload?;
+
+    if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
+        return Err(VariablesError::NotPermittedOnHead);
+    }
+
+    let variable = Variable::new(ctx, payload.name, payload.value, payload.description).await?;
+
+    tracker.track(
+        ctx,
+        "api_create_variable",
+        json!({
+            "variable_id": variable.id(),
+            "variable_name": variable.name(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateVariableV1Response {
+        variable: variable.into(),
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVariableV1Request {
+    #[schema(example = "prod.region")]
+    pub name: String,
+    #[schema(value_type = Object, example = json!("us-east-1"))]
+    pub value: serde_json::Value,
+    #[schema(value_type = Option<String>, example = "Region for production infrastructure")]
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVariableV1Response {
+    pub variable: VariableViewV1,
+}
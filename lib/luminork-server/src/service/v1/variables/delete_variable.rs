@@ -0,0 +1,73 @@
+use axum::{
+    extract::Path,
+    response::Json,
+};
+use dal::variable::Variable;
+use serde::Serialize;
+use serde_json::json;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    VariableV1RequestPath,
+    VariablesError,
+    VariablesResult,
+};
+use crate::extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/variables/{variable_id}",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("variable_id" = String, Path, description = "Variable identifier")
+    ),
+    tag = "variables",
+    summary = "Delete a variable",
+    responses(
+        (status = 200, description = "Variable deleted successfully", body = DeleteVariableV1Response),
+        (status = 400, description = "Bad Request - Not permitted on HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Variable not found", body = crate::service::v1::common::ApiError),
+        (status = 409, description = "Conflict - The variable is still in use", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn delete_variable(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(VariableV1RequestPath { variable_id }): Path<VariableV1RequestPath>,
+) -> VariablesResult<Json<DeleteVariableV1Response>> {
+    if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
+        return Err(VariablesError::NotPermittedOnHead);
+    }
+
+    let variable = Variable::get_by_id(ctx, variable_id).await?;
+    Variable::remove(ctx, variable_id).await?;
+
+    tracker.track(
+        ctx,
+        "api_delete_variable",
+        json!({
+            "variable_id": variable_id,
+            "variable_name": variable.name(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(DeleteVariableV1Response { success: true }))
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteVariableV1Response {
+    #[schema(value_type = bool)]
+    pub success: bool,
+}
@@ -0,0 +1,49 @@
+use axum::response::Json;
+use dal::variable::Variable;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    VariableViewV1,
+    VariablesResult,
+};
+use crate::extract::change_set::ChangeSetDalContext;
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/variables",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "variables",
+    summary = "List the variables in a change set",
+    responses(
+        (status = 200, description = "Variables retrieved successfully", body = ListVariablesV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn list_variables(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+) -> VariablesResult<Json<ListVariablesV1Response>> {
+    let variables = Variable::list(ctx)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListVariablesV1Response { variables }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListVariablesV1Response {
+    pub variables: Vec<VariableViewV1>,
+}
@@ -0,0 +1,132 @@
+use axum::{
+    Router,
+    extract::rejection::JsonRejection,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{
+        delete,
+        get,
+        post,
+        put,
+    },
+};
+use dal::variable::{
+    Variable,
+    VariableError,
+    VariableId,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::AppState;
+
+pub mod create_variable;
+pub mod delete_variable;
+pub mod list_variables;
+pub mod update_variable;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum VariablesError {
+    #[error("changes not permitted on HEAD change set")]
+    NotPermittedOnHead,
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+    #[error("validation error: {0}")]
+    Validation(String),
+    #[error("variable error: {0}")]
+    Variable(#[from] VariableError),
+}
+
+pub type VariablesResult<T> = Result<T, VariablesError>;
+
+impl IntoResponse for VariablesError {
+    fn into_response(self) -> axum::response::Response {
+        use crate::service::v1::common::ErrorIntoResponse;
+        self.to_api_response()
+    }
+}
+
+impl From<JsonRejection> for VariablesError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonDataError(_) => {
+                VariablesError::Validation(format!("Invalid JSON data format: {rejection}"))
+            }
+            JsonRejection::JsonSyntaxError(_) => {
+                VariablesError::Validation(format!("Invalid JSON syntax: {rejection}"))
+            }
+            JsonRejection::MissingJsonContentType(_) => VariablesError::Validation(
+                "Request must have Content-Type: application/json header".to_string(),
+            ),
+            _ => VariablesError::Validation(format!("JSON validation error: {rejection}")),
+        }
+    }
+}
+
+impl crate::service::v1::common::ErrorIntoResponse for VariablesError {
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            VariablesError::NotPermittedOnHead => (StatusCode::BAD_REQUEST, self.to_string()),
+            VariablesError::Validation(_)
+            | VariablesError::Variable(VariableError::InvalidName(_)) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            VariablesError::Variable(VariableError::VariableNotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            VariablesError::Variable(
+                VariableError::NameAlreadyInUse(_) | VariableError::VariableInUse(_, _),
+            ) => (StatusCode::CONFLICT, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct VariableV1RequestPath {
+    #[schema(value_type = String)]
+    pub variable_id: VariableId,
+}
+
+/// A named value that component attributes can use as their source with
+/// `{ "$source": { "variable": "<name>" } }`.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableViewV1 {
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub id: VariableId,
+    #[schema(example = "prod.region")]
+    pub name: String,
+    #[schema(value_type = Object, example = json!("us-east-1"))]
+    pub value: serde_json::Value,
+    #[schema(example = "Region for production infrastructure")]
+    pub description: Option<String>,
+}
+
+impl From<Variable> for VariableViewV1 {
+    fn from(variable: Variable) -> Self {
+        Self {
+            id: variable.id(),
+            name: variable.name().to_owned(),
+            value: variable.value().clone(),
+            description: variable.description().map(ToOwned::to_owned),
+        }
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_variables::list_variables))
+        .route("/", post(create_variable::create_variable))
+        .nest(
+            "/:variable_id",
+            Router::new()
+                .route("/", put(update_variable::update_variable))
+                .route("/", delete(delete_variable::delete_variable)),
+        )
+}
@@ -0,0 +1,102 @@
+use axum::{
+    extract::Path,
+    response::Json,
+};
+use dal::variable::Variable;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    VariableV1RequestPath,
+    VariableViewV1,
+    VariablesError,
+    VariablesResult,
+};
+use crate::extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+
+#[utoipa::path(
+    put,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/variables/{variable_id}",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("variable_id" = String, Path, description = "Variable identifier")
+    ),
+    tag = "variables",
+    request_body = UpdateVariableV1Request,
+    summary = "Update a variable",
+    description = "Renames the variable, updates its description and, if a value is given, sets its value. Attribute values using the variable are re-evaluated when its value changes.",
+    responses(
+        (status = 200, description = "Variable updated successfully", body = UpdateVariableV1Response),
+        (status = 400, description = "Bad Request - Not permitted on HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Variable not found", body = crate::service::v1::common::ApiError),
+        (status = 409, description = "Conflict - A variable with this name already exists", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn update_variable(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(VariableV1RequestPath { variable_id }): Path<VariableV1RequestPath>,
+    payload: Result<Json<UpdateVariableV1Request>, axum::extract::rejection::JsonRejection>,
+) -> VariablesResult<Json<UpdateVariableV1Response>> {
+    let Json(payload) = payload?;
+
+    if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
+        return Err(VariablesError::NotPermittedOnHead);
+    }
+
+    let mut variable = Variable::get_by_id(ctx, variable_id)
+        .await?
+        .update_metadata(ctx, payload.name, payload.description)
+        .await?;
+    if let Some(value) = payload.value {
+        variable = variable.set_value(ctx, value).await?;
+    }
+
+    tracker.track(
+        ctx,
+        "api_update_variable",
+        json!({
+            "variable_id": variable.id(),
+            "variable_name": variable.name(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(UpdateVariableV1Response {
+        variable: variable.into(),
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateVariableV1Request {
+    #[schema(example = "prod.region")]
+    pub name: String,
+    #[schema(value_type = Option<Object>, example = json!("us-west-2"))]
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+    #[schema(value_type = Option<String>, example = "Region for production infrastructure")]
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateVariableV1Response {
+    pub variable: VariableViewV1,
+}
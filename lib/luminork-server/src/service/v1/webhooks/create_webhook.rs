@@ -0,0 +1,120 @@
+use axum::response::Json;
+use dal::{
+    Component,
+    ComponentId,
+    management::prototype::ManagementPrototype,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use si_db::WorkspaceWebhook;
+use si_id::ManagementPrototypeId;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    WebhookViewV1,
+    WebhooksError,
+    WebhooksResult,
+};
+use crate::extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/webhooks",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "webhooks",
+    request_body = CreateWebhookV1Request,
+    summary = "Register a webhook that runs a component's management function",
+    responses(
+        (status = 200, description = "Webhook registered successfully", body = CreateWebhookV1Response),
+        (status = 400, description = "Bad Request - Not permitted on HEAD", body = crate::service::v1::common::ApiError),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Component not found", body = crate::service::v1::common::ApiError),
+        (status = 409, description = "Conflict - A webhook with this name already exists", body = crate::service::v1::common::ApiError),
+        (status = 412, description = "Precondition Failed - Management function is not valid for the component", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Invalid request data", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn create_webhook(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    payload: Result<Json<CreateWebhookV1Request>, axum::extract::rejection::JsonRejection>,
+) -> WebhooksResult<Json<CreateWebhookV1Response>> {
+    let Json(payload) = payload?;
+
+    if ctx.change_set_id() == ctx.get_workspace_default_change_set_id().await? {
+        return Err(WebhooksError::NotPermittedOnHead);
+    }
+
+    let schema_variant_id = Component::schema_variant_id(ctx, payload.component_id).await?;
+    if !ManagementPrototype::list_for_schema_and_variant_id(ctx, schema_variant_id)
+        .await?
+        .iter()
+        .any(|prototype| prototype.id == payload.management_prototype_id)
+    {
+        return Err(WebhooksError::ManagementPrototypeNotValidForComponent(
+            payload.management_prototype_id,
+            payload.component_id,
+        ));
+    }
+
+    let (webhook, signing_secret) = WorkspaceWebhook::new(
+        ctx,
+        ctx.symmetric_crypto_service(),
+        payload.name,
+        payload.component_id,
+        payload.management_prototype_id,
+    )
+    .await?;
+
+    tracker.track(
+        ctx,
+        "api_create_webhook",
+        json!({
+            "webhook_id": webhook.id,
+            "component_id": webhook.component_id,
+            "management_prototype_id": webhook.prototype_id,
+        }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(CreateWebhookV1Response {
+        signing_secret,
+        webhook: webhook.into(),
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookV1Request {
+    /// A name for the webhook that is unique within the workspace.
+    #[schema(example = "github-push")]
+    pub name: String,
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub component_id: ComponentId,
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub management_prototype_id: ManagementPrototypeId,
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookV1Response {
+    pub webhook: WebhookViewV1,
+    /// The secret used to sign deliveries with HMAC-SHA256. It is not returned again, so store it
+    /// somewhere safe.
+    #[schema(example = "5c1e7a0e2d6b4b7a9f4c9a0e2d6b4b7a9f4c9a0e2d6b4b7a9f4c9a0e2d6b4b7a")]
+    pub signing_secret: String,
+}
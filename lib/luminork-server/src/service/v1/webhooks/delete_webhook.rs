@@ -0,0 +1,67 @@
+use axum::{
+    extract::Path,
+    response::Json,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use si_db::WorkspaceWebhook;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    WebhookV1RequestPath,
+    WebhooksError,
+    WebhooksResult,
+};
+use crate::extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/webhooks/{webhook_id}",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier"),
+        ("webhook_id" = String, Path, description = "Webhook identifier")
+    ),
+    tag = "webhooks",
+    summary = "Delete a webhook",
+    responses(
+        (status = 200, description = "Webhook deleted successfully", body = DeleteWebhookV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 404, description = "Webhook not found", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn delete_webhook(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(WebhookV1RequestPath { webhook_id }): Path<WebhookV1RequestPath>,
+) -> WebhooksResult<Json<DeleteWebhookV1Response>> {
+    if !WorkspaceWebhook::delete(ctx, webhook_id).await? {
+        return Err(WebhooksError::WebhookNotFound(webhook_id));
+    }
+
+    tracker.track(
+        ctx,
+        "api_delete_webhook",
+        json!({ "webhook_id": webhook_id }),
+    );
+
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(DeleteWebhookV1Response { success: true }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWebhookV1Response {
+    pub success: bool,
+}
@@ -0,0 +1,146 @@
+use axum::{
+    body::Bytes,
+    extract::Path,
+    http::HeaderMap,
+    response::Json,
+};
+use dal::{
+    ChangeSet,
+    WsEvent,
+    diagram::view::View,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_db::{
+    ManagementFuncExecutionError,
+    ManagementFuncJobState,
+    WorkspaceWebhook,
+};
+use si_events::ChangeSetStatus;
+use si_id::ManagementFuncJobStateId;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    WebhookDeliveryV1RequestPath,
+    WebhooksError,
+    WebhooksResult,
+};
+use crate::extract::{
+    HandlerContext,
+    request::RequestUlidFromHeader,
+};
+
+/// The header carrying the hex encoded HMAC-SHA256 signature of the request body, optionally
+/// prefixed with `sha256=`. This matches the header GitHub sends, so GitHub webhooks can be
+/// pointed at us directly.
+const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks/{workspace_id}/{webhook_id}",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("webhook_id" = String, Path, description = "Webhook identifier"),
+        ("X-Hub-Signature-256" = String, Header, description = "HMAC-SHA256 signature of the request body, as `sha256=<hex digest>`")
+    ),
+    tag = "webhooks",
+    request_body(content = Object, description = "The JSON payload, passed to the management function as `webhookPayload`"),
+    summary = "Deliver a signed payload to a webhook, running its management function",
+    responses(
+        (status = 200, description = "Management function enqueued", body = DeliverWebhookV1Response),
+        (status = 401, description = "Unauthorized - Missing or invalid signature", body = crate::service::v1::common::ApiError),
+        (status = 404, description = "Webhook not found", body = crate::service::v1::common::ApiError),
+        (status = 409, description = "Conflict - The management function is already running, or the webhook's change set is no longer open", body = crate::service::v1::common::ApiError),
+        (status = 422, description = "Validation error - Payload is not valid JSON", body = crate::service::v1::common::ApiError),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn deliver_webhook(
+    HandlerContext(builder): HandlerContext,
+    RequestUlidFromHeader(request_ulid): RequestUlidFromHeader,
+    Path(WebhookDeliveryV1RequestPath {
+        workspace_id,
+        webhook_id,
+    }): Path<WebhookDeliveryV1RequestPath>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> WebhooksResult<Json<DeliverWebhookV1Response>> {
+    // Deliveries are not made by a workspace member, so we can only look the webhook up by the
+    // workspace in the URL and trust the delivery once its signature checks out.
+    let lookup_ctx = builder.build_default(request_ulid).await?;
+    let webhook = WorkspaceWebhook::get_for_workspace(&lookup_ctx, workspace_id, webhook_id)
+        .await?
+        .ok_or(WebhooksError::WebhookNotFound(webhook_id))?;
+
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(WebhooksError::MissingSignature(SIGNATURE_HEADER))?;
+    if !webhook.verify_signature(lookup_ctx.symmetric_crypto_service(), &body, signature)? {
+        return Err(WebhooksError::InvalidSignature);
+    }
+
+    // Once the change set has been applied or abandoned, running the management function in it
+    // would change nothing anyone can see, so refuse rather than quietly doing so.
+    let change_set_status = ChangeSet::find_across_workspaces(&lookup_ctx, webhook.change_set_id)
+        .await?
+        .filter(|change_set| change_set.workspace_id == Some(workspace_id))
+        .map(|change_set| change_set.status);
+    if change_set_status != Some(ChangeSetStatus::Open) {
+        return Err(WebhooksError::ChangeSetNotOpen(webhook.change_set_id));
+    }
+
+    let payload: serde_json::Value =
+        serde_json::from_slice(&body).map_err(WebhooksError::InvalidPayload)?;
+
+    let ctx = builder
+        .build_for_change_set_as_system(workspace_id, webhook.change_set_id, request_ulid)
+        .await?;
+
+    let job_state = ManagementFuncJobState::new_pending_for_webhook(
+        &ctx,
+        webhook.component_id,
+        webhook.prototype_id,
+        webhook.id,
+        payload,
+    )
+    .await
+    .map_err(|err| match err {
+        ManagementFuncExecutionError::CreationFailed => {
+            WebhooksError::ManagementFunctionAlreadyRunning(webhook.id)
+        }
+        other => other.into(),
+    })?;
+
+    let view_id = View::get_id_for_default(&ctx).await?;
+    let request_ulid = request_ulid.unwrap_or_else(ulid::Ulid::new);
+    ctx.enqueue_management_func(
+        webhook.prototype_id,
+        webhook.component_id,
+        view_id,
+        request_ulid,
+    )
+    .await?;
+    WsEvent::management_operations_in_progress(&ctx, request_ulid)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(DeliverWebhookV1Response {
+        management_func_job_state_id: job_state.id(),
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliverWebhookV1Response {
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub management_func_job_state_id: ManagementFuncJobStateId,
+}
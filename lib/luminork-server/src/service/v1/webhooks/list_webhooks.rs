@@ -0,0 +1,49 @@
+use axum::response::Json;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_db::WorkspaceWebhook;
+use utoipa::{
+    self,
+    ToSchema,
+};
+
+use super::{
+    WebhookViewV1,
+    WebhooksResult,
+};
+use crate::extract::change_set::ChangeSetDalContext;
+
+#[utoipa::path(
+    get,
+    path = "/v1/w/{workspace_id}/change-sets/{change_set_id}/webhooks",
+    params(
+        ("workspace_id" = String, Path, description = "Workspace identifier"),
+        ("change_set_id" = String, Path, description = "Change Set identifier")
+    ),
+    tag = "webhooks",
+    summary = "List the webhooks registered for a change set",
+    responses(
+        (status = 200, description = "Webhooks retrieved successfully", body = ListWebhooksV1Response),
+        (status = 401, description = "Unauthorized - Invalid or missing token"),
+        (status = 500, description = "Internal server error", body = crate::service::v1::common::ApiError)
+    )
+)]
+pub async fn list_webhooks(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+) -> WebhooksResult<Json<ListWebhooksV1Response>> {
+    let webhooks = WorkspaceWebhook::list(ctx)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListWebhooksV1Response { webhooks }))
+}
+
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhooksV1Response {
+    pub webhooks: Vec<WebhookViewV1>,
+}
@@ -0,0 +1,202 @@
+use axum::{
+    Router,
+    extract::rejection::JsonRejection,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{
+        delete,
+        get,
+        post,
+    },
+};
+use dal::{
+    ChangeSetId,
+    ComponentId,
+    WsEventError,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_db::{
+    ManagementFuncExecutionError,
+    WorkspaceWebhook,
+    WorkspaceWebhookError,
+};
+use si_id::{
+    ManagementPrototypeId,
+    WorkspacePk,
+    WorkspaceWebhookId,
+};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::AppState;
+
+pub mod create_webhook;
+pub mod delete_webhook;
+pub mod deliver_webhook;
+pub mod list_webhooks;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum WebhooksError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] dal::ChangeSetError),
+    #[error("change set for webhook is not open: {0}")]
+    ChangeSetNotOpen(ChangeSetId),
+    #[error("component error: {0}")]
+    Component(#[from] dal::ComponentError),
+    #[error("diagram error: {0}")]
+    Diagram(#[from] dal::diagram::DiagramError),
+    #[error("invalid webhook payload: {0}")]
+    InvalidPayload(#[source] serde_json::Error),
+    #[error("webhook signature does not match the payload")]
+    InvalidSignature,
+    #[error("management func execution error: {0}")]
+    ManagementFuncExecution(#[from] ManagementFuncExecutionError),
+    #[error("management function already running for webhook: {0}")]
+    ManagementFunctionAlreadyRunning(WorkspaceWebhookId),
+    #[error("management prototype error: {0}")]
+    ManagementPrototype(#[from] dal::management::prototype::ManagementPrototypeError),
+    #[error("management prototype {0} is not valid for component {1}")]
+    ManagementPrototypeNotValidForComponent(ManagementPrototypeId, ComponentId),
+    #[error("missing webhook signature header: {0}")]
+    MissingSignature(&'static str),
+    #[error("changes not permitted on HEAD change set")]
+    NotPermittedOnHead,
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+    #[error("validation error: {0}")]
+    Validation(String),
+    #[error("webhook not found: {0}")]
+    WebhookNotFound(WorkspaceWebhookId),
+    #[error("workspace webhook error: {0}")]
+    WorkspaceWebhook(#[from] WorkspaceWebhookError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] Box<WsEventError>),
+}
+
+pub type WebhooksResult<T> = Result<T, WebhooksError>;
+
+impl From<WsEventError> for WebhooksError {
+    fn from(value: WsEventError) -> Self {
+        Box::new(value).into()
+    }
+}
+
+impl IntoResponse for WebhooksError {
+    fn into_response(self) -> axum::response::Response {
+        use crate::service::v1::common::ErrorIntoResponse;
+        self.to_api_response()
+    }
+}
+
+impl From<JsonRejection> for WebhooksError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonDataError(_) => {
+                WebhooksError::Validation(format!("Invalid JSON data format: {rejection}"))
+            }
+            JsonRejection::JsonSyntaxError(_) => {
+                WebhooksError::Validation(format!("Invalid JSON syntax: {rejection}"))
+            }
+            JsonRejection::MissingJsonContentType(_) => WebhooksError::Validation(
+                "Request must have Content-Type: application/json header".to_string(),
+            ),
+            _ => WebhooksError::Validation(format!("JSON validation error: {rejection}")),
+        }
+    }
+}
+
+impl crate::service::v1::common::ErrorIntoResponse for WebhooksError {
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            WebhooksError::Component(dal::ComponentError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            WebhooksError::InvalidPayload(_) | WebhooksError::Validation(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            WebhooksError::InvalidSignature | WebhooksError::MissingSignature(_) => {
+                (StatusCode::UNAUTHORIZED, self.to_string())
+            }
+            WebhooksError::ChangeSetNotOpen(_)
+            | WebhooksError::ManagementFunctionAlreadyRunning(_)
+            | WebhooksError::WorkspaceWebhook(WorkspaceWebhookError::NameAlreadyInUse(_)) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
+            WebhooksError::ManagementPrototypeNotValidForComponent(_, _) => {
+                (StatusCode::PRECONDITION_FAILED, self.to_string())
+            }
+            WebhooksError::NotPermittedOnHead => (StatusCode::BAD_REQUEST, self.to_string()),
+            WebhooksError::WebhookNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct WebhookV1RequestPath {
+    #[schema(value_type = String)]
+    pub webhook_id: WorkspaceWebhookId,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct WebhookDeliveryV1RequestPath {
+    #[schema(value_type = String)]
+    pub workspace_id: WorkspacePk,
+    #[schema(value_type = String)]
+    pub webhook_id: WorkspaceWebhookId,
+}
+
+/// A registered webhook. The signing secret is only ever returned when the webhook is created.
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookViewV1 {
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub id: WorkspaceWebhookId,
+    #[schema(example = "github-push")]
+    pub name: String,
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub component_id: ComponentId,
+    #[schema(value_type = String, example = "01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub management_prototype_id: ManagementPrototypeId,
+    /// The path that external systems deliver signed payloads to.
+    #[schema(example = "/v1/webhooks/01H9ZQD35JPMBGHH69BT0Q79VY/01H9ZQD35JPMBGHH69BT0Q79VY")]
+    pub delivery_path: String,
+}
+
+impl From<WorkspaceWebhook> for WebhookViewV1 {
+    fn from(webhook: WorkspaceWebhook) -> Self {
+        Self {
+            id: webhook.id,
+            delivery_path: delivery_path(webhook.workspace_id, webhook.id),
+            name: webhook.name,
+            component_id: webhook.component_id,
+            management_prototype_id: webhook.prototype_id,
+        }
+    }
+}
+
+fn delivery_path(workspace_id: WorkspacePk, webhook_id: WorkspaceWebhookId) -> String {
+    format!("/v1/webhooks/{workspace_id}/{webhook_id}")
+}
+
+/// Routes for managing the webhooks of a change set. These require an authenticated workspace
+/// member, like every other change set route.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_webhook::create_webhook))
+        .route("/", get(list_webhooks::list_webhooks))
+        .route("/:webhook_id", delete(delete_webhook::delete_webhook))
+}
+
+/// Routes that external systems deliver payloads to. These are not authenticated with a token;
+/// each delivery is instead authenticated by its signature.
+pub fn delivery_routes() -> Router<AppState> {
+    Router::new().route(
+        "/:workspace_id/:webhook_id",
+        post(deliver_webhook::deliver_webhook),
+    )
+}
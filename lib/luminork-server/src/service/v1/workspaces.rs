@@ -1,4 +1,5 @@
 use axum::{
+    Extension,
     Router,
     http::StatusCode,
     middleware,
@@ -17,6 +18,7 @@ use thiserror::Error;
 use super::common::ErrorIntoResponse;
 use crate::{
     AppState,
+    event_stream::EventStreams,
     extract::{
         change_set::TargetChangeSetIdentFromPath,
         workspace::{
@@ -24,7 +26,10 @@ use crate::{
             TargetWorkspaceIdFromPath,
         },
     },
-    middleware::WorkspacePermissionLayer,
+    middleware::{
+        IdempotencyLayer,
+        WorkspacePermissionLayer,
+    },
     service::v1::user::set_ai_agent_executed,
 };
 
@@ -73,9 +78,12 @@ pub fn routes(state: AppState) -> Router<AppState> {
                             .nest("/funcs", super::funcs::routes())
                             .nest("/actions", super::actions::routes())
                             .nest("/secrets", super::secrets::routes())
+                            .nest("/variables", super::variables::routes())
+                            .nest("/templates", super::templates::routes())
                             .nest("/management-funcs", super::management_funcs::routes())
                             .nest("/debug-funcs", super::debug_funcs::routes())
                             .nest("/policy-reports", super::policy_reports::routes())
+                            .nest("/webhooks", super::webhooks::routes())
                             .route(
                                 "/request_approval",
                                 post(super::change_sets::request_approval::request_approval),
@@ -97,15 +105,40 @@ pub fn routes(state: AppState) -> Router<AppState> {
                                 "/review",
                                 get(super::change_sets::review::review_change_set),
                             )
+                            .route("/history", get(super::change_sets::history::list_history))
+                            .route(
+                                "/history/objects/:kind/:id",
+                                get(super::change_sets::history::get_object_at),
+                            )
+                            .route(
+                                "/rebase",
+                                get(super::change_sets::rebase::get_rebase)
+                                    .post(super::change_sets::rebase::rebase_change_set),
+                            )
+                            .route(
+                                "/events",
+                                get(super::change_sets::events::stream_events)
+                                    .layer(Extension(EventStreams::default())),
+                            )
                             .route_layer(
                                 middleware::from_extractor::<TargetChangeSetIdentFromPath>(),
                             ),
                     ),
             )
+            .route("/usage", get(super::usage::get_workspace_usage))
+            .nest("/promotions", super::promotions::routes())
+            .nest(
+                "/module-trust",
+                super::module_trust::routes().route_layer(WorkspacePermissionLayer::new(
+                    state.clone(),
+                    permissions::Permission::Approve,
+                )),
+            )
             .nest(
                 "/user",
                 Router::new().route("/set_ai_agent_executed", post(set_ai_agent_executed)),
             )
+            .route_layer(IdempotencyLayer::new(state.clone()))
             .route_layer(middleware::from_extractor_with_state::<
                 AuthorizedForAutomationRole,
                 AppState,
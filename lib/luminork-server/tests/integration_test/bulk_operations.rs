@@ -0,0 +1,106 @@
+use dal::{
+    Component,
+    DalContext,
+    diagram::view::View,
+};
+use dal_test::{
+    Result,
+    prelude::ChangeSetTestHelpers,
+    test,
+};
+use luminork_server::service::v1::{
+    BulkComponentOperationStatusV1,
+    BulkComponentOperationsV1Request,
+    BulkComponentOperationsV1Response,
+    apply_bulk_operations,
+};
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+fn statuses(response: &BulkComponentOperationsV1Response) -> Vec<BulkComponentOperationStatusV1> {
+    response
+        .results
+        .iter()
+        .map(|result| result.status)
+        .collect()
+}
+
+// Without `atomic`, the operations that succeed are committed and the ones that fail (even
+// partway through) leave nothing behind.
+#[test]
+async fn bulk_operations_keep_successes_and_undo_failures(ctx: &mut DalContext) -> Result<()> {
+    let request: BulkComponentOperationsV1Request = serde_json::from_value(json!({
+        "operations": [
+            { "op": "create", "name": "kept", "schemaName": "starfield" },
+            // Fails after the component has been created
+            {
+                "op": "create",
+                "name": "discarded",
+                "schemaName": "starfield",
+                "attributes": { "/domain/DoesNotExist": "value" },
+            },
+            { "op": "addToView", "component": { "component": "kept" }, "viewName": "Kept" },
+            { "op": "addToView", "component": { "component": "discarded" }, "viewName": "Discarded" },
+        ],
+    }))?;
+
+    let response = apply_bulk_operations(ctx, request).await?;
+    assert!(response.committed);
+    assert_eq!(
+        vec![
+            BulkComponentOperationStatusV1::Succeeded,
+            BulkComponentOperationStatusV1::Failed,
+            BulkComponentOperationStatusV1::Succeeded,
+            BulkComponentOperationStatusV1::Failed,
+        ],
+        statuses(&response)
+    );
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    assert_eq!(
+        response.results[0].component_id,
+        Component::find_by_name(ctx, "kept").await?
+    );
+    assert!(Component::find_by_name(ctx, "discarded").await?.is_none());
+    assert!(View::find_by_name(ctx, "Kept").await?.is_some());
+    assert!(View::find_by_name(ctx, "Discarded").await?.is_none());
+
+    Ok(())
+}
+
+// With `atomic`, the operations after a failure are skipped and nothing is committed.
+#[test]
+async fn atomic_bulk_operations_commit_nothing_on_failure(ctx: &mut DalContext) -> Result<()> {
+    let request: BulkComponentOperationsV1Request = serde_json::from_value(json!({
+        "atomic": true,
+        "operations": [
+            { "op": "create", "name": "first", "schemaName": "starfield" },
+            { "op": "create", "name": "second", "schemaName": "does not exist" },
+            { "op": "create", "name": "third", "schemaName": "starfield" },
+        ],
+    }))?;
+
+    let response = apply_bulk_operations(ctx, request).await?;
+    assert!(!response.committed);
+    assert_eq!(
+        vec![
+            BulkComponentOperationStatusV1::Succeeded,
+            BulkComponentOperationStatusV1::Failed,
+            BulkComponentOperationStatusV1::Skipped,
+        ],
+        statuses(&response)
+    );
+    assert_eq!(
+        Some(404),
+        response.results[1]
+            .error
+            .as_ref()
+            .map(|error| error.status_code)
+    );
+
+    // Without a commit, the change set is left as it was
+    ctx.update_snapshot_to_visibility().await?;
+    assert!(Component::find_by_name(ctx, "first").await?.is_none());
+
+    Ok(())
+}
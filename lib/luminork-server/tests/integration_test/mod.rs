@@ -0,0 +1 @@
+mod bulk_operations;
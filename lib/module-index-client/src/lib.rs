@@ -135,6 +135,7 @@ impl ModuleIndexClient {
         module_schema_variant_id: Option<String>,
         module_schema_variant_version: Option<String>,
         module_is_private_scoped: Option<bool>,
+        module_signature: Option<ModuleSignature>,
     ) -> ModuleIndexClientResult<ModuleDetailsResponse> {
         let module_upload_part = reqwest::multipart::Part::bytes(module_bytes)
             .file_name(format!("{module_name}_{module_version}.tar"));
@@ -177,6 +178,10 @@ impl ModuleIndexClient {
             );
         }
 
+        if let Some(signature) = module_signature {
+            multipart_form = with_signature_parts(multipart_form, signature);
+        }
+
         let upload_url = self.base_url.join("modules")?;
         let upload_response = self
             .inner
@@ -199,6 +204,7 @@ impl ModuleIndexClient {
         module_bytes: Vec<u8>,
         module_schema_variant_id: Option<String>,
         module_schema_variant_version: Option<String>,
+        module_signature: Option<ModuleSignature>,
     ) -> ModuleIndexClientResult<bool> {
         let module_upload_part = reqwest::multipart::Part::bytes(module_bytes)
             .file_name(format!("{module_name}_{module_version}.tar"));
@@ -234,6 +240,10 @@ impl ModuleIndexClient {
             );
         }
 
+        if let Some(signature) = module_signature {
+            multipart_form = with_signature_parts(multipart_form, signature);
+        }
+
         let upsert_url = self.base_url.join("builtins/upsert")?;
         let upsert_response = self
             .inner
@@ -309,6 +319,192 @@ impl ModuleIndexClient {
         Ok(response)
     }
 
+    /// Fetches the publisher signature of a module, if it was signed when uploaded.
+    pub async fn module_signature(
+        &self,
+        module_id: Ulid,
+    ) -> ModuleIndexClientResult<Option<ModuleSignature>> {
+        let signature_url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{}/", module_id.to_string()))?
+            .join("signature")?;
+
+        let response = self.inner.get(signature_url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ModuleIndexClientError::ModuleNotFound(
+                module_id.to_string(),
+            ));
+        }
+
+        Ok(response.error_for_status()?.json().await?)
+    }
+
+    /// Registers a public key with which the authenticated user signs the modules they publish.
+    pub async fn register_publisher_key(
+        &self,
+        public_key: String,
+    ) -> ModuleIndexClientResult<PublisherKeyResponse> {
+        let url = self.base_url.join("publisher_keys")?;
+
+        Ok(self
+            .inner
+            .post(url)
+            .json(&RegisterPublisherKeyRequest { public_key })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Lists the registered publisher keys, optionally only those of a single user.
+    pub async fn list_publisher_keys(
+        &self,
+        owner_user_id: Option<&str>,
+    ) -> ModuleIndexClientResult<ListPublisherKeysResponse> {
+        let mut url = self.base_url.join("publisher_keys")?;
+        if let Some(owner_user_id) = owner_user_id {
+            url.query_pairs_mut()
+                .append_pair("ownerUserId", owner_user_id);
+        }
+
+        Ok(self
+            .inner
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Searches the latest module of each schema (route: GET /modules/search).
+    pub async fn search_modules(
+        &self,
+        request: &SearchModulesRequest,
+    ) -> ModuleIndexClientResult<ListModulesResponse> {
+        let url = self.base_url.join("modules/")?.join("search")?;
+
+        Ok(self
+            .inner
+            .get(url)
+            .query(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Lists the versions of a schema matching a version requirement, newest first (route: GET
+    /// /modules/versions).
+    pub async fn list_module_versions(
+        &self,
+        request: &ModuleVersionsRequest,
+    ) -> ModuleIndexClientResult<ListModulesResponse> {
+        let url = self.base_url.join("modules/")?.join("versions")?;
+
+        Ok(self
+            .inner
+            .get(url)
+            .query(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Resolves the newest version of a schema matching a version requirement (route: GET
+    /// /modules/resolve). Returns `None` if no version matches.
+    pub async fn resolve_module_version(
+        &self,
+        request: &ModuleVersionsRequest,
+    ) -> ModuleIndexClientResult<Option<ModuleDetailsResponse>> {
+        let url = self.base_url.join("modules/")?.join("resolve")?;
+
+        let response = self.inner.get(url).query(request).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(response.error_for_status()?.json().await?))
+    }
+
+    pub async fn update_module_tags(
+        &self,
+        module_id: Ulid,
+        tags: Vec<String>,
+    ) -> ModuleIndexClientResult<ModuleDetailsResponse> {
+        let url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{}/", module_id.to_string()))?
+            .join("tags")?;
+
+        let response = self
+            .inner
+            .put(url)
+            .json(&UpdateModuleTagsRequest { tags })
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ModuleIndexClientError::ModuleNotFound(
+                module_id.to_string(),
+            ));
+        }
+
+        Ok(response.error_for_status()?.json().await?)
+    }
+
+    /// Deprecates a module along with every other version of its schema.
+    pub async fn deprecate_module(
+        &self,
+        module_id: Ulid,
+        request: &DeprecateModuleRequest,
+    ) -> ModuleIndexClientResult<ModuleDetailsResponse> {
+        let url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{}/", module_id.to_string()))?
+            .join("deprecate")?;
+
+        let response = self.inner.post(url).json(request).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ModuleIndexClientError::ModuleNotFound(
+                module_id.to_string(),
+            ));
+        }
+
+        Ok(response.error_for_status()?.json().await?)
+    }
+
+    pub async fn undeprecate_module(
+        &self,
+        module_id: Ulid,
+    ) -> ModuleIndexClientResult<ModuleDetailsResponse> {
+        let url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{}/", module_id.to_string()))?
+            .join("undeprecate")?;
+
+        let response = self.inner.post(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ModuleIndexClientError::ModuleNotFound(
+                module_id.to_string(),
+            ));
+        }
+
+        Ok(response.error_for_status()?.json().await?)
+    }
+
     pub async fn get_builtin(&self, module_id: Ulid) -> ModuleIndexClientResult<Vec<u8>> {
         let download_url = self
             .base_url
@@ -411,3 +607,18 @@ impl ModuleIndexClient {
             .await?)
     }
 }
+
+fn with_signature_parts(
+    multipart_form: reqwest::multipart::Form,
+    signature: ModuleSignature,
+) -> reqwest::multipart::Form {
+    multipart_form
+        .part(
+            MODULE_SIGNATURE_FIELD_NAME,
+            reqwest::multipart::Part::text(signature.signature),
+        )
+        .part(
+            MODULE_SIGNING_KEY_ID_FIELD_NAME,
+            reqwest::multipart::Part::text(signature.key_id),
+        )
+}
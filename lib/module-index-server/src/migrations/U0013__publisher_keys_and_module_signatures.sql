@@ -0,0 +1,13 @@
+CREATE TABLE publisher_keys
+(
+    key_id                      text primary key,
+    public_key                  text                     NOT NULL,
+    owner_user_id               ident                    NOT NULL,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE INDEX idx_publisher_keys_owner_user_id ON publisher_keys (owner_user_id);
+
+ALTER TABLE modules
+    ADD signature TEXT,
+    ADD signing_key_id TEXT REFERENCES publisher_keys (key_id),
+    ADD signing_public_key TEXT;
@@ -0,0 +1,18 @@
+ALTER TABLE modules
+    ADD category TEXT,
+    ADD tags TEXT[] NOT NULL DEFAULT '{}',
+    ADD deprecated_at timestamp with time zone,
+    ADD deprecated_by_display_name TEXT,
+    ADD deprecation_message TEXT,
+    ADD replaced_by_module_id ident REFERENCES modules (id),
+    ADD download_count BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE modules
+    ADD search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(name, '')), 'A')
+        || setweight(to_tsvector('english', coalesce(category, '')), 'B')
+        || setweight(to_tsvector('english', coalesce(description, '')), 'C')
+    ) STORED;
+
+CREATE INDEX idx_modules_search_vector ON modules USING GIN (search_vector);
+CREATE INDEX idx_modules_tags ON modules USING GIN (tags);
@@ -1 +1,2 @@
+pub mod publisher_key;
 pub mod si_module;
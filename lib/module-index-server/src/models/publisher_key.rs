@@ -0,0 +1,32 @@
+use module_index_types::PublisherKeyResponse;
+use sea_orm::entity::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "publisher_keys")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub key_id: String,
+    #[sea_orm(column_type = "Text")]
+    pub public_key: String,
+    pub owner_user_id: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub fn make_publisher_key_response(key: Model) -> PublisherKeyResponse {
+    PublisherKeyResponse {
+        key_id: key.key_id,
+        public_key: key.public_key,
+        owner_user_id: key.owner_user_id,
+        created_at: key.created_at.into(),
+    }
+}
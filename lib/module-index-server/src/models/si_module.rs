@@ -2,13 +2,16 @@ use std::str::FromStr;
 
 use module_index_types::{
     LatestModuleResponse,
+    ModuleDeprecation,
     ModuleDetailsResponse,
+    ModuleSignature,
 };
 use sea_orm::{
     TryGetError,
     entity::prelude::*,
     sea_query,
 };
+use semver::Version;
 use serde::{
     Deserialize,
     Serialize,
@@ -47,6 +50,94 @@ pub struct Model {
     pub schema_variant_id: Option<SchemaVariantId>,
     pub schema_variant_version: Option<String>,
     pub is_private_scoped: bool,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub signature: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub signing_key_id: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub signing_public_key: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub deprecated_at: Option<DateTimeWithTimeZone>,
+    pub deprecated_by_display_name: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub deprecation_message: Option<String>,
+    #[sea_orm(column_type = r##"custom("ident")"##, nullable)]
+    pub replaced_by_module_id: Option<ModuleId>,
+    pub download_count: i64,
+}
+
+impl Model {
+    /// Returns the publisher signature of the module, if it was signed when uploaded.
+    pub fn signature(&self) -> Option<ModuleSignature> {
+        match (
+            &self.signature,
+            &self.signing_key_id,
+            &self.signing_public_key,
+        ) {
+            (Some(signature), Some(key_id), Some(public_key)) => Some(ModuleSignature {
+                key_id: key_id.to_owned(),
+                public_key: public_key.to_owned(),
+                signature: signature.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the deprecation marker of the module, if it has been deprecated.
+    pub fn deprecation(&self) -> Option<ModuleDeprecation> {
+        self.deprecated_at.map(|deprecated_at| ModuleDeprecation {
+            deprecated_at: deprecated_at.into(),
+            deprecated_by_display_name: self.deprecated_by_display_name.to_owned(),
+            message: self.deprecation_message.to_owned(),
+            replaced_by_module_id: self.replaced_by_module_id.map(|id| id.to_string()),
+        })
+    }
+
+    /// Returns the schema variant version of the module read as a semantic version.
+    ///
+    /// Versions are read leniently: a leading `v` is ignored and missing minor or patch
+    /// components are taken to be zero, so `v2` reads as `2.0.0` and `1.4` as `1.4.0`.
+    pub fn semantic_version(&self) -> Option<Version> {
+        self.schema_variant_version
+            .as_deref()
+            .and_then(parse_semantic_version)
+    }
+}
+
+/// Counts a download of a module.
+pub async fn record_download(txn: &impl ConnectionTrait, module_id: ModuleId) -> Result<(), DbErr> {
+    Entity::update_many()
+        .col_expr(
+            Column::DownloadCount,
+            sea_query::Expr::col(Column::DownloadCount).add(1),
+        )
+        .filter(Column::Id.eq(module_id))
+        .exec(txn)
+        .await?;
+
+    Ok(())
+}
+
+/// Reads a version string as a semantic version (see [`Model::semantic_version`]).
+pub fn parse_semantic_version(version: &str) -> Option<Version> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    if let Ok(parsed) = Version::parse(version) {
+        return Some(parsed);
+    }
+
+    // Pad out missing minor and patch components, keeping any pre-release or build suffix
+    let core_end = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, suffix) = version.split_at(core_end);
+    let padding = match core.split('.').count() {
+        1 => ".0.0",
+        2 => ".0",
+        _ => return None,
+    };
+
+    Version::parse(&format!("{core}{padding}{suffix}")).ok()
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -146,6 +237,8 @@ pub fn make_module_details_response(
     module: Model,
     linked_modules: Vec<Model>,
 ) -> ModuleDetailsResponse {
+    let signature = module.signature();
+    let deprecation = module.deprecation();
     ModuleDetailsResponse {
         id: module.id.to_string(),
         name: module.name,
@@ -168,6 +261,20 @@ pub fn make_module_details_response(
                 .collect(),
         ),
         structural_hash: module.structural_hash,
+        signature,
+        category: module.category,
+        tags: module.tags,
+        deprecation,
+        download_count: module.download_count,
+    }
+}
+
+/// Builds the details of a module without looking up the hashes of its past versions, for
+/// responses listing many modules.
+pub fn make_module_details_response_without_past_hashes(module: Model) -> ModuleDetailsResponse {
+    ModuleDetailsResponse {
+        past_hashes: None,
+        ..make_module_details_response(module, vec![])
     }
 }
 
@@ -13,7 +13,17 @@ SELECT DISTINCT ON (schema_id)
     kind,
     is_builtin_at,
     is_builtin_at_by_display_name,
-    schema_id
+    schema_id,
+    signature,
+    signing_key_id,
+    signing_public_key,
+    category,
+    tags,
+    deprecated_at,
+    deprecated_by_display_name,
+    deprecation_message,
+    replaced_by_module_id,
+    download_count
 FROM
     modules
 WHERE
@@ -0,0 +1,28 @@
+SELECT latest_modules.*
+FROM (
+    SELECT DISTINCT ON (COALESCE(schema_id, id))
+        modules.*,
+        CASE
+            WHEN $1 = '' THEN 0
+            ELSE ts_rank(search_vector, websearch_to_tsquery('english', $1))
+        END AS search_rank
+    FROM
+        modules
+    WHERE
+        rejected_at IS NULL
+        AND kind = 'module'
+        AND is_private_scoped = FALSE
+        AND ($2 OR is_builtin_at IS NOT NULL OR owner_user_id = $3)
+        AND ($6 OR deprecated_at IS NULL)
+    ORDER BY
+        COALESCE(schema_id, id),
+        created_at DESC
+) AS latest_modules
+WHERE
+    ($1 = '' OR search_vector @@ websearch_to_tsquery('english', $1))
+    AND tags @> $4
+    AND ($5::text IS NULL OR lower(category) = lower($5))
+ORDER BY
+    search_rank DESC,
+    name ASC
+LIMIT $7;
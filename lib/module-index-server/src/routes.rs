@@ -9,6 +9,7 @@ use axum::{
     routing::{
         get,
         post,
+        put,
     },
 };
 use hyper::StatusCode;
@@ -23,15 +24,21 @@ use tower_http::{
     cors::CorsLayer,
 };
 
+mod deprecate_module_route;
 mod download_builtin_route;
 mod download_module_route;
 mod download_workspace_route;
 mod get_module_details_route;
+mod get_module_signature_route;
 mod list_builtins_route;
 mod list_latest_modules_route;
 mod list_modules_route;
+mod module_versions_route;
 pub(crate) mod promote_builtin_route;
+mod publisher_keys_route;
 pub(crate) mod reject_module_route;
+mod search_modules_route;
+mod update_module_tags_route;
 pub(crate) mod upsert_builtin_route;
 pub(crate) mod upsert_module_route;
 mod upsert_workspace_route;
@@ -54,6 +61,18 @@ pub fn routes(state: AppState) -> Router {
             "/modules/latest",
             get(list_latest_modules_route::list_latest_modules_route),
         )
+        .route(
+            "/modules/search",
+            get(search_modules_route::search_modules_route),
+        )
+        .route(
+            "/modules/versions",
+            get(module_versions_route::list_module_versions_route),
+        )
+        .route(
+            "/modules/resolve",
+            get(module_versions_route::resolve_module_version_route),
+        )
         .route("/builtins", get(list_builtins_route::list_builtins_route))
         .route(
             "/builtins/:module_id/promote",
@@ -84,10 +103,31 @@ pub fn routes(state: AppState) -> Router {
             "/modules/:module_id/download_builtin",
             get(download_builtin_route::download_builtin_route),
         )
+        .route(
+            "/modules/:module_id/signature",
+            get(get_module_signature_route::get_module_signature_route),
+        )
+        .route(
+            "/publisher_keys",
+            get(publisher_keys_route::list_publisher_keys_route)
+                .post(publisher_keys_route::register_publisher_key_route),
+        )
         .route(
             "/modules/:module_id/reject",
             post(reject_module_route::reject_module),
         )
+        .route(
+            "/modules/:module_id/tags",
+            put(update_module_tags_route::update_module_tags_route),
+        )
+        .route(
+            "/modules/:module_id/deprecate",
+            post(deprecate_module_route::deprecate_module_route),
+        )
+        .route(
+            "/modules/:module_id/undeprecate",
+            post(deprecate_module_route::undeprecate_module_route),
+        )
         .layer(CorsLayer::permissive())
         .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
         .layer(CompressionLayer::new());
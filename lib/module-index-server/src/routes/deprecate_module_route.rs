@@ -0,0 +1,233 @@
+use std::str::FromStr;
+
+use axum::{
+    Json,
+    extract::{
+        Path,
+        State,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use chrono::{
+    DateTime,
+    FixedOffset,
+    Offset,
+    Utc,
+};
+use hyper::StatusCode;
+use module_index_types::{
+    DeprecateModuleRequest,
+    ModuleDetailsResponse,
+};
+use sea_orm::{
+    ColumnTrait,
+    Condition,
+    DatabaseTransaction,
+    DbErr,
+    EntityTrait,
+    QueryFilter,
+    sea_query::Expr,
+};
+use thiserror::Error;
+
+use crate::{
+    app_state::AppState,
+    extract::{
+        Authorization,
+        DbConnection,
+    },
+    models::si_module::{
+        self,
+        ModuleId,
+        make_module_details_response_without_past_hashes,
+    },
+    whoami::{
+        WhoamiError,
+        get_email_for_auth_token,
+        is_systeminit_email,
+    },
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum DeprecateModuleError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("only the owner of module {0} can change its deprecation")]
+    Forbidden(ModuleId),
+    #[error(r#"Module "{0}" not found"#)]
+    NotFound(ModuleId),
+    #[error("module {0} cannot be replaced by a version of itself")]
+    ReplacedBySameSchema(ModuleId),
+    #[error(r#"replacement module "{0}" not found"#)]
+    ReplacementNotFound(ModuleId),
+    #[error("Ulid decode error: {0}")]
+    UlidDecode(#[from] ulid::DecodeError),
+    #[error("whoami error: {0}")]
+    Whoami(#[from] WhoamiError),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for DeprecateModuleError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::Forbidden(_) => (StatusCode::FORBIDDEN, self.to_string()),
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            Self::ReplacedBySameSchema(_) | Self::ReplacementNotFound(_) | Self::UlidDecode(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Marks a module, and every other version of its schema, as deprecated.
+///
+/// Deprecated modules are left out of searches and version resolution unless asked for, and point
+/// at the module that replaces them, if any. Only the owner of the module or a systeminit user
+/// may deprecate it.
+pub async fn deprecate_module_route(
+    Path(module_id): Path<ModuleId>,
+    Authorization {
+        user_claim,
+        auth_token,
+    }: Authorization,
+    DbConnection(txn): DbConnection,
+    State(state): State<AppState>,
+    Json(request): Json<DeprecateModuleRequest>,
+) -> Result<Json<ModuleDetailsResponse>, DeprecateModuleError> {
+    let email =
+        get_email_for_auth_token(state.auth_api_url(), &auth_token, state.token_emails()).await?;
+    let module = si_module::Entity::find_by_id(module_id)
+        .one(&txn)
+        .await?
+        .ok_or(DeprecateModuleError::NotFound(module_id))?;
+    if module.owner_user_id != user_claim.user_id().to_string() && !is_systeminit_email(&email) {
+        return Err(DeprecateModuleError::Forbidden(module_id));
+    }
+
+    let replaced_by_module_id = match request.replaced_by_module_id {
+        Some(replacement_id) => {
+            let replacement_id = ModuleId::from_str(&replacement_id)?;
+            let replacement = si_module::Entity::find_by_id(replacement_id)
+                .filter(si_module::Column::RejectedAt.is_null())
+                .one(&txn)
+                .await?
+                .ok_or(DeprecateModuleError::ReplacementNotFound(replacement_id))?;
+            if replacement.id == module.id
+                || (replacement.schema_id.is_some() && replacement.schema_id == module.schema_id)
+            {
+                return Err(DeprecateModuleError::ReplacedBySameSchema(module_id));
+            }
+            Some(replacement_id)
+        }
+        None => None,
+    };
+
+    si_module::Entity::update_many()
+        .col_expr(
+            si_module::Column::DeprecatedAt,
+            Expr::value(Some(DateTime::<FixedOffset>::from_naive_utc_and_offset(
+                Utc::now().naive_utc(),
+                Utc.fix(),
+            ))),
+        )
+        .col_expr(
+            si_module::Column::DeprecatedByDisplayName,
+            Expr::value(Some(email)),
+        )
+        .col_expr(
+            si_module::Column::DeprecationMessage,
+            Expr::value(request.message),
+        )
+        .col_expr(
+            si_module::Column::ReplacedByModuleId,
+            Expr::value(replaced_by_module_id),
+        )
+        .filter(same_schema_condition(&module))
+        .exec(&txn)
+        .await?;
+
+    respond_with_module(txn, module_id).await
+}
+
+/// Clears the deprecation of a module and every other version of its schema.
+pub async fn undeprecate_module_route(
+    Path(module_id): Path<ModuleId>,
+    Authorization {
+        user_claim,
+        auth_token,
+    }: Authorization,
+    DbConnection(txn): DbConnection,
+    State(state): State<AppState>,
+) -> Result<Json<ModuleDetailsResponse>, DeprecateModuleError> {
+    let module = si_module::Entity::find_by_id(module_id)
+        .one(&txn)
+        .await?
+        .ok_or(DeprecateModuleError::NotFound(module_id))?;
+    if module.owner_user_id != user_claim.user_id().to_string() {
+        let email =
+            get_email_for_auth_token(state.auth_api_url(), &auth_token, state.token_emails())
+                .await?;
+        if !is_systeminit_email(&email) {
+            return Err(DeprecateModuleError::Forbidden(module_id));
+        }
+    }
+
+    si_module::Entity::update_many()
+        .col_expr(
+            si_module::Column::DeprecatedAt,
+            Expr::value(Option::<DateTime<FixedOffset>>::None),
+        )
+        .col_expr(
+            si_module::Column::DeprecatedByDisplayName,
+            Expr::value(Option::<String>::None),
+        )
+        .col_expr(
+            si_module::Column::DeprecationMessage,
+            Expr::value(Option::<String>::None),
+        )
+        .col_expr(
+            si_module::Column::ReplacedByModuleId,
+            Expr::value(Option::<ModuleId>::None),
+        )
+        .filter(same_schema_condition(&module))
+        .exec(&txn)
+        .await?;
+
+    respond_with_module(txn, module_id).await
+}
+
+/// Matches every version of the schema of the module, or only the module itself if it has no
+/// schema.
+fn same_schema_condition(module: &si_module::Model) -> Condition {
+    match module.schema_id {
+        Some(schema_id) => Condition::all().add(si_module::Column::SchemaId.eq(schema_id)),
+        None => Condition::all().add(si_module::Column::Id.eq(module.id)),
+    }
+}
+
+async fn respond_with_module(
+    txn: DatabaseTransaction,
+    module_id: ModuleId,
+) -> Result<Json<ModuleDetailsResponse>, DeprecateModuleError> {
+    let module = si_module::Entity::find_by_id(module_id)
+        .one(&txn)
+        .await?
+        .ok_or(DeprecateModuleError::NotFound(module_id))?;
+
+    txn.commit().await?;
+
+    Ok(Json(make_module_details_response_without_past_hashes(
+        module,
+    )))
+}
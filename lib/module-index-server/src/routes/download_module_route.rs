@@ -65,7 +65,10 @@ pub async fn download_module_route(
         _ => return Err(DownloadModuleError::NotFound(module_id)),
     };
 
-    Ok(Redirect::temporary(
-        &bucket.url_for_module(module.latest_hash).await?,
-    ))
+    let url = bucket.url_for_module(module.latest_hash).await?;
+
+    si_module::record_download(&txn, module_id).await?;
+    txn.commit().await?;
+
+    Ok(Redirect::temporary(&url))
 }
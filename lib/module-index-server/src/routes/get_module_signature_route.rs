@@ -0,0 +1,64 @@
+use axum::{
+    Json,
+    extract::Path,
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use hyper::StatusCode;
+use module_index_types::ModuleSignature;
+use sea_orm::{
+    DbErr,
+    EntityTrait,
+};
+use thiserror::Error;
+
+use crate::{
+    extract::{
+        Authorization,
+        DbConnection,
+    },
+    models::si_module::{
+        self,
+        ModuleId,
+    },
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum GetModuleSignatureError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error(r#"Module "{0}" not found"#)]
+    NotFound(ModuleId),
+}
+
+impl IntoResponse for GetModuleSignatureError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Returns the publisher signature of a module, or `null` if the module was uploaded unsigned.
+pub async fn get_module_signature_route(
+    Path(module_id): Path<ModuleId>,
+    Authorization { .. }: Authorization,
+    DbConnection(txn): DbConnection,
+) -> Result<Json<Option<ModuleSignature>>, GetModuleSignatureError> {
+    let module = si_module::Entity::find_by_id(module_id)
+        .one(&txn)
+        .await?
+        .ok_or(GetModuleSignatureError::NotFound(module_id))?;
+
+    Ok(Json(module.signature()))
+}
@@ -0,0 +1,162 @@
+use std::{
+    cmp::Reverse,
+    str::FromStr,
+};
+
+use axum::{
+    Json,
+    extract::Query,
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use hyper::StatusCode;
+use module_index_types::{
+    ListModulesResponse,
+    ModuleDetailsResponse,
+    ModuleVersionsRequest,
+};
+use sea_orm::{
+    ColumnTrait,
+    Condition,
+    DatabaseTransaction,
+    DbErr,
+    EntityTrait,
+    QueryFilter,
+};
+use semver::VersionReq;
+use thiserror::Error;
+
+use crate::{
+    extract::{
+        Authorization,
+        DbConnection,
+    },
+    models::si_module::{
+        self,
+        ModuleKind,
+        SchemaId,
+        make_module_details_response_without_past_hashes,
+    },
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ModuleVersionsError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("invalid version requirement {0:?}: {1}")]
+    InvalidVersionRequirement(String, #[source] semver::Error),
+    #[error("either a schema id or a module name is required")]
+    MissingModuleIdentifier,
+    #[error("no module version matches the request")]
+    NoMatchingVersion,
+    #[error("Ulid decode error: {0}")]
+    UlidDecode(#[from] ulid::DecodeError),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for ModuleVersionsError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::InvalidVersionRequirement(..)
+            | Self::MissingModuleIdentifier
+            | Self::UlidDecode(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::NoMatchingVersion => (StatusCode::NOT_FOUND, self.to_string()),
+            Self::DbErr(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Lists the versions of a schema matching a version requirement, newest first.
+pub async fn list_module_versions_route(
+    Authorization { user_claim, .. }: Authorization,
+    DbConnection(txn): DbConnection,
+    Query(request): Query<ModuleVersionsRequest>,
+) -> Result<Json<ListModulesResponse>, ModuleVersionsError> {
+    let modules = find_matching_versions(&txn, &user_claim.user_id().to_string(), request)
+        .await?
+        .into_iter()
+        .map(make_module_details_response_without_past_hashes)
+        .collect();
+
+    Ok(Json(ListModulesResponse { modules }))
+}
+
+/// Returns the newest version of a schema matching a version requirement, for example the latest
+/// `2.x` of `AWS::EC2::Instance`.
+pub async fn resolve_module_version_route(
+    Authorization { user_claim, .. }: Authorization,
+    DbConnection(txn): DbConnection,
+    Query(request): Query<ModuleVersionsRequest>,
+) -> Result<Json<ModuleDetailsResponse>, ModuleVersionsError> {
+    let module = find_matching_versions(&txn, &user_claim.user_id().to_string(), request)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(ModuleVersionsError::NoMatchingVersion)?;
+
+    Ok(Json(make_module_details_response_without_past_hashes(
+        module,
+    )))
+}
+
+/// Finds the builtin and caller owned modules of a schema whose semantic version matches the
+/// requested requirement, ordered from the newest version to the oldest. Without a requirement,
+/// every module with a semantic version matches.
+async fn find_matching_versions(
+    txn: &DatabaseTransaction,
+    user_id: &str,
+    request: ModuleVersionsRequest,
+) -> Result<Vec<si_module::Model>, ModuleVersionsError> {
+    let version_req = match request.version.as_deref().map(str::trim) {
+        None | Some("") | Some("latest") => VersionReq::STAR,
+        Some(version) => VersionReq::parse(version).map_err(|err| {
+            ModuleVersionsError::InvalidVersionRequirement(version.to_owned(), err)
+        })?,
+    };
+
+    let query = si_module::Entity::find()
+        .filter(si_module::Column::RejectedAt.is_null())
+        .filter(si_module::Column::Kind.eq(ModuleKind::Module))
+        .filter(si_module::Column::IsPrivateScoped.eq(false))
+        .filter(
+            Condition::any()
+                .add(si_module::Column::IsBuiltinAt.is_not_null())
+                .add(si_module::Column::OwnerUserId.eq(user_id)),
+        );
+    let query = match (request.schema_id, request.name) {
+        (Some(schema_id), _) => {
+            query.filter(si_module::Column::SchemaId.eq(SchemaId::from_str(&schema_id)?))
+        }
+        (None, Some(name)) => query.filter(si_module::Column::Name.eq(name)),
+        (None, None) => return Err(ModuleVersionsError::MissingModuleIdentifier),
+    };
+    let query = if request.include_deprecated.unwrap_or(false) {
+        query
+    } else {
+        query.filter(si_module::Column::DeprecatedAt.is_null())
+    };
+
+    let mut versions: Vec<_> = query
+        .all(txn)
+        .await?
+        .into_iter()
+        .filter_map(|module| {
+            module
+                .semantic_version()
+                .filter(|version| version_req.matches(version))
+                .map(|version| (version, module))
+        })
+        .collect();
+    versions.sort_by_key(|(version, module)| Reverse((version.clone(), module.created_at)));
+
+    Ok(versions.into_iter().map(|(_, module)| module).collect())
+}
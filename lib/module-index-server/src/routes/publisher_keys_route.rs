@@ -0,0 +1,139 @@
+use axum::{
+    Json,
+    extract::Query,
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use chrono::{
+    DateTime,
+    FixedOffset,
+    Offset,
+    Utc,
+};
+use hyper::StatusCode;
+use module_index_types::{
+    ListPublisherKeysResponse,
+    PublisherKeyResponse,
+    RegisterPublisherKeyRequest,
+};
+use sea_orm::{
+    ActiveModelTrait,
+    ColumnTrait,
+    DbErr,
+    EntityTrait,
+    QueryFilter,
+    QueryOrder,
+    Set,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_crypto::{
+    PackageSigningError,
+    PackageVerifyingKey,
+};
+use thiserror::Error;
+
+use crate::{
+    extract::{
+        Authorization,
+        DbConnection,
+    },
+    models::publisher_key::{
+        self,
+        make_publisher_key_response,
+    },
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum PublisherKeyError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("invalid publisher key: {0}")]
+    InvalidKey(#[from] PackageSigningError),
+    #[error("publisher key {0} is registered to another user")]
+    KeyOwnedByAnotherUser(String),
+}
+
+impl IntoResponse for PublisherKeyError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::InvalidKey(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::KeyOwnedByAnotherUser(_) => (StatusCode::CONFLICT, self.to_string()),
+            Self::DbErr(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPublisherKeysRequest {
+    pub owner_user_id: Option<String>,
+}
+
+/// Registers a public key with which the calling user will sign the modules they publish.
+///
+/// Registering a key that is already registered to the calling user is a no-op.
+pub async fn register_publisher_key_route(
+    Authorization { user_claim, .. }: Authorization,
+    DbConnection(txn): DbConnection,
+    Json(request): Json<RegisterPublisherKeyRequest>,
+) -> Result<Json<PublisherKeyResponse>, PublisherKeyError> {
+    let verifying_key = PackageVerifyingKey::from_base64(&request.public_key)?;
+    let key_id = verifying_key.key_id();
+    let owner_user_id = user_claim.user_id().to_string();
+
+    let key = match publisher_key::Entity::find_by_id(key_id.clone())
+        .one(&txn)
+        .await?
+    {
+        Some(existing) if existing.owner_user_id == owner_user_id => existing,
+        Some(_) => return Err(PublisherKeyError::KeyOwnedByAnotherUser(key_id)),
+        None => {
+            publisher_key::ActiveModel {
+                key_id: Set(key_id),
+                public_key: Set(verifying_key.to_base64()),
+                owner_user_id: Set(owner_user_id),
+                created_at: Set(DateTime::<FixedOffset>::from_naive_utc_and_offset(
+                    Utc::now().naive_utc(),
+                    Utc.fix(),
+                )),
+            }
+            .insert(&txn)
+            .await?
+        }
+    };
+
+    txn.commit().await?;
+
+    Ok(Json(make_publisher_key_response(key)))
+}
+
+pub async fn list_publisher_keys_route(
+    DbConnection(txn): DbConnection,
+    Query(request): Query<ListPublisherKeysRequest>,
+) -> Result<Json<ListPublisherKeysResponse>, PublisherKeyError> {
+    let mut query = publisher_key::Entity::find().order_by_asc(publisher_key::Column::CreatedAt);
+    if let Some(owner_user_id) = request.owner_user_id {
+        query = query.filter(publisher_key::Column::OwnerUserId.eq(owner_user_id));
+    }
+
+    let keys = query
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(make_publisher_key_response)
+        .collect();
+
+    Ok(Json(ListPublisherKeysResponse { keys }))
+}
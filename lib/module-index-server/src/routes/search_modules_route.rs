@@ -0,0 +1,118 @@
+use axum::{
+    Json,
+    extract::{
+        Query,
+        State,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use hyper::StatusCode;
+use module_index_types::{
+    ListModulesResponse,
+    SearchModulesRequest,
+};
+use sea_orm::{
+    DbBackend,
+    DbErr,
+    EntityTrait,
+    Statement,
+};
+use thiserror::Error;
+
+use crate::{
+    app_state::AppState,
+    extract::{
+        Authorization,
+        DbConnection,
+    },
+    models::si_module::{
+        self,
+        make_module_details_response_without_past_hashes,
+    },
+    routes::upsert_module_route::normalize_tags,
+    whoami::{
+        WhoamiError,
+        is_systeminit_auth_token,
+    },
+};
+
+const SEARCH_MODULES_QUERY: &str = include_str!("../queries/search_modules.sql");
+
+const DEFAULT_SEARCH_LIMIT: u64 = 50;
+const MAX_SEARCH_LIMIT: u64 = 500;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum SearchModulesError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("whoami error: {0}")]
+    Whoami(#[from] WhoamiError),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for SearchModulesError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Searches the latest module of each schema by name, description and category.
+///
+/// Results are ranked by how well they match the query. Builtins and the modules owned by the
+/// caller are searched, or every module when a systeminit user passes `su`. Deprecated versions
+/// are skipped before picking the latest one, unless they are asked for.
+pub async fn search_modules_route(
+    Authorization {
+        user_claim,
+        auth_token,
+    }: Authorization,
+    DbConnection(txn): DbConnection,
+    Query(request): Query<SearchModulesRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<ListModulesResponse>, SearchModulesError> {
+    let su = request.su.unwrap_or(false)
+        && is_systeminit_auth_token(state.auth_api_url(), &auth_token, state.token_emails())
+            .await?;
+
+    let tags = request
+        .tags
+        .as_deref()
+        .map(|tags| normalize_tags(tags.split(',')))
+        .unwrap_or_default();
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+
+    let modules = si_module::Entity::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            SEARCH_MODULES_QUERY,
+            [
+                request.q.unwrap_or_default().trim().into(),
+                su.into(),
+                user_claim.user_id().to_string().into(),
+                tags.into(),
+                request.category.into(),
+                request.include_deprecated.unwrap_or(false).into(),
+                (limit as i64).into(),
+            ],
+        ))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(make_module_details_response_without_past_hashes)
+        .collect();
+
+    Ok(Json(ListModulesResponse { modules }))
+}
@@ -0,0 +1,105 @@
+use axum::{
+    Json,
+    extract::{
+        Path,
+        State,
+    },
+    response::{
+        IntoResponse,
+        Response,
+    },
+};
+use hyper::StatusCode;
+use module_index_types::{
+    ModuleDetailsResponse,
+    UpdateModuleTagsRequest,
+};
+use sea_orm::{
+    ActiveModelTrait,
+    DbErr,
+    EntityTrait,
+    Set,
+};
+use thiserror::Error;
+
+use crate::{
+    app_state::AppState,
+    extract::{
+        Authorization,
+        DbConnection,
+    },
+    models::si_module::{
+        self,
+        ModuleId,
+        make_module_details_response_without_past_hashes,
+    },
+    routes::upsert_module_route::normalize_tags,
+    whoami::{
+        WhoamiError,
+        is_systeminit_auth_token,
+    },
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum UpdateModuleTagsError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("only the owner of module {0} can change its tags")]
+    Forbidden(ModuleId),
+    #[error(r#"Module "{0}" not found"#)]
+    NotFound(ModuleId),
+    #[error("whoami error: {0}")]
+    Whoami(#[from] WhoamiError),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for UpdateModuleTagsError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::Forbidden(_) => (StatusCode::FORBIDDEN, self.to_string()),
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Replaces the tags of a module. Only the owner of the module or a systeminit user may do so.
+pub async fn update_module_tags_route(
+    Path(module_id): Path<ModuleId>,
+    Authorization {
+        user_claim,
+        auth_token,
+    }: Authorization,
+    DbConnection(txn): DbConnection,
+    State(state): State<AppState>,
+    Json(request): Json<UpdateModuleTagsRequest>,
+) -> Result<Json<ModuleDetailsResponse>, UpdateModuleTagsError> {
+    let module = si_module::Entity::find_by_id(module_id)
+        .one(&txn)
+        .await?
+        .ok_or(UpdateModuleTagsError::NotFound(module_id))?;
+
+    if module.owner_user_id != user_claim.user_id().to_string()
+        && !is_systeminit_auth_token(state.auth_api_url(), &auth_token, state.token_emails())
+            .await?
+    {
+        return Err(UpdateModuleTagsError::Forbidden(module_id));
+    }
+
+    let mut active_module: si_module::ActiveModel = module.into();
+    active_module.tags = Set(normalize_tags(request.tags.iter().map(String::as_str)));
+    let updated_module = active_module.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(Json(make_module_details_response_without_past_hashes(
+        updated_module,
+    )))
+}
@@ -28,7 +28,11 @@ use module_index_types::{
     MODULE_SCHEMA_ID_FIELD_NAME,
     MODULE_SCHEMA_VARIANT_ID_FIELD_NAME,
     MODULE_SCHEMA_VARIANT_VERSION_FIELD_NAME,
+    MODULE_SIGNATURE_FIELD_NAME,
+    MODULE_SIGNING_KEY_ID_FIELD_NAME,
+    MODULE_TAGS_FIELD_NAME,
     ModuleDetailsResponse,
+    ModuleSignature,
 };
 use s3::error::S3Error;
 use sea_orm::{
@@ -37,6 +41,7 @@ use sea_orm::{
     DbErr,
     EntityTrait,
     QueryFilter,
+    QueryOrder,
     QuerySelect,
     Set,
 };
@@ -44,6 +49,11 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use si_crypto::{
+    PackageSignature,
+    PackageSigningError,
+    PackageVerifyingKey,
+};
 use si_pkg::{
     SiPkg,
     SiPkgError,
@@ -58,13 +68,16 @@ use crate::{
         DbConnection,
         ExtractedS3Bucket,
     },
-    models::si_module::{
-        self,
-        ModuleId,
-        ModuleKind,
-        SchemaId,
-        SchemaVariantId,
-        make_module_details_response,
+    models::{
+        publisher_key,
+        si_module::{
+            self,
+            ModuleId,
+            ModuleKind,
+            SchemaId,
+            SchemaVariantId,
+            make_module_details_response,
+        },
     },
 };
 
@@ -77,22 +90,34 @@ pub struct UpsertModuleRequest {
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum UpsertModuleError {
+    #[error("component templates are workspace specific and cannot be published")]
+    ComponentTemplateNotSupported,
     #[error("db error: {0}")]
     DbErr(#[from] DbErr),
+    #[error("module signature and signing key id must be provided together")]
+    IncompleteSignature,
+    #[error("module signature does not match signing key: {0}")]
+    InvalidSignature(String),
     #[error("file upload error: {0}")]
     IoError(#[from] std::io::Error),
     #[error("multipart decode error: {0}")]
     Multipart(#[from] MultipartError),
     #[error("module with {0} could not be found after insert!")]
     NotFoundAfterInsert(ModuleId),
+    #[error("package signing error: {0}")]
+    PackageSigning(#[from] PackageSigningError),
     #[error("s3 error: {0}")]
     S3Error(#[from] S3Error),
     #[error("JSON serialization/deserialization error: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("signing key {0} is not registered to the uploading user")]
+    SigningKeyNotOwned(String),
     #[error("module parsing error: {0}")]
     SiPkgError(#[from] SiPkgError),
     #[error("Ulid decode error: {0}")]
     UlidDecode(#[from] ulid::DecodeError),
+    #[error("signing key {0} is not registered")]
+    UnknownSigningKey(String),
     #[error("upload is required")]
     UploadRequiredError,
 }
@@ -100,7 +125,15 @@ pub enum UpsertModuleError {
 // TODO: figure out how to not keep this serialization logic here
 impl IntoResponse for UpsertModuleError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+        let (status, error_message) = match self {
+            Self::ComponentTemplateNotSupported
+            | Self::IncompleteSignature
+            | Self::InvalidSignature(_)
+            | Self::PackageSigning(_)
+            | Self::SigningKeyNotOwned(_)
+            | Self::UnknownSigningKey(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
 
         error!("upsert error: {}", &error_message);
 
@@ -142,6 +175,9 @@ pub struct SiMultipartData {
     pub module_based_on_hash: Option<String>,
     pub module_data: Option<Bytes>,
     pub module_is_private_scoped: Option<bool>,
+    pub signature: Option<String>,
+    pub signing_key_id: Option<String>,
+    pub tags: Option<Vec<String>>,
 }
 
 pub async fn extract_multiparts(
@@ -153,6 +189,9 @@ pub async fn extract_multiparts(
     let mut module_schema_variant_id = None;
     let mut module_schema_variant_version = None;
     let mut module_is_private_scoped = None;
+    let mut signature = None;
+    let mut signing_key_id = None;
+    let mut tags = None;
     while let Some(field) = multipart.next_field().await? {
         match field.name() {
             Some(MODULE_BUNDLE_FIELD_NAME) => {
@@ -174,6 +213,15 @@ pub async fn extract_multiparts(
                 module_is_private_scoped =
                     Some(field.text().await?.parse::<bool>().unwrap_or_default());
             }
+            Some(MODULE_SIGNATURE_FIELD_NAME) => {
+                signature = Some(field.text().await?);
+            }
+            Some(MODULE_SIGNING_KEY_ID_FIELD_NAME) => {
+                signing_key_id = Some(field.text().await?);
+            }
+            Some(MODULE_TAGS_FIELD_NAME) => {
+                tags = Some(normalize_tags(field.text().await?.split(',')));
+            }
             _ => debug!("Unknown multipart form field on module upload, skipping..."),
         }
     }
@@ -185,6 +233,9 @@ pub async fn extract_multiparts(
         module_based_on_hash,
         module_data,
         module_is_private_scoped,
+        signature,
+        signing_key_id,
+        tags,
     })
 }
 
@@ -203,8 +254,25 @@ pub async fn upsert_module(
         "upserting module: {:?} based on hash: {:?} with provided schema id of {:?}",
         &module_metadata, &multi_part_data.module_based_on_hash, &multi_part_data.schema_id
     );
+    let signature = match (multi_part_data.signature, multi_part_data.signing_key_id) {
+        (Some(signature), Some(key_id)) => Some(
+            verify_module_signature(
+                txn,
+                &user_claim,
+                &module_metadata.hash().to_string(),
+                key_id,
+                signature,
+            )
+            .await?,
+        ),
+        (None, None) => None,
+        _ => return Err(UpsertModuleError::IncompleteSignature),
+    };
     let version = module_metadata.version().to_owned();
     let module_kind = match module_metadata.kind() {
+        SiPkgKind::ComponentTemplate => {
+            return Err(UpsertModuleError::ComponentTemplateNotSupported);
+        }
         SiPkgKind::WorkspaceBackup => ModuleKind::WorkspaceBackup,
         SiPkgKind::Module => ModuleKind::Module,
     };
@@ -268,6 +336,26 @@ pub async fn upsert_module(
         },
     };
 
+    let category = loaded_module
+        .schemas()?
+        .first()
+        .map(|schema| schema.category().to_owned())
+        .filter(|category| !category.is_empty());
+
+    // New versions of a schema keep the tags of the previous version unless new ones are given
+    let tags = match (multi_part_data.tags, schema_id) {
+        (Some(tags), _) => tags,
+        (None, Some(schema_id)) => si_module::Entity::find()
+            .filter(si_module::Column::SchemaId.eq(schema_id))
+            .filter(si_module::Column::RejectedAt.is_null())
+            .order_by_desc(si_module::Column::CreatedAt)
+            .one(txn)
+            .await?
+            .map(|module| module.tags)
+            .unwrap_or_default(),
+        (None, None) => vec![],
+    };
+
     let structural_hash = SiPkg::load_from_spec(loaded_module.to_spec().await?.anonymize())?
         .metadata()?
         .hash()
@@ -295,6 +383,11 @@ pub async fn upsert_module(
         schema_variant_id: Set(schema_variant_id),
         schema_variant_version: Set(multi_part_data.schema_variant_version),
         is_private_scoped: Set(multi_part_data.module_is_private_scoped.unwrap_or_default()),
+        signature: Set(signature.as_ref().map(|s| s.signature.to_owned())),
+        signing_key_id: Set(signature.as_ref().map(|s| s.key_id.to_owned())),
+        signing_public_key: Set(signature.map(|s| s.public_key)),
+        category: Set(category),
+        tags: Set(tags),
         ..Default::default() // all other attributes are `NotSet`
     };
     s3_bucket
@@ -304,3 +397,149 @@ pub async fn upsert_module(
 
     Ok(new_module)
 }
+
+/// Trims, lowercases, sorts and deduplicates module tags, dropping any that are empty.
+pub fn normalize_tags<'a>(tags: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .into_iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Checks that the signature was made over the module hash by a publisher key registered to the
+/// uploading user.
+async fn verify_module_signature(
+    txn: &sea_orm::DatabaseTransaction,
+    user_claim: &si_jwt_public_key::SiJwtClaims,
+    module_hash: &str,
+    key_id: String,
+    signature: String,
+) -> Result<ModuleSignature, UpsertModuleError> {
+    let key = publisher_key::Entity::find_by_id(key_id.clone())
+        .one(txn)
+        .await?
+        .ok_or_else(|| UpsertModuleError::UnknownSigningKey(key_id))?;
+
+    check_module_signature(
+        &key,
+        &user_claim.user_id().to_string(),
+        module_hash,
+        signature,
+    )
+}
+
+fn check_module_signature(
+    key: &publisher_key::Model,
+    uploading_user_id: &str,
+    module_hash: &str,
+    signature: String,
+) -> Result<ModuleSignature, UpsertModuleError> {
+    if key.owner_user_id != uploading_user_id {
+        return Err(UpsertModuleError::SigningKeyNotOwned(key.key_id.clone()));
+    }
+
+    let verifying_key = PackageVerifyingKey::from_base64(&key.public_key)?;
+    if verifying_key.key_id() != key.key_id
+        || !verifying_key.verify(module_hash, &PackageSignature::from_base64(&signature)?)
+    {
+        return Err(UpsertModuleError::InvalidSignature(key.key_id.clone()));
+    }
+
+    Ok(ModuleSignature {
+        key_id: key.key_id.clone(),
+        public_key: key.public_key.clone(),
+        signature,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use si_crypto::PackageSigningKey;
+
+    use super::*;
+
+    const MODULE_HASH: &str = "module-hash";
+    const UPLOADER: &str = "01HRFEV0S23R1G23RP75QQDCA7";
+
+    fn publisher_key(signing_key: &PackageSigningKey) -> publisher_key::Model {
+        let verifying_key = signing_key.verifying_key();
+        publisher_key::Model {
+            key_id: verifying_key.key_id(),
+            public_key: verifying_key.to_base64(),
+            owner_user_id: UPLOADER.to_owned(),
+            created_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn accepts_signatures_by_the_uploaders_key() {
+        let signing_key = PackageSigningKey::generate();
+        let key = publisher_key(&signing_key);
+        let signature = signing_key.sign(MODULE_HASH).to_base64();
+
+        let module_signature =
+            check_module_signature(&key, UPLOADER, MODULE_HASH, signature.clone())
+                .expect("signature should be accepted");
+
+        assert_eq!(key.key_id, module_signature.key_id);
+        assert_eq!(key.public_key, module_signature.public_key);
+        assert_eq!(signature, module_signature.signature);
+    }
+
+    #[test]
+    fn rejects_keys_registered_to_another_user() {
+        let signing_key = PackageSigningKey::generate();
+        let signature = signing_key.sign(MODULE_HASH).to_base64();
+
+        let result = check_module_signature(
+            &publisher_key(&signing_key),
+            "01HRFEV0S23R1G23RP75QQDCA8",
+            MODULE_HASH,
+            signature,
+        );
+
+        assert!(matches!(
+            result,
+            Err(UpsertModuleError::SigningKeyNotOwned(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_signatures_over_another_module() {
+        let signing_key = PackageSigningKey::generate();
+        let signature = signing_key.sign("another-module-hash").to_base64();
+
+        let result = check_module_signature(
+            &publisher_key(&signing_key),
+            UPLOADER,
+            MODULE_HASH,
+            signature,
+        );
+
+        assert!(matches!(
+            result,
+            Err(UpsertModuleError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_keys_that_do_not_match_their_id() {
+        let signing_key = PackageSigningKey::generate();
+        let signature = signing_key.sign(MODULE_HASH).to_base64();
+        let key = publisher_key::Model {
+            key_id: PackageSigningKey::generate().verifying_key().key_id(),
+            ..publisher_key(&signing_key)
+        };
+
+        let result = check_module_signature(&key, UPLOADER, MODULE_HASH, signature);
+
+        assert!(matches!(
+            result,
+            Err(UpsertModuleError::InvalidSignature(_))
+        ));
+    }
+}
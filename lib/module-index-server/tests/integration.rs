@@ -0,0 +1,3 @@
+const TEST_PG_DBNAME: &str = "si_test";
+
+mod integration_test;
@@ -0,0 +1,129 @@
+use std::{
+    env,
+    path::Path,
+};
+
+use buck2_resources::Buck2Resources;
+use module_index_server::Server;
+use sea_orm::DatabaseConnection;
+use si_data_pg::{
+    PgPool,
+    PgPoolConfig,
+};
+use si_tls::CertificateSource;
+
+use crate::TEST_PG_DBNAME;
+
+mod search_modules;
+
+const DEFAULT_TEST_PG_USER: &str = "si_test";
+const DEFAULT_TEST_PG_PORT_STR: &str = "6432";
+
+const ENV_VAR_PG_HOSTNAME: &str = "SI_TEST_PG_HOSTNAME";
+const ENV_VAR_PG_DBNAME: &str = "SI_TEST_PG_DBNAME";
+const ENV_VAR_PG_USER: &str = "SI_TEST_PG_USER";
+const ENV_VAR_PG_PORT: &str = "SI_TEST_PG_PORT";
+
+/// Creates a fresh database for a test, runs the module index migrations against it and returns a
+/// sea-orm connection to it.
+#[allow(clippy::disallowed_methods)] // Environment variables are used exclusively in test
+pub async fn setup_db(test_specific_db_name: &str) -> DatabaseConnection {
+    // PG pool config to setup tests
+    let setup_pg_pool_config = {
+        let mut pg = PgPoolConfig {
+            application_name: "module-index-server-db-tests".into(),
+            certificate: Some(CertificateSource::Path(
+                detect_and_configure_development()
+                    .try_into()
+                    .expect("should get a certifcate cache"),
+            )),
+            ..Default::default()
+        };
+        if let Ok(value) = env::var(ENV_VAR_PG_HOSTNAME) {
+            pg.hostname = value;
+        }
+        pg.dbname = env::var(ENV_VAR_PG_DBNAME).unwrap_or_else(|_| TEST_PG_DBNAME.to_string());
+        pg.user = env::var(ENV_VAR_PG_USER).unwrap_or_else(|_| DEFAULT_TEST_PG_USER.to_string());
+        pg.port = env::var(ENV_VAR_PG_PORT)
+            .unwrap_or_else(|_| DEFAULT_TEST_PG_PORT_STR.to_string())
+            .parse()
+            .expect("port should parse as an integer");
+        pg
+    };
+
+    let setup_pg_pool = PgPool::new(&setup_pg_pool_config)
+        .await
+        .expect("cannot create pg pool for tests");
+
+    let test_specific_pg_pool_config = {
+        let mut pg = setup_pg_pool_config.clone();
+        pg.dbname = format!("module_index_{test_specific_db_name}");
+        pg
+    };
+
+    let client = setup_pg_pool
+        .get()
+        .await
+        .expect("unable to get pg_pool client");
+    client
+        .execute(
+            &format!(
+                "DROP DATABASE IF EXISTS {}",
+                test_specific_pg_pool_config.dbname
+            ),
+            &[],
+        )
+        .await
+        .expect("able to drop database for tests");
+    client
+        .execute(
+            &format!(
+                "CREATE DATABASE {} OWNER {}",
+                test_specific_pg_pool_config.dbname, test_specific_pg_pool_config.user
+            ),
+            &[],
+        )
+        .await
+        .expect("able to create database for tests");
+
+    let test_specific_pg_pool = Server::create_pg_pool(&test_specific_pg_pool_config)
+        .await
+        .expect("cannot create pg pool for tests");
+    Server::run_migrations(&test_specific_pg_pool)
+        .await
+        .expect("able to migrate database for tests");
+
+    Server::create_db_connection(&test_specific_pg_pool_config)
+        .await
+        .expect("cannot create db connection for tests")
+}
+
+/// This function is used to determine the development environment and find the postgres root
+/// certificate accordingly.
+#[allow(clippy::disallowed_methods)]
+pub fn detect_and_configure_development() -> String {
+    if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
+        buck2_development()
+    } else if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
+        cargo_development(dir)
+    } else {
+        "".to_string()
+    }
+}
+
+pub fn buck2_development() -> String {
+    let resources = Buck2Resources::read().expect("should be able to read buck2 resources");
+
+    resources
+        .get_ends_with("dev.postgres.root.crt")
+        .expect("should be able to get cert")
+        .to_string_lossy()
+        .to_string()
+}
+
+pub fn cargo_development(dir: String) -> String {
+    Path::new(&dir)
+        .join("../../config/keys/dev.postgres.root.crt")
+        .to_string_lossy()
+        .to_string()
+}
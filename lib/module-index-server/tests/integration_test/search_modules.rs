@@ -0,0 +1,289 @@
+use sea_orm::{
+    ConnectionTrait,
+    DatabaseConnection,
+    DbBackend,
+    Statement,
+};
+use ulid::Ulid;
+
+use super::setup_db;
+
+const SEARCH_MODULES_QUERY: &str = include_str!("../../src/queries/search_modules.sql");
+
+const OWNER_USER_ID: &str = "01HRFEV0S23R1G23RP75QQDCA7";
+
+struct TestModule<'a> {
+    name: &'a str,
+    description: &'a str,
+    schema_id: &'a str,
+    tags: &'a [&'a str],
+    category: Option<&'a str>,
+    deprecated: bool,
+}
+
+impl<'a> TestModule<'a> {
+    fn new(name: &'a str, description: &'a str, schema_id: &'a str) -> Self {
+        Self {
+            name,
+            description,
+            schema_id,
+            tags: &[],
+            category: None,
+            deprecated: false,
+        }
+    }
+
+    fn tags(mut self, tags: &'a [&'a str]) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    fn category(mut self, category: &'a str) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+
+    /// Inserts the module as a builtin, returning its id. Modules inserted later are newer.
+    async fn insert(self, db: &DatabaseConnection) -> String {
+        let id = Ulid::new().to_string();
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO modules (
+                id, name, description, owner_user_id, latest_hash, schema_id, is_builtin_at,
+                tags, category, deprecated_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, CLOCK_TIMESTAMP(), $7, $8,
+                CASE WHEN $9 THEN CLOCK_TIMESTAMP() ELSE NULL END
+            )",
+            [
+                id.clone().into(),
+                self.name.into(),
+                self.description.into(),
+                OWNER_USER_ID.into(),
+                "0".repeat(64).into(),
+                self.schema_id.into(),
+                self.tags
+                    .iter()
+                    .map(|tag| tag.to_string())
+                    .collect::<Vec<_>>()
+                    .into(),
+                self.category.map(str::to_owned).into(),
+                self.deprecated.into(),
+            ],
+        ))
+        .await
+        .expect("should insert module");
+        id
+    }
+}
+
+#[derive(Default)]
+struct Search<'a> {
+    q: &'a str,
+    tags: &'a [&'a str],
+    category: Option<&'a str>,
+    include_deprecated: bool,
+}
+
+impl Search<'_> {
+    /// Runs the search as a non-systeminit user, returning the ids of the matching modules in
+    /// result order.
+    async fn run(self, db: &DatabaseConnection) -> Vec<String> {
+        db.query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            SEARCH_MODULES_QUERY,
+            [
+                self.q.into(),
+                false.into(),
+                Ulid::new().to_string().into(),
+                self.tags
+                    .iter()
+                    .map(|tag| tag.to_string())
+                    .collect::<Vec<_>>()
+                    .into(),
+                self.category.map(str::to_owned).into(),
+                self.include_deprecated.into(),
+                50i64.into(),
+            ],
+        ))
+        .await
+        .expect("should search modules")
+        .into_iter()
+        .map(|row| {
+            row.try_get::<String>("", "id")
+                .expect("should get module id")
+        })
+        .collect()
+    }
+}
+
+#[tokio::test]
+async fn search_matches_stemmed_words_in_name_and_description() {
+    let db = setup_db("search_stemming").await;
+
+    let ec2 = TestModule::new(
+        "AWS EC2 Instance",
+        "Launches virtual servers",
+        "01HRFEV0S23R1G23RP75QQDCA1",
+    )
+    .insert(&db)
+    .await;
+    let image = TestModule::new(
+        "Docker Image",
+        "A container image",
+        "01HRFEV0S23R1G23RP75QQDCA2",
+    )
+    .insert(&db)
+    .await;
+
+    let found = Search {
+        q: "launching server",
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![ec2.clone()], found);
+
+    let found = Search {
+        q: "images",
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![image.clone()], found);
+
+    let found = Search::default().run(&db).await;
+    assert_eq!(vec![ec2, image], found);
+}
+
+#[tokio::test]
+async fn search_filters_by_tags_and_category() {
+    let db = setup_db("search_tags_and_category").await;
+
+    let vpc = TestModule::new("AWS VPC", "A virtual network", "01HRFEV0S23R1G23RP75QQDCA1")
+        .tags(&["aws", "networking"])
+        .category("AWS::EC2")
+        .insert(&db)
+        .await;
+    let bucket = TestModule::new(
+        "AWS S3 Bucket",
+        "Object storage",
+        "01HRFEV0S23R1G23RP75QQDCA2",
+    )
+    .tags(&["aws", "storage"])
+    .category("AWS::S3")
+    .insert(&db)
+    .await;
+
+    let found = Search {
+        tags: &["aws"],
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![bucket.clone(), vpc.clone()], found);
+
+    let found = Search {
+        tags: &["aws", "networking"],
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![vpc], found);
+
+    let found = Search {
+        category: Some("aws::s3"),
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![bucket], found);
+}
+
+#[tokio::test]
+async fn search_returns_only_the_latest_version_of_each_schema() {
+    let db = setup_db("search_latest_version").await;
+    let schema_id = "01HRFEV0S23R1G23RP75QQDCA1";
+
+    TestModule::new("Docker Image", "A container image", schema_id)
+        .insert(&db)
+        .await;
+    let latest = TestModule::new("Docker Image", "A container image", schema_id)
+        .insert(&db)
+        .await;
+
+    let found = Search {
+        q: "docker",
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![latest], found);
+}
+
+#[tokio::test]
+async fn search_skips_deprecated_modules_unless_asked() {
+    let db = setup_db("search_deprecated").await;
+
+    let deprecated = TestModule::new(
+        "Docker Image",
+        "A container image",
+        "01HRFEV0S23R1G23RP75QQDCA1",
+    )
+    .deprecated()
+    .insert(&db)
+    .await;
+
+    let found = Search {
+        q: "docker",
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert!(found.is_empty());
+
+    let found = Search {
+        q: "docker",
+        include_deprecated: true,
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![deprecated], found);
+}
+
+#[tokio::test]
+async fn search_falls_back_to_an_older_version_when_the_latest_is_deprecated() {
+    let db = setup_db("search_deprecated_latest_version").await;
+    let schema_id = "01HRFEV0S23R1G23RP75QQDCA1";
+
+    let older = TestModule::new("Docker Image", "A container image", schema_id)
+        .insert(&db)
+        .await;
+    let latest = TestModule::new("Docker Image", "A container image", schema_id)
+        .deprecated()
+        .insert(&db)
+        .await;
+
+    let found = Search {
+        q: "docker",
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![older], found);
+
+    let found = Search {
+        q: "docker",
+        include_deprecated: true,
+        ..Default::default()
+    }
+    .run(&db)
+    .await;
+    assert_eq!(vec![latest], found);
+}
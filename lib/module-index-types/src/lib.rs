@@ -14,6 +14,9 @@ pub const MODULE_SCHEMA_ID_FIELD_NAME: &str = "schema_id";
 pub const MODULE_SCHEMA_VARIANT_ID_FIELD_NAME: &str = "schema_variant_id";
 pub const MODULE_SCHEMA_VARIANT_VERSION_FIELD_NAME: &str = "schema_variant_version";
 pub const MODULE_IS_PRIVATE_SCOPED_FIELD_NAME: &str = "is_private_scoped";
+pub const MODULE_SIGNATURE_FIELD_NAME: &str = "signature";
+pub const MODULE_SIGNING_KEY_ID_FIELD_NAME: &str = "signing_key_id";
+pub const MODULE_TAGS_FIELD_NAME: &str = "tags";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -52,6 +55,16 @@ pub struct ModuleDetailsResponse {
     pub schema_variant_id: Option<String>,
     pub schema_variant_version: Option<String>,
     pub structural_hash: Option<String>,
+    #[serde(default)]
+    pub signature: Option<ModuleSignature>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub deprecation: Option<ModuleDeprecation>,
+    #[serde(default)]
+    pub download_count: i64,
 }
 
 impl ModuleDetailsResponse {
@@ -108,3 +121,105 @@ impl LatestModuleResponse {
             .and_then(|schema_id| Ulid::from_string(schema_id).ok())
     }
 }
+
+/// An ed25519 signature over the hash of a module, made by a registered publisher key.
+///
+/// The key and signature are base64 encoded.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleSignature {
+    pub key_id: String,
+    pub public_key: String,
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterPublisherKeyRequest {
+    pub public_key: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherKeyResponse {
+    pub key_id: String,
+    pub public_key: String,
+    pub owner_user_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPublisherKeysResponse {
+    pub keys: Vec<PublisherKeyResponse>,
+}
+
+/// Marks a module as deprecated, optionally pointing at the module that replaces it.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleDeprecation {
+    pub deprecated_at: DateTime<Utc>,
+    pub deprecated_by_display_name: Option<String>,
+    pub message: Option<String>,
+    pub replaced_by_module_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecateModuleRequest {
+    pub message: Option<String>,
+    pub replaced_by_module_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateModuleTagsRequest {
+    pub tags: Vec<String>,
+}
+
+/// Query parameters for searching modules (route: GET /modules/search).
+///
+/// `tags` is a comma separated list; only modules carrying every listed tag match. Only the latest
+/// module of each schema is returned, skipping deprecated versions unless `includeDeprecated` is
+/// set.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchModulesRequest {
+    pub q: Option<String>,
+    pub tags: Option<String>,
+    pub category: Option<String>,
+    pub include_deprecated: Option<bool>,
+    pub limit: Option<u64>,
+    pub su: Option<bool>,
+}
+
+/// Query parameters for listing the versions of a schema (route: GET /modules/versions) or
+/// resolving the latest version matching a requirement (route: GET /modules/resolve).
+///
+/// Modules are identified by `schemaId` or, failing that, by `name`. `version` is a semantic
+/// version requirement such as `2.x`, `^1.4` or `>=1.2, <2`. Modules whose schema variant version
+/// cannot be read as a semantic version never match a requirement.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleVersionsRequest {
+    pub name: Option<String>,
+    pub schema_id: Option<String>,
+    pub version: Option<String>,
+    pub include_deprecated: Option<bool>,
+}
+
+/// The version of the [`ModuleBundleManifest`] format written by this crate.
+pub const MODULE_BUNDLE_MANIFEST_VERSION: u32 = 1;
+
+/// Describes the modules of an offline module bundle, a single tar of many modules that can be
+/// loaded into a module cache without reaching the module index.
+///
+/// Each module is stored in the bundle under its module id.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleBundleManifest {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub module_index_url: Option<String>,
+    pub modules: Vec<ModuleDetailsResponse>,
+}
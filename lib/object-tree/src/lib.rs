@@ -101,6 +101,10 @@ pub use graph::{
 pub use si_hash::Hash;
 
 pub use crate::tar::{
+    bundle::{
+        TarBundleReader,
+        TarBundleWriter,
+    },
     read::TarReadError,
     write::{
         TarWriter,
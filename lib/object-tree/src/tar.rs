@@ -5,6 +5,7 @@ use std::path::{
 
 use si_hash::Hash;
 
+pub mod bundle;
 pub mod read;
 pub mod write;
 
@@ -0,0 +1,131 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use ::tar::Builder;
+use si_hash::Hash;
+
+use crate::{
+    NameStr,
+    ObjectTree,
+    ReadBytes,
+    WriteBytes,
+    tar::{
+        read::{
+            TarReadError,
+            get_ref,
+            read_tar_entries,
+            read_tree,
+        },
+        ref_path,
+        write::{
+            TarWriterError,
+            write_tar_entry,
+            write_tree_objects,
+        },
+    },
+};
+
+/// Create a single tar holding many [`ObjectTree`]s, each reachable through a named ref.
+///
+/// Objects shared between trees are only written once. Arbitrary files, such as a manifest
+/// describing the trees, may be added alongside them.
+pub struct TarBundleWriter {
+    tar_builder: Builder<Vec<u8>>,
+    written: HashSet<Hash>,
+}
+
+impl TarBundleWriter {
+    /// Return an empty [`TarBundleWriter`]
+    pub fn new() -> Self {
+        Self {
+            tar_builder: Builder::new(Vec::new()),
+            written: HashSet::new(),
+        }
+    }
+
+    /// Adds the objects of the tree to the bundle and points the named ref at its root, returning
+    /// the hash of the root node
+    pub fn add_tree<T>(
+        &mut self,
+        ref_name: &str,
+        tree: &ObjectTree<T>,
+    ) -> Result<Hash, TarWriterError>
+    where
+        T: Clone + NameStr + WriteBytes + Send + Sync + 'static,
+    {
+        let root_hash = write_tree_objects(&mut self.tar_builder, tree, &mut self.written)?;
+        write_tar_entry(
+            &mut self.tar_builder,
+            ref_path(ref_name),
+            root_hash.to_string().as_bytes(),
+        )?;
+
+        Ok(root_hash)
+    }
+
+    /// Adds a file at the given path of the bundle
+    pub fn add_file(&mut self, path: impl AsRef<Path>, bytes: &[u8]) -> Result<(), TarWriterError> {
+        write_tar_entry(&mut self.tar_builder, path.as_ref().to_path_buf(), bytes)
+    }
+
+    /// Finishes the bundle and returns the tar as a `Vec<u8>`
+    pub fn finish(mut self) -> Result<Vec<u8>, TarWriterError> {
+        self.tar_builder.finish()?;
+        Ok(self.tar_builder.into_inner()?)
+    }
+}
+
+impl Default for TarBundleWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read the [`ObjectTree`]s and files of a tar written by a [`TarBundleWriter`]
+pub struct TarBundleReader {
+    entries: HashMap<PathBuf, Vec<u8>>,
+}
+
+impl TarBundleReader {
+    /// Return a [`TarBundleReader`] over the entries of the tar
+    pub fn new(tar_data: &[u8]) -> Result<Self, TarReadError> {
+        Ok(Self {
+            entries: read_tar_entries(tar_data)?,
+        })
+    }
+
+    /// Returns the names of every ref in the bundle, sorted
+    pub fn ref_names(&self) -> Vec<String> {
+        let refs_dir = ref_path("");
+        let mut names: Vec<String> = self
+            .entries
+            .keys()
+            .filter_map(|path| path.strip_prefix(&refs_dir).ok())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Reads the tree the named ref points at
+    pub fn read_tree<N>(&self, ref_name: &str) -> Result<ObjectTree<N>, TarReadError>
+    where
+        N: ReadBytes,
+    {
+        let root_hash = get_ref(&self.entries, ref_name)?;
+        read_tree(&self.entries, root_hash)
+    }
+
+    /// Returns the contents of the file at the given path, if the bundle has one
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<&[u8]> {
+        self.entries.get(path.as_ref()).map(Vec::as_slice)
+    }
+}
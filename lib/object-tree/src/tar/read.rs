@@ -69,58 +69,76 @@ impl<T> ObjectTree<T> {
     where
         N: ReadBytes,
     {
-        let mut graph = Graph::new();
-        let mut root_idx: Option<NodeIndex> = None;
-
-        let mut unpacked_tar = ::tar::Archive::new(tar_data);
-        let mut tar_data = HashMap::new();
-        for maybe_tar_entry in unpacked_tar.entries()? {
-            let mut tar_entry = maybe_tar_entry?;
-            let entry_path = tar_entry.path()?.into_owned();
-            let mut entry_data = Vec::new();
-            tar_entry.read_to_end(&mut entry_data)?;
-
-            tar_data.insert(entry_path, entry_data);
-        }
+        let tar_data = read_tar_entries(tar_data)?;
+        let root_hash = get_root_ref(&tar_data)?;
 
-        let root_hash = get_root_ref(&mut tar_data)?;
-        let root_node = get_node(&mut tar_data, root_hash)?.ok_or(TarReadError::RootNodeError)?;
+        read_tree(&tar_data, root_hash)
+    }
+}
+
+/// Reads every entry of a tar into a map of paths to their contents.
+pub(crate) fn read_tar_entries(tar_data: &[u8]) -> Result<HashMap<PathBuf, Vec<u8>>, TarReadError> {
+    let mut unpacked_tar = ::tar::Archive::new(tar_data);
+    let mut entries = HashMap::new();
+    for maybe_tar_entry in unpacked_tar.entries()? {
+        let mut tar_entry = maybe_tar_entry?;
+        let entry_path = tar_entry.path()?.into_owned();
+        let mut entry_data = Vec::new();
+        tar_entry.read_to_end(&mut entry_data)?;
+
+        entries.insert(entry_path, entry_data);
+    }
 
-        let mut stack: Vec<(HashedNodeWithEntries<N>, Option<NodeIndex>)> = vec![(root_node, None)];
+    Ok(entries)
+}
 
-        while let Some((node_with_entries, parent_idx)) = stack.pop() {
-            let (node, child_entries) = node_with_entries.into();
+/// Rebuilds the [`ObjectTree`] rooted at the object with the given hash.
+pub(crate) fn read_tree<N>(
+    tar_data: &HashMap<PathBuf, Vec<u8>>,
+    root_hash: Hash,
+) -> Result<ObjectTree<N>, TarReadError>
+where
+    N: ReadBytes,
+{
+    let mut graph = Graph::new();
+    let mut root_idx: Option<NodeIndex> = None;
 
-            let node_idx = graph.add_node(node);
+    let root_node = get_node(tar_data, root_hash)?.ok_or(TarReadError::RootNodeError)?;
 
-            match parent_idx {
-                Some(parent_idx) => {
-                    graph.add_edge(parent_idx, node_idx, ());
-                }
-                None => match root_idx {
-                    None => {
-                        root_idx = Some(node_idx);
-                    }
-                    Some(_) => return Err(TarReadError::ReadTree(GraphError::MultipleRootNode)),
-                },
-            };
-
-            for child_entry in child_entries.into_iter().rev() {
-                if let Some(child_node) = get_node(&mut tar_data, child_entry.hash())? {
-                    stack.push((child_node, Some(node_idx)));
+    let mut stack: Vec<(HashedNodeWithEntries<N>, Option<NodeIndex>)> = vec![(root_node, None)];
+
+    while let Some((node_with_entries, parent_idx)) = stack.pop() {
+        let (node, child_entries) = node_with_entries.into();
+
+        let node_idx = graph.add_node(node);
+
+        match parent_idx {
+            Some(parent_idx) => {
+                graph.add_edge(parent_idx, node_idx, ());
+            }
+            None => match root_idx {
+                None => {
+                    root_idx = Some(node_idx);
                 }
+                Some(_) => return Err(TarReadError::ReadTree(GraphError::MultipleRootNode)),
+            },
+        };
+
+        for child_entry in child_entries.into_iter().rev() {
+            if let Some(child_node) = get_node(tar_data, child_entry.hash())? {
+                stack.push((child_node, Some(node_idx)));
             }
         }
+    }
 
-        match root_idx {
-            Some(root_idx) => Ok(ObjectTree::new(graph, root_idx)),
-            None => Err(TarReadError::ReadTree(GraphError::MissingRootNode)),
-        }
+    match root_idx {
+        Some(root_idx) => Ok(ObjectTree::new(graph, root_idx)),
+        None => Err(TarReadError::ReadTree(GraphError::MissingRootNode)),
     }
 }
 
 fn get_node<N>(
-    tar_data: &mut HashMap<PathBuf, Vec<u8>>,
+    tar_data: &HashMap<PathBuf, Vec<u8>>,
     hash: Hash,
 ) -> Result<Option<HashedNodeWithEntries<N>>, TarReadError>
 where
@@ -138,8 +156,16 @@ where
         .map(|nwe| HashedNodeWithEntries::from_node_with_entries_and_hash(nwe, hash)))
 }
 
-fn get_root_ref(tar_data: &mut HashMap<PathBuf, Vec<u8>>) -> Result<Hash, TarReadError> {
-    let dst_path = ref_path("root");
+fn get_root_ref(tar_data: &HashMap<PathBuf, Vec<u8>>) -> Result<Hash, TarReadError> {
+    get_ref(tar_data, "root")
+}
+
+/// Returns the hash of the root object the named ref points at.
+pub(crate) fn get_ref(
+    tar_data: &HashMap<PathBuf, Vec<u8>>,
+    name: &str,
+) -> Result<Hash, TarReadError> {
+    let dst_path = ref_path(name);
     let buf = String::from_utf8(
         tar_data
             .get(&dst_path)
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     num::TryFromIntError,
     path::PathBuf,
 };
@@ -8,6 +9,7 @@ use ::tar::{
     Header,
 };
 use petgraph::prelude::*;
+use si_hash::Hash;
 use thiserror::Error;
 
 use crate::{
@@ -51,54 +53,13 @@ impl TarWriter {
     where
         T: Clone + NameStr + WriteBytes + Send + Sync + 'static,
     {
-        let (graph, root_idx) = tree.as_petgraph();
         let mut tar_builder = Builder::new(Vec::new());
 
-        let mut dfspo = DfsPostOrder::new(graph, root_idx);
-
-        while let Some(node_idx) = dfspo.next(graph) {
-            let node = graph
-                .node_weight(node_idx)
-                .ok_or(GraphError::NodeWeightNotFound(
-                    node_idx.index(),
-                    "tar writer: could not find next node for index for dfspo",
-                ))?
-                .clone();
-
-            let mut entries = Vec::new();
-            for child_idx in graph.neighbors_directed(node_idx, Outgoing) {
-                let child_node =
-                    graph
-                        .node_weight(child_idx)
-                        .ok_or(GraphError::NodeWeightNotFound(
-                            child_idx.index(),
-                            "tar writer: could not find child node for index",
-                        ))?;
-                entries.push(NodeEntry::new(
-                    child_node.kind(),
-                    child_node.hash(),
-                    child_node.name(),
-                ));
-            }
-
-            let tar_entry = HashedNodeWithEntries::new(node, entries);
-            write_tar_entry(
-                &mut tar_builder,
-                object_path(&tar_entry.hash()),
-                &tar_entry.to_bytes()?,
-            )?;
-        }
-
-        let root_node = graph
-            .node_weight(root_idx)
-            .ok_or(GraphError::NodeWeightNotFound(
-                root_idx.index(),
-                "tar writer: could not find root node for index",
-            ))?;
+        let root_hash = write_tree_objects(&mut tar_builder, tree, &mut HashSet::new())?;
         write_tar_entry(
             &mut tar_builder,
             ref_path("root"),
-            root_node.hash().to_string().as_bytes(),
+            root_hash.to_string().as_bytes(),
         )?;
         tar_builder.finish()?;
 
@@ -113,7 +74,66 @@ impl TarWriter {
     }
 }
 
-fn write_tar_entry(
+/// Writes every node of the tree as an object entry, skipping the objects whose hash is already in
+/// `written`, and returns the hash of the root node.
+pub(crate) fn write_tree_objects<T>(
+    tar_builder: &mut Builder<Vec<u8>>,
+    tree: &ObjectTree<T>,
+    written: &mut HashSet<Hash>,
+) -> Result<Hash, TarWriterError>
+where
+    T: Clone + NameStr + WriteBytes + Send + Sync + 'static,
+{
+    let (graph, root_idx) = tree.as_petgraph();
+
+    let mut dfspo = DfsPostOrder::new(graph, root_idx);
+
+    while let Some(node_idx) = dfspo.next(graph) {
+        let node = graph
+            .node_weight(node_idx)
+            .ok_or(GraphError::NodeWeightNotFound(
+                node_idx.index(),
+                "tar writer: could not find next node for index for dfspo",
+            ))?;
+        if written.contains(&node.hash()) {
+            continue;
+        }
+
+        let mut entries = Vec::new();
+        for child_idx in graph.neighbors_directed(node_idx, Outgoing) {
+            let child_node = graph
+                .node_weight(child_idx)
+                .ok_or(GraphError::NodeWeightNotFound(
+                    child_idx.index(),
+                    "tar writer: could not find child node for index",
+                ))?;
+            entries.push(NodeEntry::new(
+                child_node.kind(),
+                child_node.hash(),
+                child_node.name(),
+            ));
+        }
+
+        let tar_entry = HashedNodeWithEntries::new(node.clone(), entries);
+        write_tar_entry(
+            tar_builder,
+            object_path(&tar_entry.hash()),
+            &tar_entry.to_bytes()?,
+        )?;
+        written.insert(tar_entry.hash());
+    }
+
+    let root_node = graph
+        .node_weight(root_idx)
+        .ok_or(GraphError::NodeWeightNotFound(
+            root_idx.index(),
+            "tar writer: could not find root node for index",
+        ))?;
+
+    Ok(root_node.hash())
+}
+
+pub(crate) fn write_tar_entry(
     tar_builder: &mut Builder<Vec<u8>>,
     path: PathBuf,
     entry: &[u8],
@@ -49,6 +49,7 @@ use si_events::{
     EventSessionId,
     RebaseBatchAddressKind,
     WorkspacePk,
+    WorkspaceSnapshotAddress,
 };
 use telemetry::prelude::*;
 use telemetry_nats::propagation;
@@ -127,6 +128,7 @@ impl Client {
             updates_address,
             None,
             None,
+            None,
             event_session_id,
         )
         .await
@@ -157,6 +159,7 @@ impl Client {
             updates_address,
             Some(from_change_set_id),
             None,
+            None,
             event_session_id,
         )
         .await
@@ -188,6 +191,41 @@ impl Client {
             change_set_id,
             updates_address,
             None,
+            None,
+            event_session_id,
+        )
+        .await
+    }
+
+    /// Enqueues graph updates for processing by a Rebaser, to be performed only if the Change Set
+    /// still points at the expected snapshot, and return a [`Future`] that will await the
+    /// Rebaser's response with status.
+    #[instrument(
+        name = "rebaser_client.enqueue_updates_if_unchanged_with_reply",
+        level = "info",
+        skip_all,
+        fields(
+            si.change_set.id = %change_set_id,
+            si.workspace.id = %workspace_id,
+        ),
+    )]
+    pub async fn enqueue_updates_if_unchanged_with_reply(
+        &self,
+        workspace_id: WorkspacePk,
+        change_set_id: ChangeSetId,
+        updates_address: RebaseBatchAddressKind,
+        expected_snapshot_address: WorkspaceSnapshotAddress,
+        event_session_id: EventSessionId,
+    ) -> Result<(
+        RequestId,
+        BoxFuture<'static, Result<EnqueueUpdatesResponse>>,
+    )> {
+        self.call_with_reply(
+            workspace_id,
+            change_set_id,
+            updates_address,
+            None,
+            Some(expected_snapshot_address),
             event_session_id,
         )
         .await
@@ -220,11 +258,13 @@ impl Client {
             change_set_id,
             updates_address,
             Some(from_change_set_id),
+            None,
             event_session_id,
         )
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn call_async(
         &self,
         workspace_id: WorkspacePk,
@@ -232,6 +272,7 @@ impl Client {
         updates_address: RebaseBatchAddressKind,
         from_change_set_id: Option<ChangeSetId>,
         maybe_reply_inbox: Option<&Subject>,
+        expected_snapshot_address: Option<WorkspaceSnapshotAddress>,
         event_session_id: EventSessionId,
     ) -> Result<RequestId> {
         let id = RequestId::new();
@@ -249,6 +290,7 @@ impl Client {
             updates_address,
             from_change_set_id,
             event_session_id: Some(event_session_id),
+            expected_snapshot_address,
         });
 
         // Cut down on the amount of `String` allocations dealing with ids
@@ -297,6 +339,7 @@ impl Client {
         change_set_id: ChangeSetId,
         updates_address: RebaseBatchAddressKind,
         from_change_set_id: Option<ChangeSetId>,
+        expected_snapshot_address: Option<WorkspaceSnapshotAddress>,
         event_session_id: EventSessionId,
     ) -> Result<(
         RequestId,
@@ -325,6 +368,7 @@ impl Client {
                 updates_address,
                 from_change_set_id,
                 Some(&reply_inbox),
+                expected_snapshot_address,
                 event_session_id,
             )
             .await?;
@@ -11,11 +11,13 @@ use si_events::RebaseBatchAddressKind;
 mod v1;
 mod v2;
 mod v3;
+mod v4;
 
 pub use self::{
     v1::EnqueueUpdatesRequestV1,
     v2::EnqueueUpdatesRequestV2,
     v3::EnqueueUpdatesRequestV3,
+    v4::EnqueueUpdatesRequestV4,
 };
 
 #[remain::sorted]
@@ -24,8 +26,9 @@ pub use self::{
 pub enum EnqueueUpdatesRequestAllVersions {
     V1(EnqueueUpdatesRequestV1),
     V2(EnqueueUpdatesRequestV2),
-    #[acceptable(current)]
     V3(EnqueueUpdatesRequestV3),
+    #[acceptable(current)]
+    V4(EnqueueUpdatesRequestV4),
 }
 
 impl IntoContainer for EnqueueUpdatesRequestAllVersions {
@@ -40,6 +43,7 @@ impl IntoContainer for EnqueueUpdatesRequestAllVersions {
                 updates_address: RebaseBatchAddressKind::Legacy(inner.updates_address),
                 from_change_set_id: inner.from_change_set_id,
                 event_session_id: None,
+                expected_snapshot_address: None,
             }),
             Self::V2(inner) => Self::Container::new(EnqueueUpdatesRequestVCurrent {
                 id: inner.id,
@@ -48,8 +52,18 @@ impl IntoContainer for EnqueueUpdatesRequestAllVersions {
                 updates_address: RebaseBatchAddressKind::Legacy(inner.updates_address),
                 from_change_set_id: inner.from_change_set_id,
                 event_session_id: inner.event_session_id,
+                expected_snapshot_address: None,
+            }),
+            Self::V3(inner) => Self::Container::new(EnqueueUpdatesRequestVCurrent {
+                id: inner.id,
+                workspace_id: inner.workspace_id,
+                change_set_id: inner.change_set_id,
+                updates_address: inner.updates_address,
+                from_change_set_id: inner.from_change_set_id,
+                event_session_id: inner.event_session_id,
+                expected_snapshot_address: None,
             }),
-            Self::V3(inner) => Self::Container::new(inner),
+            Self::V4(inner) => Self::Container::new(inner),
         })
     }
 }
@@ -0,0 +1,29 @@
+use acceptable::{
+    RequestId,
+    Versioned,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_events::{
+    ChangeSetId,
+    EventSessionId,
+    RebaseBatchAddressKind,
+    WorkspacePk,
+    WorkspaceSnapshotAddress,
+};
+
+#[derive(Clone, Debug, Deserialize, Eq, Serialize, PartialEq, Versioned)]
+#[serde(rename_all = "camelCase")]
+#[acceptable(version = 4)]
+pub struct EnqueueUpdatesRequestV4 {
+    pub id: RequestId,
+    pub workspace_id: WorkspacePk,
+    pub change_set_id: ChangeSetId,
+    pub updates_address: RebaseBatchAddressKind,
+    pub from_change_set_id: Option<ChangeSetId>,
+    pub event_session_id: Option<EventSessionId>,
+    /// If set, the updates are only performed if the change set still points at this snapshot.
+    pub expected_snapshot_address: Option<WorkspaceSnapshotAddress>,
+}
@@ -774,7 +774,10 @@ mod handlers {
                     //   and would reliably and reproducibly failed on additional retries.
                     | RebaseError::PerformUpdates(_)
                     // - Change set was abandoned and will not be "un-abandoned"
-                    | RebaseError::AbandonedChangeSet(_) => {
+                    | RebaseError::AbandonedChangeSet(_)
+                    // - Change set was written to since the requester read it, which won't be
+                    //   undone by retrying
+                    | RebaseError::UnexpectedSnapshotAddress { .. } => {
                         Err(HandlerError::unprocessable(rebase_error))
                     }
                     // - Failed to send updates to edda, but this is after commit so should *not*
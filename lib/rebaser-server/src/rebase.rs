@@ -64,6 +64,12 @@ pub(crate) enum RebaseError {
     Transactions(#[from] TransactionsError),
     #[error("unexpected rebase batch address kind")]
     UnexpectedRebaseBatchAddressKind,
+    #[error("change set {change_set_id} points at snapshot {actual}, not the expected {expected}")]
+    UnexpectedSnapshotAddress {
+        change_set_id: ChangeSetId,
+        expected: WorkspaceSnapshotAddress,
+        actual: WorkspaceSnapshotAddress,
+    },
     #[error("workspace error: {0}")]
     Workspace(#[from] WorkspaceError),
     #[error("workspace pk expected but was none")]
@@ -117,6 +123,19 @@ pub(crate) async fn perform_rebase(
 
     let to_rebase_workspace_snapshot_address = to_rebase_change_set.workspace_snapshot_address;
 
+    // The requester only wants its updates performed if nothing was written to the change set
+    // since it read it
+    if let Some(expected) = request
+        .expected_snapshot_address
+        .filter(|expected| *expected != to_rebase_workspace_snapshot_address)
+    {
+        return Err(RebaseError::UnexpectedSnapshotAddress {
+            change_set_id: to_rebase_change_set.id,
+            expected,
+            actual: to_rebase_workspace_snapshot_address,
+        });
+    }
+
     match workspace.snapshot_kind() {
         WorkspaceSnapshotSelectorDiscriminants::LegacySnapshot => {
             rebase_legacy(
@@ -292,6 +311,8 @@ async fn rebase_split(
 
     ctx.set_workspace_split_snapshot(to_rebase_workspace_snapshot.clone());
 
+    update_base_snapshot_after_replay(ctx, request, to_rebase_change_set).await?;
+
     // Before replying to the requester or sending the Edda request, we must commit.
     ctx.commit_no_rebase().await?;
 
@@ -421,6 +442,8 @@ async fn rebase_legacy(
     debug!("rebase performed: {:?}", start.elapsed());
     span.record("si.rebase.rebase_time", start.elapsed().as_millis());
 
+    update_base_snapshot_after_replay(ctx, request, to_rebase_change_set).await?;
+
     // Before replying to the requester or sending the Edda request, we must commit.
     ctx.commit_no_rebase().await?;
 
@@ -524,6 +547,30 @@ pub(crate) async fn send_updates_to_edda_legacy_snapshot(
     Ok(())
 }
 
+/// Replaying its base change set's changes brings a change set up to date with it, so the base
+/// change set's snapshot becomes the one the change set is based on.
+async fn update_base_snapshot_after_replay(
+    ctx: &DalContext,
+    request: &EnqueueUpdatesRequest,
+    to_rebase_change_set: &mut ChangeSet,
+) -> RebaseResult<()> {
+    let base_change_set_id = match to_rebase_change_set.base_change_set_id {
+        Some(base_change_set_id) if request.from_change_set_id == Some(base_change_set_id) => {
+            base_change_set_id
+        }
+        _ => return Ok(()),
+    };
+
+    let base_snapshot_address = ChangeSet::get_by_id(ctx, base_change_set_id)
+        .await?
+        .workspace_snapshot_address;
+    to_rebase_change_set
+        .update_base_snapshot_address(ctx, Some(base_snapshot_address))
+        .await?;
+
+    Ok(())
+}
+
 /// Evict a snapshot from memory and disk caches across all service instances.
 ///
 /// This is a fire-and-forget operation for memory pressure relief. It does NOT
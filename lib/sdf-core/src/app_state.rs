@@ -19,6 +19,7 @@ use tokio_util::sync::CancellationToken;
 
 use crate::{
     BroadcastGroups,
+    index::DefinitionRebuildRequests,
     nats_multiplexer::{
         EddaUpdatesMultiplexerClient,
         NatsMultiplexerClients,
@@ -53,6 +54,7 @@ pub struct AppState {
     frigg: FriggStore,
     audit_database_context: AuditDatabaseContext,
     edda_client: EddaClient,
+    definition_rebuild_requests: DefinitionRebuildRequests,
 }
 
 impl AppState {
@@ -97,6 +99,7 @@ impl AppState {
             frigg,
             audit_database_context,
             edda_client,
+            definition_rebuild_requests: Default::default(),
         }
     }
 
@@ -151,6 +154,10 @@ impl AppState {
     pub fn edda_client(&self) -> &EddaClient {
         &self.edda_client
     }
+
+    pub fn definition_rebuild_requests(&self) -> &DefinitionRebuildRequests {
+        &self.definition_rebuild_requests
+    }
 }
 
 #[derive(Clone, Debug, FromRef)]
@@ -1,4 +1,11 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
 
 use axum::response::{
     IntoResponse,
@@ -16,10 +23,46 @@ use serde::{
 use si_frontend_mv_types::object::FrontendObject;
 use si_frontend_types::FrontEndObjectRequest;
 use thiserror::Error;
-use tokio::task::JoinError;
+use tokio::{
+    sync::Mutex,
+    task::JoinError,
+};
 
 use crate::api_error::ApiError;
 
+/// How long a request to rebuild the outdated definitions of a change set index covers later reads
+/// of the same index.
+const DEFINITION_REBUILD_COALESCE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Tracks the recent requests to rebuild the outdated definitions of change set indexes, so that
+/// every read of an outdated index doesn't send edda another request while the first one is
+/// being worked on.
+#[derive(Clone, Debug, Default)]
+pub struct DefinitionRebuildRequests(Arc<Mutex<HashMap<(WorkspacePk, ChangeSetId), Instant>>>);
+
+impl DefinitionRebuildRequests {
+    /// Returns `true` if a rebuild should be requested for the change set index, in which case it
+    /// is recorded as requested. Returns `false` if one was already requested recently.
+    pub async fn start(&self, workspace_pk: WorkspacePk, change_set_id: ChangeSetId) -> bool {
+        let now = Instant::now();
+        let mut requested = self.0.lock().await;
+        requested.retain(|_, requested_at| {
+            now.duration_since(*requested_at) < DEFINITION_REBUILD_COALESCE_PERIOD
+        });
+
+        if requested.contains_key(&(workspace_pk, change_set_id)) {
+            return false;
+        }
+        requested.insert((workspace_pk, change_set_id), now);
+        true
+    }
+
+    /// Forgets a request that could not be sent, so the next read tries again.
+    pub async fn forget(&self, workspace_pk: WorkspacePk, change_set_id: ChangeSetId) {
+        self.0.lock().await.remove(&(workspace_pk, change_set_id));
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FrontEndObjectMeta {
@@ -49,6 +92,10 @@ pub enum IndexError {
     IndexNotFoundAfterFreshBuild(WorkspacePk, ChangeSetId),
     #[error("index not found after rebuild (v1); workspace_id={0}, change_set_id={1}")]
     IndexNotFoundAfterRebuild(WorkspacePk, ChangeSetId),
+    #[error("index not found at requested point in time; workspace_id={0}, change_set_id={1}")]
+    IndexNotFoundAt(WorkspacePk, ChangeSetId),
+    #[error("exactly one of revision or timestamp must be provided")]
+    InvalidIndexAtRequest,
     #[error("item with checksum not found; workspace_id={0}, change_set_id={1}, kind={2}")]
     ItemWithChecksumNotFound(WorkspacePk, ChangeSetId, String),
     #[error("latest item not found; workspace_id={0}, change_set_id={1}, kind={2}")]
@@ -67,10 +114,12 @@ impl IntoResponse for IndexError {
     fn into_response(self) -> Response {
         let status_code = match &self {
             IndexError::IndexNotFound(_, _)
+            | IndexError::IndexNotFoundAt(_, _)
             | IndexError::IndexNotFoundAfterFreshBuild(_, _)
             | IndexError::IndexNotFoundAfterRebuild(_, _)
             | IndexError::ItemWithChecksumNotFound(_, _, _)
             | IndexError::LatestItemNotFound(_, _, _) => StatusCode::NOT_FOUND,
+            IndexError::InvalidIndexAtRequest => StatusCode::BAD_REQUEST,
             _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
         };
 
@@ -104,8 +153,20 @@ pub async fn front_end_object_meta(
                 )
             })?;
     } else {
+        // Objects from an index built for outdated definitions are still served until the
+        // outdated kinds have been rebuilt.
+        let maybe_mv_index = frigg
+            .get_change_set_index_allowing_outdated(workspace_id, change_set_id)
+            .await?
+            .map(|(index, _, _)| index);
         obj = frigg
-            .get_current_workspace_object(workspace_id, change_set_id, &request.kind, &request.id)
+            .get_current_workspace_object_with_index(
+                workspace_id,
+                change_set_id,
+                &request.kind,
+                &request.id,
+                maybe_mv_index,
+            )
             .await?
             .ok_or_else(|| {
                 IndexError::LatestItemNotFound(workspace_id, change_set_id, request.kind.clone())
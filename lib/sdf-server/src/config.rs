@@ -131,6 +131,9 @@ pub struct Config {
 
     pkgs_path: CanonicalFile,
 
+    #[builder(default)]
+    module_bundle_path: Option<CanonicalFile>,
+
     boot_feature_flags: HashSet<FeatureFlag>,
 
     create_workspace_permissions: WorkspacePermissionsMode,
@@ -166,6 +169,12 @@ pub struct Config {
 
     #[builder(default)]
     backfill_func_run_logs_cutoff_id: Option<String>,
+
+    #[builder(default = "default_split_migration_batch_size()")]
+    split_migration_batch_size: usize,
+
+    #[builder(default)]
+    split_migration_workspace_ids: Option<String>,
 }
 
 impl StandardConfig for Config {
@@ -224,6 +233,13 @@ impl Config {
         self.pkgs_path.as_path()
     }
 
+    /// Gets the path of the offline module bundle used to fill the module cache instead of the
+    /// module index, if one is configured.
+    #[must_use]
+    pub fn module_bundle_path(&self) -> Option<&Path> {
+        self.module_bundle_path.as_ref().map(CanonicalFile::as_path)
+    }
+
     /// Gets a reference to the config's posthog config.
     #[must_use]
     pub fn posthog(&self) -> &PosthogConfig {
@@ -316,6 +332,14 @@ impl Config {
     pub fn backfill_func_run_logs_cutoff_id(&self) -> Option<&str> {
         self.backfill_func_run_logs_cutoff_id.as_deref()
     }
+
+    pub fn split_migration_batch_size(&self) -> usize {
+        self.split_migration_batch_size
+    }
+
+    pub fn split_migration_workspace_ids(&self) -> Option<&str> {
+        self.split_migration_workspace_ids.as_deref()
+    }
 }
 
 impl ConfigBuilder {
@@ -349,6 +373,8 @@ pub struct ConfigFile {
     #[serde(default = "default_pkgs_path")]
     pub pkgs_path: String,
     #[serde(default)]
+    pub module_bundle_path: Option<String>,
+    #[serde(default)]
     pub posthog: PosthogConfig,
     #[serde(default = "default_layer_db_config")]
     layer_db_config: LayerDbConfig,
@@ -384,6 +410,10 @@ pub struct ConfigFile {
     backfill_func_runs_cutoff_id: Option<String>,
     #[serde(default)]
     backfill_func_run_logs_cutoff_id: Option<String>,
+    #[serde(default = "default_split_migration_batch_size")]
+    split_migration_batch_size: usize,
+    #[serde(default)]
+    split_migration_workspace_ids: Option<String>,
 }
 
 impl Default for ConfigFile {
@@ -397,6 +427,7 @@ impl Default for ConfigFile {
             jwt_secondary_signing_public_key: Default::default(),
             crypto: Default::default(),
             pkgs_path: default_pkgs_path(),
+            module_bundle_path: None,
             posthog: Default::default(),
             layer_db_config: default_layer_db_config(),
             module_index_url: default_module_index_url(),
@@ -416,6 +447,8 @@ impl Default for ConfigFile {
             backfill_max_concurrent_uploads: default_backfill_max_concurrent_uploads(),
             backfill_func_runs_cutoff_id: None,
             backfill_func_run_logs_cutoff_id: None,
+            split_migration_batch_size: default_split_migration_batch_size(),
+            split_migration_workspace_ids: None,
         }
     }
 }
@@ -440,6 +473,10 @@ impl TryFrom<ConfigFile> for Config {
             jwt_secondary_signing_public_key: value.jwt_secondary_signing_public_key,
             crypto: value.crypto,
             pkgs_path: value.pkgs_path.try_into()?,
+            module_bundle_path: value
+                .module_bundle_path
+                .map(TryInto::try_into)
+                .transpose()?,
             posthog: value.posthog,
             module_index_url: value.module_index_url,
             auth_api_url: value.auth_api_url,
@@ -459,6 +496,8 @@ impl TryFrom<ConfigFile> for Config {
             backfill_max_concurrent_uploads: value.backfill_max_concurrent_uploads,
             backfill_func_runs_cutoff_id: value.backfill_func_runs_cutoff_id,
             backfill_func_run_logs_cutoff_id: value.backfill_func_run_logs_cutoff_id,
+            split_migration_batch_size: value.split_migration_batch_size,
+            split_migration_workspace_ids: value.split_migration_workspace_ids,
         })
     }
 }
@@ -536,6 +575,10 @@ fn default_backfill_max_concurrent_uploads() -> usize {
     5
 }
 
+fn default_split_migration_batch_size() -> usize {
+    10
+}
+
 #[allow(clippy::disallowed_methods)] // Used to determine if running in development
 fn detect_and_configure_development(config: &mut ConfigFile) -> Result<()> {
     if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
@@ -83,7 +83,8 @@ impl SnapshotGarbageCollector {
         let mut open_change_set_snapshot_ids = HashSet::new();
         let mut all_snapshot_ids = HashSet::new();
 
-        // Gather the WorkspaceSnapshotAddress of all open change sets.
+        // Gather the WorkspaceSnapshotAddress of all open change sets, along with the snapshots
+        // they were forked from, which rebasing them onto HEAD compares against.
         let open_statuses: Vec<String> = ChangeSetStatus::iter()
             .filter_map(|status| {
                 if status.is_active() {
@@ -98,7 +99,8 @@ impl SnapshotGarbageCollector {
             &open_statuses
         );
         let change_set_snapshot_rows = ctx.txns().await?.pg().query(
-            "SELECT workspace_snapshot_address AS snapshot_id FROM change_set_pointers WHERE status = ANY($1::text[]) GROUP BY workspace_snapshot_address",
+            "SELECT workspace_snapshot_address AS snapshot_id FROM change_set_pointers WHERE status = ANY($1::text[])
+             UNION SELECT base_snapshot_address AS snapshot_id FROM change_set_pointers WHERE status = ANY($1::text[]) AND base_snapshot_address IS NOT NULL",
             &[&open_statuses],
         ).await?;
         for row in change_set_snapshot_rows {
@@ -110,6 +112,26 @@ impl SnapshotGarbageCollector {
             open_change_set_snapshot_ids.len()
         );
 
+        // Keep the legacy snapshots that split snapshot migrations replaced, so those
+        // migrations can still be rolled back.
+        let split_migration_snapshot_rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT DISTINCT previous_snapshot_address AS snapshot_id FROM workspace_split_snapshot_migration_change_sets",
+                &[],
+            )
+            .await?;
+        for row in split_migration_snapshot_rows {
+            let snapshot_id: WorkspaceSnapshotAddress = row.try_get("snapshot_id")?;
+            open_change_set_snapshot_ids.insert(snapshot_id);
+        }
+        info!(
+            "Found {} distinct snapshot address(es) for open change sets or split snapshot migrations.",
+            open_change_set_snapshot_ids.len()
+        );
+
         // Gather the WorkspaceSnapshotAddress of all existing snapshots that are
         // at least an hour old.
         //
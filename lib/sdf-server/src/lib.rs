@@ -35,6 +35,7 @@ mod routes;
 mod runnable;
 mod server;
 pub mod service;
+mod split_snapshot_migration;
 mod tracking;
 mod uds;
 
@@ -91,6 +92,13 @@ pub use self::{
         ServerMetadata,
         ServerSocket,
     },
+    split_snapshot_migration::{
+        PreparedWorkspaceMigration,
+        SplitSnapshotMigrationError,
+        SplitSnapshotMigrationResult,
+        SplitSnapshotMigrator,
+        verify_materialized_views,
+    },
 };
 pub(crate) use self::{
     app_state::AppState,
@@ -1,4 +1,10 @@
-use std::future::IntoFuture as _;
+use std::{
+    future::IntoFuture as _,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
 
 use audit_database::{
     AuditDatabaseContext,
@@ -78,6 +84,7 @@ type MigratorResult<T> = std::result::Result<T, MigratorError>;
 pub struct Migrator {
     services_context: ServicesContext,
     audit_database_context: AuditDatabaseContext,
+    module_bundle_path: Option<PathBuf>,
 }
 
 impl Migrator {
@@ -98,6 +105,7 @@ impl Migrator {
         Ok(Self::from_services(
             services_context,
             audit_database_context,
+            config.module_bundle_path().map(Path::to_path_buf),
         ))
     }
 
@@ -105,10 +113,12 @@ impl Migrator {
     pub fn from_services(
         services_context: ServicesContext,
         audit_database_context: AuditDatabaseContext,
+        module_bundle_path: Option<PathBuf>,
     ) -> Self {
         Self {
             services_context,
             audit_database_context,
+            module_bundle_path,
         }
     }
 
@@ -146,7 +156,9 @@ impl Migrator {
                 .map_err(|err| span.record_err(err))?;
         }
 
-        if update_module_cache {
+        // A configured module bundle is always loaded, since installations relying on one cannot
+        // fill their module cache from the module index.
+        if update_module_cache || self.module_bundle_path.is_some() {
             let nats_connection = self.services_context.nats_conn().clone();
             let edda_client = EddaClient::new(nats_connection).await?;
 
@@ -210,14 +222,36 @@ impl Migrator {
         async fn update_cached_modules(
             ctx: DalContext,
             edda_client: EddaClient,
+            module_bundle_path: Option<PathBuf>,
         ) -> MigratorResult<()> {
-            let new_modules = CachedModule::update_cached_modules(&ctx, edda_client)
-                .await
-                .map_err(MigratorError::migrate_cached_modules)?;
-            info!(
-                "{} new builtin assets found in module index",
-                new_modules.len()
-            );
+            match module_bundle_path {
+                Some(module_bundle_path) => {
+                    let bundle_bytes = tokio::fs::read(&module_bundle_path)
+                        .await
+                        .map_err(MigratorError::migrate_cached_modules)?;
+                    let new_modules = CachedModule::update_cached_modules_from_bundle(
+                        &ctx,
+                        bundle_bytes,
+                        edda_client,
+                    )
+                    .await
+                    .map_err(MigratorError::migrate_cached_modules)?;
+                    info!(
+                        "{} new assets found in module bundle {}",
+                        new_modules.len(),
+                        module_bundle_path.display()
+                    );
+                }
+                None => {
+                    let new_modules = CachedModule::update_cached_modules(&ctx, edda_client)
+                        .await
+                        .map_err(MigratorError::migrate_cached_modules)?;
+                    info!(
+                        "{} new builtin assets found in module index",
+                        new_modules.len()
+                    );
+                }
+            }
             Ok::<(), MigratorError>(())
         }
 
@@ -229,8 +263,10 @@ impl Migrator {
 
         info!("Updating local module cache");
 
+        let module_bundle_path = self.module_bundle_path.clone();
+
         tokio::spawn(async move {
-            match update_cached_modules(ctx, edda_client).await {
+            match update_cached_modules(ctx, edda_client, module_bundle_path).await {
                 Ok(()) => {
                     info!("Module cache updated successfully");
                 }
@@ -2,7 +2,10 @@ use std::{
     fmt,
     future::IntoFuture as _,
     net::SocketAddr,
-    path::PathBuf,
+    path::{
+        Path,
+        PathBuf,
+    },
     sync::Arc,
 };
 
@@ -83,6 +86,7 @@ pub struct Server {
 struct MigratorToolkit {
     services_context: ServicesContext,
     audit_database_context: AuditDatabaseContext,
+    module_bundle_path: Option<PathBuf>,
 }
 
 impl fmt::Debug for Server {
@@ -199,6 +203,7 @@ impl Server {
             frigg,
             audit_database_context,
             edda_client,
+            config.module_bundle_path().map(Path::to_path_buf),
         )
         .await
     }
@@ -223,6 +228,7 @@ impl Server {
         frigg: FriggStore,
         audit_database_context: AuditDatabaseContext,
         edda_client: EddaClient,
+        module_bundle_path: Option<PathBuf>,
     ) -> ServerResult<Self> {
         let app = AxumApp::from_services(
             services_context.clone(),
@@ -280,6 +286,7 @@ impl Server {
                 services_context,
                 // TODO(nick): split the migrator context and the reader-only context (should be read-only pg pool).
                 audit_database_context,
+                module_bundle_path,
             },
             socket,
         })
@@ -297,6 +304,7 @@ impl Server {
         Migrator::from_services(
             self.migrator_toolkit.services_context.clone(),
             self.migrator_toolkit.audit_database_context.clone(),
+            self.migrator_toolkit.module_bundle_path.clone(),
         )
     }
 }
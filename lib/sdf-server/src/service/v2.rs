@@ -31,6 +31,8 @@ pub mod integrations;
 pub mod management;
 pub mod module;
 pub mod policy_report;
+pub mod template;
+pub mod variable;
 pub mod variant;
 pub mod view;
 pub mod workspace;
@@ -53,6 +55,8 @@ fn workspace_routes(state: AppState) -> Router<AppState> {
                 .nest("/funcs", func::v2_routes())
                 .nest("/modules", module::v2_routes())
                 .nest("/schema-variants", variant::v2_routes())
+                .nest("/templates", template::v2_routes())
+                .nest("/variables", variable::v2_routes())
                 .nest("/management", management::v2_routes())
                 .nest("/views", view::v2_routes())
                 .nest("/action", action::v2_routes())
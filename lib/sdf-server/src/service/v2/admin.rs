@@ -65,14 +65,17 @@ use crate::{
 
 mod get_cas_data;
 mod get_snapshot;
+mod get_workspace_usage;
 mod innit;
 mod kill_execution;
 mod list_change_sets;
+mod repair_snapshot;
 mod search_workspaces;
 mod set_concurrency_limit;
 mod set_snapshot;
 mod update_module_cache;
 mod upload_cas_data;
+mod upload_module_bundle;
 mod validate_snapshot;
 
 // 1GB
@@ -89,6 +92,8 @@ pub enum AdminAPIError {
     ),
     #[error("attribute value error: {0}")]
     AttributeValue(#[from] dal::attribute::value::AttributeValueError),
+    #[error("audit database error: {0}")]
+    AuditDatabase(#[from] audit_database::AuditDatabaseError),
     #[error("axum http error: {0}")]
     AxumHttp(#[from] axum::http::Error),
     #[error("cached module error: {0}")]
@@ -111,6 +116,8 @@ pub enum AdminAPIError {
     NoMultipartData,
     #[error("slow runtime error: {0}")]
     SlowRuntime(#[from] SlowRuntimeError),
+    #[error("change set {0} uses a split snapshot, which can't be repaired yet")]
+    SplitSnapshotRepairUnsupported(ChangeSetId),
     #[error("tokio join error: {0}")]
     TokioJoin(#[from] tokio::task::JoinError),
     #[error("transactions error: {0}")]
@@ -121,6 +128,8 @@ pub enum AdminAPIError {
     WorkspaceSnapshot(#[from] dal::WorkspaceSnapshotError),
     #[error("change set {0} does not have a workspace snapshot address")]
     WorkspaceSnapshotAddressNotFound(ChangeSetId),
+    #[error("workspace snapshot graph error: {0}")]
+    WorkspaceSnapshotGraph(#[from] dal::workspace_snapshot::graph::WorkspaceSnapshotGraphError),
     #[error("workspace snapshot {0} for change set {1} could not be found in durable storage")]
     WorkspaceSnapshotNotFound(WorkspaceSnapshotAddress, ChangeSetId),
     #[error("ws event error: {0}")]
@@ -192,6 +201,10 @@ impl IntoResponse for AdminAPIError {
             AdminAPIError::FuncRunner(FuncRunnerError::DoNotHavePermissionToKillExecution) => {
                 StatusCode::UNAUTHORIZED
             }
+            AdminAPIError::AuditDatabase(
+                audit_database::AuditDatabaseError::InvalidUsageRange(_),
+            ) => StatusCode::BAD_REQUEST,
+            AdminAPIError::SplitSnapshotRepairUnsupported(_) => StatusCode::UNPROCESSABLE_ENTITY,
             _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
         };
 
@@ -212,6 +225,10 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
             "/update_module_cache",
             post(update_module_cache::update_module_cache),
         )
+        .route(
+            "/update_module_cache/bundle",
+            post(upload_module_bundle::upload_module_bundle),
+        )
         .route("/workspaces", get(search_workspaces::search_workspaces))
         .route(
             "/workspaces/:workspace_id/set_concurrency_limit",
@@ -221,6 +238,10 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
             "/workspaces/:workspace_id/change_sets",
             get(list_change_sets::list_change_sets),
         )
+        .route(
+            "/workspaces/:workspace_id/usage",
+            get(get_workspace_usage::get_workspace_usage),
+        )
         .route(
             "/workspaces/:workspace_id/change_sets/:change_set_id/get_snapshot",
             get(get_snapshot::get_snapshot),
@@ -245,6 +266,10 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
             "/workspaces/:workspace_id/change_sets/:change_set_id/validate_snapshot",
             post(validate_snapshot::validate_and_fix_snapshot),
         )
+        .route(
+            "/workspaces/:workspace_id/change_sets/:change_set_id/repair_snapshot",
+            post(repair_snapshot::repair_snapshot),
+        )
         .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
         .route_layer(axum::middleware::from_fn_with_state(
             state,
@@ -0,0 +1,67 @@
+use audit_database::{
+    UsageGranularity,
+    WorkspaceUsageRollup,
+};
+use axum::{
+    Json,
+    extract::{
+        Path,
+        Query,
+        State,
+    },
+};
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use dal::WorkspacePk;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use telemetry::prelude::*;
+
+use crate::{
+    AppState,
+    service::v2::admin::{
+        AdminAPIResult,
+        AdminUserContext,
+    },
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWorkspaceUsageRequest {
+    granularity: Option<UsageGranularity>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWorkspaceUsageResponse {
+    usage: Vec<WorkspaceUsageRollup>,
+}
+
+#[instrument(name = "admin.get_workspace_usage", skip_all)]
+pub async fn get_workspace_usage(
+    AdminUserContext(_ctx): AdminUserContext,
+    Path(workspace_id): Path<WorkspacePk>,
+    Query(request): Query<GetWorkspaceUsageRequest>,
+    State(state): State<AppState>,
+) -> AdminAPIResult<Json<GetWorkspaceUsageResponse>> {
+    let end = request.end.unwrap_or_else(Utc::now);
+    let start = request.start.unwrap_or(end - TimeDelta::days(7));
+
+    let usage = WorkspaceUsageRollup::list(
+        state.audit_database_context(),
+        workspace_id,
+        request.granularity.unwrap_or(UsageGranularity::Day),
+        start,
+        end,
+    )
+    .await?;
+
+    Ok(Json(GetWorkspaceUsageResponse { usage }))
+}
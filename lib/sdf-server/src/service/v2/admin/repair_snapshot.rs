@@ -0,0 +1,142 @@
+use axum::{
+    extract::{
+        Host,
+        OriginalUri,
+        Path,
+    },
+    response::Json,
+};
+use dal::{
+    ChangeSet,
+    ChangeSetId,
+    WorkspacePk,
+    WorkspaceSnapshotAddress,
+    WorkspaceSnapshotGraph,
+    slow_rt,
+    workspace_snapshot::{
+        WorkspaceSnapshotSelector,
+        graph::repair::{
+            RepairReport,
+            repair_graph,
+        },
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_db::Tenancy;
+use telemetry::prelude::*;
+
+use crate::{
+    extract::PosthogClient,
+    service::v2::admin::{
+        AdminAPIError,
+        AdminAPIResult,
+        AdminUserContext,
+    },
+    track_no_ctx,
+};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairSnapshotRequest {
+    /// Point the change set at the repaired snapshot. Otherwise this is a dry run that only
+    /// reports what would be repaired.
+    #[serde(default)]
+    pub apply: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairSnapshotResponse {
+    pub applied: bool,
+    pub workspace_snapshot_address: WorkspaceSnapshotAddress,
+    pub repairs: Vec<RepairReport>,
+}
+
+#[instrument(
+    name = "admin.repair_snapshot",
+    level = "info",
+    skip_all,
+    fields(
+        si.change_set.id = %change_set_id,
+        si.workspace.id = %workspace_id,
+        si.workspace_snapshot.address = Empty,
+    ),
+)]
+pub async fn repair_snapshot(
+    AdminUserContext(mut ctx): AdminUserContext,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((workspace_id, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(request): Json<RepairSnapshotRequest>,
+) -> AdminAPIResult<Json<RepairSnapshotResponse>> {
+    ctx.update_tenancy(Tenancy::new(workspace_id));
+    ctx.update_visibility_and_snapshot_to_visibility(change_set_id)
+        .await?;
+
+    let span = current_span_for_instrument_at!("info");
+
+    // Repairs are made to a single graph, so split snapshots (whose graph is spread across
+    // subgraphs) can't be repaired yet
+    let snapshot = match ctx.workspace_snapshot()? {
+        WorkspaceSnapshotSelector::LegacySnapshot(snapshot) => snapshot,
+        WorkspaceSnapshotSelector::SplitSnapshot(_) => {
+            return Err(AdminAPIError::SplitSnapshotRepairUnsupported(change_set_id));
+        }
+    };
+    let mut graph = snapshot.working_copy_cloned().await;
+
+    // Repairing validates the whole graph and serializing it is expensive, so keep both off the
+    // runtime handling requests
+    let (repairs, workspace_snapshot_address, snapshot_bytes) = slow_rt::spawn(async move {
+        let repairs = repair_graph(&mut graph)?;
+        let (snapshot_bytes, _) =
+            si_layer_cache::db::serialize::to_vec(&WorkspaceSnapshotGraph::V4(graph))?;
+        let address = WorkspaceSnapshotAddress::new(&snapshot_bytes);
+        Ok::<_, AdminAPIError>((repairs, address, snapshot_bytes))
+    })?
+    .await??;
+
+    span.record(
+        "si.workspace_snapshot.address",
+        workspace_snapshot_address.to_string(),
+    );
+
+    if request.apply {
+        // As with set_snapshot, write exactly the bytes we hashed so the address matches
+        ctx.layer_db()
+            .workspace_snapshot()
+            .write_bytes_to_durable_storage(&workspace_snapshot_address, &snapshot_bytes)
+            .await?;
+
+        ChangeSet::get_by_id(&ctx, change_set_id)
+            .await?
+            .update_pointer(&ctx, workspace_snapshot_address)
+            .await?;
+
+        ctx.commit_no_rebase().await?;
+
+        track_no_ctx(
+            &posthog_client,
+            &original_uri,
+            &host_name,
+            ctx.history_actor().distinct_id(),
+            workspace_id,
+            change_set_id,
+            "admin.repair_snapshot",
+            serde_json::json!({
+                "workspace_snapshot_address": workspace_snapshot_address.to_string(),
+                "repaired_issue_count": repairs.iter().filter(|report| report.resolved).count(),
+            }),
+        );
+    }
+
+    Ok(Json(RepairSnapshotResponse {
+        applied: request.apply,
+        workspace_snapshot_address,
+        repairs,
+    }))
+}
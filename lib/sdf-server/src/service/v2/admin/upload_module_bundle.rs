@@ -0,0 +1,111 @@
+use axum::{
+    extract::{
+        Host,
+        Multipart,
+        OriginalUri,
+    },
+    http::Uri,
+    response::Json,
+};
+use dal::{
+    DalContext,
+    WsEvent,
+    cached_module::CachedModule,
+};
+use sdf_core::async_route::handle_error;
+use sdf_extract::EddaClient;
+use si_db::Tenancy;
+use telemetry::prelude::*;
+use ulid::Ulid;
+
+use super::{
+    AdminAPIError,
+    AdminAPIResult,
+    AdminUserContext,
+    update_module_cache::UpdateModuleCacheResponse,
+};
+use crate::{
+    extract::{
+        PosthogClient,
+        workspace::TargetWorkspaceIdFromToken,
+    },
+    track,
+};
+
+/// Loads an offline module bundle, as written by `hoist write-module-bundle`, into the module
+/// cache. Used by installations that cannot reach the module index.
+#[instrument(name = "admin.upload_module_bundle", skip_all)]
+pub async fn upload_module_bundle(
+    workspace_id: TargetWorkspaceIdFromToken,
+    AdminUserContext(mut ctx): AdminUserContext,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    EddaClient(edda_client): EddaClient,
+    mut multipart: Multipart,
+) -> AdminAPIResult<Json<UpdateModuleCacheResponse>> {
+    let bundle_bytes = multipart
+        .next_field()
+        .await?
+        .ok_or(AdminAPIError::NoMultipartData)?
+        .bytes()
+        .await?
+        .to_vec();
+
+    let task_id = Ulid::new();
+
+    ctx.update_tenancy(Tenancy::new(workspace_id.into()));
+    tokio::task::spawn(async move {
+        if let Err(err) = upload_module_bundle_inner(
+            &ctx,
+            bundle_bytes,
+            &original_uri,
+            &host_name,
+            PosthogClient(posthog_client),
+            edda_client,
+        )
+        .await
+        {
+            return handle_error(&ctx, original_uri, task_id, err).await;
+        };
+
+        let event = match WsEvent::async_finish_workspace(&ctx, task_id).await {
+            Ok(event) => event,
+            Err(err) => {
+                return handle_error(&ctx, original_uri, task_id, err).await;
+            }
+        };
+
+        if let Err(err) = event.publish_immediately(&ctx).await {
+            handle_error(&ctx, original_uri, task_id, err).await;
+        };
+    });
+
+    Ok(Json(UpdateModuleCacheResponse { id: task_id }))
+}
+
+async fn upload_module_bundle_inner(
+    ctx: &DalContext,
+    bundle_bytes: Vec<u8>,
+    original_uri: &Uri,
+    host_name: &String,
+    PosthogClient(posthog_client): PosthogClient,
+    edda_client: edda_client::EddaClient,
+) -> AdminAPIResult<()> {
+    info!("Loading module bundle into the module cache");
+    let new_modules =
+        CachedModule::update_cached_modules_from_bundle(ctx, bundle_bytes, edda_client).await?;
+
+    track(
+        &posthog_client,
+        ctx,
+        original_uri,
+        host_name,
+        "upload_module_bundle",
+        serde_json::json!({
+            "new_module_count": new_modules.len(),
+        }),
+    );
+
+    Ok(())
+}
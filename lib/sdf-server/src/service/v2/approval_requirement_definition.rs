@@ -23,6 +23,7 @@ use crate::AppState;
 mod add_individual_approver;
 mod list;
 mod new;
+mod new_for_component_label;
 mod remove;
 mod remove_individual_approver;
 
@@ -65,6 +66,10 @@ impl IntoResponse for ApprovalRequirementDefinitionError {
 pub fn v2_routes() -> Router<AppState> {
     Router::new()
         .route("/", put(new::new))
+        .route(
+            "/component-label",
+            put(new_for_component_label::new_for_component_label),
+        )
         .route("/entity/:entity-id", get(list::list_for_entity))
         .route("/:id", delete(remove::remove))
         .route(
@@ -20,7 +20,10 @@ use dal::{
     },
     workspace_snapshot::EntityKindExt,
 };
-use si_frontend_types::ApprovalRequirementDefinition;
+use si_frontend_types::{
+    ApprovalRequirementComponentLabel,
+    ApprovalRequirementDefinition,
+};
 use si_id::EntityId;
 
 use super::ApprovalRequirementDefinitionError;
@@ -91,6 +94,12 @@ pub async fn list_for_entity(
             required_count: definition.required_count,
             approver_groups,
             approver_individuals,
+            component_label: definition.component_label.map(|label| {
+                ApprovalRequirementComponentLabel {
+                    key: label.key,
+                    value: label.value,
+                }
+            }),
         });
     }
 
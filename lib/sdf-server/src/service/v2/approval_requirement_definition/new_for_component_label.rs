@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use axum::{
+    Json,
+    extract::Path,
+};
+use dal::{
+    ChangeSet,
+    ChangeSetId,
+    UserPk,
+    WorkspacePk,
+    WsEvent,
+    approval_requirement::{
+        ApprovalRequirement,
+        ApprovalRequirementApprover,
+        ApprovalRequirementComponentLabel,
+        ApprovalRequirementDefinition,
+    },
+};
+use serde::Deserialize;
+use si_events::audit_log::AuditLogKind;
+
+use super::ApprovalRequirementDefinitionError;
+use crate::{
+    extract::{
+        HandlerContext,
+        PosthogEventTracker,
+    },
+    service::{
+        force_change_set_response::ForceChangeSetResponse,
+        v2::AccessBuilder,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    key: String,
+    value: String,
+    users: Option<Vec<UserPk>>,
+}
+
+pub async fn new_for_component_label(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    tracker: PosthogEventTracker,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(request): Json<Request>,
+) -> Result<ForceChangeSetResponse<()>, ApprovalRequirementDefinitionError> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let mut approvers = HashSet::new();
+
+    if let Some(users) = request.users.to_owned() {
+        approvers.extend(users.into_iter().map(ApprovalRequirementApprover::User));
+    }
+
+    let title = format!("Component - label:{}={}", request.key, request.value);
+    let approval_requirement_definition_id = ApprovalRequirement::new_component_label_definition(
+        &ctx,
+        ApprovalRequirementComponentLabel {
+            key: request.key,
+            value: request.value,
+        },
+        1, // TODO(nick): allow users to change the minimum approvers count
+        approvers,
+    )
+    .await?;
+    let entity_id =
+        ApprovalRequirementDefinition::entity_id_for_approval_requirement_definition_id(
+            &ctx,
+            approval_requirement_definition_id,
+        )
+        .await?;
+
+    ctx.write_audit_log(
+        AuditLogKind::CreateApprovalRequirementDefinition {
+            individual_approvers: request.users.to_owned().unwrap_or(Vec::new()),
+            approval_requirement_definition_id,
+            entity_name: Some(title.to_owned()),
+            entity_kind: "Component".to_string(),
+            entity_id,
+        },
+        title.to_owned(),
+    )
+    .await?;
+
+    tracker.track(
+        &ctx,
+        "create_approval_requirement",
+        serde_json::json!({
+            "entity_kind": "Component",
+            "entity_name": title,
+        }),
+    );
+
+    WsEvent::requirement_created(&ctx, entity_id, request.users)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::empty(force_change_set_id))
+}
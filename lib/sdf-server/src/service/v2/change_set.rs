@@ -22,6 +22,7 @@ use dal::{
     WorkspacePk,
     WorkspaceSnapshotAddress,
     WsEventError,
+    change_set::rebase::ChangeSetRebaseError,
     diagram::DiagramError,
     prop::PropError,
     property_editor::PropertyEditorError,
@@ -52,6 +53,7 @@ mod create;
 mod create_initialize_apply;
 mod force_apply;
 mod list;
+mod rebase;
 mod rename;
 mod reopen;
 mod request_approval;
@@ -71,6 +73,8 @@ pub enum Error {
     ChangeSetApproval(#[from] dal::change_set::approval::ChangeSetApprovalError),
     #[error("change set mvs error: {0}")]
     ChangeSetMvs(#[from] sdf_core::change_set_mvs::ChangeSetMvsError),
+    #[error("change set rebase error: {0}")]
+    ChangeSetRebase(#[from] ChangeSetRebaseError),
     #[error("component error: {0}")]
     Component(#[from] dal::ComponentError),
     #[error("dal wrapper error: {0}")]
@@ -161,6 +165,11 @@ impl IntoResponse for Error {
                 (StatusCode::PRECONDITION_FAILED, None)
             }
             Self::ChangeSetApply(_) => (StatusCode::CONFLICT, None),
+            Self::ChangeSetRebase(
+                ChangeSetRebaseError::CannotRebaseHead(_)
+                | ChangeSetRebaseError::MissingResolution(_)
+                | ChangeSetRebaseError::UnknownConflict(_),
+            ) => (StatusCode::UNPROCESSABLE_ENTITY, None),
             Self::Transactions(dal::TransactionsError::BadWorkspaceAndChangeSet) => {
                 (StatusCode::FORBIDDEN, None)
             }
@@ -253,6 +262,7 @@ pub fn change_set_routes(state: AppState) -> Router<AppState> {
                 permissions::Permission::Approve,
             )),
         )
+        .route("/rebase", get(rebase::detect_rebase).post(rebase::rebase))
         .route("/rename", post(rename::rename))
         // Consider how we make it editable again after it's been rejected
         .route("/reopen", post(reopen::reopen))
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use axum::Json;
+use dal::change_set::rebase::{
+    RebaseReport,
+    RebaseResolution,
+    detect,
+    rebase_onto_head,
+};
+use sdf_extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+use serde::Deserialize;
+use si_id::EntityId;
+
+use super::Result;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseRequest {
+    resolutions: HashMap<EntityId, RebaseResolution>,
+}
+
+pub async fn detect_rebase(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+) -> Result<Json<RebaseReport>> {
+    Ok(Json(detect(ctx).await?))
+}
+
+pub async fn rebase(
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Json(request): Json<RebaseRequest>,
+) -> Result<Json<RebaseReport>> {
+    let report = rebase_onto_head(ctx, &request.resolutions).await?;
+
+    tracker.track(
+        ctx,
+        "rebase_change_set",
+        serde_json::json!({
+            "change_set": ctx.change_set_id(),
+            "conflicts": report.conflicts.len(),
+            "incoming_components": report.incoming_component_ids.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(report))
+}
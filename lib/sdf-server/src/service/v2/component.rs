@@ -33,6 +33,7 @@ pub mod debug_component;
 pub mod delete_components;
 pub mod get_json;
 pub mod manage;
+pub mod metadata;
 pub mod name;
 pub mod restore_components;
 pub mod secrets;
@@ -92,6 +93,7 @@ impl IntoResponse for Error {
                 StatusCode::NOT_MODIFIED
             }
             Error::AttributeValueNotFound(_, _) => StatusCode::NOT_FOUND,
+            Error::Component(dal::ComponentError::InvalidLabelKey(_)) => StatusCode::BAD_REQUEST,
             Error::Attributes(AttributesError::AttributeValue(
                 AttributeValueError::SubscriptionWouldCycle { .. },
             ))
@@ -117,6 +119,7 @@ pub fn v2_routes() -> Router<AppState> {
                 .route("/debug", get(debug_component::debug_component))
                 .route("/json", get(get_json::get_json))
                 .nest("/attributes", attributes::v2_routes())
+                .nest("/metadata", metadata::v2_routes())
                 .nest("/name", name::v2_routes())
                 .nest("/secret", secrets::v2_routes())
                 .nest("/manage", manage::v2_routes()),
@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Json,
+    Router,
+    extract::Path,
+    routing::put,
+};
+use dal::{
+    ChangeSet,
+    Component,
+};
+use sdf_core::force_change_set_response::ForceChangeSetResponse;
+use sdf_extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+
+use super::{
+    ComponentIdFromPath,
+    Result,
+};
+use crate::app_state::AppState;
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new().route("/", put(set_metadata))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMetadataRequest {
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    pub owner: Option<String>,
+}
+
+async fn set_metadata(
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(ComponentIdFromPath { component_id }): Path<ComponentIdFromPath>,
+    Json(payload): Json<SetMetadataRequest>,
+) -> Result<ForceChangeSetResponse<()>> {
+    // only in use by the new UI, no WsEvents needed!
+    let force_change_set_id = ChangeSet::force_new(ctx).await?;
+    let label_count = payload.labels.len();
+    Component::get_by_id(ctx, component_id)
+        .await?
+        .set_labels(ctx, payload.labels)
+        .await?
+        .set_owner(ctx, payload.owner.to_owned())
+        .await?;
+    ctx.commit().await?;
+
+    tracker.track(
+        ctx,
+        "component_set_metadata",
+        json!({
+            "how": "/component/set_metadata",
+            "component_id": component_id,
+            "change_set_id": ctx.change_set_id(),
+            "label_count": label_count,
+            "has_owner": payload.owner.is_some(),
+        }),
+    );
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, ()))
+}
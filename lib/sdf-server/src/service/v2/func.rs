@@ -147,6 +147,7 @@ impl IntoResponse for FuncAPIError {
                     }
                     FunctionResultFailureErrorKind::InvalidReturnType
                     | FunctionResultFailureErrorKind::KilledExecution
+                    | FunctionResultFailureErrorKind::ResourceLimitExceeded
                     | FunctionResultFailureErrorKind::ActionFieldWrongType => {
                         (StatusCode::UNPROCESSABLE_ENTITY, Some(message))
                     }
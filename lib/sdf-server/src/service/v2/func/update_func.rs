@@ -41,6 +41,10 @@ use crate::{
 pub struct UpdateFuncRequest {
     pub display_name: Option<String>,
     pub description: Option<String>,
+    /// Opts the func in to (or out of) reusing the results of previous executions with the same
+    /// arguments. Left unchanged when unset.
+    #[serde(default)]
+    pub memoization_enabled: Option<bool>,
     client_ulid: Ulid,
 }
 
@@ -60,9 +64,18 @@ pub async fn update_func(
     let old_func = Func::get_by_id(&ctx, func_id).await?;
     let updated_func =
         FuncAuthoringClient::update_func(&ctx, func_id, request.display_name, request.description)
-            .await?
-            .into_frontend_type(&ctx)
             .await?;
+    let updated_func = match request
+        .memoization_enabled
+        .filter(|enabled| *enabled != updated_func.memoization_enabled)
+    {
+        Some(memoization_enabled) => {
+            FuncAuthoringClient::set_memoization_enabled(&ctx, func_id, memoization_enabled).await?
+        }
+        None => updated_func,
+    }
+    .into_frontend_type(&ctx)
+    .await?;
 
     WsEvent::func_updated(&ctx, updated_func.clone(), Some(request.client_ulid))
         .await?
@@ -90,6 +103,7 @@ pub async fn update_func(
             "func_id": func_id,
             "func_name": updated_func.name.clone(),
             "func_kind": updated_func.kind.clone(),
+            "memoization_enabled": updated_func.memoization_enabled,
         }),
     );
     ctx.commit().await?;
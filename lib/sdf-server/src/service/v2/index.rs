@@ -11,12 +11,18 @@ use super::AccessBuilder;
 use crate::AppState;
 
 mod get_change_set_index;
+mod get_change_set_index_at;
 mod get_front_end_object;
 mod rebuild_change_set_index;
 
 pub fn v2_change_set_routes() -> Router<AppState> {
     Router::new()
         .route("/", get(get_change_set_index::get_change_set_index))
+        .route("/at", get(get_change_set_index_at::get_change_set_index_at))
+        .route(
+            "/history",
+            get(get_change_set_index_at::list_change_set_index_history),
+        )
         .route("/mjolnir", get(get_front_end_object::get_front_end_object))
         .route(
             "/multi_mjolnir",
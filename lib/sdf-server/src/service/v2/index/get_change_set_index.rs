@@ -2,7 +2,10 @@ use std::time::Duration;
 
 use axum::{
     Json,
-    extract::Path,
+    extract::{
+        Path,
+        State,
+    },
     http::StatusCode,
     response::IntoResponse,
 };
@@ -12,6 +15,7 @@ use dal::{
 };
 use futures_lite::StreamExt;
 use sdf_core::index::{
+    DefinitionRebuildRequests,
     FrontEndObjectMeta,
     IndexError,
 };
@@ -21,10 +25,13 @@ use super::{
     AccessBuilder,
     IndexResult,
 };
-use crate::extract::{
-    EddaClient,
-    FriggStore,
-    HandlerContext,
+use crate::{
+    AppState,
+    extract::{
+        EddaClient,
+        FriggStore,
+        HandlerContext,
+    },
 };
 
 const WATCH_INDEX_TIMEOUT: Duration = Duration::from_secs(4);
@@ -35,15 +42,30 @@ pub async fn get_change_set_index(
     FriggStore(frigg): FriggStore,
     EddaClient(edda_client): EddaClient,
     Path((workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    State(state): State<AppState>,
 ) -> IndexResult<impl IntoResponse> {
     let _ctx = builder
         .build(access_builder.build(change_set_id.into()))
         .await?;
     let index = match frigg
-        .get_change_set_index(workspace_pk, change_set_id)
+        .get_change_set_index_allowing_outdated(workspace_pk, change_set_id)
         .await?
     {
-        Some((index, _kv_revision)) => index,
+        Some((index, _kv_revision, outdated_kinds)) => {
+            // Keep serving the existing index while the outdated kinds are rebuilt in the
+            // background, rather than making the caller wait on (or retry) a full rebuild.
+            if !outdated_kinds.is_empty() {
+                request_changed_definitions_rebuild(
+                    &edda_client,
+                    state.definition_rebuild_requests(),
+                    workspace_pk,
+                    change_set_id,
+                    outdated_kinds.len(),
+                )
+                .await;
+            }
+            index
+        }
         None => {
             info!(
                 "Index not found for change_set {}; attempting full build",
@@ -111,3 +133,51 @@ async fn request_rebuild_and_watch(
         _ = watch.next() => Ok(true)
     }
 }
+
+#[instrument(
+    level = "info",
+    name = "sdf.index.request_changed_definitions_rebuild",
+    skip_all,
+    fields(
+        si.workspace.id = %workspace_pk,
+        si.change_set.id = %change_set_id,
+        si.edda.mv.outdated_mv.kind_count = outdated_kind_count,
+        si.edda_request.id = Empty,
+        si.edda_request.coalesced = Empty,
+    )
+)]
+async fn request_changed_definitions_rebuild(
+    edda_client: &edda_client::EddaClient,
+    definition_rebuild_requests: &DefinitionRebuildRequests,
+    workspace_pk: WorkspacePk,
+    change_set_id: ChangeSetId,
+    outdated_kind_count: usize,
+) {
+    let span = Span::current();
+    // Every read of the index sees it as outdated until the rebuild lands, so only the first read
+    // in a while sends a request.
+    if !definition_rebuild_requests
+        .start(workspace_pk, change_set_id)
+        .await
+    {
+        span.record("si.edda_request.coalesced", true);
+        return;
+    }
+
+    match edda_client
+        .rebuild_changed_definitions_for_change_set(workspace_pk, change_set_id)
+        .await
+    {
+        Ok(request_id) => {
+            span.record("si.edda_request.id", request_id.to_string());
+        }
+        // The outdated index is still usable, so failing to request the rebuild shouldn't fail
+        // the request.
+        Err(err) => {
+            warn!(si.error.message = ?err, "failed to request rebuild of outdated definitions");
+            definition_rebuild_requests
+                .forget(workspace_pk, change_set_id)
+                .await;
+        }
+    }
+}
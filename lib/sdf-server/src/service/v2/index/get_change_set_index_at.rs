@@ -0,0 +1,83 @@
+use axum::{
+    Json,
+    extract::{
+        Path,
+        Query,
+    },
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use dal::{
+    ChangeSetId,
+    WorkspacePk,
+};
+use frigg::{
+    ChangeSetIndexHistoryEntry,
+    IndexAt,
+};
+use sdf_core::index::{
+    FrontEndObjectMeta,
+    IndexError,
+};
+use serde::Deserialize;
+
+use super::{
+    AccessBuilder,
+    IndexResult,
+};
+use crate::extract::{
+    FriggStore,
+    HandlerContext,
+};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetIndexAtRequest {
+    pub revision: Option<u64>,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+pub async fn get_change_set_index_at(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    FriggStore(frigg): FriggStore,
+    Path((workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Query(request): Query<ChangeSetIndexAtRequest>,
+) -> IndexResult<Json<FrontEndObjectMeta>> {
+    let _ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let at = IndexAt::from_revision_or_timestamp(request.revision, request.timestamp)
+        .ok_or(IndexError::InvalidIndexAtRequest)?;
+
+    let (index, _kv_revision) = frigg
+        .get_change_set_index_at(workspace_pk, change_set_id, at)
+        .await?
+        .ok_or(IndexError::IndexNotFoundAt(workspace_pk, change_set_id))?;
+
+    Ok(Json(FrontEndObjectMeta {
+        workspace_snapshot_address: index.id.clone(),
+        index_checksum: index.checksum.clone(),
+        front_end_object: index,
+    }))
+}
+
+pub async fn list_change_set_index_history(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    FriggStore(frigg): FriggStore,
+    Path((workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> IndexResult<Json<Vec<ChangeSetIndexHistoryEntry>>> {
+    let _ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(
+        frigg
+            .list_change_set_index_history(workspace_pk, change_set_id)
+            .await?,
+    ))
+}
@@ -102,6 +102,11 @@ impl IntoResponse for ModulesAPIError {
             Self::Module(dal::module::ModuleError::EmptyMetadata(_, _)) => StatusCode::BAD_REQUEST,
             Self::ContributionFailure(_) => StatusCode::BAD_REQUEST,
             Self::ModuleHashNotFound(_) => StatusCode::NOT_FOUND,
+            Self::Pkg(
+                PkgError::InvalidModuleSignature(_)
+                | PkgError::UnsignedModule(_)
+                | PkgError::UntrustedModuleSigner(_),
+            ) => StatusCode::FORBIDDEN,
             _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
         };
 
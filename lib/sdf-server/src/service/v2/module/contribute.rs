@@ -77,6 +77,7 @@ pub async fn contribute(
             Some(request.schema_variant_id.to_string()),
             Some(schema_variant_version.clone()),
             None,
+            None,
         )
         .await?;
 
@@ -13,6 +13,7 @@ use dal::{
         import_funcs_for_module_update,
         import_pkg_from_pkg,
         import_schema_variant,
+        verify_module_signature,
     },
 };
 use si_frontend_types::SchemaVariant as FrontendVariant;
@@ -54,6 +55,9 @@ pub async fn install_module_from_file(
 
     let spec: PkgSpec = serde_json::from_str(&module_string)?;
     let pkg = SiPkg::load_from_spec(spec)?;
+    // Uploaded files carry no publisher signature, so they can only be installed into workspaces
+    // that do not require one.
+    verify_module_signature(ctx, &pkg, None).await?;
 
     let mut variants = Vec::new();
 
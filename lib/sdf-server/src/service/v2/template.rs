@@ -0,0 +1,294 @@
+use axum::{
+    Json,
+    Router,
+    extract::Path,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response,
+    },
+    routing::{
+        get,
+        post,
+    },
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use dal::{
+    ChangeSet,
+    ComponentId,
+    component_template::{
+        self,
+        ComponentTemplate,
+        ComponentTemplateError,
+        ComponentTemplateInstance,
+        TemplateInstantiation,
+        TemplateParameterBindings,
+        TemplateParameterValues,
+    },
+};
+use sdf_core::{
+    api_error::ApiError,
+    force_change_set_response::ForceChangeSetResponse,
+};
+use sdf_extract::{
+    PosthogEventTracker,
+    change_set::ChangeSetDalContext,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::json;
+use si_pkg::TemplateParameterSpec;
+use thiserror::Error;
+
+use crate::app_state::AppState;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum TemplateAPIError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] dal::ChangeSetError),
+    #[error("component template error: {0}")]
+    ComponentTemplate(#[from] ComponentTemplateError),
+    #[error("component template storage error: {0}")]
+    Db(#[from] si_db::ComponentTemplateError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] dal::TransactionsError),
+}
+
+pub type TemplateAPIResult<T> = Result<T, TemplateAPIError>;
+
+impl IntoResponse for TemplateAPIError {
+    fn into_response(self) -> Response {
+        let status_code = match &self {
+            TemplateAPIError::ComponentTemplate(
+                ComponentTemplateError::InstanceNotFound(_)
+                | ComponentTemplateError::TemplateNotFound(_),
+            ) => StatusCode::NOT_FOUND,
+            TemplateAPIError::ComponentTemplate(
+                ComponentTemplateError::InstanceNameInUse { .. }
+                | ComponentTemplateError::Db(si_db::ComponentTemplateError::VersionConflict(..)),
+            )
+            | TemplateAPIError::Db(si_db::ComponentTemplateError::VersionConflict(..)) => {
+                StatusCode::CONFLICT
+            }
+            TemplateAPIError::ComponentTemplate(
+                ComponentTemplateError::EmptyTemplate(_)
+                | ComponentTemplateError::MissingParameter(_)
+                | ComponentTemplateError::ParameterBindingForUnknownComponent(_)
+                | ComponentTemplateError::UndeclaredParameter(_)
+                | ComponentTemplateError::UnknownParameter(_),
+            ) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
+        };
+
+        ApiError::new(status_code, self).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_templates).post(save_template))
+        .nest(
+            "/:template_name",
+            Router::new()
+                .route("/instantiate", post(instantiate_template))
+                .route("/instances", get(list_instances))
+                .route("/instances/:instance_name/upgrade", post(upgrade_instance)),
+        )
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TemplateNameFromPath {
+    template_name: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TemplateInstanceFromPath {
+    template_name: String,
+    instance_name: String,
+}
+
+/// The latest version of a component template.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateView {
+    pub name: String,
+    pub version: i32,
+    pub description: Option<String>,
+    pub parameters: Vec<TemplateParameterSpec>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TemplateView {
+    fn try_from_template(template: ComponentTemplate) -> TemplateAPIResult<Self> {
+        let parameters = component_template::parameters(&template)?;
+        Ok(Self {
+            name: template.name,
+            version: template.version,
+            description: template.description,
+            parameters,
+            created_at: template.created_at,
+        })
+    }
+}
+
+async fn list_templates(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+) -> TemplateAPIResult<Json<Vec<TemplateView>>> {
+    let templates = ComponentTemplate::list_latest(ctx)
+        .await?
+        .into_iter()
+        .map(TemplateView::try_from_template)
+        .collect::<TemplateAPIResult<_>>()?;
+
+    Ok(Json(templates))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveTemplateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub component_ids: Vec<ComponentId>,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameterSpec>,
+    #[serde(default)]
+    pub bindings: TemplateParameterBindings,
+}
+
+async fn save_template(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Json(payload): Json<SaveTemplateRequest>,
+) -> TemplateAPIResult<Json<TemplateView>> {
+    let template = component_template::save(
+        ctx,
+        payload.name,
+        payload.description,
+        &payload.component_ids,
+        payload.parameters,
+        payload.bindings,
+    )
+    .await?;
+
+    tracker.track(
+        ctx,
+        "save_component_template",
+        json!({
+            "how": "/templates",
+            "template_name": template.name,
+            "template_version": template.version,
+            "component_count": payload.component_ids.len(),
+        }),
+    );
+
+    // Templates are stored outside of the workspace snapshot, so there is nothing to rebase
+    ctx.commit_no_rebase().await?;
+
+    Ok(Json(TemplateView::try_from_template(template)?))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateRequest {
+    pub instance_name: String,
+    #[serde(default)]
+    pub parameters: TemplateParameterValues,
+}
+
+async fn instantiate_template(
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(TemplateNameFromPath { template_name }): Path<TemplateNameFromPath>,
+    Json(payload): Json<InstantiateTemplateRequest>,
+) -> TemplateAPIResult<ForceChangeSetResponse<TemplateInstantiation>> {
+    let force_change_set_id = ChangeSet::force_new(ctx).await?;
+
+    let instantiation = component_template::instantiate(
+        ctx,
+        &template_name,
+        payload.instance_name,
+        payload.parameters,
+    )
+    .await?;
+
+    tracker.track(
+        ctx,
+        "instantiate_component_template",
+        json!({
+            "how": "/templates/instantiate",
+            "template_name": template_name,
+            "template_version": instantiation.instance.template_version,
+            "component_count": instantiation.components.len(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(
+        force_change_set_id,
+        instantiation,
+    ))
+}
+
+async fn list_instances(
+    ChangeSetDalContext(ref ctx): ChangeSetDalContext,
+    Path(TemplateNameFromPath { template_name }): Path<TemplateNameFromPath>,
+) -> TemplateAPIResult<Json<Vec<ComponentTemplateInstance>>> {
+    Ok(Json(
+        ComponentTemplateInstance::list_for_template(ctx, &template_name).await?,
+    ))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeInstanceRequest {
+    #[serde(default)]
+    pub parameters: TemplateParameterValues,
+}
+
+async fn upgrade_instance(
+    ChangeSetDalContext(ref mut ctx): ChangeSetDalContext,
+    tracker: PosthogEventTracker,
+    Path(TemplateInstanceFromPath {
+        template_name,
+        instance_name,
+    }): Path<TemplateInstanceFromPath>,
+    Json(payload): Json<UpgradeInstanceRequest>,
+) -> TemplateAPIResult<ForceChangeSetResponse<TemplateInstantiation>> {
+    match ComponentTemplateInstance::get(ctx, &instance_name).await? {
+        Some(instance) if instance.template_name == template_name => {}
+        _ => {
+            return Err(ComponentTemplateError::InstanceNotFound(instance_name).into());
+        }
+    }
+
+    let force_change_set_id = ChangeSet::force_new(ctx).await?;
+
+    let upgrade = component_template::upgrade(ctx, &instance_name, payload.parameters).await?;
+
+    tracker.track(
+        ctx,
+        "upgrade_component_template_instance",
+        json!({
+            "how": "/templates/instances/upgrade",
+            "template_name": template_name,
+            "template_version": upgrade.instance.template_version,
+            "changed_count": upgrade.components.iter().filter(|c| !c.is_unchanged()).count(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, upgrade))
+}
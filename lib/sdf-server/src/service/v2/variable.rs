@@ -0,0 +1,72 @@
+use axum::{
+    Router,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response,
+    },
+    routing::{
+        get,
+        put,
+    },
+};
+use dal::{
+    ChangeSetError,
+    TransactionsError,
+    WorkspaceSnapshotError,
+    variable::VariableError,
+};
+use sdf_core::api_error::ApiError;
+use thiserror::Error;
+
+use crate::app_state::AppState;
+
+pub mod create_variable;
+pub mod list_variables;
+mod remove_variable;
+pub mod update_variable;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum VariableAPIError {
+    #[error("changeset error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("variable error: {0}")]
+    Variable(#[from] VariableError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+pub type VariableAPIResult<T> = Result<T, VariableAPIError>;
+
+impl IntoResponse for VariableAPIError {
+    fn into_response(self) -> Response {
+        let status_code = match &self {
+            VariableAPIError::Variable(VariableError::VariableNotFound(_)) => StatusCode::NOT_FOUND,
+            VariableAPIError::Variable(VariableError::NameAlreadyInUse(_))
+            | VariableAPIError::Variable(VariableError::VariableInUse(_, _)) => {
+                StatusCode::CONFLICT
+            }
+            VariableAPIError::Variable(VariableError::InvalidName(_)) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            _ => ApiError::DEFAULT_ERROR_STATUS_CODE,
+        };
+
+        ApiError::new(status_code, self).into_response()
+    }
+}
+
+pub fn v2_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(list_variables::list_variables).post(create_variable::create_variable),
+        )
+        .route(
+            "/:variable_id",
+            put(update_variable::update_variable).delete(remove_variable::remove_variable),
+        )
+}
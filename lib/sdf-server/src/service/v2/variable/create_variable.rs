@@ -0,0 +1,79 @@
+use axum::{
+    Json,
+    extract::{
+        Host,
+        OriginalUri,
+        Path,
+    },
+};
+use dal::{
+    ChangeSet,
+    ChangeSetId,
+    WorkspacePk,
+    variable::Variable,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::VariableAPIResult;
+use crate::{
+    extract::{
+        HandlerContext,
+        PosthogClient,
+    },
+    service::{
+        force_change_set_response::ForceChangeSetResponse,
+        v2::AccessBuilder,
+    },
+    tracking::track,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub name: String,
+    pub value: serde_json::Value,
+    pub description: Option<String>,
+}
+
+pub async fn create_variable(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+    Json(Request {
+        name,
+        value,
+        description,
+    }): Json<Request>,
+) -> VariableAPIResult<ForceChangeSetResponse<Variable>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let variable = Variable::new(&ctx, name, value, description).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "create_variable",
+        serde_json::json!({
+            "how": "/variables",
+            "variable_id": variable.id(),
+            "variable_name": variable.name(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, variable))
+}
@@ -0,0 +1,29 @@
+use axum::extract::{
+    Json,
+    Path,
+};
+use dal::{
+    ChangeSetId,
+    WorkspacePk,
+    variable::Variable,
+};
+
+use super::VariableAPIResult;
+use crate::{
+    extract::HandlerContext,
+    service::v2::AccessBuilder,
+};
+
+pub type Response = Vec<Variable>;
+
+pub async fn list_variables(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Path((_workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> VariableAPIResult<Json<Response>> {
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    Ok(Json(Variable::list(&ctx).await?))
+}
@@ -0,0 +1,63 @@
+use axum::extract::{
+    Host,
+    OriginalUri,
+    Path,
+};
+use dal::{
+    ChangeSet,
+    ChangeSetId,
+    WorkspacePk,
+    variable::{
+        Variable,
+        VariableId,
+    },
+};
+
+use super::VariableAPIResult;
+use crate::{
+    extract::{
+        HandlerContext,
+        PosthogClient,
+    },
+    service::{
+        force_change_set_response::ForceChangeSetResponse,
+        v2::AccessBuilder,
+    },
+    track,
+};
+
+pub async fn remove_variable(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, variable_id)): Path<(WorkspacePk, ChangeSetId, VariableId)>,
+) -> VariableAPIResult<ForceChangeSetResponse<()>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let variable = Variable::get_by_id(&ctx, variable_id).await?;
+    Variable::remove(&ctx, variable_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "remove_variable",
+        serde_json::json!({
+            "how": "/variables",
+            "variable_id": variable.id(),
+            "variable_name": variable.name(),
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::empty(force_change_set_id))
+}
@@ -0,0 +1,89 @@
+use axum::{
+    Json,
+    extract::{
+        Host,
+        OriginalUri,
+        Path,
+    },
+};
+use dal::{
+    ChangeSet,
+    ChangeSetId,
+    WorkspacePk,
+    variable::{
+        Variable,
+        VariableId,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::VariableAPIResult;
+use crate::{
+    extract::{
+        HandlerContext,
+        PosthogClient,
+    },
+    service::{
+        force_change_set_response::ForceChangeSetResponse,
+        v2::AccessBuilder,
+    },
+    tracking::track,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub name: String,
+    pub value: serde_json::Value,
+    pub description: Option<String>,
+}
+
+pub async fn update_variable(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((_workspace_pk, change_set_id, variable_id)): Path<(WorkspacePk, ChangeSetId, VariableId)>,
+    Json(Request {
+        name,
+        value,
+        description,
+    }): Json<Request>,
+) -> VariableAPIResult<ForceChangeSetResponse<Variable>> {
+    let mut ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
+
+    let variable = Variable::get_by_id(&ctx, variable_id).await?;
+    let old_name = variable.name().to_owned();
+    let variable = variable
+        .update_metadata(&ctx, name, description)
+        .await?
+        .set_value(&ctx, value)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        &host_name,
+        "update_variable",
+        serde_json::json!({
+            "how": "/variables",
+            "variable_id": variable.id(),
+            "variable_new_name": variable.name(),
+            "variable_old_name": old_name,
+            "change_set_id": ctx.change_set_id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(ForceChangeSetResponse::new(force_change_set_id, variable))
+}
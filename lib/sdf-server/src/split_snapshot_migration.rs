@@ -0,0 +1,782 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    future::IntoFuture as _,
+    str::FromStr,
+};
+
+use dal::{
+    ChangeSet,
+    ChangeSetError,
+    ChangeSetId,
+    ChangeSetStatus,
+    Component,
+    ComponentError,
+    DalContext,
+    ServicesContext,
+    TransactionsError,
+    Workspace,
+    WorkspaceError,
+    WorkspacePk,
+    WorkspaceSnapshot,
+    WorkspaceSnapshotAddress,
+    WorkspaceSnapshotError,
+    workspace::SnapshotVersion,
+    workspace_snapshot::{
+        migrator::{
+            SnapshotGraphMigrator,
+            SnapshotGraphMigratorError,
+        },
+        split_snapshot::{
+            SplitSnapshot,
+            SubGraphVersionDiscriminants,
+            SuperGraphVersionDiscriminants,
+        },
+    },
+};
+use futures::future::join_all;
+use serde::Serialize;
+use si_data_pg::PgError;
+use si_db::{
+    Tenancy,
+    Visibility,
+};
+use strum::Display;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio_util::{
+    sync::CancellationToken,
+    task::TaskTracker,
+};
+
+use crate::{
+    Config,
+    init,
+};
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum SplitSnapshotMigrationError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error(
+        "change set {0} was created after the workspace was migrated and cannot be rolled back"
+    )]
+    ChangeSetCreatedAfterMigration(ChangeSetId),
+    #[error("change set {0} was created while its workspace was being migrated")]
+    ChangeSetCreatedDuringMigration(ChangeSetId),
+    #[error("change set {0} was modified while being migrated")]
+    ChangeSetModifiedDuringMigration(ChangeSetId),
+    #[error(
+        "change set {0} was modified after the workspace was migrated and cannot be rolled back"
+    )]
+    ChangeSetModifiedSinceMigration(ChangeSetId),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("error while initializing: {0}")]
+    Init(#[from] init::InitError),
+    #[error("invalid workspace id {0}: {1}")]
+    InvalidWorkspaceId(String, ulid::DecodeError),
+    #[error("materialized view error: {0}")]
+    MaterializedView(#[from] dal_materialized_views::Error),
+    #[error("no split snapshot migration recorded for workspace {0}")]
+    MigrationNotFound(WorkspacePk),
+    #[error("snapshot migrator error: {0}")]
+    Migrator(#[from] SnapshotGraphMigratorError),
+    #[error("workspace ids are required to roll back split snapshot migrations")]
+    MissingWorkspaceIds,
+    #[error("workspace {0} is not migrated (status: {1})")]
+    NotMigrated(WorkspacePk, String),
+    #[error("Pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("Transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("{view} materialized view differs after migrating change set {change_set_id}")]
+    VerificationFailed {
+        change_set_id: ChangeSetId,
+        view: String,
+    },
+    #[error("workspace error: {0}")]
+    Workspace(#[from] WorkspaceError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+}
+
+impl SplitSnapshotMigrationError {
+    /// Whether the workspace was written to while it was being migrated, in which case migrating
+    /// it again on a later run may succeed.
+    fn is_workspace_busy(&self) -> bool {
+        matches!(
+            self,
+            Self::ChangeSetCreatedDuringMigration(_) | Self::ChangeSetModifiedDuringMigration(_)
+        )
+    }
+}
+
+pub type SplitSnapshotMigrationResult<T> = std::result::Result<T, SplitSnapshotMigrationError>;
+
+type Result<T> = SplitSnapshotMigrationResult<T>;
+
+/// The status recorded in `workspace_split_snapshot_migrations` for a workspace.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[strum(serialize_all = "camelCase")]
+enum MigrationStatus {
+    Failed,
+    Migrated,
+    RolledBack,
+}
+
+/// A change set that was pointed at a split snapshot by the migration.
+struct MigratedChangeSet {
+    change_set_id: ChangeSetId,
+    previous_snapshot_address: WorkspaceSnapshotAddress,
+    migrated_snapshot_address: WorkspaceSnapshotAddress,
+}
+
+/// A workspace whose change sets have been converted to split snapshots and verified, but not yet
+/// switched over to them.
+pub struct PreparedWorkspaceMigration {
+    workspace: Workspace,
+    migrated_change_sets: Vec<MigratedChangeSet>,
+}
+
+/// Migrates workspaces from legacy to split snapshots in batches, verifying that every migrated
+/// change set produces the same materialized views as before. Workspaces are only switched over
+/// once all of their change sets verify, and the previous snapshot addresses and versions are
+/// recorded so the migration can be rolled back.
+pub struct SplitSnapshotMigrator {
+    services_context: ServicesContext,
+    batch_size: usize,
+    workspace_ids: Option<Vec<WorkspacePk>>,
+}
+
+impl SplitSnapshotMigrator {
+    #[instrument(name = "sdf.split_snapshot_migrator.new", level = "info", skip_all)]
+    pub async fn new(
+        config: Config,
+        task_tracker: &TaskTracker,
+        task_token: CancellationToken,
+    ) -> Result<Self> {
+        let workspace_ids = config
+            .split_migration_workspace_ids()
+            .map(|ids| {
+                ids.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| {
+                        WorkspacePk::from_str(id).map_err(|err| {
+                            SplitSnapshotMigrationError::InvalidWorkspaceId(id.to_string(), err)
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+
+        let (services_context, layer_db_graceful_shutdown) =
+            init::services_context_from_config(&config, task_token).await?;
+
+        task_tracker.spawn(layer_db_graceful_shutdown.into_future());
+
+        Ok(Self::from_services_context(
+            services_context,
+            config.split_migration_batch_size(),
+            workspace_ids,
+        ))
+    }
+
+    pub fn from_services_context(
+        services_context: ServicesContext,
+        batch_size: usize,
+        workspace_ids: Option<Vec<WorkspacePk>>,
+    ) -> Self {
+        Self {
+            services_context,
+            batch_size: batch_size.max(1),
+            workspace_ids,
+        }
+    }
+
+    #[instrument(name = "sdf.split_snapshot_migrator.migrate", level = "info", skip_all)]
+    pub async fn migrate(self, shutdown_token: CancellationToken) -> Result<()> {
+        let span = current_span_for_instrument_at!("info");
+
+        let ctx = self.new_ctx().await.map_err(|err| span.record_err(err))?;
+
+        let workspaces = match &self.workspace_ids {
+            Some(workspace_ids) => {
+                let mut workspaces = Vec::with_capacity(workspace_ids.len());
+                for workspace_id in workspace_ids {
+                    workspaces.push(Workspace::get_by_pk(&ctx, *workspace_id).await?);
+                }
+                workspaces
+            }
+            None => {
+                // Without an explicit list, leave workspaces that already have a recorded
+                // migration alone so failures and rollbacks are not retried automatically.
+                // Workspaces that were only busy are not recorded, so they are retried.
+                let recorded = recorded_workspace_ids(&ctx).await?;
+                Workspace::list_all(&ctx)
+                    .await?
+                    .into_iter()
+                    .filter(|workspace| !recorded.contains(workspace.pk()))
+                    .collect()
+            }
+        };
+        let workspaces: Vec<Workspace> = workspaces
+            .into_iter()
+            .filter(|workspace| !workspace.is_current_version_and_kind())
+            .collect();
+        drop(ctx);
+
+        info!(
+            "Migrating {} workspace(s) to split snapshots in batches of {}",
+            workspaces.len(),
+            self.batch_size
+        );
+
+        let mut migrated = 0;
+        let mut busy = 0;
+        let mut failed = 0;
+        for batch in workspaces.chunks(self.batch_size) {
+            if shutdown_token.is_cancelled() {
+                info!("split snapshot migration shutting down before the next batch");
+                break;
+            }
+
+            let results = join_all(
+                batch
+                    .iter()
+                    .map(|workspace| self.migrate_workspace(workspace.clone())),
+            )
+            .await;
+
+            for (workspace, result) in batch.iter().zip(results) {
+                match result {
+                    Ok(()) => migrated += 1,
+                    // Not recorded as a failure, so that a later run picks the workspace up again
+                    Err(err) if err.is_workspace_busy() => {
+                        info!(
+                            si.error.message = ?err,
+                            si.workspace.id = %workspace.pk(),
+                            "workspace was written to while migrating it to split snapshots, leaving it for a later run"
+                        );
+                        busy += 1;
+                    }
+                    Err(err) => {
+                        error!(
+                            si.error.message = ?err,
+                            si.workspace.id = %workspace.pk(),
+                            "failed to migrate workspace to split snapshots"
+                        );
+                        if let Err(record_err) = self.record_failure(workspace, &err).await {
+                            error!(
+                                si.error.message = ?record_err,
+                                si.workspace.id = %workspace.pk(),
+                                "failed to record split snapshot migration failure"
+                            );
+                        }
+                        failed += 1;
+                    }
+                }
+            }
+
+            info!(
+                "Split snapshot migration checkpoint: {migrated} migrated, {busy} busy, {failed} failed"
+            );
+        }
+
+        info!(
+            "Split snapshot migration finished: {migrated} migrated, {busy} busy, {failed} failed"
+        );
+
+        span.record_ok();
+        Ok(())
+    }
+
+    #[instrument(
+        name = "sdf.split_snapshot_migrator.rollback",
+        level = "info",
+        skip_all
+    )]
+    pub async fn rollback(self) -> Result<()> {
+        let span = current_span_for_instrument_at!("info");
+
+        let workspace_ids = self
+            .workspace_ids
+            .as_deref()
+            .ok_or(SplitSnapshotMigrationError::MissingWorkspaceIds)
+            .map_err(|err| span.record_err(err))?;
+
+        for workspace_id in workspace_ids {
+            self.rollback_workspace(*workspace_id)
+                .await
+                .map_err(|err| span.record_err(err))?;
+            info!(si.workspace.id = %workspace_id, "rolled back split snapshot migration");
+        }
+
+        span.record_ok();
+        Ok(())
+    }
+
+    async fn new_ctx(&self) -> Result<DalContext> {
+        Ok(self
+            .services_context
+            .clone()
+            .into_builder(true)
+            .build_default(None)
+            .await?)
+    }
+
+    /// Converts, verifies and switches over every change set of the workspace.
+    #[instrument(
+        name = "sdf.split_snapshot_migrator.migrate_workspace",
+        level = "info",
+        skip_all,
+        fields(si.workspace.id = %workspace.pk())
+    )]
+    pub async fn migrate_workspace(&self, workspace: Workspace) -> Result<()> {
+        let prepared = self.prepare_workspace(workspace).await?;
+        self.switch_over(prepared).await
+    }
+
+    /// Converts and verifies every change set of the workspace without touching any pointers, so
+    /// a workspace is either fully migrated or left exactly as it was.
+    pub async fn prepare_workspace(
+        &self,
+        workspace: Workspace,
+    ) -> Result<PreparedWorkspaceMigration> {
+        let workspace_pk = *workspace.pk();
+        let ctx = self.new_ctx().await?;
+
+        let mut migrated_change_sets = Vec::new();
+        let mut migration_map = HashMap::new();
+        for change_set in ChangeSet::list_active_for_workspace(&ctx, workspace_pk).await? {
+            if change_set.workspace_id.is_none() || change_set.status == ChangeSetStatus::Failed {
+                continue;
+            }
+
+            let mut legacy_ctx = ctx.clone_with_new_visibility(Visibility::from(change_set.id));
+            legacy_ctx.update_tenancy(Tenancy::new(workspace_pk));
+            legacy_ctx.set_change_set(change_set.clone())?;
+
+            let previous_snapshot_address = change_set.workspace_snapshot_address;
+            let migrated_snapshot_address = match migration_map.get(&previous_snapshot_address) {
+                Some(address) => *address,
+                None => {
+                    let address = SnapshotGraphMigrator::new()
+                        .migrate_snapshot(&legacy_ctx, previous_snapshot_address)
+                        .await?;
+                    migration_map.insert(previous_snapshot_address, address);
+                    address
+                }
+            };
+
+            let mut split_ctx = legacy_ctx.clone();
+            legacy_ctx.set_workspace_snapshot(
+                WorkspaceSnapshot::find(&legacy_ctx, previous_snapshot_address).await?,
+            );
+            split_ctx.set_workspace_split_snapshot(
+                SplitSnapshot::find(&split_ctx, migrated_snapshot_address).await?,
+            );
+            verify_materialized_views(change_set.id, &legacy_ctx, &split_ctx).await?;
+
+            migrated_change_sets.push(MigratedChangeSet {
+                change_set_id: change_set.id,
+                previous_snapshot_address,
+                migrated_snapshot_address,
+            });
+        }
+
+        Ok(PreparedWorkspaceMigration {
+            workspace,
+            migrated_change_sets,
+        })
+    }
+
+    /// Points the change sets of a prepared workspace at their split snapshots and records the
+    /// migration, all in one transaction.
+    ///
+    /// The workspace stays live while it is prepared, so this bails if any change set was
+    /// written to or created since. The workspace will be picked up again on a later run.
+    pub async fn switch_over(&self, prepared: PreparedWorkspaceMigration) -> Result<()> {
+        let PreparedWorkspaceMigration {
+            mut workspace,
+            migrated_change_sets,
+        } = prepared;
+        let workspace_pk = *workspace.pk();
+        let ctx = self.new_ctx().await?;
+
+        lock_change_set_pointers(&ctx, workspace_pk).await?;
+
+        let migrated_change_set_ids: HashSet<ChangeSetId> = migrated_change_sets
+            .iter()
+            .map(|migrated_change_set| migrated_change_set.change_set_id)
+            .collect();
+        for change_set in ChangeSet::list_active_for_workspace(&ctx, workspace_pk).await? {
+            if !migrated_change_set_ids.contains(&change_set.id) {
+                return Err(
+                    SplitSnapshotMigrationError::ChangeSetCreatedDuringMigration(change_set.id),
+                );
+            }
+        }
+
+        for migrated_change_set in &migrated_change_sets {
+            let mut change_set =
+                ChangeSet::get_by_id_across_workspaces(&ctx, migrated_change_set.change_set_id)
+                    .await?;
+            let swapped = change_set
+                .compare_and_swap_pointer(
+                    &ctx,
+                    migrated_change_set.previous_snapshot_address,
+                    migrated_change_set.migrated_snapshot_address,
+                )
+                .await?;
+            if !swapped {
+                return Err(
+                    SplitSnapshotMigrationError::ChangeSetModifiedDuringMigration(
+                        migrated_change_set.change_set_id,
+                    ),
+                );
+            }
+            // The change set was forked from a legacy snapshot, which its split snapshot can't
+            // be compared against
+            change_set.update_base_snapshot_address(&ctx, None).await?;
+        }
+
+        let previous_snapshot_version = workspace.snapshot_version();
+        let previous_subgraph_version = workspace.subgraph_version();
+        workspace
+            .set_snapshot_versions(
+                &ctx,
+                SnapshotVersion::Split(SuperGraphVersionDiscriminants::V1),
+                Some(SubGraphVersionDiscriminants::V1),
+            )
+            .await?;
+
+        record_migration(
+            &ctx,
+            workspace_pk,
+            MigrationStatus::Migrated,
+            previous_snapshot_version,
+            previous_subgraph_version,
+            None,
+        )
+        .await?;
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "DELETE FROM workspace_split_snapshot_migration_change_sets WHERE workspace_id = $1",
+                &[&workspace_pk],
+            )
+            .await?;
+        for migrated_change_set in &migrated_change_sets {
+            ctx.txns()
+                .await?
+                .pg()
+                .execute(
+                    "INSERT INTO workspace_split_snapshot_migration_change_sets
+                        (workspace_id, change_set_id, previous_snapshot_address, migrated_snapshot_address)
+                     VALUES ($1, $2, $3, $4)",
+                    &[
+                        &workspace_pk,
+                        &migrated_change_set.change_set_id,
+                        &migrated_change_set.previous_snapshot_address,
+                        &migrated_change_set.migrated_snapshot_address,
+                    ],
+                )
+                .await?;
+        }
+
+        ctx.commit_no_rebase().await?;
+
+        info!(
+            "Migrated {} change set(s) to split snapshots",
+            migrated_change_sets.len()
+        );
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        workspace: &Workspace,
+        error: &SplitSnapshotMigrationError,
+    ) -> Result<()> {
+        let ctx = self.new_ctx().await?;
+        record_migration(
+            &ctx,
+            *workspace.pk(),
+            MigrationStatus::Failed,
+            workspace.snapshot_version(),
+            workspace.subgraph_version(),
+            Some(error.to_string()),
+        )
+        .await?;
+        ctx.commit_no_rebase().await?;
+
+        Ok(())
+    }
+
+    /// Points the change sets of a migrated workspace back at their legacy snapshots, refusing
+    /// to if anything has been written to the workspace since it was migrated.
+    #[instrument(
+        name = "sdf.split_snapshot_migrator.rollback_workspace",
+        level = "info",
+        skip_all,
+        fields(si.workspace.id = %workspace_pk)
+    )]
+    pub async fn rollback_workspace(&self, workspace_pk: WorkspacePk) -> Result<()> {
+        let ctx = self.new_ctx().await?;
+
+        lock_change_set_pointers(&ctx, workspace_pk).await?;
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT status, previous_snapshot_version, previous_subgraph_version
+                 FROM workspace_split_snapshot_migrations
+                 WHERE workspace_id = $1",
+                &[&workspace_pk],
+            )
+            .await?
+            .ok_or(SplitSnapshotMigrationError::MigrationNotFound(workspace_pk))?;
+        let status: String = row.try_get("status")?;
+        if status != MigrationStatus::Migrated.to_string() {
+            return Err(SplitSnapshotMigrationError::NotMigrated(
+                workspace_pk,
+                status,
+            ));
+        }
+        let previous_snapshot_version: SnapshotVersion =
+            serde_json::from_value(row.try_get("previous_snapshot_version")?)?;
+        let previous_subgraph_version: Option<serde_json::Value> =
+            row.try_get("previous_subgraph_version")?;
+        let previous_subgraph_version: Option<SubGraphVersionDiscriminants> =
+            previous_subgraph_version
+                .map(serde_json::from_value)
+                .transpose()?;
+
+        let mut previous_addresses = HashMap::new();
+        for row in ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT change_set_id, previous_snapshot_address, migrated_snapshot_address
+                 FROM workspace_split_snapshot_migration_change_sets
+                 WHERE workspace_id = $1",
+                &[&workspace_pk],
+            )
+            .await?
+        {
+            let change_set_id: ChangeSetId = row.try_get("change_set_id")?;
+            let previous_snapshot_address: WorkspaceSnapshotAddress =
+                row.try_get("previous_snapshot_address")?;
+            let migrated_snapshot_address: WorkspaceSnapshotAddress =
+                row.try_get("migrated_snapshot_address")?;
+            previous_addresses.insert(
+                change_set_id,
+                (previous_snapshot_address, migrated_snapshot_address),
+            );
+        }
+
+        // Anything written after the migration only exists in the split snapshots. Refuse to
+        // roll back rather than silently dropping that work.
+        for change_set in ChangeSet::list_active_for_workspace(&ctx, workspace_pk).await? {
+            if change_set.status == ChangeSetStatus::Failed {
+                continue;
+            }
+            match previous_addresses.get(&change_set.id) {
+                Some((_, migrated_snapshot_address))
+                    if *migrated_snapshot_address == change_set.workspace_snapshot_address => {}
+                Some(_) => {
+                    return Err(
+                        SplitSnapshotMigrationError::ChangeSetModifiedSinceMigration(change_set.id),
+                    );
+                }
+                None => {
+                    return Err(SplitSnapshotMigrationError::ChangeSetCreatedAfterMigration(
+                        change_set.id,
+                    ));
+                }
+            }
+        }
+
+        for (change_set_id, (previous_snapshot_address, migrated_snapshot_address)) in
+            previous_addresses
+        {
+            let swapped = ChangeSet::get_by_id_across_workspaces(&ctx, change_set_id)
+                .await?
+                .compare_and_swap_pointer(
+                    &ctx,
+                    migrated_snapshot_address,
+                    previous_snapshot_address,
+                )
+                .await?;
+            if !swapped {
+                return Err(
+                    SplitSnapshotMigrationError::ChangeSetModifiedSinceMigration(change_set_id),
+                );
+            }
+        }
+
+        Workspace::get_by_pk(&ctx, workspace_pk)
+            .await?
+            .set_snapshot_versions(&ctx, previous_snapshot_version, previous_subgraph_version)
+            .await?;
+
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "UPDATE workspace_split_snapshot_migrations
+                 SET status = $2, error = NULL, updated_at = NOW()
+                 WHERE workspace_id = $1",
+                &[&workspace_pk, &MigrationStatus::RolledBack.to_string()],
+            )
+            .await?;
+
+        ctx.commit_no_rebase().await?;
+
+        Ok(())
+    }
+}
+
+/// Locks the pointers of every change set in the workspace until the transaction commits, so
+/// nothing (such as the rebaser) can move them between checking and switching them.
+async fn lock_change_set_pointers(ctx: &DalContext, workspace_pk: WorkspacePk) -> Result<()> {
+    ctx.txns()
+        .await?
+        .pg()
+        .query(
+            "SELECT id FROM change_set_pointers WHERE workspace_id = $1 FOR UPDATE",
+            &[&workspace_pk],
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn recorded_workspace_ids(ctx: &DalContext) -> Result<HashSet<WorkspacePk>> {
+    let mut workspace_ids = HashSet::new();
+    for row in ctx
+        .txns()
+        .await?
+        .pg()
+        .query(
+            "SELECT workspace_id FROM workspace_split_snapshot_migrations",
+            &[],
+        )
+        .await?
+    {
+        workspace_ids.insert(row.try_get("workspace_id")?);
+    }
+
+    Ok(workspace_ids)
+}
+
+async fn record_migration(
+    ctx: &DalContext,
+    workspace_pk: WorkspacePk,
+    status: MigrationStatus,
+    previous_snapshot_version: SnapshotVersion,
+    previous_subgraph_version: Option<SubGraphVersionDiscriminants>,
+    error: Option<String>,
+) -> Result<()> {
+    let previous_snapshot_version = serde_json::to_value(previous_snapshot_version)?;
+    let previous_subgraph_version = previous_subgraph_version
+        .map(serde_json::to_value)
+        .transpose()?;
+
+    ctx.txns()
+        .await?
+        .pg()
+        .execute(
+            "INSERT INTO workspace_split_snapshot_migrations
+                (workspace_id, status, previous_snapshot_version, previous_subgraph_version, error)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (workspace_id) DO UPDATE SET
+                status = EXCLUDED.status,
+                previous_snapshot_version = EXCLUDED.previous_snapshot_version,
+                previous_subgraph_version = EXCLUDED.previous_subgraph_version,
+                error = EXCLUDED.error,
+                updated_at = NOW()",
+            &[
+                &workspace_pk,
+                &status.to_string(),
+                &previous_snapshot_version,
+                &previous_subgraph_version,
+                &error,
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Builds the component list, attribute tree and incoming connections views from both snapshots
+/// and checks that they match.
+pub async fn verify_materialized_views(
+    change_set_id: ChangeSetId,
+    legacy_ctx: &DalContext,
+    split_ctx: &DalContext,
+) -> Result<()> {
+    fn ensure_equal(
+        change_set_id: ChangeSetId,
+        view: &str,
+        legacy: impl Serialize,
+        split: impl Serialize,
+    ) -> Result<()> {
+        if serde_json::to_value(legacy)? != serde_json::to_value(split)? {
+            return Err(SplitSnapshotMigrationError::VerificationFailed {
+                change_set_id,
+                view: view.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    ensure_equal(
+        change_set_id,
+        "component list",
+        dal_materialized_views::component_list::assemble(legacy_ctx.clone()).await?,
+        dal_materialized_views::component_list::assemble(split_ctx.clone()).await?,
+    )?;
+
+    for component_id in Component::list_ids(legacy_ctx).await? {
+        ensure_equal(
+            change_set_id,
+            "attribute tree",
+            dal_materialized_views::component::attribute_tree::assemble(
+                legacy_ctx.clone(),
+                component_id,
+            )
+            .await?,
+            dal_materialized_views::component::attribute_tree::assemble(
+                split_ctx.clone(),
+                component_id,
+            )
+            .await?,
+        )?;
+        ensure_equal(
+            change_set_id,
+            "incoming connections",
+            dal_materialized_views::incoming_connections::assemble(
+                legacy_ctx.clone(),
+                component_id,
+            )
+            .await?,
+            dal_materialized_views::incoming_connections::assemble(split_ctx.clone(), component_id)
+                .await?,
+        )?;
+    }
+
+    Ok(())
+}
@@ -1216,7 +1216,8 @@ async fn list_approval_requirement_definitions_for_entity(
             approvers: [ApprovalRequirementApprover::User(user_id)]
                 .iter()
                 .cloned()
-                .collect()
+                .collect(),
+            component_label: None,
         }],
         explicit_definitions,
     );
@@ -1,2 +1,3 @@
 mod change_set_apply;
 mod change_set_approval;
+mod split_snapshot_migration;
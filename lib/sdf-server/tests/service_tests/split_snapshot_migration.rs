@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+
+use dal::{
+    ChangeSet,
+    Component,
+    ComponentId,
+    DalContext,
+    WorkspaceSnapshot,
+    diagram::view::View,
+    workspace_snapshot::{
+        migrator::SnapshotGraphMigrator,
+        split_snapshot::SplitSnapshot,
+    },
+};
+use dal_test::{
+    Result,
+    helpers::create_component_for_default_schema_name,
+    prelude::ChangeSetTestHelpers,
+    sdf_test,
+};
+use pretty_assertions_sorted::assert_eq;
+use sdf_server::{
+    SplitSnapshotMigrationError,
+    SplitSnapshotMigrator,
+    verify_materialized_views,
+};
+
+fn migrator(ctx: &DalContext) -> SplitSnapshotMigrator {
+    SplitSnapshotMigrator::from_services_context(ctx.services_context(), 1, None)
+}
+
+async fn component_ids(ctx: &DalContext) -> Result<HashSet<ComponentId>> {
+    Ok(Component::list_ids(ctx).await?.into_iter().collect())
+}
+
+async fn migration_status(ctx: &DalContext) -> Result<String> {
+    let row = ctx
+        .txns()
+        .await?
+        .pg()
+        .query_one(
+            "SELECT status FROM workspace_split_snapshot_migrations WHERE workspace_id = $1",
+            &[&ctx.workspace_pk()?],
+        )
+        .await?;
+    Ok(row.try_get("status")?)
+}
+
+async fn create_component(ctx: &mut DalContext, name: &str) -> Result<ComponentId> {
+    let view_id = View::get_id_for_default(ctx).await?;
+    let component = create_component_for_default_schema_name(ctx, "starfield", name, view_id)
+        .await?
+        .id();
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+    Ok(component)
+}
+
+#[sdf_test]
+async fn migrates_workspace_to_split_snapshots(ctx: &mut DalContext) -> Result<()> {
+    create_component(ctx, "before migration").await?;
+    assert!(ctx.workspace_snapshot()?.as_legacy_snapshot().is_ok());
+    let legacy_component_ids = component_ids(ctx).await?;
+
+    migrator(ctx)
+        .migrate_workspace(ctx.get_workspace().await?)
+        .await?;
+
+    ctx.update_snapshot_to_visibility().await?;
+    assert!(ctx.workspace_snapshot()?.as_split_snapshot().is_ok());
+    assert_eq!(legacy_component_ids, component_ids(ctx).await?);
+    assert_eq!("migrated", migration_status(ctx).await?);
+    // The legacy snapshot it was forked from can't be compared against anymore
+    assert_eq!(
+        None,
+        ChangeSet::get_by_id(ctx, ctx.change_set_id())
+            .await?
+            .base_snapshot_address
+    );
+
+    // The migrated change set can still be written to
+    let component_id = create_component(ctx, "after migration").await?;
+    assert!(component_ids(ctx).await?.contains(&component_id));
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn verification_fails_when_views_differ(ctx: &mut DalContext) -> Result<()> {
+    let before_address = ctx.workspace_snapshot()?.id().await;
+    create_component(ctx, "added").await?;
+    let after_address = ctx.workspace_snapshot()?.id().await;
+    let migrated_address = SnapshotGraphMigrator::new()
+        .migrate_snapshot(ctx, after_address)
+        .await?;
+
+    let mut split_ctx = ctx.clone();
+    split_ctx.set_workspace_split_snapshot(SplitSnapshot::find(ctx, migrated_address).await?);
+
+    let mut legacy_ctx = ctx.clone();
+    legacy_ctx.set_workspace_snapshot(WorkspaceSnapshot::find(ctx, after_address).await?);
+    verify_materialized_views(ctx.change_set_id(), &legacy_ctx, &split_ctx).await?;
+
+    legacy_ctx.set_workspace_snapshot(WorkspaceSnapshot::find(ctx, before_address).await?);
+    let result = verify_materialized_views(ctx.change_set_id(), &legacy_ctx, &split_ctx).await;
+    assert!(matches!(
+        result,
+        Err(SplitSnapshotMigrationError::VerificationFailed { change_set_id, view })
+            if change_set_id == ctx.change_set_id() && view == "component list"
+    ));
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn rolls_back_migrated_workspace(ctx: &mut DalContext) -> Result<()> {
+    create_component(ctx, "before migration").await?;
+    let legacy_address = ctx.workspace_snapshot()?.id().await;
+    let legacy_component_ids = component_ids(ctx).await?;
+
+    let migrator = migrator(ctx);
+    migrator
+        .migrate_workspace(ctx.get_workspace().await?)
+        .await?;
+    migrator.rollback_workspace(ctx.workspace_pk()?).await?;
+
+    ctx.update_snapshot_to_visibility().await?;
+    assert!(ctx.workspace_snapshot()?.as_legacy_snapshot().is_ok());
+    assert_eq!(legacy_address, ctx.workspace_snapshot()?.id().await);
+    assert_eq!(legacy_component_ids, component_ids(ctx).await?);
+    assert_eq!("rolledBack", migration_status(ctx).await?);
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn rollback_refuses_change_sets_modified_since_migration(ctx: &mut DalContext) -> Result<()> {
+    let migrator = migrator(ctx);
+    migrator
+        .migrate_workspace(ctx.get_workspace().await?)
+        .await?;
+    ctx.update_snapshot_to_visibility().await?;
+    create_component(ctx, "after migration").await?;
+
+    let result = migrator.rollback_workspace(ctx.workspace_pk()?).await;
+    assert!(matches!(
+        result,
+        Err(SplitSnapshotMigrationError::ChangeSetModifiedSinceMigration(change_set_id))
+            if change_set_id == ctx.change_set_id()
+    ));
+
+    ctx.update_snapshot_to_visibility().await?;
+    assert!(ctx.workspace_snapshot()?.as_split_snapshot().is_ok());
+    assert_eq!("migrated", migration_status(ctx).await?);
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn switch_over_refuses_change_sets_modified_during_migration(
+    ctx: &mut DalContext,
+) -> Result<()> {
+    let migrator = migrator(ctx);
+    let prepared = migrator
+        .prepare_workspace(ctx.get_workspace().await?)
+        .await?;
+
+    // Written after the change set was converted, so its split snapshot is now stale
+    let component_id = create_component(ctx, "during migration").await?;
+    let address = ctx.workspace_snapshot()?.id().await;
+
+    let result = migrator.switch_over(prepared).await;
+    assert!(matches!(
+        result,
+        Err(SplitSnapshotMigrationError::ChangeSetModifiedDuringMigration(change_set_id))
+            if change_set_id == ctx.change_set_id()
+    ));
+
+    // Nothing was switched over
+    ctx.update_snapshot_to_visibility().await?;
+    assert!(ctx.workspace_snapshot()?.as_legacy_snapshot().is_ok());
+    assert_eq!(address, ctx.workspace_snapshot()?.id().await);
+
+    // A later run picks up the change
+    migrator
+        .migrate_workspace(ctx.get_workspace().await?)
+        .await?;
+    ctx.update_snapshot_to_visibility().await?;
+    assert!(ctx.workspace_snapshot()?.as_split_snapshot().is_ok());
+    assert!(component_ids(ctx).await?.contains(&component_id));
+
+    Ok(())
+}
+
+#[sdf_test]
+async fn switch_over_refuses_change_sets_created_during_migration(
+    ctx: &mut DalContext,
+) -> Result<()> {
+    let migrator = migrator(ctx);
+    let prepared = migrator
+        .prepare_workspace(ctx.get_workspace().await?)
+        .await?;
+
+    let change_set = ChangeSet::fork_head(ctx, "created during migration").await?;
+    ctx.commit_no_rebase().await?;
+
+    let result = migrator.switch_over(prepared).await;
+    assert!(matches!(
+        result,
+        Err(SplitSnapshotMigrationError::ChangeSetCreatedDuringMigration(change_set_id))
+            if change_set_id == change_set.id
+    ));
+
+    ctx.update_snapshot_to_visibility().await?;
+    assert!(ctx.workspace_snapshot()?.as_legacy_snapshot().is_ok());
+
+    Ok(())
+}
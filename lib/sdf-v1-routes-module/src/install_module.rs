@@ -14,6 +14,7 @@ use dal::{
     pkg::{
         ImportOptions,
         import_pkg_from_pkg,
+        verify_module_signature,
     },
 };
 use module_index_client::ModuleIndexClient;
@@ -109,6 +110,7 @@ pub async fn install_module(
         let pkg_data = module_index_client.download_module(id).await?;
 
         let pkg = SiPkg::load_from_bytes(&pkg_data)?;
+        verify_module_signature(&ctx, &pkg, module_details.signature.as_ref()).await?;
 
         let (schema_id, past_module_hashes) = if pkg.schemas()?.len() > 1 {
             (None, None)
@@ -153,6 +153,11 @@ impl IntoResponse for ModuleError {
             | ModuleError::SchemaNotFoundForVariant(_)
             | ModuleError::SchemaVariantNotFound(_)
             | ModuleError::WorkspaceNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            ModuleError::DalPkg(
+                DalPkgError::InvalidModuleSignature(_)
+                | DalPkgError::UnsignedModule(_)
+                | DalPkgError::UntrustedModuleSigner(_),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -18,6 +18,7 @@ use dal::{
     pkg::{
         ImportOptions,
         import_pkg_from_pkg,
+        verify_module_signature,
     },
 };
 use sdf_core::{
@@ -119,6 +120,7 @@ pub async fn upgrade_modules_inner(
         };
 
         let si_pkg = cached_module.si_pkg(ctx).await?;
+        verify_module_signature(ctx, &si_pkg, cached_module.signature.as_ref()).await?;
 
         let metadata = si_pkg.metadata()?;
         let (_, schema_variant_ids, _) = match import_pkg_from_pkg(
@@ -16,10 +16,18 @@
     clippy::module_name_repetitions
 )]
 
+mod package_signing;
 mod sensitive_strings;
 mod symmetric;
 mod veritech;
 
+pub use package_signing::{
+    PackageSignature,
+    PackageSigningError,
+    PackageSigningKey,
+    PackageSigningResult,
+    PackageVerifyingKey,
+};
 pub use sensitive_strings::SensitiveStrings;
 pub use symmetric::{
     SymmetricCryptoError,
@@ -0,0 +1,197 @@
+//! Signing and verification of module packages.
+//!
+//! Publishers sign the hash of a package with an ed25519 signing key. Anyone holding the
+//! corresponding verifying key can then check that the package was published by the key holder
+//! and has not been altered since.
+
+use std::fmt;
+
+use base64::{
+    Engine,
+    engine::general_purpose,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_hash::Hash;
+use sodiumoxide::crypto::sign;
+use thiserror::Error;
+
+/// An error that can be returned when working with package signing keys and signatures.
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum PackageSigningError {
+    /// When a base64 encoded key or signature fails to be decoded.
+    #[error("failed to decode base64 encoded value")]
+    Base64Decode(#[source] base64::DecodeError),
+    /// When decoded bytes are not a valid signature.
+    #[error("invalid package signature")]
+    InvalidSignature,
+    /// When decoded bytes are not a valid signing key.
+    #[error("invalid package signing key")]
+    InvalidSigningKey,
+    /// When decoded bytes are not a valid verifying key.
+    #[error("invalid package verifying key")]
+    InvalidVerifyingKey,
+}
+
+/// A result type when working with package signing keys and signatures.
+pub type PackageSigningResult<T> = Result<T, PackageSigningError>;
+
+/// A secret key used by a publisher to sign packages.
+#[derive(Clone)]
+pub struct PackageSigningKey(sign::SecretKey);
+
+impl PackageSigningKey {
+    /// Generates a new, random signing key.
+    pub fn generate() -> Self {
+        let (_public_key, secret_key) = sign::gen_keypair();
+        Self(secret_key)
+    }
+
+    /// Loads a signing key from a base64 encoded string.
+    pub fn from_base64(value: impl AsRef<str>) -> PackageSigningResult<Self> {
+        let bytes = decode(value)?;
+        sign::SecretKey::from_slice(&bytes)
+            .map(Self)
+            .ok_or(PackageSigningError::InvalidSigningKey)
+    }
+
+    /// Returns the signing key as a base64 encoded string.
+    pub fn to_base64(&self) -> String {
+        general_purpose::STANDARD_NO_PAD.encode(self.0.as_ref())
+    }
+
+    /// Returns the verifying key which checks signatures made by this key.
+    pub fn verifying_key(&self) -> PackageVerifyingKey {
+        PackageVerifyingKey(self.0.public_key())
+    }
+
+    /// Signs a message, typically the string form of a package hash.
+    pub fn sign(&self, message: impl AsRef<[u8]>) -> PackageSignature {
+        PackageSignature(sign::sign_detached(message.as_ref(), &self.0))
+    }
+}
+
+impl fmt::Debug for PackageSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PackageSigningKey")
+            .field("key_id", &self.verifying_key().key_id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A public key used to verify the signatures of packages.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackageVerifyingKey(sign::PublicKey);
+
+impl PackageVerifyingKey {
+    /// Loads a verifying key from a base64 encoded string.
+    pub fn from_base64(value: impl AsRef<str>) -> PackageSigningResult<Self> {
+        let bytes = decode(value)?;
+        sign::PublicKey::from_slice(&bytes)
+            .map(Self)
+            .ok_or(PackageSigningError::InvalidVerifyingKey)
+    }
+
+    /// Returns the verifying key as a base64 encoded string.
+    pub fn to_base64(&self) -> String {
+        general_purpose::STANDARD_NO_PAD.encode(self.0.as_ref())
+    }
+
+    /// Returns a stable identifier for this key, derived from a hash of the key bytes.
+    pub fn key_id(&self) -> String {
+        Hash::new(self.0.as_ref()).to_string()
+    }
+
+    /// Returns true if the signature was made over the message by the matching signing key.
+    pub fn verify(&self, message: impl AsRef<[u8]>, signature: &PackageSignature) -> bool {
+        sign::verify_detached(&signature.0, message.as_ref(), &self.0)
+    }
+}
+
+/// A detached signature over a package hash.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackageSignature(sign::Signature);
+
+impl PackageSignature {
+    /// Loads a signature from a base64 encoded string.
+    pub fn from_base64(value: impl AsRef<str>) -> PackageSigningResult<Self> {
+        let bytes = decode(value)?;
+        sign::Signature::try_from(bytes.as_slice())
+            .map(Self)
+            .map_err(|_| PackageSigningError::InvalidSignature)
+    }
+
+    /// Returns the signature as a base64 encoded string.
+    pub fn to_base64(&self) -> String {
+        general_purpose::STANDARD_NO_PAD.encode(self.0.to_bytes())
+    }
+}
+
+impl Serialize for PackageSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_base64())
+    }
+}
+
+impl<'de> Deserialize<'de> for PackageSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::from_base64(value).map_err(serde::de::Error::custom)
+    }
+}
+
+fn decode(value: impl AsRef<str>) -> PackageSigningResult<Vec<u8>> {
+    general_purpose::STANDARD_NO_PAD
+        .decode(value.as_ref().trim().trim_end_matches('='))
+        .map_err(PackageSigningError::Base64Decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify_round_trip() {
+        let signing_key = PackageSigningKey::generate();
+        let verifying_key = signing_key.verifying_key();
+
+        let signature = signing_key.sign("package-hash");
+
+        assert!(verifying_key.verify("package-hash", &signature));
+        assert!(!verifying_key.verify("another-package-hash", &signature));
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        let signing_key = PackageSigningKey::generate();
+        let signature = signing_key.sign("package-hash");
+
+        let signing_key = PackageSigningKey::from_base64(signing_key.to_base64())
+            .expect("failed to load signing key");
+        let verifying_key =
+            PackageVerifyingKey::from_base64(signing_key.verifying_key().to_base64())
+                .expect("failed to load verifying key");
+        let signature =
+            PackageSignature::from_base64(signature.to_base64()).expect("failed to load signature");
+
+        assert_eq!(signing_key.verifying_key().key_id(), verifying_key.key_id());
+        assert!(verifying_key.verify("package-hash", &signature));
+    }
+
+    #[test]
+    fn rejects_signature_from_another_key() {
+        let signature = PackageSigningKey::generate().sign("package-hash");
+        let verifying_key = PackageSigningKey::generate().verifying_key();
+
+        assert!(!verifying_key.verify("package-hash", &signature));
+    }
+}
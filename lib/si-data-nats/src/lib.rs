@@ -1447,6 +1447,17 @@ impl NatsTxn {
         }
     }
 
+    /// Returns the number of messages waiting to be published on commit.
+    pub async fn pending_publish_count(&self) -> usize {
+        self.pending_publish.lock().await.len()
+    }
+
+    /// Drops the messages queued to be published on commit after the first `count`, to undo the
+    /// work that queued them without rolling back the transaction.
+    pub async fn truncate_pending_publish(&self, count: usize) {
+        self.pending_publish.lock().await.truncate(count);
+    }
+
     #[instrument(
         name = "nats_txn.publish",
         skip_all,
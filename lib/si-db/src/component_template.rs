@@ -0,0 +1,219 @@
+//! This module provides storage for [component templates](ComponentTemplate), which are saved as
+//! packages so that they can be instantiated into change sets.
+
+#![warn(
+    bad_style,
+    clippy::missing_panics_doc,
+    clippy::panic,
+    clippy::panic_in_result_fn,
+    clippy::unwrap_in_result,
+    clippy::unwrap_used,
+    dead_code,
+    improper_ctypes,
+    missing_debug_implementations,
+    missing_docs,
+    no_mangle_generic_items,
+    non_shorthand_field_patterns,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unreachable_pub,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true
+)]
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_data_pg::PgRow;
+use si_id::{
+    ComponentTemplateId,
+    UserPk,
+    WorkspacePk,
+};
+use thiserror::Error;
+
+use crate::{
+    SiDbContext,
+    SiDbTransactions,
+};
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ComponentTemplateError {
+    #[error("pg error: {0}")]
+    Pg(#[from] si_data_pg::PgError),
+    #[error("si db error: {0}")]
+    SiDb(#[from] crate::SiDbError),
+    #[error("si db transactions error: {0}")]
+    SiDbTransactions(#[from] crate::transactions::SiDbTransactionsError),
+    #[error("version {1} of template {0} already exists")]
+    VersionConflict(String, i32),
+}
+
+type Result<T> = std::result::Result<T, ComponentTemplateError>;
+
+/// A version of a component template saved in a workspace.
+///
+/// Saving a template under an existing name adds a new version rather than replacing it, so that
+/// instances can keep track of which version they were created from.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ComponentTemplate {
+    /// The unique identifier of this version of the template.
+    pub id: ComponentTemplateId,
+    /// The workspace the template belongs to.
+    pub workspace_id: WorkspacePk,
+    /// The name of the template, unique within the workspace.
+    pub name: String,
+    /// The version of the template, starting at 1.
+    pub version: i32,
+    /// A description of what the template creates.
+    pub description: Option<String>,
+    /// The template, as the bytes of a component template package.
+    #[serde(skip_serializing)]
+    pub pkg_data: Vec<u8>,
+    /// The user that saved this version of the template.
+    pub user_id: Option<UserPk>,
+    /// When this version of the template was saved.
+    pub created_at: DateTime<Utc>,
+}
+
+// Implemented by hand so that the package bytes don't end up in a log line.
+impl std::fmt::Debug for ComponentTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentTemplate")
+            .field("id", &self.id)
+            .field("workspace_id", &self.workspace_id)
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .field("description", &self.description)
+            .field("user_id", &self.user_id)
+            .field("created_at", &self.created_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TryFrom<PgRow> for ComponentTemplate {
+    type Error = ComponentTemplateError;
+
+    fn try_from(row: PgRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            workspace_id: row.try_get("workspace_id")?,
+            name: row.try_get("name")?,
+            version: row.try_get("version")?,
+            description: row.try_get("description")?,
+            pkg_data: row.try_get("pkg_data")?,
+            user_id: row.try_get("user_id")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl ComponentTemplate {
+    /// Saves a version of the named template for the current workspace. Versions can't be
+    /// overwritten, so saving a version that already exists is an error.
+    pub async fn new(
+        ctx: &impl SiDbContext,
+        name: String,
+        version: i32,
+        description: Option<String>,
+        pkg_data: Vec<u8>,
+    ) -> Result<Self> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "INSERT INTO component_templates (
+                    workspace_id,
+                    name,
+                    version,
+                    description,
+                    pkg_data,
+                    user_id
+                ) VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT DO NOTHING
+                RETURNING *",
+                &[
+                    &ctx.tenancy().workspace_pk()?,
+                    &name,
+                    &version,
+                    &description,
+                    &pkg_data,
+                    &ctx.history_actor().user_pk(),
+                ],
+            )
+            .await?;
+
+        match maybe_row {
+            Some(row) => Self::try_from(row),
+            None => Err(ComponentTemplateError::VersionConflict(name, version)),
+        }
+    }
+
+    /// Fetches the latest version of the named template for the current workspace.
+    pub async fn get_latest(ctx: &impl SiDbContext, name: &str) -> Result<Option<Self>> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM component_templates
+                    WHERE workspace_id = $1 AND name = $2
+                    ORDER BY version DESC
+                    LIMIT 1",
+                &[&ctx.tenancy().workspace_pk()?, &name],
+            )
+            .await?;
+
+        maybe_row.map(Self::try_from).transpose()
+    }
+
+    /// Fetches a specific version of the named template for the current workspace.
+    pub async fn get_version(
+        ctx: &impl SiDbContext,
+        name: &str,
+        version: i32,
+    ) -> Result<Option<Self>> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM component_templates
+                    WHERE workspace_id = $1 AND name = $2 AND version = $3",
+                &[&ctx.tenancy().workspace_pk()?, &name, &version],
+            )
+            .await?;
+
+        maybe_row.map(Self::try_from).transpose()
+    }
+
+    /// Lists the latest version of every template in the current workspace.
+    pub async fn list_latest(ctx: &impl SiDbContext) -> Result<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT DISTINCT ON (name) * FROM component_templates
+                    WHERE workspace_id = $1
+                    ORDER BY name, version DESC",
+                &[&ctx.tenancy().workspace_pk()?],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+}
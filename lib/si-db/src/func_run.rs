@@ -8,6 +8,7 @@ use si_events::{
     FuncId,
     FuncRun,
     FuncRunId,
+    FuncRunResourceUsage,
     WorkspacePk,
 };
 use telemetry::prelude::*;
@@ -508,6 +509,74 @@ impl FuncRunDb {
             .ok_or(SiDbError::MissingFuncRun(key))
     }
 
+    /// Records the resources consumed by the execution of a func run.
+    pub async fn set_resource_usage(
+        ctx: &impl SiDbContext,
+        key: FuncRunId,
+        usage: &FuncRunResourceUsage,
+    ) -> SiDbResult<()> {
+        let to_column = |value: Option<u64>| value.map(|v| i64::try_from(v).unwrap_or(i64::MAX));
+
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                &format!(
+                    "UPDATE {DBNAME} SET
+                        queued_ms = $2,
+                        execution_ms = $3,
+                        cpu_time_ms = $4,
+                        peak_memory_bytes = $5
+                    WHERE key = $1"
+                ),
+                &[
+                    &key.to_string(),
+                    &to_column(usage.queued_ms),
+                    &to_column(usage.execution_ms),
+                    &to_column(usage.cpu_time_ms),
+                    &to_column(usage.peak_memory_bytes),
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the resources consumed by the execution of a func run, if they were recorded.
+    pub async fn read_resource_usage(
+        ctx: &impl SiDbContext,
+        key: FuncRunId,
+    ) -> SiDbResult<Option<FuncRunResourceUsage>> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                &format!(
+                    "SELECT queued_ms, execution_ms, cpu_time_ms, peak_memory_bytes
+                    FROM {DBNAME} WHERE key = $1"
+                ),
+                &[&key.to_string()],
+            )
+            .await?;
+
+        let Some(row) = maybe_row else {
+            return Ok(None);
+        };
+        let from_column = |column: &str| -> SiDbResult<Option<u64>> {
+            let value: Option<i64> = row.try_get(column)?;
+            Ok(value.and_then(|v| u64::try_from(v).ok()))
+        };
+        let usage = FuncRunResourceUsage {
+            queued_ms: from_column("queued_ms")?,
+            execution_ms: from_column("execution_ms")?,
+            cpu_time_ms: from_column("cpu_time_ms")?,
+            peak_memory_bytes: from_column("peak_memory_bytes")?,
+        };
+
+        Ok((usage != FuncRunResourceUsage::default()).then_some(usage))
+    }
+
     pub async fn read_many_for_workspace(
         ctx: &impl SiDbContext,
         workspace_pk: WorkspacePk,
@@ -0,0 +1,204 @@
+//! This module provides storage for [idempotency keys](IdempotencyKey), which let API clients
+//! retry a write without applying it twice.
+
+#![warn(
+    bad_style,
+    clippy::missing_panics_doc,
+    clippy::panic,
+    clippy::panic_in_result_fn,
+    clippy::unwrap_in_result,
+    clippy::unwrap_used,
+    dead_code,
+    improper_ctypes,
+    missing_debug_implementations,
+    missing_docs,
+    no_mangle_generic_items,
+    non_shorthand_field_patterns,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unreachable_pub,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true
+)]
+
+use std::time::Duration;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use si_data_pg::PgRow;
+use si_id::WorkspacePk;
+use thiserror::Error;
+
+use crate::SiDbContext;
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum IdempotencyKeyError {
+    #[error("pg error: {0}")]
+    Pg(#[from] si_data_pg::PgError),
+    #[error("si db error: {0}")]
+    SiDb(#[from] crate::SiDbError),
+    #[error("si db transactions error: {0}")]
+    SiDbTransactions(#[from] crate::transactions::SiDbTransactionsError),
+}
+
+type Result<T> = std::result::Result<T, IdempotencyKeyError>;
+
+/// A request made with an idempotency key, along with the response to replay if the request is
+/// made again with the same key.
+///
+/// Keys are scoped to a workspace and expire once they are older than the window given to
+/// [`IdempotencyKey::reserve`]. Until the request completes, the key is only held for the lease
+/// given to [`IdempotencyKey::reserve`], so that a request that never completes (because the
+/// server handling it went away, for example) doesn't hold on to its key for the whole window.
+#[derive(Clone, Debug)]
+pub struct IdempotencyKey {
+    /// The workspace the request was made in.
+    pub workspace_id: WorkspacePk,
+    /// The key given by the client.
+    pub key: String,
+    /// A hash of the request, so that reusing a key for a different request can be detected.
+    pub request_hash: String,
+    /// The status code of the response, if the request has completed.
+    pub status_code: Option<i32>,
+    /// The body of the response, if the request has completed.
+    pub response_body: Option<Vec<u8>>,
+    /// When the key can be reserved again, if the request hasn't completed by then.
+    pub lease_expires_at: Option<DateTime<Utc>>,
+    /// When the key was first used.
+    pub created_at: DateTime<Utc>,
+    /// When the request completed.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PgRow> for IdempotencyKey {
+    type Error = IdempotencyKeyError;
+
+    fn try_from(row: PgRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Self {
+            workspace_id: row.try_get("workspace_id")?,
+            key: row.try_get("key")?,
+            request_hash: row.try_get("request_hash")?,
+            status_code: row.try_get("status_code")?,
+            response_body: row.try_get("response_body")?,
+            lease_expires_at: row.try_get("lease_expires_at")?,
+            created_at: row.try_get("created_at")?,
+            completed_at: row.try_get("completed_at")?,
+        })
+    }
+}
+
+impl IdempotencyKey {
+    /// Reserves the key for a request in the current workspace for `lease`, first forgetting any
+    /// keys in the workspace that are older than `window`. A key whose request didn't complete
+    /// before its lease expired is reserved again, for the new request.
+    ///
+    /// Returns `None` if the key was reserved, or the existing entry if the key has already been
+    /// used within the window (whether or not that request has completed).
+    pub async fn reserve(
+        ctx: &impl SiDbContext,
+        key: &str,
+        request_hash: &str,
+        window: Duration,
+        lease: Duration,
+    ) -> Result<Option<Self>> {
+        let workspace_id = ctx.tenancy().workspace_pk()?;
+        let window_secs = i64::try_from(window.as_secs()).unwrap_or(i64::MAX);
+        let lease_ms = i64::try_from(lease.as_millis()).unwrap_or(i64::MAX);
+        let txns = ctx.txns().await?;
+
+        txns.pg()
+            .execute(
+                "DELETE FROM idempotency_keys
+                    WHERE workspace_id = $1
+                    AND created_at < now() - ($2::bigint * interval '1 second')",
+                &[&workspace_id, &window_secs],
+            )
+            .await?;
+
+        let reserved = txns
+            .pg()
+            .query_opt(
+                "INSERT INTO idempotency_keys (workspace_id, key, request_hash, lease_expires_at)
+                    VALUES ($1, $2, $3, now() + ($4::bigint * interval '1 millisecond'))
+                    ON CONFLICT (workspace_id, key) DO UPDATE
+                    SET request_hash = EXCLUDED.request_hash,
+                        lease_expires_at = EXCLUDED.lease_expires_at,
+                        created_at = now()
+                    WHERE idempotency_keys.status_code IS NULL
+                    AND idempotency_keys.lease_expires_at <= now()
+                    RETURNING *",
+                &[&workspace_id, &key, &request_hash, &lease_ms],
+            )
+            .await?;
+        if reserved.is_some() {
+            return Ok(None);
+        }
+
+        let row = txns
+            .pg()
+            .query_one(
+                "SELECT * FROM idempotency_keys WHERE workspace_id = $1 AND key = $2",
+                &[&workspace_id, &key],
+            )
+            .await?;
+
+        Self::try_from(row).map(Some)
+    }
+
+    /// Records the response to the request the key was reserved for, so that it can be replayed.
+    pub async fn complete(
+        ctx: &impl SiDbContext,
+        key: &str,
+        status_code: u16,
+        response_body: &[u8],
+    ) -> Result<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "UPDATE idempotency_keys
+                    SET status_code = $3,
+                        response_body = $4,
+                        completed_at = now(),
+                        lease_expires_at = NULL
+                    WHERE workspace_id = $1 AND key = $2",
+                &[
+                    &ctx.tenancy().workspace_pk()?,
+                    &key,
+                    &i32::from(status_code),
+                    &response_body,
+                ],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Forgets a key, so that the request it was reserved for can be retried.
+    pub async fn release(ctx: &impl SiDbContext, key: &str) -> Result<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "DELETE FROM idempotency_keys WHERE workspace_id = $1 AND key = $2",
+                &[&ctx.tenancy().workspace_pk()?, &key],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Whether the request the key was reserved for has completed.
+    pub fn is_complete(&self) -> bool {
+        self.status_code.is_some()
+    }
+}
@@ -4,13 +4,16 @@ use si_id::UserPk;
 
 mod actor_view;
 pub mod change_set;
+mod component_template;
 mod context;
 mod func_run;
 mod func_run_log;
 mod history_event;
+mod idempotency_key;
 pub mod key_pair;
 mod management_func_execution;
 pub mod migrate;
+mod module_trust_store;
 mod policy_report;
 // TODO remove pub once we move users out of dal
 pub mod standard_accessors;
@@ -19,8 +22,13 @@ mod transactions;
 mod user;
 mod visibility;
 pub mod workspace;
+mod workspace_webhook;
 
 pub use actor_view::ActorView;
+pub use component_template::{
+    ComponentTemplate,
+    ComponentTemplateError,
+};
 pub use context::SiDbContext;
 pub use func_run::FuncRunDb;
 pub use func_run_log::FuncRunLogDb;
@@ -29,11 +37,20 @@ pub use history_event::{
     HistoryEvent,
     HistoryEventMetadata,
 };
+pub use idempotency_key::{
+    IdempotencyKey,
+    IdempotencyKeyError,
+};
 pub use management_func_execution::{
     ManagementFuncExecutionError,
     ManagementFuncJobState,
     ManagementState,
 };
+pub use module_trust_store::{
+    ModuleTrustStore,
+    ModuleTrustStoreError,
+    TrustedPublisherKey,
+};
 pub use policy_report::{
     DEFAULT_PAGE_NUMBER,
     DEFAULT_PAGE_SIZE,
@@ -49,6 +66,10 @@ pub use transactions::{
 };
 pub use user::User;
 pub use visibility::Visibility;
+pub use workspace_webhook::{
+    WorkspaceWebhook,
+    WorkspaceWebhookError,
+};
 
 mod embedded {
     use refinery::embed_migrations;
@@ -18,6 +18,7 @@ use si_id::{
     ManagementPrototypeId,
     UserPk,
     WorkspacePk,
+    WorkspaceWebhookId,
 };
 use strum::EnumString;
 
@@ -105,6 +106,8 @@ impl TryFrom<PgRow> for ManagementFuncJobState {
         let created_at: DateTime<Utc> = row.try_get("created_at")?;
         let updated_at: DateTime<Utc> = row.try_get("updated_at")?;
         let message: Option<String> = row.try_get("message")?;
+        let webhook_id: Option<WorkspaceWebhookId> = row.try_get("webhook_id")?;
+        let webhook_payload: Option<serde_json::Value> = row.try_get("webhook_payload")?;
 
         Ok(Self {
             id,
@@ -117,6 +120,8 @@ impl TryFrom<PgRow> for ManagementFuncJobState {
             state,
             timestamp: Timestamp::new(created_at, updated_at),
             message,
+            webhook_id,
+            webhook_payload,
         })
     }
 }
@@ -133,6 +138,8 @@ pub struct ManagementFuncJobState {
     state: ManagementState,
     timestamp: Timestamp,
     message: Option<String>,
+    webhook_id: Option<WorkspaceWebhookId>,
+    webhook_payload: Option<serde_json::Value>,
 }
 
 impl ManagementFuncJobState {
@@ -146,6 +153,8 @@ impl ManagementFuncJobState {
     getter_copy!(state, ManagementState);
     getter_copy!(timestamp, Timestamp);
     getter!(message, Option<String>);
+    getter_copy!(webhook_id, Option<WorkspaceWebhookId>);
+    getter!(webhook_payload, Option<serde_json::Value>);
 
     pub async fn new_pending(
         ctx: &impl SiDbContext,
@@ -165,6 +174,28 @@ impl ManagementFuncJobState {
         Self::try_from(row.ok_or(ManagementFuncExecutionError::CreationFailed)?)
     }
 
+    /// Creates a pending execution for a management function triggered by a delivery to the given
+    /// webhook, recording the delivered payload so that it can be passed to the function.
+    pub async fn new_pending_for_webhook(
+        ctx: &impl SiDbContext,
+        component_id: ComponentId,
+        prototype_id: ManagementPrototypeId,
+        webhook_id: WorkspaceWebhookId,
+        webhook_payload: serde_json::Value,
+    ) -> ManagementFuncExecutionResult<Self> {
+        let state = ManagementState::Pending;
+        let user_pk = ctx.history_actor().user_pk();
+        let workspace_id = ctx.tenancy().workspace_pk()?;
+        let change_set_id = ctx.change_set_id();
+
+        let row = ctx.txns().await?.pg().query_opt(
+            r#"INSERT INTO management_func_job_states (workspace_id, change_set_id, component_id, prototype_id, user_id, state, webhook_id, webhook_payload) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT DO NOTHING RETURNING *"#,
+            &[&workspace_id, &change_set_id, &component_id, &prototype_id, &user_pk, &state.to_string(), &webhook_id, &webhook_payload]
+        ).await?;
+
+        Self::try_from(row.ok_or(ManagementFuncExecutionError::CreationFailed)?)
+    }
+
     pub async fn get_pending(
         ctx: &impl SiDbContext,
         component_id: ComponentId,
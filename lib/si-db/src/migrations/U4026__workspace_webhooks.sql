@@ -0,0 +1,21 @@
+CREATE TABLE workspace_webhooks
+(
+    id                      ident primary key default ident_create_v1(),
+    workspace_id            ident not null,
+    change_set_id           ident not null,
+    component_id            ident not null,
+    prototype_id            ident not null,
+    user_id                 ident,
+    name                    text not null,
+    signing_secret_crypted  bytea not null,
+    signing_secret_nonce    bytea not null,
+    signing_secret_key_hash text not null,
+    created_at              timestamp with time zone not null default now(),
+    updated_at              timestamp with time zone not null default now()
+);
+
+CREATE UNIQUE INDEX unique_idx_workspace_webhooks_name ON workspace_webhooks (workspace_id, name);
+
+ALTER TABLE management_func_job_states
+    ADD COLUMN webhook_id ident,
+    ADD COLUMN webhook_payload jsonb;
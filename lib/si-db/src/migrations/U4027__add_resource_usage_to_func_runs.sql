@@ -0,0 +1,5 @@
+ALTER TABLE func_runs
+    ADD COLUMN IF NOT EXISTS queued_ms         bigint,
+    ADD COLUMN IF NOT EXISTS execution_ms      bigint,
+    ADD COLUMN IF NOT EXISTS cpu_time_ms       bigint,
+    ADD COLUMN IF NOT EXISTS peak_memory_bytes bigint;
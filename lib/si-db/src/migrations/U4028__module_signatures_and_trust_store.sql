@@ -0,0 +1,19 @@
+CREATE TABLE workspace_module_trust_settings
+(
+    workspace_id   ident primary key,
+    strict         boolean not null default false,
+    updated_at     timestamp with time zone not null default now()
+);
+
+CREATE TABLE workspace_trusted_publisher_keys
+(
+    workspace_id   ident not null,
+    key_id         text not null,
+    public_key     text not null,
+    label          text,
+    user_id        ident,
+    created_at     timestamp with time zone not null default now(),
+    PRIMARY KEY (workspace_id, key_id)
+);
+
+ALTER TABLE cached_modules ADD COLUMN signature jsonb;
@@ -0,0 +1,19 @@
+CREATE TABLE workspace_split_snapshot_migrations
+(
+    workspace_id              ident primary key,
+    status                    text not null,
+    previous_snapshot_version jsonb not null,
+    previous_subgraph_version jsonb,
+    error                     text,
+    created_at                timestamp with time zone not null default now(),
+    updated_at                timestamp with time zone not null default now()
+);
+
+CREATE TABLE workspace_split_snapshot_migration_change_sets
+(
+    workspace_id              ident not null,
+    change_set_id             ident not null,
+    previous_snapshot_address text not null,
+    migrated_snapshot_address text not null,
+    PRIMARY KEY (workspace_id, change_set_id)
+);
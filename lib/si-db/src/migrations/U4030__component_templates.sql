@@ -0,0 +1,31 @@
+CREATE TABLE component_templates
+(
+    id             ident primary key default ident_create_v1(),
+    workspace_id   ident not null,
+    name           text not null,
+    version        integer not null,
+    description    text,
+    pkg_data       bytea not null,
+    user_id        ident,
+    created_at     timestamp with time zone not null default now()
+);
+
+CREATE UNIQUE INDEX unique_idx_component_templates_name_version
+    ON component_templates (workspace_id, name, version);
+
+CREATE TABLE component_template_instances
+(
+    workspace_id     ident not null,
+    name             text not null,
+    template_name    text not null,
+    template_version integer not null,
+    parameters       jsonb not null,
+    change_set_id    ident not null,
+    user_id          ident,
+    created_at       timestamp with time zone not null default now(),
+    updated_at       timestamp with time zone not null default now(),
+    PRIMARY KEY (workspace_id, name)
+);
+
+CREATE INDEX idx_component_template_instances_template_name
+    ON component_template_instances (workspace_id, template_name);
@@ -0,0 +1,15 @@
+CREATE TABLE idempotency_keys
+(
+    workspace_id     ident not null,
+    key              text not null,
+    request_hash     text not null,
+    status_code      integer,
+    response_body    bytea,
+    lease_expires_at timestamp with time zone,
+    created_at       timestamp with time zone not null default now(),
+    completed_at     timestamp with time zone,
+    PRIMARY KEY (workspace_id, key)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at
+    ON idempotency_keys (workspace_id, created_at);
@@ -0,0 +1,3 @@
+-- The snapshot a change set was forked from (or last rebased onto), used as the common ancestor
+-- when comparing it with its base change set. Unknown for change sets created before this column.
+ALTER TABLE change_set_pointers ADD COLUMN base_snapshot_address text;
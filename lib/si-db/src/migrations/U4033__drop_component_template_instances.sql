@@ -0,0 +1,3 @@
+-- Template instances are now recorded on their components in the snapshot, so that they follow
+-- the change set they were created in.
+DROP TABLE component_template_instances;
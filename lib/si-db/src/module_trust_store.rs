@@ -0,0 +1,208 @@
+//! This module provides the [trust store](ModuleTrustStore) of a workspace, which decides which
+//! publishers' signed modules may be installed into it.
+
+#![warn(
+    bad_style,
+    clippy::missing_panics_doc,
+    clippy::panic,
+    clippy::panic_in_result_fn,
+    clippy::unwrap_in_result,
+    clippy::unwrap_used,
+    dead_code,
+    improper_ctypes,
+    missing_debug_implementations,
+    missing_docs,
+    no_mangle_generic_items,
+    non_shorthand_field_patterns,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unreachable_pub,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true
+)]
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_data_pg::PgRow;
+use si_id::UserPk;
+use thiserror::Error;
+
+use crate::{
+    SiDbContext,
+    SiDbTransactions,
+};
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ModuleTrustStoreError {
+    #[error("pg error: {0}")]
+    Pg(#[from] si_data_pg::PgError),
+    #[error("si db error: {0}")]
+    SiDb(#[from] crate::SiDbError),
+    #[error("si db transactions error: {0}")]
+    SiDbTransactions(#[from] crate::transactions::SiDbTransactionsError),
+}
+
+type Result<T> = std::result::Result<T, ModuleTrustStoreError>;
+
+/// The publisher keys a workspace trusts to sign the modules installed into it.
+///
+/// When `strict` is set, modules that are unsigned or signed by a key outside of the trust store
+/// are refused. Otherwise they are installed with a warning.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleTrustStore {
+    /// Whether unsigned and untrusted modules are refused.
+    pub strict: bool,
+    /// The publisher keys trusted by the workspace.
+    pub trusted_keys: Vec<TrustedPublisherKey>,
+}
+
+/// A publisher key trusted by a workspace.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedPublisherKey {
+    /// The identifier of the key, as assigned by the module index.
+    pub key_id: String,
+    /// The base64 encoded ed25519 public key.
+    pub public_key: String,
+    /// A human readable label for the key, such as the name of the publisher.
+    pub label: Option<String>,
+    /// The user that added the key.
+    pub user_id: Option<UserPk>,
+    /// When the key was added.
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for TrustedPublisherKey {
+    type Error = ModuleTrustStoreError;
+
+    fn try_from(row: PgRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Self {
+            key_id: row.try_get("key_id")?,
+            public_key: row.try_get("public_key")?,
+            label: row.try_get("label")?,
+            user_id: row.try_get("user_id")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl ModuleTrustStore {
+    /// Fetches the trust store of the current workspace. Workspaces that never configured one get
+    /// an empty, non-strict trust store.
+    pub async fn get(ctx: &impl SiDbContext) -> Result<Self> {
+        let workspace_id = ctx.tenancy().workspace_pk()?;
+        let txns = ctx.txns().await?;
+
+        let strict = txns
+            .pg()
+            .query_opt(
+                "SELECT strict FROM workspace_module_trust_settings WHERE workspace_id = $1",
+                &[&workspace_id],
+            )
+            .await?
+            .map(|row| row.try_get::<_, bool>("strict"))
+            .transpose()?
+            .unwrap_or_default();
+
+        let rows = txns
+            .pg()
+            .query(
+                "SELECT * FROM workspace_trusted_publisher_keys WHERE workspace_id = $1 ORDER BY created_at",
+                &[&workspace_id],
+            )
+            .await?;
+        let trusted_keys = rows
+            .into_iter()
+            .map(TrustedPublisherKey::try_from)
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            strict,
+            trusted_keys,
+        })
+    }
+
+    /// Sets whether the current workspace refuses unsigned and untrusted modules.
+    pub async fn set_strict(ctx: &impl SiDbContext, strict: bool) -> Result<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "INSERT INTO workspace_module_trust_settings (workspace_id, strict) VALUES ($1, $2)
+                ON CONFLICT (workspace_id) DO UPDATE SET strict = EXCLUDED.strict, updated_at = now()",
+                &[&ctx.tenancy().workspace_pk()?, &strict],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Trusts a publisher key in the current workspace, replacing the label of a key that is
+    /// already trusted.
+    pub async fn add_key(
+        ctx: &impl SiDbContext,
+        key_id: String,
+        public_key: String,
+        label: Option<String>,
+    ) -> Result<TrustedPublisherKey> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "INSERT INTO workspace_trusted_publisher_keys (
+                    workspace_id,
+                    key_id,
+                    public_key,
+                    label,
+                    user_id
+                ) VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (workspace_id, key_id) DO UPDATE SET label = EXCLUDED.label
+                RETURNING *",
+                &[
+                    &ctx.tenancy().workspace_pk()?,
+                    &key_id,
+                    &public_key,
+                    &label,
+                    &ctx.history_actor().user_pk(),
+                ],
+            )
+            .await?;
+
+        TrustedPublisherKey::try_from(row)
+    }
+
+    /// Stops trusting a publisher key in the current workspace, returning whether it was trusted.
+    pub async fn remove_key(ctx: &impl SiDbContext, key_id: &str) -> Result<bool> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "DELETE FROM workspace_trusted_publisher_keys WHERE workspace_id = $1 AND key_id = $2 RETURNING key_id",
+                &[&ctx.tenancy().workspace_pk()?, &key_id],
+            )
+            .await?;
+
+        Ok(maybe_row.is_some())
+    }
+
+    /// Returns the trusted key with the given identifier, if any.
+    pub fn trusted_key(&self, key_id: &str) -> Option<&TrustedPublisherKey> {
+        self.trusted_keys.iter().find(|key| key.key_id == key_id)
+    }
+}
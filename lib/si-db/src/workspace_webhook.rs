@@ -0,0 +1,414 @@
+//! This module provides the ability to register and look up [workspace webhooks](WorkspaceWebhook),
+//! which let external systems trigger a management function by sending a signed payload.
+
+#![warn(
+    bad_style,
+    clippy::missing_panics_doc,
+    clippy::panic,
+    clippy::panic_in_result_fn,
+    clippy::unwrap_in_result,
+    clippy::unwrap_used,
+    dead_code,
+    improper_ctypes,
+    missing_debug_implementations,
+    missing_docs,
+    no_mangle_generic_items,
+    non_shorthand_field_patterns,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unreachable_pub,
+    unused,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true
+)]
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use ring::{
+    hmac,
+    rand::{
+        SecureRandom,
+        SystemRandom,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use si_crypto::{
+    SymmetricCryptoError,
+    SymmetricCryptoService,
+    SymmetricNonce,
+};
+use si_data_pg::PgRow;
+use si_hash::{
+    Hash,
+    HashParseError,
+};
+use si_id::{
+    ChangeSetId,
+    ComponentId,
+    ManagementPrototypeId,
+    UserPk,
+    WorkspacePk,
+    WorkspaceWebhookId,
+};
+use thiserror::Error;
+
+use crate::{
+    SiDbContext,
+    SiDbTransactions,
+};
+
+/// The number of random bytes used for a newly generated signing secret.
+const SIGNING_SECRET_LEN: usize = 32;
+
+/// The prefix that GitHub-style signature headers put in front of the hex encoded digest.
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+#[allow(missing_docs)]
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum WorkspaceWebhookError {
+    #[error("webhook name already in use: {0}")]
+    NameAlreadyInUse(String),
+    #[error("pg error: {0}")]
+    Pg(#[from] si_data_pg::PgError),
+    #[error("si db error: {0}")]
+    SiDb(#[from] crate::SiDbError),
+    #[error("si db transactions error: {0}")]
+    SiDbTransactions(#[from] crate::transactions::SiDbTransactionsError),
+    #[error("could not decrypt signing secret: {0}")]
+    SigningSecretDecrypt(#[from] SymmetricCryptoError),
+    #[error("could not generate signing secret")]
+    SigningSecretGeneration,
+    #[error("invalid signing secret key hash: {0}")]
+    SigningSecretKeyHash(#[from] HashParseError),
+    #[error("invalid signing secret nonce")]
+    SigningSecretNonce,
+}
+
+type Result<T> = std::result::Result<T, WorkspaceWebhookError>;
+
+/// An inbound webhook endpoint that runs a management prototype on a component in a change set
+/// whenever it receives a correctly signed payload.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorkspaceWebhook {
+    /// The unique identifier of the webhook.
+    pub id: WorkspaceWebhookId,
+    /// The workspace the webhook belongs to.
+    pub workspace_id: WorkspacePk,
+    /// The change set that management functions triggered by the webhook run in.
+    pub change_set_id: ChangeSetId,
+    /// The component that the management prototype runs on.
+    pub component_id: ComponentId,
+    /// The management prototype to run.
+    pub prototype_id: ManagementPrototypeId,
+    /// The user that registered the webhook.
+    pub user_id: Option<UserPk>,
+    /// The unique name of the webhook within the workspace.
+    pub name: String,
+    /// The shared secret used to sign payloads sent to the webhook, as stored: encrypted.
+    #[serde(skip)]
+    signing_secret: EncryptedSigningSecret,
+    /// When the webhook was registered.
+    pub created_at: DateTime<Utc>,
+    /// When the webhook was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A signing secret encrypted with the [`SymmetricCryptoService`], so that it is never stored in
+/// the clear.
+#[derive(Clone, Default)]
+struct EncryptedSigningSecret {
+    crypted: Vec<u8>,
+    nonce: Vec<u8>,
+    key_hash: String,
+}
+
+impl EncryptedSigningSecret {
+    fn encrypt(symmetric_crypto_service: &SymmetricCryptoService, signing_secret: &str) -> Self {
+        let (crypted, nonce, key_hash) =
+            symmetric_crypto_service.encrypt(signing_secret.as_bytes());
+        Self {
+            crypted,
+            nonce: nonce.as_ref().to_vec(),
+            key_hash: key_hash.to_string(),
+        }
+    }
+
+    fn decrypt(&self, symmetric_crypto_service: &SymmetricCryptoService) -> Result<Vec<u8>> {
+        let nonce = SymmetricNonce::from_slice(&self.nonce)
+            .ok_or(WorkspaceWebhookError::SigningSecretNonce)?;
+        let key_hash: Hash = self.key_hash.parse()?;
+        Ok(symmetric_crypto_service.decrypt(&self.crypted, &nonce, &key_hash)?)
+    }
+}
+
+// Implemented by hand so that the signing secret never ends up in a log line.
+impl std::fmt::Debug for WorkspaceWebhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkspaceWebhook")
+            .field("id", &self.id)
+            .field("workspace_id", &self.workspace_id)
+            .field("change_set_id", &self.change_set_id)
+            .field("component_id", &self.component_id)
+            .field("prototype_id", &self.prototype_id)
+            .field("user_id", &self.user_id)
+            .field("name", &self.name)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TryFrom<PgRow> for WorkspaceWebhook {
+    type Error = WorkspaceWebhookError;
+
+    fn try_from(row: PgRow) -> std::result::Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            workspace_id: row.try_get("workspace_id")?,
+            change_set_id: row.try_get("change_set_id")?,
+            component_id: row.try_get("component_id")?,
+            prototype_id: row.try_get("prototype_id")?,
+            user_id: row.try_get("user_id")?,
+            name: row.try_get("name")?,
+            signing_secret: EncryptedSigningSecret {
+                crypted: row.try_get("signing_secret_crypted")?,
+                nonce: row.try_get("signing_secret_nonce")?,
+                key_hash: row.try_get("signing_secret_key_hash")?,
+            },
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+impl WorkspaceWebhook {
+    /// Registers a new webhook for the current workspace and change set with a freshly generated
+    /// signing secret, which is returned alongside the webhook. This is the only time the secret
+    /// is available, as it is stored encrypted.
+    pub async fn new(
+        ctx: &impl SiDbContext,
+        symmetric_crypto_service: &SymmetricCryptoService,
+        name: String,
+        component_id: ComponentId,
+        prototype_id: ManagementPrototypeId,
+    ) -> Result<(Self, String)> {
+        let signing_secret = Self::generate_signing_secret()?;
+        let encrypted_signing_secret =
+            EncryptedSigningSecret::encrypt(symmetric_crypto_service, &signing_secret);
+
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "INSERT INTO workspace_webhooks (
+                    workspace_id,
+                    change_set_id,
+                    component_id,
+                    prototype_id,
+                    user_id,
+                    name,
+                    signing_secret_crypted,
+                    signing_secret_nonce,
+                    signing_secret_key_hash
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT DO NOTHING
+                RETURNING *",
+                &[
+                    &ctx.tenancy().workspace_pk()?,
+                    &ctx.change_set_id(),
+                    &component_id,
+                    &prototype_id,
+                    &ctx.history_actor().user_pk(),
+                    &name,
+                    &encrypted_signing_secret.crypted,
+                    &encrypted_signing_secret.nonce,
+                    &encrypted_signing_secret.key_hash,
+                ],
+            )
+            .await?;
+
+        match maybe_row {
+            Some(row) => Ok((Self::try_from(row)?, signing_secret)),
+            None => Err(WorkspaceWebhookError::NameAlreadyInUse(name)),
+        }
+    }
+
+    /// Lists the webhooks registered for the current workspace and change set.
+    pub async fn list(ctx: &impl SiDbContext) -> Result<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * FROM workspace_webhooks WHERE workspace_id = $1 AND change_set_id = $2 ORDER BY name",
+                &[&ctx.tenancy().workspace_pk()?, &ctx.change_set_id()],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::try_from).collect()
+    }
+
+    /// Fetches a webhook by ID for the given workspace.
+    ///
+    /// The workspace is passed explicitly rather than taken from the context since webhook
+    /// deliveries are not authenticated as a workspace member, so we only know the workspace from
+    /// the URL that was called.
+    pub async fn get_for_workspace(
+        ctx: &impl SiDbContext,
+        workspace_id: WorkspacePk,
+        id: WorkspaceWebhookId,
+    ) -> Result<Option<Self>> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "SELECT * FROM workspace_webhooks WHERE workspace_id = $1 AND id = $2",
+                &[&workspace_id, &id],
+            )
+            .await?;
+
+        maybe_row.map(Self::try_from).transpose()
+    }
+
+    /// Deletes a webhook registered for the current workspace and change set, returning whether it
+    /// existed.
+    pub async fn delete(ctx: &impl SiDbContext, id: WorkspaceWebhookId) -> Result<bool> {
+        let maybe_row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                "DELETE FROM workspace_webhooks WHERE workspace_id = $1 AND change_set_id = $2 AND id = $3 RETURNING id",
+                &[&ctx.tenancy().workspace_pk()?, &ctx.change_set_id(), &id],
+            )
+            .await?;
+
+        Ok(maybe_row.is_some())
+    }
+
+    /// Checks that the signature was produced by signing the body with this webhook's secret
+    /// using HMAC-SHA256.
+    ///
+    /// The signature is the hex encoded digest and may be prefixed with `sha256=`, as is done in
+    /// GitHub's `X-Hub-Signature-256` header.
+    pub fn verify_signature(
+        &self,
+        symmetric_crypto_service: &SymmetricCryptoService,
+        body: &[u8],
+        signature: &str,
+    ) -> Result<bool> {
+        let signature = signature.trim();
+        let signature = signature
+            .strip_prefix(SIGNATURE_PREFIX)
+            .unwrap_or(signature);
+        let Ok(tag) = hex::decode(signature) else {
+            return Ok(false);
+        };
+
+        let signing_secret = self.signing_secret.decrypt(symmetric_crypto_service)?;
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &signing_secret);
+        Ok(hmac::verify(&key, body, &tag).is_ok())
+    }
+
+    fn generate_signing_secret() -> Result<String> {
+        let mut bytes = [0u8; SIGNING_SECRET_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| WorkspaceWebhookError::SigningSecretGeneration)?;
+        Ok(hex::encode(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symmetric_crypto_service() -> SymmetricCryptoService {
+        SymmetricCryptoService::new(SymmetricCryptoService::generate_key(), vec![])
+    }
+
+    fn webhook(
+        symmetric_crypto_service: &SymmetricCryptoService,
+        signing_secret: &str,
+    ) -> WorkspaceWebhook {
+        WorkspaceWebhook {
+            id: WorkspaceWebhookId::new(),
+            workspace_id: WorkspacePk::new(),
+            change_set_id: ChangeSetId::new(),
+            component_id: ComponentId::new(),
+            prototype_id: ManagementPrototypeId::new(),
+            user_id: None,
+            name: "push".to_string(),
+            signing_secret: EncryptedSigningSecret::encrypt(
+                symmetric_crypto_service,
+                signing_secret,
+            ),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn verify_signature() {
+        let symmetric_crypto_service = symmetric_crypto_service();
+        let webhook = webhook(&symmetric_crypto_service, "It's a Secret to Everybody");
+        let body = b"Hello, World!";
+        // Taken from GitHub's documentation on validating webhook deliveries.
+        let signature = "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+        let verify = |body: &[u8], signature: &str| {
+            webhook
+                .verify_signature(&symmetric_crypto_service, body, signature)
+                .expect("should decrypt signing secret")
+        };
+
+        assert!(verify(body, signature));
+        assert!(verify(body, &format!("sha256={signature}")));
+        assert!(!verify(b"Hello, World?", signature));
+        assert!(!verify(body, "sha256=not-hex"));
+        assert!(!verify(body, ""));
+    }
+
+    #[test]
+    fn signing_secret_is_stored_encrypted() {
+        let symmetric_crypto_service = symmetric_crypto_service();
+        let webhook = webhook(&symmetric_crypto_service, "It's a Secret to Everybody");
+
+        assert_ne!(
+            b"It's a Secret to Everybody".as_slice(),
+            webhook.signing_secret.crypted.as_slice()
+        );
+        assert_eq!(
+            b"It's a Secret to Everybody".to_vec(),
+            webhook
+                .signing_secret
+                .decrypt(&symmetric_crypto_service)
+                .expect("should decrypt signing secret")
+        );
+    }
+
+    #[test]
+    fn verify_signature_fails_with_another_key() {
+        let webhook = webhook(&symmetric_crypto_service(), "It's a Secret to Everybody");
+
+        assert!(matches!(
+            webhook.verify_signature(
+                &symmetric_crypto_service(),
+                b"Hello, World!",
+                "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+            ),
+            Err(WorkspaceWebhookError::SigningSecretDecrypt(_))
+        ));
+    }
+}
@@ -7,6 +7,7 @@ use si_id::{
     EntityId,
     ManagementPrototypeId,
     UserPk,
+    VariableId,
 };
 use strum::{
     Display,
@@ -1935,4 +1936,12 @@ pub enum PropValueSource {
         source_path: String,
     },
     None,
+    Variable {
+        value: serde_json::Value,
+        variable_id: VariableId,
+        variable_name: String,
+    },
+    StringTemplate {
+        template: String,
+    },
 }
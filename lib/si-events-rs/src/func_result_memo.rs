@@ -0,0 +1,203 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use thiserror::Error;
+
+use crate::{
+    CasValue,
+    WorkspacePk,
+};
+
+/// Mixed into every [`FuncResultMemoKey`] to record that the memoized execution had no access to
+/// secrets. Executions that receive decrypted secrets are never memoized, so a key can only ever be
+/// produced for a secret-free execution.
+const SECRET_FREE_MARKER: &[u8] = b"secret-free";
+
+/// The result of a function execution that can be reused by later executions of the same function
+/// code with the same arguments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FuncResultMemo {
+    unprocessed_value: Option<CasValue>,
+    value: Option<CasValue>,
+    created_at: DateTime<Utc>,
+}
+
+impl FuncResultMemo {
+    pub fn new(
+        unprocessed_value: Option<serde_json::Value>,
+        value: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            unprocessed_value: unprocessed_value.map(Into::into),
+            value: value.map(Into::into),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Returns true if the memo was created more than `max_age` before `now`, after which it must
+    /// not be reused.
+    pub fn is_expired(&self, now: DateTime<Utc>, max_age: TimeDelta) -> bool {
+        now - self.created_at > max_age
+    }
+
+    pub fn unprocessed_value(&self) -> Option<serde_json::Value> {
+        self.unprocessed_value.clone().map(Into::into)
+    }
+
+    pub fn value(&self) -> Option<serde_json::Value> {
+        self.value.clone().map(Into::into)
+    }
+}
+
+/// Identifies a [`FuncResultMemo`] by the workspace it was produced in, the function that produced
+/// it and the arguments it was called with.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FuncResultMemoKey(blake3::Hash);
+
+impl FuncResultMemoKey {
+    /// Creates a key from the workspace the function runs in, a string identifying the function (its
+    /// code hash, handler and return type) and the arguments it was called with. Results are never
+    /// shared across workspaces.
+    ///
+    /// Arguments are hashed in a canonical form, so objects with the same entries produce the same
+    /// key regardless of the order of their keys.
+    #[must_use]
+    pub fn new(workspace_pk: WorkspacePk, func_identity: &str, args: &serde_json::Value) -> Self {
+        let mut args_hasher = blake3::Hasher::new();
+        hash_canonical_json(&mut args_hasher, args);
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(workspace_pk.to_string().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(func_identity.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(args_hasher.finalize().as_bytes());
+        hasher.update(SECRET_FREE_MARKER);
+
+        Self(hasher.finalize())
+    }
+}
+
+fn hash_canonical_json(hasher: &mut blake3::Hasher, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Null => {
+            hasher.update(b"n");
+        }
+        serde_json::Value::Bool(boolean) => {
+            hasher.update(if *boolean { b"t" } else { b"f" });
+        }
+        serde_json::Value::Number(number) => {
+            let number = number.to_string();
+            hasher.update(b"d");
+            hasher.update(&(number.len() as u64).to_le_bytes());
+            hasher.update(number.as_bytes());
+        }
+        serde_json::Value::String(string) => {
+            hasher.update(b"s");
+            hasher.update(&(string.len() as u64).to_le_bytes());
+            hasher.update(string.as_bytes());
+        }
+        serde_json::Value::Array(values) => {
+            hasher.update(b"a");
+            hasher.update(&(values.len() as u64).to_le_bytes());
+            for value in values {
+                hash_canonical_json(hasher, value);
+            }
+        }
+        serde_json::Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            hasher.update(b"o");
+            hasher.update(&(entries.len() as u64).to_le_bytes());
+            for (key, value) in entries {
+                hasher.update(&(key.len() as u64).to_le_bytes());
+                hasher.update(key.as_bytes());
+                hash_canonical_json(hasher, value);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("failed to parse hash hex string")]
+pub struct FuncResultMemoKeyParseError(#[from] blake3::HexError);
+
+impl FromStr for FuncResultMemoKey {
+    type Err = FuncResultMemoKeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(blake3::Hash::from_str(s)?))
+    }
+}
+
+impl fmt::Display for FuncResultMemoKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn key_ignores_object_key_order() {
+        let workspace_pk = WorkspacePk::new();
+        let a = FuncResultMemoKey::new(
+            workspace_pk,
+            "code",
+            &json!({"a": 1, "b": {"c": [1, "2"], "d": null}}),
+        );
+        let b = FuncResultMemoKey::new(
+            workspace_pk,
+            "code",
+            &json!({"b": {"d": null, "c": [1, "2"]}, "a": 1}),
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn key_depends_on_workspace_func_and_args() {
+        let workspace_pk = WorkspacePk::new();
+        let key = FuncResultMemoKey::new(workspace_pk, "code", &json!({"a": 1}));
+        assert_ne!(
+            key,
+            FuncResultMemoKey::new(WorkspacePk::new(), "code", &json!({"a": 1}))
+        );
+        assert_ne!(
+            key,
+            FuncResultMemoKey::new(workspace_pk, "other code", &json!({"a": 1}))
+        );
+        assert_ne!(
+            key,
+            FuncResultMemoKey::new(workspace_pk, "code", &json!({"a": "1"}))
+        );
+        assert_ne!(
+            key,
+            FuncResultMemoKey::new(workspace_pk, "code", &json!({"a": [1]}))
+        );
+    }
+
+    #[test]
+    fn memo_expires_after_max_age() {
+        let memo = FuncResultMemo::new(None, Some(json!("value")));
+        let max_age = TimeDelta::days(7);
+        assert!(!memo.is_expired(memo.created_at, max_age));
+        assert!(!memo.is_expired(memo.created_at + max_age, max_age));
+        assert!(memo.is_expired(memo.created_at + max_age + TimeDelta::seconds(1), max_age));
+    }
+}
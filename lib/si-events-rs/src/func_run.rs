@@ -123,6 +123,11 @@ pub enum FuncBackendKind {
     NormalizeToArray,
     Float,
     Debug,
+    /// A [`Func`](crate::Func) compiled to a WebAssembly component that resolves an attribute
+    /// value, run by cyclone instead of lang-js.
+    WasmAttribute,
+    /// Renders a string template from a set of bound values, natively in the dal.
+    StringTemplate,
 }
 
 // NOTE(nick,zack): do not add "remain::sorted" for postcard de/ser. We need the order to be
@@ -373,6 +378,23 @@ impl FuncRun {
     }
 }
 
+/// The resources consumed by the execution of a [`FuncRun`], as measured by veritech.
+///
+/// Stored alongside the func run rather than within it, so that func runs serialized before these
+/// measurements existed can still be read.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncRunResourceUsage {
+    /// Time spent waiting for an execution environment, in milliseconds.
+    pub queued_ms: Option<u64>,
+    /// Wall-clock time spent executing, in milliseconds.
+    pub execution_ms: Option<u64>,
+    /// CPU time spent executing, in milliseconds.
+    pub cpu_time_ms: Option<u64>,
+    /// Peak memory used while executing, in bytes.
+    pub peak_memory_bytes: Option<u64>,
+}
+
 #[derive(Debug)]
 pub struct FuncRunValue {
     func_run_id: FuncRunId,
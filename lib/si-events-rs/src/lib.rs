@@ -3,6 +3,7 @@ pub mod authentication_method;
 pub mod change_batch;
 pub mod content_hash;
 pub mod encrypted_secret;
+pub mod func_result_memo;
 pub mod merkle_tree_hash;
 pub mod rebase_batch_address;
 pub mod split_snapshot_rebase_batch_address;
@@ -82,6 +83,7 @@ pub use crate::{
         FuncRunBuilder,
         FuncRunBuilderError,
         FuncRunId,
+        FuncRunResourceUsage,
         FuncRunState,
         FuncRunValue,
         ManagementPrototypeId,
@@ -39,6 +39,7 @@ pub enum EntityKind {
     CategoryOverlay,
     CategorySchema,
     CategorySecret,
+    CategoryVariable,
     CategoryView,
     Component,
     DependentValueRoot,
@@ -69,5 +70,6 @@ pub enum EntityKind {
     SubGraphRoot,
     ValidationOutput,
     ValidationPrototype,
+    Variable,
     View,
 }
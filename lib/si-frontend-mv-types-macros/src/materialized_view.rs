@@ -28,6 +28,9 @@ struct MaterializedViewOptions {
     trigger_entity: Option<Path>,
     reference_kind: Option<Path>,
     build_priority: Option<String>,
+    /// Bumped whenever the way a view is built changes in a way that is not reflected in the
+    /// shape of the type, so that existing views of this kind are rebuilt.
+    schema_version: Option<u32>,
 }
 
 #[derive(Debug, Default, FromField)]
@@ -125,8 +128,18 @@ pub fn derive_materialized_view(
         format_ident!("{}", priority)
     };
 
+    let schema_version = struct_options.schema_version.unwrap_or(1);
+
     let definition_checksum = {
         let mut hash_updates = TokenStream::new();
+        // Views that have never had their version bumped keep the checksum they had before
+        // versions existed, so that introducing versions doesn't rebuild every view.
+        if schema_version > 1 {
+            hash_updates.extend(quote! {
+                hasher.update(b"schema_version");
+                hasher.update(&#schema_version.to_le_bytes());
+            });
+        }
         for update in definition_checksum_updates {
             hash_updates.extend(update);
         }
@@ -160,6 +173,10 @@ pub fn derive_materialized_view(
             fn build_priority() -> ::si_events::materialized_view::BuildPriority {
                 ::si_events::materialized_view::BuildPriority::#build_priority
             }
+
+            fn schema_version() -> u32 {
+                #schema_version
+            }
         }
 
         static #checksum_static_ident: ::std::sync::LazyLock<::si_events::workspace_snapshot::Checksum> =
@@ -172,6 +189,7 @@ pub fn derive_materialized_view(
                 #self_reference_kind,
                 #trigger_entity,
                 ::si_events::materialized_view::BuildPriority::#build_priority,
+                #schema_version,
                 &#checksum_static_ident,
             )
         };
@@ -30,6 +30,7 @@ use si_id::{
     SchemaId,
     SchemaVariantId,
     SecretId,
+    VariableId,
     ViewId,
     WorkspaceId,
     WorkspacePk,
@@ -159,6 +160,12 @@ impl FrontendChecksum for SecretId {
     }
 }
 
+impl FrontendChecksum for VariableId {
+    fn checksum(&self) -> Checksum {
+        FrontendChecksum::checksum(&self.to_string())
+    }
+}
+
 impl FrontendChecksum for OutputSocketId {
     fn checksum(&self) -> Checksum {
         FrontendChecksum::checksum(&self.to_string())
@@ -719,6 +726,17 @@ impl DefinitionChecksum for SecretId {
     }
 }
 
+impl DefinitionChecksum for VariableId {
+    fn definition_checksum() -> Checksum {
+        static CHECKSUM: ::std::sync::LazyLock<Checksum> = ::std::sync::LazyLock::new(|| {
+            let mut hasher = ChecksumHasher::new();
+            hasher.update(b"VariableId");
+            hasher.finalize()
+        });
+        *CHECKSUM
+    }
+}
+
 impl DefinitionChecksum for PropId {
     fn definition_checksum() -> Checksum {
         static CHECKSUM: ::std::sync::LazyLock<Checksum> = ::std::sync::LazyLock::new(|| {
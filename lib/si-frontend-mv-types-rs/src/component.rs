@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{
     Deserialize,
     Serialize,
@@ -96,6 +98,8 @@ pub struct Component {
     pub resource_diff: ComponentTextDiff,
     pub is_secret_defining: bool,
     pub to_delete: bool,
+    pub labels: HashMap<String, String>,
+    pub owner: Option<String>,
 }
 
 #[remain::sorted]
@@ -155,6 +159,8 @@ pub struct ComponentInList {
     pub to_delete: bool,
     // TODO (jkeiser) remove (this is always false)
     pub has_socket_connections: bool,
+    pub labels: HashMap<String, String>,
+    pub owner: Option<String>,
 }
 
 #[derive(
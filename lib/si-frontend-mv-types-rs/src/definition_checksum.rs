@@ -1,7 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 
 use si_events::workspace_snapshot::Checksum;
 
+use crate::materialized_view::MaterializedViewInventoryItem;
+
 /// Trait for computing definition checksums of types based on their schema/shape.
 /// Unlike FrontendChecksum which handles data values, DefinitionChecksum focuses
 /// on the structure and type information of the data.
@@ -81,13 +86,39 @@ impl DefinitionChecksumInventoryItem {
 
 static DEFINITION_CHECKSUMS: ::std::sync::LazyLock<HashMap<String, Checksum>> =
     ::std::sync::LazyLock::new(|| {
-        ::inventory::iter::<DefinitionChecksumInventoryItem>()
-            .map(|inv_item| (inv_item.ident.to_string(), **inv_item.definition_checksum))
-            .collect()
+        let mut definition_checksums: HashMap<String, Checksum> =
+            ::inventory::iter::<DefinitionChecksumInventoryItem>()
+                .map(|inv_item| (inv_item.ident.to_string(), **inv_item.definition_checksum))
+                .collect();
+        // Once a materialized view kind has had its schema version bumped, its checksum covers
+        // the version as well as the type's shape, so it takes precedence. Kinds still on the
+        // first version keep the checksum of their shape alone, as they had before versions.
+        for mv_item in ::inventory::iter::<MaterializedViewInventoryItem>() {
+            if mv_item.schema_version() > 1 {
+                definition_checksums
+                    .insert(mv_item.kind().to_string(), mv_item.definition_checksum());
+            }
+        }
+
+        definition_checksums
     });
 
 pub fn materialized_view_definition_checksums() -> &'static HashMap<String, Checksum> {
     &DEFINITION_CHECKSUMS
 }
 
+/// Returns the kinds whose current definition checksum differs from the one an index was built
+/// with, including kinds that did not exist when the index was built.
+pub fn outdated_definition_kinds(
+    existing_definition_checksums: &HashMap<String, Checksum>,
+) -> HashSet<String> {
+    materialized_view_definition_checksums()
+        .iter()
+        .filter(|(kind, current_checksum)| {
+            existing_definition_checksums.get(kind.as_str()) != Some(current_checksum)
+        })
+        .map(|(kind, _)| kind.clone())
+        .collect()
+}
+
 ::inventory::collect!(DefinitionChecksumInventoryItem);
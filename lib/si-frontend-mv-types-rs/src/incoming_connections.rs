@@ -13,6 +13,7 @@ use si_id::{
     AttributeValueId,
     ComponentId,
     PropId,
+    VariableId,
     WorkspacePk,
 };
 
@@ -55,6 +56,16 @@ pub enum Connection {
         to_attribute_value_id: AttributeValueId,
         to_attribute_value_path: String,
     },
+    #[serde(rename_all = "camelCase")]
+    Variable {
+        variable_id: VariableId,
+        variable_name: String,
+        to_component_id: ComponentId,
+        to_prop_id: PropId,
+        to_prop_path: String,
+        to_attribute_value_id: AttributeValueId,
+        to_attribute_value_path: String,
+    },
 }
 
 #[derive(
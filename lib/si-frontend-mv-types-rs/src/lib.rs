@@ -61,12 +61,64 @@ pub use crate::{
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use serde::{
         Deserialize,
         Serialize,
     };
+    use si_events::workspace_snapshot::Checksum;
+
+    use crate::{
+        checksum::FrontendChecksum,
+        definition_checksum::{
+            DefinitionChecksumInventoryItem,
+            materialized_view_definition_checksums,
+            outdated_definition_kinds,
+        },
+        materialized_view::MaterializedViewInventoryItem,
+        reference::ReferenceKind,
+    };
+
+    #[test]
+    fn first_schema_version_keeps_the_shape_checksum() {
+        let definition_checksums = materialized_view_definition_checksums();
+        for mv_item in ::inventory::iter::<MaterializedViewInventoryItem>() {
+            if mv_item.schema_version() > 1 {
+                continue;
+            }
+            let kind = mv_item.kind().to_string();
+            let Some(shape_item) = ::inventory::iter::<DefinitionChecksumInventoryItem>()
+                .find(|item| item.ident() == kind)
+            else {
+                continue;
+            };
+
+            assert_eq!(
+                Some(&shape_item.definition_checksum()),
+                definition_checksums.get(&kind),
+                "{kind} should keep the checksum of its shape",
+            );
+        }
+    }
 
-    use crate::checksum::FrontendChecksum;
+    #[test]
+    fn outdated_definition_kinds_are_changed_and_new_kinds() {
+        let current_definition_checksums = materialized_view_definition_checksums();
+        assert!(outdated_definition_kinds(current_definition_checksums).is_empty());
+
+        let changed_kind = ReferenceKind::Component.to_string();
+        let new_kind = ReferenceKind::ComponentList.to_string();
+        let mut existing_definition_checksums = current_definition_checksums.clone();
+        existing_definition_checksums.insert(changed_kind.clone(), Checksum::nil());
+        existing_definition_checksums.remove(&new_kind);
+        existing_definition_checksums.insert("RemovedKind".to_string(), Checksum::nil());
+
+        assert_eq!(
+            HashSet::from([changed_kind, new_kind]),
+            outdated_definition_kinds(&existing_definition_checksums),
+        );
+    }
 
     #[test]
     fn enum_with_tuple_variant_bytestreams() {
@@ -15,6 +15,8 @@ pub trait MaterializedView {
     fn trigger_entity() -> EntityKind;
     fn definition_checksum() -> Checksum;
     fn build_priority() -> BuildPriority;
+    /// The version of the way this view is built, which is part of its definition checksum.
+    fn schema_version() -> u32;
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +24,7 @@ pub struct MaterializedViewInventoryItem {
     kind: ReferenceKind,
     trigger_entity: EntityKind,
     build_priority: BuildPriority,
+    schema_version: u32,
     definition_checksum: &'static ::std::sync::LazyLock<Checksum>,
 }
 
@@ -30,12 +33,14 @@ impl MaterializedViewInventoryItem {
         kind: ReferenceKind,
         trigger_entity: EntityKind,
         build_priority: BuildPriority,
+        schema_version: u32,
         definition_checksum: &'static ::std::sync::LazyLock<Checksum>,
     ) -> Self {
         MaterializedViewInventoryItem {
             kind,
             trigger_entity,
             build_priority,
+            schema_version,
             definition_checksum,
         }
     }
@@ -52,6 +57,10 @@ impl MaterializedViewInventoryItem {
         self.build_priority
     }
 
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
     pub fn definition_checksum(&self) -> Checksum {
         **self.definition_checksum
     }
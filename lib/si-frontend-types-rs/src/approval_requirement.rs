@@ -26,4 +26,13 @@ pub struct ApprovalRequirementDefinition {
     pub approver_groups: HashMap<String, Vec<UserPk>>,
     // What individuals can approve this?
     pub approver_individuals: Vec<UserPk>,
+    // What label must a component carry for this to apply to it, if any?
+    pub component_label: Option<ApprovalRequirementComponentLabel>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequirementComponentLabel {
+    pub key: String,
+    pub value: String,
 }
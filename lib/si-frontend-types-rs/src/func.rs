@@ -59,6 +59,8 @@ pub struct FuncSummary {
     pub types: Option<String>,
     pub backend_kind: FuncBackendKind,
     pub is_transformation: bool,
+    #[serde(default)]
+    pub memoization_enabled: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Serialize, PartialEq)]
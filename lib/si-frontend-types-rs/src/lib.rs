@@ -11,7 +11,10 @@ pub mod schema_variant;
 mod workspace;
 
 pub use crate::{
-    approval_requirement::ApprovalRequirementDefinition,
+    approval_requirement::{
+        ApprovalRequirementComponentLabel,
+        ApprovalRequirementDefinition,
+    },
     audit_log::AuditLog,
     change_set::{
         ChangeSet,
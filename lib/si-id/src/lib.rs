@@ -60,6 +60,7 @@ id!(PropertyEditorValueId);
 id!(SecretId);
 id!(StaticArgumentValueId);
 id!(ValidationOutputId);
+id!(VariableId);
 id!(VectorClockActorId);
 id!(VectorClockChangeSetId);
 id!(ViewId);
@@ -71,6 +72,7 @@ id_with_pg_types!(CachedModuleId);
 id_with_pg_types!(ChangeSetId);
 id_with_pg_types!(ChangeSetApprovalId);
 id_with_pg_types!(ComponentId);
+id_with_pg_types!(ComponentTemplateId);
 id_with_pg_types!(DebugFuncJobStateId);
 id_with_pg_types!(FuncId);
 id_with_pg_types!(FuncRunId);
@@ -79,6 +81,7 @@ id_with_pg_types!(ManagementPrototypeId);
 id_with_pg_types!(PolicyReportId);
 id_with_pg_types!(UserPk);
 id_with_pg_types!(WorkspaceIntegrationId);
+id_with_pg_types!(WorkspaceWebhookId);
 
 // Please keep these alphabetically sorted!
 id_with_pg_and_sea_orm_types!(ModuleIndexModuleId);
@@ -44,6 +44,7 @@ use crate::{
     activity_client::ActivityClient,
     db::{
         encrypted_secret::EncryptedSecretDb,
+        func_result_memo::FuncResultMemoDb,
         func_run::FuncRunLayerDb,
         func_run_log::FuncRunLogLayerDb,
     },
@@ -62,6 +63,7 @@ mod cache_updates;
 pub mod cas;
 pub mod change_batch;
 pub mod encrypted_secret;
+pub mod func_result_memo;
 pub mod func_run;
 pub mod func_run_log;
 pub mod rebase_batch;
@@ -101,6 +103,7 @@ pub struct LayerDb<
     cas: CasDb<CasValue>,
     change_batch: ChangeBatchDb,
     encrypted_secret: EncryptedSecretDb<EncryptedSecretValue>,
+    func_result_memo: FuncResultMemoDb,
     func_run: FuncRunLayerDb,
     func_run_log: FuncRunLogLayerDb,
     rebase_batch: RebaseBatchDb<RebaseBatchValue>,
@@ -199,6 +202,10 @@ where
                 encrypted_secret::CACHE_NAME,
                 KeyTransformStrategy::Passthrough,
             ),
+            (
+                func_result_memo::CACHE_NAME,
+                KeyTransformStrategy::Passthrough,
+            ),
             (func_run::CACHE_NAME, KeyTransformStrategy::ReverseKey), // ULID-based
             (func_run_log::CACHE_NAME, KeyTransformStrategy::ReverseKey), // ULID-based
             (
@@ -274,6 +281,7 @@ where
             cas_cache,
             change_batch_cache,
             encrypted_secret_cache,
+            func_result_memo_cache,
             func_run_cache,
             func_run_log_cache,
             rebase_batch_cache,
@@ -318,6 +326,18 @@ where
                 s3_layers.clone(),
                 config.persister_mode,
             ),
+            create_layer_cache(
+                func_result_memo::CACHE_NAME,
+                pg_pool.clone(),
+                cache_config.clone(),
+                compute_executor.clone(),
+                tracker.clone(),
+                token.clone(),
+                5,
+                5,
+                s3_layers.clone(),
+                config.persister_mode,
+            ),
             create_layer_cache(
                 func_run::CACHE_NAME,
                 pg_pool.clone(),
@@ -410,6 +430,7 @@ where
             cas_cache.clone(),
             change_batch_cache.clone(),
             encrypted_secret_cache.clone(),
+            func_result_memo_cache.clone(),
             func_run_cache.clone(),
             func_run_log_cache.clone(),
             rebase_batch_cache.clone(),
@@ -439,6 +460,8 @@ where
         let change_batch = ChangeBatchDb::new(change_batch_cache, persister_client.clone());
         let encrypted_secret =
             EncryptedSecretDb::new(encrypted_secret_cache, persister_client.clone());
+        let func_result_memo =
+            FuncResultMemoDb::new(func_result_memo_cache, persister_client.clone());
         let func_run = FuncRunLayerDb::new(func_run_cache, persister_client.clone());
         let func_run_log = FuncRunLogLayerDb::new(func_run_log_cache, persister_client.clone());
         let workspace_snapshot = WorkspaceSnapshotDb::new(snapshot_cache, persister_client.clone());
@@ -462,6 +485,7 @@ where
             cas,
             change_batch,
             encrypted_secret,
+            func_result_memo,
             func_run,
             func_run_log,
             workspace_snapshot,
@@ -502,6 +526,10 @@ where
         &self.encrypted_secret
     }
 
+    pub fn func_result_memo(&self) -> &FuncResultMemoDb {
+        &self.func_result_memo
+    }
+
     pub fn func_run(&self) -> &FuncRunLayerDb {
         &self.func_run
     }
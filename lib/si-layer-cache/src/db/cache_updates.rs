@@ -9,6 +9,7 @@ use si_events::{
     FuncRun,
     FuncRunLog,
     change_batch::ChangeBatch,
+    func_result_memo::FuncResultMemo,
 };
 use telemetry::prelude::*;
 use tokio::sync::mpsc::UnboundedReceiver;
@@ -47,6 +48,7 @@ pub struct CacheUpdatesTask<
     cas_cache: Arc<LayerCache<Arc<CasValue>>>,
     change_batch_cache: Arc<LayerCache<Arc<ChangeBatch>>>,
     encrypted_secret_cache: Arc<LayerCache<Arc<EncryptedSecretValue>>>,
+    func_result_memo_cache: Arc<LayerCache<Arc<FuncResultMemo>>>,
     func_run_cache: Arc<LayerCache<Arc<FuncRun>>>,
     func_run_log_cache: Arc<LayerCache<Arc<FuncRunLog>>>,
     rebase_batch_cache: Arc<LayerCache<Arc<RebaseBatchValue>>>,
@@ -95,6 +97,7 @@ where
         cas_cache: Arc<LayerCache<Arc<CasValue>>>,
         change_batch_cache: Arc<LayerCache<Arc<ChangeBatch>>>,
         encrypted_secret_cache: Arc<LayerCache<Arc<EncryptedSecretValue>>>,
+        func_result_memo_cache: Arc<LayerCache<Arc<FuncResultMemo>>>,
         func_run_cache: Arc<LayerCache<Arc<FuncRun>>>,
         func_run_log_cache: Arc<LayerCache<Arc<FuncRunLog>>>,
         rebase_batch_cache: Arc<LayerCache<Arc<RebaseBatchValue>>>,
@@ -115,6 +118,7 @@ where
             cas_cache,
             change_batch_cache,
             encrypted_secret_cache,
+            func_result_memo_cache,
             func_run_cache,
             func_run_log_cache,
             rebase_batch_cache,
@@ -148,6 +152,7 @@ where
                 self.cas_cache.clone(),
                 self.change_batch_cache.clone(),
                 self.encrypted_secret_cache.clone(),
+                self.func_result_memo_cache.clone(),
                 self.func_run_cache.clone(),
                 self.func_run_log_cache.clone(),
                 self.snapshot_cache.clone(),
@@ -175,6 +180,7 @@ where
     cas_cache: Arc<LayerCache<Arc<Q>>>,
     change_batch_cache: Arc<LayerCache<Arc<ChangeBatch>>>,
     encrypted_secret_cache: Arc<LayerCache<Arc<R>>>,
+    func_result_memo_cache: Arc<LayerCache<Arc<FuncResultMemo>>>,
     func_run_cache: Arc<LayerCache<Arc<FuncRun>>>,
     func_run_log_cache: Arc<LayerCache<Arc<FuncRunLog>>>,
     snapshot_cache: Arc<LayerCache<Arc<S>>>,
@@ -199,6 +205,7 @@ where
         cas_cache: Arc<LayerCache<Arc<Q>>>,
         change_batch_cache: Arc<LayerCache<Arc<ChangeBatch>>>,
         encrypted_secret_cache: Arc<LayerCache<Arc<R>>>,
+        func_result_memo_cache: Arc<LayerCache<Arc<FuncResultMemo>>>,
         func_run_cache: Arc<LayerCache<Arc<FuncRun>>>,
         func_run_log_cache: Arc<LayerCache<Arc<FuncRunLog>>>,
         snapshot_cache: Arc<LayerCache<Arc<S>>>,
@@ -211,6 +218,7 @@ where
             cas_cache,
             change_batch_cache,
             encrypted_secret_cache,
+            func_result_memo_cache,
             func_run_cache,
             func_run_log_cache,
             snapshot_cache,
@@ -250,6 +258,13 @@ where
                         .insert_from_cache_updates(event.key, serialized_value);
                 }
             }
+            crate::event::LayeredEventKind::FuncResultMemoWrite => {
+                // Memos are rewritten once they expire, so always replace any copy we hold.
+                let serialized_value =
+                    Arc::try_unwrap(event.payload.value).unwrap_or_else(|arc| (*arc).clone());
+                self.func_result_memo_cache
+                    .insert_or_update_from_cache_updates(event.key, serialized_value);
+            }
             crate::event::LayeredEventKind::FuncRunWrite => {
                 let serialized_value =
                     Arc::try_unwrap(event.payload.value).unwrap_or_else(|arc| (*arc).clone());
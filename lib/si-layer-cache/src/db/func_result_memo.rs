@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use chrono::{
+    TimeDelta,
+    Utc,
+};
+use si_events::{
+    Actor,
+    Tenancy,
+    func_result_memo::{
+        FuncResultMemo,
+        FuncResultMemoKey,
+    },
+};
+use telemetry::prelude::*;
+
+use super::serialize;
+use crate::{
+    error::LayerDbResult,
+    event::{
+        LayeredEvent,
+        LayeredEventKind,
+    },
+    layer_cache::LayerCache,
+    persister::{
+        PersisterClient,
+        PersisterStatusReader,
+    },
+};
+
+pub const DBNAME: &str = "func_result_memos";
+pub const CACHE_NAME: &str = DBNAME;
+pub const PARTITION_KEY: &str = CACHE_NAME;
+const SORT_KEY: &str = CACHE_NAME;
+
+/// How long a memoized result may be reused for after it was produced.
+pub const MAX_AGE: TimeDelta = TimeDelta::days(7);
+
+/// Stores the results of pure function executions so that they can be reused instead of
+/// dispatching the same function with the same arguments again. Results are only reused for
+/// [`MAX_AGE`] after they were produced.
+#[derive(Debug, Clone)]
+pub struct FuncResultMemoDb {
+    pub cache: Arc<LayerCache<Arc<FuncResultMemo>>>,
+    persister_client: PersisterClient,
+}
+
+impl FuncResultMemoDb {
+    pub fn new(
+        cache: Arc<LayerCache<Arc<FuncResultMemo>>>,
+        persister_client: PersisterClient,
+    ) -> Self {
+        Self {
+            cache,
+            persister_client,
+        }
+    }
+
+    #[instrument(
+        name = "func_result_memo.write",
+        level = "debug",
+        skip_all,
+        fields(
+            si.func_result_memo.key = %key,
+        )
+    )]
+    pub fn write(
+        &self,
+        key: FuncResultMemoKey,
+        value: Arc<FuncResultMemo>,
+        tenancy: Tenancy,
+        actor: Actor,
+    ) -> LayerDbResult<PersisterStatusReader> {
+        let (postcard_value, size_hint) = serialize::to_vec(&value)?;
+
+        let cache_key: Arc<str> = key.to_string().into();
+
+        self.cache.insert(cache_key.clone(), value, size_hint);
+
+        let event = LayeredEvent::new(
+            LayeredEventKind::FuncResultMemoWrite,
+            Arc::new(DBNAME.to_string()),
+            cache_key,
+            Arc::new(postcard_value),
+            Arc::new(SORT_KEY.to_string()),
+            None,
+            tenancy,
+            actor,
+        );
+        let reader = self.persister_client.write_event(event)?;
+
+        Ok(reader)
+    }
+
+    #[instrument(
+        name = "func_result_memo.read",
+        level = "debug",
+        skip_all,
+        fields(
+            si.func_result_memo.key = %key,
+        )
+    )]
+    pub async fn read(
+        &self,
+        key: &FuncResultMemoKey,
+    ) -> LayerDbResult<Option<Arc<FuncResultMemo>>> {
+        let cache_key: Arc<str> = key.to_string().into();
+        match self.cache.get(cache_key.clone()).await? {
+            Some(memo) if memo.is_expired(Utc::now(), MAX_AGE) => {
+                // Drop the expired memo so that the next execution's result can take its place.
+                self.cache.remove_from_memory(&cache_key);
+                self.cache.pg().delete(&cache_key).await?;
+                Ok(None)
+            }
+            memo => Ok(memo),
+        }
+    }
+}
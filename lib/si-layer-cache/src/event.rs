@@ -104,6 +104,7 @@ pub enum LayeredEventKind {
     ChangeBatchEvict,
     ChangeBatchWrite,
     EncryptedSecretInsertion,
+    FuncResultMemoWrite,
     FuncRunLogWrite,
     FuncRunWrite,
     Raw,
@@ -38,6 +38,7 @@ use crate::{
         cas,
         change_batch,
         encrypted_secret,
+        func_result_memo,
         func_run::{
             self,
             FuncRunLayerDb,
@@ -466,6 +467,7 @@ impl PersisterTask {
                     | LayeredEventKind::ChangeBatchEvict
                     | LayeredEventKind::ChangeBatchWrite
                     | LayeredEventKind::EncryptedSecretInsertion
+                    | LayeredEventKind::FuncResultMemoWrite
                     | LayeredEventKind::Raw
                     | LayeredEventKind::RebaseBatchEvict
                     | LayeredEventKind::RebaseBatchWrite
@@ -572,6 +574,7 @@ impl PersisterTask {
             cas::CACHE_NAME,
             change_batch::CACHE_NAME,
             encrypted_secret::CACHE_NAME,
+            func_result_memo::CACHE_NAME,
             func_run::CACHE_NAME,
             func_run_log::CACHE_NAME,
             rebase_batch::CACHE_NAME,
@@ -1104,6 +1107,7 @@ impl PersistEventTask {
             | LayeredEventKind::ChangeBatchEvict
             | LayeredEventKind::ChangeBatchWrite
             | LayeredEventKind::EncryptedSecretInsertion
+            | LayeredEventKind::FuncResultMemoWrite
             | LayeredEventKind::Raw
             | LayeredEventKind::RebaseBatchEvict
             | LayeredEventKind::RebaseBatchWrite
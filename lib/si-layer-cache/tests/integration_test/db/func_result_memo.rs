@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use si_events::{
+    Actor,
+    CasValue,
+    ChangeSetId,
+    Tenancy,
+    UserPk,
+    WorkspacePk,
+    func_result_memo::{
+        FuncResultMemo,
+        FuncResultMemoKey,
+    },
+};
+use si_layer_cache::{
+    LayerDb,
+    db::serialize,
+    persister::PersistStatus,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::integration_test::{
+    make_test_layerdb_config,
+    setup_compute_executor,
+    setup_nats_client,
+    setup_pg_db,
+};
+
+type TestLayerDb = LayerDb<CasValue, String, String, String, String, String, String>;
+
+#[tokio::test]
+async fn write_and_read() {
+    let token = CancellationToken::new();
+
+    let (ldb, _): (TestLayerDb, _) = LayerDb::from_services(
+        make_test_layerdb_config(),
+        setup_pg_db("func_result_memo_write_and_read").await,
+        setup_nats_client(Some("func_result_memo_write_and_read".to_string())).await,
+        setup_compute_executor(),
+        token,
+    )
+    .await
+    .expect("cannot create layerdb");
+    ldb.pg_migrate().await.expect("migrate layer db");
+
+    let key = FuncResultMemoKey::new(
+        WorkspacePk::new(),
+        "code",
+        &serde_json::json!({"name": "killswitch"}),
+    );
+    let memo = Arc::new(FuncResultMemo::new(
+        Some(serde_json::json!("engage")),
+        Some(serde_json::json!("engage")),
+    ));
+
+    let status = ldb
+        .func_result_memo()
+        .write(
+            key,
+            memo.clone(),
+            Tenancy::new(WorkspacePk::new(), ChangeSetId::new()),
+            Actor::User(UserPk::new()),
+        )
+        .expect("failed to write to layerdb");
+
+    match status.get_status().await.expect("failed to get status") {
+        PersistStatus::Finished => {}
+        PersistStatus::Error(e) => panic!("Write failed; {e}"),
+    }
+
+    let read = ldb
+        .func_result_memo()
+        .read(&key)
+        .await
+        .expect("failed to read from layerdb")
+        .expect("memo not found");
+    assert_eq!(memo, read);
+
+    let in_pg_postcard = ldb
+        .func_result_memo()
+        .cache
+        .pg()
+        .get(&key.to_string())
+        .await
+        .expect("error getting data from pg")
+        .expect("no memo in pg");
+    let in_pg: FuncResultMemo =
+        serialize::from_bytes(&in_pg_postcard[..]).expect("cannot deserialize data");
+    assert_eq!(memo.as_ref(), &in_pg);
+}
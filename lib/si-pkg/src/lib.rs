@@ -164,4 +164,148 @@ mod tests {
 
         let _ = dbg!(props.lock().await);
     }
+
+    #[tokio::test]
+    async fn pkg_bundle_round_trip() {
+        let module: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
+        let workspace: PkgSpec = serde_json::from_str(WORKSPACE_JSON).unwrap();
+        let module_pkg = SiPkg::load_from_spec(module).expect("failed to load module spec");
+        let workspace_pkg =
+            SiPkg::load_from_spec(workspace).expect("failed to load workspace spec");
+
+        let mut writer = SiPkgBundleWriter::new();
+        let module_hash = writer
+            .add_pkg("module", &module_pkg)
+            .expect("failed to add module pkg");
+        let workspace_hash = writer
+            .add_pkg("workspace", &workspace_pkg)
+            .expect("failed to add workspace pkg");
+        let bundle_data = writer
+            .finish(&vec!["module", "workspace"])
+            .expect("failed to write bundle");
+
+        let bundle =
+            SiPkgBundle::load_from_bytes(&bundle_data).expect("failed to load bundle from bytes");
+        let manifest: Vec<String> = bundle.manifest().expect("failed to read manifest");
+        assert_eq!(vec!["module", "workspace"], manifest);
+        assert_eq!(vec!["module", "workspace"], bundle.pkg_names());
+
+        let read_module = bundle.pkg("module").expect("failed to read module pkg");
+        assert_eq!(module_hash, read_module.hash().expect("get module hash"));
+        assert_eq!(
+            module_pkg.write_to_bytes().expect("serialize module pkg"),
+            read_module
+                .write_to_bytes()
+                .expect("serialize read module pkg")
+        );
+
+        let read_workspace = bundle
+            .pkg("workspace")
+            .expect("failed to read workspace pkg");
+        assert_eq!(
+            workspace_hash,
+            read_workspace.hash().expect("get workspace hash")
+        );
+        assert_eq!(
+            SiPkgKind::WorkspaceBackup,
+            read_workspace.metadata().expect("get metadata").kind()
+        );
+    }
+
+    #[tokio::test]
+    async fn component_sources_round_trip() {
+        let mut spec: PkgSpec = serde_json::from_str(WORKSPACE_JSON).unwrap();
+        let sources = serde_json::json!({
+            "/si/name": "bucket policy",
+            "/domain/Region": "us-east-1",
+            "/domain/Bucket": {
+                "$source": { "component": "bucket", "path": "/domain/BucketName" }
+            },
+            "/domain/Resource": {
+                "$source": {
+                    "template": "arn:aws:s3:::${bucket}/${prefix}",
+                    "bindings": {
+                        "bucket": { "component": "bucket", "path": "/domain/BucketName" },
+                        "prefix": { "component": "bucket", "path": "/domain/Prefix" }
+                    }
+                }
+            }
+        });
+        let labels = std::collections::BTreeMap::from([
+            ("team".to_string(), "payments".to_string()),
+            ("cost-center".to_string(), "1234".to_string()),
+        ]);
+        let component = ComponentSpec::builder()
+            .name("bucket policy")
+            .position(
+                serde_json::from_value::<PositionSpec>(serde_json::json!({
+                    "x": "0", "y": "0", "width": null, "height": null
+                }))
+                .expect("deserialize position"),
+            )
+            .variant(ComponentSpecVariant::BuiltinVariant {
+                schema_name: "policy".into(),
+                variant_name: "v0".into(),
+            })
+            .needs_destroy(false)
+            .deletion_user_pk(None)
+            .unique_id("policy-component")
+            .deleted(false)
+            .sources(sources.clone())
+            .labels(labels.clone())
+            .owner("payments")
+            .build()
+            .expect("build component spec");
+        spec.change_sets[0].components.push(component);
+
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        let pkg_data = pkg.write_to_bytes().expect("failed to serialize pkg");
+        let read_pkg = SiPkg::load_from_bytes(&pkg_data).expect("failed to load pkg from bytes");
+
+        let change_sets = read_pkg.change_sets().expect("get change sets");
+        let component = change_sets
+            .first()
+            .expect("has head change set")
+            .components()
+            .expect("get components")
+            .pop()
+            .expect("has a component");
+        let spec = ComponentSpec::try_from(component).expect("convert component to spec");
+
+        assert_eq!(Some(sources), spec.sources);
+        assert_eq!(labels, spec.labels);
+        assert_eq!(Some("payments"), spec.owner.as_deref());
+    }
+
+    #[tokio::test]
+    async fn component_template_round_trip() {
+        let mut spec: PkgSpec = serde_json::from_str(WORKSPACE_JSON).unwrap();
+        spec.kind = SiPkgKind::ComponentTemplate;
+        spec.change_sets.truncate(1);
+        spec.template_parameters = vec![
+            TemplateParameterSpec::builder()
+                .name("region")
+                .default(serde_json::json!("us-east-1"))
+                .build()
+                .expect("build parameter"),
+            TemplateParameterSpec::builder()
+                .name("bucket_name")
+                .description("Name of the bucket")
+                .build()
+                .expect("build parameter"),
+        ];
+        let template_parameters = spec.template_parameters.clone();
+
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        let pkg_data = pkg.write_to_bytes().expect("failed to serialize pkg");
+        let read_pkg = SiPkg::load_from_bytes(&pkg_data).expect("failed to load pkg from bytes");
+
+        let metadata = read_pkg.metadata().expect("get metadata");
+        assert_eq!(SiPkgKind::ComponentTemplate, metadata.kind());
+        assert_eq!(template_parameters, metadata.template_parameters());
+
+        let spec = read_pkg.to_spec().await.expect("convert pkg to spec");
+        assert_eq!(template_parameters, spec.template_parameters);
+        assert_eq!(1, spec.change_sets.len());
+    }
 }
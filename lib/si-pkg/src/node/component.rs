@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     io::{
         BufRead,
         Write,
@@ -15,7 +16,9 @@ use object_tree::{
     ReadBytes,
     WriteBytes,
     read_key_value_line,
+    read_key_value_line_opt,
     write_key_value_line,
+    write_key_value_line_opt,
 };
 
 use super::{
@@ -33,6 +36,9 @@ const KEY_NAME_STR: &str = "name";
 const KEY_VARIANT_STR: &str = "variant";
 const KEY_NEEDS_DESTROY_STR: &str = "needs_destroy";
 const KEY_DELETION_USER_PK_STR: &str = "deletion_user_pk";
+const KEY_SOURCES_STR: &str = "sources";
+const KEY_LABELS_STR: &str = "labels";
+const KEY_OWNER_STR: &str = "owner";
 
 #[derive(Clone, Debug)]
 pub struct ComponentNode {
@@ -42,6 +48,9 @@ pub struct ComponentNode {
     pub deletion_user_pk: Option<String>,
     pub unique_id: String,
     pub deleted: bool,
+    pub sources: Option<serde_json::Value>,
+    pub labels: BTreeMap<String, String>,
+    pub owner: Option<String>,
 }
 
 impl NameStr for ComponentNode {
@@ -69,6 +78,20 @@ impl WriteBytes for ComponentNode {
         write_key_value_line(writer, KEY_UNIQUE_ID_STR, &self.unique_id)?;
         write_key_value_line(writer, KEY_DELETED_STR, self.deleted)?;
 
+        let sources = match self.sources.as_ref() {
+            Some(sources) => Some(serde_json::to_string(sources).map_err(GraphError::parse)?),
+            None => None,
+        };
+        write_key_value_line_opt(writer, KEY_SOURCES_STR, sources)?;
+
+        let labels = if self.labels.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&self.labels).map_err(GraphError::parse)?)
+        };
+        write_key_value_line_opt(writer, KEY_LABELS_STR, labels)?;
+        write_key_value_line_opt(writer, KEY_OWNER_STR, self.owner.as_deref())?;
+
         Ok(())
     }
 }
@@ -95,6 +118,18 @@ impl ReadBytes for ComponentNode {
         let deleted = bool::from_str(&read_key_value_line(reader, KEY_DELETED_STR)?)
             .map_err(GraphError::parse)?;
 
+        let sources = match read_key_value_line_opt(reader, KEY_SOURCES_STR)? {
+            Some(sources_str) => {
+                Some(serde_json::from_str(&sources_str).map_err(GraphError::parse)?)
+            }
+            None => None,
+        };
+        let labels = match read_key_value_line_opt(reader, KEY_LABELS_STR)? {
+            Some(labels_str) => serde_json::from_str(&labels_str).map_err(GraphError::parse)?,
+            None => BTreeMap::new(),
+        };
+        let owner = read_key_value_line_opt(reader, KEY_OWNER_STR)?;
+
         Ok(Some(Self {
             name,
             variant,
@@ -102,6 +137,9 @@ impl ReadBytes for ComponentNode {
             deletion_user_pk,
             unique_id,
             deleted,
+            sources,
+            labels,
+            owner,
         }))
     }
 }
@@ -119,6 +157,9 @@ impl NodeChild for ComponentSpec {
                 deletion_user_pk: self.deletion_user_pk.to_owned(),
                 unique_id: self.unique_id.to_owned(),
                 deleted: self.deleted,
+                sources: self.sources.to_owned(),
+                labels: self.labels.to_owned(),
+                owner: self.owner.to_owned(),
             }),
             vec![
                 Box::new(ComponentChild::Attributes(self.attributes.to_owned()))
@@ -30,6 +30,7 @@ use super::{
 use crate::{
     PkgSpec,
     SiPkgKind,
+    TemplateParameterSpec,
 };
 
 const KEY_CREATED_AT_STR: &str = "created_at";
@@ -38,6 +39,7 @@ const KEY_DEFAULT_CHANGE_SET: &str = "default_change_set";
 const KEY_DESCRIPTION_STR: &str = "description";
 const KEY_KIND_STR: &str = "kind";
 const KEY_NAME_STR: &str = "name";
+const KEY_TEMPLATE_PARAMETERS_STR: &str = "template_parameters";
 const KEY_VERSION_STR: &str = "version";
 const KEY_WORKSPACE_PK_STR: &str = "workspace_pk";
 const KEY_WORKSPACE_NAME_STR: &str = "workspace_name";
@@ -54,6 +56,7 @@ pub struct PackageNode {
     pub default_change_set: Option<String>,
     pub workspace_pk: Option<String>,
     pub workspace_name: Option<String>,
+    pub template_parameters: Vec<TemplateParameterSpec>,
 }
 
 impl NameStr for PackageNode {
@@ -79,6 +82,11 @@ impl WriteBytes for PackageNode {
         if let Some(workspace_name) = &self.workspace_name {
             write_key_value_line(writer, KEY_WORKSPACE_NAME_STR, workspace_name.as_str())?;
         }
+        if !self.template_parameters.is_empty() {
+            let template_parameters =
+                serde_json::to_string(&self.template_parameters).map_err(GraphError::parse)?;
+            write_key_value_line(writer, KEY_TEMPLATE_PARAMETERS_STR, template_parameters)?;
+        }
         Ok(())
     }
 }
@@ -103,6 +111,13 @@ impl ReadBytes for PackageNode {
         let default_change_set = read_key_value_line_opt(reader, KEY_DEFAULT_CHANGE_SET)?;
         let workspace_pk = read_key_value_line_opt(reader, KEY_WORKSPACE_PK_STR)?;
         let workspace_name = read_key_value_line_opt(reader, KEY_WORKSPACE_NAME_STR)?;
+        let template_parameters =
+            match read_key_value_line_opt(reader, KEY_TEMPLATE_PARAMETERS_STR)? {
+                Some(template_parameters_str) => {
+                    serde_json::from_str(&template_parameters_str).map_err(GraphError::parse)?
+                }
+                None => vec![],
+            };
 
         Ok(Some(Self {
             kind,
@@ -114,6 +129,7 @@ impl ReadBytes for PackageNode {
             default_change_set,
             workspace_pk,
             workspace_name,
+            template_parameters,
         }))
     }
 }
@@ -134,20 +150,21 @@ impl NodeChild for PkgSpec {
                 default_change_set: self.default_change_set.to_owned(),
                 workspace_pk: self.workspace_pk.to_owned(),
                 workspace_name: self.workspace_name.to_owned(),
+                template_parameters: self.template_parameters.to_owned(),
             }),
             match self.kind {
+                SiPkgKind::ComponentTemplate | SiPkgKind::WorkspaceBackup => {
+                    vec![
+                        Box::new(PackageCategory::ChangeSets(self.change_sets.clone()))
+                            as Box<dyn NodeChild<NodeType = Self::NodeType>>,
+                    ]
+                }
                 SiPkgKind::Module => vec![
                     Box::new(PackageCategory::Schemas(self.schemas.clone()))
                         as Box<dyn NodeChild<NodeType = Self::NodeType>>,
                     Box::new(PackageCategory::Funcs(self.funcs.clone()))
                         as Box<dyn NodeChild<NodeType = Self::NodeType>>,
                 ],
-                SiPkgKind::WorkspaceBackup => {
-                    vec![
-                        Box::new(PackageCategory::ChangeSets(self.change_sets.clone()))
-                            as Box<dyn NodeChild<NodeType = Self::NodeType>>,
-                    ]
-                }
             },
         )
     }
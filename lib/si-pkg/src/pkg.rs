@@ -38,6 +38,7 @@ mod action_func;
 mod attr_func_input;
 mod attribute_value;
 mod auth_func;
+mod bundle;
 mod change_set;
 mod component;
 mod edge;
@@ -57,6 +58,7 @@ pub use action_func::*;
 pub use attr_func_input::*;
 pub use attribute_value::*;
 pub use auth_func::*;
+pub use bundle::*;
 pub use change_set::*;
 pub use component::*;
 pub use edge::*;
@@ -82,12 +84,15 @@ use crate::{
         PkgSpec,
         SchemaVariantSpecPropRoot,
         SpecError,
+        TemplateParameterSpec,
     },
 };
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum SiPkgError {
+    #[error("pkg bundle has no manifest")]
+    BundleManifestNotFound,
     #[error("component pkg node {0} missing position child")]
     ComponentMissingPosition(String),
     #[error("graph error: {0}")]
@@ -156,9 +161,13 @@ impl SiPkg {
     pub fn load_from_bytes(bytes: &[u8]) -> PkgResult<Self> {
         let tree: ObjectTree<PkgNode> = ObjectTree::<PkgNode>::read_from_tar(bytes)?;
 
-        Ok(Self {
+        Ok(Self::from_tree(tree))
+    }
+
+    fn from_tree(tree: ObjectTree<PkgNode>) -> Self {
+        Self {
             tree: Arc::new(tree),
-        })
+        }
     }
 
     pub fn load_from_spec<I>(spec: I) -> PkgResult<Self>
@@ -294,13 +303,25 @@ impl SiPkg {
             builder.schema(schema.to_spec().await?);
         }
 
-        if let SiPkgKind::WorkspaceBackup = metadata.kind() {
-            if let Some(default_change_set) = metadata.default_change_set() {
-                builder.default_change_set(default_change_set);
-            }
+        match metadata.kind() {
+            SiPkgKind::ComponentTemplate => {
+                for parameter in metadata.template_parameters() {
+                    builder.template_parameter(parameter.clone());
+                }
 
-            for change_set in self.change_sets()? {
-                builder.change_set(change_set.to_spec().await?);
+                for change_set in self.change_sets()? {
+                    builder.change_set(change_set.to_spec().await?);
+                }
+            }
+            SiPkgKind::Module => {}
+            SiPkgKind::WorkspaceBackup => {
+                if let Some(default_change_set) = metadata.default_change_set() {
+                    builder.default_change_set(default_change_set);
+                }
+
+                for change_set in self.change_sets()? {
+                    builder.change_set(change_set.to_spec().await?);
+                }
             }
         }
 
@@ -402,6 +423,7 @@ impl fmt::Debug for Source<'_> {
 #[serde(rename_all = "camelCase")]
 #[strum(serialize_all = "camelCase")]
 pub enum SiPkgKind {
+    ComponentTemplate,
     Module,
     WorkspaceBackup,
 }
@@ -417,6 +439,7 @@ pub struct SiPkgMetadata {
     default_change_set: Option<String>,
     workspace_pk: Option<String>,
     workspace_name: Option<String>,
+    template_parameters: Vec<TemplateParameterSpec>,
     hash: Hash,
 }
 
@@ -443,6 +466,7 @@ impl SiPkgMetadata {
             default_change_set: metadata_node.default_change_set,
             workspace_pk: metadata_node.workspace_pk,
             workspace_name: metadata_node.workspace_name,
+            template_parameters: metadata_node.template_parameters,
             hash: metadata_hashed_node.hash(),
         })
     }
@@ -483,6 +507,10 @@ impl SiPkgMetadata {
         self.workspace_name.as_deref()
     }
 
+    pub fn template_parameters(&self) -> &[TemplateParameterSpec] {
+        &self.template_parameters
+    }
+
     pub fn hash(&self) -> Hash {
         self.hash
     }
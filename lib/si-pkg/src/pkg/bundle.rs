@@ -0,0 +1,79 @@
+use object_tree::{
+    Hash,
+    ObjectTree,
+    TarBundleReader,
+    TarBundleWriter,
+};
+use serde::{
+    Serialize,
+    de::DeserializeOwned,
+};
+
+use super::{
+    PkgResult,
+    SiPkg,
+    SiPkgError,
+};
+use crate::node::PkgNode;
+
+/// The path of the manifest describing the packages of a bundle.
+pub const PKG_BUNDLE_MANIFEST_PATH: &str = "manifest.json";
+
+/// Writes many [`SiPkg`]s into a single tar, alongside a JSON manifest describing them.
+///
+/// Each package is reachable by the name it was added under. Objects shared between packages are
+/// only stored once.
+#[derive(Default)]
+pub struct SiPkgBundleWriter {
+    writer: TarBundleWriter,
+}
+
+impl SiPkgBundleWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the package under the given name and returns its hash.
+    pub fn add_pkg(&mut self, name: &str, pkg: &SiPkg) -> PkgResult<Hash> {
+        self.writer.add_tree(name, &pkg.tree)?;
+        pkg.hash()
+    }
+
+    pub fn finish(mut self, manifest: &impl Serialize) -> PkgResult<Vec<u8>> {
+        self.writer.add_file(
+            PKG_BUNDLE_MANIFEST_PATH,
+            &serde_json::to_vec_pretty(manifest)?,
+        )?;
+        Ok(self.writer.finish()?)
+    }
+}
+
+/// A bundle of [`SiPkg`]s written by a [`SiPkgBundleWriter`].
+pub struct SiPkgBundle {
+    reader: TarBundleReader,
+}
+
+impl SiPkgBundle {
+    pub fn load_from_bytes(bytes: &[u8]) -> PkgResult<Self> {
+        Ok(Self {
+            reader: TarBundleReader::new(bytes)?,
+        })
+    }
+
+    pub fn manifest<M: DeserializeOwned>(&self) -> PkgResult<M> {
+        let bytes = self
+            .reader
+            .file(PKG_BUNDLE_MANIFEST_PATH)
+            .ok_or(SiPkgError::BundleManifestNotFound)?;
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    pub fn pkg_names(&self) -> Vec<String> {
+        self.reader.ref_names()
+    }
+
+    pub fn pkg(&self, name: &str) -> PkgResult<SiPkg> {
+        let tree: ObjectTree<PkgNode> = self.reader.read_tree(name)?;
+        Ok(SiPkg::from_tree(tree))
+    }
+}
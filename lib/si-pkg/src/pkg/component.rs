@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use object_tree::{
     Hash,
     HashedNode,
@@ -30,6 +32,9 @@ pub struct SiPkgComponent<'a> {
     deletion_user_pk: Option<String>,
     unique_id: String,
     deleted: bool,
+    sources: Option<serde_json::Value>,
+    labels: BTreeMap<String, String>,
+    owner: Option<String>,
 
     hash: Hash,
     source: Source<'a>,
@@ -89,6 +94,9 @@ impl<'a> SiPkgComponent<'a> {
             deletion_user_pk: node.deletion_user_pk,
             deleted: node.deleted,
             unique_id: node.unique_id,
+            sources: node.sources,
+            labels: node.labels,
+            owner: node.owner,
 
             hash: hashed_node.hash(),
             source: Source::new(graph, node_idx),
@@ -139,6 +147,18 @@ impl<'a> SiPkgComponent<'a> {
         self.deleted
     }
 
+    pub fn sources(&self) -> Option<&serde_json::Value> {
+        self.sources.as_ref()
+    }
+
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
     pub fn hash(&self) -> Hash {
         self.hash
     }
@@ -167,6 +187,13 @@ impl<'a> TryFrom<SiPkgComponent<'a>> for ComponentSpec {
             .deletion_user_pk(value.deletion_user_pk().map(ToString::to_string))
             .unique_id(value.unique_id())
             .deleted(value.deleted());
+        if let Some(sources) = value.sources() {
+            builder.sources(sources.to_owned());
+        }
+        builder.labels(value.labels().to_owned());
+        if let Some(owner) = value.owner() {
+            builder.owner(owner);
+        }
 
         for attribute in value.attributes()? {
             builder.attribute(AttributeValueSpec::try_from(attribute)?);
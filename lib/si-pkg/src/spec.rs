@@ -31,6 +31,7 @@ mod root_prop_func;
 mod schema;
 mod si_prop_func;
 mod socket;
+mod template_parameter;
 mod variant;
 
 #[cfg(test)]
@@ -59,6 +60,7 @@ pub use root_prop_func::*;
 pub use schema::*;
 pub use si_prop_func::*;
 pub use socket::*;
+pub use template_parameter::*;
 pub use variant::*;
 
 use super::SiPkgKind;
@@ -88,6 +90,11 @@ pub struct PkgSpec {
     #[builder(setter(into, strip_option), default)]
     pub workspace_name: Option<String>,
 
+    /// Parameters declared by a [`SiPkgKind::ComponentTemplate`] package.
+    #[builder(setter(each(name = "template_parameter", into)), default)]
+    #[serde(default)]
+    pub template_parameters: Vec<TemplateParameterSpec>,
+
     #[builder(setter(each(name = "schema", into)), default)]
     #[serde(default)]
     pub schemas: Vec<SchemaSpec>,
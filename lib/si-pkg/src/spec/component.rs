@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use derive_builder::Builder;
 use serde::{
     Deserialize,
@@ -55,6 +57,22 @@ pub struct ComponentSpec {
 
     #[builder(setter(each(name = "output_socket"), into), default)]
     pub output_sockets: Vec<AttributeValueSpec>,
+
+    /// The component's attributes in the same form as the attributes API (a map of attribute
+    /// paths to values or `$source`s), with any referenced components identified by name.
+    #[builder(setter(into, strip_option), default)]
+    #[serde(default)]
+    pub sources: Option<serde_json::Value>,
+
+    /// Key/value labels attached to the component.
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+
+    /// The team or user that owns the component.
+    #[builder(setter(into, strip_option), default)]
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 impl ComponentSpec {
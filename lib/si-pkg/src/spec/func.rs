@@ -102,8 +102,10 @@ pub enum FuncSpecBackendKind {
     Object,
     ResourcePayloadToValue,
     String,
+    StringTemplate,
     Unset,
     Validation,
+    WasmAttribute,
 }
 
 #[remain::sorted]
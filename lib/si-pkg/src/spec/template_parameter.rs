@@ -0,0 +1,34 @@
+use derive_builder::Builder;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::SpecError;
+
+/// A parameter declared by a component template, to be given a value when the template is
+/// instantiated.
+#[derive(Builder, Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct TemplateParameterSpec {
+    #[builder(setter(into))]
+    pub name: String,
+
+    #[builder(setter(into, strip_option), default)]
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The value used when an instantiation does not provide one. Parameters without a default
+    /// are required.
+    #[builder(setter(into, strip_option), default)]
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
+}
+
+impl TemplateParameterSpec {
+    #[must_use]
+    pub fn builder() -> TemplateParameterSpecBuilder {
+        TemplateParameterSpecBuilder::default()
+    }
+}
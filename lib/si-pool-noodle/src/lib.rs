@@ -29,6 +29,8 @@ pub use cyclone_core::{
     CycloneRequestable,
     DebugRequest,
     DebugResultSuccess,
+    ExecutionResourceLimits,
+    ExecutionResourceUsage,
     FunctionResult,
     FunctionResultFailure,
     FunctionResultFailureError,
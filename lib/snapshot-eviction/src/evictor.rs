@@ -157,6 +157,7 @@ impl SnapshotEvictor {
                 "SELECT s.snapshot_id, s.last_used_at
                  FROM snapshot_last_used s
                  LEFT JOIN change_set_pointers cs ON cs.workspace_snapshot_address = s.snapshot_id
+                   OR cs.base_snapshot_address = s.snapshot_id
                  WHERE s.last_used_at < (NOW() - $1 * INTERVAL '1 second')
                    AND cs.id IS NULL
                  ORDER BY s.last_used_at ASC
@@ -8,9 +8,12 @@ pub use cyclone_core::{
     ComponentViewWithGeometry,
     DebugRequest,
     DebugResultSuccess,
+    ExecutionResourceLimits,
+    ExecutionResourceUsage,
     FunctionResult,
     FunctionResultFailure,
     FunctionResultFailureErrorKind,
+    FunctionRuntime,
     KillExecutionRequest,
     ManagementFuncStatus,
     ManagementRequest,
@@ -53,6 +56,7 @@ use tokio_util::sync::CancellationToken;
 use veritech_core::{
     FINAL_MESSAGE_HEADER_KEY,
     GetNatsSubjectFor,
+    RESOURCE_USAGE_HEADER_KEY,
     reply_mailbox_for_output,
     reply_mailbox_for_result,
 };
@@ -84,6 +88,15 @@ pub enum ClientError {
 
 pub type ClientResult<T> = Result<T, ClientError>;
 
+/// The result of executing a function, along with the resources consumed by the execution.
+#[derive(Debug)]
+pub struct ExecutionResponse<T> {
+    pub result: FunctionResult<T>,
+    /// Only present if veritech reported the resources used (for example, executions that were
+    /// killed or timed out are not measured).
+    pub resource_usage: Option<ExecutionResourceUsage>,
+}
+
 /// This _private_ enum helps dictate what NATS technology should be used in communicating with veritech.
 enum RequestMode {
     /// Publish messages using core NATS to communicate with veritech.
@@ -123,7 +136,7 @@ impl Client {
         request: &ActionRunRequest,
         workspace_id: &str,
         change_set_id: &str,
-    ) -> ClientResult<FunctionResult<ActionRunResultSuccess>> {
+    ) -> ClientResult<ExecutionResponse<ActionRunResultSuccess>> {
         self.execute_jetstream_request(output_tx, request, workspace_id, change_set_id)
             .await
     }
@@ -143,7 +156,7 @@ impl Client {
         request: &ResolverFunctionRequest,
         workspace_id: &str,
         change_set_id: &str,
-    ) -> ClientResult<FunctionResult<ResolverFunctionResultSuccess>> {
+    ) -> ClientResult<ExecutionResponse<ResolverFunctionResultSuccess>> {
         self.execute_jetstream_request(output_tx, request, workspace_id, change_set_id)
             .await
     }
@@ -163,7 +176,7 @@ impl Client {
         request: &SchemaVariantDefinitionRequest,
         workspace_id: &str,
         change_set_id: &str,
-    ) -> ClientResult<FunctionResult<SchemaVariantDefinitionResultSuccess>> {
+    ) -> ClientResult<ExecutionResponse<SchemaVariantDefinitionResultSuccess>> {
         self.execute_jetstream_request(output_tx, request, workspace_id, change_set_id)
             .await
     }
@@ -183,7 +196,7 @@ impl Client {
         request: &ValidationRequest,
         workspace_id: &str,
         change_set_id: &str,
-    ) -> ClientResult<FunctionResult<ValidationResultSuccess>> {
+    ) -> ClientResult<ExecutionResponse<ValidationResultSuccess>> {
         self.execute_jetstream_request(output_tx, request, workspace_id, change_set_id)
             .await
     }
@@ -203,7 +216,7 @@ impl Client {
         request: &ManagementRequest,
         workspace_id: &str,
         change_set_id: &str,
-    ) -> ClientResult<FunctionResult<ManagementResultSuccess>> {
+    ) -> ClientResult<ExecutionResponse<ManagementResultSuccess>> {
         self.execute_jetstream_request(output_tx, request, workspace_id, change_set_id)
             .await
     }
@@ -223,7 +236,7 @@ impl Client {
         request: &DebugRequest,
         workspace_id: &str,
         change_set_id: &str,
-    ) -> ClientResult<FunctionResult<DebugResultSuccess>> {
+    ) -> ClientResult<ExecutionResponse<DebugResultSuccess>> {
         self.execute_jetstream_request(output_tx, request, workspace_id, change_set_id)
             .await
     }
@@ -245,6 +258,7 @@ impl Client {
             RequestMode::Core,
         )
         .await
+        .map(|response| response.result)
     }
 
     async fn execute_jetstream_request<R>(
@@ -253,7 +267,7 @@ impl Client {
         request: &R,
         workspace_id: &str,
         change_set_id: &str,
-    ) -> ClientResult<FunctionResult<R::Response>>
+    ) -> ClientResult<ExecutionResponse<R::Response>>
     where
         R: Serialize + CycloneRequestable + GetNatsSubjectFor,
         R::Response: DeserializeOwned,
@@ -277,7 +291,7 @@ impl Client {
         output_tx: Option<mpsc::Sender<OutputStream>>,
         request: &R,
         request_mode: RequestMode,
-    ) -> ClientResult<FunctionResult<R::Response>> {
+    ) -> ClientResult<ExecutionResponse<R::Response>> {
         // Subscribe to responses and send the request. These unsubscribe when dropped.
         let (root_subscriber, mut result_subscriber, output_subscriber) =
             self.send_request(subject, request, request_mode).await?;
@@ -297,7 +311,15 @@ impl Client {
             result = result_subscriber.try_next() => {
                 let result = result?.ok_or(ClientError::NoResult)?;
                 Span::current().follows_from(result.process_span);
-                Ok(result.payload)
+                let resource_usage = result
+                    .headers
+                    .as_ref()
+                    .and_then(|headers| headers.get(RESOURCE_USAGE_HEADER_KEY))
+                    .and_then(|value| serde_json::from_str(value.as_str()).ok());
+                Ok(ExecutionResponse {
+                    result: result.payload,
+                    resource_usage,
+                })
             }
 
             // Because the channel never responds on success, we have to await this simultaneously
@@ -16,6 +16,7 @@ use cyclone_core::{
     DebugRequest,
     FunctionResult,
     FunctionResultFailureErrorKind,
+    FunctionRuntime,
     ManagementRequest,
     ResolverFunctionComponent,
     ResolverFunctionRequest,
@@ -151,6 +152,7 @@ async fn executes_simple_management_function() {
         },
         components: HashMap::new(),
         variant_socket_map: HashMap::new(),
+        webhook_payload: None,
         code_base64: base64_encode(
             "function numberOfInputs({ thisComponent }) {
                 const number = Object.keys(thisComponent.properties)?.length;
@@ -158,12 +160,14 @@ async fn executes_simple_management_function() {
              }",
         ),
         before: vec![],
+        limits: Default::default(),
     };
 
     let result = client
         .execute_management(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
         .await
-        .expect("failed to execute resolver function");
+        .expect("failed to execute resolver function")
+        .result;
 
     match result {
         FunctionResult::Success(success) => {
@@ -199,12 +203,14 @@ async fn executes_simple_action_run() {
             "function numberOfInputs(input) { return { status: 'ok', payload: Object.keys(input)?.length ?? 0 } }",
         ),
         before: vec![],
+        limits: Default::default(),
     };
 
     let result = client
         .execute_action_run(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
         .await
-        .expect("failed to execute resolver function");
+        .expect("failed to execute resolver function")
+        .result;
 
     match result {
         FunctionResult::Success(success) => {
@@ -250,12 +256,15 @@ async fn executes_simple_resolver_function() {
             "function numberOfInputs(input) { return Object.keys(input)?.length ?? 0; }",
         ),
         before: vec![],
+        runtime: FunctionRuntime::LangJs,
+        limits: Default::default(),
     };
 
     let result = client
         .execute_resolver_function(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
         .await
-        .expect("failed to execute resolver function");
+        .expect("failed to execute resolver function")
+        .result;
 
     match result {
         FunctionResult::Success(success) => {
@@ -320,12 +329,15 @@ async fn type_checks_resolve_function() {
             response_type,
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
             before: vec![],
+            runtime: FunctionRuntime::LangJs,
+            limits: Default::default(),
         };
 
         let result = client
             .execute_resolver_function(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
             .await
-            .expect("failed to execute resolver function");
+            .expect("failed to execute resolver function")
+            .result;
 
         match result {
             FunctionResult::Success(success) => {
@@ -385,12 +397,15 @@ async fn type_checks_resolve_function() {
             response_type: response_type.clone(),
             code_base64: base64_encode("function returnInputValue(input) { return input.value; }"),
             before: vec![],
+            runtime: FunctionRuntime::LangJs,
+            limits: Default::default(),
         };
 
         let result = client
             .execute_resolver_function(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
             .await
-            .expect("failed to execute resolver function");
+            .expect("failed to execute resolver function")
+            .result;
 
         match result {
             FunctionResult::Success(success) => {
@@ -435,7 +450,8 @@ async fn executes_simple_validation() {
     let result = client
         .execute_validation(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
         .await
-        .expect("failed to execute validation");
+        .expect("failed to execute validation")
+        .result;
 
     match result {
         FunctionResult::Success(success) => {
@@ -480,7 +496,8 @@ async fn executes_simple_schema_variant_definition() {
     let result = client
         .execute_schema_variant_definition(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
         .await
-        .expect("failed to execute schema variant definition");
+        .expect("failed to execute schema variant definition")
+        .result;
 
     match result {
         FunctionResult::Success(success) => {
@@ -548,7 +565,8 @@ async fn executes_simple_debug_function() {
     let result = client
         .execute_debug(tx, &request, WORKSPACE_ID, CHANGE_SET_ID)
         .await
-        .expect("failed to execute debug function");
+        .expect("failed to execute debug function")
+        .result;
 
     match result {
         FunctionResult::Success(success) => {
@@ -614,6 +632,8 @@ async fn backpressure_queues_messages_in_nats() {
                     response_type: ResolverFunctionResponseType::Integer,
                     code_base64: code,
                     before: vec![],
+                    runtime: FunctionRuntime::LangJs,
+                    limits: Default::default(),
                 };
 
                 client
@@ -644,7 +664,7 @@ async fn backpressure_queues_messages_in_nats() {
             r.as_ref()
                 .ok()
                 .and_then(|inner| inner.as_ref().ok())
-                .is_some_and(|result| matches!(result, FunctionResult::Success(_)))
+                .is_some_and(|response| matches!(response.result, FunctionResult::Success(_)))
         })
         .count();
 
@@ -51,6 +51,8 @@ const INCOMING_SUBJECT: &str = "veritech.requests.*.*.*";
 const SUBJECT_PREFIX: &str = "veritech.requests";
 
 pub const FINAL_MESSAGE_HEADER_KEY: &str = "X-Final-Message";
+/// Header carrying the JSON encoded resources consumed by an execution, set on result messages.
+pub const RESOURCE_USAGE_HEADER_KEY: &str = "X-Resource-Usage";
 
 // NOTE(nick,fletcher): we can probably take this type formalization a step further, but this is
 // essentially the "FuncRunId" from the "dal".
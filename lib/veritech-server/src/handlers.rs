@@ -3,7 +3,10 @@ use std::{
     result,
     str::Utf8Error,
     sync::Arc,
-    time::Duration,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use chrono::Utc;
@@ -38,6 +41,7 @@ use si_pool_noodle::{
     CycloneRequestable,
     DebugResultSuccess,
     ExecutionError,
+    ExecutionResourceUsage,
     FunctionResultFailure,
     FunctionResultFailureError,
     ManagementResultSuccess,
@@ -230,6 +234,7 @@ where
     HandlerError: From<ExecutionError<<Request as CycloneRequestable>::Response>>,
 {
     let span = current_span_for_instrument_at!("info");
+    let queued_at = Instant::now();
     let mut client = match state.cyclone_pool.get().await {
         Ok(client) => client,
         Err(err) => {
@@ -239,6 +244,7 @@ where
             return Err(span.record_err(HandlerError::CyclonePool(Box::new(err))));
         }
     };
+    let queued_ms = u64::try_from(queued_at.elapsed().as_millis()).unwrap_or(u64::MAX);
 
     request.inc_run_metric();
 
@@ -298,12 +304,18 @@ where
             span.record_err(err)
         })?;
 
+        let resource_usage = progress
+            .resource_usage()
+            .map(|usage| ExecutionResourceUsage {
+                queued_ms: Some(queued_ms),
+                ..usage
+            });
         let function_result = progress.finish().await.map_err(|err| {
             request.dec_run_metric();
             span.record_err(err)
         })?;
 
-        HandlerResult::Ok(function_result)
+        HandlerResult::Ok((function_result, resource_usage))
     };
 
     // we do not want to return errors at this point as it will Nack the message and end up auto-retrying
@@ -328,8 +340,11 @@ where
 
     match result {
         // Got an Ok - let anyone subscribing to a reply know
-        Ok(function_result) => {
-            if let Err(err) = publisher.publish_result(&function_result).await {
+        Ok((function_result, resource_usage)) => {
+            if let Err(err) = publisher
+                .publish_result(&function_result, resource_usage)
+                .await
+            {
                 error!(si.error.message = ?err, "failed to publish errored result");
             }
 
@@ -373,7 +388,7 @@ where
                 }
             };
             request.dec_run_metric();
-            if let Err(err) = publisher.publish_result(&func_result_error).await {
+            if let Err(err) = publisher.publish_result(&func_result_error, None).await {
                 error!(si.error.message = ?err, "failed to publish errored result");
             }
         }
@@ -64,7 +64,7 @@ async fn kill_execution_request_task(
         )),
     };
 
-    if let Err(err) = publisher.publish_result(&result).await {
+    if let Err(err) = publisher.publish_result(&result, None).await {
         error!(?err, "failed to publish result");
     }
 }
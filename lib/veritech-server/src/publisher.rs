@@ -4,6 +4,7 @@ use si_data_nats::{
     Subject,
 };
 use si_pool_noodle::{
+    ExecutionResourceUsage,
     FunctionResult,
     OutputStream,
 };
@@ -12,6 +13,7 @@ use telemetry_nats::propagation;
 use thiserror::Error;
 use veritech_core::{
     FINAL_MESSAGE_HEADER_KEY,
+    RESOURCE_USAGE_HEADER_KEY,
     reply_mailbox_for_output,
     reply_mailbox_for_result,
 };
@@ -67,18 +69,25 @@ impl<'a> Publisher<'a> {
             .map_err(|err| PublisherError::NatsPublish(err, self.reply_mailbox_output.to_string()))
     }
 
-    pub async fn publish_result<R>(&self, result: &FunctionResult<R>) -> Result<()>
+    pub async fn publish_result<R>(
+        &self,
+        result: &FunctionResult<R>,
+        resource_usage: Option<ExecutionResourceUsage>,
+    ) -> Result<()>
     where
         R: Serialize,
     {
         let nats_msg = serde_json::to_string(result).map_err(PublisherError::JSONSerialize)?;
 
+        let mut headers = propagation::empty_injected_headers();
+        if let Some(resource_usage) = resource_usage {
+            let resource_usage =
+                serde_json::to_string(&resource_usage).map_err(PublisherError::JSONSerialize)?;
+            headers.insert(RESOURCE_USAGE_HEADER_KEY, resource_usage.as_str());
+        }
+
         self.nats
-            .publish_with_headers(
-                self.reply_mailbox_result.clone(),
-                propagation::empty_injected_headers(),
-                nats_msg.into(),
-            )
+            .publish_with_headers(self.reply_mailbox_result.clone(), headers, nats_msg.into())
             .await
             .map_err(|err| PublisherError::NatsPublish(err, self.reply_mailbox_result.to_string()))
     }